@@ -14,6 +14,10 @@ pub enum Def2 {
         identifier_id: IdentId,
         expr_id: NodeId<Expr2>,
     },
+    // ExpectDef example: `expect 1 + 1 == 2`. expr -> `1 + 1 == 2`
+    Expect {
+        expr_id: NodeId<Expr2>,
+    },
     Blank,
     CommentsBefore {
         comments: String,
@@ -43,6 +47,13 @@ pub fn def2_to_string(node_id: DefId, pool: &Pool) -> String {
                 expr2_to_string(*expr_id, pool)
             );
         }
+        Def2::Expect { expr_id } => {
+            let _ = write!(
+                full_string,
+                "Def2::Expect(expr_id: >>{:?})",
+                expr2_to_string(*expr_id, pool)
+            );
+        }
         Def2::Blank => {
             full_string.push_str("Def2::Blank");
         }
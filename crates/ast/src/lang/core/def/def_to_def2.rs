@@ -56,6 +56,13 @@ pub fn toplevel_defs_to_defs2<'a>(
                 }
             }
 
+            Err(roc_parse::ast::ValueDef::Expect { condition, .. }) => {
+                let expr2 = loc_expr_to_expr2(arena, *condition, env, scope, region).0;
+                let expr_id = env.pool.add(expr2);
+
+                Def2::Expect { expr_id }
+            }
+
             other => {
                 unimplemented!(
                     "I don't know how to make an expr2 from this def yet: {:?}",
@@ -1,5 +1,8 @@
 use bumpalo::Bump;
-use roc_load::{ExecutionMode, LoadConfig, LoadedModule, Threading};
+use roc_load::{
+    CancelToken, ExecutionMode, HotModule, LoadConfig, LoadedModule, ProgressReporter, SolveFuel,
+    Threading,
+};
 use roc_packaging::cache::RocCacheDir;
 use roc_reporting::report::DEFAULT_PALETTE;
 use roc_target::TargetInfo;
@@ -16,6 +19,10 @@ pub fn load_module(
         palette: DEFAULT_PALETTE,
         threading,
         exec_mode: ExecutionMode::Check,
+        cancel: CancelToken::default(),
+        progress: ProgressReporter::default(),
+        hot_module: HotModule::default(),
+        solve_fuel: SolveFuel::default(),
     };
 
     let arena = Bump::new();
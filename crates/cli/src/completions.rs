@@ -0,0 +1,19 @@
+//! Shell completion scripts, printed by `roc completions <SHELL>`.
+//!
+//! clap_complete generates the scripts from the [Command] that [crate::build_app] returns, so
+//! every subcommand, flag and possible value the CLI accepts can be completed, and new flags show
+//! up without changes here. The value hints of the positional arguments decide whether they
+//! complete files or directories.
+use clap::Command;
+pub use clap_complete::Shell;
+
+/// The completion script for `app`, to be sourced by `shell`
+pub fn generate_completions(app: &Command, shell: Shell) -> String {
+    let mut app = app.clone();
+    let bin_name = app.get_name().to_string();
+    let mut script = Vec::new();
+
+    clap_complete::generate(shell, &mut app, bin_name, &mut script);
+
+    String::from_utf8(script).expect("clap_complete generated a script that is not UTF-8")
+}
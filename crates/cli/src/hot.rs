@@ -0,0 +1,110 @@
+//! `roc dev --hot`: keeps the app built as a dynamic library, and rebuilds it whenever one of
+//! the .roc files next to it changes. A long-running host, like a web server or a game, can
+//! then load the new version of the app without restarting.
+
+use crate::{
+    CMD_BUILD, FLAG_DEBUG, FLAG_DEV, FLAG_LIB, FLAG_LINKER, FLAG_MAX_THREADS, FLAG_OPTIMIZE,
+    FLAG_OPT_SIZE, FLAG_PREBUILT, ROC_FILE,
+};
+use clap::ArgMatches;
+use std::ffi::OsString;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime};
+use std::{env, fs, thread};
+
+/// How often the .roc files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+
+/// Rebuilds the app until the process is stopped. Every build runs in its own `roc build --lib`
+/// process, so the memory of a build is freed when it is done, and a build that fails only
+/// reports its problems and waits for the next change.
+pub fn watch_and_rebuild(matches: &ArgMatches) -> io::Result<i32> {
+    let roc_file = Path::new(matches.value_of_os(ROC_FILE).unwrap());
+    let watched_dir = match roc_file.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+
+    let roc_exe = env::current_exe()?;
+    let build_args = build_args(matches);
+
+    let mut last_snapshot = snapshot(watched_dir)?;
+
+    loop {
+        Command::new(&roc_exe).args(&build_args).status()?;
+
+        println!(
+            "\nWatching the .roc files in {} for changes…",
+            watched_dir.display()
+        );
+
+        loop {
+            thread::sleep(POLL_INTERVAL);
+
+            let new_snapshot = snapshot(watched_dir)?;
+
+            if new_snapshot != last_snapshot {
+                last_snapshot = new_snapshot;
+                break;
+            }
+        }
+    }
+}
+
+/// The arguments for `roc build` that build the same app as the `roc dev` arguments.
+fn build_args(matches: &ArgMatches) -> Vec<OsString> {
+    let mut args: Vec<OsString> = vec![CMD_BUILD.into(), format!("--{FLAG_LIB}").into()];
+
+    for flag in [FLAG_OPTIMIZE, FLAG_OPT_SIZE, FLAG_DEV, FLAG_DEBUG] {
+        if matches.is_present(flag) {
+            args.push(format!("--{flag}").into());
+        }
+    }
+
+    for flag in [FLAG_LINKER, FLAG_PREBUILT, FLAG_MAX_THREADS] {
+        if let Some(value) = matches.value_of(flag) {
+            args.push(format!("--{flag}={value}").into());
+        }
+    }
+
+    args.push(matches.value_of_os(ROC_FILE).unwrap().to_owned());
+
+    args
+}
+
+/// Every .roc file in `dir` and its subdirectories, with the time it was last modified.
+/// Comparing these also notices files that were added or deleted. The build output in the same
+/// directory is not included, otherwise every build would start the next one.
+fn roc_files(dir: &Path, files: &mut Vec<(PathBuf, Option<SystemTime>)>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+
+        if entry.file_type()?.is_dir() {
+            // skip hidden directories like .git, and the build output of hosts
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+
+            if !(name.starts_with('.') || name == "target" || name == "zig-cache") {
+                roc_files(&path, files)?;
+            }
+        } else if path.extension().map_or(false, |ext| ext == "roc") {
+            let modified = entry.metadata()?.modified().ok();
+
+            files.push((path, modified));
+        }
+    }
+
+    Ok(())
+}
+
+fn snapshot(dir: &Path) -> io::Result<Vec<(PathBuf, Option<SystemTime>)>> {
+    let mut files = Vec::new();
+
+    roc_files(dir, &mut files)?;
+    files.sort();
+
+    Ok(files)
+}
@@ -0,0 +1,267 @@
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+use bumpalo::Bump;
+use roc_build::program::{check_file, DEFAULT_ROC_FILENAME};
+use roc_load::{LoadingProblem, Threading};
+use roc_packaging::cache::RocCacheDir;
+use strum::{EnumIter, IntoStaticStr};
+
+const BASIC_CLI_URL: &str = "https://github.com/roc-lang/basic-cli/releases/download/0.3.1/97mY3sUwo433-pcnEQUlMhn-sWiIf_J9bPhcAFZoqY4.tar.br";
+
+const GITIGNORE: &str = "\
+# build output
+main
+*.o
+*.obj
+*.wasm
+*.exe
+";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter, IntoStaticStr)]
+pub enum Template {
+    #[strum(serialize = "cli")]
+    Cli,
+    #[strum(serialize = "webserver")]
+    Webserver,
+    #[strum(serialize = "wasm")]
+    Wasm,
+}
+
+impl Default for Template {
+    fn default() -> Self {
+        Template::Cli
+    }
+}
+
+impl Template {
+    /// The platform used when no `--platform` is given, if there is an official one.
+    pub fn default_platform(self) -> Option<&'static str> {
+        match self {
+            Template::Cli => Some(BASIC_CLI_URL),
+            Template::Webserver | Template::Wasm => None,
+        }
+    }
+
+    fn imports(self) -> &'static str {
+        match self {
+            Template::Cli => "pf.Stdout",
+            Template::Webserver => "pf.Task.{ Task }, pf.Http.{ Request, Response }",
+            Template::Wasm => "",
+        }
+    }
+
+    fn body(self) -> &'static str {
+        match self {
+            Template::Cli => indoc::indoc!(
+                r#"
+                main =
+                    Stdout.line "Hello, World!"
+                "#
+            ),
+            Template::Webserver => indoc::indoc!(
+                r#"
+                main : Request -> Task Response []
+                main = \_req ->
+                    Task.ok { status: 200, headers: [], body: Str.toUtf8 "Hello, World!" }
+                "#
+            ),
+            Template::Wasm => indoc::indoc!(
+                r#"
+                main = "Hello, World!\n"
+                "#
+            ),
+        }
+    }
+
+    fn main_roc(self, app_name: &str, platform: &str) -> String {
+        format!(
+            "app \"{}\"\n    packages {{ pf: \"{}\" }}\n    imports [{}]\n    provides [main] to pf\n\n{}",
+            app_name,
+            platform,
+            self.imports(),
+            self.body()
+        )
+    }
+}
+
+impl std::fmt::Display for Template {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", Into::<&'static str>::into(self))
+    }
+}
+
+impl std::str::FromStr for Template {
+    type Err = String;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        match string {
+            "cli" => Ok(Template::Cli),
+            "webserver" => Ok(Template::Webserver),
+            "wasm" => Ok(Template::Wasm),
+            _ => Err(format!(
+                "Roc does not have a project template named {}",
+                string
+            )),
+        }
+    }
+}
+
+/// Scaffolds a new app in `project_dir`. Nothing is written unless the generated
+/// main.roc typechecks against the chosen platform.
+pub fn init(
+    project_dir: &Path,
+    template: Template,
+    platform_opt: Option<&str>,
+    roc_cache_dir: RocCacheDir<'_>,
+    threading: Threading,
+) -> io::Result<i32> {
+    let platform = match platform_opt.or_else(|| template.default_platform()) {
+        Some(platform) => platform,
+        None => {
+            eprintln!(
+                "The {} template has no default platform. Specify one with `--platform`.",
+                template
+            );
+
+            return Ok(1);
+        }
+    };
+
+    let main_path = project_dir.join(DEFAULT_ROC_FILENAME);
+
+    if main_path.exists() {
+        eprintln!(
+            "{} already exists, I will not overwrite it.",
+            main_path.display()
+        );
+
+        return Ok(1);
+    }
+
+    let app_name = std::env::current_dir()?
+        .join(project_dir)
+        .components()
+        .filter(|component| matches!(component, Component::Normal(_)))
+        .last()
+        .map(|component| component.as_os_str().to_string_lossy().to_string())
+        .unwrap_or_else(|| template.to_string());
+
+    let is_url = platform.starts_with("https://") || platform.starts_with("http://");
+
+    let platform_path_opt = if is_url {
+        None
+    } else {
+        match Path::new(platform).canonicalize() {
+            Ok(platform_path) => Some(platform_path),
+            Err(_) => {
+                eprintln!("I could not find a platform at {}", platform);
+
+                return Ok(1);
+            }
+        }
+    };
+
+    // Typecheck in a scratch directory first, so a platform that does not resolve
+    // or does not fit the template does not leave a half-initialized project behind.
+    let scratch_dir = tempfile::tempdir()?;
+    let scratch_main_path = scratch_dir.path().join(DEFAULT_ROC_FILENAME);
+    let checked_platform = match &platform_path_opt {
+        Some(platform_path) => platform_path.to_string_lossy().to_string(),
+        None => platform.to_owned(),
+    };
+
+    fs::write(
+        &scratch_main_path,
+        template.main_roc(&app_name, &checked_platform),
+    )?;
+
+    let arena = Bump::new();
+
+    match check_file(
+        &arena,
+        scratch_main_path,
+        false,
+        false,
+        false,
+        None,
+        roc_cache_dir,
+        threading,
+    ) {
+        Ok((problems, _)) => {
+            if problems.errors > 0 {
+                eprintln!(
+                    "The {} template does not typecheck with the platform {}, so I did not create any files.",
+                    template, platform
+                );
+
+                return Ok(1);
+            }
+        }
+        Err(LoadingProblem::FormattedReport(report)) => {
+            print!("{}", report);
+
+            return Ok(1);
+        }
+        Err(other) => {
+            eprintln!("I could not load the platform {}:\n{:?}", platform, other);
+
+            return Ok(1);
+        }
+    }
+
+    fs::create_dir_all(project_dir)?;
+
+    let written_platform = match &platform_path_opt {
+        Some(platform_path) => {
+            let project_dir_path = project_dir.canonicalize()?;
+
+            relative_path(&project_dir_path, platform_path)
+                .to_string_lossy()
+                .replace('\\', "/")
+        }
+        None => platform.to_owned(),
+    };
+
+    fs::write(&main_path, template.main_roc(&app_name, &written_platform))?;
+
+    let gitignore_path = project_dir.join(".gitignore");
+
+    if !gitignore_path.exists() {
+        fs::write(gitignore_path, GITIGNORE)?;
+    }
+
+    println!(
+        "Created a {} app in {}\n\nRun it with: roc dev {}",
+        template,
+        project_dir.display(),
+        main_path.display()
+    );
+
+    Ok(0)
+}
+
+/// Both paths must be absolute, e.g. `/a/b` to `/a/c/main.roc` gives `../c/main.roc`.
+fn relative_path(from_dir: &Path, to: &Path) -> PathBuf {
+    let from_components: Vec<Component> = from_dir.components().collect();
+    let to_components: Vec<Component> = to.components().collect();
+
+    let common_len = from_components
+        .iter()
+        .zip(to_components.iter())
+        .take_while(|(from, to)| from == to)
+        .count();
+
+    let mut relative = PathBuf::new();
+
+    for _ in common_len..from_components.len() {
+        relative.push("..");
+    }
+
+    for component in &to_components[common_len..] {
+        relative.push(component);
+    }
+
+    relative
+}
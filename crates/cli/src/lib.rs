@@ -4,7 +4,7 @@
 extern crate const_format;
 
 use bumpalo::Bump;
-use clap::{Arg, ArgMatches, Command, ValueSource};
+use clap::{value_parser, Arg, ArgMatches, Command, ValueHint, ValueSource};
 use roc_build::link::{LinkType, LinkingStrategy};
 use roc_build::program::{
     handle_error_module, handle_loading_problem, standard_load_config, BuildFileError,
@@ -12,7 +12,7 @@ use roc_build::program::{
 };
 use roc_error_macros::{internal_error, user_error};
 use roc_gen_llvm::llvm::build::LlvmBackendMode;
-use roc_load::{ExpectMetadata, Threading};
+use roc_load::{CancelToken, ExpectMetadata, Threading};
 use roc_mono::ir::OptLevel;
 use roc_packaging::cache::RocCacheDir;
 use roc_packaging::tarball::Compression;
@@ -32,9 +32,21 @@ use target_lexicon::{
 #[cfg(not(target_os = "linux"))]
 use tempfile::TempDir;
 
+mod completions;
+pub use completions::{generate_completions, Shell};
+
 mod format;
 pub use format::format;
 
+mod init;
+pub use init::{init, Template};
+
+mod hot;
+pub use hot::watch_and_rebuild;
+
+mod progress;
+use progress::BuildProgress;
+
 pub const CMD_BUILD: &str = "build";
 pub const CMD_RUN: &str = "run";
 pub const CMD_DEV: &str = "dev";
@@ -47,6 +59,8 @@ pub const CMD_FORMAT: &str = "format";
 pub const CMD_TEST: &str = "test";
 pub const CMD_GLUE: &str = "glue";
 pub const CMD_GEN_STUB_LIB: &str = "gen-stub-lib";
+pub const CMD_INIT: &str = "init";
+pub const CMD_COMPLETIONS: &str = "completions";
 
 pub const FLAG_DEBUG: &str = "debug";
 pub const FLAG_BUNDLE: &str = "bundle";
@@ -62,12 +76,24 @@ pub const FLAG_LINKER: &str = "linker";
 pub const FLAG_PREBUILT: &str = "prebuilt-platform";
 pub const FLAG_CHECK: &str = "check";
 pub const FLAG_WASM_STACK_SIZE_KB: &str = "wasm-stack-size-kb";
+pub const FLAG_STACK_SIZE: &str = "stack-size";
+pub const FLAG_VERIFY: &str = "verify";
+pub const FLAG_TEMPLATE: &str = "template";
+pub const FLAG_PLATFORM: &str = "platform";
+pub const FLAG_CALL_GRAPH: &str = "call-graph";
+pub const FLAG_UNUSED: &str = "unused";
+pub const FLAG_QUIET: &str = "quiet";
+pub const FLAG_HOT: &str = "hot";
+pub const FLAG_EMIT_CAN: &str = "emit-can";
+pub const FLAG_EXPLAIN: &str = "explain";
 pub const ROC_FILE: &str = "ROC_FILE";
 pub const ROC_DIR: &str = "ROC_DIR";
 pub const GLUE_DIR: &str = "GLUE_DIR";
 pub const GLUE_SPEC: &str = "GLUE_SPEC";
 pub const DIRECTORY_OR_FILES: &str = "DIRECTORY_OR_FILES";
 pub const ARGS_FOR_APP: &str = "ARGS_FOR_APP";
+pub const PROJECT_DIR: &str = "PROJECT_DIR";
+pub const SHELL: &str = "SHELL";
 
 const VERSION: &str = include_str!("../../../version.txt");
 
@@ -123,6 +149,18 @@ pub fn build_app<'a>() -> Command<'a> {
         .validator(|s| s.parse::<u32>())
         .required(false);
 
+    let flag_stack_size = Arg::new(FLAG_STACK_SIZE)
+        .long(FLAG_STACK_SIZE)
+        .help("Stack size in kilobytes for the Roc code of the built program\n(When Roc code uses more, the program crashes with \"stack overflow in <def>\". This only applies to the LLVM backend.)")
+        .takes_value(true)
+        .validator(|s| s.parse::<u32>())
+        .required(false);
+
+    let flag_verify = Arg::new(FLAG_VERIFY)
+        .long(FLAG_VERIFY)
+        .help("Keep the `expect`s in an optimized build\n(When one fails, the program crashes with its location and condition. This only applies to the LLVM backend; unoptimized builds run expects anyway.)")
+        .required(false);
+
     let roc_file_to_run = Arg::new(ROC_FILE)
         .help("The .roc file of an app to run")
         .allow_invalid_utf8(true)
@@ -151,6 +189,8 @@ pub fn build_app<'a>() -> Command<'a> {
             .arg(flag_linker.clone())
             .arg(flag_prebuilt.clone())
             .arg(flag_wasm_stack_size_kb.clone())
+            .arg(flag_stack_size.clone())
+            .arg(flag_verify.clone())
             .arg(
                 Arg::new(FLAG_TARGET)
                     .long(FLAG_TARGET)
@@ -181,6 +221,12 @@ pub fn build_app<'a>() -> Command<'a> {
                     .help("Do not link\n(Instead, just output the `.o` file.)")
                     .required(false),
             )
+            .arg(
+                Arg::new(FLAG_QUIET)
+                    .long(FLAG_QUIET)
+                    .help("Don't show the progress of the build\n(It is also hidden when stderr is not a terminal.)")
+                    .required(false),
+            )
             .arg(
                 Arg::new(ROC_FILE)
                     .help("The .roc file to build")
@@ -221,11 +267,19 @@ pub fn build_app<'a>() -> Command<'a> {
             .arg(flag_time.clone())
             .arg(flag_linker.clone())
             .arg(flag_prebuilt.clone())
+            .arg(flag_stack_size.clone())
+            .arg(flag_verify.clone())
             .arg(roc_file_to_run.clone())
             .arg(args_for_app.clone())
         )
         .subcommand(Command::new(CMD_DEV)
             .about("`check` a .roc file, and then run it if there were no errors")
+            .arg(
+                Arg::new(FLAG_HOT)
+                    .long(FLAG_HOT)
+                    .help("Build the app as a dynamic library instead of running it, and rebuild it whenever a .roc file changes\n(A host that loads the library can then reload the new version without restarting.)")
+                    .required(false),
+            )
             .arg(flag_optimize.clone())
             .arg(flag_max_threads.clone())
             .arg(flag_opt_size.clone())
@@ -234,6 +288,7 @@ pub fn build_app<'a>() -> Command<'a> {
             .arg(flag_time.clone())
             .arg(flag_linker.clone())
             .arg(flag_prebuilt.clone())
+            .arg(flag_stack_size.clone())
             .arg(roc_file_to_run.clone())
             .arg(args_for_app.clone())
         )
@@ -258,6 +313,35 @@ pub fn build_app<'a>() -> Command<'a> {
             .about("Check the code for problems, but don’t build or run it")
             .arg(flag_time.clone())
             .arg(flag_max_threads.clone())
+            .arg(
+                Arg::new(FLAG_UNUSED)
+                    .long(FLAG_UNUSED)
+                    .help("Also warn about values that an interface module exposes, but no other module uses")
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_EMIT_CAN)
+                    .long(FLAG_EMIT_CAN)
+                    .help("Print the canonical AST of the app and its packages, after desugaring and with resolved names\n(Each top-level definition starts with a comment that has its region.)")
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_EXPLAIN)
+                    .long(FLAG_EXPLAIN)
+                    .help("Print a longer explanation of a problem code shown in a report, like E0001, instead of checking")
+                    .takes_value(true)
+                    .value_name("ERRORCODE")
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_CALL_GRAPH)
+                    .long(FLAG_CALL_GRAPH)
+                    .help("Write the calls between the top-level definitions of all modules to this file, as JSON")
+                    .takes_value(true)
+                    .value_hint(ValueHint::FilePath)
+                    .allow_invalid_utf8(true)
+                    .required(false),
+            )
             .arg(
                 Arg::new(ROC_FILE)
                     .help("The .roc file of an app to check")
@@ -288,6 +372,7 @@ pub fn build_app<'a>() -> Command<'a> {
             .arg(
                 Arg::new(GLUE_DIR)
                     .help("The directory for the generated glue code.\nNote: The implementation can write to any file in this directory.")
+                    .value_hint(ValueHint::DirPath)
                     .allow_invalid_utf8(true)
                     .required(true)
             )
@@ -299,6 +384,35 @@ pub fn build_app<'a>() -> Command<'a> {
                     .default_value(DEFAULT_ROC_FILENAME)
             )
         )
+        .subcommand(Command::new(CMD_INIT)
+            .about("Create a new app from a template, after checking that it typechecks with the chosen platform")
+            .arg(flag_max_threads.clone())
+            .arg(
+                Arg::new(FLAG_TEMPLATE)
+                    .long(FLAG_TEMPLATE)
+                    .help("The kind of app to create")
+                    .default_value(Template::default().into())
+                    .possible_values(Template::iter().map(|template| {
+                        Into::<&'static str>::into(template)
+                    }))
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_PLATFORM)
+                    .long(FLAG_PLATFORM)
+                    .help("The URL or path of the platform's main.roc\n(The cli template uses basic-cli by default, the other templates require this.)")
+                    .takes_value(true)
+                    .required(false),
+            )
+            .arg(
+                Arg::new(PROJECT_DIR)
+                    .help("The directory to create the app in")
+                    .value_hint(ValueHint::DirPath)
+                    .allow_invalid_utf8(true)
+                    .required(false)
+                    .default_value("."),
+            )
+        )
         .subcommand(Command::new(CMD_GEN_STUB_LIB)
             .about("Generate a stubbed shared library that can be used for linking a platform binary.\nThe stubbed library has prototypes, but no function bodies.\n\nNote: This command will be removed in favor of just using `roc build` once all platforms support the surgical linker")
             .arg(
@@ -318,6 +432,15 @@ pub fn build_app<'a>() -> Command<'a> {
                     .required(false),
             )
         )
+        .subcommand(Command::new(CMD_COMPLETIONS)
+            .about("Print a script that completes roc's subcommands, flags and file paths in a shell\n(For example, `roc completions bash > ~/.local/share/bash-completion/completions/roc`)")
+            .arg(
+                Arg::new(SHELL)
+                    .help("The shell to complete in")
+                    .value_parser(value_parser!(Shell))
+                    .required(true),
+            )
+        )
         .trailing_var_arg(true)
         .arg(flag_optimize)
         .arg(flag_max_threads.clone())
@@ -327,6 +450,8 @@ pub fn build_app<'a>() -> Command<'a> {
         .arg(flag_time)
         .arg(flag_linker)
         .arg(flag_prebuilt)
+        .arg(flag_stack_size)
+        .arg(flag_verify)
         .arg(roc_file_to_run.required(false))
         .arg(args_for_app);
 
@@ -367,7 +492,9 @@ pub fn test(_matches: &ArgMatches, _triple: Triple) -> io::Result<i32> {
 #[cfg(not(windows))]
 pub fn test(matches: &ArgMatches, triple: Triple) -> io::Result<i32> {
     use roc_build::program::report_problems_monomorphized;
-    use roc_load::{ExecutionMode, LoadConfig, LoadMonomorphizedError};
+    use roc_load::{
+        ExecutionMode, HotModule, LoadConfig, LoadMonomorphizedError, ProgressReporter, SolveFuel,
+    };
     use roc_packaging::cache;
     use roc_target::TargetInfo;
 
@@ -429,6 +556,10 @@ pub fn test(matches: &ArgMatches, triple: Triple) -> io::Result<i32> {
         palette: roc_reporting::report::DEFAULT_PALETTE,
         threading,
         exec_mode: ExecutionMode::Test,
+        cancel: CancelToken::default(),
+        progress: ProgressReporter::default(),
+        hot_module: HotModule::default(),
+        solve_fuel: SolveFuel::default(),
     };
     let load_result = roc_load::load_and_monomorphize(
         arena,
@@ -624,6 +755,11 @@ pub fn build(
             false => {
                 let backend_mode = match opt_level {
                     OptLevel::Development => LlvmBackendMode::BinaryDev,
+                    OptLevel::Normal | OptLevel::Size | OptLevel::Optimize
+                        if matches.is_present(FLAG_VERIFY) =>
+                    {
+                        LlvmBackendMode::BinaryVerify
+                    }
                     OptLevel::Normal | OptLevel::Size | OptLevel::Optimize => {
                         LlvmBackendMode::Binary
                     }
@@ -676,6 +812,13 @@ pub fn build(
         .and_then(|s| s.parse::<u32>().ok())
         .map(|x| x * 1024);
 
+    let stack_bytes: Option<u32> = matches
+        .try_get_one::<&str>(FLAG_STACK_SIZE)
+        .ok()
+        .flatten()
+        .and_then(|s| s.parse::<u32>().ok())
+        .map(|x| x * 1024);
+
     let build_ordering = match config {
         BuildAndRunIfNoErrors => BuildOrdering::BuildIfChecks,
         _ => BuildOrdering::AlwaysBuild,
@@ -685,9 +828,20 @@ pub fn build(
         backend: code_gen_backend,
         opt_level,
         emit_debug_info,
+        stack_bytes,
     };
 
-    let load_config = standard_load_config(&triple, build_ordering, threading);
+    let mut load_config = standard_load_config(&triple, build_ordering, threading);
+
+    // Only `roc build` shows its progress; `roc run` and friends go straight to the app's output.
+    let progress = match config {
+        BuildOnly => BuildProgress::start(matches.is_present(FLAG_QUIET)),
+        _ => None,
+    };
+
+    if let Some(progress) = &progress {
+        load_config.progress = progress.reporter();
+    }
 
     let res_binary_path = build_file(
         &arena,
@@ -703,6 +857,10 @@ pub fn build(
         load_config,
     );
 
+    if let Some(progress) = &progress {
+        progress.finish();
+    }
+
     match res_binary_path {
         Ok(BuiltFile {
             binary_path,
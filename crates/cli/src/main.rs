@@ -2,15 +2,18 @@
 use roc_build::link::LinkType;
 use roc_build::program::check_file;
 use roc_cli::{
-    build_app, format, test, BuildConfig, FormatMode, Target, CMD_BUILD, CMD_CHECK, CMD_DEV,
-    CMD_DOCS, CMD_EDIT, CMD_FORMAT, CMD_GEN_STUB_LIB, CMD_GLUE, CMD_REPL, CMD_RUN, CMD_TEST,
-    CMD_VERSION, DIRECTORY_OR_FILES, FLAG_CHECK, FLAG_LIB, FLAG_NO_LINK, FLAG_TARGET, FLAG_TIME,
-    GLUE_DIR, GLUE_SPEC, ROC_FILE,
+    build_app, format, generate_completions, init, test, watch_and_rebuild, BuildConfig,
+    FormatMode, Shell, Target, Template, CMD_BUILD, CMD_CHECK, CMD_COMPLETIONS, CMD_DEV, CMD_DOCS,
+    CMD_EDIT, CMD_FORMAT, CMD_GEN_STUB_LIB, CMD_GLUE, CMD_INIT, CMD_REPL, CMD_RUN, CMD_TEST,
+    CMD_VERSION, DIRECTORY_OR_FILES, FLAG_CALL_GRAPH, FLAG_CHECK, FLAG_EMIT_CAN, FLAG_EXPLAIN,
+    FLAG_HOT, FLAG_LIB, FLAG_NO_LINK, FLAG_PLATFORM, FLAG_TARGET, FLAG_TEMPLATE, FLAG_TIME,
+    FLAG_UNUSED, GLUE_DIR, GLUE_SPEC, PROJECT_DIR, ROC_FILE, SHELL,
 };
 use roc_docs::generate_docs_html;
 use roc_error_macros::user_error;
 use roc_load::{LoadingProblem, Threading};
 use roc_packaging::cache::{self, RocCacheDir};
+use roc_reporting::error_code;
 use std::fs::{self, FileType};
 use std::io;
 use std::path::{Path, PathBuf};
@@ -72,7 +75,9 @@ fn main() -> io::Result<()> {
             }
         }
         Some((CMD_DEV, matches)) => {
-            if matches.is_present(ROC_FILE) {
+            if matches.is_present(ROC_FILE) && matches.is_present(FLAG_HOT) {
+                watch_and_rebuild(matches)
+            } else if matches.is_present(ROC_FILE) {
                 build(
                     matches,
                     BuildConfig::BuildAndRunIfNoErrors,
@@ -128,10 +133,32 @@ fn main() -> io::Result<()> {
                 link_type,
             )?)
         }
+        Some((CMD_CHECK, matches)) if matches.is_present(FLAG_EXPLAIN) => {
+            let code = matches.value_of(FLAG_EXPLAIN).unwrap();
+
+            match error_code::by_code(code) {
+                Some(error_code) => {
+                    println!(
+                        "{} {}\n\n{}",
+                        error_code.code, error_code.title, error_code.explanation
+                    );
+
+                    Ok(0)
+                }
+                None => {
+                    eprintln!("There is no problem with the code {}.", code);
+
+                    Ok(1)
+                }
+            }
+        }
         Some((CMD_CHECK, matches)) => {
             let arena = bumpalo::Bump::new();
 
             let emit_timings = matches.is_present(FLAG_TIME);
+            let report_unused = matches.is_present(FLAG_UNUSED);
+            let emit_can = matches.is_present(FLAG_EMIT_CAN);
+            let call_graph_path = matches.value_of_os(FLAG_CALL_GRAPH).map(Path::new);
             let filename = matches.value_of_os(ROC_FILE).unwrap();
             let roc_file_path = PathBuf::from(filename);
             let threading = match matches
@@ -148,6 +175,9 @@ fn main() -> io::Result<()> {
                 &arena,
                 roc_file_path,
                 emit_timings,
+                report_unused,
+                emit_can,
+                call_graph_path,
                 RocCacheDir::Persistent(cache::roc_cache_dir().as_path()),
                 threading,
             ) {
@@ -192,6 +222,27 @@ fn main() -> io::Result<()> {
                 }
             }
         }
+        Some((CMD_INIT, matches)) => {
+            let project_dir = Path::new(matches.value_of_os(PROJECT_DIR).unwrap());
+            let template: Template = matches.value_of_t(FLAG_TEMPLATE).unwrap_or_default();
+            let threading = match matches
+                .value_of(roc_cli::FLAG_MAX_THREADS)
+                .and_then(|s| s.parse::<usize>().ok())
+            {
+                None => Threading::AllAvailable,
+                Some(0) => user_error!("cannot build with at most 0 threads"),
+                Some(1) => Threading::Single,
+                Some(n) => Threading::AtMost(n),
+            };
+
+            init(
+                project_dir,
+                template,
+                matches.value_of(FLAG_PLATFORM),
+                RocCacheDir::Persistent(cache::roc_cache_dir().as_path()),
+                threading,
+            )
+        }
         Some((CMD_REPL, _)) => Ok(roc_repl_cli::main()),
         Some((CMD_EDIT, matches)) => {
             match matches
@@ -262,6 +313,13 @@ fn main() -> io::Result<()> {
 
             Ok(format_exit_code)
         }
+        Some((CMD_COMPLETIONS, matches)) => {
+            let shell = *matches.get_one::<Shell>(SHELL).unwrap();
+
+            print!("{}", generate_completions(&build_app(), shell));
+
+            Ok(0)
+        }
         Some((CMD_VERSION, _)) => {
             print!(
                 "{}",
@@ -0,0 +1,136 @@
+//! The live progress line that `roc build` shows while it loads an app.
+
+use roc_collections::all::MutSet;
+use roc_load::{LoadEvent, ProgressReporter};
+use roc_module::symbol::ModuleId;
+use roc_reporting::report::ANSI_STYLE_CODES;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+/// Draws a line on stderr with how many modules were parsed, checked and specialized,
+/// and redraws it whenever the load makes progress.
+pub struct BuildProgress {
+    line: Arc<Mutex<ProgressLine>>,
+}
+
+impl BuildProgress {
+    /// Returns `None` when no progress should be shown: when `quiet` is set, or when stderr is
+    /// not a terminal, so that logs and piped output don't fill up with redrawn lines.
+    pub fn start(quiet: bool) -> Option<Self> {
+        if quiet || !stderr_is_terminal() {
+            return None;
+        }
+
+        let line = ProgressLine {
+            start: Instant::now(),
+            loaded: MutSet::default(),
+            parsed: MutSet::default(),
+            checked: MutSet::default(),
+            specialized: MutSet::default(),
+            shown: false,
+        };
+
+        Some(Self {
+            line: Arc::new(Mutex::new(line)),
+        })
+    }
+
+    pub fn reporter(&self) -> ProgressReporter {
+        let line = Arc::clone(&self.line);
+
+        ProgressReporter::new(move |event| line.lock().unwrap().update(event))
+    }
+
+    /// Removes the progress line, if it is still there, so that whatever is printed next
+    /// starts on an empty line. The line removes itself when the load finishes, but not when
+    /// the load fails.
+    pub fn finish(&self) {
+        self.line.lock().unwrap().clear();
+    }
+}
+
+struct ProgressLine {
+    start: Instant,
+    loaded: MutSet<ModuleId>,
+    parsed: MutSet<ModuleId>,
+    checked: MutSet<ModuleId>,
+    specialized: MutSet<ModuleId>,
+    /// Whether the line is currently on screen
+    shown: bool,
+}
+
+impl ProgressLine {
+    fn update(&mut self, event: LoadEvent) {
+        match event {
+            LoadEvent::HeaderLoaded(module_id) => self.loaded.insert(module_id),
+            LoadEvent::Parsed(module_id) => self.parsed.insert(module_id),
+            LoadEvent::Checked(module_id) => self.checked.insert(module_id),
+            LoadEvent::Specialized(module_id) => self.specialized.insert(module_id),
+            LoadEvent::Finished => {
+                self.clear();
+                return;
+            }
+        };
+
+        self.draw();
+    }
+
+    /// The first phase that some modules have not completed yet. Modules move through the
+    /// phases at different speeds, so this is the phase that holds up the build.
+    fn phase(&self) -> &'static str {
+        let total = self.loaded.len();
+
+        if self.parsed.len() < total {
+            "Parsing"
+        } else if self.checked.len() < total {
+            "Checking types"
+        } else {
+            "Specializing"
+        }
+    }
+
+    fn draw(&mut self) {
+        let total = self.loaded.len();
+
+        let line = format!(
+            "{}{}{:<14}{} {}/{total} parsed, {}/{total} checked, {}/{total} specialized ({:.1}s)",
+            ANSI_STYLE_CODES.bold,
+            ANSI_STYLE_CODES.cyan,
+            self.phase(),
+            ANSI_STYLE_CODES.reset,
+            self.parsed.len(),
+            self.checked.len(),
+            self.specialized.len(),
+            self.start.elapsed().as_secs_f64(),
+        );
+
+        // Go back to the start of the line and erase it, so the new line replaces the old one.
+        let mut stderr = std::io::stderr().lock();
+        let _ = write!(stderr, "\r\x1b[2K{line}");
+        let _ = stderr.flush();
+
+        self.shown = true;
+    }
+
+    fn clear(&mut self) {
+        if self.shown {
+            let mut stderr = std::io::stderr().lock();
+            let _ = write!(stderr, "\r\x1b[2K");
+            let _ = stderr.flush();
+
+            self.shown = false;
+        }
+    }
+}
+
+#[cfg(unix)]
+fn stderr_is_terminal() -> bool {
+    unsafe { libc::isatty(libc::STDERR_FILENO) == 1 }
+}
+
+#[cfg(not(unix))]
+fn stderr_is_terminal() -> bool {
+    // Without a reliable way to tell, assume the output goes somewhere that can't be redrawn.
+    false
+}
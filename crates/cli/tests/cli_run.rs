@@ -9,13 +9,17 @@ extern crate roc_module;
 
 #[cfg(test)]
 mod cli_run {
+    use clap::ValueEnum;
     use cli_utils::helpers::{
         extract_valgrind_errors, file_path_from_root, fixture_file, fixtures_dir, known_bad_file,
         run_cmd, run_roc, run_with_valgrind, strip_colors, Out, ValgrindError, ValgrindErrorXWhat,
     };
     use const_format::concatcp;
     use indoc::indoc;
-    use roc_cli::{CMD_BUILD, CMD_CHECK, CMD_DEV, CMD_FORMAT, CMD_RUN, CMD_TEST};
+    use roc_cli::{
+        build_app, Shell, CMD_BUILD, CMD_CHECK, CMD_COMPLETIONS, CMD_DEV, CMD_FORMAT, CMD_INIT,
+        CMD_RUN, CMD_TEST, FLAG_OPTIMIZE, FLAG_PLATFORM, FLAG_TEMPLATE,
+    };
     use roc_test_utils::assert_multiline_str_eq;
     use serial_test::serial;
     use std::iter;
@@ -1395,7 +1399,7 @@ mod cli_run {
             &[],
             indoc!(
                 r#"
-                ── TYPE MISMATCH ─────────────────────────────── tests/known_bad/TypeError.roc ─
+                ── TYPE MISMATCH [E0001] ─────────────────────── tests/known_bad/TypeError.roc ─
 
                 Something is off with the body of the main definition:
 
@@ -1431,7 +1435,7 @@ mod cli_run {
             &[],
             indoc!(
                 r#"
-                ── MISSING DEFINITION ────────────────── tests/known_bad/ExposedNotDefined.roc ─
+                ── MISSING DEFINITION [E0003] ────────── tests/known_bad/ExposedNotDefined.roc ─
 
                 bar is listed as exposed, but it isn't defined in this module.
 
@@ -1452,7 +1456,7 @@ mod cli_run {
             &[],
             indoc!(
                 r#"
-                ── UNUSED IMPORT ──────────────────────────── tests/known_bad/UnusedImport.roc ─
+                ── UNUSED IMPORT [E0005] ──────────────────── tests/known_bad/UnusedImport.roc ─
 
                 Nothing from Symbol is used in this module.
 
@@ -1475,7 +1479,7 @@ mod cli_run {
             &[],
             indoc!(
                 r#"
-                ── UNKNOWN GENERATES FUNCTION ─────── tests/known_bad/UnknownGeneratesWith.roc ─
+                ── UNKNOWN GENERATES FUNCTION [E0017] ─ .../known_bad/UnknownGeneratesWith.roc ─
 
                 I don't know how to generate the foobar function.
 
@@ -1510,6 +1514,109 @@ mod cli_run {
         // This doesn't fail, since only "Formatted.roc" and non-roc files are present in this folder
         check_format_check_as_expected(&fixtures_dir("format/formatted_directory"), true);
     }
+
+    fn wasm_platform_path() -> String {
+        file_path_from_root(
+            "examples/platform-switching/web-assembly-platform",
+            "main.roc",
+        )
+        .to_str()
+        .unwrap()
+        .to_owned()
+    }
+
+    #[test]
+    fn init_wasm_template() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let project_dir = tmp_dir.path().join("hello");
+
+        let out = run_roc(
+            [
+                CMD_INIT,
+                &format!("--{}", FLAG_TEMPLATE),
+                "wasm",
+                &format!("--{}", FLAG_PLATFORM),
+                &wasm_platform_path(),
+                project_dir.to_str().unwrap(),
+            ],
+            &[],
+            &[],
+        );
+        assert!(out.status.success(), "{}", out.stderr);
+
+        let main_roc = std::fs::read_to_string(project_dir.join("main.roc")).unwrap();
+        assert!(main_roc.starts_with("app \"hello\""));
+        assert!(project_dir.join(".gitignore").exists());
+
+        let out = run_roc(
+            [CMD_CHECK, project_dir.join("main.roc").to_str().unwrap()],
+            &[],
+            &[],
+        );
+        assert!(out.status.success());
+    }
+
+    #[test]
+    fn init_platform_does_not_fit_template() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let project_dir = tmp_dir.path().join("hello");
+
+        // the wasm platform has no Stdout module
+        let out = run_roc(
+            [
+                CMD_INIT,
+                &format!("--{}", FLAG_TEMPLATE),
+                "cli",
+                &format!("--{}", FLAG_PLATFORM),
+                &wasm_platform_path(),
+                project_dir.to_str().unwrap(),
+            ],
+            &[],
+            &[],
+        );
+        assert!(!out.status.success());
+        assert!(!project_dir.exists());
+    }
+
+    #[test]
+    fn init_template_without_default_platform() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let project_dir = tmp_dir.path().join("server");
+
+        let out = run_roc(
+            [
+                CMD_INIT,
+                &format!("--{}", FLAG_TEMPLATE),
+                "webserver",
+                project_dir.to_str().unwrap(),
+            ],
+            &[],
+            &[],
+        );
+        assert!(!out.status.success());
+        assert!(out.stderr.contains("--platform"));
+        assert!(!project_dir.exists());
+    }
+
+    #[test]
+    fn completions_cover_all_subcommands() {
+        for shell in Shell::value_variants() {
+            let shell_name = shell.to_string();
+            let out = run_roc([CMD_COMPLETIONS, shell_name.as_str()], &[], &[]);
+            assert!(out.status.success(), "{}", out.stderr);
+
+            for subcommand in build_app().get_subcommands() {
+                assert!(
+                    out.stdout.contains(subcommand.get_name()),
+                    "the {} completions don't mention `{}`",
+                    shell_name,
+                    subcommand.get_name()
+                );
+            }
+
+            assert!(out.stdout.contains(&format!("-{}", FLAG_OPTIMIZE)));
+        }
+    }
 }
 
 #[cfg(feature = "wasm32-cli-run")]
@@ -129,6 +129,14 @@ pub fn new_module_var_mn(
     make_nested_mn(vec![module_name_id, dot_id, ident_id], 0)
 }
 
+pub fn new_expect_mn(expect_mn_id: MarkNodeId, cond_expr_mn_id: MarkNodeId) -> MarkupNode {
+    make_nested_mn(vec![expect_mn_id, cond_expr_mn_id], NEW_LINES_AFTER_DEF)
+}
+
+pub fn expect_mn() -> MarkupNode {
+    keyword_mn("expect ")
+}
+
 pub fn if_mn() -> MarkupNode {
     keyword_mn("if ")
 }
@@ -141,6 +149,15 @@ pub fn else_mn() -> MarkupNode {
     keyword_mn(" else ")
 }
 
+// ends the first line of a `when`, the branches start on the next line
+pub fn is_mn() -> MarkupNode {
+    common_text_node(" is".to_owned(), HighlightStyle::Keyword, 1)
+}
+
+pub fn guard_if_mn() -> MarkupNode {
+    keyword_mn(" if ")
+}
+
 fn keyword_mn(keyword: &str) -> MarkupNode {
     common_text_node(keyword.to_owned(), HighlightStyle::Keyword, 0)
 }
@@ -1,6 +1,6 @@
 use crate::{
     markup::{
-        common_nodes::new_blank_mn_w_nls,
+        common_nodes::{expect_mn, new_blank_mn_w_nls, new_expect_mn},
         mark_id_ast_id_map::MarkIdAstIdMap,
         nodes::MarkupNode,
         top_level_def::{assignment_mark_node, tld_w_comments_mark_node},
@@ -71,6 +71,27 @@ pub fn def2_to_markup<'a>(
 
             add_node(tld_mn, ast_node_id, mark_node_pool, mark_id_ast_id_map)
         }
+        Def2::Expect { expr_id } => {
+            let expect_mn_id =
+                add_node(expect_mn(), ast_node_id, mark_node_pool, mark_id_ast_id_map);
+
+            let cond_mn_id = expr2_to_markup(
+                env,
+                env.pool.get(*expr_id),
+                *expr_id,
+                mark_node_pool,
+                mark_id_ast_id_map,
+                interns,
+                0,
+            )?;
+
+            add_node(
+                new_expect_mn(expect_mn_id, cond_mn_id),
+                ast_node_id,
+                mark_node_pool,
+                mark_id_ast_id_map,
+            )
+        }
         Def2::Blank => add_node(
             new_blank_mn_w_nls(2),
             ast_node_id,
@@ -2,13 +2,14 @@ use crate::{
     markup::{
         attribute::Attributes,
         common_nodes::{
-            new_arg_name_mn, new_arrow_mn, new_blank_mn, new_colon_mn, new_comma_mn, new_equals_mn,
-            new_left_accolade_mn, new_left_square_mn, new_operator_mn, new_right_accolade_mn,
-            new_right_square_mn,
+            guard_if_mn, is_mn, new_arg_name_mn, new_arrow_mn, new_blank_mn, new_colon_mn,
+            new_comma_mn, new_equals_mn, new_left_accolade_mn, new_left_square_mn, new_operator_mn,
+            new_right_accolade_mn, new_right_square_mn,
         },
         mark_id_ast_id_map::MarkIdAstIdMap,
         nodes::{
-            get_string, join_mark_nodes_commas, join_mark_nodes_spaces, new_markup_node, MarkupNode,
+            get_string, join_mark_nodes_commas, join_mark_nodes_spaces, mark_nodes_to_string,
+            new_markup_node, MarkupNode, COMMA, LEFT_ACCOLADE, RIGHT_ACCOLADE,
         },
     },
     slow_pool::{MarkNodeId, SlowPool},
@@ -21,10 +22,10 @@ use roc_ast::{
         core::{
             ast::ASTNodeId,
             expr::{
-                expr2::{Expr2, ExprId},
+                expr2::{Expr2, ExprId, FloatVal, IntVal},
                 record_field::RecordField,
             },
-            pattern::{get_identifier_string, Pattern2},
+            pattern::{get_identifier_string, DestructType, Pattern2},
             val_def::ValueDef,
         },
         env::Env,
@@ -455,6 +456,116 @@ pub fn expr2_to_markup<'a>(
 
             add_node(function_mn, ast_node_id, mark_node_pool, mark_id_ast_id_map)
         }
+        Expr2::When { branches, cond, .. } => {
+            let when_mn_id = new_markup_node(
+                "when ".to_string(),
+                ast_node_id,
+                HighlightStyle::Keyword,
+                mark_node_pool,
+                mark_id_ast_id_map,
+                indent_level,
+            );
+
+            let cond_mn_id = expr2_to_markup(
+                env,
+                env.pool.get(*cond),
+                *cond,
+                mark_node_pool,
+                mark_id_ast_id_map,
+                interns,
+                0,
+            )?;
+
+            let is_mn_id = add_node(is_mn(), ast_node_id, mark_node_pool, mark_id_ast_id_map);
+
+            let mut children_ids = vec![when_mn_id, cond_mn_id, is_mn_id];
+
+            let nr_of_branches = branches.len();
+
+            for (index, branch) in branches.iter(env.pool).enumerate() {
+                let patterns_str = branch
+                    .patterns
+                    .iter(env.pool)
+                    .map(|pattern| pattern2_to_string(env, pattern, interns))
+                    .collect::<ASTResult<Vec<String>>>()?
+                    .join(" | ");
+
+                let patterns_mn_id = new_markup_node(
+                    patterns_str,
+                    ast_node_id,
+                    HighlightStyle::Type,
+                    mark_node_pool,
+                    mark_id_ast_id_map,
+                    indent_level + 1,
+                );
+
+                let mut branch_children_ids = vec![patterns_mn_id];
+
+                if let Some(guard_id) = branch.guard {
+                    branch_children_ids.push(add_node(
+                        guard_if_mn(),
+                        ast_node_id,
+                        mark_node_pool,
+                        mark_id_ast_id_map,
+                    ));
+
+                    branch_children_ids.push(expr2_to_markup(
+                        env,
+                        env.pool.get(guard_id),
+                        guard_id,
+                        mark_node_pool,
+                        mark_id_ast_id_map,
+                        interns,
+                        0,
+                    )?);
+                }
+
+                branch_children_ids.push(add_node(
+                    new_arrow_mn(0),
+                    ast_node_id,
+                    mark_node_pool,
+                    mark_id_ast_id_map,
+                ));
+
+                let body_mn_id = expr2_to_markup(
+                    env,
+                    env.pool.get(branch.body),
+                    branch.body,
+                    mark_node_pool,
+                    mark_id_ast_id_map,
+                    interns,
+                    0,
+                )?;
+
+                // the last branch ends where the `when` ends
+                if index + 1 < nr_of_branches {
+                    mark_node_pool.get_mut(body_mn_id).add_newline_at_end();
+                }
+
+                branch_children_ids.push(body_mn_id);
+
+                let branch_mn = MarkupNode::Nested {
+                    children_ids: branch_children_ids,
+                    parent_id_opt: None,
+                    newlines_at_end: 0,
+                };
+
+                children_ids.push(add_node(
+                    branch_mn,
+                    ast_node_id,
+                    mark_node_pool,
+                    mark_id_ast_id_map,
+                ));
+            }
+
+            let when_mn = MarkupNode::Nested {
+                children_ids,
+                parent_id_opt: None,
+                newlines_at_end: 0,
+            };
+
+            add_node(when_mn, ast_node_id, mark_node_pool, mark_id_ast_id_map)
+        }
         Expr2::RuntimeError() => new_markup_node(
             "RunTimeError".to_string(),
             ast_node_id,
@@ -469,6 +580,113 @@ pub fn expr2_to_markup<'a>(
     Ok(mark_node_id)
 }
 
+// the text of a pattern of a `when` branch, e.g. `Ok _`
+fn pattern2_to_string(env: &Env, pattern: &Pattern2, interns: &Interns) -> ASTResult<String> {
+    let pattern_str = match pattern {
+        Pattern2::Identifier(symbol) => env
+            .ident_ids
+            .get_name_str_res(symbol.ident_id())?
+            .to_owned(),
+        Pattern2::NumLiteral(_, num) => num.to_string(),
+        Pattern2::IntLiteral(int_val) => match int_val {
+            IntVal::I64(num) => num.to_string(),
+            IntVal::U64(num) => num.to_string(),
+            IntVal::I32(num) => num.to_string(),
+            IntVal::U32(num) => num.to_string(),
+            IntVal::I16(num) => num.to_string(),
+            IntVal::U16(num) => num.to_string(),
+            IntVal::I8(num) => num.to_string(),
+            IntVal::U8(num) => num.to_string(),
+        },
+        // Debug keeps the `.0` of whole numbers, so they stay floats
+        Pattern2::FloatLiteral(float_val) => match float_val {
+            FloatVal::F64(num) => format!("{:?}", num),
+            FloatVal::F32(num) => format!("{:?}", num),
+        },
+        Pattern2::StrLiteral(text) => format!("\"{}\"", text.as_str(env.pool)),
+        Pattern2::CharacterLiteral(character) => format!("'{}'", character),
+        Pattern2::Underscore => "_".to_string(),
+        Pattern2::Tag {
+            tag_name,
+            arguments,
+            ..
+        } => {
+            let mut pattern_str = tag_name.as_str(env.pool).to_owned();
+
+            for (_, arg_id) in arguments.iter(env.pool) {
+                let arg_pattern = env.pool.get(*arg_id);
+                let arg_str = pattern2_to_string(env, arg_pattern, interns)?;
+
+                pattern_str.push(' ');
+
+                match arg_pattern {
+                    Pattern2::Tag { arguments, .. } if !arguments.is_empty() => {
+                        pattern_str.push_str(&format!("({})", arg_str));
+                    }
+                    _ => pattern_str.push_str(&arg_str),
+                }
+            }
+
+            pattern_str
+        }
+        Pattern2::RecordDestructure { destructs, .. } => {
+            if destructs.is_empty() {
+                return Ok("{}".to_string());
+            }
+
+            let fields = destructs
+                .iter(env.pool)
+                .map(|destruct| {
+                    let label = destruct.label.as_str(env.pool);
+
+                    match env.pool.get(destruct.typ) {
+                        DestructType::Required => Ok(label.to_owned()),
+                        DestructType::Optional(_, default_id) => Ok(format!(
+                            "{} ? {}",
+                            label,
+                            default_to_string(env, *default_id, interns)?
+                        )),
+                        DestructType::Guard(_, pattern_id) => Ok(format!(
+                            "{}: {}",
+                            label,
+                            pattern2_to_string(env, env.pool.get(*pattern_id), interns)?
+                        )),
+                    }
+                })
+                .collect::<ASTResult<Vec<String>>>()?;
+
+            format!("{}{}{}", LEFT_ACCOLADE, fields.join(COMMA), RIGHT_ACCOLADE)
+        }
+        Pattern2::Shadowed { shadowed_ident } => shadowed_ident.as_str(env.pool).to_owned(),
+        // like Expr2::RuntimeError, there is no source text left to show
+        Pattern2::UnsupportedPattern(_) | Pattern2::MalformedPattern(_, _) => {
+            "MalformedPattern".to_string()
+        }
+    };
+
+    Ok(pattern_str)
+}
+
+// the text of the default of an optional record field in a pattern
+fn default_to_string(env: &Env, expr_id: ExprId, interns: &Interns) -> ASTResult<String> {
+    let mut mark_node_pool = SlowPool::default();
+    let mut mark_id_ast_id_map = MarkIdAstIdMap::default();
+
+    let mark_node_id = expr2_to_markup(
+        env,
+        env.pool.get(expr_id),
+        expr_id,
+        &mut mark_node_pool,
+        &mut mark_id_ast_id_map,
+        interns,
+        0,
+    )?;
+
+    Ok(mark_nodes_to_string(&[mark_node_id], &mark_node_pool)
+        .trim()
+        .to_owned())
+}
+
 fn with_indent(indent_level: usize, some_str: &str) -> String {
     let full_indent = std::iter::repeat(" ").take(indent_level * 4);
     let mut full_string: String = full_indent.collect();
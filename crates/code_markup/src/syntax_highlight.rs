@@ -23,6 +23,8 @@ pub enum HighlightStyle {
     UppercaseIdent,
     LowercaseIdent, // TODO we probably don't want all lowercase identifiers to have the same color?
     Keyword,        // if, else, when...
+    Module,         // `Str` in `Str.concat`
+    Unresolved,     // a name that does not refer to anything in scope
 }
 
 pub fn default_highlight_map() -> HashMap<HighlightStyle, RgbaTup> {
@@ -50,6 +52,49 @@ pub fn default_highlight_map() -> HashMap<HighlightStyle, RgbaTup> {
         (UppercaseIdent, almost_white),
         (LowercaseIdent, from_hsb(225, 50, 100)),
         (Keyword, almost_white),
+        (Module, from_hsb(40, 60, 95)),
+        (Unresolved, from_hsb(0, 70, 95)),
+    ]
+    .iter()
+    .for_each(|tup| {
+        highlight_map.insert(tup.0, tup.1);
+    });
+
+    highlight_map
+}
+
+// Every style has a color that reaches a contrast ratio of at least 7:1 against a black background.
+pub fn high_contrast_highlight_map() -> HashMap<HighlightStyle, RgbaTup> {
+    use HighlightStyle::*;
+
+    let white = from_hsb(0, 0, 100);
+    let yellow = from_hsb(60, 100, 100);
+    let cyan = from_hsb(180, 100, 100);
+    let green = from_hsb(120, 60, 100);
+    let pink = from_hsb(320, 35, 100);
+
+    let mut highlight_map = HashMap::new();
+    [
+        (Operator, cyan),
+        (String, green),
+        (FunctionName, white),
+        (FunctionArgName, yellow),
+        (Type, white),
+        (Bracket, cyan),
+        (Number, yellow),
+        (PackageRelated, white),
+        (Value, white),
+        (RecordField, pink),
+        (Import, yellow),
+        (Provides, yellow),
+        (Blank, pink),
+        (Comment, pink),
+        (DocsComment, pink),
+        (UppercaseIdent, white),
+        (LowercaseIdent, yellow),
+        (Keyword, cyan),
+        (Module, green),
+        (Unresolved, from_hsb(0, 55, 100)),
     ]
     .iter()
     .for_each(|tup| {
@@ -18,3 +18,12 @@ pub fn default_underline_color_map() -> HashMap<UnderlineStyle, RgbaTup> {
 
     underline_colors
 }
+
+pub fn high_contrast_underline_color_map() -> HashMap<UnderlineStyle, RgbaTup> {
+    let mut underline_colors = HashMap::new();
+
+    underline_colors.insert(UnderlineStyle::Error, from_hsb(0, 70, 100));
+    underline_colors.insert(UnderlineStyle::Warning, from_hsb(60, 100, 100));
+
+    underline_colors
+}
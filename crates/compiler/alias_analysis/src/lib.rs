@@ -567,6 +567,7 @@ fn stmt_spec<'a>(
             cond_layout: _,
             branches,
             default_branch,
+            branch_hints: _,
             ret_layout: _lies,
         } => {
             let mut cases = Vec::with_capacity(branches.len() + 1);
@@ -1440,6 +1441,8 @@ fn expr_spec<'a>(
             }
         }
 
+        StaticArray { .. } => new_static_list(builder, block),
+
         EmptyArray => match interner.get(layout) {
             Layout::Builtin(Builtin::List(element_layout)) => {
                 let type_id = layout_spec(env, builder, interner, element_layout)?;
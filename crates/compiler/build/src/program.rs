@@ -3,17 +3,21 @@ use crate::link::{
 };
 use bumpalo::Bump;
 use inkwell::memory_buffer::MemoryBuffer;
+use roc_collections::all::MutMap;
 use roc_error_macros::internal_error;
 use roc_gen_llvm::llvm::build::{module_from_builtins, LlvmBackendMode};
 use roc_gen_llvm::llvm::externs::add_default_roc_externs;
 use roc_load::{
-    EntryPoint, ExecutionMode, ExpectMetadata, LoadConfig, LoadMonomorphizedError, LoadedModule,
-    LoadingProblem, MonomorphizedModule, Threading,
+    CancelToken, EntryPoint, ExecutionMode, ExpectMetadata, HotModule, LoadConfig,
+    LoadMonomorphizedError, LoadedModule, LoadingProblem, MonomorphizedModule, ProgressReporter,
+    SolveFuel, Threading,
 };
 use roc_mono::ir::{OptLevel, SingleEntryPoint};
 use roc_packaging::cache::RocCacheDir;
+use roc_problem::can::Problem;
+use roc_region::all::Loc;
 use roc_reporting::{
-    cli::{report_problems, Problems},
+    cli::{problem_reports, report_problems, Problems},
     report::{RenderTarget, DEFAULT_PALETTE},
 };
 use roc_target::TargetInfo;
@@ -41,6 +45,7 @@ pub fn report_problems_monomorphized(loaded: &mut MonomorphizedModule) -> Proble
         loaded.total_problems(),
         &loaded.sources,
         &loaded.interns,
+        &mut MutMap::default(),
         &mut loaded.can_problems,
         &mut loaded.type_problems,
     )
@@ -51,6 +56,20 @@ pub fn report_problems_typechecked(loaded: &mut LoadedModule) -> Problems {
         loaded.total_problems(),
         &loaded.sources,
         &loaded.interns,
+        &mut loaded.parse_problem_reports,
+        &mut loaded.can_problems,
+        &mut loaded.type_problems,
+    )
+}
+
+/// The reports of the problems as text without colors, for tools that show them somewhere else
+/// than in a terminal
+pub fn problem_reports_typechecked(loaded: &mut LoadedModule) -> (Problems, Vec<String>) {
+    problem_reports(
+        loaded.total_problems(),
+        &loaded.sources,
+        &loaded.interns,
+        &mut loaded.parse_problem_reports,
         &mut loaded.can_problems,
         &mut loaded.type_problems,
     )
@@ -84,6 +103,8 @@ pub struct CodeGenOptions {
     pub backend: CodeGenBackend,
     pub opt_level: OptLevel,
     pub emit_debug_info: bool,
+    /// Report a stack overflow once the Roc code of a native build uses this many bytes of stack
+    pub stack_bytes: Option<u32>,
 }
 
 type GenFromMono<'a> = (CodeObject, CodeGenTiming, ExpectMetadata<'a>);
@@ -101,6 +122,7 @@ pub fn gen_from_mono_module<'a>(
     let path = roc_file_path;
     let debug = code_gen_options.emit_debug_info;
     let opt = code_gen_options.opt_level;
+    let stack_bytes = code_gen_options.stack_bytes;
 
     match code_gen_options.backend {
         CodeGenBackend::Assembly => gen_from_mono_module_dev(
@@ -110,9 +132,16 @@ pub fn gen_from_mono_module<'a>(
             preprocessed_host_path,
             wasm_dev_stack_bytes,
         ),
-        CodeGenBackend::Llvm(backend_mode) => {
-            gen_from_mono_module_llvm(arena, loaded, path, target, opt, backend_mode, debug)
-        }
+        CodeGenBackend::Llvm(backend_mode) => gen_from_mono_module_llvm(
+            arena,
+            loaded,
+            path,
+            target,
+            opt,
+            backend_mode,
+            debug,
+            stack_bytes,
+        ),
         CodeGenBackend::Wasm => {
             // emit wasm via the llvm backend
 
@@ -121,7 +150,16 @@ pub fn gen_from_mono_module<'a>(
                 OptLevel::Normal | OptLevel::Size | OptLevel::Optimize => LlvmBackendMode::Binary,
             };
 
-            gen_from_mono_module_llvm(arena, loaded, path, target, opt, backend_mode, debug)
+            gen_from_mono_module_llvm(
+                arena,
+                loaded,
+                path,
+                target,
+                opt,
+                backend_mode,
+                debug,
+                stack_bytes,
+            )
         }
     }
 }
@@ -137,6 +175,7 @@ fn gen_from_mono_module_llvm<'a>(
     opt_level: OptLevel,
     backend_mode: LlvmBackendMode,
     emit_debug_info: bool,
+    stack_bytes: Option<u32>,
 ) -> GenFromMono<'a> {
     use crate::target::{self, convert_opt_level};
     use inkwell::attributes::{Attribute, AttributeLoc};
@@ -190,6 +229,13 @@ fn gen_from_mono_module_llvm<'a>(
     let (dibuilder, compile_unit) = roc_gen_llvm::llvm::build::Env::new_debug_info(module);
     let (mpm, _fpm) = roc_gen_llvm::llvm::build::construct_optimization_passes(module, opt_level);
 
+    // a failed expect only shows its source when verifying, and the sources aren't needed after
+    // code gen
+    let sources = match backend_mode {
+        LlvmBackendMode::BinaryVerify => Some(&*arena.alloc(std::mem::take(&mut loaded.sources))),
+        _ => None,
+    };
+
     // Compile and add all the Procs before adding main
     let env = roc_gen_llvm::llvm::build::Env {
         arena,
@@ -208,6 +254,8 @@ fn gen_from_mono_module_llvm<'a>(
             .keys()
             .copied()
             .collect(),
+        stack_bytes,
+        sources,
     };
 
     // does not add any externs for this mode (we have a host) but cleans up some functions around
@@ -718,6 +766,10 @@ pub fn standard_load_config(
         palette: DEFAULT_PALETTE,
         threading,
         exec_mode,
+        cancel: CancelToken::default(),
+        progress: ProgressReporter::default(),
+        hot_module: HotModule::default(),
+        solve_fuel: SolveFuel::default(),
     }
 }
 
@@ -868,6 +920,16 @@ fn build_loaded_file<'a>(
         }
     }
 
+    buf.push('\n');
+    buf.push_str("    ");
+    buf.push_str("Make Specializations");
+    buf.push('\n');
+
+    {
+        use std::fmt::Write;
+        write!(buf, "{}", loaded.mono_stats).unwrap();
+    }
+
     // This only needs to be mutable for report_problems. This can't be done
     // inside a nested scope without causing a borrow error!
     let mut loaded = loaded;
@@ -1159,6 +1221,9 @@ pub fn check_file<'a>(
     arena: &'a Bump,
     roc_file_path: PathBuf,
     emit_timings: bool,
+    report_unused: bool,
+    emit_can: bool,
+    call_graph_path: Option<&Path>,
     roc_cache_dir: RocCacheDir<'_>,
     threading: Threading,
 ) -> Result<(Problems, Duration), LoadingProblem<'a>> {
@@ -1177,6 +1242,10 @@ pub fn check_file<'a>(
         palette: DEFAULT_PALETTE,
         threading,
         exec_mode: ExecutionMode::Check,
+        cancel: CancelToken::default(),
+        progress: ProgressReporter::default(),
+        hot_module: HotModule::default(),
+        solve_fuel: SolveFuel::default(),
     };
     let mut loaded =
         roc_load::load_and_typecheck(arena, roc_file_path, roc_cache_dir, load_config)?;
@@ -1224,6 +1293,33 @@ pub fn check_file<'a>(
         println!("Finished checking in {} ms\n", compilation_end.as_millis(),);
     }
 
+    if report_unused {
+        for Loc {
+            value: symbol,
+            region,
+        } in loaded.unused_exposed_values()
+        {
+            loaded
+                .can_problems
+                .entry(symbol.module_id())
+                .or_default()
+                .push(Problem::UnusedExposedValue(symbol, region));
+        }
+    }
+
+    if emit_can {
+        print!("{}", loaded.pretty_print_can());
+    }
+
+    if let Some(call_graph_path) = call_graph_path {
+        let json = loaded.call_graph().to_json(&loaded.interns);
+
+        std::fs::write(call_graph_path, json).map_err(|error| LoadingProblem::FileProblem {
+            filename: call_graph_path.to_path_buf(),
+            error: error.kind(),
+        })?;
+    }
+
     Ok((report_problems_typechecked(&mut loaded), compilation_end))
 }
 
@@ -1239,6 +1335,7 @@ pub fn build_str_test<'a>(
         backend: CodeGenBackend::Llvm(LlvmBackendMode::Binary),
         opt_level: OptLevel::Normal,
         emit_debug_info: false,
+        stack_bytes: None,
     };
 
     let emit_timings = false;
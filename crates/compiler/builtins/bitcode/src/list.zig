@@ -138,15 +138,15 @@ pub const RocList = extern struct {
         return self.refcountMachine() - utils.REFCOUNT_ONE + 1;
     }
 
-    pub fn makeUniqueExtra(self: RocList, alignment: u32, element_width: usize, update_mode: UpdateMode) RocList {
+    pub fn makeUniqueExtra(self: RocList, alignment: u32, element_width: usize, inc_element: ?IncN, update_mode: UpdateMode) RocList {
         if (update_mode == .InPlace) {
             return self;
         } else {
-            return self.makeUnique(alignment, element_width);
+            return self.makeUnique(alignment, element_width, inc_element);
         }
     }
 
-    pub fn makeUnique(self: RocList, alignment: u32, element_width: usize) RocList {
+    pub fn makeUnique(self: RocList, alignment: u32, element_width: usize, inc_element: ?IncN) RocList {
         if (self.isUnique()) {
             return self;
         }
@@ -167,12 +167,25 @@ pub const RocList = extern struct {
         const number_of_bytes = self.len() * element_width;
         @memcpy(new_bytes, old_bytes, number_of_bytes);
 
-        // NOTE we fuse an increment of all keys/values with a decrement of the input list.
+        // the input list is shared, so the copy is a new owner of its elements
+        self.incrementElements(element_width, inc_element);
         self.decref(alignment);
 
         return new_list;
     }
 
+    /// `inc_element` is null when the elements are not refcounted, then copying their bytes is enough.
+    fn incrementElements(self: RocList, element_width: usize, inc_element: ?IncN) void {
+        if (inc_element) |inc| {
+            if (self.bytes) |source| {
+                var i: usize = 0;
+                while (i < self.len()) : (i += 1) {
+                    inc(source + i * element_width, 1);
+                }
+            }
+        }
+    }
+
     pub fn allocate(
         alignment: u32,
         length: usize,
@@ -213,6 +226,7 @@ pub const RocList = extern struct {
         alignment: u32,
         new_length: usize,
         element_width: usize,
+        inc_element: ?IncN,
     ) RocList {
         if (self.bytes) |source_ptr| {
             if (self.isUnique() and !self.isSeamlessSlice()) {
@@ -225,7 +239,7 @@ pub const RocList = extern struct {
                     return RocList{ .bytes = new_source, .length = new_length, .capacity_or_ref_ptr = new_capacity };
                 }
             }
-            return self.reallocateFresh(alignment, new_length, element_width);
+            return self.reallocateFresh(alignment, new_length, element_width, inc_element);
         }
         return RocList.allocate(alignment, new_length, element_width);
     }
@@ -236,6 +250,7 @@ pub const RocList = extern struct {
         alignment: u32,
         new_length: usize,
         element_width: usize,
+        inc_element: ?IncN,
     ) RocList {
         const old_length = self.length;
         const delta_length = new_length - old_length;
@@ -250,6 +265,9 @@ pub const RocList = extern struct {
             @memset(dest_ptr + old_length * element_width, 0, delta_length * element_width);
         }
 
+        if (!self.isUnique()) {
+            self.incrementElements(element_width, inc_element);
+        }
         self.decref(alignment);
 
         return result;
@@ -474,7 +492,7 @@ pub fn listWithCapacity(
     alignment: u32,
     element_width: usize,
 ) callconv(.C) RocList {
-    return listReserve(RocList.empty(), alignment, capacity, element_width, .InPlace);
+    return listReserve(RocList.empty(), alignment, capacity, element_width, null, .InPlace);
 }
 
 pub fn listReserve(
@@ -482,13 +500,14 @@ pub fn listReserve(
     alignment: u32,
     spare: usize,
     element_width: usize,
+    inc_element: ?IncN,
     update_mode: UpdateMode,
 ) callconv(.C) RocList {
     const old_length = list.len();
     if ((update_mode == .InPlace or list.isUnique()) and list.getCapacity() >= list.len() + spare) {
         return list;
     } else {
-        var output = list.reallocate(alignment, old_length + spare, element_width);
+        var output = list.reallocate(alignment, old_length + spare, element_width, inc_element);
         output.length = old_length;
         return output;
     }
@@ -498,6 +517,7 @@ pub fn listReleaseExcessCapacity(
     list: RocList,
     alignment: u32,
     element_width: usize,
+    inc_element: ?IncN,
     update_mode: UpdateMode,
 ) callconv(.C) RocList {
     const old_length = list.len();
@@ -514,6 +534,9 @@ pub fn listReleaseExcessCapacity(
 
             @memcpy(dest_ptr, source_ptr, old_length * element_width);
         }
+        if (!list.isUnique()) {
+            list.incrementElements(element_width, inc_element);
+        }
         list.decref(alignment);
         return output;
     }
@@ -537,15 +560,15 @@ pub fn listAppendUnsafe(
     return output;
 }
 
-fn listAppend(list: RocList, alignment: u32, element: Opaque, element_width: usize, update_mode: UpdateMode) callconv(.C) RocList {
-    const with_capacity = listReserve(list, alignment, 1, element_width, update_mode);
+fn listAppend(list: RocList, alignment: u32, element: Opaque, element_width: usize, inc_element: ?IncN, update_mode: UpdateMode) callconv(.C) RocList {
+    const with_capacity = listReserve(list, alignment, 1, element_width, inc_element, update_mode);
     return listAppendUnsafe(with_capacity, element, element_width);
 }
 
-pub fn listPrepend(list: RocList, alignment: u32, element: Opaque, element_width: usize) callconv(.C) RocList {
+pub fn listPrepend(list: RocList, alignment: u32, element: Opaque, element_width: usize, inc_element: ?IncN) callconv(.C) RocList {
     const old_length = list.len();
     // TODO: properly wire in update mode.
-    var with_capacity = listReserve(list, alignment, 1, element_width, .Immutable);
+    var with_capacity = listReserve(list, alignment, 1, element_width, inc_element, .Immutable);
     with_capacity.length += 1;
 
     // can't use one memcpy here because source and target overlap
@@ -574,6 +597,7 @@ pub fn listSwap(
     element_width: usize,
     index_1: usize,
     index_2: usize,
+    inc_element: ?IncN,
     update_mode: UpdateMode,
 ) callconv(.C) RocList {
     const size = list.len();
@@ -586,7 +610,7 @@ pub fn listSwap(
         if (update_mode == .InPlace) {
             break :blk list;
         } else {
-            break :blk list.makeUnique(alignment, element_width);
+            break :blk list.makeUnique(alignment, element_width, inc_element);
         }
     };
 
@@ -774,8 +798,9 @@ pub fn listSortWith(
     data_is_owned: bool,
     alignment: u32,
     element_width: usize,
+    inc_element: ?IncN,
 ) callconv(.C) RocList {
-    var list = input.makeUnique(alignment, element_width);
+    var list = input.makeUnique(alignment, element_width, inc_element);
 
     if (data_is_owned) {
         inc_n_data(data, list.len());
@@ -831,7 +856,7 @@ fn swapElements(source_ptr: [*]u8, element_width: usize, index_1: usize, index_2
     return swap(element_width, element_at_i, element_at_j);
 }
 
-pub fn listConcat(list_a: RocList, list_b: RocList, alignment: u32, element_width: usize) callconv(.C) RocList {
+pub fn listConcat(list_a: RocList, list_b: RocList, alignment: u32, element_width: usize, inc_element: ?IncN) callconv(.C) RocList {
     // NOTE we always use list_a! because it is owned, we must consume it, and it may have unused capacity
     if (list_b.isEmpty()) {
         if (list_a.getCapacity() == 0) {
@@ -847,21 +872,24 @@ pub fn listConcat(list_a: RocList, list_b: RocList, alignment: u32, element_widt
     } else if (list_a.isUnique()) {
         const total_length: usize = list_a.len() + list_b.len();
 
-        const resized_list_a = list_a.reallocate(alignment, total_length, element_width);
+        const resized_list_a = list_a.reallocate(alignment, total_length, element_width, inc_element);
 
         // These must exist, otherwise, the lists would have been empty.
         const source_a = resized_list_a.bytes orelse unreachable;
         const source_b = list_b.bytes orelse unreachable;
         @memcpy(source_a + list_a.len() * element_width, source_b, list_b.len() * element_width);
 
-        // decrement list b.
+        // decrement list b. If it is shared, list a is a new owner of its elements.
+        if (!list_b.isUnique()) {
+            list_b.incrementElements(element_width, inc_element);
+        }
         list_b.decref(alignment);
 
         return resized_list_a;
     } else if (list_b.isUnique()) {
         const total_length: usize = list_a.len() + list_b.len();
 
-        const resized_list_b = list_b.reallocate(alignment, total_length, element_width);
+        const resized_list_b = list_b.reallocate(alignment, total_length, element_width, inc_element);
 
         // These must exist, otherwise, the lists would have been empty.
         const source_a = list_a.bytes orelse unreachable;
@@ -875,7 +903,8 @@ pub fn listConcat(list_a: RocList, list_b: RocList, alignment: u32, element_widt
         mem.copyBackwards(u8, source_b[byte_count_a .. byte_count_a + byte_count_b], source_b[0..byte_count_b]);
         @memcpy(source_b, source_a, byte_count_a);
 
-        // decrement list a.
+        // decrement list a, which is shared, so list b is a new owner of its elements.
+        list_a.incrementElements(element_width, inc_element);
         list_a.decref(alignment);
 
         return resized_list_b;
@@ -892,7 +921,9 @@ pub fn listConcat(list_a: RocList, list_b: RocList, alignment: u32, element_widt
     @memcpy(target, source_a, list_a.len() * element_width);
     @memcpy(target + list_a.len() * element_width, source_b, list_b.len() * element_width);
 
-    // decrement list a and b.
+    // decrement list a and b. Both are shared, so the output is a new owner of their elements.
+    list_a.incrementElements(element_width, inc_element);
+    list_b.incrementElements(element_width, inc_element);
     list_a.decref(alignment);
     list_b.decref(alignment);
 
@@ -922,6 +953,7 @@ pub fn listReplace(
     element: Opaque,
     element_width: usize,
     out_element: ?[*]u8,
+    inc_element: ?IncN,
 ) callconv(.C) RocList {
     // INVARIANT: bounds checking happens on the roc side
     //
@@ -929,7 +961,7 @@ pub fn listReplace(
     // `if inBounds then LowLevelListReplace input index item else input`
     // so we don't do a bounds check here. Hence, the list is also non-empty,
     // because inserting into an empty list is always out of bounds
-    return listReplaceInPlaceHelp(list.makeUnique(alignment, element_width), index, element, element_width, out_element);
+    return listReplaceInPlaceHelp(list.makeUnique(alignment, element_width, inc_element), index, element, element_width, out_element);
 }
 
 inline fn listReplaceInPlaceHelp(
@@ -980,7 +1012,7 @@ test "listConcat: non-unique with unique overlapping" {
     var unique = RocList.fromSlice(u8, ([_]u8{ 2, 3, 4 })[0..]);
     defer unique.decref(@sizeOf(u8));
 
-    var concatted = listConcat(nonUnique, unique, 1, 1);
+    var concatted = listConcat(nonUnique, unique, 1, 1, null);
     var wanted = RocList.fromSlice(u8, ([_]u8{ 1, 2, 3, 4 })[0..]);
     defer wanted.decref(@sizeOf(u8));
 
@@ -1,6 +1,6 @@
 interface Box
     exposes [box, unbox]
-    imports []
+    imports [Bool.{ Bool, Eq }]
 
 ## Allocate a value on the heap. Boxing is an expensive process as it copies
 ## the value from the stack to the heap. This may provide a performance
@@ -17,6 +17,11 @@ box : a -> Box a
 ## ```
 unbox : Box a -> a
 
+# INTERNAL COMPILER USE ONLY: used by derived `isEq` implementations to compare
+# boxes whose contents may have a custom `isEq`, by calling `isEq` on the contents.
+isEq : Box a, Box a -> Bool | a has Eq
+isEq = \a, b -> unbox a == unbox b
+
 # # we'd need reset/reuse for box for this to be efficient
 # # that is currently not implemented
 # map : Box a, (a -> b) -> Box b
@@ -11,6 +11,7 @@ interface Dict
         contains,
         insert,
         remove,
+        removeOrdered,
         update,
         walk,
         walkUntil,
@@ -82,6 +83,15 @@ interface Dict
 ## This move is done as a performance optimization, and it lets [remove] have
 ## [constant time complexity](https://en.wikipedia.org/wiki/Time_complexity#Constant_time). ##
 ##
+## If the order matters, for example because the output of [Dict.walk] or
+## [Dict.toList] ends up in user-visible output, use [Dict.removeOrdered] instead.
+## It keeps the remaining entries in insertion order, at the cost of taking
+## time proportional to the size of the dictionary.
+##
+## The iteration order never depends on the hash of the keys, so a dictionary
+## built by the same sequence of operations is always walked in the same order,
+## across runs and across platforms.
+##
 ## Dict is inspired by [IndexMap](https://docs.rs/indexmap/latest/indexmap/map/struct.IndexMap.html).
 ## The internal implementation of a dictionary is similar to [absl::flat_hash_map](https://abseil.io/docs/cpp/guides/container).
 ## It has a list of keys value pairs that is ordered based on insertion.
@@ -387,6 +397,52 @@ remove = \@Dict { metadata, dataIndices, data, size }, key ->
         Err NotFound ->
             @Dict { metadata, dataIndices, data, size }
 
+## Remove a value from the dictionary for a specified key, keeping the
+## remaining entries in insertion order.
+##
+## Unlike [Dict.remove], which moves the most recently inserted entry into the
+## vacated spot, this shifts all later entries down by one. That makes it slower
+## for large dictionaries, but the order seen by [Dict.walk], [Dict.keys] and
+## friends stays exactly the order in which keys were inserted.
+## ```
+## expect
+##     Dict.empty {}
+##     |> Dict.insert "London" 8_961_989
+##     |> Dict.insert "Philadelphia" 1_603_797
+##     |> Dict.insert "Shanghai" 24_870_895
+##     |> Dict.removeOrdered "Philadelphia"
+##     |> Dict.keys
+##     |> Bool.isEq ["London", "Shanghai"]
+## ```
+removeOrdered : Dict k v, k -> Dict k v | k has Hash & Eq
+removeOrdered = \@Dict { metadata, dataIndices, data, size }, key ->
+    hashKey =
+        createLowLevelHasher {}
+        |> Hash.hash key
+        |> complete
+    h1Key = h1 hashKey
+    h2Key = h2 hashKey
+    probe = newProbe h1Key (div8 (List.len metadata))
+
+    when findIndexHelper metadata dataIndices data h2Key key probe 0 is
+        Ok index ->
+            dataIndex = listGetUnsafe dataIndices index
+
+            # Every entry after the removed one moves down a spot in `data`,
+            # so the indices pointing at them have to move down as well.
+            nextDataIndices =
+                List.map dataIndices \i -> if i > dataIndex then i - 1 else i
+
+            @Dict {
+                metadata: List.set metadata index deletedSlot,
+                dataIndices: nextDataIndices,
+                data: List.dropAt data dataIndex,
+                size: size - 1,
+            }
+
+        Err NotFound ->
+            @Dict { metadata, dataIndices, data, size }
+
 ## Insert or remove a value for a specified key. This function enables a
 ## performance optimisation for the use case of providing a default when a value
 ## is missing. This is more efficient than doing both a `Dict.get` and then a
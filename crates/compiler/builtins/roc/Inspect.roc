@@ -0,0 +1,85 @@
+interface Inspect
+    exposes [
+        Inspect,
+        inspect,
+        inspectBool,
+        inspectNum,
+        inspectStr,
+        inspectList,
+        append,
+        parenthesize,
+    ] imports [
+        Bool.{ Bool },
+        List,
+        Str,
+        Num.{ Num },
+    ]
+
+## A value that can be converted to a diagnostic [Str].
+Inspect has
+    ## Converts a value to a [Str] describing it, for debugging and logging.
+    ##
+    ## The representation is meant for people rather than programs, and is not
+    ## guaranteed to be stable across compiler versions. For example,
+    ## ```
+    ## Inspect.inspect { name: "Roc", tags: [Fast, Friendly] }
+    ## ```
+    ## returns `{ name: "Roc", tags: [Fast, Friendly] }`.
+    inspect : val -> Str | val has Inspect
+
+## Describes a [Bool] as `Bool.true` or `Bool.false`.
+inspectBool : Bool -> Str
+inspectBool = \b ->
+    if b then "Bool.true" else "Bool.false"
+
+## Describes a number the way [Num.toStr] does.
+inspectNum : Num * -> Str
+inspectNum = \n -> Num.toStr n
+
+## Describes a [Str] as a Roc string literal, with quotes and backslashes escaped.
+inspectStr : Str -> Str
+inspectStr = \str ->
+    escaped =
+        str
+        |> replaceEach "\\" "\\\\"
+        |> replaceEach "\"" "\\\""
+
+    "\"" |> Str.concat escaped |> Str.concat "\""
+
+replaceEach : Str, Str, Str -> Str
+replaceEach = \str, needle, replacement ->
+    when Str.replaceEach str needle replacement is
+        Ok replaced -> replaced
+        Err NotFound -> str
+
+## Describes a list of [Inspect]able elements, like `[1, 2, 3]`.
+inspectList : List a -> Str | a has Inspect
+inspectList = \list ->
+    elems =
+        list
+        |> List.map inspect
+        |> Str.joinWith ", "
+
+    "[" |> Str.concat elems |> Str.concat "]"
+
+## Appends one description to another. Derived implementations of [inspect]
+## use this to build up the description of records, tuples and tags.
+append : Str, Str -> Str
+append = \description, suffix -> Str.concat description suffix
+
+## Wraps the description of a tag or opaque payload in parentheses if it would
+## otherwise be ambiguous, like the `Some 1` in `Ok (Some 1)`.
+parenthesize : Str -> Str
+parenthesize = \str ->
+    isDelimited =
+        Str.startsWith str "{"
+        || Str.startsWith str "["
+        || Str.startsWith str "("
+        || Str.startsWith str "\""
+
+    when Str.splitFirst str " " is
+        Ok _ if !isDelimited ->
+            "(" |> Str.concat str |> Str.concat ")"
+
+        _ ->
+            str
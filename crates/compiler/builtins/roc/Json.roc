@@ -31,6 +31,17 @@
 ## expect name == Ok (Str.toUtf8 "\"Röc Lang\"")
 ## ```
 ##
+## When JSON is the only format involved, [toStr] and [fromStr] do the same
+## without naming the format, and decoding errors say where in the input they
+## happened:
+##
+## ```
+## result : Result Language _
+## result = Json.fromStr "{\"name\":42}"
+##
+## expect result == Err (TooShort { offset: 8 })
+## ```
+##
 ## **Note:** This module is likely to be moved out of the builtins in future.
 ## It is currently located here to facilitate development of the Abilities
 ## language feature and testing. You are welcome to use this module, just note
@@ -38,8 +49,13 @@
 interface Json
     exposes [
         Json,
+        DecodeProblem,
         toUtf8,
         fromUtf8,
+        toBytes,
+        toStr,
+        fromBytes,
+        fromStr,
     ]
     imports [
         List,
@@ -47,12 +63,14 @@ interface Json
         Result.{ Result },
         Encode,
         Encode.{
+            Encoding,
             Encoder,
             EncoderFormatting,
             appendWith,
         },
         Decode,
         Decode.{
+            Decoding,
             DecoderFormatting,
             DecodeResult,
         },
@@ -67,6 +85,7 @@ interface Json
             I32,
             I64,
             I128,
+            Nat,
             F32,
             F64,
             Dec,
@@ -127,6 +146,44 @@ toUtf8 = @Json {}
 ## Returns a JSON `Encoder`
 fromUtf8 = @Json {}
 
+## Encodes a value as JSON, returning the UTF-8 bytes of the JSON text.
+toBytes : val -> List U8 | val has Encoding
+toBytes = \val -> Encode.toBytes val toUtf8
+
+## Encodes a value as JSON text.
+toStr : val -> Str | val has Encoding
+toStr = \val ->
+    when Str.fromUtf8 (toBytes val) is
+        Ok str -> str
+        Err _ -> crash "Json.toStr: a custom encoder produced bytes that are not valid UTF-8"
+
+## The ways decoding with [fromBytes] or [fromStr] can fail. `offset` is the
+## number of bytes into the input at which decoding stopped.
+DecodeProblem : [TooShort { offset : Nat }, Leftover { offset : Nat }]
+
+## Decodes a value from the UTF-8 bytes of JSON text. Whitespace around the
+## value is ignored; anything else after it is reported as `Leftover`.
+fromBytes : List U8 -> Result val DecodeProblem | val has Decoding
+fromBytes = \bytes ->
+    offsetOf = \rest -> List.len bytes - List.len rest
+
+    when Decode.fromBytesPartial (eatWhitespace bytes) fromUtf8 is
+        { result, rest } ->
+            when result is
+                Ok val ->
+                    trailing = eatWhitespace rest
+
+                    if List.isEmpty trailing then
+                        Ok val
+                    else
+                        Err (Leftover { offset: offsetOf trailing })
+
+                Err TooShort -> Err (TooShort { offset: offsetOf rest })
+
+## Decodes a value from JSON text, like [fromBytes].
+fromStr : Str -> Result val DecodeProblem | val has Decoding
+fromStr = \str -> fromBytes (Str.toUtf8 str)
+
 numToBytes = \n ->
     n |> Num.toStr |> Str.toUtf8
 
@@ -166,9 +223,37 @@ encodeBool = \b -> Encode.custom \bytes, @Json {} ->
 
 encodeString = \s -> Encode.custom \bytes, @Json {} ->
         List.append bytes (Num.toU8 '"')
-        |> List.concat (Str.toUtf8 s)
+        |> appendEscaped (Str.toUtf8 s)
         |> List.append (Num.toU8 '"')
 
+# Appends the bytes of a string, escaping the characters JSON does not allow
+# inside strings. Most strings contain none of them, so we check for that first
+# and append those in one go.
+appendEscaped : List U8, List U8 -> List U8
+appendEscaped = \bytes, unescaped ->
+    if List.any unescaped needsEscape then
+        List.walk unescaped bytes \buffer, byte ->
+            when byte is
+                '"' -> List.concat buffer ['\\', '"']
+                '\\' -> List.concat buffer ['\\', '\\']
+                0x08 -> List.concat buffer ['\\', 'b']
+                0x0C -> List.concat buffer ['\\', 'f']
+                '\n' -> List.concat buffer ['\\', 'n']
+                '\r' -> List.concat buffer ['\\', 'r']
+                '\t' -> List.concat buffer ['\\', 't']
+                _ if byte < 0x20 ->
+                    List.concat buffer ['\\', 'u', '0', '0', hexDigit (byte // 16), hexDigit (byte % 16)]
+
+                _ -> List.append buffer byte
+    else
+        List.concat bytes unescaped
+
+needsEscape : U8 -> Bool
+needsEscape = \byte -> byte == '"' || byte == '\\' || byte < 0x20
+
+hexDigit : U8 -> U8
+hexDigit = \n -> if n < 10 then '0' + n else 'a' + n - 10
+
 encodeList = \lst, encodeElem ->
     Encode.custom \bytes, @Json {} ->
         writeList = \{ buffer, elemsLeft }, elem ->
@@ -432,16 +517,137 @@ jsonString = \bytes ->
     then
         { taken: strSequence, rest } = takeWhile afterStartingQuote \n -> n != '"'
 
-        when Str.fromUtf8 strSequence is
-            Ok s ->
-                { others: afterEndingQuote } = List.split rest 1
-
-                { result: Ok s, rest: afterEndingQuote }
+        when rest is
+            ['"', ..] ->
+                when unescape strSequence is
+                    Ok s -> { result: Ok s, rest: List.dropFirst rest }
+                    Err InvalidString -> { result: Err TooShort, rest: bytes }
 
-            Err _ -> { result: Err TooShort, rest }
+            _ ->
+                # The string is never closed
+                { result: Err TooShort, rest }
     else
         { result: Err TooShort, rest: bytes }
 
+# Replaces the escape sequences in the contents of a JSON string with the
+# characters they stand for.
+unescape : List U8 -> Result Str [InvalidString]
+unescape = \escaped ->
+    unescaped =
+        if List.contains escaped '\\' then
+            unescapeHelp [] escaped
+        else
+            Ok escaped
+
+    when unescaped is
+        Ok bytes ->
+            when Str.fromUtf8 bytes is
+                Ok s -> Ok s
+                Err _ -> Err InvalidString
+
+        Err InvalidString -> Err InvalidString
+
+unescapeHelp : List U8, List U8 -> Result (List U8) [InvalidString]
+unescapeHelp = \unescaped, rest ->
+    when rest is
+        [] -> Ok unescaped
+        ['\\', 'u', ..] ->
+            when unicodeEscape rest is
+                Ok { codePoint, length } -> unescapeHelp (appendUtf8 unescaped codePoint) (List.drop rest length)
+                Err InvalidString -> Err InvalidString
+
+        ['\\', escape, ..] ->
+            when escapedByte escape is
+                Ok byte -> unescapeHelp (List.append unescaped byte) (List.drop rest 2)
+                Err InvalidString -> Err InvalidString
+
+        [byte, ..] ->
+            unescapeHelp (List.append unescaped byte) (List.dropFirst rest)
+
+escapedByte : U8 -> Result U8 [InvalidString]
+escapedByte = \escape ->
+    when escape is
+        '"' -> Ok '"'
+        '\\' -> Ok '\\'
+        '/' -> Ok '/'
+        'b' -> Ok 0x08
+        'f' -> Ok 0x0C
+        'n' -> Ok '\n'
+        'r' -> Ok '\r'
+        't' -> Ok '\t'
+        _ -> Err InvalidString
+
+# Reads the code point of the `\u` escape at the start of `bytes`, and how many
+# bytes the escape takes up.
+unicodeEscape : List U8 -> Result { codePoint : U32, length : Nat } [InvalidString]
+unicodeEscape = \bytes ->
+    when hexDigitsAt bytes 2 4 0 is
+        Ok high if isHighSurrogate high ->
+            # Code points outside the Basic Multilingual Plane are escaped as a
+            # UTF-16 surrogate pair, like `\ud83d\ude00`.
+            when hexDigitsAt bytes 8 4 0 is
+                Ok low if isLowSurrogate low && List.sublist bytes { start: 6, len: 2 } == ['\\', 'u'] ->
+                    Ok { codePoint: 0x10000 + (high - 0xD800) * 0x400 + (low - 0xDC00), length: 12 }
+
+                _ -> Err InvalidString
+
+        Ok codePoint if !(isLowSurrogate codePoint) ->
+            Ok { codePoint, length: 6 }
+
+        _ -> Err InvalidString
+
+isHighSurrogate : U32 -> Bool
+isHighSurrogate = \n -> n >= 0xD800 && n <= 0xDBFF
+
+isLowSurrogate : U32 -> Bool
+isLowSurrogate = \n -> n >= 0xDC00 && n <= 0xDFFF
+
+# Reads `count` hex digits starting at `index`, adding them to `value`.
+hexDigitsAt : List U8, Nat, Nat, U32 -> Result U32 [InvalidString]
+hexDigitsAt = \bytes, index, count, value ->
+    if count == 0 then
+        Ok value
+    else
+        # 16 stands for a byte that is not a hex digit
+        digit =
+            when List.get bytes index is
+                Ok byte if byte >= '0' && byte <= '9' -> Num.toU32 (byte - '0')
+                Ok byte if byte >= 'a' && byte <= 'f' -> Num.toU32 (byte - 'a' + 10)
+                Ok byte if byte >= 'A' && byte <= 'F' -> Num.toU32 (byte - 'A' + 10)
+                _ -> 16
+
+        if digit < 16 then
+            hexDigitsAt bytes (index + 1) (count - 1) (value * 16 + digit)
+        else
+            Err InvalidString
+
+appendUtf8 : List U8, U32 -> List U8
+appendUtf8 = \bytes, codePoint ->
+    if codePoint < 0x80 then
+        List.append bytes (Num.toU8 codePoint)
+    else if codePoint < 0x800 then
+        List.concat bytes [
+            Num.toU8 (Num.bitwiseOr 0xC0 (Num.shiftRightZfBy codePoint 6)),
+            continuationByte codePoint 0,
+        ]
+    else if codePoint < 0x10000 then
+        List.concat bytes [
+            Num.toU8 (Num.bitwiseOr 0xE0 (Num.shiftRightZfBy codePoint 12)),
+            continuationByte codePoint 6,
+            continuationByte codePoint 0,
+        ]
+    else
+        List.concat bytes [
+            Num.toU8 (Num.bitwiseOr 0xF0 (Num.shiftRightZfBy codePoint 18)),
+            continuationByte codePoint 12,
+            continuationByte codePoint 6,
+            continuationByte codePoint 0,
+        ]
+
+continuationByte : U32, U8 -> U8
+continuationByte = \codePoint, shift ->
+    Num.toU8 (Num.bitwiseOr 0x80 (Num.bitwiseAnd 0x3F (Num.shiftRightZfBy codePoint shift)))
+
 decodeString = Decode.custom \bytes, @Json {} ->
     jsonString bytes
 
@@ -604,19 +810,55 @@ expect
 # Test json string decoding with escapes
 expect
     input = Str.toUtf8 "\"a\r\nbc\\\"xz\""
-    expected = Ok "a\r\nbc\\\"xz"
+    expected = Ok "a\r\nbc\"xz"
     actual = Decode.fromBytes input fromUtf8
 
     actual == expected
 
 # Test json string encoding with escapes
 expect
-    input = "a\r\nbc\\\"xz"
-    expected = Str.toUtf8 "\"a\r\nbc\\\"xz\""
+    input = "a\r\nbc\\\"xz\u(01)"
+    expected = Str.toUtf8 "\"a\\r\\nbc\\\\\\\"xz\\u0001\""
     actual = Encode.toBytes input toUtf8
 
     actual == expected
 
+# Test json string decoding of unicode escapes
+expect
+    input = Str.toUtf8 "\"\\u00e9\\ud83d\\ude00\""
+    expected = Ok "é😀"
+    actual = Decode.fromBytes input fromUtf8
+
+    actual == expected
+
+# Test json string decoding rejects unknown escapes
+expect
+    actual : Result Str _
+    actual = fromStr "\"\\x\""
+
+    actual == Err (TooShort { offset: 0 })
+
+# Test encoding to a string
+expect
+    actual = toStr ["a\"b", "\t"]
+    expected = "[\"a\\\"b\",\"\\t\"]"
+
+    actual == expected
+
+# Test decoding reports the offset of the failure
+expect
+    actual : Result (List U8) _
+    actual = fromStr "[1, 2, x]"
+
+    actual == Err (TooShort { offset: 7 })
+
+# Test decoding ignores surrounding whitespace but not other leftovers
+expect
+    actual : Result Str _
+    actual = fromStr " \"a\" x"
+
+    actual == Err (Leftover { offset: 5 })
+
 # Test json array decode empty list
 expect
     input = Str.toUtf8 "[ ]"
@@ -308,9 +308,14 @@ concat : List a, List a -> List a
 ## Returns the last element in the list, or `ListWasEmpty` if it was empty.
 last : List a -> Result a [ListWasEmpty]
 last = \list ->
-    when List.get list (Num.subSaturated (List.len list) 1) is
-        Ok v -> Ok v
-        Err _ -> Err ListWasEmpty
+    length = List.len list
+
+    # read the element directly, rather than re-wrapping the result of `List.get`,
+    # so the (possibly large) element is not copied out of one Result into another
+    if length > 0 then
+        Ok (List.getUnsafe list (length - 1))
+    else
+        Err ListWasEmpty
 
 ## A list with a single element in it.
 ##
@@ -817,9 +822,11 @@ swap : List a, Nat, Nat -> List a
 ## Returns the first element in the list, or `ListWasEmpty` if it was empty.
 first : List a -> Result a [ListWasEmpty]
 first = \list ->
-    when List.get list 0 is
-        Ok v -> Ok v
-        Err _ -> Err ListWasEmpty
+    # like `last`, read the element directly instead of going through `List.get`
+    if List.len list > 0 then
+        Ok (List.getUnsafe list 0)
+    else
+        Err ListWasEmpty
 
 ## Remove the first element from the list.
 ##
@@ -1057,6 +1064,29 @@ endsWith = \list, suffix ->
 
     suffix == List.sublist list { start, len: length }
 
+# INTERNAL COMPILER USE ONLY: used by derived `isEq` implementations to compare
+# lists whose elements may have a custom `isEq`, by calling `isEq` on each pair of
+# elements. Use `==` instead, which resolves to this when it is needed.
+isEq : List a, List a -> Bool | a has Eq
+isEq = \xs, ys ->
+    length = List.len xs
+
+    if length == List.len ys then
+        isEqHelp xs ys 0 length
+    else
+        Bool.false
+
+## internal helper
+isEqHelp : List a, List a, Nat, Nat -> Bool | a has Eq
+isEqHelp = \xs, ys, index, length ->
+    if index < length then
+        if List.getUnsafe xs index == List.getUnsafe ys index then
+            isEqHelp xs ys (index + 1) length
+        else
+            Bool.false
+    else
+        Bool.true
+
 ## Splits the list into two lists, around the given index.
 ##
 ## The returned lists are labeled `before` and `others`. The `before` list will
@@ -8,6 +8,7 @@ interface Set
         insert,
         len,
         remove,
+        removeOrdered,
         contains,
         toList,
         fromList,
@@ -146,6 +147,20 @@ remove : Set k, k -> Set k | k has Hash & Eq
 remove = \@Set dict, key ->
     Dict.remove dict key |> @Set
 
+## Removes the value from the given `Set`, keeping the remaining values in
+## insertion order. See [Dict.removeOrdered] for the trade-off compared to
+## [Set.remove].
+## ```
+## expect
+##     Set.fromList [1, 2, 3]
+##     |> Set.removeOrdered 1
+##     |> Set.toList
+##     |> Bool.isEq [2, 3]
+## ```
+removeOrdered : Set k, k -> Set k | k has Hash & Eq
+removeOrdered = \@Set dict, key ->
+    Dict.removeOrdered dict key |> @Set
+
 ## Test if a value is in the `Set`.
 ## ```
 ## Fruit : [Apple, Pear, Banana]
@@ -15,6 +15,7 @@ pub fn module_source(module_id: ModuleId) -> &'static str {
         ModuleId::DECODE => DECODE,
         ModuleId::HASH => HASH,
         ModuleId::JSON => JSON,
+        ModuleId::INSPECT => INSPECT,
         _ => panic!(
             "ModuleId {:?} is not part of the standard library",
             module_id
@@ -34,3 +35,4 @@ const ENCODE: &str = include_str!("../roc/Encode.roc");
 const DECODE: &str = include_str!("../roc/Decode.roc");
 const HASH: &str = include_str!("../roc/Hash.roc");
 const JSON: &str = include_str!("../roc/Json.roc");
+const INSPECT: &str = include_str!("../roc/Inspect.roc");
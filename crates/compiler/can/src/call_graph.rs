@@ -0,0 +1,272 @@
+//! A def-level call graph: which top-level defs each top-level def refers to.
+//!
+//! Any reference counts as a call, so a def that is only passed along as a value
+//! (like `f` in `List.map list f`) is still a callee. References to defs of other modules
+//! are kept as they are, which gives the cross-module edges once the declarations of
+//! every module are added to the same graph.
+use roc_collections::{MutMap, MutSet};
+use roc_module::symbol::{Interns, Symbol};
+use roc_region::all::{Loc, Region};
+use roc_types::subs::Variable;
+
+use crate::expr::{DeclarationTag, Declarations, Expr};
+use crate::traverse::{
+    symbols_introduced_from_pattern, walk_decl, walk_decls, walk_expr, DeclarationInfo, Visitor,
+};
+
+#[derive(Debug, Clone, Default)]
+pub struct CallGraph {
+    /// Top-level defs, in the order their declarations were added
+    defs: Vec<Symbol>,
+    /// Every reference from a def to a top-level def, in source order
+    calls: MutMap<Symbol, Vec<Loc<Symbol>>>,
+}
+
+impl CallGraph {
+    pub fn from_declarations(decls: &Declarations) -> Self {
+        let mut call_graph = Self::default();
+        call_graph.add_declarations(decls);
+
+        call_graph
+    }
+
+    /// Adds the top-level defs of a module. Expectations are not defs, so what they refer
+    /// to is not part of the graph.
+    pub fn add_declarations(&mut self, decls: &Declarations) {
+        let top_level = top_level_symbols(decls);
+
+        let mut collector = Collector {
+            top_level: &top_level,
+            call_graph: self,
+            callers: Vec::new(),
+            references: Vec::new(),
+        };
+
+        walk_decls(&mut collector, decls);
+    }
+
+    pub fn defs(&self) -> &[Symbol] {
+        &self.defs
+    }
+
+    /// The references in the body of `caller`, with the region of each reference
+    pub fn calls_from(&self, caller: Symbol) -> &[Loc<Symbol>] {
+        match self.calls.get(&caller) {
+            Some(calls) => calls,
+            None => &[],
+        }
+    }
+
+    /// The defs `caller` refers to, each once
+    pub fn callees(&self, caller: Symbol) -> Vec<Symbol> {
+        let mut callees: Vec<Symbol> = Vec::new();
+
+        for call in self.calls_from(caller) {
+            if !callees.contains(&call.value) {
+                callees.push(call.value);
+            }
+        }
+
+        callees
+    }
+
+    /// The defs that refer to `callee`, in the order they were added
+    pub fn callers(&self, callee: Symbol) -> Vec<Symbol> {
+        self.defs
+            .iter()
+            .copied()
+            .filter(|caller| {
+                self.calls_from(*caller)
+                    .iter()
+                    .any(|call| call.value == callee)
+            })
+            .collect()
+    }
+
+    /// The defs that can not be reached from any of the `roots`, e.g. the values exposed
+    /// to the host, in the order they were added
+    pub fn unreachable_from(&self, roots: impl IntoIterator<Item = Symbol>) -> Vec<Symbol> {
+        let mut reachable = MutSet::default();
+        let mut stack: Vec<Symbol> = roots.into_iter().collect();
+
+        while let Some(symbol) = stack.pop() {
+            if reachable.insert(symbol) {
+                stack.extend(self.calls_from(symbol).iter().map(|call| call.value));
+            }
+        }
+
+        self.defs
+            .iter()
+            .copied()
+            .filter(|symbol| !reachable.contains(symbol))
+            .collect()
+    }
+
+    /// Renders the graph as JSON for tools, e.g.
+    ///
+    /// ```json
+    /// {
+    ///   "defs": [
+    ///     {
+    ///       "module": "Dep1",
+    ///       "name": "str1",
+    ///       "calls": [
+    ///         { "module": "Dep2", "name": "two", "start": 42, "end": 45 }
+    ///       ]
+    ///     }
+    ///   ]
+    /// }
+    /// ```
+    ///
+    /// `start` and `end` are the byte offsets of the reference in the source of the caller.
+    pub fn to_json(&self, interns: &Interns) -> String {
+        let mut buf = String::from("{\n  \"defs\": [");
+
+        for (def_index, def) in self.defs.iter().enumerate() {
+            if def_index > 0 {
+                buf.push(',');
+            }
+
+            buf.push_str("\n    {\n      ");
+            push_json_symbol(&mut buf, *def, interns);
+            buf.push_str(",\n      \"calls\": [");
+
+            let calls = self.calls_from(*def);
+
+            for (call_index, call) in calls.iter().enumerate() {
+                if call_index > 0 {
+                    buf.push(',');
+                }
+
+                buf.push_str("\n        { ");
+                push_json_symbol(&mut buf, call.value, interns);
+                buf.push_str(&format!(
+                    ", \"start\": {}, \"end\": {} }}",
+                    call.region.start().offset,
+                    call.region.end().offset
+                ));
+            }
+
+            if !calls.is_empty() {
+                buf.push_str("\n      ");
+            }
+
+            buf.push_str("]\n    }");
+        }
+
+        if !self.defs.is_empty() {
+            buf.push_str("\n  ");
+        }
+
+        buf.push_str("]\n}\n");
+
+        buf
+    }
+}
+
+fn top_level_symbols(decls: &Declarations) -> MutSet<Symbol> {
+    let mut symbols = MutSet::default();
+
+    for (index, tag) in decls.declarations.iter().enumerate() {
+        match tag {
+            DeclarationTag::Value
+            | DeclarationTag::Function(_)
+            | DeclarationTag::Recursive(_)
+            | DeclarationTag::TailRecursive(_) => {
+                symbols.insert(decls.symbols[index].value);
+            }
+            DeclarationTag::Destructure(destructure_index) => {
+                let destructure = &decls.destructs[destructure_index.index() as usize];
+
+                symbols.extend(
+                    symbols_introduced_from_pattern(&destructure.loc_pattern)
+                        .map(|loc_symbol| loc_symbol.value),
+                );
+            }
+            DeclarationTag::Expectation
+            | DeclarationTag::ExpectationFx
+            | DeclarationTag::MutualRecursion { .. } => {}
+        }
+    }
+
+    symbols
+}
+
+fn push_json_symbol(buf: &mut String, symbol: Symbol, interns: &Interns) {
+    buf.push_str("\"module\": ");
+    push_json_str(buf, symbol.module_string(interns).as_str());
+    buf.push_str(", \"name\": ");
+    push_json_str(buf, symbol.as_str(interns));
+}
+
+fn push_json_str(buf: &mut String, string: &str) {
+    buf.push('"');
+
+    for ch in string.chars() {
+        match ch {
+            '"' => buf.push_str("\\\""),
+            '\\' => buf.push_str("\\\\"),
+            _ => buf.push(ch),
+        }
+    }
+
+    buf.push('"');
+}
+
+struct Collector<'g> {
+    top_level: &'g MutSet<Symbol>,
+    call_graph: &'g mut CallGraph,
+    callers: Vec<Symbol>,
+    references: Vec<Loc<Symbol>>,
+}
+
+impl Collector<'_> {
+    fn add_reference(&mut self, symbol: Symbol, region: Region) {
+        // defs of other modules are always top-level, locals of this module never are
+        let is_def = match self.callers.first() {
+            Some(caller) => symbol.module_id() != caller.module_id(),
+            None => false,
+        } || self.top_level.contains(&symbol);
+
+        if is_def {
+            self.references.push(Loc::at(region, symbol));
+        }
+    }
+}
+
+impl Visitor for Collector<'_> {
+    fn visit_decl(&mut self, decl: DeclarationInfo<'_>) {
+        self.callers = match &decl {
+            DeclarationInfo::Value { loc_symbol, .. }
+            | DeclarationInfo::Function { loc_symbol, .. } => vec![loc_symbol.value],
+            DeclarationInfo::Destructure { loc_pattern, .. } => {
+                symbols_introduced_from_pattern(loc_pattern)
+                    .map(|loc_symbol| loc_symbol.value)
+                    .collect()
+            }
+            DeclarationInfo::Expectation { .. } => return,
+        };
+
+        walk_decl(self, decl);
+
+        let references = std::mem::take(&mut self.references);
+
+        for caller in std::mem::take(&mut self.callers) {
+            self.call_graph.defs.push(caller);
+            self.call_graph
+                .calls
+                .entry(caller)
+                .or_default()
+                .extend(references.iter().copied());
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &Expr, region: Region, var: Variable) {
+        match expr {
+            Expr::Var(symbol, _) | Expr::AbilityMember(symbol, _, _) => {
+                self.add_reference(*symbol, region);
+            }
+            _ => walk_expr(self, expr, var),
+        }
+    }
+}
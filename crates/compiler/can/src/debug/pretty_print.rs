@@ -5,9 +5,11 @@ use crate::expr::Expr::{self, *};
 use crate::expr::{
     ClosureData, DeclarationTag, Declarations, FunctionDef, OpaqueWrapFunctionData, WhenBranch,
 };
-use crate::pattern::{Pattern, RecordDestruct, TupleDestruct};
+use crate::pattern::{ListPatterns, Pattern, RecordDestruct, TupleDestruct};
 
 use roc_module::symbol::{Interns, ModuleId, Symbol};
+use roc_region::all::{Loc, Region};
+use roc_types::types::IndexOrField;
 
 use ven_pretty::{Arena, DocAllocator, DocBuilder};
 
@@ -15,6 +17,8 @@ pub struct Ctx<'a> {
     pub home: ModuleId,
     pub interns: &'a Interns,
     pub print_lambda_names: bool,
+    /// Starts every top-level declaration with a comment that has its region
+    pub print_regions: bool,
 }
 
 pub fn pretty_print_declarations(c: &Ctx, declarations: &Declarations) -> String {
@@ -59,15 +63,42 @@ fn print_declarations_help<'a>(
                 let function_def = &declarations.function_bodies[f_index.index()].value;
                 toplevel_function(c, f, symbol, function_def, &body.value)
             }
-            DeclarationTag::Expectation => todo!(),
-            DeclarationTag::ExpectationFx => todo!(),
-            DeclarationTag::Destructure(_) => todo!(),
+            DeclarationTag::Expectation => f
+                .text("expect")
+                .append(f.line())
+                .append(expr(c, EPrec::Free, f, &body.value))
+                .nest(2)
+                .group(),
+            DeclarationTag::ExpectationFx => f
+                .text("expect-fx")
+                .append(f.line())
+                .append(expr(c, EPrec::Free, f, &body.value))
+                .nest(2)
+                .group(),
+            DeclarationTag::Destructure(d_index) => {
+                let destruct = &declarations.destructs[d_index.index()];
+                def_help(c, f, &destruct.loc_pattern.value, &body.value)
+            }
             DeclarationTag::MutualRecursion { .. } => {
                 // the defs will be printed next
                 continue;
             }
         };
 
+        let def = if c.print_regions {
+            // the symbol of an expectation is at the comment before it
+            let region = match tag {
+                DeclarationTag::Expectation | DeclarationTag::ExpectationFx => body.region,
+                _ => Region::span_across(&declarations.symbols[index].region, &body.region),
+            };
+
+            f.text(format!("# {:?}", region))
+                .append(f.hardline())
+                .append(def)
+        } else {
+            def
+        };
+
         defs.push(def);
     }
 
@@ -242,7 +273,11 @@ fn expr<'a>(c: &Ctx, p: EPrec, f: &'a Arena<'a>, e: &'a Expr) -> DocBuilder<'a,
                     .nest(2),
             )
             .group(),
-        LetRec(_, _, _) => todo!(),
+        LetRec(defs, body, _) => f
+            .intersperse(defs.iter().map(|d| def(c, f, d)), f.hardline())
+            .append(f.hardline())
+            .append(expr(c, Free, f, &body.value))
+            .group(),
         LetNonRec(loc_def, body) => def(c, f, loc_def)
             .append(f.hardline())
             .append(expr(c, Free, f, &body.value))
@@ -284,7 +319,25 @@ fn expr<'a>(c: &Ctx, p: EPrec, f: &'a Arena<'a>, e: &'a Expr) -> DocBuilder<'a,
                     .nest(2)
             )
         }
-        ForeignCall { .. } => todo!(),
+        ForeignCall {
+            foreign_symbol,
+            args,
+            ..
+        } => maybe_paren!(
+            Free,
+            p,
+            f.reflow("ForeignCall ")
+                .append(f.text(foreign_symbol.as_str().to_owned()))
+                .append(
+                    f.concat(
+                        args.iter()
+                            .map(|le| f.line().append(expr(c, AppArg, f, &le.1)))
+                    )
+                    .group()
+                )
+                .group()
+                .nest(2)
+        ),
         Closure(ClosureData {
             arguments,
             loc_body,
@@ -364,7 +417,10 @@ fn expr<'a>(c: &Ctx, p: EPrec, f: &'a Arena<'a>, e: &'a Expr) -> DocBuilder<'a,
         OpaqueWrapFunction(OpaqueWrapFunctionData { opaque_name, .. }) => {
             f.text(format!("@{}", opaque_name.as_str(c.interns)))
         }
-        RecordAccessor(_) => todo!(),
+        RecordAccessor(data) => match &data.field {
+            IndexOrField::Field(field) => f.text(format!(".{}", field.as_str())),
+            IndexOrField::Index(index) => f.text(format!(".{index}")),
+        },
         RecordUpdate {
             symbol, updates, ..
         } => f
@@ -413,8 +469,16 @@ fn expr<'a>(c: &Ctx, p: EPrec, f: &'a Arena<'a>, e: &'a Expr) -> DocBuilder<'a,
                 )
                 .group()
         ),
-        Crash { .. } => todo!(),
-        ZeroArgumentTag { .. } => todo!(),
+        Crash { msg, .. } => maybe_paren!(
+            Free,
+            p,
+            f.text("crash")
+                .append(f.line())
+                .append(expr(c, AppArg, f, &msg.value))
+                .group()
+                .nest(2)
+        ),
+        ZeroArgumentTag { name, .. } => f.text(name.0.as_str()),
         OpaqueRef { name, argument, .. } => maybe_paren!(
             Free,
             p,
@@ -424,14 +488,43 @@ fn expr<'a>(c: &Ctx, p: EPrec, f: &'a Arena<'a>, e: &'a Expr) -> DocBuilder<'a,
                 .append(expr(c, AppArg, f, &argument.1.value))
                 .group()
         ),
-        Dbg { .. } => todo!(),
-        Expect { .. } => todo!(),
-        ExpectFx { .. } => todo!(),
-        TypedHole(_) => todo!(),
-        RuntimeError(_) => todo!(),
+        Dbg {
+            loc_condition,
+            loc_continuation,
+            ..
+        } => statement(c, f, "dbg", loc_condition, loc_continuation),
+        Expect {
+            loc_condition,
+            loc_continuation,
+            ..
+        } => statement(c, f, "expect", loc_condition, loc_continuation),
+        ExpectFx {
+            loc_condition,
+            loc_continuation,
+            ..
+        } => statement(c, f, "expect-fx", loc_condition, loc_continuation),
+        TypedHole(_) => f.text("_"),
+        RuntimeError(problem) => f.text(format!("<runtime error: {:?}>", problem)),
     }
 }
 
+/// `dbg`, `expect` or `expect-fx` in front of the rest of an expression
+fn statement<'a>(
+    c: &Ctx,
+    f: &'a Arena<'a>,
+    keyword: &'static str,
+    loc_condition: &'a Loc<Expr>,
+    loc_continuation: &'a Loc<Expr>,
+) -> DocBuilder<'a, Arena<'a>> {
+    f.text(keyword)
+        .append(f.line())
+        .append(expr(c, EPrec::Free, f, &loc_condition.value))
+        .nest(2)
+        .group()
+        .append(f.hardline())
+        .append(expr(c, EPrec::Free, f, &loc_continuation.value))
+}
+
 fn pp_sym<'a>(c: &Ctx, f: &'a Arena<'a>, sym: Symbol) -> DocBuilder<'a, Arena<'a>> {
     if sym.module_id() == c.home {
         f.text(sym.as_str(c.interns).to_owned())
@@ -555,7 +648,29 @@ fn pattern<'a>(
             )
             .append(f.text(")"))
             .group(),
-        List { .. } => todo!(),
+        List {
+            patterns: ListPatterns { patterns, opt_rest },
+            ..
+        } => {
+            let mut elems: Vec<_> = patterns
+                .iter()
+                .map(|lp| pattern(c, Free, f, &lp.value))
+                .collect();
+
+            if let Some((index, opt_name)) = opt_rest {
+                let rest = match opt_name {
+                    Some(name) => f.text(".. as ").append(pp_sym(c, f, *name)),
+                    None => f.text(".."),
+                };
+
+                elems.insert(*index, rest);
+            }
+
+            f.text("[")
+                .append(f.intersperse(elems, f.text(", ")))
+                .append(f.text("]"))
+                .group()
+        }
         NumLiteral(_, n, _, _) | IntLiteral(_, _, n, _, _) | FloatLiteral(_, _, n, _, _) => {
             f.text(&**n)
         }
@@ -563,9 +678,8 @@ fn pattern<'a>(
         SingleQuote(_, _, c, _) => f.text(format!("'{}'", c)),
         Underscore => f.text("_"),
 
-        Shadowed(_, _, _) => todo!(),
-        OpaqueNotInScope(_) => todo!(),
-        UnsupportedPattern(_) => todo!(),
-        MalformedPattern(_, _) => todo!(),
+        Shadowed(_, _, symbol) => pp_sym(c, f, *symbol),
+        OpaqueNotInScope(loc_ident) => f.text(format!("@{}", loc_ident.value.as_str())),
+        UnsupportedPattern(_) | MalformedPattern(_, _) => f.text("<malformed pattern>"),
     }
 }
@@ -213,6 +213,66 @@ fn is_eq<'a>(env: &mut Env<'a>, at_opaque: &'a str) -> ast::Expr<'a> {
     )
 }
 
+fn inspect<'a>(env: &mut Env<'a>, at_opaque: &'a str) -> ast::Expr<'a> {
+    let alloc_pat = |it| env.arena.alloc(Loc::at(DERIVED_REGION, it));
+    let alloc_expr = |it| env.arena.alloc(Loc::at(DERIVED_REGION, it));
+
+    let payload = "#payload";
+
+    // \@Opaq payload
+    let opaque_ref = alloc_pat(ast::Pattern::OpaqueRef(at_opaque));
+    let opaque_apply_pattern = ast::Pattern::Apply(
+        opaque_ref,
+        &*env
+            .arena
+            .alloc([Loc::at(DERIVED_REGION, ast::Pattern::Identifier(payload))]),
+    );
+
+    // Inspect.inspect payload
+    let call_member = alloc_expr(ast::Expr::Apply(
+        alloc_expr(ast::Expr::Var {
+            module_name: "Inspect",
+            ident: "inspect",
+        }),
+        &*env.arena.alloc([&*alloc_expr(ast::Expr::Var {
+            module_name: "",
+            ident: payload,
+        })]),
+        CalledVia::Space,
+    ));
+
+    // Inspect.parenthesize (Inspect.inspect payload)
+    let call_parenthesize = alloc_expr(ast::Expr::Apply(
+        alloc_expr(ast::Expr::Var {
+            module_name: "Inspect",
+            ident: "parenthesize",
+        }),
+        &*env.arena.alloc([&*call_member]),
+        CalledVia::Space,
+    ));
+
+    // Str.concat "@Opaq " (Inspect.parenthesize (Inspect.inspect payload))
+    let opaque_prefix = env.arena.alloc_str(&format!("{} ", at_opaque));
+    let call_concat = alloc_expr(ast::Expr::Apply(
+        alloc_expr(ast::Expr::Var {
+            module_name: "Str",
+            ident: "concat",
+        }),
+        &*env.arena.alloc([
+            &*alloc_expr(ast::Expr::Str(ast::StrLiteral::PlainLine(opaque_prefix))),
+            &*call_parenthesize,
+        ]),
+        CalledVia::Space,
+    ));
+
+    // \@Opaq payload -> Str.concat "@Opaq " (Inspect.parenthesize (Inspect.inspect payload))
+    ast::Expr::Closure(
+        env.arena
+            .alloc([Loc::at(DERIVED_REGION, opaque_apply_pattern)]),
+        call_concat,
+    )
+}
+
 pub const DERIVED_REGION: Region = Region::zero();
 
 pub(crate) fn synthesize_member_impl<'a>(
@@ -232,6 +292,7 @@ pub(crate) fn synthesize_member_impl<'a>(
         Symbol::DECODE_DECODER => (format!("#{}_decoder", opaque_name), decoder(env, at_opaque)),
         Symbol::HASH_HASH => (format!("#{}_hash", opaque_name), hash(env, at_opaque)),
         Symbol::BOOL_IS_EQ => (format!("#{}_isEq", opaque_name), is_eq(env, at_opaque)),
+        Symbol::INSPECT_INSPECT => (format!("#{}_inspect", opaque_name), inspect(env, at_opaque)),
         other => internal_error!("{:?} is not a derivable ability member!", other),
     };
 
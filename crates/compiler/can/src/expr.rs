@@ -21,7 +21,10 @@ use roc_module::symbol::Symbol;
 use roc_parse::ast::{self, Defs, StrLiteral};
 use roc_parse::ident::Accessor;
 use roc_parse::pattern::PatternType::*;
-use roc_problem::can::{PrecedenceProblem, Problem, RuntimeError};
+use roc_problem::can::{
+    LargeLiteralKind, PrecedenceProblem, Problem, RuntimeError, MAX_LIST_LITERAL_ELEMENTS,
+    MAX_STR_LITERAL_BYTES,
+};
 use roc_region::all::{Loc, Region};
 use roc_types::num::SingleQuoteBound;
 use roc_types::subs::{ExhaustiveMark, IllegalCycleMark, RedundantMark, VarStore, Variable};
@@ -735,7 +738,7 @@ pub fn canonicalize_expr<'a>(
             )
         }
 
-        ast::Expr::Str(literal) => flatten_str_literal(env, var_store, scope, literal),
+        ast::Expr::Str(literal) => flatten_str_literal(env, var_store, scope, region, literal),
 
         ast::Expr::IngestedFile(file_path, _) => match File::open(file_path) {
             Ok(mut file) => {
@@ -817,6 +820,20 @@ pub fn canonicalize_expr<'a>(
                     },
                     Output::default(),
                 )
+            } else if loc_elems.len() > MAX_LIST_LITERAL_ELEMENTS {
+                env.problem(Problem::LiteralTooLarge {
+                    region,
+                    kind: LargeLiteralKind::List,
+                    size: loc_elems.len(),
+                });
+
+                (
+                    Expr::RuntimeError(roc_problem::can::RuntimeError::LiteralTooLarge(
+                        LargeLiteralKind::List,
+                        region,
+                    )),
+                    Output::default(),
+                )
             } else {
                 let mut can_elems = Vec::with_capacity(loc_elems.len());
                 let mut references = References::new();
@@ -2338,17 +2355,55 @@ fn flatten_str_literal<'a>(
     env: &mut Env<'a>,
     var_store: &mut VarStore,
     scope: &mut Scope,
+    region: Region,
     literal: &StrLiteral<'a>,
 ) -> (Expr, Output) {
     use ast::StrLiteral::*;
 
+    let size = match literal {
+        PlainLine(str_slice) => str_slice.len(),
+        Line(segments) => str_lines_len(&[segments]),
+        Block(lines) => str_lines_len(lines),
+    };
+
+    // the whole literal ends up in the binary, and is copied a few times on the way there
+    if size > MAX_STR_LITERAL_BYTES {
+        env.problem(Problem::LiteralTooLarge {
+            region,
+            kind: LargeLiteralKind::Str,
+            size,
+        });
+
+        return (
+            Expr::RuntimeError(RuntimeError::LiteralTooLarge(LargeLiteralKind::Str, region)),
+            Output::default(),
+        );
+    }
+
     match literal {
         PlainLine(str_slice) => (Expr::Str((*str_slice).into()), Output::default()),
-        Line(segments) => flatten_str_lines(env, var_store, scope, &[segments]),
-        Block(lines) => flatten_str_lines(env, var_store, scope, lines),
+        Line(segments) => flatten_str_lines(env, var_store, scope, size, &[segments]),
+        Block(lines) => flatten_str_lines(env, var_store, scope, size, lines),
     }
 }
 
+/// The number of bytes of the text of a string literal, not counting its interpolations
+fn str_lines_len(lines: &[&[ast::StrSegment<'_>]]) -> usize {
+    use ast::StrSegment::*;
+
+    lines
+        .iter()
+        .flat_map(|line| line.iter())
+        .map(|segment| match segment {
+            Plaintext(string) => string.len(),
+            // a code point is at most 4 bytes of UTF-8, escaped characters are all ASCII
+            Unicode(_) => 4,
+            EscapedChar(_) => 1,
+            Interpolated(_) => 0,
+        })
+        .sum()
+}
+
 pub fn is_valid_interpolation(expr: &ast::Expr<'_>) -> bool {
     match expr {
         ast::Expr::Var { .. } => true,
@@ -2366,11 +2421,13 @@ fn flatten_str_lines<'a>(
     env: &mut Env<'a>,
     var_store: &mut VarStore,
     scope: &mut Scope,
+    size: usize,
     lines: &[&[ast::StrSegment<'a>]],
 ) -> (Expr, Output) {
     use ast::StrSegment::*;
 
-    let mut buf = String::new();
+    // allocated once up front, so big literals are not copied every time the buffer grows
+    let mut buf = String::with_capacity(size);
     let mut segments = Vec::new();
     let mut output = Output::default();
 
@@ -9,6 +9,7 @@
 pub mod abilities;
 pub mod annotation;
 pub mod builtins;
+pub mod call_graph;
 pub mod constraint;
 pub mod copy;
 pub mod def;
@@ -1254,6 +1254,23 @@ pub struct TypeState {
     pub solved_implementations: ResolvedImplementations,
 }
 
+/// Written before a serialized [TypeState], so a cache in another format is rejected instead of
+/// misread. Bump [TYPE_STATE_VERSION] whenever the serialized format of [Subs], the
+/// [AbilitiesStore] or the resolved implementations changes.
+const TYPE_STATE_MAGIC: [u8; 4] = *b"ROCT";
+pub const TYPE_STATE_VERSION: u32 = 1;
+
+/// The header is 8 bytes, so the data after it keeps its alignment.
+const TYPE_STATE_HEADER_LEN: usize = TYPE_STATE_MAGIC.len() + std::mem::size_of::<u32>();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeStateHeaderError {
+    /// The bytes are not a serialized [TypeState]
+    NotTypeState,
+    /// The bytes are a [TypeState] in the format of another version
+    Version { found: u32 },
+}
+
 impl TypeState {
     pub fn serialize(&self, writer: &mut impl std::io::Write) -> std::io::Result<usize> {
         let Self {
@@ -1263,15 +1280,21 @@ impl TypeState {
             solved_implementations,
         } = self;
 
+        writer.write_all(&TYPE_STATE_MAGIC)?;
+        writer.write_all(&TYPE_STATE_VERSION.to_le_bytes())?;
+
         let written_subs = subs.serialize(exposed_vars_by_symbol, writer)?;
         let written_ab = abilities.serialize(writer)?;
         let written_solved_impls =
             crate::abilities::serialize_solved_implementations(solved_implementations, writer)?;
 
-        Ok(written_subs + written_ab + written_solved_impls)
+        Ok(TYPE_STATE_HEADER_LEN + written_subs + written_ab + written_solved_impls)
     }
 
-    pub fn deserialize(bytes: &[u8]) -> (Self, usize) {
+    pub fn deserialize(bytes: &[u8]) -> Result<(Self, usize), TypeStateHeaderError> {
+        Self::check_header(bytes)?;
+        let bytes = &bytes[TYPE_STATE_HEADER_LEN..];
+
         let ((subs, exposed_vars_by_symbol), len_subs) = Subs::deserialize(bytes);
         let bytes = &bytes[len_subs..];
 
@@ -1281,9 +1304,9 @@ impl TypeState {
         let (solved_implementations, len_solved_impls) =
             crate::abilities::deserialize_solved_implementations(bytes);
 
-        let total_offset = len_subs + len_abilities + len_solved_impls;
+        let total_offset = TYPE_STATE_HEADER_LEN + len_subs + len_abilities + len_solved_impls;
 
-        (
+        Ok((
             Self {
                 subs,
                 exposed_vars_by_symbol: exposed_vars_by_symbol.to_vec(),
@@ -1291,6 +1314,108 @@ impl TypeState {
                 solved_implementations,
             },
             total_offset,
-        )
+        ))
+    }
+
+    /// Whether the bytes are a [TypeState] that this version of the compiler can read
+    pub fn check_header(bytes: &[u8]) -> Result<(), TypeStateHeaderError> {
+        if bytes.len() < TYPE_STATE_HEADER_LEN || bytes[..4] != TYPE_STATE_MAGIC {
+            return Err(TypeStateHeaderError::NotTypeState);
+        }
+
+        let found = u32::from_le_bytes(bytes[4..TYPE_STATE_HEADER_LEN].try_into().unwrap());
+
+        if found == TYPE_STATE_VERSION {
+            Ok(())
+        } else {
+            Err(TypeStateHeaderError::Version { found })
+        }
+    }
+
+    /// Like [TypeState::check_header], but usable in a `const` assertion, so that a cache
+    /// included in the binary is checked when the compiler is built
+    pub const fn has_current_header(bytes: &[u8]) -> bool {
+        if bytes.len() < TYPE_STATE_HEADER_LEN {
+            return false;
+        }
+
+        let mut i = 0;
+        while i < TYPE_STATE_MAGIC.len() {
+            if bytes[i] != TYPE_STATE_MAGIC[i] {
+                return false;
+            }
+            i += 1;
+        }
+
+        u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]) == TYPE_STATE_VERSION
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use roc_module::symbol::Symbol;
+    use roc_types::subs::{Subs, Variable};
+
+    use super::{TypeState, TypeStateHeaderError, TYPE_STATE_VERSION};
+    use crate::abilities::AbilitiesStore;
+
+    fn serialized_type_state() -> Vec<u8> {
+        let type_state = TypeState {
+            subs: Subs::new(),
+            exposed_vars_by_symbol: vec![(Symbol::ARG_1, Variable::BOOL)],
+            abilities: AbilitiesStore::default(),
+            solved_implementations: Default::default(),
+        };
+
+        let mut bytes = Vec::new();
+        let written = type_state.serialize(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), written);
+
+        bytes
+    }
+
+    #[test]
+    fn serde_type_state() {
+        let bytes = serialized_type_state();
+
+        let (type_state, offset) = TypeState::deserialize(&bytes).unwrap();
+
+        assert_eq!(bytes.len(), offset);
+        assert_eq!(
+            type_state.exposed_vars_by_symbol,
+            vec![(Symbol::ARG_1, Variable::BOOL)]
+        );
+    }
+
+    #[test]
+    fn type_state_of_other_version() {
+        let mut bytes = serialized_type_state();
+        bytes[4..8].copy_from_slice(&(TYPE_STATE_VERSION + 1).to_le_bytes());
+
+        assert_eq!(
+            TypeState::deserialize(&bytes).err(),
+            Some(TypeStateHeaderError::Version {
+                found: TYPE_STATE_VERSION + 1
+            })
+        );
+    }
+
+    #[test]
+    fn not_a_type_state() {
+        assert_eq!(
+            TypeState::check_header(b"not types"),
+            Err(TypeStateHeaderError::NotTypeState)
+        );
+    }
+
+    #[test]
+    fn const_header_check() {
+        let mut bytes = serialized_type_state();
+        assert!(TypeState::has_current_header(&bytes));
+
+        bytes[4..8].copy_from_slice(&(TYPE_STATE_VERSION + 1).to_le_bytes());
+        assert!(!TypeState::has_current_header(&bytes));
+
+        assert!(!TypeState::has_current_header(b"not types"));
     }
 }
@@ -623,6 +623,24 @@ pub fn canonicalize_pattern<'a>(
             let mut opt_erroneous = None;
 
             for loc_pattern in patterns.iter() {
+                let label = match loc_pattern.value {
+                    Identifier(label) | RequiredField(label, _) | OptionalField(label, _) => label,
+                    _ => unreachable!("Any other pattern should have given a parse error"),
+                };
+
+                // only the first destructure of a field is used, so the later ones
+                // don't introduce (and shadow) a second symbol with the same name
+                if let Some(first) = destructs.iter().find(|d| d.value.label.as_str() == label) {
+                    env.problem(Problem::DuplicateRecordFieldPattern {
+                        field_name: Lowercase::from(label),
+                        record_region: region,
+                        field_region: loc_pattern.region,
+                        first_region: first.region,
+                    });
+
+                    continue;
+                }
+
                 match loc_pattern.value {
                     Identifier(label) => {
                         match scope.introduce(label.into(), region) {
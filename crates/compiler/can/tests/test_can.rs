@@ -16,7 +16,10 @@ mod test_can {
     use bumpalo::Bump;
     use roc_can::expr::Expr::{self, *};
     use roc_can::expr::{ClosureData, IntValue, Recursive};
-    use roc_problem::can::{CycleEntry, FloatErrorKind, IntErrorKind, Problem, RuntimeError};
+    use roc_problem::can::{
+        CycleEntry, FloatErrorKind, IntErrorKind, LargeLiteralKind, Problem, RuntimeError,
+        MAX_LIST_LITERAL_ELEMENTS, MAX_STR_LITERAL_BYTES,
+    };
     use roc_region::all::{Position, Region};
     use std::{f64, i64};
 
@@ -1566,6 +1569,28 @@ mod test_can {
         );
     }
 
+    #[test]
+    fn string_too_large() {
+        let string = format!("\"{}\"", "x".repeat(MAX_STR_LITERAL_BYTES + 1));
+        let region = Region::new(Position::new(0), Position::new(string.len() as u32));
+
+        assert_can_runtime_error(
+            &string,
+            RuntimeError::LiteralTooLarge(LargeLiteralKind::Str, region),
+        );
+    }
+
+    #[test]
+    fn list_too_large() {
+        let list = format!("[{}0]", "0,".repeat(MAX_LIST_LITERAL_ELEMENTS));
+        let region = Region::new(Position::new(0), Position::new(list.len() as u32));
+
+        assert_can_runtime_error(
+            &list,
+            RuntimeError::LiteralTooLarge(LargeLiteralKind::List, region),
+        );
+    }
+
     //     #[test]
     //     fn string_with_too_large_unicode_escape() {
     //         // Should be too big - max size should be 10FFFF.
@@ -5,6 +5,7 @@
 
 pub mod all;
 mod reference_matrix;
+mod slice_interner;
 mod small_string_interner;
 mod small_vec;
 pub mod soa;
@@ -13,6 +14,7 @@ mod vec_set;
 
 pub use all::{default_hasher, BumpMap, ImEntry, ImMap, ImSet, MutMap, MutSet, SendMap};
 pub use reference_matrix::{ReferenceMatrix, Sccs, TopologicalSort};
+pub use slice_interner::SliceInterner;
 pub use small_string_interner::SmallStringInterner;
 pub use small_vec::SmallVec;
 pub use vec_map::VecMap;
@@ -0,0 +1,78 @@
+use std::hash::Hash;
+
+use bumpalo::Bump;
+
+use crate::all::MutSet;
+
+/// Stores slices in an arena, once per distinct contents. Equal slices that are inserted
+/// again are shared, e.g. the argument layouts of many calls to the same function.
+#[derive(Debug)]
+pub struct SliceInterner<'a, T> {
+    arena: &'a Bump,
+    slices: MutSet<&'a [T]>,
+}
+
+impl<'a, T: Hash + Eq + Clone> SliceInterner<'a, T> {
+    pub fn new_in(arena: &'a Bump) -> Self {
+        Self {
+            arena,
+            slices: MutSet::default(),
+        }
+    }
+
+    /// The arena copy of `slice`, which is only allocated the first time these contents are seen.
+    pub fn insert(&mut self, slice: &[T]) -> &'a [T] {
+        if slice.is_empty() {
+            return &[];
+        }
+
+        match self.slices.get(slice) {
+            Some(interned) => interned,
+            None => {
+                let interned: &'a [T] = self.arena.alloc_slice_clone(slice);
+
+                self.slices.insert(interned);
+
+                interned
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.slices.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slices.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SliceInterner;
+    use bumpalo::Bump;
+
+    #[test]
+    fn equal_slices_are_shared() {
+        let arena = Bump::new();
+        let mut interner = SliceInterner::new_in(&arena);
+
+        let a = interner.insert(&[1u32, 2, 3]);
+        let b = interner.insert(&vec![1, 2, 3]);
+        let c = interner.insert(&[1, 2]);
+
+        assert!(std::ptr::eq(a, b));
+        assert!(!std::ptr::eq(a, c));
+        assert_eq!(c, &[1, 2]);
+        assert_eq!(interner.len(), 2);
+    }
+
+    #[test]
+    fn empty_slices_are_not_stored() {
+        let arena = Bump::new();
+        let mut interner = SliceInterner::<u8>::new_in(&arena);
+
+        assert!(interner.insert(&[]).is_empty());
+        assert!(interner.is_empty());
+    }
+}
@@ -1,5 +1,6 @@
 use std::fmt::Debug;
 
+use bumpalo::Bump;
 use smallvec::SmallVec as Vec;
 
 #[derive(Default)]
@@ -17,6 +18,28 @@ impl<T, const N: usize> SmallVec<T, N> {
     pub fn pop(&mut self) -> Option<T> {
         self.0.pop()
     }
+
+    pub fn retain<F: FnMut(&mut T) -> bool>(&mut self, f: F) {
+        self.0.retain(f)
+    }
+
+    /// Moves the elements into `arena` with a single allocation of the exact size,
+    /// where growing a vector in the arena would leave all its smaller buffers behind.
+    pub fn into_bump_slice(self, arena: &Bump) -> &[T] {
+        arena.alloc_slice_fill_iter(self.0)
+    }
+}
+
+impl<T, const N: usize> std::ops::DerefMut for SmallVec<T, N> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.0.deref_mut()
+    }
+}
+
+impl<T, const N: usize> Extend<T> for SmallVec<T, N> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.0.extend(iter)
+    }
 }
 
 impl<T, const N: usize> std::ops::Deref for SmallVec<T, N> {
@@ -59,3 +82,22 @@ impl<T, const N: usize> FromIterator<T> for SmallVec<T, N> {
         Self(Vec::from_iter(iter))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::SmallVec;
+    use bumpalo::Bump;
+
+    #[test]
+    fn into_bump_slice_after_spilling() {
+        let arena = Bump::new();
+
+        let mut inline: SmallVec<u32, 2> = SmallVec::new();
+        inline.push(1);
+        assert_eq!(inline.into_bump_slice(&arena), &[1]);
+
+        let mut spilled: SmallVec<u32, 2> = SmallVec::new();
+        spilled.extend([1, 2, 3]);
+        assert_eq!(spilled.into_bump_slice(&arena), &[1, 2, 3]);
+    }
+}
@@ -122,6 +122,10 @@ flags! {
     /// Type-checks the mono IR after specialization.
     ROC_CHECK_MONO_IR
 
+    /// Writes the mono IR in the format of the mono cache after specialization, and continues the
+    /// build with what is read back.
+    ROC_CHECK_MONO_CACHE
+
     /// Writes a pretty-printed mono IR to stderr after function specialization.
     ROC_PRINT_IR_AFTER_SPECIALIZATION
 
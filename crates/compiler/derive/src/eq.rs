@@ -0,0 +1,492 @@
+//! Derivers for the `Eq` ability.
+
+use std::iter::once;
+
+use roc_can::{
+    expr::{AnnotatedMark, ClosureData, Expr, Recursive, WhenBranch, WhenBranchPattern},
+    pattern::Pattern,
+};
+use roc_derive_key::eq::FlatEqKey;
+use roc_module::{
+    called_via::CalledVia,
+    ident::{Lowercase, TagName},
+    symbol::Symbol,
+};
+use roc_region::all::{Loc, Region};
+use roc_types::{
+    subs::{
+        Content, ExhaustiveMark, FlatType, GetSubsSlice, LambdaSet, OptVariable, RecordFields,
+        RedundantMark, SubsSlice, TagExt, TupleElems, UnionLambdas, UnionTags, Variable,
+        VariableSubsSlice,
+    },
+    types::RecordField,
+};
+
+use crate::{synth_var, util::Env, DerivedBody};
+
+pub(crate) fn derive_eq(env: &mut Env<'_>, key: FlatEqKey, def_symbol: Symbol) -> DerivedBody {
+    let (body_type, body) = match key {
+        FlatEqKey::Record(fields) => eq_record(env, def_symbol, fields),
+        FlatEqKey::Tuple(arity) => eq_tuple(env, def_symbol, arity),
+        FlatEqKey::TagUnion(tags) => eq_tag_union(env, def_symbol, tags),
+    };
+
+    let specialization_lambda_sets =
+        env.get_specialization_lambda_sets(body_type, Symbol::BOOL_IS_EQ);
+
+    DerivedBody {
+        body,
+        body_type,
+        specialization_lambda_sets,
+    }
+}
+
+fn eq_record(env: &mut Env<'_>, fn_name: Symbol, fields: Vec<Lowercase>) -> (Variable, Expr) {
+    // Suppose rcd = { f1, ..., fn }.
+    // Build a generalized type t_rcd = { f1: t1, ..., fn: tn }, with fresh t1, ..., tn,
+    // so that we can re-use the derived impl for many records of the same fields.
+    let (record_var, record_fields) = {
+        let flex_fields = fields
+            .into_iter()
+            .map(|name| {
+                (
+                    name,
+                    RecordField::Required(env.subs.fresh_unnamed_flex_var()),
+                )
+            })
+            .collect::<Vec<(Lowercase, _)>>();
+        let fields = RecordFields::insert_into_subs(env.subs, flex_fields);
+        let record_var = synth_var(
+            env.subs,
+            Content::Structure(FlatType::Record(fields, Variable::EMPTY_RECORD)),
+        );
+
+        (record_var, fields)
+    };
+
+    // Now, an equality check for this record is
+    //
+    // eq_rcd : { f1: t1, ..., fn: tn }, { f1: t1, ..., fn: tn } -> Bool | t1 has Eq, ..., tn has Eq
+    // eq_rcd = \rcd1, rcd2 ->
+    //   Bool.and
+    //      (Bool.isEq rcd1.f1 rcd2.f1)
+    //      (Bool.and ... (Bool.isEq rcd1.fn rcd2.fn))
+    let rcd1_sym = env.new_symbol("rcd1");
+    let rcd2_sym = env.new_symbol("rcd2");
+
+    let field_comparisons: Vec<_> = record_fields
+        .iter_all()
+        .map(|(field_name, field_var, _)| {
+            let field_name = env.subs[field_name].clone();
+            let field_var = env.subs[field_var];
+
+            let mut field_access = |rcd_sym| Expr::RecordAccess {
+                record_var,
+                field_var,
+                ext_var: env.subs.fresh_unnamed_flex_var(),
+                loc_expr: Box::new(Loc::at_zero(Expr::Var(
+                    rcd_sym,
+                    env.subs.fresh_unnamed_flex_var(),
+                ))),
+                field: field_name.clone(),
+            };
+            let left = field_access(rcd1_sym);
+            let right = field_access(rcd2_sym);
+
+            call_bool_is_eq(env, field_var, left, right)
+        })
+        .collect();
+
+    let (body_var, body) = and_all(env, field_comparisons);
+
+    // Finally, build the closure
+    // \rcd1, rcd2 -> body
+    build_outer_derived_closure(
+        env,
+        fn_name,
+        record_var,
+        (Pattern::Identifier(rcd1_sym), Pattern::Identifier(rcd2_sym)),
+        (body_var, body),
+    )
+}
+
+fn eq_tuple(env: &mut Env<'_>, fn_name: Symbol, arity: u32) -> (Variable, Expr) {
+    // Suppose tup = (v1, ..., vn).
+    // Build a generalized type t_tup = (t1, ..., tn), with fresh t1, ..., tn,
+    // so that we can re-use the derived impl for many tuples of the same arity.
+    let (tuple_var, tuple_elems) = {
+        let flex_elems: Vec<_> = (0..arity)
+            .map(|i| (i as usize, env.subs.fresh_unnamed_flex_var()))
+            .collect();
+        let elems = TupleElems::insert_into_subs(env.subs, flex_elems);
+        let tuple_var = synth_var(
+            env.subs,
+            Content::Structure(FlatType::Tuple(elems, Variable::EMPTY_TUPLE)),
+        );
+
+        (tuple_var, elems)
+    };
+
+    // Now, an equality check for this tuple is
+    //
+    // eq_tup : (t1, ..., tn), (t1, ..., tn) -> Bool | t1 has Eq, ..., tn has Eq
+    // eq_tup = \tup1, tup2 ->
+    //   Bool.and
+    //      (Bool.isEq tup1.0 tup2.0)
+    //      (Bool.and ... (Bool.isEq tup1.n tup2.n))
+    let tup1_sym = env.new_symbol("tup1");
+    let tup2_sym = env.new_symbol("tup2");
+
+    let elem_comparisons: Vec<_> = tuple_elems
+        .iter_all()
+        .map(|(elem_idx, elem_var)| {
+            let index = env.subs[elem_idx];
+            let elem_var = env.subs[elem_var];
+
+            let mut elem_access = |tup_sym| Expr::TupleAccess {
+                tuple_var,
+                elem_var,
+                ext_var: env.subs.fresh_unnamed_flex_var(),
+                loc_expr: Box::new(Loc::at_zero(Expr::Var(
+                    tup_sym,
+                    env.subs.fresh_unnamed_flex_var(),
+                ))),
+                index,
+            };
+            let left = elem_access(tup1_sym);
+            let right = elem_access(tup2_sym);
+
+            call_bool_is_eq(env, elem_var, left, right)
+        })
+        .collect();
+
+    let (body_var, body) = and_all(env, elem_comparisons);
+
+    // Finally, build the closure
+    // \tup1, tup2 -> body
+    build_outer_derived_closure(
+        env,
+        fn_name,
+        tuple_var,
+        (Pattern::Identifier(tup1_sym), Pattern::Identifier(tup2_sym)),
+        (body_var, body),
+    )
+}
+
+fn eq_tag_union(env: &mut Env<'_>, fn_name: Symbol, tags: Vec<(TagName, u16)>) -> (Variable, Expr) {
+    // Suppose tags = [ A p11 .. p1n, ..., Q pq1 .. pqm ]
+    // Build a generalized type t_tags = [ A t11 .. t1n, ..., Q tq1 .. tqm ],
+    // with fresh t1, ..., tqm, so that we can re-use the derived impl for many
+    // unions of the same tags and payloads.
+    let (union_var, union_tags) = {
+        let flex_tag_labels = tags
+            .into_iter()
+            .map(|(label, arity)| {
+                let variables_slice = VariableSubsSlice::reserve_into_subs(env.subs, arity.into());
+                for var_index in variables_slice {
+                    env.subs[var_index] = env.subs.fresh_unnamed_flex_var();
+                }
+                (label, variables_slice)
+            })
+            .collect::<Vec<_>>();
+        let union_tags = UnionTags::insert_slices_into_subs(env.subs, flex_tag_labels);
+        let tag_union_var = synth_var(
+            env.subs,
+            Content::Structure(FlatType::TagUnion(
+                union_tags,
+                TagExt::Any(Variable::EMPTY_TAG_UNION),
+            )),
+        );
+
+        (tag_union_var, union_tags)
+    };
+
+    // Now, an equality check for this tag union is
+    //
+    // eq_union : [ A t11 .. t1n, ..., Q tq1 .. tqm ], [ A t11 .. t1n, ..., Q tq1 .. tqm ] -> Bool
+    // eq_union = \union1, union2 ->
+    //   when union1 is
+    //      A x11 .. x1n ->
+    //          when union2 is
+    //              A y11 .. y1n -> Bool.and (Bool.isEq x11 y11) (... (Bool.isEq x1n y1n))
+    //              _ -> Bool.false
+    //      ...
+    //      Q xq1 .. xqm -> ...
+    //
+    // where a tag without payloads compares equal with `Bool.true`.
+    let union1_sym = env.new_symbol("union1");
+    let union2_sym = env.new_symbol("union2");
+
+    let has_multiple_tags = union_tags.len() > 1;
+    let whole_eq_var = env.subs.fresh_unnamed_flex_var();
+
+    let branches = union_tags
+        .iter_all()
+        .map(|(tag, payloads)| {
+            // A
+            let tag_name = env.subs[tag].clone();
+            // t11 .. t1n
+            let payload_vars = env.subs.get_subs_slice(env.subs[payloads]).to_vec();
+            // x11 .. x1n
+            let left_syms: Vec<_> = std::iter::repeat_with(|| env.unique_symbol())
+                .take(payload_vars.len())
+                .collect();
+            // y11 .. y1n
+            let right_syms: Vec<_> = std::iter::repeat_with(|| env.unique_symbol())
+                .take(payload_vars.len())
+                .collect();
+
+            // Bool.and (Bool.isEq x11 y11) (... (Bool.isEq x1n y1n))
+            let (payloads_eq_var, payloads_eq) = if payload_vars.is_empty() {
+                bool_value(env, Symbol::BOOL_TRUE)
+            } else {
+                let payload_comparisons: Vec<_> = (payload_vars.iter())
+                    .zip(left_syms.iter().zip(right_syms.iter()))
+                    .map(|(var, (left, right))| {
+                        call_bool_is_eq(env, *var, Expr::Var(*left, *var), Expr::Var(*right, *var))
+                    })
+                    .collect();
+
+                and_all(env, payload_comparisons)
+            };
+
+            // when union2 is
+            //   A y11 .. y1n -> ...
+            //   _ -> Bool.false
+            let mut inner_branches = vec![applied_tag_branch(
+                union_var,
+                tag_name.clone(),
+                &payload_vars,
+                &right_syms,
+                payloads_eq,
+            )];
+            if has_multiple_tags {
+                let (false_var, false_expr) = bool_value(env, Symbol::BOOL_FALSE);
+                env.unify(payloads_eq_var, false_var);
+
+                inner_branches.push(WhenBranch {
+                    patterns: vec![WhenBranchPattern {
+                        pattern: Loc::at_zero(Pattern::Underscore),
+                        degenerate: false,
+                    }],
+                    value: Loc::at_zero(false_expr),
+                    guard: None,
+                    redundant: RedundantMark::known_non_redundant(),
+                });
+            }
+
+            let inner_when = Expr::When {
+                loc_cond: Box::new(Loc::at_zero(Expr::Var(union2_sym, union_var))),
+                cond_var: union_var,
+                expr_var: payloads_eq_var,
+                region: Region::zero(),
+                branches: inner_branches,
+                branches_cond_var: union_var,
+                exhaustive: ExhaustiveMark::known_exhaustive(),
+            };
+
+            env.unify(whole_eq_var, payloads_eq_var);
+
+            applied_tag_branch(union_var, tag_name, &payload_vars, &left_syms, inner_when)
+        })
+        .collect();
+
+    // when union1 is
+    //   ...
+    let when_var = whole_eq_var;
+    let when_expr = Expr::When {
+        loc_cond: Box::new(Loc::at_zero(Expr::Var(union1_sym, union_var))),
+        cond_var: union_var,
+        expr_var: when_var,
+        region: Region::zero(),
+        branches,
+        branches_cond_var: union_var,
+        exhaustive: ExhaustiveMark::known_exhaustive(),
+    };
+
+    // Finally, build the closure
+    // \union1, union2 -> body
+    build_outer_derived_closure(
+        env,
+        fn_name,
+        union_var,
+        (
+            Pattern::Identifier(union1_sym),
+            Pattern::Identifier(union2_sym),
+        ),
+        (when_var, when_expr),
+    )
+}
+
+/// `A x1 .. xn -> body`
+fn applied_tag_branch(
+    union_var: Variable,
+    tag_name: TagName,
+    payload_vars: &[Variable],
+    payload_syms: &[Symbol],
+    body: Expr,
+) -> WhenBranch {
+    let pattern = Pattern::AppliedTag {
+        whole_var: union_var,
+        tag_name,
+        ext_var: Variable::EMPTY_TAG_UNION,
+        arguments: (payload_vars.iter())
+            .zip(payload_syms.iter())
+            .map(|(var, sym)| (*var, Loc::at_zero(Pattern::Identifier(*sym))))
+            .collect(),
+    };
+
+    WhenBranch {
+        patterns: vec![WhenBranchPattern {
+            pattern: Loc::at_zero(pattern),
+            degenerate: false,
+        }],
+        value: Loc::at_zero(body),
+        guard: None,
+        redundant: RedundantMark::known_non_redundant(),
+    }
+}
+
+/// `Bool.true` or `Bool.false`
+fn bool_value(env: &mut Env<'_>, symbol: Symbol) -> (Variable, Expr) {
+    let bool_var = env.import_builtin_symbol_var(symbol);
+
+    (bool_var, Expr::Var(symbol, bool_var))
+}
+
+/// Folds `Bool.and` over a non-empty list of boolean expressions, from the right, so that the
+/// comparisons are evaluated in order.
+fn and_all(env: &mut Env<'_>, comparisons: Vec<(Variable, Expr)>) -> (Variable, Expr) {
+    let mut comparisons = comparisons.into_iter().rev();
+    let last = comparisons
+        .next()
+        .expect("derived equality must compare at least one component");
+
+    comparisons.fold(last, |rest, first| {
+        call_bool_fn(env, Symbol::BOOL_AND, first, rest)
+    })
+}
+
+fn call_bool_is_eq(
+    env: &mut Env<'_>,
+    val_var: Variable,
+    left: Expr,
+    right: Expr,
+) -> (Variable, Expr) {
+    call_bool_fn(env, Symbol::BOOL_IS_EQ, (val_var, left), (val_var, right))
+}
+
+fn call_bool_fn(
+    env: &mut Env<'_>,
+    member: Symbol,
+    left: (Variable, Expr),
+    right: (Variable, Expr),
+) -> (Variable, Expr) {
+    let (left_var, left_expr) = left;
+    let (right_var, right_expr) = right;
+
+    // build `member ...` function type. `member` here is `Bool.isEq` or `Bool.and`.
+    //
+    // a, a -[uls]-> Bool | a has Eq
+    let exposed_fn_var = env.import_builtin_symbol_var(member);
+
+    // (typeof left), (typeof right) -[clos]-> ret
+    let this_arguments_slice = VariableSubsSlice::insert_into_subs(env.subs, [left_var, right_var]);
+    let this_clos_var = env.subs.fresh_unnamed_flex_var();
+    let this_ret_var = env.subs.fresh_unnamed_flex_var();
+    let this_fn_var = synth_var(
+        env.subs,
+        Content::Structure(FlatType::Func(
+            this_arguments_slice,
+            this_clos_var,
+            this_ret_var,
+        )),
+    );
+
+    //   a,             a              -[uls]->  Bool | a has Eq
+    // ~ (typeof left), (typeof right) -[clos]-> ret
+    env.unify(exposed_fn_var, this_fn_var);
+
+    // Only `isEq` is an ability member; the others are plain functions.
+    let fn_head = if member == Symbol::BOOL_IS_EQ {
+        Expr::AbilityMember(member, None, this_fn_var)
+    } else {
+        Expr::Var(member, this_fn_var)
+    };
+    let fn_data = Box::new((
+        this_fn_var,
+        Loc::at_zero(fn_head),
+        this_clos_var,
+        this_ret_var,
+    ));
+
+    let arguments = vec![
+        (left_var, Loc::at_zero(left_expr)),
+        (right_var, Loc::at_zero(right_expr)),
+    ];
+    let call = Expr::Call(fn_data, arguments, CalledVia::Space);
+
+    (this_ret_var, call)
+}
+
+fn build_outer_derived_closure(
+    env: &mut Env<'_>,
+    fn_name: Symbol,
+    val_var: Variable,
+    patterns: (Pattern, Pattern),
+    body: (Variable, Expr),
+) -> (Variable, Expr) {
+    let (left_pattern, right_pattern) = patterns;
+    let (body_var, body_expr) = body;
+
+    let (fn_var, fn_clos_var) = {
+        // Create fn_var for ambient capture; we fix it up below.
+        let fn_var = synth_var(env.subs, Content::Error);
+
+        // -[fn_name]->
+        let fn_captures = vec![];
+        let fn_name_labels = UnionLambdas::insert_into_subs(env.subs, once((fn_name, fn_captures)));
+        let fn_clos_var = synth_var(
+            env.subs,
+            Content::LambdaSet(LambdaSet {
+                solved: fn_name_labels,
+                recursion_var: OptVariable::NONE,
+                unspecialized: SubsSlice::default(),
+                ambient_function: fn_var,
+            }),
+        );
+
+        // val_var, val_var -[fn_name]-> body_var
+        let args_slice = SubsSlice::insert_into_subs(env.subs, [val_var, val_var]);
+        env.subs.set_content(
+            fn_var,
+            Content::Structure(FlatType::Func(args_slice, fn_clos_var, body_var)),
+        );
+
+        (fn_var, fn_clos_var)
+    };
+
+    let clos_expr = Expr::Closure(ClosureData {
+        function_type: fn_var,
+        closure_type: fn_clos_var,
+        return_type: body_var,
+        name: fn_name,
+        captured_symbols: vec![],
+        recursive: Recursive::NotRecursive,
+        arguments: vec![
+            (
+                val_var,
+                AnnotatedMark::known_exhaustive(),
+                Loc::at_zero(left_pattern),
+            ),
+            (
+                val_var,
+                AnnotatedMark::known_exhaustive(),
+                Loc::at_zero(right_pattern),
+            ),
+        ],
+        loc_body: Box::new(Loc::at_zero(body_expr)),
+    });
+
+    (fn_var, clos_expr)
+}
@@ -0,0 +1,397 @@
+//! Derivers for the `Inspect` ability.
+
+use std::iter::once;
+
+use roc_can::{
+    expr::{AnnotatedMark, ClosureData, Expr, Recursive, WhenBranch, WhenBranchPattern},
+    pattern::Pattern,
+};
+use roc_derive_key::inspect::FlatInspectKey;
+use roc_module::{
+    called_via::CalledVia,
+    ident::{Lowercase, TagName},
+    symbol::Symbol,
+};
+use roc_region::all::{Loc, Region};
+use roc_types::{
+    subs::{
+        Content, ExhaustiveMark, FlatType, GetSubsSlice, LambdaSet, OptVariable, RecordFields,
+        RedundantMark, SubsSlice, TagExt, TupleElems, UnionLambdas, UnionTags, Variable,
+        VariableSubsSlice,
+    },
+    types::RecordField,
+};
+
+use crate::{synth_var, util::Env, DerivedBody};
+
+pub(crate) fn derive_inspect(
+    env: &mut Env<'_>,
+    key: FlatInspectKey,
+    def_symbol: Symbol,
+) -> DerivedBody {
+    let (body_type, body) = match key {
+        FlatInspectKey::Record(fields) => inspect_record(env, def_symbol, fields),
+        FlatInspectKey::Tuple(arity) => inspect_tuple(env, def_symbol, arity),
+        FlatInspectKey::TagUnion(tags) => inspect_tag_union(env, def_symbol, tags),
+    };
+
+    let specialization_lambda_sets =
+        env.get_specialization_lambda_sets(body_type, Symbol::INSPECT_INSPECT);
+
+    DerivedBody {
+        body,
+        body_type,
+        specialization_lambda_sets,
+    }
+}
+
+fn inspect_record(env: &mut Env<'_>, fn_name: Symbol, fields: Vec<Lowercase>) -> (Variable, Expr) {
+    // Suppose rcd = { f1, ..., fn }.
+    // Build a generalized type t_rcd = { f1: t1, ..., fn: tn }, with fresh t1, ..., tn,
+    // so that we can re-use the derived impl for many records of the same fields.
+    let (record_var, record_fields) = {
+        let flex_fields = fields
+            .into_iter()
+            .map(|name| {
+                (
+                    name,
+                    RecordField::Required(env.subs.fresh_unnamed_flex_var()),
+                )
+            })
+            .collect::<Vec<(Lowercase, _)>>();
+        let fields = RecordFields::insert_into_subs(env.subs, flex_fields);
+        let record_var = synth_var(
+            env.subs,
+            Content::Structure(FlatType::Record(fields, Variable::EMPTY_RECORD)),
+        );
+
+        (record_var, fields)
+    };
+
+    // Now, a description of this record is
+    //
+    // inspect_rcd : { f1: t1, ..., fn: tn } -> Str | t1 has Inspect, ..., tn has Inspect
+    // inspect_rcd = \rcd ->
+    //   "{ f1: " ++ (Inspect.inspect rcd.f1) ++ ", " ++ ... ++ " }"
+    //
+    // where `++` is `Inspect.append`, and the empty record is described as "{}".
+    let rcd_sym = env.new_symbol("rcd");
+
+    let mut pieces = vec![];
+    for (i, (field_name, field_var, _)) in record_fields.iter_all().enumerate() {
+        let field_name = env.subs[field_name].clone();
+        let field_var = env.subs[field_var];
+
+        let separator = if i == 0 { "{ " } else { ", " };
+        pieces.push(str_literal(&format!("{}{}: ", separator, field_name)));
+
+        let field_access = Expr::RecordAccess {
+            record_var,
+            field_var,
+            ext_var: env.subs.fresh_unnamed_flex_var(),
+            loc_expr: Box::new(Loc::at_zero(Expr::Var(
+                rcd_sym,
+                env.subs.fresh_unnamed_flex_var(),
+            ))),
+            field: field_name,
+        };
+        pieces.push(call_inspect(env, field_var, field_access));
+    }
+    pieces.push(str_literal(if pieces.is_empty() { "{}" } else { " }" }));
+
+    let body = concat_all(env, pieces);
+
+    // Finally, build the closure
+    // \rcd -> body
+    build_outer_derived_closure(env, fn_name, record_var, Pattern::Identifier(rcd_sym), body)
+}
+
+fn inspect_tuple(env: &mut Env<'_>, fn_name: Symbol, arity: u32) -> (Variable, Expr) {
+    // Suppose tup = (v1, ..., vn).
+    // Build a generalized type t_tup = (t1, ..., tn), with fresh t1, ..., tn,
+    // so that we can re-use the derived impl for many tuples of the same arity.
+    let (tuple_var, tuple_elems) = {
+        let flex_elems: Vec<_> = (0..arity)
+            .map(|i| (i as usize, env.subs.fresh_unnamed_flex_var()))
+            .collect();
+        let elems = TupleElems::insert_into_subs(env.subs, flex_elems);
+        let tuple_var = synth_var(
+            env.subs,
+            Content::Structure(FlatType::Tuple(elems, Variable::EMPTY_TUPLE)),
+        );
+
+        (tuple_var, elems)
+    };
+
+    // Now, a description of this tuple is
+    //
+    // inspect_tup : (t1, ..., tn) -> Str | t1 has Inspect, ..., tn has Inspect
+    // inspect_tup = \tup ->
+    //   "(" ++ (Inspect.inspect tup.0) ++ ", " ++ ... ++ ")"
+    let tup_sym = env.new_symbol("tup");
+
+    let mut pieces = vec![str_literal("(")];
+    for (i, (elem_idx, elem_var)) in tuple_elems.iter_all().enumerate() {
+        let index = env.subs[elem_idx];
+        let elem_var = env.subs[elem_var];
+
+        if i > 0 {
+            pieces.push(str_literal(", "));
+        }
+
+        let elem_access = Expr::TupleAccess {
+            tuple_var,
+            elem_var,
+            ext_var: env.subs.fresh_unnamed_flex_var(),
+            loc_expr: Box::new(Loc::at_zero(Expr::Var(
+                tup_sym,
+                env.subs.fresh_unnamed_flex_var(),
+            ))),
+            index,
+        };
+        pieces.push(call_inspect(env, elem_var, elem_access));
+    }
+    pieces.push(str_literal(")"));
+
+    let body = concat_all(env, pieces);
+
+    // Finally, build the closure
+    // \tup -> body
+    build_outer_derived_closure(env, fn_name, tuple_var, Pattern::Identifier(tup_sym), body)
+}
+
+fn inspect_tag_union(
+    env: &mut Env<'_>,
+    fn_name: Symbol,
+    tags: Vec<(TagName, u16)>,
+) -> (Variable, Expr) {
+    // Suppose tags = [ A p11 .. p1n, ..., Q pq1 .. pqm ]
+    // Build a generalized type t_tags = [ A t11 .. t1n, ..., Q tq1 .. tqm ],
+    // with fresh t1, ..., tqm, so that we can re-use the derived impl for many
+    // unions of the same tags and payloads.
+    let (union_var, union_tags) = {
+        let flex_tag_labels = tags
+            .into_iter()
+            .map(|(label, arity)| {
+                let variables_slice = VariableSubsSlice::reserve_into_subs(env.subs, arity.into());
+                for var_index in variables_slice {
+                    env.subs[var_index] = env.subs.fresh_unnamed_flex_var();
+                }
+                (label, variables_slice)
+            })
+            .collect::<Vec<_>>();
+        let union_tags = UnionTags::insert_slices_into_subs(env.subs, flex_tag_labels);
+        let tag_union_var = synth_var(
+            env.subs,
+            Content::Structure(FlatType::TagUnion(
+                union_tags,
+                TagExt::Any(Variable::EMPTY_TAG_UNION),
+            )),
+        );
+
+        (tag_union_var, union_tags)
+    };
+
+    // Now, a description of this tag union is
+    //
+    // inspect_union : [ A t11 .. t1n, ..., Q tq1 .. tqm ] -> Str
+    // inspect_union = \union ->
+    //   when union is
+    //      A x11 .. x1n ->
+    //          "A" ++ " " ++ (Inspect.parenthesize (Inspect.inspect x11)) ++ ... ++ " " ++ (...)
+    //      ...
+    //      Q xq1 .. xqm -> ...
+    let union_sym = env.new_symbol("union");
+
+    let branches = union_tags
+        .iter_all()
+        .map(|(tag, payloads)| {
+            // A
+            let tag_name = env.subs[tag].clone();
+            // t11 .. t1n
+            let payload_vars = env.subs.get_subs_slice(env.subs[payloads]).to_vec();
+            // x11 .. x1n
+            let payload_syms: Vec<_> = std::iter::repeat_with(|| env.unique_symbol())
+                .take(payload_vars.len())
+                .collect();
+
+            let mut pieces = vec![str_literal(tag_name.0.as_str())];
+            for (var, sym) in payload_vars.iter().zip(payload_syms.iter()) {
+                let inspected = call_inspect(env, *var, Expr::Var(*sym, *var));
+                let parenthesized = call_str_fn(env, Symbol::INSPECT_PARENTHESIZE, vec![inspected]);
+
+                pieces.push(str_literal(" "));
+                pieces.push(parenthesized);
+            }
+            let (_, body) = concat_all(env, pieces);
+
+            let pattern = Pattern::AppliedTag {
+                whole_var: union_var,
+                tag_name,
+                ext_var: Variable::EMPTY_TAG_UNION,
+                arguments: (payload_vars.into_iter())
+                    .zip(payload_syms)
+                    .map(|(var, sym)| (var, Loc::at_zero(Pattern::Identifier(sym))))
+                    .collect(),
+            };
+
+            WhenBranch {
+                patterns: vec![WhenBranchPattern {
+                    pattern: Loc::at_zero(pattern),
+                    degenerate: false,
+                }],
+                value: Loc::at_zero(body),
+                guard: None,
+                redundant: RedundantMark::known_non_redundant(),
+            }
+        })
+        .collect();
+
+    // when union is
+    //   ...
+    let when_expr = Expr::When {
+        loc_cond: Box::new(Loc::at_zero(Expr::Var(union_sym, union_var))),
+        cond_var: union_var,
+        expr_var: Variable::STR,
+        region: Region::zero(),
+        branches,
+        branches_cond_var: union_var,
+        exhaustive: ExhaustiveMark::known_exhaustive(),
+    };
+
+    // Finally, build the closure
+    // \union -> body
+    build_outer_derived_closure(
+        env,
+        fn_name,
+        union_var,
+        Pattern::Identifier(union_sym),
+        (Variable::STR, when_expr),
+    )
+}
+
+fn str_literal(s: &str) -> (Variable, Expr) {
+    (Variable::STR, Expr::Str(s.into()))
+}
+
+/// Folds `Inspect.append` over a non-empty list of string expressions, so that they are joined in
+/// order.
+fn concat_all(env: &mut Env<'_>, pieces: Vec<(Variable, Expr)>) -> (Variable, Expr) {
+    let mut pieces = pieces.into_iter();
+    let first = pieces
+        .next()
+        .expect("a derived description must have at least one piece");
+
+    pieces.fold(first, |joined, next| {
+        call_str_fn(env, Symbol::INSPECT_APPEND, vec![joined, next])
+    })
+}
+
+/// `Inspect.inspect val`
+fn call_inspect(env: &mut Env<'_>, val_var: Variable, val: Expr) -> (Variable, Expr) {
+    call_str_fn(env, Symbol::INSPECT_INSPECT, vec![(val_var, val)])
+}
+
+fn call_str_fn(
+    env: &mut Env<'_>,
+    function: Symbol,
+    arguments: Vec<(Variable, Expr)>,
+) -> (Variable, Expr) {
+    // build `function ...` function type. `function` here is `Inspect.inspect`, or a plain
+    // function returning a `Str`, like `Inspect.append`.
+    //
+    // val -[uls]-> Str | val has Inspect
+    let exposed_fn_var = env.import_builtin_symbol_var(function);
+
+    // (typeof arguments) -[clos]-> Str
+    let this_arguments_slice =
+        VariableSubsSlice::insert_into_subs(env.subs, arguments.iter().map(|(var, _)| *var));
+    let this_clos_var = env.subs.fresh_unnamed_flex_var();
+    let this_fn_var = synth_var(
+        env.subs,
+        Content::Structure(FlatType::Func(
+            this_arguments_slice,
+            this_clos_var,
+            Variable::STR,
+        )),
+    );
+
+    //   val             -[uls]->  Str | val has Inspect
+    // ~ (typeof val)    -[clos]-> Str
+    env.unify(exposed_fn_var, this_fn_var);
+
+    // Only `inspect` is an ability member; the others are plain functions.
+    let fn_head = if function == Symbol::INSPECT_INSPECT {
+        Expr::AbilityMember(function, None, this_fn_var)
+    } else {
+        Expr::Var(function, this_fn_var)
+    };
+    let fn_data = Box::new((
+        this_fn_var,
+        Loc::at_zero(fn_head),
+        this_clos_var,
+        Variable::STR,
+    ));
+
+    let arguments = arguments
+        .into_iter()
+        .map(|(var, expr)| (var, Loc::at_zero(expr)))
+        .collect();
+    let call = Expr::Call(fn_data, arguments, CalledVia::Space);
+
+    (Variable::STR, call)
+}
+
+fn build_outer_derived_closure(
+    env: &mut Env<'_>,
+    fn_name: Symbol,
+    val_var: Variable,
+    pattern: Pattern,
+    body: (Variable, Expr),
+) -> (Variable, Expr) {
+    let (body_var, body_expr) = body;
+
+    let (fn_var, fn_clos_var) = {
+        // Create fn_var for ambient capture; we fix it up below.
+        let fn_var = synth_var(env.subs, Content::Error);
+
+        // -[fn_name]->
+        let fn_captures = vec![];
+        let fn_name_labels = UnionLambdas::insert_into_subs(env.subs, once((fn_name, fn_captures)));
+        let fn_clos_var = synth_var(
+            env.subs,
+            Content::LambdaSet(LambdaSet {
+                solved: fn_name_labels,
+                recursion_var: OptVariable::NONE,
+                unspecialized: SubsSlice::default(),
+                ambient_function: fn_var,
+            }),
+        );
+
+        // val_var -[fn_name]-> body_var
+        let args_slice = SubsSlice::insert_into_subs(env.subs, [val_var]);
+        env.subs.set_content(
+            fn_var,
+            Content::Structure(FlatType::Func(args_slice, fn_clos_var, body_var)),
+        );
+
+        (fn_var, fn_clos_var)
+    };
+
+    let clos_expr = Expr::Closure(ClosureData {
+        function_type: fn_var,
+        closure_type: fn_clos_var,
+        return_type: body_var,
+        name: fn_name,
+        captured_symbols: vec![],
+        recursive: Recursive::NotRecursive,
+        arguments: vec![(
+            val_var,
+            AnnotatedMark::known_exhaustive(),
+            Loc::at_zero(pattern),
+        )],
+        loc_body: Box::new(Loc::at_zero(body_expr)),
+    });
+
+    (fn_var, clos_expr)
+}
@@ -18,7 +18,9 @@ use util::Env;
 
 mod decoding;
 mod encoding;
+mod eq;
 mod hash;
+mod inspect;
 
 mod util;
 
@@ -79,6 +81,10 @@ fn build_derived_body(
             decoding::derive_decoder(&mut env, decoder_key, derived_symbol)
         }
         DeriveKey::Hash(hash_key) => hash::derive_hash(&mut env, hash_key, derived_symbol),
+        DeriveKey::IsEq(eq_key) => eq::derive_eq(&mut env, eq_key, derived_symbol),
+        DeriveKey::Inspect(inspect_key) => {
+            inspect::derive_inspect(&mut env, inspect_key, derived_symbol)
+        }
     };
 
     let def = Def {
@@ -0,0 +1,182 @@
+use roc_module::{
+    ident::{Lowercase, TagName},
+    symbol::{ModuleId, Symbol},
+};
+use roc_types::{
+    subs::{Content, FlatType, GetSubsSlice, Subs, Variable},
+    types::AliasKind,
+};
+
+use crate::{
+    util::{check_derivable_ext_var, debug_name_record, debug_name_tag, debug_name_tuple},
+    DeriveError,
+};
+
+#[derive(Hash)]
+pub enum FlatEq {
+    // `isEq` is always of form `a, a -> Bool`, so all immediates must have exactly one lambda set!
+    SingleLambdaSetImmediate(Symbol),
+    Key(FlatEqKey),
+}
+
+/// Types that may contain an opaque type with its own `isEq` are compared by calling `isEq` on
+/// each of their components, so that the custom implementation is used for that opaque.
+#[derive(Hash, PartialEq, Eq, Debug, Clone)]
+pub enum FlatEqKey {
+    // Unfortunate that we must allocate here, c'est la vie
+    Record(Vec<Lowercase>),
+    Tuple(u32),
+    TagUnion(Vec<(TagName, u16)>),
+}
+
+impl FlatEqKey {
+    pub(crate) fn debug_name(&self) -> String {
+        match self {
+            FlatEqKey::Record(fields) => debug_name_record(fields),
+            FlatEqKey::Tuple(arity) => debug_name_tuple(*arity),
+            FlatEqKey::TagUnion(tags) => debug_name_tag(tags),
+        }
+    }
+}
+
+impl FlatEq {
+    pub(crate) fn from_var(subs: &Subs, var: Variable) -> Result<FlatEq, DeriveError> {
+        use DeriveError::*;
+        use FlatEq::*;
+
+        // If obligation checking passes, we lower derived implementations of `isEq` for types
+        // that cannot contain a custom `isEq` to the `Eq` low-level, to be fulfilled by the
+        // backends.
+        if is_structurally_comparable(subs, var, &mut Vec::new()) {
+            return Ok(SingleLambdaSetImmediate(Symbol::BOOL_STRUCTURAL_EQ));
+        }
+
+        match *subs.get_content_without_compacting(var) {
+            Content::Structure(flat_type) => match flat_type {
+                // Containers compare their elements with `isEq`, through a builtin for each.
+                FlatType::Apply(symbol, _) => match symbol {
+                    Symbol::LIST_LIST => Ok(SingleLambdaSetImmediate(Symbol::LIST_IS_EQ)),
+                    Symbol::BOX_BOX_TYPE => Ok(SingleLambdaSetImmediate(Symbol::BOX_IS_EQ)),
+                    _ => Err(Underivable),
+                },
+                FlatType::Record(fields, ext) => {
+                    let (fields_iter, ext) = fields.unsorted_iterator_and_ext(subs, ext);
+
+                    check_derivable_ext_var(subs, ext, |ext| {
+                        matches!(ext, Content::Structure(FlatType::EmptyRecord))
+                    })?;
+
+                    let mut field_names = Vec::with_capacity(fields.len());
+                    for (field_name, record_field) in fields_iter {
+                        if record_field.is_optional() {
+                            return Err(Underivable);
+                        }
+                        field_names.push(field_name.clone());
+                    }
+
+                    field_names.sort();
+
+                    Ok(Key(FlatEqKey::Record(field_names)))
+                }
+                FlatType::Tuple(elems, ext) => {
+                    let (elems_iter, ext) = elems.sorted_iterator_and_ext(subs, ext);
+
+                    check_derivable_ext_var(subs, ext, |ext| {
+                        matches!(ext, Content::Structure(FlatType::EmptyTuple))
+                    })?;
+
+                    Ok(Key(FlatEqKey::Tuple(elems_iter.count() as _)))
+                }
+                FlatType::TagUnion(tags, ext) | FlatType::RecursiveTagUnion(_, tags, ext) => {
+                    // Like for `Hash`, only the surface of the tag union matters; the payloads
+                    // are compared with `isEq` at their own types.
+                    let (tags_iter, ext) = tags.unsorted_tags_and_ext(subs, ext);
+
+                    check_derivable_ext_var(subs, ext.var(), |ext| {
+                        matches!(ext, Content::Structure(FlatType::EmptyTagUnion))
+                    })?;
+
+                    let mut tag_names_and_payload_sizes: Vec<_> = tags_iter
+                        .tags
+                        .into_iter()
+                        .map(|(name, payload_slice)| {
+                            let payload_size = payload_slice.len();
+                            (name.clone(), payload_size as _)
+                        })
+                        .collect();
+
+                    tag_names_and_payload_sizes.sort_by(|(t1, _), (t2, _)| t1.cmp(t2));
+
+                    Ok(Key(FlatEqKey::TagUnion(tag_names_and_payload_sizes)))
+                }
+                _ => Ok(SingleLambdaSetImmediate(Symbol::BOOL_STRUCTURAL_EQ)),
+            },
+            // NB: like for `Hash`, it is okay to unwrap opaques here because derivers are only
+            // used by the backend, and the backend treats opaques like structural aliases.
+            Content::Alias(_, _, real_var, _) => Self::from_var(subs, real_var),
+            Content::RecursionVar { structure, .. } => Self::from_var(subs, structure),
+            _ => Ok(SingleLambdaSetImmediate(Symbol::BOOL_STRUCTURAL_EQ)),
+        }
+    }
+}
+
+/// Whether `var` is fully known and cannot contain an opaque type with a custom `isEq`, so that
+/// comparing its values structurally is the same as comparing them with `isEq`.
+fn is_structurally_comparable(subs: &Subs, var: Variable, seen: &mut Vec<Variable>) -> bool {
+    let root = subs.get_root_key_without_compacting(var);
+    if seen.contains(&root) {
+        return true;
+    }
+    seen.push(root);
+
+    let all = |vars: &[Variable], seen: &mut Vec<Variable>| {
+        vars.iter()
+            .all(|var| is_structurally_comparable(subs, *var, seen))
+    };
+
+    // The extension of a record, tuple or tag union is either empty or unknown; in both cases
+    // only the known part of the type is compared, so it does not matter here.
+    match *subs.get_content_without_compacting(var) {
+        Content::Structure(flat_type) => match flat_type {
+            FlatType::Apply(_, args) => all(subs.get_subs_slice(args), seen),
+            FlatType::Record(fields, ext) => {
+                let (fields_iter, _) = fields.unsorted_iterator_and_ext(subs, ext);
+                let field_vars: Vec<_> = fields_iter.map(|(_, field)| field.into_inner()).collect();
+
+                all(&field_vars, seen)
+            }
+            FlatType::Tuple(elems, ext) => {
+                let (elems_iter, _) = elems.sorted_iterator_and_ext(subs, ext);
+                let elem_vars: Vec<_> = elems_iter.map(|(_, elem_var)| elem_var).collect();
+
+                all(&elem_vars, seen)
+            }
+            FlatType::TagUnion(tags, ext) | FlatType::RecursiveTagUnion(_, tags, ext) => {
+                let (tags_iter, _) = tags.unsorted_tags_and_ext(subs, ext);
+                let payload_vars: Vec<_> = (tags_iter.tags.iter())
+                    .flat_map(|(_, payloads)| payloads.iter().copied())
+                    .collect();
+
+                all(&payload_vars, seen)
+            }
+            FlatType::FunctionOrTagUnion(..)
+            | FlatType::EmptyRecord
+            | FlatType::EmptyTuple
+            | FlatType::EmptyTagUnion
+            | FlatType::Func(..) => true,
+        },
+        Content::Alias(symbol, _, real_var, kind) => {
+            let has_structural_eq = matches!(kind, AliasKind::Structural)
+                || matches!(symbol.module_id(), ModuleId::NUM | ModuleId::BOOL);
+
+            has_structural_eq && all(&[real_var], seen)
+        }
+        Content::RecursionVar { structure, .. } => all(&[structure], seen),
+        Content::RangedNumber(_) | Content::LambdaSet(_) | Content::Error => true,
+        // We don't know yet what this will be, so it might be an opaque with a custom `isEq`.
+        Content::FlexVar(_)
+        | Content::RigidVar(_)
+        | Content::FlexAbleVar(..)
+        | Content::RigidAbleVar(..) => false,
+    }
+}
@@ -15,12 +15,16 @@
 
 pub mod decoding;
 pub mod encoding;
+pub mod eq;
 pub mod hash;
+pub mod inspect;
 mod util;
 
 use decoding::{FlatDecodable, FlatDecodableKey};
 use encoding::{FlatEncodable, FlatEncodableKey};
+use eq::{FlatEq, FlatEqKey};
 use hash::{FlatHash, FlatHashKey};
+use inspect::{FlatInspect, FlatInspectKey};
 
 use roc_module::symbol::Symbol;
 use roc_types::subs::{Subs, Variable};
@@ -40,6 +44,8 @@ pub enum DeriveKey {
     ToEncoder(FlatEncodableKey),
     Decoder(FlatDecodableKey),
     Hash(FlatHashKey),
+    IsEq(FlatEqKey),
+    Inspect(FlatInspectKey),
 }
 
 impl DeriveKey {
@@ -48,6 +54,8 @@ impl DeriveKey {
             DeriveKey::ToEncoder(key) => format!("toEncoder_{}", key.debug_name()),
             DeriveKey::Decoder(key) => format!("decoder_{}", key.debug_name()),
             DeriveKey::Hash(key) => format!("hash_{}", key.debug_name()),
+            DeriveKey::IsEq(key) => format!("isEq_{}", key.debug_name()),
+            DeriveKey::Inspect(key) => format!("inspect_{}", key.debug_name()),
         }
     }
 }
@@ -77,6 +85,7 @@ pub enum DeriveBuiltin {
     Decoder,
     Hash,
     IsEq,
+    Inspect,
 }
 
 impl TryFrom<Symbol> for DeriveBuiltin {
@@ -88,6 +97,7 @@ impl TryFrom<Symbol> for DeriveBuiltin {
             Symbol::DECODE_DECODER => Ok(DeriveBuiltin::Decoder),
             Symbol::HASH_HASH => Ok(DeriveBuiltin::Hash),
             Symbol::BOOL_IS_EQ => Ok(DeriveBuiltin::IsEq),
+            Symbol::INSPECT_INSPECT => Ok(DeriveBuiltin::Inspect),
             _ => Err(value),
         }
     }
@@ -114,13 +124,16 @@ impl Derived {
                 }
                 FlatHash::Key(repr) => Ok(Derived::Key(DeriveKey::Hash(repr))),
             },
-            DeriveBuiltin::IsEq => {
-                // If obligation checking passes, we always lower derived implementations of `isEq`
-                // to the `Eq` low-level, to be fulfilled by the backends.
-                Ok(Derived::SingleLambdaSetImmediate(
-                    Symbol::BOOL_STRUCTURAL_EQ,
-                ))
-            }
+            DeriveBuiltin::IsEq => match eq::FlatEq::from_var(subs, var)? {
+                FlatEq::SingleLambdaSetImmediate(imm) => Ok(Derived::SingleLambdaSetImmediate(imm)),
+                FlatEq::Key(repr) => Ok(Derived::Key(DeriveKey::IsEq(repr))),
+            },
+            DeriveBuiltin::Inspect => match inspect::FlatInspect::from_var(subs, var)? {
+                FlatInspect::SingleLambdaSetImmediate(imm) => {
+                    Ok(Derived::SingleLambdaSetImmediate(imm))
+                }
+                FlatInspect::Key(repr) => Ok(Derived::Key(DeriveKey::Inspect(repr))),
+            },
         }
     }
 
@@ -151,6 +164,12 @@ impl Derived {
                     Symbol::BOOL_STRUCTURAL_EQ,
                 ))
             }
+            DeriveBuiltin::Inspect => match inspect::FlatInspect::from_builtin_symbol(symbol)? {
+                FlatInspect::SingleLambdaSetImmediate(imm) => {
+                    Ok(Derived::SingleLambdaSetImmediate(imm))
+                }
+                FlatInspect::Key(repr) => Ok(Derived::Key(DeriveKey::Inspect(repr))),
+            },
         }
     }
 }
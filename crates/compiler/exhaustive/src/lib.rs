@@ -160,6 +160,15 @@ impl Error {
             Error::Unmatchable { .. } => Warning,
         }
     }
+
+    pub fn region(&self) -> Region {
+        match self {
+            Error::Incomplete(region, ..) => *region,
+            Error::Redundant { branch_region, .. } | Error::Unmatchable { branch_region, .. } => {
+                *branch_region
+            }
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
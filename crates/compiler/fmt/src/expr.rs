@@ -503,10 +503,22 @@ fn is_str_multiline(literal: &StrLiteral) -> bool {
     }
 }
 
-fn needs_unicode_escape(ch: char) -> bool {
+/// Control characters are written as `\u(...)` in literals.
+pub fn needs_unicode_escape(ch: char) -> bool {
     matches!(ch, '\u{0000}'..='\u{001f}' | '\u{007f}'..='\u{009f}')
 }
 
+/// The escape sequence used in literals for characters that have a short one, e.g. `\n`.
+pub fn short_escape(ch: char) -> Option<&'static str> {
+    match ch {
+        '\t' => Some("\\t"),
+        '\r' => Some("\\r"),
+        '\n' => Some("\\n"),
+        '\\' => Some("\\\\"),
+        _ => None,
+    }
+}
+
 pub(crate) fn format_sq_literal(buf: &mut Buf, s: &str) {
     buf.push('\'');
     for c in s.chars() {
@@ -516,12 +528,10 @@ pub(crate) fn format_sq_literal(buf: &mut Buf, s: &str) {
             match c {
                 '"' => buf.push_str("\""),
                 '\'' => buf.push_str("\\\'"),
-                '\t' => buf.push_str("\\t"),
-                '\r' => buf.push_str("\\r"),
-                '\n' => buf.push_str("\\n"),
-                '\\' => buf.push_str("\\\\"),
                 _ => {
-                    if needs_unicode_escape(c) {
+                    if let Some(escaped) = short_escape(c) {
+                        buf.push_str(escaped)
+                    } else if needs_unicode_escape(c) {
                         buf.push_str(&format!("\\u({:x})", c as u32))
                     } else {
                         buf.push_char_literal(c)
@@ -10,8 +10,9 @@ pub mod module;
 pub mod pattern;
 pub mod spaces;
 
+use annotation::Formattable;
 use bumpalo::{collections::String, Bump};
-use roc_parse::ast::Module;
+use roc_parse::ast::{Expr, Module, TypeDef, ValueDef};
 
 #[derive(Debug)]
 pub struct Ast<'a> {
@@ -166,3 +167,30 @@ impl<'a> Buf<'a> {
         self.spaces_to_flush == 0 && self.text.is_empty()
     }
 }
+
+/// Formats a single expression by the same rules as the module formatter, for tools that work
+/// on fragments of a file, like the editor and the repl. Every line of the result, including the
+/// first one, is indented by `indent` spaces, and there is no trailing newline.
+pub fn format_expr<'a>(arena: &'a Bump, expr: &Expr<'_>, indent: u16) -> &'a str {
+    let mut buf = Buf::new_in(arena);
+
+    expr.format(&mut buf, indent);
+
+    buf.into_bump_str()
+}
+
+/// Formats a single def, as returned by `Defs::defs`, like `format_expr` formats an expression.
+pub fn format_def<'a>(
+    arena: &'a Bump,
+    def: Result<&TypeDef<'_>, &ValueDef<'_>>,
+    indent: u16,
+) -> &'a str {
+    let mut buf = Buf::new_in(arena);
+
+    match def {
+        Ok(type_def) => type_def.format(&mut buf, indent),
+        Err(value_def) => value_def.format(&mut buf, indent),
+    }
+
+    buf.into_bump_str()
+}
@@ -9,7 +9,8 @@ use roc_error_macros::internal_error;
 use roc_module::symbol::{Interns, ModuleId, Symbol};
 use roc_mono::code_gen_help::CodeGenHelp;
 use roc_mono::ir::{
-    BranchInfo, JoinPointId, ListLiteralElement, Literal, Param, ProcLayout, SelfRecursive, Stmt,
+    BranchHint, BranchInfo, JoinPointId, ListLiteralElement, Literal, Param, ProcLayout,
+    SelfRecursive, Stmt,
 };
 use roc_mono::layout::{
     Builtin, InLayout, Layout, LayoutInterner, STLayoutInterner, TagIdIntType, UnionLayout,
@@ -258,6 +259,7 @@ pub trait Assembler<GeneralReg: RegTrait, FloatReg: RegTrait>: Sized + Copy {
     fn mov_reg8_base32(buf: &mut Vec<'_, u8>, dst: GeneralReg, offset: i32);
 
     fn mov_base32_freg64(buf: &mut Vec<'_, u8>, offset: i32, src: FloatReg);
+    fn mov_base32_freg32(buf: &mut Vec<'_, u8>, offset: i32, src: FloatReg);
 
     fn mov_base32_reg64(buf: &mut Vec<'_, u8>, offset: i32, src: GeneralReg);
     fn mov_base32_reg32(buf: &mut Vec<'_, u8>, offset: i32, src: GeneralReg);
@@ -813,6 +815,7 @@ impl<
         _cond_layout: &InLayout<'a>, // cond_layout must be a integer due to potential jump table optimizations.
         branches: &'a [(u64, BranchInfo<'a>, Stmt<'a>)],
         default_branch: &(BranchInfo<'a>, &'a Stmt<'a>),
+        branch_hints: &'a [BranchHint],
         ret_layout: &InLayout<'a>,
     ) {
         // Switches are a little complex due to keeping track of jumps.
@@ -830,7 +833,14 @@ impl<
         let mut max_branch_stack_size = 0;
         let mut ret_jumps = bumpalo::vec![in self.env.arena];
         let mut tmp = bumpalo::vec![in self.env.arena];
-        for (val, _branch_info, stmt) in branches.iter() {
+
+        // The branches are compared one after the other, so compare against the likely ones first.
+        // Their values are distinct, so this does not change which branch is taken.
+        let mut ordered_branches = bumpalo::vec![in self.env.arena];
+        ordered_branches.extend(branches.iter().enumerate());
+        ordered_branches.sort_by_key(|(index, _)| BranchHint::of(branch_hints, *index));
+
+        for (_, (val, _branch_info, stmt)) in ordered_branches {
             // TODO: look into branch info and if it matters here.
             tmp.clear();
             // Create jump to next branch if cond_sym not equal to value.
@@ -1639,6 +1649,9 @@ impl<
         // Load element_width argument (usize).
         self.load_layout_stack_size(*ret_layout, Symbol::DEV_TMP2);
 
+        // Load inc_element argument (?IncN).
+        self.load_inc_element_fn(Symbol::DEV_TMP5);
+
         // Load UpdateMode.Immutable argument (0u8)
         let u8_layout = Layout::U8;
         let update_mode = 0u8;
@@ -1661,6 +1674,8 @@ impl<
             spare,
             // element_width
             Symbol::DEV_TMP2,
+            // inc_element
+            Symbol::DEV_TMP5,
             // update_mode
             Symbol::DEV_TMP3,
 
@@ -1670,6 +1685,7 @@ impl<
             Layout::U32,
             spare_layout,
             Layout::U64,
+            Layout::U64,
             u8_layout,
         ];
 
@@ -1683,6 +1699,7 @@ impl<
         self.free_symbol(&Symbol::DEV_TMP);
         self.free_symbol(&Symbol::DEV_TMP2);
         self.free_symbol(&Symbol::DEV_TMP3);
+        self.free_symbol(&Symbol::DEV_TMP5);
 
         // Return list value from fn call
         self.storage_manager.copy_symbol_to_stack_offset(
@@ -1834,6 +1851,9 @@ impl<
         // Load the elements size.
         self.load_layout_stack_size(elem_layout, Symbol::DEV_TMP3);
 
+        // Load inc_element argument (?IncN).
+        self.load_inc_element_fn(Symbol::DEV_TMP6);
+
         // Setup the return location.
         let base_offset = self
             .storage_manager
@@ -1878,6 +1898,7 @@ impl<
             Symbol::DEV_TMP2,
             Symbol::DEV_TMP3,
             Symbol::DEV_TMP4,
+            Symbol::DEV_TMP6,
          ];
         let lowlevel_arg_layouts = [
             list_layout,
@@ -1886,6 +1907,7 @@ impl<
             u64_layout,
             u64_layout,
             u64_layout,
+            u64_layout,
         ];
 
         self.build_fn_call(
@@ -1899,6 +1921,7 @@ impl<
         self.free_symbol(&Symbol::DEV_TMP2);
         self.free_symbol(&Symbol::DEV_TMP3);
         self.free_symbol(&Symbol::DEV_TMP4);
+        self.free_symbol(&Symbol::DEV_TMP6);
 
         // Copy from list to the output record.
         self.storage_manager.copy_symbol_to_stack_offset(
@@ -1931,6 +1954,9 @@ impl<
         // Load element_width argument (usize).
         self.load_layout_stack_size(elem_layout, Symbol::DEV_TMP2);
 
+        // Load inc_element argument (?IncN).
+        self.load_inc_element_fn(Symbol::DEV_TMP4);
+
         // Setup the return location.
         let base_offset = self
             .storage_manager
@@ -1944,8 +1970,16 @@ impl<
             Symbol::DEV_TMP,
             // element_width
             Symbol::DEV_TMP2,
+            // inc_element
+            Symbol::DEV_TMP4,
          ];
-        let lowlevel_arg_layouts = [list_a_layout, list_b_layout, Layout::U32, Layout::U64];
+        let lowlevel_arg_layouts = [
+            list_a_layout,
+            list_b_layout,
+            Layout::U32,
+            Layout::U64,
+            Layout::U64,
+        ];
 
         self.build_fn_call(
             &Symbol::DEV_TMP3,
@@ -1957,6 +1991,7 @@ impl<
 
         self.free_symbol(&Symbol::DEV_TMP);
         self.free_symbol(&Symbol::DEV_TMP2);
+        self.free_symbol(&Symbol::DEV_TMP4);
 
         // Return list value from fn call
         self.storage_manager.copy_symbol_to_stack_offset(
@@ -1999,6 +2034,9 @@ impl<
         // Load element_witdh argument (usize).
         self.load_layout_stack_size(elem_layout, Symbol::DEV_TMP3);
 
+        // Load inc_element argument (?IncN).
+        self.load_inc_element_fn(Symbol::DEV_TMP5);
+
         // Setup the return location.
         let base_offset = self
             .storage_manager
@@ -2013,8 +2051,16 @@ impl<
             Symbol::DEV_TMP2,
             // element_width
             Symbol::DEV_TMP3,
+            // inc_element
+            Symbol::DEV_TMP5,
          ];
-        let lowlevel_arg_layouts = [list_layout, Layout::U32, Layout::U64, Layout::U64];
+        let lowlevel_arg_layouts = [
+            list_layout,
+            Layout::U32,
+            Layout::U64,
+            Layout::U64,
+            Layout::U64,
+        ];
 
         self.build_fn_call(
             &Symbol::DEV_TMP4,
@@ -2026,6 +2072,7 @@ impl<
         self.free_symbol(&Symbol::DEV_TMP);
         self.free_symbol(&Symbol::DEV_TMP2);
         self.free_symbol(&Symbol::DEV_TMP3);
+        self.free_symbol(&Symbol::DEV_TMP5);
 
         // Return list value from fn call
         self.storage_manager.copy_symbol_to_stack_offset(
@@ -2851,6 +2898,16 @@ impl<
 
         self.load_literal(&symbol, &u64_layout, &width_literal);
     }
+
+    /// Loads the function that zig uses to increment the elements of a shared list it copies
+    fn load_inc_element_fn(&mut self, symbol: Symbol) {
+        // TODO: pass the increment function of refcounted elements, once the dev backend can pass
+        // function pointers to zig. A null pointer means zig only copies the bytes.
+        let u64_layout = Layout::U64;
+        let null_literal = Literal::Int(0i128.to_ne_bytes());
+
+        self.load_literal(&symbol, &u64_layout, &null_literal);
+    }
 }
 
 #[macro_export]
@@ -14,8 +14,8 @@ use roc_module::low_level::{LowLevel, LowLevelWrapperType};
 use roc_module::symbol::{Interns, ModuleId, Symbol};
 use roc_mono::code_gen_help::CodeGenHelp;
 use roc_mono::ir::{
-    BranchInfo, CallType, Expr, JoinPointId, ListLiteralElement, Literal, Param, Proc, ProcLayout,
-    SelfRecursive, Stmt,
+    BranchHint, BranchInfo, CallType, Expr, JoinPointId, ListLiteralElement, Literal, Param, Proc,
+    ProcLayout, SelfRecursive, Stmt,
 };
 use roc_mono::layout::{
     Builtin, InLayout, Layout, LayoutIds, LayoutInterner, STLayoutInterner, TagIdIntType,
@@ -225,6 +225,7 @@ trait Backend<'a> {
                 cond_layout,
                 branches,
                 default_branch,
+                branch_hints,
                 ret_layout,
             } => {
                 self.load_literal_symbols(&[*cond_symbol]);
@@ -233,6 +234,7 @@ trait Backend<'a> {
                     cond_layout,
                     branches,
                     default_branch,
+                    branch_hints,
                     ret_layout,
                 );
                 self.free_symbols(stmt);
@@ -275,6 +277,7 @@ trait Backend<'a> {
         cond_layout: &InLayout<'a>,
         branches: &'a [(u64, BranchInfo<'a>, Stmt<'a>)],
         default_branch: &(BranchInfo<'a>, &'a Stmt<'a>),
+        branch_hints: &'a [BranchHint],
         ret_layout: &InLayout<'a>,
     );
 
@@ -392,6 +395,14 @@ trait Backend<'a> {
                 self.load_literal_symbols(syms.into_bump_slice());
                 self.create_array(sym, elem_layout, elems);
             }
+            Expr::StaticArray { elem_layout, elems } => {
+                // TODO: put these in the read-only data section instead of allocating them.
+                let elems = Vec::from_iter_in(
+                    elems.iter().map(|lit| ListLiteralElement::Literal(*lit)),
+                    self.env().arena,
+                );
+                self.create_array(sym, elem_layout, elems.into_bump_slice());
+            }
             Expr::Struct(fields) => {
                 self.load_literal_symbols(fields);
                 self.create_struct(sym, layout, fields);
@@ -1605,7 +1616,7 @@ trait Backend<'a> {
                     Expr::Reset { symbol, .. } | Expr::ResetRef { symbol, .. } => {
                         self.set_last_seen(*symbol, stmt);
                     }
-                    Expr::EmptyArray => {}
+                    Expr::StaticArray { .. } | Expr::EmptyArray => {}
                     Expr::RuntimeErrorFunction(_) => {}
                 }
                 self.scan_ast(following);
@@ -3,7 +3,7 @@ use crate::llvm::build_list::{self, allocate_list, empty_polymorphic_list};
 use crate::llvm::convert::{
     argument_type_from_layout, basic_type_from_builtin, basic_type_from_layout, zig_str_type,
 };
-use crate::llvm::expect::{clone_to_shared_memory, SharedMemoryPointer};
+use crate::llvm::expect::{clone_to_shared_memory, verify_failure_message, SharedMemoryPointer};
 use crate::llvm::refcounting::{
     build_reset, decrement_refcount_layout, increment_refcount_layout, PointerToRefcount,
 };
@@ -39,8 +39,8 @@ use roc_debug_flags::dbg_do;
 use roc_debug_flags::ROC_PRINT_LLVM_FN_VERIFICATION;
 use roc_module::symbol::{Interns, ModuleId, Symbol};
 use roc_mono::ir::{
-    BranchInfo, CallType, CrashTag, EntryPoint, GlueLayouts, HostExposedLambdaSet, JoinPointId,
-    ListLiteralElement, ModifyRc, OptLevel, ProcLayout, SingleEntryPoint,
+    BranchHint, BranchInfo, CallType, CrashTag, EntryPoint, GlueLayouts, HostExposedLambdaSet,
+    JoinPointId, ListLiteralElement, ModifyRc, OptLevel, ProcLayout, SingleEntryPoint,
 };
 use roc_mono::layout::{
     Builtin, InLayout, LambdaName, LambdaSet, Layout, LayoutIds, LayoutInterner, Niche,
@@ -49,7 +49,7 @@ use roc_mono::layout::{
 use roc_std::RocDec;
 use roc_target::{PtrWidth, TargetInfo};
 use std::convert::TryInto;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use target_lexicon::{Architecture, OperatingSystem, Triple};
 
 use super::convert::{struct_type_from_union_layout, RocUnion};
@@ -201,6 +201,9 @@ pub enum LlvmBackendMode {
     /// Creates a test wrapper around the main roc function to catch and report panics.
     /// Provides a testing implementation of primitives (roc_alloc, roc_panic, etc)
     BinaryGlue,
+    /// Like [LlvmBackendMode::Binary], but expects stay in the program: a failed expect calls
+    /// roc_panic with the location and source of its condition, see [Env::sources].
+    BinaryVerify,
     GenTest,
     WasmGenTest,
     CliTest,
@@ -212,6 +215,7 @@ impl LlvmBackendMode {
             LlvmBackendMode::Binary => true,
             LlvmBackendMode::BinaryDev => true,
             LlvmBackendMode::BinaryGlue => false,
+            LlvmBackendMode::BinaryVerify => true,
             LlvmBackendMode::GenTest => false,
             LlvmBackendMode::WasmGenTest => true,
             LlvmBackendMode::CliTest => false,
@@ -224,6 +228,7 @@ impl LlvmBackendMode {
             LlvmBackendMode::Binary => false,
             LlvmBackendMode::BinaryDev => false,
             LlvmBackendMode::BinaryGlue => false,
+            LlvmBackendMode::BinaryVerify => false,
             LlvmBackendMode::GenTest => true,
             LlvmBackendMode::WasmGenTest => true,
             LlvmBackendMode::CliTest => true,
@@ -235,6 +240,7 @@ impl LlvmBackendMode {
             LlvmBackendMode::Binary => false,
             LlvmBackendMode::BinaryDev => true,
             LlvmBackendMode::BinaryGlue => false,
+            LlvmBackendMode::BinaryVerify => false,
             LlvmBackendMode::GenTest => false,
             LlvmBackendMode::WasmGenTest => false,
             LlvmBackendMode::CliTest => true,
@@ -253,6 +259,11 @@ pub struct Env<'a, 'ctx, 'env> {
     pub target_info: TargetInfo,
     pub mode: LlvmBackendMode,
     pub exposed_to_host: MutSet<Symbol>,
+    /// When set, every proc panics with "stack overflow in <def>" once Roc code has used more
+    /// than this many bytes of stack, instead of running into the guard page of the host.
+    pub stack_bytes: Option<u32>,
+    /// The path and source of every module, only used by [LlvmBackendMode::BinaryVerify]
+    pub sources: Option<&'a MutMap<ModuleId, (PathBuf, Box<str>)>>,
 }
 
 impl<'a, 'ctx, 'env> Env<'a, 'ctx, 'env> {
@@ -1332,6 +1343,9 @@ pub fn build_exp_expr<'a, 'ctx, 'env>(
         Array { elem_layout, elems } => {
             list_literal(env, layout_interner, parent, scope, *elem_layout, elems)
         }
+        StaticArray { elem_layout, elems } => {
+            static_list_literal(env, layout_interner, parent, *elem_layout, elems)
+        }
         RuntimeErrorFunction(_) => todo!(),
 
         UnionAtIndex {
@@ -2382,6 +2396,92 @@ fn list_literal<'a, 'ctx, 'env>(
     }
 }
 
+/// The elements of a static list literal are stored in the constants section, after a refcount of
+/// zero. Such a list is never freed or updated in-place, so nothing has to be allocated at runtime.
+fn static_list_literal<'a, 'ctx, 'env>(
+    env: &Env<'a, 'ctx, 'env>,
+    layout_interner: &mut STLayoutInterner<'a>,
+    parent: FunctionValue<'ctx>,
+    element_layout: InLayout<'a>,
+    elems: &[roc_mono::ir::Literal<'a>],
+) -> BasicValueEnum<'ctx> {
+    let element_type = basic_type_from_layout(env, layout_interner, element_layout);
+    let element_width = layout_interner.stack_size(element_layout);
+    let ptr_width = env.target_info.ptr_width() as u32;
+    let alignment = layout_interner
+        .alignment_bytes(element_layout)
+        .max(ptr_width);
+
+    // the refcount is stored in the bytes right before the first element
+    let zero_elements = (ptr_width as f64 / element_width as f64).ceil() as usize;
+
+    let mut global_elements = Vec::with_capacity_in(zero_elements + elems.len(), env.arena);
+
+    for _ in 0..zero_elements {
+        global_elements.push(element_type.const_zero());
+    }
+
+    for literal in elems {
+        global_elements.push(build_exp_literal(
+            env,
+            layout_interner,
+            parent,
+            element_layout,
+            literal,
+        ));
+    }
+
+    let initializer = match element_type {
+        BasicTypeEnum::IntType(int_type) => {
+            let values = Vec::from_iter_in(
+                global_elements.iter().map(|value| value.into_int_value()),
+                env.arena,
+            );
+
+            int_type.const_array(&values)
+        }
+        BasicTypeEnum::FloatType(float_type) => {
+            let values = Vec::from_iter_in(
+                global_elements.iter().map(|value| value.into_float_value()),
+                env.arena,
+            );
+
+            float_type.const_array(&values)
+        }
+        other => unreachable!("static list literal with elements of type {:?}", other),
+    };
+
+    // use None for the address space (e.g. Const does not work)
+    let global = env.module.add_global(
+        element_type.array_type(global_elements.len() as u32),
+        None,
+        "roc__static_list_literal",
+    );
+
+    global.set_constant(true);
+    global.set_alignment(alignment);
+    global.set_unnamed_addr(true);
+    global.set_linkage(inkwell::module::Linkage::Private);
+    global.set_initializer(&initializer);
+
+    // skip the bytes of the refcount
+    let zero = env.ptr_int().const_zero();
+    let offset = env.ptr_int().const_int(zero_elements as _, false);
+
+    let ptr = unsafe {
+        env.builder.new_build_in_bounds_gep(
+            element_type,
+            global.as_pointer_value(),
+            &[zero, offset],
+            "first_element_pointer",
+        )
+    };
+
+    let list_length = env.ptr_int().const_int(elems.len() as _, false);
+
+    super::build_list::store_list(env, ptr, list_length).into()
+}
+
 pub fn load_roc_value<'a, 'ctx, 'env>(
     env: &Env<'a, 'ctx, 'env>,
     layout_interner: &mut STLayoutInterner<'a>,
@@ -2640,6 +2740,7 @@ pub fn build_exp_stmt<'a, 'ctx, 'env>(
         Switch {
             branches,
             default_branch,
+            branch_hints,
             ret_layout,
             cond_layout,
             cond_symbol,
@@ -2651,6 +2752,7 @@ pub fn build_exp_stmt<'a, 'ctx, 'env>(
                 cond_symbol: *cond_symbol,
                 branches,
                 default_branch: default_branch.1,
+                branch_hints,
                 ret_type,
             };
 
@@ -2952,6 +3054,11 @@ pub fn build_exp_stmt<'a, 'ctx, 'env>(
                         throw_internal_exception(env, parent, "An expectation failed!");
                     }
                 }
+            } else if let LlvmBackendMode::BinaryVerify = env.mode {
+                bd.position_at_end(throw_block);
+
+                let message = verify_failure_message(env, *cond_symbol, *region);
+                throw_internal_exception(env, parent, &message);
             } else {
                 bd.position_at_end(throw_block);
                 bd.build_unconditional_branch(then_block);
@@ -3020,6 +3127,11 @@ pub fn build_exp_stmt<'a, 'ctx, 'env>(
                         throw_internal_exception(env, parent, "An expectation failed!");
                     }
                 }
+            } else if let LlvmBackendMode::BinaryVerify = env.mode {
+                bd.position_at_end(throw_block);
+
+                let message = verify_failure_message(env, *cond_symbol, *region);
+                throw_internal_exception(env, parent, &message);
             } else {
                 bd.position_at_end(throw_block);
                 bd.build_unconditional_branch(then_block);
@@ -3323,9 +3435,43 @@ struct SwitchArgsIr<'a, 'ctx> {
     pub cond_layout: InLayout<'a>,
     pub branches: &'a [(u64, BranchInfo<'a>, roc_mono::ir::Stmt<'a>)],
     pub default_branch: &'a roc_mono::ir::Stmt<'a>,
+    pub branch_hints: &'a [BranchHint],
     pub ret_type: BasicTypeEnum<'ctx>,
 }
 
+fn branch_weight(hint: BranchHint) -> u64 {
+    // likely and unlikely are 2000:1, like `__builtin_expect` in clang
+    match hint {
+        BranchHint::Likely => 2000,
+        BranchHint::None => 64,
+        BranchHint::Unlikely | BranchHint::Cold => 1,
+    }
+}
+
+/// Attach `!prof` branch weights to a `br` or `switch`, so LLVM lays out the hot path contiguously.
+/// The weights are in the order of the instruction's successors.
+fn set_branch_weights<'ctx>(
+    env: &Env<'_, 'ctx, '_>,
+    instruction: InstructionValue<'ctx>,
+    hints: impl Iterator<Item = BranchHint>,
+) {
+    let context = env.context;
+
+    let mut values: std::vec::Vec<BasicMetadataValueEnum<'ctx>> =
+        vec![context.metadata_string("branch_weights").into()];
+
+    values.extend(hints.map(|hint| {
+        context
+            .i32_type()
+            .const_int(branch_weight(hint), false)
+            .into()
+    }));
+
+    instruction
+        .set_metadata(context.metadata_node(&values), context.get_kind_id("prof"))
+        .unwrap();
+}
+
 fn const_i128<'a, 'ctx, 'env>(env: &Env<'a, 'ctx, 'env>, value: i128) -> IntValue<'ctx> {
     // truncate the lower 64 bits
     let value = value as u128;
@@ -3369,6 +3515,7 @@ fn build_switch_ir<'a, 'ctx, 'env>(
         cond_symbol,
         mut cond_layout,
         default_branch,
+        branch_hints,
         ret_type,
         ..
     } = switch_args;
@@ -3423,7 +3570,21 @@ fn build_switch_ir<'a, 'ctx, 'env>(
                 let then_block = context.append_basic_block(parent, "then_block");
                 let else_block = context.append_basic_block(parent, "else_block");
 
-                builder.build_conditional_branch(cond, then_block, else_block);
+                let br = builder.build_conditional_branch(cond, then_block, else_block);
+
+                if !branch_hints.is_empty() {
+                    // the default branch comes after the single branch in the hints
+                    let (true_index, false_index) =
+                        if branches[0].0 == 1 { (0, 1) } else { (1, 0) };
+
+                    set_branch_weights(
+                        env,
+                        br,
+                        [true_index, false_index]
+                            .into_iter()
+                            .map(|index| BranchHint::of(branch_hints, index)),
+                    );
+                }
 
                 {
                     builder.position_at_end(then_block);
@@ -3496,7 +3657,20 @@ fn build_switch_ir<'a, 'ctx, 'env>(
             cases.push((int_val, block));
         }
 
-        builder.build_switch(cond, default_block, &cases);
+        let switch = builder.build_switch(cond, default_block, &cases);
+
+        if !branch_hints.is_empty() {
+            // llvm expects the weight of the default destination first
+            let default_index = branches.len();
+
+            set_branch_weights(
+                env,
+                switch,
+                std::iter::once(default_index)
+                    .chain(0..branches.len())
+                    .map(|index| BranchHint::of(branch_hints, index)),
+            );
+        }
 
         for ((_, _, branch_expr), (_, block)) in branches.iter().zip(cases) {
             builder.position_at_end(block);
@@ -4173,7 +4347,10 @@ fn expose_function_to_host_help_c_abi<'a, 'ctx, 'env>(
             )
         }
 
-        LlvmBackendMode::Binary | LlvmBackendMode::BinaryDev | LlvmBackendMode::BinaryGlue => {}
+        LlvmBackendMode::Binary
+        | LlvmBackendMode::BinaryDev
+        | LlvmBackendMode::BinaryGlue
+        | LlvmBackendMode::BinaryVerify => {}
     }
 
     // a generic version that writes the result into a passed *u8 pointer
@@ -4226,7 +4403,10 @@ fn expose_function_to_host_help_c_abi<'a, 'ctx, 'env>(
             roc_call_result_type(env, roc_function.get_type().get_return_type().unwrap()).into()
         }
 
-        LlvmBackendMode::Binary | LlvmBackendMode::BinaryDev | LlvmBackendMode::BinaryGlue => {
+        LlvmBackendMode::Binary
+        | LlvmBackendMode::BinaryDev
+        | LlvmBackendMode::BinaryGlue
+        | LlvmBackendMode::BinaryVerify => {
             basic_type_from_layout(env, layout_interner, return_layout)
         }
     };
@@ -4364,6 +4544,89 @@ pub fn get_panic_tag_ptr<'a, 'ctx, 'env>(env: &Env<'a, 'ctx, 'env>) -> PointerVa
     global.as_pointer_value()
 }
 
+/// The lowest address the stack may grow to, set by the first proc that runs on this thread.
+fn get_stack_limit_ptr<'a, 'ctx, 'env>(env: &Env<'a, 'ctx, 'env>) -> PointerValue<'ctx> {
+    let usize_type = env.ptr_int();
+
+    let global_name = "roc_stack_limit";
+    let global = env.module.get_global(global_name).unwrap_or_else(|| {
+        let global = env.module.add_global(usize_type, None, global_name);
+        global.set_initializer(&usize_type.const_zero());
+        global.set_linkage(Linkage::Internal);
+        global.set_thread_local(true);
+        global
+    });
+
+    global.as_pointer_value()
+}
+
+/// Panics with "stack overflow in <def>" when the frame of `parent` is beyond the stack limit.
+/// The stack grows down on all our targets, so the limit is `stack_bytes` below the frame of the
+/// first proc that runs.
+fn build_stack_check<'a, 'ctx, 'env>(
+    env: &Env<'a, 'ctx, 'env>,
+    parent: FunctionValue<'ctx>,
+    symbol: Symbol,
+    stack_bytes: u32,
+) {
+    let builder = env.builder;
+    let context = env.context;
+    let usize_type = env.ptr_int();
+
+    let frame_address = env
+        .call_intrinsic(
+            LLVM_FRAME_ADDRESS,
+            &[context.i32_type().const_zero().into()],
+        )
+        .into_pointer_value();
+    let stack_pointer = builder.build_ptr_to_int(frame_address, usize_type, "stack_pointer");
+
+    let limit_ptr = get_stack_limit_ptr(env);
+    let limit = builder
+        .new_build_load(usize_type, limit_ptr, "load_stack_limit")
+        .into_int_value();
+
+    let stack_bytes = usize_type.const_int(stack_bytes as u64, false);
+    let has_room = builder.build_int_compare(
+        IntPredicate::UGT,
+        stack_pointer,
+        stack_bytes,
+        "stack_has_room",
+    );
+    let fresh_limit = builder
+        .build_select(
+            has_room,
+            builder.build_int_sub(stack_pointer, stack_bytes, "fresh_limit"),
+            usize_type.const_int(1, false),
+            "select_fresh_limit",
+        )
+        .into_int_value();
+
+    let is_unset = builder.build_int_compare(
+        IntPredicate::EQ,
+        limit,
+        usize_type.const_zero(),
+        "stack_limit_unset",
+    );
+    let limit = builder
+        .build_select(is_unset, fresh_limit, limit, "stack_limit")
+        .into_int_value();
+    builder.build_store(limit_ptr, limit);
+
+    let overflowed =
+        builder.build_int_compare(IntPredicate::ULT, stack_pointer, limit, "stack_overflowed");
+
+    let overflow_block = context.append_basic_block(parent, "stack_overflow");
+    let cont_block = context.append_basic_block(parent, "stack_ok");
+    builder.build_conditional_branch(overflowed, overflow_block, cont_block);
+
+    builder.position_at_end(overflow_block);
+    let message = format!("stack overflow in {}", symbol.as_str(&env.interns));
+    throw_internal_exception(env, parent, &message);
+
+    builder.position_at_end(cont_block);
+}
+
 fn set_jump_and_catch_long_jump<'a, 'ctx, 'env>(
     env: &Env<'a, 'ctx, 'env>,
     layout_interner: &mut STLayoutInterner<'a>,
@@ -5307,7 +5570,7 @@ fn build_proc<'a, 'ctx, 'env>(
                 GenTest | WasmGenTest | CliTest => {
                     /* no host, or exposing types is not supported */
                 }
-                Binary | BinaryDev | BinaryGlue => {
+                Binary | BinaryDev | BinaryGlue | BinaryVerify => {
                     for (alias_name, hels) in aliases.iter() {
                         let ident_string = proc.name.name().as_str(&env.interns);
                         let fn_name: String = format!("{}_{}", ident_string, hels.id.0);
@@ -5337,6 +5600,10 @@ fn build_proc<'a, 'ctx, 'env>(
 
     debug_info_init!(env, fn_val);
 
+    if let Some(stack_bytes) = env.stack_bytes {
+        build_stack_check(env, fn_val, proc.name.name(), stack_bytes);
+    }
+
     // Add args to scope
     for (arg_val, (layout, arg_symbol)) in fn_val.get_param_iter().zip(args) {
         arg_val.set_name(arg_symbol.as_str(&env.interns));
@@ -6087,24 +6354,21 @@ fn define_global_str_literal<'a, 'ctx, 'env>(
         Some(current) => current,
 
         None => {
-            let size = message.bytes().len() + env.target_info.ptr_width() as usize;
-            let mut bytes = Vec::with_capacity_in(size, env.arena);
+            let refcount_bytes = env.target_info.ptr_width() as usize;
+            let mut bytes = Vec::with_capacity_in(refcount_bytes + message.len(), env.arena);
 
-            // insert NULL bytes for the refcount
-            for _ in 0..env.target_info.ptr_width() as usize {
-                bytes.push(env.context.i8_type().const_zero());
-            }
+            // NULL bytes for the refcount, then the data bytes
+            bytes.resize(refcount_bytes, 0u8);
+            bytes.extend_from_slice(message.as_bytes());
 
-            // then add the data bytes
-            for b in message.bytes() {
-                bytes.push(env.context.i8_type().const_int(b as u64, false));
-            }
+            // the bytes are stored as they are, not as one LLVM constant per byte, which makes
+            // big literals a lot cheaper to emit
+            let initializer = env.context.const_string(&bytes, false);
 
             // use None for the address space (e.g. Const does not work)
-            let typ = env.context.i8_type().array_type(bytes.len() as u32);
-            let global = module.add_global(typ, None, &name);
+            let global = module.add_global(initializer.get_type(), None, &name);
 
-            global.set_initializer(&env.context.i8_type().const_array(bytes.into_bump_slice()));
+            global.set_initializer(&initializer);
 
             // mimic the `global_string` function; we cannot use it directly because it assumes
             // strings are NULL-terminated, which means we can't store the refcount (which is 8
@@ -1,4 +1,4 @@
-use crate::llvm::bitcode::build_dec_wrapper;
+use crate::llvm::bitcode::{build_dec_wrapper, build_inc_n_wrapper};
 use crate::llvm::build::{
     allocate_with_refcount_help, cast_basic_basic, Env, RocFunctionCall, Scope,
 };
@@ -80,6 +80,33 @@ fn pass_element_as_opaque<'a, 'ctx, 'env>(
         .into()
 }
 
+/// Zig copies the elements of a shared list byte for byte. When the elements are refcounted,
+/// it uses this function to increment them, because the copy is a new owner of every element.
+/// For other elements a null pointer is passed, and copying the bytes is enough.
+fn pass_inc_element_fn<'a, 'ctx, 'env>(
+    env: &Env<'a, 'ctx, 'env>,
+    layout_interner: &mut STLayoutInterner<'a>,
+    layout_ids: &mut LayoutIds<'a>,
+    element_layout: InLayout<'a>,
+) -> BasicValueEnum<'ctx> {
+    if layout_interner.contains_refcounted(element_layout) {
+        let inc_element_fn = build_inc_n_wrapper(env, layout_interner, layout_ids, element_layout);
+
+        inc_element_fn.as_global_value().as_pointer_value().into()
+    } else {
+        let opaque_ptr_type = env.context.i8_type().ptr_type(AddressSpace::default());
+        let inc_n_type = env
+            .context
+            .void_type()
+            .fn_type(&[opaque_ptr_type.into(), env.ptr_int().into()], false);
+
+        inc_n_type
+            .ptr_type(AddressSpace::default())
+            .const_null()
+            .into()
+    }
+}
+
 pub(crate) fn layout_width<'a, 'ctx, 'env>(
     env: &Env<'a, 'ctx, 'env>,
     layout_interner: &mut STLayoutInterner<'a>,
@@ -165,6 +192,7 @@ pub(crate) fn list_get_unsafe<'a, 'ctx, 'env>(
 pub(crate) fn list_reserve<'a, 'ctx, 'env>(
     env: &Env<'a, 'ctx, 'env>,
     layout_interner: &mut STLayoutInterner<'a>,
+    layout_ids: &mut LayoutIds<'a>,
     list: BasicValueEnum<'ctx>,
     spare: BasicValueEnum<'ctx>,
     element_layout: InLayout<'a>,
@@ -177,6 +205,7 @@ pub(crate) fn list_reserve<'a, 'ctx, 'env>(
             env.alignment_intvalue(layout_interner, element_layout),
             spare,
             layout_width(env, layout_interner, element_layout),
+            pass_inc_element_fn(env, layout_interner, layout_ids, element_layout),
             pass_update_mode(env, update_mode),
         ],
         bitcode::LIST_RESERVE,
@@ -187,6 +216,7 @@ pub(crate) fn list_reserve<'a, 'ctx, 'env>(
 pub(crate) fn list_release_excess_capacity<'a, 'ctx, 'env>(
     env: &Env<'a, 'ctx, 'env>,
     layout_interner: &mut STLayoutInterner<'a>,
+    layout_ids: &mut LayoutIds<'a>,
     list: BasicValueEnum<'ctx>,
     element_layout: InLayout<'a>,
     update_mode: UpdateMode,
@@ -197,6 +227,7 @@ pub(crate) fn list_release_excess_capacity<'a, 'ctx, 'env>(
         &[
             env.alignment_intvalue(layout_interner, element_layout),
             layout_width(env, layout_interner, element_layout),
+            pass_inc_element_fn(env, layout_interner, layout_ids, element_layout),
             pass_update_mode(env, update_mode),
         ],
         bitcode::LIST_RELEASE_EXCESS_CAPACITY,
@@ -226,6 +257,7 @@ pub(crate) fn list_append_unsafe<'a, 'ctx, 'env>(
 pub(crate) fn list_prepend<'a, 'ctx, 'env>(
     env: &Env<'a, 'ctx, 'env>,
     layout_interner: &mut STLayoutInterner<'a>,
+    layout_ids: &mut LayoutIds<'a>,
     original_wrapper: StructValue<'ctx>,
     element: BasicValueEnum<'ctx>,
     element_layout: InLayout<'a>,
@@ -237,6 +269,7 @@ pub(crate) fn list_prepend<'a, 'ctx, 'env>(
             env.alignment_intvalue(layout_interner, element_layout),
             pass_element_as_opaque(env, layout_interner, element, element_layout),
             layout_width(env, layout_interner, element_layout),
+            pass_inc_element_fn(env, layout_interner, layout_ids, element_layout),
         ],
         bitcode::LIST_PREPEND,
     )
@@ -246,6 +279,7 @@ pub(crate) fn list_prepend<'a, 'ctx, 'env>(
 pub(crate) fn list_swap<'a, 'ctx, 'env>(
     env: &Env<'a, 'ctx, 'env>,
     layout_interner: &mut STLayoutInterner<'a>,
+    layout_ids: &mut LayoutIds<'a>,
     original_wrapper: StructValue<'ctx>,
     index_1: IntValue<'ctx>,
     index_2: IntValue<'ctx>,
@@ -260,6 +294,7 @@ pub(crate) fn list_swap<'a, 'ctx, 'env>(
             layout_width(env, layout_interner, element_layout),
             index_1.into(),
             index_2.into(),
+            pass_inc_element_fn(env, layout_interner, layout_ids, element_layout),
             pass_update_mode(env, update_mode),
         ],
         bitcode::LIST_SWAP,
@@ -318,7 +353,7 @@ pub(crate) fn list_drop_at<'a, 'ctx, 'env>(
 pub(crate) fn list_replace_unsafe<'a, 'ctx, 'env>(
     env: &Env<'a, 'ctx, 'env>,
     layout_interner: &mut STLayoutInterner<'a>,
-    _layout_ids: &mut LayoutIds<'a>,
+    layout_ids: &mut LayoutIds<'a>,
     list: BasicValueEnum<'ctx>,
     index: IntValue<'ctx>,
     element: BasicValueEnum<'ctx>,
@@ -353,6 +388,7 @@ pub(crate) fn list_replace_unsafe<'a, 'ctx, 'env>(
                 pass_element_as_opaque(env, layout_interner, element, element_layout),
                 layout_width(env, layout_interner, element_layout),
                 pass_as_opaque(env, element_ptr),
+                pass_inc_element_fn(env, layout_interner, layout_ids, element_layout),
             ],
             bitcode::LIST_REPLACE,
         ),
@@ -470,6 +506,7 @@ pub(crate) fn destructure<'ctx>(
 pub(crate) fn list_sort_with<'a, 'ctx, 'env>(
     env: &Env<'a, 'ctx, 'env>,
     layout_interner: &mut STLayoutInterner<'a>,
+    layout_ids: &mut LayoutIds<'a>,
     roc_function_call: RocFunctionCall<'ctx>,
     compare_wrapper: PointerValue<'ctx>,
     list: BasicValueEnum<'ctx>,
@@ -485,6 +522,7 @@ pub(crate) fn list_sort_with<'a, 'ctx, 'env>(
             roc_function_call.data_is_owned.into(),
             env.alignment_intvalue(layout_interner, element_layout),
             layout_width(env, layout_interner, element_layout),
+            pass_inc_element_fn(env, layout_interner, layout_ids, element_layout),
         ],
         bitcode::LIST_SORT_WITH,
     )
@@ -645,6 +683,7 @@ pub(crate) fn list_map4<'a, 'ctx, 'env>(
 pub(crate) fn list_concat<'a, 'ctx, 'env>(
     env: &Env<'a, 'ctx, 'env>,
     layout_interner: &mut STLayoutInterner<'a>,
+    layout_ids: &mut LayoutIds<'a>,
     list1: BasicValueEnum<'ctx>,
     list2: BasicValueEnum<'ctx>,
     element_layout: InLayout<'a>,
@@ -655,6 +694,7 @@ pub(crate) fn list_concat<'a, 'ctx, 'env>(
         &[
             env.alignment_intvalue(layout_interner, element_layout),
             layout_width(env, layout_interner, element_layout),
+            pass_inc_element_fn(env, layout_interner, layout_ids, element_layout),
         ],
         BitcodeReturns::List,
         bitcode::LIST_CONCAT,
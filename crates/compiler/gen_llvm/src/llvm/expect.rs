@@ -15,7 +15,7 @@ use roc_mono::ir::LookupType;
 use roc_mono::layout::{
     Builtin, InLayout, Layout, LayoutIds, LayoutInterner, STLayoutInterner, UnionLayout,
 };
-use roc_region::all::Region;
+use roc_region::all::{LineInfo, Region};
 
 use super::build::BuilderExt;
 use super::build::{
@@ -24,6 +24,37 @@ use super::build::{
 };
 use super::convert::struct_type_from_union_layout;
 
+/// The message of a failed expect in [LlvmBackendMode::BinaryVerify], e.g.
+///
+/// ```text
+/// An expectation failed at main.roc:12:
+///
+///     List.len items > 0
+/// ```
+pub(crate) fn verify_failure_message(env: &Env, condition: Symbol, region: Region) -> String {
+    let source = env
+        .sources
+        .and_then(|sources| sources.get(&condition.module_id()));
+
+    let (path, src) = match source {
+        Some((path, src)) => (path, src),
+        None => return String::from("An expectation failed!"),
+    };
+
+    let line = LineInfo::new(src).convert_pos(region.start()).line + 1;
+    let start = region.start().offset as usize;
+    let end = region.end().offset as usize;
+
+    let mut message = format!("An expectation failed at {}:{}:\n", path.display(), line);
+
+    for condition_line in src.get(start..end).unwrap_or_default().lines() {
+        message.push_str("\n    ");
+        message.push_str(condition_line);
+    }
+
+    message
+}
+
 pub(crate) struct SharedMemoryPointer<'ctx>(PointerValue<'ctx>);
 
 impl<'ctx> SharedMemoryPointer<'ctx> {
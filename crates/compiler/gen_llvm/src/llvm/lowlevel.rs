@@ -679,6 +679,7 @@ pub(crate) fn run_low_level<'a, 'ctx, 'env>(
             list_concat(
                 env,
                 layout_interner,
+                layout_ids,
                 first_list,
                 second_list,
                 element_layout,
@@ -700,7 +701,14 @@ pub(crate) fn run_low_level<'a, 'ctx, 'env>(
             let original_wrapper = load_symbol(scope, &args[0]).into_struct_value();
             let (elem, elem_layout) = load_symbol_and_layout(scope, &args[1]);
 
-            list_prepend(env, layout_interner, original_wrapper, elem, elem_layout)
+            list_prepend(
+                env,
+                layout_interner,
+                layout_ids,
+                original_wrapper,
+                elem,
+                elem_layout,
+            )
         }
         ListReserve => {
             // List.reserve : List elem, Nat -> List elem
@@ -713,6 +721,7 @@ pub(crate) fn run_low_level<'a, 'ctx, 'env>(
             list_reserve(
                 env,
                 layout_interner,
+                layout_ids,
                 list,
                 spare,
                 element_layout,
@@ -726,7 +735,14 @@ pub(crate) fn run_low_level<'a, 'ctx, 'env>(
             let (list, list_layout) = load_symbol_and_layout(scope, &args[0]);
             let element_layout = list_element_layout!(layout_interner, list_layout);
 
-            list_release_excess_capacity(env, layout_interner, list, element_layout, update_mode)
+            list_release_excess_capacity(
+                env,
+                layout_interner,
+                layout_ids,
+                list,
+                element_layout,
+                update_mode,
+            )
         }
         ListSwap => {
             // List.swap : List elem, Nat, Nat -> List elem
@@ -742,6 +758,7 @@ pub(crate) fn run_low_level<'a, 'ctx, 'env>(
             list_swap(
                 env,
                 layout_interner,
+                layout_ids,
                 original_wrapper,
                 index_1.into_int_value(),
                 index_2.into_int_value(),
@@ -2629,6 +2646,7 @@ pub(crate) fn run_higher_order_low_level<'a, 'ctx, 'env>(
                     list_sort_with(
                         env,
                         layout_interner,
+                        layout_ids,
                         roc_function_call,
                         compare_wrapper,
                         list,
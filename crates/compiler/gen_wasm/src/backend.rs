@@ -709,6 +709,7 @@ impl<'a, 'r> WasmBackend<'a, 'r> {
                 cond_layout,
                 branches,
                 default_branch,
+                branch_hints: _,
                 ret_layout: _,
             } => self.stmt_switch(*cond_symbol, *cond_layout, branches, default_branch),
 
@@ -1060,6 +1061,10 @@ impl<'a, 'r> WasmBackend<'a, 'r> {
                 self.expr_array(sym, storage, *elem_layout, elems)
             }
 
+            Expr::StaticArray { elems, elem_layout } => {
+                self.expr_static_array(sym, storage, *elem_layout, elems)
+            }
+
             Expr::EmptyArray => self.expr_empty_array(sym, storage),
 
             Expr::Tag {
@@ -1600,6 +1605,73 @@ impl<'a, 'r> WasmBackend<'a, 'r> {
         }
     }
 
+    fn expr_static_array(
+        &mut self,
+        sym: Symbol,
+        storage: &StoredValue,
+        elem_layout: InLayout<'a>,
+        elems: &'a [Literal<'a>],
+    ) {
+        if let StoredValue::StackMemory { location, .. } = storage {
+            let elem_size = self.layout_interner.stack_size(elem_layout) as usize;
+            let mut bytes = Vec::with_capacity_in(elem_size * elems.len(), self.env.arena);
+
+            for lit in elems.iter() {
+                let lit_bytes = match lit {
+                    Literal::Int(x) | Literal::U128(x) | Literal::Decimal(x) => {
+                        i128::from_ne_bytes(*x).to_le_bytes()
+                    }
+                    Literal::Float(x) if elem_size == 4 => {
+                        let mut lit_bytes = [0; 16];
+                        lit_bytes[..4].copy_from_slice(&(*x as f32).to_le_bytes());
+                        lit_bytes
+                    }
+                    Literal::Float(x) => {
+                        let mut lit_bytes = [0; 16];
+                        lit_bytes[..8].copy_from_slice(&x.to_le_bytes());
+                        lit_bytes
+                    }
+                    Literal::Bool(x) => (*x as i128).to_le_bytes(),
+                    Literal::Byte(x) => (*x as i128).to_le_bytes(),
+                    Literal::Str(_) => {
+                        internal_error!("StaticArray {:?} contains a string: {:?}", sym, elems)
+                    }
+                };
+
+                bytes.extend_from_slice(&lit_bytes[..elem_size]);
+            }
+
+            // The elements are prefixed with the "infinite" refcount, so the list is never freed
+            // or updated in-place
+            let elements_addr = self.store_bytes_in_data_section(&bytes);
+
+            let (local_id, offset) = location.local_and_offset(self.storage.stack_frame_pointer);
+
+            // elements pointer
+            self.code_builder.get_local(local_id);
+            self.code_builder.i32_const(elements_addr as i32);
+            self.code_builder.i32_store(Align::Bytes4, offset);
+
+            // length of the list
+            self.code_builder.get_local(local_id);
+            self.code_builder.i32_const(elems.len() as i32);
+            self.code_builder
+                .i32_store(Align::Bytes4, offset + 4 * Builtin::WRAPPER_LEN);
+
+            // capacity of the list
+            self.code_builder.get_local(local_id);
+            self.code_builder.i32_const(elems.len() as i32);
+            self.code_builder
+                .i32_store(Align::Bytes4, offset + 4 * Builtin::WRAPPER_CAPACITY);
+        } else {
+            internal_error!(
+                "Unexpected storage for StaticArray {:?}: {:?}",
+                sym,
+                storage
+            )
+        }
+    }
+
     fn expr_empty_array(&mut self, sym: Symbol, storage: &StoredValue) {
         if let StoredValue::StackMemory { location, .. } = storage {
             let (local_id, offset) = location.local_and_offset(self.storage.stack_frame_pointer);
@@ -420,6 +420,7 @@ impl<'a> LowLevelCall<'a> {
                 let (elem_width, elem_alignment) = backend
                     .layout_interner
                     .stack_size_and_alignment(elem_layout);
+                let inc_elem_fn_ptr = inc_element_fn_ptr(backend, elem_layout);
 
                 // Ensure the new element is stored in memory so we can pass a pointer to Zig
                 let (new_elem_local, new_elem_offset, _) =
@@ -433,6 +434,7 @@ impl<'a> LowLevelCall<'a> {
                 //    element: Opaque,       i32
                 //    element_width: usize,  i32
                 //    out_element: ?[*]u8,   i32
+                //    inc_element: ?IncN,    i32
 
                 let code_builder = &mut backend.code_builder;
 
@@ -460,8 +462,10 @@ impl<'a> LowLevelCall<'a> {
                     code_builder.i32_add();
                 }
 
+                code_builder.i32_const(inc_elem_fn_ptr);
+
                 // There is an in-place version of this but we don't use it for dev backends. No morphic_lib analysis.
-                backend.call_host_fn_after_loading_args(bitcode::LIST_REPLACE, 8, false);
+                backend.call_host_fn_after_loading_args(bitcode::LIST_REPLACE, 9, false);
             }
             ListWithCapacity => {
                 // List.withCapacity : Nat -> List elem
@@ -494,6 +498,7 @@ impl<'a> LowLevelCall<'a> {
                 //  list_b: RocList        i64, i32
                 //  alignment: u32         i32
                 //  element_width: usize   i32
+                //  inc_element: ?IncN     i32
 
                 // Load the arguments that have symbols
                 backend.storage.load_symbols_for_call(
@@ -507,13 +512,15 @@ impl<'a> LowLevelCall<'a> {
 
                 // Load monomorphization constants
                 let elem_layout = unwrap_list_elem_layout(self.ret_layout_raw);
+                let inc_elem_fn_ptr = inc_element_fn_ptr(backend, elem_layout);
                 let elem_layout = backend.layout_interner.get(elem_layout);
                 let (elem_width, elem_align) =
                     elem_layout.stack_size_and_alignment(backend.layout_interner, TARGET_INFO);
                 backend.code_builder.i32_const(elem_align as i32);
                 backend.code_builder.i32_const(elem_width as i32);
+                backend.code_builder.i32_const(inc_elem_fn_ptr);
 
-                backend.call_host_fn_after_loading_args(bitcode::LIST_CONCAT, 7, false);
+                backend.call_host_fn_after_loading_args(bitcode::LIST_CONCAT, 8, false);
             }
 
             ListReserve => {
@@ -523,6 +530,7 @@ impl<'a> LowLevelCall<'a> {
                 let spare: Symbol = self.arguments[1];
 
                 let elem_layout = unwrap_list_elem_layout(self.ret_layout_raw);
+                let inc_elem_fn_ptr = inc_element_fn_ptr(backend, elem_layout);
                 let elem_layout = backend.layout_interner.get(elem_layout);
                 let (elem_width, elem_align) =
                     elem_layout.stack_size_and_alignment(backend.layout_interner, TARGET_INFO);
@@ -533,6 +541,7 @@ impl<'a> LowLevelCall<'a> {
                 //  alignment: u32             i32
                 //  spare: usize               i32
                 //  element_width: usize       i32
+                //  inc_element: ?IncN         i32
                 //  update_mode: UpdateMode    i32
 
                 // return pointer and list
@@ -553,9 +562,11 @@ impl<'a> LowLevelCall<'a> {
 
                 backend.code_builder.i32_const(elem_width as i32);
 
+                backend.code_builder.i32_const(inc_elem_fn_ptr);
+
                 backend.code_builder.i32_const(UPDATE_MODE_IMMUTABLE);
 
-                backend.call_host_fn_after_loading_args(bitcode::LIST_RESERVE, 7, false);
+                backend.call_host_fn_after_loading_args(bitcode::LIST_RESERVE, 8, false);
             }
 
             ListReleaseExcessCapacity => {
@@ -564,6 +575,7 @@ impl<'a> LowLevelCall<'a> {
                 let list: Symbol = self.arguments[0];
 
                 let elem_layout = unwrap_list_elem_layout(self.ret_layout_raw);
+                let inc_elem_fn_ptr = inc_element_fn_ptr(backend, elem_layout);
                 let elem_layout = backend.layout_interner.get(elem_layout);
                 let (elem_width, elem_align) =
                     elem_layout.stack_size_and_alignment(backend.layout_interner, TARGET_INFO);
@@ -573,6 +585,7 @@ impl<'a> LowLevelCall<'a> {
                 //  list: RocList              i64, i32
                 //  alignment: u32             i32
                 //  element_width: usize       i32
+                //  inc_element: ?IncN         i32
                 //  update_mode: UpdateMode    i32
 
                 // return pointer and list
@@ -589,11 +602,13 @@ impl<'a> LowLevelCall<'a> {
 
                 backend.code_builder.i32_const(elem_width as i32);
 
+                backend.code_builder.i32_const(inc_elem_fn_ptr);
+
                 backend.code_builder.i32_const(UPDATE_MODE_IMMUTABLE);
 
                 backend.call_host_fn_after_loading_args(
                     bitcode::LIST_RELEASE_EXCESS_CAPACITY,
-                    6,
+                    7,
                     false,
                 );
             }
@@ -645,6 +660,7 @@ impl<'a> LowLevelCall<'a> {
                 let (elem_width, elem_align) = backend
                     .layout_interner
                     .stack_size_and_alignment(elem_layout);
+                let inc_elem_fn_ptr = inc_element_fn_ptr(backend, elem_layout);
                 let (elem_local, elem_offset, _) =
                     ensure_symbol_is_in_memory(backend, elem, elem_layout, backend.env.arena);
 
@@ -654,6 +670,7 @@ impl<'a> LowLevelCall<'a> {
                 //  alignment: u32             i32
                 //  element: Opaque            i32
                 //  element_width: usize       i32
+                //  inc_element: ?IncN         i32
 
                 // return pointer and list
                 backend.storage.load_symbols_for_call(
@@ -673,8 +690,9 @@ impl<'a> LowLevelCall<'a> {
                     backend.code_builder.i32_add();
                 }
                 backend.code_builder.i32_const(elem_width as i32);
+                backend.code_builder.i32_const(inc_elem_fn_ptr);
 
-                backend.call_host_fn_after_loading_args(bitcode::LIST_PREPEND, 6, false);
+                backend.call_host_fn_after_loading_args(bitcode::LIST_PREPEND, 7, false);
             }
             ListSublist => {
                 // As a low-level, record is destructured
@@ -781,6 +799,7 @@ impl<'a> LowLevelCall<'a> {
                 let (elem_width, elem_align) = backend
                     .layout_interner
                     .stack_size_and_alignment(elem_layout);
+                let inc_elem_fn_ptr = inc_element_fn_ptr(backend, elem_layout);
 
                 // Zig arguments              Wasm types
                 //  (return pointer)           i32
@@ -789,6 +808,7 @@ impl<'a> LowLevelCall<'a> {
                 //  element_width: usize,      i32
                 //  index_1: usize,            i32
                 //  index_2: usize,            i32
+                //  inc_element: ?IncN,        i32
                 //  update_mode: UpdateMode,   i32
 
                 // Load the return pointer and the list
@@ -806,9 +826,10 @@ impl<'a> LowLevelCall<'a> {
                 backend
                     .storage
                     .load_symbols(&mut backend.code_builder, &[index_1, index_2]);
+                backend.code_builder.i32_const(inc_elem_fn_ptr);
                 backend.code_builder.i32_const(UPDATE_MODE_IMMUTABLE);
 
-                backend.call_host_fn_after_loading_args(bitcode::LIST_SWAP, 8, false);
+                backend.call_host_fn_after_loading_args(bitcode::LIST_SWAP, 9, false);
             }
 
             // Num
@@ -2438,6 +2459,7 @@ pub fn call_higher_order_lowlevel<'a>(
                     .layout_interner
                     .get(backend.storage.symbol_layouts[xs]),
             );
+            let inc_elem_fn_ptr = inc_element_fn_ptr(backend, elem_layout);
             let elem_layout = backend.layout_interner.get(elem_layout);
             let (element_width, alignment) =
                 elem_layout.stack_size_and_alignment(backend.layout_interner, TARGET_INFO);
@@ -2452,6 +2474,7 @@ pub fn call_higher_order_lowlevel<'a>(
             // data_is_owned: bool,  i32
             // alignment: u32,       i32
             // element_width: usize, i32
+            // inc_element: ?IncN,   i32
 
             backend.storage.load_symbols(cb, &[return_sym]);
             backend.storage.load_symbol_zig(cb, *xs);
@@ -2469,8 +2492,9 @@ pub fn call_higher_order_lowlevel<'a>(
             cb.i32_const(*owns_captured_environment as i32);
             cb.i32_const(alignment as i32);
             cb.i32_const(element_width as i32);
+            cb.i32_const(inc_elem_fn_ptr);
 
-            backend.call_host_fn_after_loading_args(bitcode::LIST_SORT_WITH, 9, false);
+            backend.call_host_fn_after_loading_args(bitcode::LIST_SORT_WITH, 10, false);
         }
     }
 }
@@ -2482,6 +2506,23 @@ fn unwrap_list_elem_layout(list_layout: Layout) -> InLayout {
     }
 }
 
+/// The function Zig uses to increment the elements of a shared list that it copies, or a null
+/// pointer if the elements are not refcounted and copying their bytes is enough.
+fn inc_element_fn_ptr<'a>(backend: &mut WasmBackend<'a, '_>, elem_layout: InLayout<'a>) -> i32 {
+    if !backend.layout_interner.contains_refcounted(elem_layout) {
+        return 0;
+    }
+
+    // The refcount function receives a pointer to an element in the list
+    // This is the same as a Struct containing the element
+    let in_memory_layout = backend.layout_interner.insert(Layout::Struct {
+        field_order_hash: FieldOrderHash::from_ordered_fields(&[]),
+        field_layouts: backend.env.arena.alloc([elem_layout]),
+    });
+    let inc_fn = backend.get_refcount_fn_index(in_memory_layout, HelperOp::Inc);
+    backend.get_fn_ptr(inc_fn)
+}
+
 #[allow(clippy::too_many_arguments)]
 fn list_map_n<'a>(
     zig_fn_name: &'static str,
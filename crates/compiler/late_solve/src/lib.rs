@@ -84,6 +84,7 @@ impl WorldAbilities {
     }
 }
 
+#[derive(Clone, Copy)]
 pub enum AbilitiesView<'a> {
     World(&'a WorldAbilities),
     Module(&'a AbilitiesStore),
@@ -25,6 +25,7 @@ const MODULES: &[(ModuleId, &str)] = &[
     (ModuleId::DECODE, "Decode.roc"),
     (ModuleId::HASH, "Hash.roc"),
     (ModuleId::JSON, "Json.roc"),
+    (ModuleId::INSPECT, "Inspect.roc"),
 ];
 
 fn main() {
@@ -104,6 +105,7 @@ fn write_types_for_module_real(module_id: ModuleId, filename: &str, output_path:
         module.total_problems(),
         &module.sources,
         &module.interns,
+        &mut module.parse_problem_reports,
         &mut module.can_problems,
         &mut module.type_problems,
     );
@@ -18,8 +18,9 @@ const SKIP_SUBS_CACHE: bool = {
 
 pub use roc_load_internal::docs;
 pub use roc_load_internal::file::{
-    EntryPoint, ExecutionMode, ExpectMetadata, Expectations, ExposedToHost, LoadConfig, LoadResult,
-    LoadStart, LoadedModule, LoadingProblem, MonomorphizedModule, Phase, Threading,
+    CancelToken, EntryPoint, ExecutionMode, ExpectMetadata, Expectations, ExposedToHost, HotModule,
+    LoadConfig, LoadEvent, LoadResult, LoadStart, LoadedModule, LoadingProblem,
+    MonomorphizedModule, Phase, ProgressReporter, SolveFuel, Threading,
 };
 
 #[allow(clippy::too_many_arguments)]
@@ -66,6 +67,10 @@ pub fn load_single_threaded<'a>(
         palette,
         exec_mode,
         roc_cache_dir,
+        &CancelToken::default(),
+        ProgressReporter::default(),
+        HotModule::default(),
+        SolveFuel::default(),
     )
 }
 
@@ -204,9 +209,32 @@ const BOX: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/Box.dat")) as &[_];
 const ENCODE: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/Encode.dat")) as &[_];
 const DECODE: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/Decode.dat")) as &[_];
 const HASH: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/Hash.dat")) as &[_];
+const INSPECT: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/Inspect.dat")) as &[_];
+
+// A cache in another format must not be misread, and must not be dropped without anyone noticing
+// either. The files are empty when build.rs skips writing them.
+const fn is_current_cache(bytes: &[u8]) -> bool {
+    bytes.is_empty() || TypeState::has_current_header(bytes)
+}
+
+const _: () = {
+    let caches = [
+        BOOL, DICT, SET, RESULT, NUM, LIST, STR, BOX, ENCODE, DECODE, HASH, INSPECT,
+    ];
+
+    let mut i = 0;
+    while i < caches.len() {
+        assert!(
+            is_current_cache(caches[i]),
+            "a builtin types cache has a header of another compiler version"
+        );
+        i += 1;
+    }
+};
 
 fn deserialize_help(bytes: &[u8]) -> TypeState {
-    let (state, _offset) = TypeState::deserialize(bytes);
+    let (state, _offset) =
+        TypeState::deserialize(bytes).expect("the header was checked when building roc_load");
     debug_assert_eq!(bytes.len(), _offset);
 
     state
@@ -234,6 +262,7 @@ fn read_cached_types() -> MutMap<ModuleId, TypeState> {
         output.insert(ModuleId::DECODE, deserialize_help(DECODE));
 
         output.insert(ModuleId::HASH, deserialize_help(HASH));
+        output.insert(ModuleId::INSPECT, deserialize_help(INSPECT));
     }
 
     output
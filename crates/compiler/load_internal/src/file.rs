@@ -1,26 +1,29 @@
 #![allow(clippy::too_many_arguments)]
 
 use crate::docs::ModuleDocumentation;
+use crate::ident_ids::SharedIdentIds;
 use bumpalo::{collections::CollectIn, Bump};
 use crossbeam::channel::{bounded, Sender};
-use crossbeam::deque::{Injector, Stealer, Worker};
+use crossbeam::deque::{Stealer, Worker};
 use crossbeam::thread;
 use parking_lot::Mutex;
 use roc_builtins::roc::module_source;
 use roc_can::abilities::{AbilitiesStore, PendingAbilitiesStore, ResolvedImpl};
+use roc_can::call_graph::CallGraph;
 use roc_can::constraint::{Constraint as ConstraintSoa, Constraints, TypeOrVar};
+use roc_can::debug::{pretty_print_declarations, PPCtx};
 use roc_can::expr::{DbgLookup, Declarations, ExpectLookup, PendingDerives};
 use roc_can::module::{
     canonicalize_module_defs, ExposedByModule, ExposedForModule, ExposedModuleTypes, Module,
     ResolvedImplementations, TypeState,
 };
-use roc_collections::{default_hasher, BumpMap, MutMap, MutSet, VecMap, VecSet};
+use roc_collections::{default_hasher, BumpMap, MutMap, MutSet, SliceInterner, VecMap, VecSet};
 use roc_constrain::module::constrain_module;
 use roc_debug_flags::dbg_do;
 #[cfg(debug_assertions)]
 use roc_debug_flags::{
-    ROC_CHECK_MONO_IR, ROC_PRINT_IR_AFTER_REFCOUNT, ROC_PRINT_IR_AFTER_RESET_REUSE,
-    ROC_PRINT_IR_AFTER_SPECIALIZATION, ROC_PRINT_LOAD_LOG,
+    ROC_CHECK_MONO_CACHE, ROC_CHECK_MONO_IR, ROC_PRINT_IR_AFTER_REFCOUNT,
+    ROC_PRINT_IR_AFTER_RESET_REUSE, ROC_PRINT_IR_AFTER_SPECIALIZATION, ROC_PRINT_LOAD_LOG,
 };
 use roc_derive::SharedDerivedModule;
 use roc_error_macros::internal_error;
@@ -33,13 +36,14 @@ use roc_module::symbol::{
 use roc_mono::inc_dec;
 use roc_mono::ir::{
     CapturedSymbols, ExternalSpecializations, GlueLayouts, LambdaSetId, PartialProc, Proc,
-    ProcLayout, Procs, ProcsBase, UpdateModeIds,
+    ProcLayout, Procs, ProcsBase, SpecializationHelpers, SpecializationParts, UpdateModeIds,
 };
 use roc_mono::layout::LayoutInterner;
 use roc_mono::layout::{
     GlobalLayoutInterner, LambdaName, Layout, LayoutCache, LayoutProblem, Niche, STLayoutInterner,
 };
 use roc_mono::reset_reuse;
+use roc_mono::stats::MonoStats;
 use roc_packaging::cache::RocCacheDir;
 use roc_parse::ast::{
     self, CommentOrNewline, Defs, Expr, ExtractSpaces, Pattern, Spaced, StrLiteral, TypeAnnotation,
@@ -51,13 +55,17 @@ use roc_parse::header::{
 use roc_parse::header::{HeaderType, PackageName};
 use roc_parse::module::module_defs;
 use roc_parse::parser::{FileError, Parser, SourceError, SyntaxError};
+use roc_problem::cancel::Cancelled;
 use roc_problem::Severity;
 use roc_region::all::{LineInfo, Loc, Region};
-use roc_reporting::report::{to_file_problem_report_string, Palette, RenderTarget};
+use roc_reporting::report::{
+    to_file_problem_report_string, to_invalid_utf8_report_string,
+    to_module_not_found_report_string, Palette, RenderTarget,
+};
 use roc_solve::module::{extract_module_owned_implementations, Solved, SolvedModule};
 use roc_solve_problem::TypeError;
 use roc_target::TargetInfo;
-use roc_types::subs::{CopiedImport, ExposedTypesStorageSubs, Subs, VarStore, Variable};
+use roc_types::subs::{Content, CopiedImport, ExposedTypesStorageSubs, Subs, VarStore, Variable};
 use roc_types::types::{Alias, Types};
 use std::collections::hash_map::Entry::{Occupied, Vacant};
 use std::collections::HashMap;
@@ -67,6 +75,7 @@ use std::iter;
 use std::ops::ControlFlow;
 use std::path::{Path, PathBuf};
 use std::str::from_utf8_unchecked;
+use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
 use std::{env, fs};
 #[cfg(not(target_family = "wasm"))]
@@ -75,8 +84,13 @@ use {
     roc_packaging::https::PackageMetadata,
 };
 
+pub use crate::progress::{LoadEvent, ProgressReporter};
+pub use crate::schedule::HotModule;
+use crate::schedule::{Priority, TaskQueue};
 pub use crate::work::Phase;
 use crate::work::{DepCycle, Dependencies};
+pub use roc_problem::cancel::CancelToken;
+pub use roc_solve::solve::SolveFuel;
 
 #[cfg(target_family = "wasm")]
 use crate::wasm_instant::{Duration, Instant};
@@ -105,6 +119,14 @@ pub struct LoadConfig {
     pub palette: Palette,
     pub threading: Threading,
     pub exec_mode: ExecutionMode,
+    /// Stops the load early, with [LoadingProblem::Cancelled], once it is cancelled.
+    pub cancel: CancelToken,
+    /// Told about each module as it moves through the phases of the load.
+    pub progress: ProgressReporter,
+    /// The module the user is editing, which is worked on before the rest of the project.
+    pub hot_module: HotModule,
+    /// How much work solving the types of one module may take before it is given up on.
+    pub solve_fuel: SolveFuel,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -131,6 +153,12 @@ impl ExecutionMode {
     fn build_if_checks(&self) -> bool {
         matches!(self, Self::ExecutableIfCheck | Self::Test)
     }
+
+    /// When only checking, an interface whose body fails to parse does not stop the modules that
+    /// import it from being checked; they see its exposed values with an error type.
+    fn salvages_parse_failures(&self) -> bool {
+        matches!(self, Self::Check)
+    }
 }
 
 /// Struct storing various intermediate stages by their ModuleId
@@ -155,6 +183,9 @@ struct ModuleCache<'a> {
     documentation: VecMap<ModuleId, ModuleDocumentation>,
     can_problems: MutMap<ModuleId, Vec<roc_problem::can::Problem>>,
     type_problems: MutMap<ModuleId, Vec<TypeError>>,
+    /// Modules whose body failed to parse or whose file could not be loaded, but that were
+    /// checked anyway as if they were empty
+    parse_problem_reports: MutMap<ModuleId, String>,
 
     sources: MutMap<ModuleId, (PathBuf, &'a str)>,
 }
@@ -167,6 +198,10 @@ impl<'a> ModuleCache<'a> {
             .any(|problem| problem.severity() == Severity::RuntimeError)
     }
 
+    fn is_salvaged(&self, module_id: ModuleId) -> bool {
+        self.parse_problem_reports.contains_key(&module_id)
+    }
+
     fn has_type_errors(&self) -> bool {
         self.type_problems
             .values()
@@ -175,7 +210,7 @@ impl<'a> ModuleCache<'a> {
     }
 
     pub fn has_errors(&self) -> bool {
-        self.has_can_errors() || self.has_type_errors()
+        !self.parse_problem_reports.is_empty() || self.has_can_errors() || self.has_type_errors()
     }
 }
 
@@ -205,6 +240,7 @@ impl Default for ModuleCache<'_> {
             DECODE,
             HASH,
             JSON,
+            INSPECT,
         }
 
         Self {
@@ -223,12 +259,13 @@ impl Default for ModuleCache<'_> {
             documentation: Default::default(),
             can_problems: Default::default(),
             type_problems: Default::default(),
+            parse_problem_reports: Default::default(),
             sources: Default::default(),
         }
     }
 }
 
-type SharedIdentIdsByModule = Arc<Mutex<roc_module::symbol::IdentIdsByModule>>;
+type SharedIdentIdsByModule = Arc<SharedIdentIds>;
 
 fn start_phase<'a>(
     module_id: ModuleId,
@@ -274,6 +311,8 @@ fn start_phase<'a>(
                             module_ids: Arc::clone(&state.arc_modules),
                             shorthands: Arc::clone(&state.arc_shorthands),
                             ident_ids_by_module: Arc::clone(&state.ident_ids_by_module),
+                            // a missing or unreadable import doesn't stop its importers from being checked
+                            salvage_failure: state.exec_mode.salvages_parse_failures(),
                         }
                     }
                 }
@@ -281,8 +320,15 @@ fn start_phase<'a>(
             Phase::Parse => {
                 // parse the file
                 let header = state.module_cache.headers.remove(&module_id).unwrap();
-
-                BuildTask::Parse { header }
+                // Only imported interfaces have importers that can be checked without them.
+                let salvage_failure = state.exec_mode.salvages_parse_failures()
+                    && !header.is_root_module
+                    && matches!(header.header_type, HeaderType::Interface { .. });
+
+                BuildTask::Parse {
+                    header,
+                    salvage_failure,
+                }
             }
             Phase::CanonicalizeAndConstrain => {
                 // canonicalize the file
@@ -292,29 +338,20 @@ fn start_phase<'a>(
                 let num_deps = deps_by_name.len();
                 let mut dep_idents: IdentIdsByModule = IdentIds::exposed_builtins(num_deps);
 
-                let State {
-                    ident_ids_by_module,
-                    ..
-                } = &state;
-
-                {
-                    let ident_ids_by_module = (*ident_ids_by_module).lock();
-
-                    // Populate dep_idents with each of their IdentIds,
-                    // which we'll need during canonicalization to translate
-                    // identifier strings into IdentIds, which we need to build Symbols.
-                    // We only include the modules we care about (the ones we import).
-                    //
-                    // At the end of this loop, dep_idents contains all the information to
-                    // resolve a symbol from another module: if it's in here, that means
-                    // we have both imported the module and the ident was exported by that mdoule.
-                    for dep_id in deps_by_name.values() {
-                        // We already verified that these are all present,
-                        // so unwrapping should always succeed here.
-                        let idents = ident_ids_by_module.get(dep_id).unwrap();
-
-                        dep_idents.insert(*dep_id, idents.clone());
-                    }
+                // Populate dep_idents with each of their IdentIds,
+                // which we'll need during canonicalization to translate
+                // identifier strings into IdentIds, which we need to build Symbols.
+                // We only include the modules we care about (the ones we import).
+                //
+                // At the end of this loop, dep_idents contains all the information to
+                // resolve a symbol from another module: if it's in here, that means
+                // we have both imported the module and the ident was exported by that mdoule.
+                for dep_id in deps_by_name.values() {
+                    // We already verified that these are all present,
+                    // so unwrapping should always succeed here.
+                    let idents = state.ident_ids_by_module.get_cloned(*dep_id).unwrap();
+
+                    dep_idents.insert(*dep_id, idents);
                 }
 
                 // Clone the module_ids we'll need for canonicalization.
@@ -431,6 +468,7 @@ fn start_phase<'a>(
                     declarations,
                     state.cached_types.clone(),
                     derived_module,
+                    state.solve_fuel,
                 )
             }
             Phase::FindSpecializations => {
@@ -611,7 +649,7 @@ fn start_phase<'a>(
                     module_timing,
                     world_abilities: state.world_abilities.clone_ref(),
                     // TODO: awful, how can we get rid of the clone?
-                    exposed_by_module: state.exposed_types.clone(),
+                    exposed_by_module: Arc::new(state.exposed_types.clone()),
                     derived_module,
                     expectations,
                 }
@@ -629,7 +667,12 @@ pub struct LoadedModule {
     pub solved: Solved<Subs>,
     pub can_problems: MutMap<ModuleId, Vec<roc_problem::can::Problem>>,
     pub type_problems: MutMap<ModuleId, Vec<TypeError>>,
+    /// Reports of modules whose body failed to parse, when only checking
+    pub parse_problem_reports: MutMap<ModuleId, String>,
+    /// The declarations of the root module and of all modules it depends on that were not cached
     pub declarations_by_id: MutMap<ModuleId, Declarations>,
+    /// What the interface modules of the root package expose, e.g. not those of the platform
+    pub interface_exposes: MutMap<ModuleId, VecSet<Symbol>>,
     pub exposed_to_host: MutMap<Symbol, Variable>,
     pub dep_idents: IdentIdsByModule,
     pub exposed_aliases: MutMap<Symbol, Alias>,
@@ -644,7 +687,7 @@ pub struct LoadedModule {
 
 impl LoadedModule {
     pub fn total_problems(&self) -> usize {
-        let mut total = 0;
+        let mut total = self.parse_problem_reports.len();
 
         for problems in self.can_problems.values() {
             total += problems.len();
@@ -657,6 +700,104 @@ impl LoadedModule {
         total
     }
 
+    /// The def-level call graph of all loaded modules, including the calls between them.
+    /// Modules are added in the order of their names, so the graph is the same on every load.
+    pub fn call_graph(&self) -> CallGraph {
+        let mut module_ids: Vec<ModuleId> = self.declarations_by_id.keys().copied().collect();
+        module_ids.sort_by_key(|module_id| self.interns.module_name(*module_id));
+
+        let mut call_graph = CallGraph::default();
+
+        for module_id in module_ids {
+            call_graph.add_declarations(&self.declarations_by_id[&module_id]);
+        }
+
+        call_graph
+    }
+
+    /// The canonical AST of every module that is not a builtin, desugared and with resolved
+    /// symbols. Every top-level declaration starts with a comment that has its region.
+    pub fn pretty_print_can(&self) -> String {
+        let mut module_ids: Vec<ModuleId> = self
+            .declarations_by_id
+            .keys()
+            .copied()
+            .filter(|module_id| !module_id.is_builtin())
+            .collect();
+        module_ids.sort_by_key(|module_id| self.interns.module_name(*module_id));
+
+        let mut buf = String::new();
+
+        for module_id in module_ids {
+            let module_name = self.interns.module_name(module_id);
+
+            if module_name.is_empty() {
+                // the App module
+                buf.push_str("# Application Module\n\n");
+            } else {
+                buf.push_str(&format!("# {}\n\n", module_name.as_str()));
+            }
+
+            let ctx = PPCtx {
+                home: module_id,
+                interns: &self.interns,
+                print_lambda_names: true,
+                print_regions: true,
+            };
+
+            buf.push_str(&pretty_print_declarations(
+                &ctx,
+                &self.declarations_by_id[&module_id],
+            ));
+            buf.push_str("\n\n");
+        }
+
+        buf
+    }
+
+    /// Values exposed by the interface modules of the root package that no other module uses,
+    /// at their definition. Entry points the platform requires are exposed by the app module,
+    /// and other packages are not ours to prune, so neither are reported.
+    pub fn unused_exposed_values(&self) -> Vec<Loc<Symbol>> {
+        let call_graph = self.call_graph();
+
+        let mut module_ids: Vec<ModuleId> = self.interface_exposes.keys().copied().collect();
+        module_ids.sort_by_key(|module_id| self.interns.module_name(*module_id));
+
+        let mut unused = Vec::new();
+
+        for module_id in module_ids {
+            let decls = match self.declarations_by_id.get(&module_id) {
+                Some(decls) => decls,
+                None => continue,
+            };
+
+            let exposes = &self.interface_exposes[&module_id];
+            let module_start = unused.len();
+
+            for (index, loc_symbol) in decls.symbols.iter().enumerate() {
+                let symbol = loc_symbol.value;
+
+                // ability implementations are used through the ability member
+                let is_unused = exposes.contains(&symbol)
+                    && decls.specializes.get(&index).is_none()
+                    && call_graph
+                        .callers(symbol)
+                        .iter()
+                        .all(|caller| caller.module_id() == module_id);
+
+                if is_unused {
+                    unused.push(*loc_symbol);
+                }
+            }
+
+            // declarations are sorted by their dependencies, report in source order instead
+            unused[module_start..].sort_by_key(|loc_symbol| loc_symbol.region.start());
+        }
+
+        unused
+    }
+
     pub fn exposed_values_str(&self) -> Vec<&str> {
         self.exposed_values
             .iter()
@@ -695,6 +836,8 @@ struct ModuleHeader<'a> {
     symbols_from_requires: Vec<(Loc<Symbol>, Loc<TypeAnnotation<'a>>)>,
     module_timing: ModuleTiming,
     defined_values: Vec<ValueDef<'a>>,
+    /// Why the file of this module could not be loaded, when it's checked as an empty interface
+    load_problem: Option<LoadingProblem<'a>>,
 }
 
 #[derive(Debug)]
@@ -768,6 +911,7 @@ pub struct MonomorphizedModule<'a> {
     pub exposed_to_host: ExposedToHost,
     pub sources: MutMap<ModuleId, (PathBuf, Box<str>)>,
     pub timings: MutMap<ModuleId, ModuleTiming>,
+    pub mono_stats: MonoStats,
     pub expectations: VecMap<ModuleId, Expectations>,
     pub uses_prebuilt_platform: bool,
     pub glue_layouts: GlueLayouts<'a>,
@@ -836,6 +980,8 @@ struct ParsedModule<'a> {
     exposed_ident_ids: IdentIds,
     exposed_imports: MutMap<Ident, (Symbol, Region)>,
     parsed_defs: Defs<'a>,
+    /// The body failed to parse, and `parsed_defs` is empty
+    parse_problem: Option<FileError<'a, SyntaxError<'a>>>,
     symbols_from_requires: Vec<(Loc<Symbol>, Loc<TypeAnnotation<'a>>)>,
     header_type: HeaderType<'a>,
     header_comments: &'a [CommentOrNewline<'a>],
@@ -885,6 +1031,13 @@ enum Msg<'a> {
         toplevel_expects: ToplevelExpects,
         expectations: Option<Expectations>,
     },
+    /// A worker split the specializations of a module into parts, and asks this many idle
+    /// workers to help make them
+    HelpMakeSpecializations {
+        module_id: ModuleId,
+        parts: Arc<SpecializationParts<'a>>,
+        helpers: usize,
+    },
     MadeSpecializations {
         module_id: ModuleId,
         ident_ids: IdentIds,
@@ -896,6 +1049,7 @@ enum Msg<'a> {
         module_timing: ModuleTiming,
         subs: Subs,
         expectations: Option<Expectations>,
+        mono_stats: MonoStats,
     },
 
     /// The task is to only typecheck AND monomorphize modules
@@ -1004,6 +1158,9 @@ struct State<'a> {
 
     pub exposed_symbols_by_module: MutMap<ModuleId, VecSet<Symbol>>,
 
+    /// What the interface modules of the root package expose, e.g. not those of the platform
+    pub interface_exposes: MutMap<ModuleId, VecSet<Symbol>>,
+
     pub timings: MutMap<ModuleId, ModuleTiming>,
 
     // Each thread gets its own layout cache. When one "pending specializations"
@@ -1013,9 +1170,15 @@ struct State<'a> {
     // (Granted, this has not been attempted or measured!)
     pub layout_caches: std::vec::Vec<LayoutCache<'a>>,
 
+    /// What making specializations took, summed over all modules
+    pub mono_stats: MonoStats,
+
     pub render: RenderTarget,
     pub palette: Palette,
     pub exec_mode: ExecutionMode,
+    pub progress: ProgressReporter,
+    pub hot_module: HotModule,
+    pub solve_fuel: SolveFuel,
 
     /// All abilities across all modules.
     pub world_abilities: WorldAbilities,
@@ -1035,6 +1198,40 @@ impl<'a> State<'a> {
         self.exec_mode.goal_phase()
     }
 
+    /// Tasks for the hot module and for the modules that import it directly are hot.
+    fn priority(&self, module_id: ModuleId) -> Priority {
+        if !self.hot_module.is_set() {
+            return Priority::Background;
+        }
+
+        let is_hot = |id| match self.module_path(id) {
+            Some(path) => self.hot_module.is(path),
+            None => false,
+        };
+
+        let imports_hot = || match self.module_cache.imports.get(&module_id) {
+            Some(imports) => imports.iter().any(|import| is_hot(*import)),
+            None => false,
+        };
+
+        if is_hot(module_id) || imports_hot() {
+            Priority::Hot
+        } else {
+            Priority::Background
+        }
+    }
+
+    fn module_path(&self, module_id: ModuleId) -> Option<&Path> {
+        match self.module_cache.sources.get(&module_id) {
+            Some((path, _)) => Some(path),
+            None => self
+                .module_cache
+                .headers
+                .get(&module_id)
+                .map(|header| header.module_path.as_path()),
+        }
+    }
+
     fn new(
         root_id: ModuleId,
         opt_platform_shorthand: Option<&'a str>,
@@ -1047,6 +1244,9 @@ impl<'a> State<'a> {
         palette: Palette,
         number_of_workers: usize,
         exec_mode: ExecutionMode,
+        progress: ProgressReporter,
+        hot_module: HotModule,
+        solve_fuel: SolveFuel,
     ) -> Self {
         let arc_shorthands = Arc::new(Mutex::new(MutMap::default()));
         let cache_dir = roc_packaging::cache::roc_cache_dir();
@@ -1075,12 +1275,17 @@ impl<'a> State<'a> {
             ident_ids_by_module,
             declarations_by_id: MutMap::default(),
             exposed_symbols_by_module: MutMap::default(),
+            interface_exposes: MutMap::default(),
             timings: MutMap::default(),
             layout_caches: std::vec::Vec::with_capacity(number_of_workers),
+            mono_stats: MonoStats::default(),
             cached_types: Arc::new(Mutex::new(cached_types)),
             render,
             palette,
             exec_mode,
+            progress,
+            hot_module,
+            solve_fuel,
             make_specializations_pass: MakeSpecializationsPass::Pass(1),
             world_abilities: Default::default(),
             layout_interner: GlobalLayoutInterner::with_capacity(128, target_info),
@@ -1212,9 +1417,11 @@ enum BuildTask<'a> {
         module_ids: Arc<Mutex<PackageModuleIds<'a>>>,
         shorthands: Arc<Mutex<MutMap<&'a str, ShorthandPath>>>,
         ident_ids_by_module: SharedIdentIdsByModule,
+        salvage_failure: bool,
     },
     Parse {
         header: ModuleHeader<'a>,
+        salvage_failure: bool,
     },
     CanonicalizeAndConstrain {
         parsed: ParsedModule<'a>,
@@ -1240,6 +1447,7 @@ enum BuildTask<'a> {
         dep_idents: IdentIdsByModule,
         cached_subs: CachedTypeState,
         derived_module: SharedDerivedModule,
+        fuel: SolveFuel,
     },
     BuildPendingSpecializations {
         module_timing: ModuleTiming,
@@ -1264,11 +1472,14 @@ enum BuildTask<'a> {
         layout_cache: LayoutCache<'a>,
         specializations_we_must_make: Vec<ExternalSpecializations<'a>>,
         module_timing: ModuleTiming,
-        exposed_by_module: ExposedByModule,
+        exposed_by_module: Arc<ExposedByModule>,
         world_abilities: WorldAbilities,
         derived_module: SharedDerivedModule,
         expectations: Option<Expectations>,
     },
+    /// Make parts of the specializations of a module, while the worker that makes the module
+    /// waits for them
+    HelpMakeSpecializations { parts: Arc<SpecializationParts<'a>> },
 }
 
 #[derive(Debug)]
@@ -1277,6 +1488,37 @@ enum WorkerMsg {
     TaskAdded,
 }
 
+/// How many of the workers are running a task, to know how many could help with another one
+#[derive(Debug)]
+struct BusyWorkers {
+    workers: usize,
+    busy: AtomicUsize,
+}
+
+impl BusyWorkers {
+    fn new(workers: usize) -> Self {
+        Self {
+            workers,
+            busy: AtomicUsize::new(0),
+        }
+    }
+
+    fn idle(&self) -> usize {
+        self.workers
+            .saturating_sub(self.busy.load(std::sync::atomic::Ordering::Relaxed))
+    }
+
+    fn run<T>(&self, task: impl FnOnce() -> T) -> T {
+        use std::sync::atomic::Ordering::Relaxed;
+
+        self.busy.fetch_add(1, Relaxed);
+        let result = task();
+        self.busy.fetch_sub(1, Relaxed);
+
+        result
+    }
+}
+
 #[derive(Debug)]
 pub struct IncorrectModuleName<'a> {
     pub module_id: ModuleId,
@@ -1292,6 +1534,16 @@ pub enum LoadingProblem<'a> {
     },
     ParsingFailed(FileError<'a, SyntaxError<'a>>),
     UnexpectedHeader(String),
+    /// An imported module whose file is not where its name says it is
+    ModuleNotFound {
+        module_name: String,
+        filename: PathBuf,
+    },
+    /// A file that is not valid UTF-8 from this byte on
+    InvalidUtf8 {
+        filename: PathBuf,
+        byte_index: usize,
+    },
 
     MsgChannelDied,
     ErrJoiningWorkerThreads,
@@ -1303,6 +1555,9 @@ pub enum LoadingProblem<'a> {
     ImportCycle(PathBuf, Vec<ModuleId>),
     IncorrectModuleName(FileError<'a, IncorrectModuleName<'a>>),
     CouldNotFindCacheDir,
+
+    /// The [CancelToken] of the load was cancelled before it finished
+    Cancelled,
 }
 
 pub enum Phases {
@@ -1316,11 +1571,12 @@ type MsgSender<'a> = Sender<Msg<'a>>;
 
 /// Add a task to the queue, and notify all the listeners.
 fn enqueue_task<'a>(
-    injector: &Injector<BuildTask<'a>>,
+    injector: &TaskQueue<BuildTask<'a>>,
     listeners: &[Sender<WorkerMsg>],
     task: BuildTask<'a>,
+    priority: Priority,
 ) -> Result<(), LoadingProblem<'a>> {
-    injector.push(task);
+    injector.push(task, priority);
 
     for listener in listeners {
         listener
@@ -1357,6 +1613,10 @@ pub fn load_and_typecheck_str<'a>(
         palette,
         threading,
         exec_mode: ExecutionMode::Check,
+        cancel: CancelToken::default(),
+        progress: ProgressReporter::default(),
+        hot_module: HotModule::default(),
+        solve_fuel: SolveFuel::default(),
     };
 
     match load(
@@ -1397,7 +1657,7 @@ impl<'a> LoadStart<'a> {
     ) -> Result<Self, LoadingProblem<'a>> {
         let arc_modules = Arc::new(Mutex::new(PackageModuleIds::default()));
         let root_exposed_ident_ids = IdentIds::exposed_builtins(0);
-        let ident_ids_by_module = Arc::new(Mutex::new(root_exposed_ident_ids));
+        let ident_ids_by_module = Arc::new(SharedIdentIds::new(root_exposed_ident_ids));
         let mut src_dir = filename.parent().unwrap().to_path_buf();
 
         // Load the root module synchronously; we can't proceed until we have its id.
@@ -1480,7 +1740,7 @@ impl<'a> LoadStart<'a> {
     ) -> Result<Self, LoadingProblem<'a>> {
         let arc_modules = Arc::new(Mutex::new(PackageModuleIds::default()));
         let root_exposed_ident_ids = IdentIds::exposed_builtins(0);
-        let ident_ids_by_module = Arc::new(Mutex::new(root_exposed_ident_ids));
+        let ident_ids_by_module = Arc::new(SharedIdentIds::new(root_exposed_ident_ids));
 
         // Load the root module synchronously; we can't proceed until we have its id.
         let HeaderOutput {
@@ -1617,6 +1877,10 @@ pub fn load<'a>(
             load_config.palette,
             load_config.exec_mode,
             roc_cache_dir,
+            &load_config.cancel,
+            load_config.progress,
+            load_config.hot_module,
+            load_config.solve_fuel,
         ),
         Threads::Many(threads) => load_multi_threaded(
             arena,
@@ -1629,6 +1893,10 @@ pub fn load<'a>(
             threads,
             load_config.exec_mode,
             roc_cache_dir,
+            &load_config.cancel,
+            load_config.progress,
+            load_config.hot_module,
+            load_config.solve_fuel,
         ),
     }
 }
@@ -1644,6 +1912,10 @@ pub fn load_single_threaded<'a>(
     palette: Palette,
     exec_mode: ExecutionMode,
     roc_cache_dir: RocCacheDir<'_>,
+    cancel: &CancelToken,
+    progress: ProgressReporter,
+    hot_module: HotModule,
+    solve_fuel: SolveFuel,
 ) -> Result<LoadResult<'a>, LoadingProblem<'a>> {
     let LoadStart {
         arc_modules,
@@ -1674,10 +1946,13 @@ pub fn load_single_threaded<'a>(
         palette,
         number_of_workers,
         exec_mode,
+        progress,
+        hot_module,
+        solve_fuel,
     );
 
     // We'll add tasks to this, and then worker threads will take tasks from it.
-    let injector = Injector::new();
+    let injector = TaskQueue::default();
 
     let (worker_msg_tx, worker_msg_rx) = bounded(1024);
     let worker_listener = worker_msg_tx;
@@ -1687,6 +1962,8 @@ pub fn load_single_threaded<'a>(
     let stealer = worker.stealer();
     let stealers = &[stealer];
 
+    let busy_workers = BusyWorkers::new(number_of_workers);
+
     // now we just manually interleave stepping the state "thread" and the worker "thread"
     loop {
         match state_thread_step(
@@ -1697,6 +1974,7 @@ pub fn load_single_threaded<'a>(
             &injector,
             &msg_tx,
             &msg_rx,
+            cancel,
         ) {
             Ok(ControlFlow::Break(done)) => return Ok(done),
             Ok(ControlFlow::Continue(new_state)) => {
@@ -1716,6 +1994,8 @@ pub fn load_single_threaded<'a>(
             &src_dir,
             roc_cache_dir,
             target_info,
+            &busy_workers,
+            cancel,
         );
 
         match control_flow {
@@ -1733,10 +2013,16 @@ fn state_thread_step<'a>(
     state: State<'a>,
     src_dir: &Path,
     worker_listeners: &'a [Sender<WorkerMsg>],
-    injector: &Injector<BuildTask<'a>>,
+    injector: &TaskQueue<BuildTask<'a>>,
     msg_tx: &crossbeam::channel::Sender<Msg<'a>>,
     msg_rx: &crossbeam::channel::Receiver<Msg<'a>>,
+    cancel: &CancelToken,
 ) -> Result<ControlFlow<LoadResult<'a>, State<'a>>, LoadingProblem<'a>> {
+    // Give up right away, rather than waiting for the workers to notice the cancellation.
+    if cancel.is_cancelled() {
+        return Err(LoadingProblem::Cancelled);
+    }
+
     match msg_rx.try_recv() {
         Ok(msg) => {
             match msg {
@@ -1758,6 +2044,8 @@ fn state_thread_step<'a>(
                         .map(|(k, (_, v))| (k, v))
                         .collect();
 
+                    state.progress.report(LoadEvent::Finished);
+
                     let typechecked = finish(
                         state,
                         solved_subs,
@@ -1781,6 +2069,8 @@ fn state_thread_step<'a>(
                     // We're done! There should be no more messages pending.
                     debug_assert!(msg_rx.is_empty());
 
+                    state.progress.report(LoadEvent::Finished);
+
                     let monomorphized = finish_specialization(
                         arena,
                         state,
@@ -1796,6 +2086,15 @@ fn state_thread_step<'a>(
                     let buf = to_file_problem_report_string(&filename, error);
                     Err(LoadingProblem::FormattedReport(buf))
                 }
+                Msg::FailedToLoad(
+                    problem @ (LoadingProblem::ModuleNotFound { .. }
+                    | LoadingProblem::InvalidUtf8 { .. }),
+                ) => {
+                    let module_ids = (*state.arc_modules).lock().clone().into_module_ids();
+                    let buf =
+                        report_loading_problem(problem, module_ids, state.render, state.palette);
+                    Err(LoadingProblem::FormattedReport(buf))
+                }
 
                 Msg::FailedToParse(problem) => {
                     let module_ids = (*state.arc_modules).lock().clone().into_module_ids();
@@ -1944,6 +2243,14 @@ pub fn report_loading_problem(
         LoadingProblem::FileProblem { filename, error } => {
             to_file_problem_report_string(&filename, error)
         }
+        LoadingProblem::ModuleNotFound {
+            module_name,
+            filename,
+        } => to_module_not_found_report_string(&module_name, &filename),
+        LoadingProblem::InvalidUtf8 {
+            filename,
+            byte_index,
+        } => to_invalid_utf8_report_string(&filename, byte_index),
         err => todo!("Loading error: {:?}", err),
     }
 }
@@ -1959,6 +2266,10 @@ fn load_multi_threaded<'a>(
     available_threads: usize,
     exec_mode: ExecutionMode,
     roc_cache_dir: RocCacheDir<'_>,
+    cancel: &CancelToken,
+    progress: ProgressReporter,
+    hot_module: HotModule,
+    solve_fuel: SolveFuel,
 ) -> Result<LoadResult<'a>, LoadingProblem<'a>> {
     let LoadStart {
         arc_modules,
@@ -2004,6 +2315,9 @@ fn load_multi_threaded<'a>(
         palette,
         num_workers,
         exec_mode,
+        progress,
+        hot_module,
+        solve_fuel,
     );
 
     // an arena for every worker, stored in an arena-allocated bumpalo vec to make the lifetimes work
@@ -2011,7 +2325,10 @@ fn load_multi_threaded<'a>(
     let worker_arenas = arena.alloc(bumpalo::collections::Vec::from_iter_in(arenas, arena));
 
     // We'll add tasks to this, and then worker threads will take tasks from it.
-    let injector = Injector::new();
+    let injector = TaskQueue::default();
+
+    // Workers that are not running a task help to make the specializations of large modules.
+    let busy_workers = BusyWorkers::new(num_workers);
 
     // We need to allocate worker *queues* on the main thread and then move them
     // into the worker threads, because those workers' stealers need to be
@@ -2049,6 +2366,7 @@ fn load_multi_threaded<'a>(
                 // (since other threads need to reference it too). Same with src_dir.
                 let injector = &injector;
                 let src_dir = &src_dir;
+                let busy_workers = &busy_workers;
 
                 // Record this thread's handle so the main thread can join it later.
                 let res_join_handle = thread_scope
@@ -2066,6 +2384,8 @@ fn load_multi_threaded<'a>(
                             src_dir,
                             roc_cache_dir,
                             target_info,
+                            busy_workers,
+                            cancel,
                         )
                     });
 
@@ -2103,6 +2423,7 @@ fn load_multi_threaded<'a>(
                     &injector,
                     &msg_tx,
                     &msg_rx,
+                    cancel,
                 ) {
                     Ok(ControlFlow::Break(load_result)) => {
                         shut_down_worker_threads!();
@@ -2128,13 +2449,15 @@ fn load_multi_threaded<'a>(
 fn worker_task_step<'a>(
     worker_arena: &'a Bump,
     worker: &Worker<BuildTask<'a>>,
-    injector: &Injector<BuildTask<'a>>,
+    injector: &TaskQueue<BuildTask<'a>>,
     stealers: &[Stealer<BuildTask<'a>>],
     worker_msg_rx: &crossbeam::channel::Receiver<WorkerMsg>,
     msg_tx: &MsgSender<'a>,
     src_dir: &Path,
     roc_cache_dir: RocCacheDir<'_>,
     target_info: TargetInfo,
+    busy_workers: &BusyWorkers,
+    cancel: &CancelToken,
 ) -> Result<ControlFlow<(), ()>, LoadingProblem<'a>> {
     match worker_msg_rx.try_recv() {
         Ok(msg) => {
@@ -2157,14 +2480,18 @@ fn worker_task_step<'a>(
                     // added. In that case, do nothing, and keep waiting
                     // until we receive a Shutdown message.
                     if let Some(task) = find_task(worker, injector, stealers) {
-                        let result = run_task(
-                            task,
-                            worker_arena,
-                            src_dir,
-                            msg_tx.clone(),
-                            roc_cache_dir,
-                            target_info,
-                        );
+                        let result = busy_workers.run(|| {
+                            run_task(
+                                task,
+                                worker_arena,
+                                src_dir,
+                                msg_tx.clone(),
+                                roc_cache_dir,
+                                target_info,
+                                busy_workers,
+                                cancel,
+                            )
+                        });
 
                         match result {
                             Ok(()) => {}
@@ -2179,9 +2506,18 @@ fn worker_task_step<'a>(
                                     .send(Msg::FailedToReadFile { filename, error })
                                     .unwrap();
                             }
+                            Err(
+                                problem @ (LoadingProblem::ModuleNotFound { .. }
+                                | LoadingProblem::InvalidUtf8 { .. }),
+                            ) => {
+                                msg_tx.send(Msg::FailedToLoad(problem)).unwrap();
+                            }
                             Err(LoadingProblem::IncorrectModuleName(err)) => {
                                 msg_tx.send(Msg::IncorrectModuleName(err)).unwrap();
                             }
+                            Err(LoadingProblem::Cancelled) => {
+                                // the main thread stops the load once it sees the cancellation
+                            }
                             Err(other) => {
                                 return Err(other);
                             }
@@ -2202,13 +2538,15 @@ fn worker_task_step<'a>(
 fn worker_task<'a>(
     worker_arena: &'a Bump,
     worker: Worker<BuildTask<'a>>,
-    injector: &Injector<BuildTask<'a>>,
+    injector: &TaskQueue<BuildTask<'a>>,
     stealers: &[Stealer<BuildTask<'a>>],
     worker_msg_rx: crossbeam::channel::Receiver<WorkerMsg>,
     msg_tx: MsgSender<'a>,
     src_dir: &Path,
     roc_cache_dir: RocCacheDir<'_>,
     target_info: TargetInfo,
+    busy_workers: &BusyWorkers,
+    cancel: &CancelToken,
 ) -> Result<(), LoadingProblem<'a>> {
     // Keep listening until we receive a Shutdown msg
     for msg in worker_msg_rx.iter() {
@@ -2237,7 +2575,7 @@ fn worker_task<'a>(
                             BuildTask::LoadModule { module_name, .. } => {
                                 format!("BuildTask::LoadModule({:?})", module_name)
                             }
-                            BuildTask::Parse { header } => {
+                            BuildTask::Parse { header, .. } => {
                                 format!("BuildTask::Parse({})", header.module_path.display())
                             }
                             BuildTask::CanonicalizeAndConstrain { parsed, .. } => format!(
@@ -2253,17 +2591,24 @@ fn worker_task<'a>(
                             BuildTask::MakeSpecializations { module_id, .. } => {
                                 format!("BuildTask::MakeSpecializations({:?})", module_id)
                             }
+                            BuildTask::HelpMakeSpecializations { .. } => {
+                                "BuildTask::HelpMakeSpecializations".to_string()
+                            }
                         }
                     );
 
-                    let result = run_task(
-                        task,
-                        worker_arena,
-                        src_dir,
-                        msg_tx.clone(),
-                        roc_cache_dir,
-                        target_info,
-                    );
+                    let result = busy_workers.run(|| {
+                        run_task(
+                            task,
+                            worker_arena,
+                            src_dir,
+                            msg_tx.clone(),
+                            roc_cache_dir,
+                            target_info,
+                            busy_workers,
+                            cancel,
+                        )
+                    });
 
                     match result {
                         Ok(()) => {}
@@ -2278,9 +2623,18 @@ fn worker_task<'a>(
                                 .send(Msg::FailedToReadFile { filename, error })
                                 .unwrap();
                         }
+                        Err(
+                            problem @ (LoadingProblem::ModuleNotFound { .. }
+                            | LoadingProblem::InvalidUtf8 { .. }),
+                        ) => {
+                            msg_tx.send(Msg::FailedToLoad(problem)).unwrap();
+                        }
                         Err(LoadingProblem::IncorrectModuleName(err)) => {
                             msg_tx.send(Msg::IncorrectModuleName(err)).unwrap();
                         }
+                        Err(LoadingProblem::Cancelled) => {
+                            // the main thread stops the load once it sees the cancellation
+                        }
                         Err(other) => {
                             return Err(other);
                         }
@@ -2297,12 +2651,14 @@ fn start_tasks<'a>(
     arena: &'a Bump,
     state: &mut State<'a>,
     work: MutSet<(ModuleId, Phase)>,
-    injector: &Injector<BuildTask<'a>>,
+    injector: &TaskQueue<BuildTask<'a>>,
     worker_listeners: &'a [Sender<WorkerMsg>],
 ) -> Result<(), LoadingProblem<'a>> {
     for (module_id, phase) in work {
+        let priority = state.priority(module_id);
+
         for task in start_phase(module_id, phase, arena, state) {
-            enqueue_task(injector, worker_listeners, task)?
+            enqueue_task(injector, worker_listeners, task, priority)?
         }
     }
 
@@ -2346,6 +2702,24 @@ macro_rules! debug_check_ir {
     };
 }
 
+macro_rules! debug_round_trip_ir {
+    ($state:expr, $arena:expr, $interner:expr, $flag:path) => {
+        dbg_do!($flag, {
+            use roc_mono::serialize::{deserialize_procs, serialize_procs};
+
+            let mut bytes = Vec::new();
+            serialize_procs(&$interner, &$state.procedures, &mut bytes)
+                .expect("writing to a Vec cannot fail");
+
+            let ((interner, procedures), _) = deserialize_procs($arena, $state.target_info, &bytes)
+                .expect("the mono IR could not be read back from the mono cache format");
+
+            $interner = interner;
+            $state.procedures = procedures;
+        })
+    };
+}
+
 /// Report modules that are imported, but from which nothing is used
 fn report_unused_imported_modules<'a>(
     state: &mut State<'a>,
@@ -2401,7 +2775,7 @@ fn update<'a>(
     src_dir: &Path,
     msg: Msg<'a>,
     msg_tx: MsgSender<'a>,
-    injector: &Injector<BuildTask<'a>>,
+    injector: &TaskQueue<BuildTask<'a>>,
     worker_listeners: &'a [Sender<WorkerMsg>],
     arena: &'a Bump,
 ) -> Result<State<'a>, LoadingProblem<'a>> {
@@ -2418,12 +2792,23 @@ fn update<'a>(
 
             Ok(state)
         }
-        Header(header) => {
+        Header(mut header) => {
             use HeaderType::*;
 
             log!("loaded header for {:?}", header.module_id);
             let home = header.module_id;
 
+            if let Some(problem) = header.load_problem.take() {
+                let module_ids = state.arc_modules.lock().clone().into_module_ids();
+                let report =
+                    report_loading_problem(problem, module_ids, state.render, state.palette);
+
+                state
+                    .module_cache
+                    .parse_problem_reports
+                    .insert(home, report);
+            }
+
             let mut work = MutSet::default();
 
             // Register the package's path under its shorthand
@@ -2594,6 +2979,19 @@ fn update<'a>(
             // We need a proper solution that marks a phase as in-progress so it's not repeated
             // debug_assert!(!state.exposed_symbols_by_module.contains_key(&home));
 
+            let is_root_package_interface = !header.is_root_module
+                && matches!(header.header_type, HeaderType::Interface { .. })
+                && matches!(
+                    state.arc_modules.lock().get_name(home),
+                    Some(PQModuleName::Unqualified(_))
+                );
+
+            if is_root_package_interface {
+                state
+                    .interface_exposes
+                    .insert(home, exposed_symbols.clone());
+            }
+
             state
                 .exposed_symbols_by_module
                 .insert(home, exposed_symbols);
@@ -2615,6 +3013,7 @@ fn update<'a>(
                 extend_header_with_builtin(header, ModuleId::ENCODE);
                 extend_header_with_builtin(header, ModuleId::DECODE);
                 extend_header_with_builtin(header, ModuleId::HASH);
+                extend_header_with_builtin(header, ModuleId::INSPECT);
             }
 
             state
@@ -2651,18 +3050,35 @@ fn update<'a>(
 
             start_tasks(arena, &mut state, work, injector, worker_listeners)?;
 
+            state.progress.report(LoadEvent::HeaderLoaded(home));
             let work = state.dependencies.notify(home, Phase::LoadHeader);
 
             start_tasks(arena, &mut state, work, injector, worker_listeners)?;
 
             Ok(state)
         }
-        Parsed(parsed) => {
+        Parsed(mut parsed) => {
             state
                 .module_cache
                 .sources
                 .insert(parsed.module_id, (parsed.module_path.clone(), parsed.src));
 
+            if let Some(problem) = parsed.parse_problem.take() {
+                let module_ids = state.arc_modules.lock().clone().into_module_ids();
+                let report = to_parse_problem_report(
+                    problem,
+                    module_ids,
+                    state.constrained_ident_ids.clone(),
+                    state.render,
+                    state.palette,
+                );
+
+                state
+                    .module_cache
+                    .parse_problem_reports
+                    .insert(parsed.module_id, report);
+            }
+
             // If this was an app module, set the output path to be
             // the module's declared "name".
             //
@@ -2682,6 +3098,7 @@ fn update<'a>(
 
             state.module_cache.parsed.insert(parsed.module_id, parsed);
 
+            state.progress.report(LoadEvent::Parsed(module_id));
             let work = state.dependencies.notify(module_id, Phase::Parse);
 
             start_tasks(arena, &mut state, work, injector, worker_listeners)?;
@@ -2696,17 +3113,22 @@ fn update<'a>(
         }) => {
             let module_id = constrained_module.module.module_id;
             log!("generated constraints for {:?}", module_id);
-            state
-                .module_cache
-                .can_problems
-                .insert(module_id, canonicalization_problems);
+
+            // A module whose body failed to parse only reports that; everything it exposes
+            // would be reported as not defined, and all of its imports as unused.
+            if !state.module_cache.is_salvaged(module_id) {
+                state
+                    .module_cache
+                    .can_problems
+                    .insert(module_id, canonicalization_problems);
+
+                report_unused_imported_modules(&mut state, module_id, &constrained_module);
+            }
 
             if let Some(docs) = module_docs {
                 state.module_cache.documentation.insert(module_id, docs);
             }
 
-            report_unused_imported_modules(&mut state, module_id, &constrained_module);
-
             state
                 .module_cache
                 .aliases
@@ -2733,7 +3155,7 @@ fn update<'a>(
         SolvedTypes {
             module_id,
             ident_ids,
-            solved_module,
+            mut solved_module,
             solved_subs,
             decls,
             dep_idents,
@@ -2745,10 +3167,52 @@ fn update<'a>(
             log!("solved types for {:?}", module_id);
             module_timing.end_time = Instant::now();
 
-            state
-                .module_cache
-                .type_problems
-                .insert(module_id, solved_module.problems);
+            if state.module_cache.is_salvaged(module_id) {
+                // Importers get an error type for everything this module exposes, so that
+                // their uses of it don't cause further errors.
+                let exposed_types = &mut solved_module.exposed_types;
+
+                for (_, var) in exposed_types.stored_vars_by_symbol.iter() {
+                    exposed_types
+                        .storage_subs
+                        .as_inner_mut()
+                        .set_content(*var, Content::Error);
+                }
+            } else {
+                // Solving stopped half way, so the types of this module can't be trusted and
+                // nothing can be built from them. Checking still reports it with everything else.
+                let did_not_converge = solved_module
+                    .problems
+                    .iter()
+                    .find(|problem| matches!(problem, TypeError::DidNotConverge(_)));
+
+                if let (Some(problem), false) = (
+                    did_not_converge,
+                    matches!(state.exec_mode, ExecutionMode::Check),
+                ) {
+                    let (filename, src) = state.module_cache.sources.get(&module_id).unwrap();
+                    let mut all_ident_ids = state.constrained_ident_ids.clone();
+                    all_ident_ids.insert(module_id, ident_ids.clone());
+
+                    let buf = to_did_not_converge_report(
+                        module_id,
+                        problem.clone(),
+                        filename.clone(),
+                        src,
+                        state.arc_modules.lock().clone().into_module_ids(),
+                        all_ident_ids,
+                        state.render,
+                        state.palette,
+                    );
+
+                    return Err(LoadingProblem::FormattedReport(buf));
+                }
+
+                state
+                    .module_cache
+                    .type_problems
+                    .insert(module_id, solved_module.problems);
+            }
 
             let should_include_expects = (!loc_expects.is_empty() || !loc_dbgs.is_empty()) && {
                 let modules = state.arc_modules.lock();
@@ -2771,6 +3235,7 @@ fn update<'a>(
                 None
             };
 
+            state.progress.report(LoadEvent::Checked(module_id));
             let work = state.dependencies.notify(module_id, Phase::SolveTypes);
 
             // if there is a platform, the `platform` module provides host-exposed,
@@ -2824,12 +3289,14 @@ fn update<'a>(
                         TypeCheckedModule {
                             ident_ids,
                             module_timing,
+                            decls,
                             ..
                         },
                     ) in state.module_cache.typechecked.drain()
                     {
                         state.constrained_ident_ids.insert(module_id, ident_ids);
                         state.timings.insert(module_id, module_timing);
+                        state.declarations_by_id.insert(module_id, decls);
                     }
                 }
 
@@ -2892,6 +3359,7 @@ fn update<'a>(
                 } else {
                     state.constrained_ident_ids.insert(module_id, ident_ids);
                     state.timings.insert(module_id, module_timing);
+                    state.declarations_by_id.insert(module_id, decls);
                 }
 
                 let work = if is_host_exposed && state.exec_mode.build_if_checks() {
@@ -2972,6 +3440,7 @@ fn update<'a>(
             module_timing,
             layout_cache,
             expectations,
+            mono_stats,
             ..
         } => {
             debug_assert!(
@@ -2981,6 +3450,8 @@ fn update<'a>(
 
             log!("made specializations for {:?}", module_id);
 
+            state.mono_stats.merge(mono_stats);
+
             // in the future, layouts will be in SoA form and we'll want to hold on to this data
             let _ = layout_cache;
 
@@ -2997,6 +3468,7 @@ fn update<'a>(
                 },
             );
 
+            state.progress.report(LoadEvent::Specialized(module_id));
             let work = state
                 .dependencies
                 .notify(module_id, Phase::MakeSpecializations);
@@ -3063,12 +3535,14 @@ fn update<'a>(
                             ident_ids,
                             subs,
                             module_timing,
-                            layout_cache: _layout_cache,
+                            layout_cache,
                             procs_base: _,
                             expectations,
                         },
                     ) in state.module_cache.late_specializations.drain()
                     {
+                        state.mono_stats.add_layout_cache(&layout_cache);
+
                         state.constrained_ident_ids.insert(module_id, ident_ids);
                         if module_id == state.root_id {
                             state.root_subs = Some(subs);
@@ -3080,7 +3554,7 @@ fn update<'a>(
 
                         #[cfg(debug_assertions)]
                         {
-                            log_layout_stats(module_id, &_layout_cache);
+                            log_layout_stats(module_id, &layout_cache);
                         }
                     }
 
@@ -3099,6 +3573,7 @@ fn update<'a>(
 
                     debug_print_ir!(state, &layout_interner, ROC_PRINT_IR_AFTER_SPECIALIZATION);
                     debug_check_ir!(state, arena, layout_interner, ROC_CHECK_MONO_IR);
+                    debug_round_trip_ir!(state, arena, layout_interner, ROC_CHECK_MONO_CACHE);
 
                     let ident_ids = state.constrained_ident_ids.get_mut(&module_id).unwrap();
 
@@ -3226,6 +3701,23 @@ fn update<'a>(
                 }
             }
         }
+        HelpMakeSpecializations {
+            module_id,
+            parts,
+            helpers,
+        } => {
+            let priority = state.priority(module_id);
+
+            for _ in 0..helpers {
+                let task = BuildTask::HelpMakeSpecializations {
+                    parts: Arc::clone(&parts),
+                };
+
+                enqueue_task(injector, worker_listeners, task, priority)?;
+            }
+
+            Ok(state)
+        }
         Msg::FailedToLoad(problem) => {
             // TODO report the error and continue instead of erroring out
             Err(problem)
@@ -3468,6 +3960,7 @@ fn finish_specialization<'a>(
         entry_point,
         sources,
         timings: state.timings,
+        mono_stats: state.mono_stats,
         toplevel_expects,
         glue_layouts: GlueLayouts {
             getters: glue_getters,
@@ -3542,7 +4035,9 @@ fn finish(
         solved,
         can_problems: state.module_cache.can_problems,
         type_problems: state.module_cache.type_problems,
+        parse_problem_reports: state.module_cache.parse_problem_reports,
         declarations_by_id: state.declarations_by_id,
+        interface_exposes: state.interface_exposes,
         dep_idents,
         exposed_aliases: exposed_aliases_by_symbol,
         exposed_values,
@@ -3567,7 +4062,7 @@ fn load_package_from_disk<'a>(
 ) -> Result<Msg<'a>, LoadingProblem<'a>> {
     let module_start_time = Instant::now();
     let file_io_start = module_start_time;
-    let read_result = fs::read(filename);
+    let read_result = read_roc_file(filename);
     let file_io_duration = file_io_start.elapsed();
 
     match read_result {
@@ -3675,10 +4170,7 @@ fn load_package_from_disk<'a>(
             }
         }
 
-        Err(err) => Err(LoadingProblem::FileProblem {
-            filename: filename.to_path_buf(),
-            error: err.kind(),
-        }),
+        Err(problem) => Err(problem),
     }
 }
 
@@ -3686,13 +4178,12 @@ fn get_exposes_ids<'a>(
     entries: &'a [Loc<Spaced<'a, roc_parse::header::ModuleName<'a>>>],
     arena: &'a Bump,
     module_ids: &Arc<Mutex<PackageModuleIds<'a>>>,
-    ident_ids_by_module: &Arc<Mutex<IdentIdsByModule>>,
+    ident_ids_by_module: &SharedIdentIds,
 ) -> bumpalo::collections::Vec<'a, ModuleId> {
     let mut exposes_ids = bumpalo::collections::Vec::with_capacity_in(entries.len(), arena);
 
     // Lock just long enough to perform the minimal operations necessary.
     let mut module_ids = (**module_ids).lock();
-    let mut ident_ids_by_module = (**ident_ids_by_module).lock();
 
     // TODO can we "iterate unspaced" instead of calling unspace here?
     for entry in unspace(arena, entries) {
@@ -3700,7 +4191,7 @@ fn get_exposes_ids<'a>(
             module_ids.get_or_insert(&PQModuleName::Unqualified(entry.value.as_str().into()));
 
         // Ensure this module has an entry in the ident_ids_by_module map.
-        ident_ids_by_module.get_or_insert(module_id);
+        ident_ids_by_module.module(module_id);
 
         exposes_ids.push(module_id);
     }
@@ -3785,6 +4276,7 @@ fn load_module<'a>(
     arc_shorthands: Arc<Mutex<MutMap<&'a str, ShorthandPath>>>,
     roc_cache_dir: RocCacheDir<'_>,
     ident_ids_by_module: SharedIdentIdsByModule,
+    salvage_failure: bool,
 ) -> Result<HeaderOutput<'a>, LoadingProblem<'a>> {
     let module_start_time = Instant::now();
 
@@ -3831,21 +4323,65 @@ fn load_module<'a>(
         "Decode", ModuleId::DECODE
         "Hash", ModuleId::HASH
         "Json", ModuleId::JSON
+        "Inspect", ModuleId::INSPECT
     }
 
     let (filename, opt_shorthand) = module_name_to_path(src_dir, &module_name, arc_shorthands);
 
-    load_filename(
+    let result = load_filename(
         arena,
+        filename.clone(),
+        false,
+        opt_shorthand,
+        Some(module_name.clone()),
+        Arc::clone(&module_ids),
+        Arc::clone(&ident_ids_by_module),
+        roc_cache_dir,
+        module_start_time,
+    );
+
+    let problem = match result {
+        Err(LoadingProblem::FileProblem {
+            filename,
+            error: io::ErrorKind::NotFound,
+        }) => LoadingProblem::ModuleNotFound {
+            module_name: module_name.as_inner().as_str().to_string(),
+            filename,
+        },
+        Err(problem @ LoadingProblem::InvalidUtf8 { .. }) => problem,
+        other => return other,
+    };
+
+    if !salvage_failure {
+        return Err(problem);
+    }
+
+    // Carry on as if the module was an interface that exposes nothing, so its importers can
+    // still be checked.
+    let src = arena.alloc_str(&format!(
+        "interface {} exposes [] imports []",
+        module_name.as_inner().as_str()
+    ));
+
+    let mut output = parse_header(
+        arena,
+        Duration::default(),
         filename,
         false,
         opt_shorthand,
         Some(module_name),
         module_ids,
         ident_ids_by_module,
+        src.as_bytes(),
         roc_cache_dir,
         module_start_time,
-    )
+    )?;
+
+    if let Msg::Header(header) = &mut output.msg {
+        header.load_problem = Some(problem);
+    }
+
+    Ok(output)
 }
 
 #[derive(Debug)]
@@ -3928,12 +4464,12 @@ fn module_name_to_path<'a>(
 /// Find a task according to the following algorithm:
 ///
 /// 1. Look in a local Worker queue. If it has a task, pop it off the queue and return it.
-/// 2. If that queue was empty, ask the global queue for a task.
+/// 2. If that queue was empty, ask the global queue for a task, hot tasks first.
 /// 3. If the global queue is also empty, iterate through each Stealer (each Worker queue has a
 ///    corresponding Stealer, which can steal from it. Stealers can be shared across threads.)
 ///
 /// Based on https://docs.rs/crossbeam/0.7.3/crossbeam/deque/index.html#examples
-fn find_task<T>(local: &Worker<T>, global: &Injector<T>, stealers: &[Stealer<T>]) -> Option<T> {
+fn find_task<T>(local: &Worker<T>, global: &TaskQueue<T>, stealers: &[Stealer<T>]) -> Option<T> {
     // Pop a task from the local queue, if not empty.
     local.pop().or_else(|| {
         // Otherwise, we need to look for a task elsewhere.
@@ -4367,28 +4903,39 @@ fn load_filename<'a>(
     module_start_time: Instant,
 ) -> Result<HeaderOutput<'a>, LoadingProblem<'a>> {
     let file_io_start = Instant::now();
-    let file = fs::read(&filename);
+    let bytes = read_roc_file(&filename)?;
     let file_io_duration = file_io_start.elapsed();
 
-    match file {
-        Ok(bytes) => parse_header(
-            arena,
-            file_io_duration,
-            filename,
-            is_root_module,
-            opt_shorthand,
-            opt_expected_module_name,
-            module_ids,
-            ident_ids_by_module,
-            arena.alloc(bytes),
-            roc_cache_dir,
-            module_start_time,
-        ),
-        Err(err) => Err(LoadingProblem::FileProblem {
-            filename,
-            error: err.kind(),
-        }),
+    parse_header(
+        arena,
+        file_io_duration,
+        filename,
+        is_root_module,
+        opt_shorthand,
+        opt_expected_module_name,
+        module_ids,
+        ident_ids_by_module,
+        arena.alloc(bytes),
+        roc_cache_dir,
+        module_start_time,
+    )
+}
+
+/// The parser and the reports assume that sources are valid UTF-8, so that's checked right away
+fn read_roc_file<'a>(filename: &Path) -> Result<Vec<u8>, LoadingProblem<'a>> {
+    let bytes = fs::read(filename).map_err(|err| LoadingProblem::FileProblem {
+        filename: filename.to_path_buf(),
+        error: err.kind(),
+    })?;
+
+    if let Err(err) = std::str::from_utf8(&bytes) {
+        return Err(LoadingProblem::InvalidUtf8 {
+            filename: filename.to_path_buf(),
+            byte_index: err.valid_up_to(),
+        });
     }
+
+    Ok(bytes)
 }
 
 /// Load a module from a str
@@ -4517,8 +5064,8 @@ fn build_header<'a>(
 
     let ident_ids = {
         // Lock just long enough to perform the minimal operations necessary.
+        // The IdentIds of each module are locked one at a time, see SharedIdentIds.
         let mut module_ids = (*module_ids).lock();
-        let mut ident_ids_by_module = (*ident_ids_by_module).lock();
 
         name = match opt_shorthand {
             Some(shorthand) => PQModuleName::Qualified(shorthand, declared_name),
@@ -4527,7 +5074,7 @@ fn build_header<'a>(
         home = module_ids.get_or_insert(&name);
 
         // Ensure this module has an entry in the ident_ids_by_module map.
-        ident_ids_by_module.get_or_insert(home);
+        let home_ident_ids = ident_ids_by_module.module(home);
 
         // For each of our imports, add an entry to deps_by_name
         //
@@ -4546,7 +5093,8 @@ fn build_header<'a>(
             // Add the new exposed idents to the dep module's IdentIds, so
             // once that module later gets loaded, its lookups will resolve
             // to the same symbols as the ones we're using here.
-            let ident_ids = ident_ids_by_module.get_or_insert(module_id);
+            let dep_ident_ids = ident_ids_by_module.module(module_id);
+            let mut ident_ids = dep_ident_ids.lock();
 
             for loc_ident in exposed_idents {
                 let ident_id = ident_ids.get_or_insert(loc_ident.value.as_str());
@@ -4575,7 +5123,8 @@ fn build_header<'a>(
             // want to make sure we're referencing the same symbols!
             let module_id = opt_app_module_id.unwrap_or(home);
             let mut symbols_from_requires = Vec::with_capacity(requires.len());
-            let ident_ids = ident_ids_by_module.get_or_insert(module_id);
+            let requires_ident_ids = ident_ids_by_module.module(module_id);
+            let mut ident_ids = requires_ident_ids.lock();
 
             for Loc {
                 value: entry,
@@ -4609,7 +5158,7 @@ fn build_header<'a>(
             Vec::new()
         };
 
-        let ident_ids = ident_ids_by_module.get_mut(&home).unwrap();
+        let mut ident_ids = home_ident_ids.lock();
 
         for loc_exposed in exposed_values.iter() {
             // Use get_or_insert here because the ident_ids may already
@@ -4651,7 +5200,7 @@ fn build_header<'a>(
         }
 
         if cfg!(debug_assertions) {
-            home.register_debug_idents(ident_ids);
+            home.register_debug_idents(&ident_ids);
         }
 
         ident_ids.clone()
@@ -4749,6 +5298,7 @@ fn build_header<'a>(
             header_comments,
             module_timing,
             defined_values,
+            load_problem: None,
         },
     ))
 }
@@ -4770,6 +5320,7 @@ impl<'a> BuildTask<'a> {
         declarations: Declarations,
         cached_subs: CachedTypeState,
         derived_module: SharedDerivedModule,
+        fuel: SolveFuel,
     ) -> Self {
         let exposed_by_module = exposed_types.retain_modules(imported_modules.keys());
 
@@ -4791,6 +5342,7 @@ impl<'a> BuildTask<'a> {
             module_timing,
             cached_subs,
             derived_module,
+            fuel,
         }
     }
 }
@@ -5050,13 +5602,18 @@ fn run_solve_solve(
     var_store: VarStore,
     module: Module,
     derived_module: SharedDerivedModule,
-) -> (
-    Solved<Subs>,
-    ResolvedImplementations,
-    Vec<(Symbol, Variable)>,
-    Vec<TypeError>,
-    AbilitiesStore,
-) {
+    fuel: SolveFuel,
+    cancel: &CancelToken,
+) -> Result<
+    (
+        Solved<Subs>,
+        ResolvedImplementations,
+        Vec<(Symbol, Variable)>,
+        Vec<TypeError>,
+        AbilitiesStore,
+    ),
+    Cancelled,
+> {
     let Module {
         exposed_symbols,
         aliases,
@@ -5110,7 +5667,9 @@ fn run_solve_solve(
             pending_derives,
             &exposed_for_module.exposed_by_module,
             derived_module,
-        );
+            cancel,
+            fuel,
+        )?;
 
         let solved_implementations =
             extract_module_owned_implementations(module_id, &abilities_store);
@@ -5144,13 +5703,13 @@ fn run_solve_solve(
         )
     };
 
-    (
+    Ok((
         solved_subs,
         solved_implementations,
         exposed_vars_by_symbol,
         problems,
         abilities_store,
-    )
+    ))
 }
 
 fn run_solve<'a>(
@@ -5167,7 +5726,9 @@ fn run_solve<'a>(
     dep_idents: IdentIdsByModule,
     cached_types: CachedTypeState,
     derived_module: SharedDerivedModule,
-) -> Msg<'a> {
+    fuel: SolveFuel,
+    cancel: &CancelToken,
+) -> Result<Msg<'a>, Cancelled> {
     let solve_start = Instant::now();
 
     let module_id = module.module_id;
@@ -5192,7 +5753,9 @@ fn run_solve<'a>(
                     var_store,
                     module,
                     derived_module,
-                ),
+                    fuel,
+                    cancel,
+                )?,
                 Some(TypeState {
                     subs,
                     exposed_vars_by_symbol,
@@ -5216,7 +5779,9 @@ fn run_solve<'a>(
                 var_store,
                 module,
                 derived_module,
-            )
+                fuel,
+                cancel,
+            )?
         }
     };
 
@@ -5242,7 +5807,7 @@ fn run_solve<'a>(
     module_timing.solve = solve_end.duration_since(solve_start);
 
     // Send the subs to the main thread for processing,
-    Msg::SolvedTypes {
+    Ok(Msg::SolvedTypes {
         module_id,
         solved_subs,
         ident_ids,
@@ -5253,7 +5818,7 @@ fn run_solve<'a>(
         abilities_store,
         loc_expects,
         loc_dbgs,
-    }
+    })
 }
 
 fn unspace<'a, T: Copy>(arena: &'a Bump, items: &[Loc<Spaced<'a, T>>]) -> &'a [Loc<T>] {
@@ -5531,6 +6096,7 @@ fn canonicalize_and_constrain<'a>(
                         | ModuleId::DICT
                         | ModuleId::SET
                         | ModuleId::HASH
+                        | ModuleId::INSPECT
                 );
 
                 if !name.is_builtin() || should_include_builtin {
@@ -5574,17 +6140,28 @@ fn canonicalize_and_constrain<'a>(
     }
 }
 
-fn parse<'a>(arena: &'a Bump, header: ModuleHeader<'a>) -> Result<Msg<'a>, LoadingProblem<'a>> {
+fn parse<'a>(
+    arena: &'a Bump,
+    header: ModuleHeader<'a>,
+    salvage_failure: bool,
+) -> Result<Msg<'a>, LoadingProblem<'a>> {
     let mut module_timing = header.module_timing;
     let parse_start = Instant::now();
     let source = header.parse_state.original_bytes();
     let parse_state = header.parse_state;
+    let mut parse_problem = None;
     let mut parsed_defs = match module_defs().parse(arena, parse_state.clone(), 0) {
         Ok((_, success, _state)) => success,
         Err((_, fail)) => {
-            return Err(LoadingProblem::ParsingFailed(
-                fail.into_file_error(header.module_path, &parse_state),
-            ));
+            let problem = fail.into_file_error(header.module_path.clone(), &parse_state);
+
+            if !salvage_failure {
+                return Err(LoadingProblem::ParsingFailed(problem));
+            }
+
+            // Carry on as if the module had no defs, so its dependents can still be checked.
+            parse_problem = Some(problem);
+            Defs::default()
         }
     };
     for value in header.defined_values.into_iter() {
@@ -5600,9 +6177,8 @@ fn parse<'a>(arena: &'a Bump, header: ModuleHeader<'a>) -> Result<Msg<'a>, Loadi
 
     let imported_modules = header.imported_modules;
 
-    // SAFETY: By this point we've already incrementally verified that there
-    // are no UTF-8 errors in these bytes. If there had been any UTF-8 errors,
-    // we'd have bailed out before now.
+    // SAFETY: Files are checked to be valid UTF-8 when they are read, see read_roc_file,
+    // and the other sources are strs to begin with.
     let src = unsafe { from_utf8_unchecked(source) };
 
     let ModuleHeader {
@@ -5627,6 +6203,7 @@ fn parse<'a>(arena: &'a Bump, header: ModuleHeader<'a>) -> Result<Msg<'a>, Loadi
         exposed_ident_ids,
         exposed_imports,
         parsed_defs,
+        parse_problem,
         symbols_from_requires,
         header_type,
         header_comments: header_docs,
@@ -5747,11 +6324,16 @@ fn make_specializations<'a>(
     mut module_timing: ModuleTiming,
     target_info: TargetInfo,
     world_abilities: WorldAbilities,
-    exposed_by_module: &ExposedByModule,
+    exposed_by_module: &Arc<ExposedByModule>,
     derived_module: SharedDerivedModule,
     mut expectations: Option<Expectations>,
-) -> Msg<'a> {
+    busy_workers: &BusyWorkers,
+    msg_tx: &MsgSender<'a>,
+    cancel: &CancelToken,
+) -> Result<Msg<'a>, Cancelled> {
     let make_specializations_start = Instant::now();
+    let arena_bytes_before = arena.allocated_bytes();
+    let mut mono_stats = MonoStats::default();
     let mut update_mode_ids = UpdateModeIds::new();
     // do the thing
     let mut mono_env = roc_mono::ir::Env {
@@ -5767,6 +6349,10 @@ fn make_specializations<'a>(
         abilities: AbilitiesView::World(&world_abilities),
         exposed_by_module,
         derived_module: &derived_module,
+        argument_layouts: SliceInterner::new_in(arena),
+        thread_ids: None,
+        problems: None,
+        region: Region::zero(),
     };
 
     let mut procs = Procs::new_in(arena);
@@ -5783,20 +6369,41 @@ fn make_specializations<'a>(
     procs.runtime_errors = procs_base.runtime_errors;
     procs.imported_module_thunks = procs_base.imported_module_thunks;
 
-    // TODO: for now this final specialization pass is sequential,
-    // with no parallelization at all. We should try to parallelize
-    // this, but doing so will require a redesign of Procs.
-    procs = roc_mono::ir::specialize_all(
+    // The parts are made in the arenas of the workers that help, which live as long as the load
+    // does, because the layouts that the parts intern point into them.
+    let ask = |parts, helpers| {
+        // when the channel died, the load stops anyway, and this worker makes all the parts
+        let _ = msg_tx.send(Msg::HelpMakeSpecializations {
+            module_id: home,
+            parts,
+            helpers,
+        });
+    };
+
+    let helpers = SpecializationHelpers {
+        idle: busy_workers.idle(),
+        world_abilities: &world_abilities,
+        exposed_by_module,
+        ask: &ask,
+    };
+
+    procs = roc_mono::ir::specialize_all_in_parallel(
         &mut mono_env,
         procs,
         specializations_we_must_make,
         procs_base.host_specializations,
         &mut layout_cache,
-    );
+        &helpers,
+        &mut mono_stats,
+        cancel,
+    )?;
 
     let external_specializations_requested = procs.externals_we_need.clone();
     let (procedures, restored_procs_base) = procs.get_specialized_procs_without_rc(&mut mono_env);
 
+    mono_stats.add_arena(arena, arena_bytes_before);
+    mono_stats.add_procs(procedures.values());
+
     // Turn `Bytes.Decode.IdentId(238)` into `Bytes.Decode.238`, we rely on this in mono tests
     mono_env.home.register_debug_idents(mono_env.ident_ids);
 
@@ -5805,7 +6412,7 @@ fn make_specializations<'a>(
         .make_specializations
         .push(make_specializations_end.duration_since(make_specializations_start));
 
-    Msg::MadeSpecializations {
+    Ok(Msg::MadeSpecializations {
         module_id: home,
         ident_ids,
         layout_cache,
@@ -5816,7 +6423,8 @@ fn make_specializations<'a>(
         expectations,
         external_specializations_requested,
         module_timing,
-    }
+        mono_stats,
+    })
 }
 
 fn build_pending_specializations<'a>(
@@ -5867,6 +6475,10 @@ fn build_pending_specializations<'a>(
         abilities: AbilitiesView::Module(&abilities_store),
         exposed_by_module,
         derived_module: &derived_module,
+        argument_layouts: SliceInterner::new_in(arena),
+        thread_ids: None,
+        problems: None,
+        region: Region::zero(),
     };
 
     let layout_cache_snapshot = layout_cache.snapshot();
@@ -6348,6 +6960,10 @@ fn load_derived_partial_procs<'a>(
             abilities: AbilitiesView::World(world_abilities),
             exposed_by_module,
             derived_module,
+            argument_layouts: SliceInterner::new_in(arena),
+            thread_ids: None,
+            problems: None,
+            region: Region::zero(),
         };
 
         let partial_proc = match derived_expr {
@@ -6412,15 +7028,23 @@ fn run_task<'a>(
     msg_tx: MsgSender<'a>,
     roc_cache_dir: RocCacheDir<'_>,
     target_info: TargetInfo,
+    busy_workers: &BusyWorkers,
+    cancel: &CancelToken,
 ) -> Result<(), LoadingProblem<'a>> {
     use BuildTask::*;
 
+    // Tasks that were queued before the cancellation are dropped.
+    if cancel.is_cancelled() {
+        return Err(LoadingProblem::Cancelled);
+    }
+
     let msg = match task {
         LoadModule {
             module_name,
             module_ids,
             shorthands,
             ident_ids_by_module,
+            salvage_failure,
         } => load_module(
             arena,
             src_dir,
@@ -6429,9 +7053,13 @@ fn run_task<'a>(
             shorthands,
             roc_cache_dir,
             ident_ids_by_module,
+            salvage_failure,
         )
         .map(|HeaderOutput { msg, .. }| msg),
-        Parse { header } => parse(arena, header),
+        Parse {
+            header,
+            salvage_failure,
+        } => parse(arena, header, salvage_failure),
         CanonicalizeAndConstrain {
             parsed,
             module_ids,
@@ -6470,7 +7098,8 @@ fn run_task<'a>(
             dep_idents,
             cached_subs,
             derived_module,
-        } => Ok(run_solve(
+            fuel,
+        } => run_solve(
             module,
             ident_ids,
             module_timing,
@@ -6484,7 +7113,10 @@ fn run_task<'a>(
             dep_idents,
             cached_subs,
             derived_module,
-        )),
+            fuel,
+            cancel,
+        )
+        .map_err(|Cancelled| LoadingProblem::Cancelled),
         BuildPendingSpecializations {
             module_id,
             ident_ids,
@@ -6528,7 +7160,7 @@ fn run_task<'a>(
             exposed_by_module,
             derived_module,
             expectations,
-        } => Ok(make_specializations(
+        } => make_specializations(
             arena,
             module_id,
             ident_ids,
@@ -6542,7 +7174,16 @@ fn run_task<'a>(
             &exposed_by_module,
             derived_module,
             expectations,
-        )),
+            busy_workers,
+            &msg_tx,
+            cancel,
+        )
+        .map_err(|Cancelled| LoadingProblem::Cancelled),
+        HelpMakeSpecializations { parts } => {
+            parts.help(arena, cancel);
+
+            return Ok(());
+        }
     }?;
 
     msg_tx
@@ -6660,6 +7301,36 @@ fn to_incorrect_module_name_report<'a>(
     buf
 }
 
+#[allow(clippy::too_many_arguments)]
+fn to_did_not_converge_report(
+    module_id: ModuleId,
+    problem: TypeError,
+    filename: PathBuf,
+    src: &str,
+    module_ids: ModuleIds,
+    all_ident_ids: IdentIdsByModule,
+    render: RenderTarget,
+    palette: Palette,
+) -> String {
+    use roc_reporting::report::{type_problem, RocDocAllocator};
+
+    let src_lines = src.lines().collect::<Vec<_>>();
+    let lines = LineInfo::new(src);
+
+    let interns = Interns {
+        module_ids,
+        all_ident_ids,
+    };
+    let alloc = RocDocAllocator::new(&src_lines, module_id, &interns);
+
+    let report = type_problem(&alloc, &lines, filename, problem)
+        .expect("a solver that did not converge is always reported");
+
+    let mut buf = String::new();
+    report.render(render, &mut buf, &alloc, &palette);
+    buf
+}
+
 fn to_parse_problem_report<'a>(
     problem: FileError<'a, SyntaxError<'a>>,
     mut module_ids: ModuleIds,
@@ -0,0 +1,50 @@
+//! The [IdentIds] of every module, shared by the threads of the load.
+//!
+//! Headers add idents to the modules they import, and canonicalization copies the idents of the
+//! modules it imports. With one lock around all modules, every worker that parses a header waits
+//! for every other one. Instead, every module has its own lock, so threads only wait on each
+//! other when they use the same module. The map from modules to their locks is only written when
+//! a module is seen for the first time; finding a module that's already there only takes a read
+//! lock, and readers never wait on each other.
+use std::sync::Arc;
+
+use parking_lot::{Mutex, RwLock};
+use roc_collections::MutMap;
+use roc_module::symbol::{IdentIds, IdentIdsByModule, ModuleId};
+
+#[derive(Debug, Default)]
+pub struct SharedIdentIds {
+    by_module: RwLock<MutMap<ModuleId, Arc<Mutex<IdentIds>>>>,
+}
+
+impl SharedIdentIds {
+    pub fn new(ident_ids_by_module: IdentIdsByModule) -> Self {
+        let by_module = ident_ids_by_module
+            .into_modules()
+            .map(|(module_id, ident_ids)| (module_id, Arc::new(Mutex::new(ident_ids))))
+            .collect();
+
+        Self {
+            by_module: RwLock::new(by_module),
+        }
+    }
+
+    /// The IdentIds of a module, which start out empty when the module has none yet.
+    ///
+    /// Only lock one module at a time, so two threads can't wait on each other.
+    pub fn module(&self, module_id: ModuleId) -> Arc<Mutex<IdentIds>> {
+        if let Some(ident_ids) = self.by_module.read().get(&module_id) {
+            return Arc::clone(ident_ids);
+        }
+
+        Arc::clone(self.by_module.write().entry(module_id).or_default())
+    }
+
+    /// A copy of the IdentIds of a module, if it has any
+    pub fn get_cloned(&self, module_id: ModuleId) -> Option<IdentIds> {
+        let ident_ids = Arc::clone(self.by_module.read().get(&module_id)?);
+        let cloned = ident_ids.lock().clone();
+
+        Some(cloned)
+    }
+}
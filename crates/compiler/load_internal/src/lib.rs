@@ -6,6 +6,9 @@
 use roc_module::symbol::ModuleId;
 pub mod docs;
 pub mod file;
+mod ident_ids;
+mod progress;
+mod schedule;
 mod work;
 
 #[cfg(target_family = "wasm")]
@@ -24,4 +27,5 @@ pub const BUILTIN_MODULES: &[(ModuleId, &str)] = &[
     (ModuleId::DECODE, "Decode"),
     (ModuleId::HASH, "Hash"),
     (ModuleId::JSON, "Json"),
+    (ModuleId::INSPECT, "Inspect"),
 ];
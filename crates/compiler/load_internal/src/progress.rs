@@ -0,0 +1,47 @@
+//! Events that let tools like `roc build` show how far along a load is.
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use roc_module::symbol::ModuleId;
+
+/// A step of the load that was just completed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadEvent {
+    /// The header of the module was loaded, so the module is part of the build.
+    HeaderLoaded(ModuleId),
+    /// The body of the module was parsed.
+    Parsed(ModuleId),
+    /// The module was type-checked.
+    Checked(ModuleId),
+    /// Specializations were made for the module. With several specialization passes, this
+    /// happens once per pass.
+    Specialized(ModuleId),
+    /// All modules reached the goal phase of the load.
+    Finished,
+}
+
+/// Receives the [LoadEvent]s of a load, in the order they happen, on the thread that
+/// coordinates the load. The default reporter drops all events.
+#[derive(Clone, Default)]
+pub struct ProgressReporter(Option<Arc<dyn Fn(LoadEvent) + Send + Sync>>);
+
+impl ProgressReporter {
+    pub fn new(on_event: impl Fn(LoadEvent) + Send + Sync + 'static) -> Self {
+        Self(Some(Arc::new(on_event)))
+    }
+
+    pub(crate) fn report(&self, event: LoadEvent) {
+        if let Some(on_event) = &self.0 {
+            on_event(event)
+        }
+    }
+}
+
+impl Debug for ProgressReporter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            Some(_) => f.write_str("ProgressReporter(..)"),
+            None => f.write_str("ProgressReporter(None)"),
+        }
+    }
+}
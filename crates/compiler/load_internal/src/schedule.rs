@@ -0,0 +1,70 @@
+//! Lets a frontend that re-checks a project while it is being edited, like the editor or a
+//! language server, tell the load which module the user is working on, so that the work on
+//! that module is done before the work on the rest of the project.
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crossbeam::deque::{Injector, Steal};
+use parking_lot::Mutex;
+
+/// The module the user is editing. Tasks for this module, and for the modules that import it
+/// directly, are taken from the task queue before any other task.
+///
+/// Clones share the same module, so a frontend can keep a clone and change the module while a
+/// load is running. The change applies to the tasks that are queued after it.
+#[derive(Debug, Clone, Default)]
+pub struct HotModule(Arc<Mutex<Option<PathBuf>>>);
+
+impl HotModule {
+    /// The path must be the one the load finds the module at, like the path given to
+    /// `LoadStart::from_path` for the root module.
+    pub fn set(&self, path: impl Into<PathBuf>) {
+        *self.0.lock() = Some(path.into());
+    }
+
+    pub fn clear(&self) {
+        *self.0.lock() = None;
+    }
+
+    pub(crate) fn is(&self, path: &Path) -> bool {
+        self.0.lock().as_deref() == Some(path)
+    }
+
+    pub(crate) fn is_set(&self) -> bool {
+        self.0.lock().is_some()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Priority {
+    Hot,
+    Background,
+}
+
+/// The global task queue. Workers only get a background task when there are no hot tasks.
+pub(crate) struct TaskQueue<T> {
+    hot: Injector<T>,
+    background: Injector<T>,
+}
+
+impl<T> Default for TaskQueue<T> {
+    fn default() -> Self {
+        Self {
+            hot: Injector::new(),
+            background: Injector::new(),
+        }
+    }
+}
+
+impl<T> TaskQueue<T> {
+    pub fn push(&self, task: T, priority: Priority) {
+        match priority {
+            Priority::Hot => self.hot.push(task),
+            Priority::Background => self.background.push(task),
+        }
+    }
+
+    pub fn steal(&self) -> Steal<T> {
+        self.hot.steal().or_else(|| self.background.steal())
+    }
+}
@@ -17,27 +17,54 @@ mod helpers;
 use crate::helpers::fixtures_dir;
 use bumpalo::Bump;
 use roc_can::module::ExposedByModule;
-use roc_load_internal::file::{ExecutionMode, LoadConfig, Threading};
+use roc_load_internal::file::{
+    CancelToken, ExecutionMode, HotModule, LoadConfig, LoadEvent, ProgressReporter, SolveFuel,
+    Threading,
+};
 use roc_load_internal::file::{LoadResult, LoadStart, LoadedModule, LoadingProblem};
 use roc_module::ident::ModuleName;
-use roc_module::symbol::{Interns, ModuleId};
+use roc_module::symbol::{Interns, ModuleId, Symbol};
 use roc_packaging::cache::RocCacheDir;
 use roc_problem::can::Problem;
 use roc_region::all::LineInfo;
 use roc_reporting::report::RenderTarget;
 use roc_reporting::report::RocDocAllocator;
 use roc_reporting::report::{can_problem, DEFAULT_PALETTE};
+use roc_solve_problem::TypeError;
 use roc_target::TargetInfo;
 use roc_types::pretty_print::name_and_print_var;
 use roc_types::pretty_print::DebugPrint;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
 
 fn load_and_typecheck(
     arena: &Bump,
     filename: PathBuf,
     exposed_types: ExposedByModule,
     target_info: TargetInfo,
+) -> Result<LoadedModule, LoadingProblem> {
+    load_and_typecheck_with(
+        arena,
+        filename,
+        exposed_types,
+        target_info,
+        CancelToken::default(),
+        ProgressReporter::default(),
+        HotModule::default(),
+        Threading::Single,
+    )
+}
+
+fn load_and_typecheck_with(
+    arena: &Bump,
+    filename: PathBuf,
+    exposed_types: ExposedByModule,
+    target_info: TargetInfo,
+    cancel: CancelToken,
+    progress: ProgressReporter,
+    hot_module: HotModule,
+    threading: Threading,
 ) -> Result<LoadedModule, LoadingProblem> {
     use LoadResult::*;
 
@@ -52,8 +79,12 @@ fn load_and_typecheck(
         target_info,
         render: RenderTarget::Generic,
         palette: DEFAULT_PALETTE,
-        threading: Threading::Single,
+        threading,
         exec_mode: ExecutionMode::Check,
+        cancel,
+        progress,
+        hot_module,
+        solve_fuel: SolveFuel::default(),
     };
 
     match roc_load_internal::file::load(
@@ -103,7 +134,7 @@ fn multiple_modules(subdir: &str, files: Vec<(&str, &str)>) -> Result<LoadedModu
     let arena = Bump::new();
     let arena = &arena;
 
-    match multiple_modules_help(subdir, arena, files) {
+    match multiple_modules_help(subdir, arena, files, Threading::Single) {
         Err(io_error) => panic!("IO trouble: {:?}", io_error),
         Ok(Err(LoadingProblem::FormattedReport(buf))) => Err(buf),
         Ok(Err(loading_problem)) => Err(format!("{:?}", loading_problem)),
@@ -137,6 +168,7 @@ fn multiple_modules_help<'a>(
     subdir: &str,
     arena: &'a Bump,
     mut files: Vec<(&str, &str)>,
+    threading: Threading,
 ) -> Result<Result<LoadedModule, roc_load_internal::file::LoadingProblem<'a>>, std::io::Error> {
     use std::fs::{self, File};
     use std::io::Write;
@@ -174,7 +206,16 @@ fn multiple_modules_help<'a>(
         writeln!(file, "{}", source)?;
         file_handles.push(file);
 
-        load_and_typecheck(arena, full_file_path, Default::default(), TARGET_INFO)
+        load_and_typecheck_with(
+            arena,
+            full_file_path,
+            Default::default(),
+            TARGET_INFO,
+            CancelToken::default(),
+            ProgressReporter::default(),
+            HotModule::default(),
+            threading,
+        )
     };
 
     Ok(result)
@@ -339,6 +380,242 @@ fn import_transitive_alias() {
     assert!(multiple_modules("import_transitive_alias", modules).is_ok());
 }
 
+#[test]
+fn cancelled_load() {
+    let src_dir = fixtures_dir().join("interface_with_deps");
+    let filename = src_dir.join("Primary.roc");
+    let arena = Bump::new();
+
+    let cancel = CancelToken::new();
+    cancel.cancel();
+
+    let loaded = load_and_typecheck_with(
+        &arena,
+        filename,
+        Default::default(),
+        TARGET_INFO,
+        cancel,
+        ProgressReporter::default(),
+        HotModule::default(),
+        Threading::Single,
+    );
+
+    assert!(matches!(loaded, Err(LoadingProblem::Cancelled)));
+}
+
+#[test]
+fn build_stops_when_solving_does_not_converge() {
+    fn load_with_mode(
+        arena: &Bump,
+        exec_mode: ExecutionMode,
+    ) -> Result<LoadResult, LoadingProblem> {
+        let filename = fixtures_dir()
+            .join("interface_with_deps")
+            .join("Primary.roc");
+        let load_start = LoadStart::from_path(
+            arena,
+            filename,
+            RenderTarget::Generic,
+            RocCacheDir::Disallowed,
+            DEFAULT_PALETTE,
+        )?;
+        let load_config = LoadConfig {
+            target_info: TARGET_INFO,
+            render: RenderTarget::Generic,
+            palette: DEFAULT_PALETTE,
+            threading: Threading::Single,
+            exec_mode,
+            cancel: CancelToken::default(),
+            progress: ProgressReporter::default(),
+            hot_module: HotModule::default(),
+            solve_fuel: SolveFuel(8),
+        };
+
+        roc_load_internal::file::load(
+            arena,
+            load_start,
+            Default::default(),
+            Default::default(),
+            RocCacheDir::Disallowed,
+            load_config,
+        )
+    }
+
+    // checking reports the module that did not converge along with all other problems
+    let arena = Bump::new();
+    match load_with_mode(&arena, ExecutionMode::Check) {
+        Ok(LoadResult::TypeChecked(module)) => {
+            assert!(module
+                .type_problems
+                .values()
+                .flatten()
+                .any(|problem| matches!(problem, TypeError::DidNotConverge(_))));
+        }
+        other => panic!("expected the module to type check, got {:?}", other.err()),
+    }
+
+    // building must not go on with types that were only half solved
+    let arena = Bump::new();
+    match load_with_mode(&arena, ExecutionMode::Executable) {
+        Err(LoadingProblem::FormattedReport(report)) => {
+            assert!(report.contains("TYPE CHECKING DID NOT CONVERGE"));
+        }
+        Ok(_) => panic!("the build went on after type checking did not converge"),
+        Err(other) => panic!("expected a formatted report, got {:?}", other),
+    }
+}
+
+#[test]
+fn load_progress_events() {
+    let src_dir = fixtures_dir().join("interface_with_deps");
+    let filename = src_dir.join("Primary.roc");
+    let arena = Bump::new();
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let progress = {
+        let events = Arc::clone(&events);
+        ProgressReporter::new(move |event| events.lock().unwrap().push(event))
+    };
+
+    let loaded = load_and_typecheck_with(
+        &arena,
+        filename,
+        Default::default(),
+        TARGET_INFO,
+        CancelToken::default(),
+        progress,
+        HotModule::default(),
+        Threading::Single,
+    );
+    let home = loaded.expect("Test module failed to load").module_id;
+
+    let events = events.lock().unwrap();
+    let (mut loaded_modules, mut parsed, mut checked) = (Vec::new(), Vec::new(), Vec::new());
+    for event in events.iter() {
+        match event {
+            LoadEvent::HeaderLoaded(id) => loaded_modules.push(*id),
+            LoadEvent::Parsed(id) => parsed.push(*id),
+            LoadEvent::Checked(id) => checked.push(*id),
+            LoadEvent::Specialized(_) | LoadEvent::Finished => {}
+        }
+    }
+
+    // Every module that is part of the load is parsed and checked exactly once.
+    let as_set = |modules: &[ModuleId]| modules.iter().copied().collect::<HashSet<_>>();
+    assert!(loaded_modules.contains(&home));
+    assert_eq!(parsed.len(), loaded_modules.len());
+    assert_eq!(checked.len(), loaded_modules.len());
+    assert_eq!(as_set(&parsed), as_set(&loaded_modules));
+    assert_eq!(as_set(&checked), as_set(&loaded_modules));
+    assert_eq!(events.last(), Some(&LoadEvent::Finished));
+}
+
+#[test]
+fn hot_module_load() {
+    let src_dir = fixtures_dir().join("interface_with_deps");
+    let filename = src_dir.join("Primary.roc");
+    let arena = Bump::new();
+
+    let checked = Arc::new(Mutex::new(Vec::new()));
+    let progress = {
+        let checked = Arc::clone(&checked);
+        ProgressReporter::new(move |event| {
+            if let LoadEvent::Checked(id) = event {
+                checked.lock().unwrap().push(id)
+            }
+        })
+    };
+
+    let hot_module = HotModule::default();
+    hot_module.set(src_dir.join("Dep1.roc"));
+
+    let loaded = load_and_typecheck_with(
+        &arena,
+        filename,
+        Default::default(),
+        TARGET_INFO,
+        CancelToken::default(),
+        progress,
+        hot_module,
+        Threading::Single,
+    );
+    let mut loaded_module = loaded.expect("Test module failed to load");
+    let home = loaded_module.module_id;
+
+    // The hot module only changes the order of the work, every module is still checked.
+    assert!(loaded_module
+        .type_problems
+        .remove(&home)
+        .unwrap_or_default()
+        .is_empty());
+    assert!(checked.lock().unwrap().contains(&home));
+}
+
+#[test]
+fn headers_loaded_concurrently() {
+    // every header adds the idents it imports to Shared, on as many threads as we can get
+    const DEPS: usize = 8;
+
+    let shared = format!(
+        "interface Shared exposes [{}] imports []\n\n{}",
+        (0..DEPS)
+            .map(|i| format!("v{i}"))
+            .collect::<Vec<_>>()
+            .join(", "),
+        (0..DEPS)
+            .map(|i| format!("v{i} = {i}\n"))
+            .collect::<String>(),
+    );
+
+    let deps: Vec<_> = (0..DEPS)
+        .map(|i| {
+            // every header imports the idents in another order
+            let imports: Vec<_> = (0..DEPS).map(|j| format!("v{}", (i + j) % DEPS)).collect();
+
+            (
+                format!("Dep{i}"),
+                format!(
+                    "interface Dep{i} exposes [d{i}] imports [Shared.{{ {} }}]\n\nd{i} = {}\n",
+                    imports.join(", "),
+                    imports.join(" + "),
+                ),
+            )
+        })
+        .collect();
+
+    let main = format!(
+        "interface Main exposes [main] imports [{}]\n\nmain = {}\n",
+        (0..DEPS)
+            .map(|i| format!("Dep{i}"))
+            .collect::<Vec<_>>()
+            .join(", "),
+        (0..DEPS)
+            .map(|i| format!("Dep{i}.d{i}"))
+            .collect::<Vec<_>>()
+            .join(" + "),
+    );
+
+    let mut modules = vec![("Shared", shared.as_str())];
+    modules.extend(deps.iter().map(|(name, src)| (name.as_str(), src.as_str())));
+    modules.push(("Main", main.as_str()));
+
+    for _ in 0..10 {
+        let arena = Bump::new();
+        let loaded_module = match multiple_modules_help(
+            "headers_loaded_concurrently",
+            &arena,
+            modules.clone(),
+            Threading::AtMost(DEPS),
+        ) {
+            Ok(Ok(loaded_module)) => loaded_module,
+            other => panic!("{:?}", other),
+        };
+
+        assert_eq!(loaded_module.total_problems(), 0);
+        expect_types(loaded_module, hashmap! { "main" => "Num *" });
+    }
+}
+
 #[test]
 fn interface_with_deps() {
     let subs_by_module = Default::default();
@@ -654,16 +931,67 @@ fn file_not_found() {
 }
 
 #[test]
-#[should_panic(expected = "FILE NOT FOUND")]
 fn imported_file_not_found() {
-    let subs_by_module = Default::default();
-    let loaded_module = load_fixture("no_deps", "MissingDep", subs_by_module);
+    let filename = fixtures_dir().join("no_deps").join("MissingDep.roc");
+    let arena = Bump::new();
+    let mut loaded_module =
+        load_and_typecheck(&arena, filename, Default::default(), TARGET_INFO).unwrap();
 
-    expect_types(
-        loaded_module,
-        hashmap! {
-            "str" => "Str",
-        },
+    let home = loaded_module.module_id;
+
+    // the missing module is reported, and MissingDep is checked as if it exposed nothing
+    assert_eq!(loaded_module.parse_problem_reports.len(), 1);
+    assert!(!loaded_module.parse_problem_reports.contains_key(&home));
+
+    let report = loaded_module.parse_problem_reports.values().next().unwrap();
+    assert!(report.contains("MODULE NOT FOUND"), "report=({})", report);
+    assert!(
+        report.contains("ThisFileIsMissing.roc"),
+        "report=({})",
+        report
+    );
+
+    assert!(loaded_module
+        .type_problems
+        .remove(&home)
+        .unwrap_or_default()
+        .is_empty());
+}
+
+#[test]
+fn imported_file_not_utf8() {
+    use std::fs;
+
+    let dir = roc_test_utils::TmpDir::new("tmp/imported_file_not_utf8");
+
+    let mut dep_src = b"interface Dep exposes [x] imports []\n\nx = \"".to_vec();
+    let byte_index = dep_src.len();
+    dep_src.extend([0xff, b'"', b'\n']);
+
+    fs::write(dir.path().join("Dep.roc"), dep_src).unwrap();
+    fs::write(
+        dir.path().join("Main.roc"),
+        "interface Main exposes [main] imports [Dep]\n\nmain = 1\n",
+    )
+    .unwrap();
+
+    let arena = Bump::new();
+    let loaded_module = load_and_typecheck(
+        &arena,
+        dir.path().join("Main.roc"),
+        Default::default(),
+        TARGET_INFO,
+    )
+    .unwrap();
+
+    assert_eq!(loaded_module.parse_problem_reports.len(), 1);
+
+    let report = loaded_module.parse_problem_reports.values().next().unwrap();
+    assert!(report.contains("INVALID UTF-8"), "report=({})", report);
+    assert!(
+        report.contains(&byte_index.to_string()),
+        "report=({})",
+        report
     );
 }
 
@@ -868,6 +1196,187 @@ fn opaque_wrapped_unwrapped_outside_defining_module() {
     );
 }
 
+#[test]
+fn check_importer_of_module_that_fails_to_parse() {
+    let modules = vec![
+        (
+            "Dep",
+            indoc!(
+                r#"
+                    interface Dep exposes [x, f] imports []
+
+                    x = 1
+
+                    f = \n -> )
+                    "#
+            ),
+        ),
+        (
+            "Main",
+            indoc!(
+                r#"
+                    interface Main exposes [main] imports [Dep]
+
+                    main = Dep.f Dep.x |> Num.add 1
+                    "#
+            ),
+        ),
+    ];
+
+    let arena = Bump::new();
+    let mut loaded_module = match multiple_modules_help(
+        "check_importer_of_module_that_fails_to_parse",
+        &arena,
+        modules,
+        Threading::Single,
+    ) {
+        Ok(Ok(loaded_module)) => loaded_module,
+        other => panic!("{:?}", other),
+    };
+
+    let home = loaded_module.module_id;
+
+    // the parse problem is reported for Dep, and Main is checked without further problems
+    assert_eq!(loaded_module.parse_problem_reports.len(), 1);
+    assert!(!loaded_module.parse_problem_reports.contains_key(&home));
+    assert_eq!(loaded_module.total_problems(), 1);
+    assert!(loaded_module
+        .type_problems
+        .remove(&home)
+        .unwrap_or_default()
+        .is_empty());
+}
+
+#[test]
+fn call_graph_across_modules() {
+    let modules = vec![
+        (
+            "Dep",
+            indoc!(
+                r#"
+                    interface Dep exposes [one, two, unused] imports []
+
+                    one = 1
+
+                    two = \x -> Num.add x one
+
+                    unused = 3
+                    "#
+            ),
+        ),
+        (
+            "Main",
+            indoc!(
+                r#"
+                    interface Main exposes [main] imports [Dep]
+
+                    main = \n ->
+                        local = \y -> Dep.two y
+
+                        local n
+                    "#
+            ),
+        ),
+    ];
+
+    let loaded_module = multiple_modules("call_graph_across_modules", modules).unwrap();
+    let interns = &loaded_module.interns;
+    let call_graph = loaded_module.call_graph();
+
+    let qualified = |symbol: &Symbol| {
+        format!(
+            "{}.{}",
+            symbol.module_string(interns).as_str(),
+            symbol.as_str(interns)
+        )
+    };
+    let lookup = |name: &str| {
+        *call_graph
+            .defs()
+            .iter()
+            .find(|symbol| qualified(symbol) == name)
+            .unwrap()
+    };
+
+    let main = lookup("Main.main");
+    let two = lookup("Dep.two");
+
+    // calls from the body of a local def belong to the top-level def, locals are not in the graph
+    assert_eq!(
+        call_graph
+            .callees(main)
+            .iter()
+            .map(qualified)
+            .collect::<Vec<_>>(),
+        ["Dep.two"]
+    );
+    assert_eq!(
+        call_graph
+            .callees(two)
+            .iter()
+            .map(qualified)
+            .collect::<Vec<_>>(),
+        ["Num.add", "Dep.one"]
+    );
+    assert_eq!(call_graph.callers(two), [main]);
+    assert_eq!(
+        call_graph
+            .unreachable_from([main])
+            .iter()
+            .filter(|symbol| !symbol.is_builtin())
+            .map(qualified)
+            .collect::<Vec<_>>(),
+        ["Dep.unused"]
+    );
+
+    let json = call_graph.to_json(interns);
+    assert!(json.contains(r#"{ "module": "Dep", "name": "two", "start": "#));
+}
+
+#[test]
+fn unused_exposed_values() {
+    let modules = vec![
+        (
+            "Dep",
+            indoc!(
+                r#"
+                    interface Dep exposes [one, two, unused] imports []
+
+                    one = 1
+
+                    two = \x -> Num.add x one
+
+                    unused = 3
+                    "#
+            ),
+        ),
+        (
+            "Main",
+            indoc!(
+                r#"
+                    interface Main exposes [main, alsoUnused] imports [Dep]
+
+                    main = Dep.two 1
+
+                    alsoUnused = 4
+                    "#
+            ),
+        ),
+    ];
+
+    let loaded_module = multiple_modules("unused_exposed_values", modules).unwrap();
+    let interns = &loaded_module.interns;
+
+    // `one` is only used by Dep itself, and what the root module exposes is never reported
+    let unused: Vec<&str> = loaded_module
+        .unused_exposed_values()
+        .iter()
+        .map(|loc_symbol| loc_symbol.value.as_str(interns))
+        .collect();
+
+    assert_eq!(unused, ["one", "unused"]);
+}
+
 #[test]
 fn issue_2863_module_type_does_not_exist() {
     let modules = vec![
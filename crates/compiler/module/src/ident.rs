@@ -114,6 +114,7 @@ impl ModuleName {
     pub const DECODE: &'static str = "Decode";
     pub const HASH: &'static str = "Hash";
     pub const JSON: &'static str = "Json";
+    pub const INSPECT: &'static str = "Inspect";
 
     pub fn as_str(&self) -> &str {
         self.0.as_str()
@@ -4,6 +4,7 @@ use crate::symbol::Symbol;
 /// These are always wrapped when exposed to end users, and can only make it
 /// into an Expr when added directly by can::builtins
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u8)]
 pub enum LowLevel {
     StrConcat,
     StrJoinWith,
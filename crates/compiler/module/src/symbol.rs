@@ -52,6 +52,7 @@ pub const DERIVABLE_ABILITIES: &[(Symbol, &[Symbol])] = &[
     (Symbol::DECODE_DECODING, &[Symbol::DECODE_DECODER]),
     (Symbol::HASH_HASH_ABILITY, &[Symbol::HASH_HASH]),
     (Symbol::BOOL_EQ, &[Symbol::BOOL_IS_EQ]),
+    (Symbol::INSPECT_INSPECT_ABILITY, &[Symbol::INSPECT_INSPECT]),
 ];
 
 /// In Debug builds only, Symbol has a name() method that lets
@@ -107,6 +108,9 @@ impl Symbol {
             // The `structuralEq` call used deriving structural equality, which will wrap the `Eq`
             // low-level implementation.
             &Self::BOOL_STRUCTURAL_EQ
+            // Used for lists and boxes whose elements may have a custom `isEq`.
+            | &Self::LIST_IS_EQ
+            | &Self::BOX_IS_EQ
         )
     }
 
@@ -721,6 +725,10 @@ impl IdentIdsByModule {
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
+
+    pub fn into_modules(self) -> impl Iterator<Item = (ModuleId, IdentIds)> {
+        self.0.into_iter()
+    }
 }
 
 // BUILTINS
@@ -1090,6 +1098,9 @@ define_builtins! {
         31 ATTR_INVALID: "#attr_invalid"
 
         32 CLONE: "#clone" // internal function that clones a value into a buffer
+
+        // used in dev backend
+        33 DEV_TMP6: "#dev_tmp6"
     }
     // Fake module for synthesizing and storing derived implementations
     1 DERIVED_SYNTH: "#Derived" => {
@@ -1414,6 +1425,7 @@ define_builtins! {
         79 LIST_WALK_FROM_UNTIL: "walkFromUntil"
         80 LIST_ITER_HELP: "iterHelp"
         81 LIST_RELEASE_EXCESS_CAPACITY: "releaseExcessCapacity"
+        82 LIST_IS_EQ: "isEq"
     }
     7 RESULT: "Result" => {
         0 RESULT_RESULT: "Result" exposed_type=true // the Result.Result type alias
@@ -1455,6 +1467,7 @@ define_builtins! {
         21 DICT_UPDATE: "update"
 
         22 DICT_LIST_GET_UNSAFE: "listGetUnsafe"
+        23 DICT_REMOVE_ORDERED: "removeOrdered"
     }
     9 SET: "Set" => {
         0 SET_SET: "Set" exposed_type=true // the Set.Set type alias
@@ -1474,11 +1487,13 @@ define_builtins! {
         14 SET_CONTAINS: "contains"
         15 SET_TO_DICT: "toDict"
         16 SET_CAPACITY: "capacity"
+        17 SET_REMOVE_ORDERED: "removeOrdered"
     }
     10 BOX: "Box" => {
         0 BOX_BOX_TYPE: "Box" exposed_apply_type=true // the Box.Box opaque type
         1 BOX_BOX_FUNCTION: "box" // Box.box
         2 BOX_UNBOX: "unbox"
+        3 BOX_IS_EQ: "isEq"
     }
     11 ENCODE: "Encode" => {
         0 ENCODE_ENCODER: "Encoder" exposed_type=true
@@ -1565,6 +1580,16 @@ define_builtins! {
     14 JSON: "Json" => {
         0 JSON_JSON: "Json"
     }
+    15 INSPECT: "Inspect" => {
+        0 INSPECT_INSPECT_ABILITY: "Inspect" exposed_type=true
+        1 INSPECT_INSPECT: "inspect"
+        2 INSPECT_INSPECT_BOOL: "inspectBool"
+        3 INSPECT_INSPECT_NUM: "inspectNum"
+        4 INSPECT_INSPECT_STR: "inspectStr"
+        5 INSPECT_INSPECT_LIST: "inspectList"
+        6 INSPECT_APPEND: "append"
+        7 INSPECT_PARENTHESIZE: "parenthesize"
+    }
 
-    num_modules: 15 // Keep this count up to date by hand! (TODO: see the mut_map! macro for how we could determine this count correctly in the macro)
+    num_modules: 16 // Keep this count up to date by hand! (TODO: see the mut_map! macro for how we could determine this count correctly in the macro)
 }
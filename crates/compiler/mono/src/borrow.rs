@@ -537,7 +537,7 @@ impl<'a> BorrowInfState<'a> {
                 arg_layouts,
                 ..
             } => {
-                let top_level = ProcLayout::new(self.arena, arg_layouts, name.niche(), *ret_layout);
+                let top_level = ProcLayout::new(arg_layouts, name.niche(), *ret_layout);
 
                 // get the borrow signature of the applied function
                 let ps = param_map
@@ -717,7 +717,7 @@ impl<'a> BorrowInfState<'a> {
                 self.own_var(*x);
                 self.own_args_if_param(ys);
             }
-            EmptyArray => {
+            StaticArray { .. } | EmptyArray => {
                 self.own_var(z);
             }
 
@@ -775,7 +775,7 @@ impl<'a> BorrowInfState<'a> {
             Stmt::Ret(z),
         ) = (v, b)
         {
-            let top_level = ProcLayout::new(self.arena, arg_layouts, g.niche(), *ret_layout);
+            let top_level = ProcLayout::new(arg_layouts, g.niche(), *ret_layout);
 
             if self.current_proc == g.name() && x == *z {
                 // anonymous functions (for which the ps may not be known)
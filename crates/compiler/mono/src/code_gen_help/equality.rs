@@ -114,6 +114,7 @@ fn if_pointers_equal_return_true<'a>(
                         Stmt::Ret(Symbol::BOOL_TRUE),
                     )]),
                     default_branch: (BranchInfo::None, following),
+                    branch_hints: &[],
                     ret_layout: LAYOUT_BOOL,
                 }),
             )),
@@ -133,6 +134,7 @@ fn if_false_return_false<'a>(
             .arena
             .alloc([(0, BranchInfo::None, Stmt::Ret(Symbol::BOOL_FALSE))]),
         default_branch: (BranchInfo::None, following),
+        branch_hints: &[],
         ret_layout: LAYOUT_BOOL,
     }
 }
@@ -407,6 +409,7 @@ fn eq_tag_union_help<'a>(
                 default_tag,
             )),
         ),
+        branch_hints: &[],
         ret_layout: LAYOUT_BOOL,
     };
 
@@ -415,6 +418,7 @@ fn eq_tag_union_help<'a>(
         cond_layout: LAYOUT_BOOL,
         branches: if_equal_ids_branches,
         default_branch: (BranchInfo::None, root.arena.alloc(tag_switch_stmt)),
+        branch_hints: &[],
         ret_layout: LAYOUT_BOOL,
     };
 
@@ -824,6 +828,7 @@ fn eq_list<'a>(
     let if_end_of_list = Stmt::Switch {
         cond_symbol: is_end,
         cond_layout: LAYOUT_BOOL,
+        branch_hints: &[],
         ret_layout: LAYOUT_BOOL,
         branches: root
             .arena
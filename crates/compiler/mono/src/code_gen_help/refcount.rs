@@ -9,7 +9,8 @@ use roc_target::PtrWidth;
 use crate::borrow::Ownership;
 use crate::code_gen_help::let_lowlevel;
 use crate::ir::{
-    BranchInfo, Call, CallType, Expr, JoinPointId, Literal, ModifyRc, Param, Stmt, UpdateModeId,
+    BranchHint, BranchInfo, Call, CallType, Expr, JoinPointId, Literal, ModifyRc, Param, Stmt,
+    UpdateModeId,
 };
 use crate::layout::{
     Builtin, InLayout, Layout, LayoutInterner, STLayoutInterner, TagIdIntType, UnionLayout,
@@ -318,6 +319,8 @@ pub fn refcount_reset_proc_body<'a>(
         cond_layout: LAYOUT_BOOL,
         branches: root.arena.alloc([(1, BranchInfo::None, then_stmt)]),
         default_branch: (BranchInfo::None, root.arena.alloc(else_stmt)),
+        // the point of resetting is to reuse the allocation, which needs it to be unique
+        branch_hints: &[BranchHint::Likely, BranchHint::None],
         ret_layout: layout,
     };
 
@@ -478,6 +481,8 @@ pub fn refcount_resetref_proc_body<'a>(
         cond_layout: LAYOUT_BOOL,
         branches: root.arena.alloc([(1, BranchInfo::None, then_stmt)]),
         default_branch: (BranchInfo::None, root.arena.alloc(else_stmt)),
+        // the point of resetting is to reuse the allocation, which needs it to be unique
+        branch_hints: &[BranchHint::Likely, BranchHint::None],
         ret_layout: layout,
     };
 
@@ -829,6 +834,7 @@ fn refcount_str<'a>(
             BranchInfo::None,
             root.arena.alloc(rc_return_stmt(root, ident_ids, ctx)),
         ),
+        branch_hints: &[],
         ret_layout: LAYOUT_UNIT,
     };
 
@@ -959,6 +965,7 @@ fn refcount_list<'a>(
             .arena
             .alloc([(1, BranchInfo::None, rc_return_stmt(root, ident_ids, ctx))]),
         default_branch: (BranchInfo::None, non_empty_branch),
+        branch_hints: &[],
         ret_layout: LAYOUT_UNIT,
     };
 
@@ -1084,6 +1091,7 @@ fn refcount_list_elems<'a>(
     let if_end_of_list = Stmt::Switch {
         cond_symbol: is_end,
         cond_layout: LAYOUT_BOOL,
+        branch_hints: &[],
         ret_layout,
         branches: root.arena.alloc([(1, BranchInfo::None, following)]),
         default_branch: (
@@ -1437,6 +1445,7 @@ fn refcount_union_contents<'a>(
         cond_layout: tag_id_layout,
         branches: tag_branches.into_bump_slice(),
         default_branch: (BranchInfo::None, root.arena.alloc(default_stmt)),
+        branch_hints: &[],
         ret_layout: LAYOUT_UNIT,
     };
 
@@ -1576,6 +1585,7 @@ fn refcount_union_tailrec<'a>(
             cond_layout: root.layout_isize,
             branches: root.arena.alloc([(0, BranchInfo::None, exit_stmt)]),
             default_branch: (BranchInfo::None, root.arena.alloc(jump_to_loop)),
+            branch_hints: &[],
             ret_layout: LAYOUT_UNIT,
         };
         let loop_or_exit_based_on_next_addr = {
@@ -1705,6 +1715,7 @@ fn refcount_union_tailrec<'a>(
             cond_layout: tag_id_layout,
             branches: tag_branches.into_bump_slice(),
             default_branch: (BranchInfo::None, root.arena.alloc(default_stmt)),
+            branch_hints: &[],
             ret_layout: LAYOUT_UNIT,
         };
 
@@ -287,6 +287,7 @@ impl<'a, 'r> Ctx<'a, 'r> {
                 cond_layout,
                 branches,
                 default_branch,
+                branch_hints: _,
                 ret_layout: _,
             } => {
                 self.check_sym_layout(*cond_symbol, *cond_layout, UseKind::SwitchCond);
@@ -440,6 +441,10 @@ impl<'a, 'r> Ctx<'a, 'r> {
                         .insert(Layout::Builtin(Builtin::List(*elem_layout))),
                 )
             }
+            Expr::StaticArray { elem_layout, .. } => Some(
+                self.interner
+                    .insert(Layout::Builtin(Builtin::List(*elem_layout))),
+            ),
             Expr::EmptyArray => {
                 // TODO don't know what the element layout is
                 None
@@ -521,6 +521,7 @@ fn expand_and_cancel<'a>(env: &mut Env<'a, '_>, stmt: &'a Stmt<'a>) -> &'a Stmt<
                 ret_layout,
                 branches,
                 default_branch,
+                branch_hints,
             } => {
                 let mut new_branches = Vec::with_capacity_in(branches.len(), env.arena);
 
@@ -549,6 +550,7 @@ fn expand_and_cancel<'a>(env: &mut Env<'a, '_>, stmt: &'a Stmt<'a>) -> &'a Stmt<
                     ret_layout: *ret_layout,
                     branches: new_branches.into_bump_slice(),
                     default_branch: new_default,
+                    branch_hints,
                 };
 
                 &*env.arena.alloc(stmt)
@@ -169,6 +169,7 @@ impl<'a, 'i> SymbolRcTypesEnv<'a, 'i> {
                 cond_layout: _,
                 branches,
                 default_branch,
+                branch_hints: _,
                 ret_layout: _,
             } => {
                 // Collect the types of the symbols in all the branches, including the default one.
@@ -513,6 +514,7 @@ fn insert_refcount_operations_stmt<'v, 'a>(
             cond_layout,
             branches,
             default_branch,
+            branch_hints,
             ret_layout,
         } => {
             let new_branches = branches
@@ -631,6 +633,7 @@ fn insert_refcount_operations_stmt<'v, 'a>(
                 cond_layout: *cond_layout,
                 branches: newer_branches,
                 default_branch: newer_default_branch,
+                branch_hints,
                 ret_layout: *ret_layout,
             })
         }
@@ -890,8 +893,12 @@ fn insert_refcount_operations_binding<'a>(
     }
 
     match expr {
-        Expr::Literal(_) | Expr::NullPointer | Expr::EmptyArray | Expr::RuntimeErrorFunction(_) => {
-            // Literals, empty arrays, and runtime errors are not (and have nothing) reference counted.
+        Expr::Literal(_)
+        | Expr::NullPointer
+        | Expr::StaticArray { .. }
+        | Expr::EmptyArray
+        | Expr::RuntimeErrorFunction(_) => {
+            // Literals, static and empty arrays, and runtime errors have nothing reference counted.
             new_let!(stmt)
         }
         Expr::Call(Call {
@@ -7,13 +7,16 @@ use crate::layout::{
     LambdaSet, Layout, LayoutCache, LayoutInterner, LayoutProblem, Niche, RawFunctionLayout,
     TLLayoutInterner, TagIdIntType, UnionLayout, WrappedVariant,
 };
+use crate::stats::MonoStats;
 use bumpalo::collections::{CollectIn, Vec};
 use bumpalo::Bump;
+use parking_lot::Mutex;
 use roc_can::abilities::SpecializationId;
 use roc_can::expr::{AnnotatedMark, ClosureData, ExpectLookup};
 use roc_can::module::ExposedByModule;
+use roc_can::pattern::BindingsFromPattern;
 use roc_collections::all::{default_hasher, BumpMap, BumpMapDefault, MutMap};
-use roc_collections::VecMap;
+use roc_collections::{SliceInterner, SmallVec, VecMap};
 use roc_debug_flags::dbg_do;
 #[cfg(debug_assertions)]
 use roc_debug_flags::{
@@ -23,11 +26,14 @@ use roc_debug_flags::{
 use roc_derive::SharedDerivedModule;
 use roc_error_macros::{internal_error, todo_abilities};
 use roc_late_solve::storage::{ExternalModuleStorage, ExternalModuleStorageSnapshot};
-use roc_late_solve::{resolve_ability_specialization, AbilitiesView, Resolved, UnificationFailed};
+use roc_late_solve::{
+    resolve_ability_specialization, AbilitiesView, Resolved, UnificationFailed, WorldAbilities,
+};
 use roc_module::ident::{ForeignSymbol, Lowercase, TagName};
 use roc_module::low_level::LowLevel;
-use roc_module::symbol::{IdentIds, ModuleId, Symbol};
+use roc_module::symbol::{IdentId, IdentIds, ModuleId, Symbol};
 use roc_problem::can::{RuntimeError, ShadowKind};
+use roc_problem::cancel::{CancelToken, Cancelled};
 use roc_region::all::{Loc, Region};
 use roc_std::RocDec;
 use roc_target::TargetInfo;
@@ -36,6 +42,8 @@ use roc_types::subs::{
     StorageSubs, Subs, Variable, VariableSubsSlice,
 };
 use std::collections::HashMap;
+use std::sync::{mpsc, Arc};
+use std::time::Instant;
 use ven_pretty::{BoxAllocator, DocAllocator, DocBuilder};
 
 use pattern::{from_can_pattern, store_pattern, Pattern};
@@ -88,6 +96,19 @@ fn runtime_error<'a>(env: &mut Env<'a, '_>, msg: &'a str) -> Stmt<'a> {
     )
 }
 
+/// Like [runtime_error], for a construct that could not be lowered rather than an error of the
+/// program itself. While [Stmt::try_new] runs, the problem is recorded at [Env::region].
+fn lowering_error<'a>(env: &mut Env<'a, '_>, kind: MonoProblemKind, msg: &'a str) -> Stmt<'a> {
+    if let Some(problems) = env.problems.as_mut() {
+        problems.push(MonoProblem {
+            kind,
+            region: env.region,
+        });
+    }
+
+    runtime_error(env, msg)
+}
+
 macro_rules! return_on_layout_error {
     ($env:expr, $layout_result:expr, $context_msg:expr) => {
         match $layout_result {
@@ -101,15 +122,17 @@ macro_rules! return_on_layout_error_help {
     ($env:expr, $error:expr, $context_msg:expr) => {{
         match $error {
             LayoutProblem::UnresolvedTypeVar(_) => {
-                return runtime_error(
+                return lowering_error(
                     $env,
+                    MonoProblemKind::UnresolvedTypeVar,
                     $env.arena
                         .alloc(format!("UnresolvedTypeVar: {}", $context_msg,)),
                 )
             }
             LayoutProblem::Erroneous => {
-                return runtime_error(
+                return lowering_error(
                     $env,
+                    MonoProblemKind::Erroneous,
                     $env.arena.alloc(format!("Erroneous: {}", $context_msg,)),
                 )
             }
@@ -191,6 +214,12 @@ impl<'a> PartialProcs<'a> {
         id
     }
 
+    fn to_vec(&self) -> std::vec::Vec<(Symbol, PartialProc<'a>)> {
+        let partial_procs = self.partial_procs.iter().cloned();
+
+        self.symbols.iter().copied().zip(partial_procs).collect()
+    }
+
     pub fn drain(self) -> impl Iterator<Item = (Symbol, PartialProc<'a>)> {
         debug_assert_eq!(self.symbols.len(), self.partial_procs.len());
 
@@ -418,6 +447,20 @@ impl<'a> Proc<'a> {
         }
 
         use self::SelfRecursive::*;
+        if let NotSelfRecursive = self.is_self_recursive {
+            let arg_layouts =
+                Vec::from_iter_in(self.args.iter().map(|(layout, _)| *layout), env.arena);
+
+            if crate::tail_recursion::has_self_tail_call(
+                self.name,
+                &self.body,
+                &arg_layouts,
+                self.ret_layout,
+            ) {
+                self.is_self_recursive = SelfRecursive(JoinPointId(env.unique_symbol()));
+            }
+        }
+
         if let SelfRecursive(id) = self.is_self_recursive {
             let transformed = crate::tail_recursion::make_tail_recursive(
                 env.arena,
@@ -551,7 +594,7 @@ impl<'a> ExternalSpecializations<'a> {
     fn insert_external(
         &mut self,
         symbol_or_lambda: LambdaName<'a>,
-        env_subs: &mut Subs,
+        env_subs: &Subs,
         variable: Variable,
     ) {
         let stored_variable = self.storage.extend_with_variable(env_subs, variable);
@@ -573,6 +616,44 @@ impl<'a> ExternalSpecializations<'a> {
         }
     }
 
+    /// The number of specializations, counting every type of a symbol
+    fn len(&self) -> usize {
+        self.types_to_specialize
+            .iter()
+            .map(|types| types.len())
+            .sum()
+    }
+
+    /// Adds the specializations of `other`, which may have been stored on another thread.
+    fn extend(&mut self, other: Self) {
+        let (store, it) = other.decompose();
+
+        for (symbol_or_lambda, variables) in it {
+            for variable in variables {
+                self.insert_external(symbol_or_lambda, store.as_inner(), variable);
+            }
+        }
+    }
+
+    /// Deals the specializations out over `parts` new [ExternalSpecializations].
+    fn split(self, parts: usize) -> std::vec::Vec<Self> {
+        let mut split: std::vec::Vec<Self> =
+            std::iter::repeat_with(Self::new).take(parts).collect();
+        let (store, it) = self.decompose();
+
+        let variables = it.flat_map(|(symbol_or_lambda, variables)| {
+            variables
+                .into_iter()
+                .map(move |variable| (symbol_or_lambda, variable))
+        });
+
+        for (i, (symbol_or_lambda, variable)) in variables.enumerate() {
+            split[i % parts].insert_external(symbol_or_lambda, store.as_inner(), variable);
+        }
+
+        split
+    }
+
     fn decompose(
         self,
     ) -> (
@@ -1385,20 +1466,161 @@ pub struct Env<'a, 'i> {
     pub abilities: AbilitiesView<'i>,
     pub exposed_by_module: &'i ExposedByModule,
     pub derived_module: &'i SharedDerivedModule,
+    /// Argument layouts extended with a closure environment, shared between the calls to a lambda.
+    pub argument_layouts: SliceInterner<'a, InLayout<'a>>,
+    /// [Some] in the parts of [specialize_all_in_parallel], which must not hand out the same
+    /// symbols and ids.
+    pub thread_ids: Option<ThreadIds>,
+    /// [Some] while [Stmt::try_new] runs, with the constructs that could not be lowered so far.
+    pub problems: Option<std::vec::Vec<MonoProblem>>,
+    /// The region of the innermost located expression that is being lowered.
+    pub region: Region,
+}
+
+/// A construct that [Stmt::try_new] could not lower. [Stmt::new] lowers it to a crash instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MonoProblem {
+    pub kind: MonoProblemKind,
+    /// The region of the innermost located expression around the construct
+    pub region: Region,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MonoProblemKind {
+    /// A type has no layout because it contains an unresolved type variable
+    UnresolvedTypeVar,
+    /// A type has no layout because it is part of a type error
+    Erroneous,
+    /// The layout of a value does not match how the value is used
+    ImproperLayout,
+    /// A function is called through a lambda set without any lambdas in it
+    EmptyLambdaSet,
+    /// A typed hole, `_`, is evaluated
+    TypedHole,
+    /// An ingested file has a type other than `Str` or `List U8`
+    InvalidIngestedFileType,
+}
+
+impl From<LayoutProblem> for MonoProblemKind {
+    fn from(problem: LayoutProblem) -> Self {
+        match problem {
+            LayoutProblem::UnresolvedTypeVar(_) => MonoProblemKind::UnresolvedTypeVar,
+            LayoutProblem::Erroneous => MonoProblemKind::Erroneous,
+        }
+    }
+}
+
+/// The ids that a part of [specialize_all_in_parallel] hands out. Part `index` of `threads`
+/// takes every `threads`th id after the ones the module used before, so the ids of a part do
+/// not collide with those of the others, and only depend on the specializations it makes.
+#[derive(Debug, Clone, Copy)]
+pub struct ThreadIds {
+    stride: u32,
+    ident_id: u32,
+    update_mode_id: u32,
+    call_specialization_id: u32,
+}
+
+impl ThreadIds {
+    fn new(env: &Env<'_, '_>, index: usize, threads: usize) -> Self {
+        let index = index as u32;
+
+        Self {
+            stride: threads as u32,
+            ident_id: env.ident_ids.len() as u32 + index,
+            update_mode_id: env.update_mode_ids.next + index,
+            call_specialization_id: env.call_specialization_counter + index,
+        }
+    }
+
+    fn take(next: &mut u32, stride: u32) -> u32 {
+        let id = *next;
+        *next += stride;
+
+        id
+    }
+
+    /// The threads only took the generated identifiers, this adds them to the [IdentIds] of the
+    /// module, and continues the counters of `env` after the ids that any thread used.
+    fn write_back(threads: impl IntoIterator<Item = Self>, env: &mut Env<'_, '_>) {
+        for ids in threads {
+            // the first id this thread did not take is above all the ids it took
+            let ident_ids_len = (ids.ident_id + 1).saturating_sub(ids.stride) as usize;
+
+            while env.ident_ids.len() < ident_ids_len {
+                env.ident_ids.gen_unique();
+            }
+
+            let update_mode_id = (ids.update_mode_id + 1).saturating_sub(ids.stride);
+            env.update_mode_ids.next = env.update_mode_ids.next.max(update_mode_id);
+
+            let call_specialization_id =
+                (ids.call_specialization_id + 1).saturating_sub(ids.stride);
+            env.call_specialization_counter =
+                env.call_specialization_counter.max(call_specialization_id);
+        }
+    }
 }
 
 impl<'a, 'i> Env<'a, 'i> {
     pub fn unique_symbol(&mut self) -> Symbol {
-        let ident_id = self.ident_ids.gen_unique();
+        let ident_id = match &mut self.thread_ids {
+            Some(ids) => {
+                let index = ThreadIds::take(&mut ids.ident_id, ids.stride);
+
+                // Safety: the id is added to the IdentIds of the module by ThreadIds::write_back
+                unsafe { IdentId::from_index(index) }
+            }
+            None => self.ident_ids.gen_unique(),
+        };
 
         Symbol::new(self.home, ident_id)
     }
 
+    /// Like [LambdaSet::extend_argument_list_for_named], but every call to a lambda that captures
+    /// gets the same extended slice, rather than allocating a new one.
+    fn extended_argument_layouts(
+        &mut self,
+        lambda_set: LambdaSet<'a>,
+        lambda_name: LambdaName<'a>,
+        argument_layouts: &'a [InLayout<'a>],
+    ) -> &'a [InLayout<'a>] {
+        if lambda_name.no_captures() {
+            return argument_layouts;
+        }
+
+        let mut extended: SmallVec<InLayout<'a>, 8> = argument_layouts.iter().copied().collect();
+        extended.push(lambda_set.full_layout);
+
+        self.argument_layouts.insert(&extended)
+    }
+
+    /// Lowers a located sub-expression with its region as [Env::region], so that the problems
+    /// found in it point at it rather than at the expression around it.
+    fn in_region<T>(&mut self, region: Region, lower: impl FnOnce(&mut Self) -> T) -> T {
+        let outer = std::mem::replace(&mut self.region, region);
+        let result = lower(self);
+        self.region = outer;
+
+        result
+    }
+
     pub fn next_update_mode_id(&mut self) -> UpdateModeId {
-        self.update_mode_ids.next_id()
+        match &mut self.thread_ids {
+            Some(ids) => UpdateModeId {
+                id: ThreadIds::take(&mut ids.update_mode_id, ids.stride),
+            },
+            None => self.update_mode_ids.next_id(),
+        }
     }
 
     pub fn next_call_specialization_id(&mut self) -> CallSpecId {
+        if let Some(ids) = &mut self.thread_ids {
+            return CallSpecId {
+                id: ThreadIds::take(&mut ids.call_specialization_id, ids.stride),
+            };
+        }
+
         let id = CallSpecId {
             id: self.call_specialization_counter,
         };
@@ -1498,6 +1720,10 @@ pub fn cond<'a>(
     fail: Stmt<'a>,
     ret_layout: InLayout<'a>,
 ) -> Stmt<'a> {
+    let branch_hints = BranchHint::for_branches(
+        env.arena,
+        [BranchHint::from_stmt(&pass), BranchHint::from_stmt(&fail)],
+    );
     let branches = env.arena.alloc([(1u64, BranchInfo::None, pass)]);
     let default_branch = (BranchInfo::None, &*env.arena.alloc(fail));
 
@@ -1507,6 +1733,7 @@ pub fn cond<'a>(
         ret_layout,
         branches,
         default_branch,
+        branch_hints,
     }
 }
 
@@ -1527,6 +1754,9 @@ pub enum Stmt<'a> {
         branches: &'a [(u64, BranchInfo<'a>, Stmt<'a>)],
         /// If no other branches pass, this default branch will be taken.
         default_branch: (BranchInfo<'a>, &'a Stmt<'a>),
+        /// How likely each of the branches is, followed by the default branch.
+        /// Empty when nothing is known, see [BranchHint::of].
+        branch_hints: &'a [BranchHint],
         /// Each branch must return a value of this type.
         ret_layout: InLayout<'a>,
     },
@@ -1603,6 +1833,61 @@ pub enum BranchInfo<'a> {
     },
 }
 
+/// How likely it is that a branch of a [Stmt::Switch] is taken, from most to least likely.
+/// Backends use this to keep the hot path contiguous, e.g. LLVM gets them as branch weights.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BranchHint {
+    Likely,
+    None,
+    Unlikely,
+    /// The branch is not expected to ever be taken, e.g. because it crashes
+    Cold,
+}
+
+impl BranchHint {
+    /// The hint for branch `index` of a switch, where the default branch comes after all other branches.
+    pub fn of(branch_hints: &[BranchHint], index: usize) -> BranchHint {
+        branch_hints.get(index).copied().unwrap_or(BranchHint::None)
+    }
+
+    /// A branch that can only end in a crash is cold.
+    pub fn from_stmt(stmt: &Stmt) -> BranchHint {
+        let mut stmt = stmt;
+
+        loop {
+            match stmt {
+                Stmt::Let(_, _, _, cont)
+                | Stmt::Refcounting(_, cont)
+                | Stmt::Expect {
+                    remainder: cont, ..
+                }
+                | Stmt::ExpectFx {
+                    remainder: cont, ..
+                }
+                | Stmt::Dbg {
+                    remainder: cont, ..
+                } => stmt = cont,
+                Stmt::Crash(..) => return BranchHint::Cold,
+                _ => return BranchHint::None,
+            }
+        }
+    }
+
+    /// Hints for the branches of a switch, or no hints at all if nothing is known about them.
+    pub fn for_branches<'a>(
+        arena: &'a Bump,
+        hints: impl IntoIterator<Item = BranchHint>,
+    ) -> &'a [BranchHint] {
+        let hints = Vec::from_iter_in(hints, arena);
+
+        if hints.iter().all(|hint| *hint == BranchHint::None) {
+            &[]
+        } else {
+            hints.into_bump_slice()
+        }
+    }
+}
+
 impl<'a> BranchInfo<'a> {
     pub fn to_doc<'b, D, A>(&'b self, alloc: &'b D, _pretty: bool) -> DocBuilder<'b, D, A>
     where
@@ -1725,7 +2010,7 @@ impl<'a> Call<'a> {
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct CallSpecId {
-    id: u32,
+    pub(crate) id: u32,
 }
 
 impl CallSpecId {
@@ -1740,7 +2025,7 @@ impl CallSpecId {
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct UpdateModeId {
-    id: u32,
+    pub(crate) id: u32,
 }
 
 impl UpdateModeId {
@@ -1857,6 +2142,12 @@ pub enum Expr<'a> {
         elem_layout: InLayout<'a>,
         elems: &'a [ListLiteralElement<'a>],
     },
+    /// A list literal of number literals. It is never updated in-place, so a backend can put it
+    /// in read-only static data instead of allocating and reference counting it.
+    StaticArray {
+        elem_layout: InLayout<'a>,
+        elems: &'a [Literal<'a>],
+    },
     EmptyArray,
 
     ExprBox {
@@ -2041,6 +2332,14 @@ impl<'a> Expr<'a> {
                     .append(alloc.intersperse(it, ", "))
                     .append(alloc.text("]"))
             }
+            StaticArray { elems, .. } => {
+                let it = elems.iter().map(|l| l.to_doc(alloc));
+
+                alloc
+                    .text("StaticArray [")
+                    .append(alloc.intersperse(it, ", "))
+                    .append(alloc.text("]"))
+            }
             EmptyArray => alloc.text("Array []"),
 
             StructAtIndex {
@@ -2097,6 +2396,31 @@ impl<'a> Stmt<'a> {
         from_can(env, var, can_expr, procs, layout_cache)
     }
 
+    /// Like [Stmt::new], but fails with the constructs that could not be lowered, instead of
+    /// lowering them to a crash. Errors of the program itself, like a `crash` or a problem that
+    /// canonicalization already reported, are still lowered to a crash.
+    pub fn try_new(
+        env: &mut Env<'a, '_>,
+        loc_expr: Loc<roc_can::expr::Expr>,
+        var: Variable,
+        procs: &mut Procs<'a>,
+        layout_cache: &mut LayoutCache<'a>,
+    ) -> Result<Self, std::vec::Vec<MonoProblem>> {
+        let outer_problems = env.problems.replace(std::vec::Vec::new());
+
+        let stmt = env.in_region(loc_expr.region, |env| {
+            from_can(env, var, loc_expr.value, procs, layout_cache)
+        });
+
+        let problems = std::mem::replace(&mut env.problems, outer_problems).unwrap_or_default();
+
+        if problems.is_empty() {
+            Ok(stmt)
+        } else {
+            Err(problems)
+        }
+    }
+
     pub fn to_doc<'b, D, A, I>(
         &'b self,
         alloc: &'b D,
@@ -2287,6 +2611,13 @@ impl<'a> Stmt<'a> {
             layout: Layout::BOOL,
             tag_id: 1,
         };
+        let branch_hints = BranchHint::for_branches(
+            arena,
+            [
+                BranchHint::from_stmt(&then_branch_stmt),
+                BranchHint::from_stmt(else_branch_stmt),
+            ],
+        );
         let then_branch = (1u64, then_branch_info, then_branch_stmt);
 
         let else_branch_info = BranchInfo::Constructor {
@@ -2301,6 +2632,7 @@ impl<'a> Stmt<'a> {
             cond_layout: Layout::BOOL,
             branches: &*arena.alloc([then_branch]),
             default_branch: else_branch,
+            branch_hints,
             ret_layout: return_layout,
         }
     }
@@ -2603,15 +2935,20 @@ fn from_can_let<'a>(
             _ => {
                 let rest = lower_rest!(variable, cont.value);
 
-                with_hole(
-                    env,
-                    def.loc_expr.value,
-                    def.expr_var,
-                    procs,
-                    layout_cache,
-                    *symbol,
-                    env.arena.alloc(rest),
-                )
+                let expr_var = def.expr_var;
+                let Loc { region, value } = def.loc_expr;
+
+                env.in_region(region, |env| {
+                    with_hole(
+                        env,
+                        value,
+                        expr_var,
+                        procs,
+                        layout_cache,
+                        *symbol,
+                        env.arena.alloc(rest),
+                    )
+                })
             }
         };
     }
@@ -2641,15 +2978,20 @@ fn from_can_let<'a>(
             stmt = store_pattern(env, procs, layout_cache, &mono_pattern, outer_symbol, stmt);
 
             // convert the def body, store in outer_symbol
-            with_hole(
-                env,
-                def.loc_expr.value,
-                def.expr_var,
-                procs,
-                layout_cache,
-                outer_symbol,
-                env.arena.alloc(stmt),
-            )
+            let expr_var = def.expr_var;
+            let Loc { region, value } = def.loc_expr;
+
+            env.in_region(region, |env| {
+                with_hole(
+                    env,
+                    value,
+                    expr_var,
+                    procs,
+                    layout_cache,
+                    outer_symbol,
+                    env.arena.alloc(stmt),
+                )
+            })
         }
     }
 }
@@ -2895,7 +3237,352 @@ pub fn specialize_all<'a>(
     externals_others_need: std::vec::Vec<ExternalSpecializations<'a>>,
     specializations_for_host: HostSpecializations<'a>,
     layout_cache: &mut LayoutCache<'a>,
-) -> Procs<'a> {
+    cancel: &CancelToken,
+) -> Result<Procs<'a>, Cancelled> {
+    specialize_found(env, &mut procs, layout_cache);
+
+    // Specialize all the symbols everyone else needs.
+    for externals in externals_others_need {
+        cancel.check()?;
+
+        specialize_external_specializations(env, &mut procs, layout_cache, externals);
+    }
+
+    specialize_host_and_pending(env, procs, specializations_for_host, layout_cache, cancel)
+}
+
+/// Below this many specializations for other modules, copying the module for other threads costs
+/// more than it saves
+const MIN_PARALLEL_SPECIALIZATIONS: usize = 64;
+
+/// Lets idle threads help [specialize_all_in_parallel] with the specializations of a module.
+pub struct SpecializationHelpers<'r, 'a> {
+    /// How many threads are idle. A module is split into one part more than this, for the
+    /// thread that makes its specializations.
+    pub idle: usize,
+    pub world_abilities: &'r WorldAbilities,
+    pub exposed_by_module: &'r Arc<ExposedByModule>,
+    /// Gets this many idle threads to call [SpecializationParts::help]
+    pub ask: &'r dyn Fn(Arc<SpecializationParts<'a>>, usize),
+}
+
+/// Like [specialize_all], but the specializations that other modules need, which is where large
+/// modules spend most of their time, are split into parts that idle threads help to make.
+///
+/// Each part is made with a copy of the [Subs] of the module, by whichever thread takes it, in
+/// the arena of that thread. The current thread takes parts too, until none are left, and then
+/// waits for the parts that other threads took. The procs of all parts are merged into `procs` in
+/// the order of the parts, so when two parts make the same specialization, the one of the first
+/// part is kept. Host-exposed procs get their glue procs as they are merged, because those need
+/// named identifiers.
+///
+/// Modules with `expect`s or `dbg`s are specialized on the current thread, because the types they
+/// look up must all be stored in the one `expectation_subs`.
+///
+/// The memory and layout caches of the parts are added to `thread_stats`.
+pub fn specialize_all_in_parallel<'a>(
+    env: &mut Env<'a, '_>,
+    mut procs: Procs<'a>,
+    externals_others_need: std::vec::Vec<ExternalSpecializations<'a>>,
+    specializations_for_host: HostSpecializations<'a>,
+    layout_cache: &mut LayoutCache<'a>,
+    helpers: &SpecializationHelpers<'_, 'a>,
+    thread_stats: &mut MonoStats,
+    cancel: &CancelToken,
+) -> Result<Procs<'a>, Cancelled> {
+    let specializations: usize = externals_others_need.iter().map(|e| e.len()).sum();
+
+    if helpers.idle == 0
+        || specializations < MIN_PARALLEL_SPECIALIZATIONS
+        || env.expectation_subs.is_some()
+    {
+        return specialize_all(
+            env,
+            procs,
+            externals_others_need,
+            specializations_for_host,
+            layout_cache,
+            cancel,
+        );
+    }
+
+    specialize_found(env, &mut procs, layout_cache);
+
+    let threads = helpers.idle + 1;
+
+    // every part gets a share of what each module needs
+    let mut shares: std::vec::Vec<std::vec::Vec<_>> = std::iter::repeat_with(std::vec::Vec::new)
+        .take(threads)
+        .collect();
+
+    for externals in externals_others_need {
+        for (share, split) in shares.iter_mut().zip(externals.split(threads)) {
+            share.push(split);
+        }
+    }
+
+    // The parts only need to read the subs of the module, and each part copies them when it
+    // starts. That way the copies are made by the threads that take the parts, at the same time,
+    // instead of one after another on this thread before any part can start.
+    let subs = Arc::new(std::mem::take(env.subs));
+    let (made_tx, made_rx) = mpsc::channel();
+
+    let parts = shares
+        .into_iter()
+        .enumerate()
+        .map(|(index, externals)| {
+            let part = SpecializationPart {
+                index,
+                home: env.home,
+                target_info: env.target_info,
+                subs: Arc::clone(&subs),
+                ident_ids: env.ident_ids.clone(),
+                world_abilities: helpers.world_abilities.clone_ref(),
+                exposed_by_module: Arc::clone(helpers.exposed_by_module),
+                derived_module: Arc::clone(env.derived_module),
+                ids: ThreadIds::new(env, index, threads),
+                layout_cache: LayoutCache::new(
+                    layout_cache.interner.fork(),
+                    layout_cache.target_info,
+                ),
+                partial_procs: procs.partial_procs.to_vec(),
+                module_thunks: procs.module_thunks,
+                imported_module_thunks: procs.imported_module_thunks,
+                host_exposed_symbols: procs.host_exposed_symbols,
+                runtime_errors: procs.runtime_errors.clone(),
+                externals,
+            };
+
+            (part, made_tx.clone())
+        })
+        .collect();
+
+    // only the parts hold a sender, so receiving ends when every part was made or dropped
+    drop(made_tx);
+
+    let parts = Arc::new(SpecializationParts {
+        parts: Mutex::new(parts),
+    });
+
+    (helpers.ask)(Arc::clone(&parts), helpers.idle);
+
+    while parts.help(env.arena, cancel) {}
+
+    let mut made_in_parts: std::vec::Vec<_> = made_rx.iter().collect();
+
+    if made_in_parts.len() < threads {
+        internal_error!("a thread making specializations of {:?} panicked", env.home);
+    }
+
+    *env.subs = Arc::try_unwrap(subs)
+        .unwrap_or_else(|_| internal_error!("all parts are made, but their subs are still shared"));
+
+    made_in_parts.sort_by_key(|(index, _)| *index);
+
+    let made_in_parts = made_in_parts
+        .into_iter()
+        .map(|(_, made)| made)
+        .collect::<Result<std::vec::Vec<_>, _>>()?;
+
+    ThreadIds::write_back(made_in_parts.iter().map(|made| made.ids), env);
+
+    for made in made_in_parts {
+        let MadeInPart {
+            specialized,
+            partial_procs,
+            externals_we_need,
+            ids: _,
+            stats,
+        } = made;
+
+        thread_stats.merge(stats);
+
+        // the closures that a part found
+        for (symbol, partial_proc) in partial_procs {
+            if !procs.partial_procs.contains_key(symbol) {
+                procs.partial_procs.insert(symbol, partial_proc);
+            }
+        }
+
+        for (symbol, layout, mut proc) in specialized {
+            if !procs.specialized.is_specialized(symbol, &layout) {
+                if procs.host_exposed_symbols.contains(&symbol) {
+                    expose_to_host(env, &mut procs, layout_cache, &mut proc, layout);
+                }
+
+                procs.specialized.insert_specialized(symbol, layout, proc);
+            }
+        }
+
+        for (module_id, externals) in externals_we_need {
+            procs
+                .externals_we_need
+                .entry(module_id)
+                .or_default()
+                .extend(externals);
+        }
+    }
+
+    specialize_host_and_pending(env, procs, specializations_for_host, layout_cache, cancel)
+}
+
+type MadeSender<'a> = mpsc::Sender<(usize, Result<MadeInPart<'a>, Cancelled>)>;
+
+/// The parts of a module that [specialize_all_in_parallel] split off, and that no thread has
+/// taken yet
+pub struct SpecializationParts<'a> {
+    parts: Mutex<std::vec::Vec<(SpecializationPart<'a>, MadeSender<'a>)>>,
+}
+
+impl<'a> SpecializationParts<'a> {
+    /// Makes a part that no thread has taken yet, in `arena`. Returns `false` when there was
+    /// none left.
+    pub fn help(&self, arena: &'a Bump, cancel: &CancelToken) -> bool {
+        let next = self.parts.lock().pop();
+
+        match next {
+            Some((part, made_tx)) => {
+                let index = part.index;
+                let made = part.run(arena, cancel);
+
+                // the module stops waiting for its parts when its own thread panicked
+                let _ = made_tx.send((index, made));
+
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl std::fmt::Debug for SpecializationParts<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SpecializationParts")
+            .field("left", &self.parts.lock().len())
+            .finish()
+    }
+}
+
+/// A share of the specializations of a module, and everything needed to make it on any thread.
+/// Everything it changes is its own, and it takes its symbols and ids from its [ThreadIds].
+struct SpecializationPart<'a> {
+    index: usize,
+    home: ModuleId,
+    target_info: TargetInfo,
+    subs: Arc<Subs>,
+    ident_ids: IdentIds,
+    world_abilities: WorldAbilities,
+    exposed_by_module: Arc<ExposedByModule>,
+    derived_module: SharedDerivedModule,
+    ids: ThreadIds,
+    layout_cache: LayoutCache<'a>,
+    partial_procs: std::vec::Vec<(Symbol, PartialProc<'a>)>,
+    module_thunks: &'a [Symbol],
+    imported_module_thunks: &'a [Symbol],
+    host_exposed_symbols: &'a [Symbol],
+    runtime_errors: BumpMap<Symbol, &'a str>,
+    externals: std::vec::Vec<ExternalSpecializations<'a>>,
+}
+
+/// What a part of [specialize_all_in_parallel] made
+struct MadeInPart<'a> {
+    specialized: std::vec::Vec<(Symbol, ProcLayout<'a>, Proc<'a>)>,
+    partial_procs: std::vec::Vec<(Symbol, PartialProc<'a>)>,
+    externals_we_need: BumpMap<ModuleId, ExternalSpecializations<'a>>,
+    ids: ThreadIds,
+    stats: MonoStats,
+}
+
+impl<'a> SpecializationPart<'a> {
+    fn run(self, arena: &'a Bump, cancel: &CancelToken) -> Result<MadeInPart<'a>, Cancelled> {
+        let Self {
+            index: _,
+            home,
+            target_info,
+            subs: shared_subs,
+            mut ident_ids,
+            world_abilities,
+            exposed_by_module,
+            derived_module,
+            ids,
+            mut layout_cache,
+            partial_procs,
+            module_thunks,
+            imported_module_thunks,
+            host_exposed_symbols,
+            runtime_errors,
+            externals,
+        } = self;
+
+        let arena_bytes_before = arena.allocated_bytes();
+        let mut stats = MonoStats::default();
+
+        let copy_start = Instant::now();
+        let mut subs = Subs::clone(&shared_subs);
+        drop(shared_subs);
+        stats.add_subs_copy(copy_start.elapsed());
+
+        // the ids are taken from the thread ids instead
+        let mut update_mode_ids = UpdateModeIds::new();
+
+        let mut env = Env {
+            arena,
+            subs: &mut subs,
+            expectation_subs: None,
+            home,
+            ident_ids: &mut ident_ids,
+            target_info,
+            update_mode_ids: &mut update_mode_ids,
+            call_specialization_counter: 1,
+            abilities: AbilitiesView::World(&world_abilities),
+            exposed_by_module: &exposed_by_module,
+            derived_module: &derived_module,
+            argument_layouts: SliceInterner::new_in(arena),
+            thread_ids: Some(ids),
+            problems: None,
+            region: Region::zero(),
+        };
+
+        let mut procs = Procs::new_in(arena);
+
+        procs.pending_specializations = PendingSpecializations::Making(Suspended::new_in(arena));
+
+        for (symbol, partial_proc) in partial_procs {
+            procs.partial_procs.insert(symbol, partial_proc);
+        }
+
+        procs.module_thunks = module_thunks;
+        procs.imported_module_thunks = imported_module_thunks;
+        procs.host_exposed_symbols = host_exposed_symbols;
+        procs.runtime_errors = runtime_errors;
+
+        for externals in externals {
+            cancel.check()?;
+
+            specialize_external_specializations(&mut env, &mut procs, &mut layout_cache, externals);
+        }
+
+        specialize_pending(&mut env, &mut procs, &mut layout_cache, cancel)?;
+
+        stats.add_arena(arena, arena_bytes_before);
+        stats.add_layout_cache(&layout_cache);
+
+        Ok(MadeInPart {
+            specialized: procs.specialized.into_iter_assert_done().collect(),
+            partial_procs: procs.partial_procs.drain().collect(),
+            externals_we_need: procs.externals_we_need,
+            ids: env.thread_ids.expect("a part has thread ids"),
+            stats,
+        })
+    }
+}
+
+/// Makes the specializations that were found before making specializations started.
+fn specialize_found<'a>(
+    env: &mut Env<'a, '_>,
+    procs: &mut Procs<'a>,
+    layout_cache: &mut LayoutCache<'a>,
+) {
     // When calling from_can, pending_specializations should be unavailable.
     // This must be a single pass, and we must not add any more entries to it!
     let pending_specializations = std::mem::replace(
@@ -2906,7 +3593,7 @@ pub fn specialize_all<'a>(
     // Add all of our existing pending specializations.
     match pending_specializations {
         PendingSpecializations::Finding(suspended) => {
-            specialize_suspended(env, &mut procs, layout_cache, suspended)
+            specialize_suspended(env, procs, layout_cache, suspended)
         }
         PendingSpecializations::Making(suspended) => {
             debug_assert!(
@@ -2915,25 +3602,47 @@ pub fn specialize_all<'a>(
             );
         }
     }
+}
 
-    // Specialize all the symbols everyone else needs.
-    for externals in externals_others_need {
-        specialize_external_specializations(env, &mut procs, layout_cache, externals);
-    }
-
+fn specialize_host_and_pending<'a>(
+    env: &mut Env<'a, '_>,
+    mut procs: Procs<'a>,
+    specializations_for_host: HostSpecializations<'a>,
+    layout_cache: &mut LayoutCache<'a>,
+    cancel: &CancelToken,
+) -> Result<Procs<'a>, Cancelled> {
     // Specialize any symbols the host needs.
     specialize_host_specializations(env, &mut procs, layout_cache, specializations_for_host);
 
+    specialize_pending(env, &mut procs, layout_cache, cancel)?;
+
+    debug_assert!(
+        procs.symbol_specializations.is_empty(),
+        "{:?}",
+        &procs.symbol_specializations
+    );
+
+    Ok(procs)
+}
+
+fn specialize_pending<'a>(
+    env: &mut Env<'a, '_>,
+    procs: &mut Procs<'a>,
+    layout_cache: &mut LayoutCache<'a>,
+    cancel: &CancelToken,
+) -> Result<(), Cancelled> {
     // Now, we must go through and continuously complete any new suspended specializations that were
     // discovered in specializing the other demanded symbols.
     while !procs.pending_specializations.is_empty() {
+        cancel.check()?;
+
         let pending_specializations = std::mem::replace(
             &mut procs.pending_specializations,
             PendingSpecializations::Making(Suspended::new_in(env.arena)),
         );
         match pending_specializations {
             PendingSpecializations::Making(suspended) => {
-                specialize_suspended(env, &mut procs, layout_cache, suspended);
+                specialize_suspended(env, procs, layout_cache, suspended);
             }
             PendingSpecializations::Finding(_) => {
                 internal_error!("should not have this variant after making specializations")
@@ -2941,13 +3650,7 @@ pub fn specialize_all<'a>(
         }
     }
 
-    debug_assert!(
-        procs.symbol_specializations.is_empty(),
-        "{:?}",
-        &procs.symbol_specializations
-    );
-
-    procs
+    Ok(())
 }
 
 fn specialize_host_specializations<'a>(
@@ -3016,111 +3719,125 @@ fn specialize_external_help<'a>(
                 debug_assert!(top_level.arguments.is_empty());
             }
 
-            if procs.host_exposed_symbols.contains(&proc.name.name()) {
-                // layouts that are (transitively) used in the type of `mainForHost`.
-                let mut host_exposed_layouts: Vec<_> = top_level
-                    .arguments
-                    .iter()
-                    .copied()
-                    .chain([top_level.result])
-                    .collect_in(env.arena);
-
-                // it is very likely we see the same types across functions, or in multiple arguments
-                host_exposed_layouts.sort();
-                host_exposed_layouts.dedup();
+            // the glue procs get named identifiers, which a part of [specialize_all_in_parallel]
+            // can not add. The module exposes the procs of its parts when it merges them.
+            if env.thread_ids.is_none() && procs.host_exposed_symbols.contains(&proc.name.name()) {
+                expose_to_host(env, procs, layout_cache, &mut proc, top_level);
+            }
 
-                for in_layout in host_exposed_layouts {
-                    let layout = layout_cache.interner.get(in_layout);
+            procs
+                .specialized
+                .insert_specialized(name.name(), top_level, proc);
+        }
+        Err(SpecializeFailure { attempted_layout }) => {
+            let proc = generate_runtime_error_function(env, name, attempted_layout);
 
-                    let all_glue_procs = generate_glue_procs(
-                        env.home,
-                        env.ident_ids,
-                        env.arena,
-                        &mut layout_cache.interner,
-                        env.arena.alloc(layout),
-                    );
+            let top_level = ProcLayout::from_raw_named(env.arena, name, attempted_layout);
 
-                    // for now, getters are not processed here
-                    let GlueProcs {
-                        getters,
-                        extern_names,
-                    } = all_glue_procs;
-
-                    for (_layout, glue_procs) in getters {
-                        for glue_proc in glue_procs {
-                            procs.specialized.insert_specialized(
-                                glue_proc.proc.name.name(),
-                                glue_proc.proc_layout,
-                                glue_proc.proc,
-                            );
-                        }
-                    }
+            procs
+                .specialized
+                .insert_specialized(name.name(), top_level, proc);
+        }
+    }
+}
 
-                    let mut aliases = BumpMap::default();
+/// Adds the glue procs for the layouts in the type of a host-exposed `proc`, and the procs of the
+/// functions that the host gets back from it.
+fn expose_to_host<'a>(
+    env: &mut Env<'a, '_>,
+    procs: &mut Procs<'a>,
+    layout_cache: &mut LayoutCache<'a>,
+    proc: &mut Proc<'a>,
+    top_level: ProcLayout<'a>,
+) {
+    // layouts that are (transitively) used in the type of `mainForHost`.
+    let mut host_exposed_layouts: Vec<_> = top_level
+        .arguments
+        .iter()
+        .copied()
+        .chain([top_level.result])
+        .collect_in(env.arena);
 
-                    for (id, mut raw_function_layout) in extern_names {
-                        let symbol = env.unique_symbol();
-                        let lambda_name = LambdaName::no_niche(symbol);
+    // it is very likely we see the same types across functions, or in multiple arguments
+    host_exposed_layouts.sort();
+    host_exposed_layouts.dedup();
 
-                        // fix the recursion in the rocLovesRust example
-                        if false {
-                            raw_function_layout = match raw_function_layout {
-                                RawFunctionLayout::Function(a, mut lambda_set, _) => {
-                                    lambda_set.ret = in_layout;
-                                    RawFunctionLayout::Function(a, lambda_set, in_layout)
-                                }
-                                RawFunctionLayout::ZeroArgumentThunk(x) => {
-                                    RawFunctionLayout::ZeroArgumentThunk(x)
-                                }
-                            };
-                        }
+    for in_layout in host_exposed_layouts {
+        let layout = layout_cache.interner.get(in_layout);
 
-                        let (key, (top_level, proc)) = generate_host_exposed_function(
-                            env,
-                            procs,
-                            layout_cache,
-                            lambda_name,
-                            raw_function_layout,
-                        );
+        let all_glue_procs = generate_glue_procs(
+            env.home,
+            env.ident_ids,
+            env.arena,
+            &mut layout_cache.interner,
+            env.arena.alloc(layout),
+        );
 
-                        procs
-                            .specialized
-                            .insert_specialized(symbol, top_level, proc);
+        // for now, getters are not processed here
+        let GlueProcs {
+            getters,
+            extern_names,
+        } = all_glue_procs;
+
+        for (_layout, glue_procs) in getters {
+            for glue_proc in glue_procs {
+                procs.specialized.insert_specialized(
+                    glue_proc.proc.name.name(),
+                    glue_proc.proc_layout,
+                    glue_proc.proc,
+                );
+            }
+        }
 
-                        let hels = HostExposedLambdaSet {
-                            id,
-                            symbol,
-                            proc_layout: top_level,
-                            raw_function_layout,
-                        };
+        let mut aliases = BumpMap::default();
 
-                        aliases.insert(key, hels);
+        for (id, mut raw_function_layout) in extern_names {
+            let symbol = env.unique_symbol();
+            let lambda_name = LambdaName::no_niche(symbol);
+
+            // fix the recursion in the rocLovesRust example
+            if false {
+                raw_function_layout = match raw_function_layout {
+                    RawFunctionLayout::Function(a, mut lambda_set, _) => {
+                        lambda_set.ret = in_layout;
+                        RawFunctionLayout::Function(a, lambda_set, in_layout)
                     }
-
-                    match &mut proc.host_exposed_layouts {
-                        HostExposedLayouts::HostExposed { aliases: old, .. } => old.extend(aliases),
-                        hep @ HostExposedLayouts::NotHostExposed => {
-                            *hep = HostExposedLayouts::HostExposed {
-                                aliases,
-                                rigids: Default::default(),
-                            };
-                        }
+                    RawFunctionLayout::ZeroArgumentThunk(x) => {
+                        RawFunctionLayout::ZeroArgumentThunk(x)
                     }
-                }
+                };
             }
 
+            let (key, (top_level, proc)) = generate_host_exposed_function(
+                env,
+                procs,
+                layout_cache,
+                lambda_name,
+                raw_function_layout,
+            );
+
             procs
                 .specialized
-                .insert_specialized(name.name(), top_level, proc);
-        }
-        Err(SpecializeFailure { attempted_layout }) => {
-            let proc = generate_runtime_error_function(env, name, attempted_layout);
+                .insert_specialized(symbol, top_level, proc);
 
-            let top_level = ProcLayout::from_raw_named(env.arena, name, attempted_layout);
+            let hels = HostExposedLambdaSet {
+                id,
+                symbol,
+                proc_layout: top_level,
+                raw_function_layout,
+            };
 
-            procs
-                .specialized
-                .insert_specialized(name.name(), top_level, proc);
+            aliases.insert(key, hels);
+        }
+
+        match &mut proc.host_exposed_layouts {
+            HostExposedLayouts::HostExposed { aliases: old, .. } => old.extend(aliases),
+            hep @ HostExposedLayouts::NotHostExposed => {
+                *hep = HostExposedLayouts::HostExposed {
+                    aliases,
+                    rigids: Default::default(),
+                };
+            }
         }
     }
 }
@@ -3334,7 +4051,6 @@ fn generate_host_exposed_lambda_set<'a>(
     };
 
     let top_level = ProcLayout::new(
-        env.arena,
         top_level_arguments.into_bump_slice(),
         Niche::NONE,
         return_layout,
@@ -3916,25 +4632,14 @@ pub struct ProcLayout<'a> {
 
 impl<'a> ProcLayout<'a> {
     pub(crate) fn new(
-        arena: &'a Bump,
         old_arguments: &'a [InLayout<'a>],
         old_niche: Niche<'a>,
         result: InLayout<'a>,
     ) -> Self {
-        let mut arguments = Vec::with_capacity_in(old_arguments.len(), arena);
-
-        for old in old_arguments {
-            let other = old;
-            arguments.push(*other);
-        }
-
-        let other = result;
-        let new_result = other;
-
         ProcLayout {
-            arguments: arguments.into_bump_slice(),
+            arguments: old_arguments,
             niche: old_niche,
-            result: new_result,
+            result,
         }
     }
 
@@ -3947,10 +4652,10 @@ impl<'a> ProcLayout<'a> {
             RawFunctionLayout::Function(arguments, lambda_set, result) => {
                 let arguments =
                     lambda_set.extend_argument_list_for_named(arena, lambda_name, arguments);
-                ProcLayout::new(arena, arguments, lambda_name.niche(), result)
+                ProcLayout::new(arguments, lambda_name.niche(), result)
             }
             RawFunctionLayout::ZeroArgumentThunk(result) => {
-                ProcLayout::new(arena, &[], Niche::NONE, result)
+                ProcLayout::new(&[], Niche::NONE, result)
             }
         }
     }
@@ -4074,6 +4779,33 @@ fn try_make_literal<'a>(
     }
 }
 
+/// A list literal whose elements are all number literals becomes a `StaticArray`.
+fn array_expr<'a>(
+    arena: &'a Bump,
+    elem_layout: InLayout<'a>,
+    elems: &'a [ListLiteralElement<'a>],
+) -> Expr<'a> {
+    if elems.is_empty() {
+        return Expr::Array { elem_layout, elems };
+    }
+
+    let mut literals = Vec::with_capacity_in(elems.len(), arena);
+
+    for elem in elems {
+        match elem {
+            ListLiteralElement::Literal(Literal::Str(_)) | ListLiteralElement::Symbol(_) => {
+                return Expr::Array { elem_layout, elems };
+            }
+            ListLiteralElement::Literal(literal) => literals.push(*literal),
+        }
+    }
+
+    Expr::StaticArray {
+        elem_layout,
+        elems: literals.into_bump_slice(),
+    }
+}
+
 pub fn with_hole<'a>(
     env: &mut Env<'a, '_>,
     can_expr: roc_can::expr::Expr,
@@ -4135,10 +4867,7 @@ pub fn with_hole<'a>(
                     for byte in bytes.iter() {
                         elements.push(ListLiteralElement::Literal(Literal::Byte(*byte)));
                     }
-                    let expr = Expr::Array {
-                        elem_layout,
-                        elems: elements.into_bump_slice(),
-                    };
+                    let expr = array_expr(arena, elem_layout, elements.into_bump_slice());
 
                     Stmt::Let(assigned, expr, interned, hole)
                 }
@@ -4156,7 +4885,11 @@ pub fn with_hole<'a>(
                 _ => {
                     // This will not manifest as a real runtime error and is just returned to have a value here.
                     // The actual type error during solve will be fatal.
-                    runtime_error(env, "Invalid type for ingested file")
+                    lowering_error(
+                        env,
+                        MonoProblemKind::InvalidIngestedFileType,
+                        "Invalid type for ingested file",
+                    )
                 }
             }
         }
@@ -4320,15 +5053,17 @@ pub fn with_hole<'a>(
                     substitute_in_exprs(arena, &mut result, assigned, real_name);
                     result
                 }
-                _ => with_hole(
-                    env,
-                    loc_arg_expr.value,
-                    arg_var,
-                    procs,
-                    layout_cache,
-                    assigned,
-                    hole,
-                ),
+                _ => env.in_region(loc_arg_expr.region, |env| {
+                    with_hole(
+                        env,
+                        loc_arg_expr.value,
+                        arg_var,
+                        procs,
+                        layout_cache,
+                        assigned,
+                        hole,
+                    )
+                }),
             }
         }
 
@@ -4346,7 +5081,13 @@ pub fn with_hole<'a>(
             };
             let sorted_elems = match sorted_elems_result {
                 Ok(elems) => elems,
-                Err(_) => return runtime_error(env, "Can't create tuple with improper layout"),
+                Err(_) => {
+                    return lowering_error(
+                        env,
+                        MonoProblemKind::ImproperLayout,
+                        "Can't create tuple with improper layout",
+                    )
+                }
             };
 
             // Hacky way to let us remove the owned elements from the vector, possibly out-of-order.
@@ -4381,7 +5122,13 @@ pub fn with_hole<'a>(
             };
             let sorted_fields = match sorted_fields_result {
                 Ok(fields) => fields,
-                Err(_) => return runtime_error(env, "Can't create record with improper layout"),
+                Err(_) => {
+                    return lowering_error(
+                        env,
+                        MonoProblemKind::ImproperLayout,
+                        "Can't create record with improper layout",
+                    )
+                }
             };
 
             let take_field_expr =
@@ -4423,41 +5170,47 @@ pub fn with_hole<'a>(
                     if is_terminated {
                         let terminator = hole;
 
-                        let mut stmt = with_hole(
-                            env,
-                            final_else.value,
-                            branch_var,
-                            procs,
-                            layout_cache,
-                            assigned,
-                            terminator,
-                        );
-
-                        for (loc_cond, loc_then) in branches.into_iter().rev() {
-                            let branching_symbol = env.unique_symbol();
-
-                            let then = with_hole(
+                        let mut stmt = env.in_region(final_else.region, |env| {
+                            with_hole(
                                 env,
-                                loc_then.value,
+                                final_else.value,
                                 branch_var,
                                 procs,
                                 layout_cache,
                                 assigned,
                                 terminator,
-                            );
+                            )
+                        });
+
+                        for (loc_cond, loc_then) in branches.into_iter().rev() {
+                            let branching_symbol = env.unique_symbol();
+
+                            let then = env.in_region(loc_then.region, |env| {
+                                with_hole(
+                                    env,
+                                    loc_then.value,
+                                    branch_var,
+                                    procs,
+                                    layout_cache,
+                                    assigned,
+                                    terminator,
+                                )
+                            });
 
                             stmt = cond(env, branching_symbol, cond_layout, then, stmt, ret_layout);
 
                             // add condition
-                            stmt = with_hole(
-                                env,
-                                loc_cond.value,
-                                cond_var,
-                                procs,
-                                layout_cache,
-                                branching_symbol,
-                                env.arena.alloc(stmt),
-                            );
+                            stmt = env.in_region(loc_cond.region, |env| {
+                                with_hole(
+                                    env,
+                                    loc_cond.value,
+                                    cond_var,
+                                    procs,
+                                    layout_cache,
+                                    branching_symbol,
+                                    env.arena.alloc(stmt),
+                                )
+                            });
                         }
                         stmt
                     } else {
@@ -4468,15 +5221,17 @@ pub fn with_hole<'a>(
                             .arena
                             .alloc(Stmt::Jump(id, env.arena.alloc([assigned_in_jump])));
 
-                        let mut stmt = with_hole(
-                            env,
-                            final_else.value,
-                            branch_var,
-                            procs,
-                            layout_cache,
-                            assigned_in_jump,
-                            terminator,
-                        );
+                        let mut stmt = env.in_region(final_else.region, |env| {
+                            with_hole(
+                                env,
+                                final_else.value,
+                                branch_var,
+                                procs,
+                                layout_cache,
+                                assigned_in_jump,
+                                terminator,
+                            )
+                        });
 
                         for (loc_cond, loc_then) in branches.into_iter().rev() {
                             let branching_symbol = possible_reuse_symbol_or_specialize(
@@ -4487,15 +5242,17 @@ pub fn with_hole<'a>(
                                 cond_var,
                             );
 
-                            let then = with_hole(
-                                env,
-                                loc_then.value,
-                                branch_var,
-                                procs,
-                                layout_cache,
-                                assigned_in_jump,
-                                terminator,
-                            );
+                            let then = env.in_region(loc_then.region, |env| {
+                                with_hole(
+                                    env,
+                                    loc_then.value,
+                                    branch_var,
+                                    procs,
+                                    layout_cache,
+                                    assigned_in_jump,
+                                    terminator,
+                                )
+                            });
 
                             stmt = cond(env, branching_symbol, cond_layout, then, stmt, ret_layout);
 
@@ -4531,8 +5288,12 @@ pub fn with_hole<'a>(
                         }
                     }
                 }
-                (Err(_), _) => runtime_error(env, "invalid ret_layout"),
-                (_, Err(_)) => runtime_error(env, "invalid cond_layout"),
+                (Err(_), _) => {
+                    lowering_error(env, MonoProblemKind::ImproperLayout, "invalid ret_layout")
+                }
+                (_, Err(_)) => {
+                    lowering_error(env, MonoProblemKind::ImproperLayout, "invalid cond_layout")
+                }
             }
         }
 
@@ -4655,10 +5416,7 @@ pub fn with_hole<'a>(
             }
             let arg_symbols = arg_symbols.into_bump_slice();
 
-            let expr = Expr::Array {
-                elem_layout,
-                elems: elements.into_bump_slice(),
-            };
+            let expr = array_expr(env.arena, elem_layout, elements.into_bump_slice());
 
             let list_layout = layout_cache.put_in(Layout::Builtin(Builtin::List(elem_layout)));
 
@@ -4691,7 +5449,13 @@ pub fn with_hole<'a>(
             };
             let sorted_fields = match sorted_fields_result {
                 Ok(fields) => fields,
-                Err(_) => return runtime_error(env, "Can't access record with improper layout"),
+                Err(_) => {
+                    return lowering_error(
+                        env,
+                        MonoProblemKind::ImproperLayout,
+                        "Can't access record with improper layout",
+                    )
+                }
             };
 
             let mut index = None;
@@ -4778,8 +5542,9 @@ pub fn with_hole<'a>(
                     }
                 }
 
-                Err(_error) => runtime_error(
+                Err(error) => lowering_error(
                     env,
+                    MonoProblemKind::from(error),
                     "TODO convert anonymous function error to a RuntimeError string",
                 ),
             }
@@ -4803,7 +5568,13 @@ pub fn with_hole<'a>(
             };
             let sorted_elems = match sorted_elems_result {
                 Ok(fields) => fields,
-                Err(_) => return runtime_error(env, "Can't access tuple with improper layout"),
+                Err(_) => {
+                    return lowering_error(
+                        env,
+                        MonoProblemKind::ImproperLayout,
+                        "Can't access tuple with improper layout",
+                    )
+                }
             };
             let mut field_layouts = Vec::with_capacity_in(sorted_elems.len(), env.arena);
 
@@ -4881,8 +5652,9 @@ pub fn with_hole<'a>(
                     }
                 }
 
-                Err(_error) => runtime_error(
+                Err(error) => lowering_error(
                     env,
+                    MonoProblemKind::from(error),
                     "TODO convert anonymous function error to a RuntimeError string",
                 ),
             }
@@ -4917,7 +5689,13 @@ pub fn with_hole<'a>(
 
             let sorted_fields = match sorted_fields_result {
                 Ok(fields) => fields,
-                Err(_) => return runtime_error(env, "Can't update record with improper layout"),
+                Err(_) => {
+                    return lowering_error(
+                        env,
+                        MonoProblemKind::ImproperLayout,
+                        "Can't update record with improper layout",
+                    )
+                }
             };
 
             let mut field_layouts = Vec::with_capacity_in(sorted_fields.len(), env.arena);
@@ -5566,7 +6344,7 @@ pub fn with_hole<'a>(
                 }
             }
         }
-        TypedHole(_) => runtime_error(env, "Hit a blank"),
+        TypedHole(_) => lowering_error(env, MonoProblemKind::TypedHole, "Hit a blank"),
         RuntimeError(e) => runtime_error(env, env.arena.alloc(e.runtime_message())),
         Crash { msg, ret_var: _ } => {
             let msg_sym = possible_reuse_symbol_or_specialize(
@@ -5693,7 +6471,13 @@ fn compile_struct_like<'a, L, UnusedLayout>(
     // creating a record from the var will unpack it if it's just a single field.
     let layout = match layout_cache.from_var(env.arena, struct_like_var, env.subs) {
         Ok(layout) => layout,
-        Err(_) => return runtime_error(env, "Can't create record with improper layout"),
+        Err(_) => {
+            return lowering_error(
+                env,
+                MonoProblemKind::ImproperLayout,
+                "Can't create record with improper layout",
+            )
+        }
     };
 
     let elem_symbols = elem_symbols.into_bump_slice();
@@ -5723,15 +6507,17 @@ fn compile_struct_like<'a, L, UnusedLayout>(
                 );
             }
             Field::Field(var, loc_expr) => {
-                stmt = with_hole(
-                    env,
-                    loc_expr.value,
-                    var,
-                    procs,
-                    layout_cache,
-                    *symbol,
-                    env.arena.alloc(stmt),
-                );
+                stmt = env.in_region(loc_expr.region, |env| {
+                    with_hole(
+                        env,
+                        loc_expr.value,
+                        var,
+                        procs,
+                        layout_cache,
+                        *symbol,
+                        env.arena.alloc(stmt),
+                    )
+                });
             }
         }
     }
@@ -6000,8 +6786,9 @@ fn convert_tag_union<'a>(
     let variant = match res_variant {
         Ok(cached) => cached,
         Err(LayoutProblem::UnresolvedTypeVar(_)) => {
-            return runtime_error(
+            return lowering_error(
                 env,
+                MonoProblemKind::UnresolvedTypeVar,
                 env.arena.alloc(format!(
                     "Unresolved type variable for tag {}",
                     tag_name.0.as_str()
@@ -6009,8 +6796,9 @@ fn convert_tag_union<'a>(
             )
         }
         Err(LayoutProblem::Erroneous) => {
-            return runtime_error(
+            return lowering_error(
                 env,
+                MonoProblemKind::Erroneous,
                 env.arena.alloc(format!(
                     "Tag {} was part of a type error!",
                     tag_name.0.as_str()
@@ -6043,7 +6831,11 @@ fn convert_tag_union<'a>(
                     Layout::U8,
                     hole,
                 ),
-                None => runtime_error(env, "tag must be in its own type"),
+                None => lowering_error(
+                    env,
+                    MonoProblemKind::ImproperLayout,
+                    "tag must be in its own type",
+                ),
             }
         }
 
@@ -6603,7 +7395,9 @@ pub fn from_can<'a>(
                 .from_var(env.arena, cond_var, env.subs)
                 .expect("invalid cond_layout");
 
-            let mut stmt = from_can(env, branch_var, final_else.value, procs, layout_cache);
+            let mut stmt = env.in_region(final_else.region, |env| {
+                from_can(env, branch_var, final_else.value, procs, layout_cache)
+            });
 
             for (loc_cond, loc_then) in branches.into_iter().rev() {
                 let branching_symbol = possible_reuse_symbol_or_specialize(
@@ -6613,7 +7407,9 @@ pub fn from_can<'a>(
                     &loc_cond.value,
                     cond_var,
                 );
-                let then = from_can(env, branch_var, loc_then.value, procs, layout_cache);
+                let then = env.in_region(loc_then.region, |env| {
+                    from_can(env, branch_var, loc_then.value, procs, layout_cache)
+                });
 
                 stmt = cond(env, branching_symbol, cond_layout, then, stmt, ret_layout);
 
@@ -6869,7 +7665,7 @@ fn to_opt_branches<'a>(
     exhaustive_mark: ExhaustiveMark,
     layout_cache: &mut LayoutCache<'a>,
 ) -> std::vec::Vec<(
-    Pattern<'a>,
+    decision_tree::Alternatives<'a>,
     Option<Loc<roc_can::expr::Expr>>,
     roc_can::expr::Expr,
 )> {
@@ -6883,6 +7679,12 @@ fn to_opt_branches<'a>(
             continue;
         }
 
+        if let Some(alternatives) = shared_alternatives(env, procs, layout_cache, &when_branch) {
+            opt_branches.push((alternatives, None, when_branch.value.value));
+
+            continue;
+        }
+
         for loc_pattern in when_branch.patterns {
             match from_can_pattern(env, procs, layout_cache, &loc_pattern.pattern.value) {
                 Ok((mono_pattern, assignments)) => {
@@ -6916,12 +7718,16 @@ fn to_opt_branches<'a>(
                     };
 
                     // TODO remove clone?
-                    opt_branches.push((mono_pattern, when_branch.guard.clone(), loc_expr.value));
+                    opt_branches.push((
+                        std::iter::once(mono_pattern).collect(),
+                        when_branch.guard.clone(),
+                        loc_expr.value,
+                    ));
                 }
                 Err(runtime_error) => {
                     // TODO remove clone?
                     opt_branches.push((
-                        Pattern::Underscore,
+                        std::iter::once(Pattern::Underscore).collect(),
                         when_branch.guard.clone(),
                         roc_can::expr::Expr::RuntimeError(runtime_error),
                     ));
@@ -6934,7 +7740,7 @@ fn to_opt_branches<'a>(
         // In contrast to elm (currently), we still do codegen even if a pattern is non-exhaustive.
         // So we not only report exhaustiveness errors, but also correct them
         opt_branches.push((
-            Pattern::Underscore,
+            std::iter::once(Pattern::Underscore).collect(),
             None,
             roc_can::expr::Expr::RuntimeError(roc_problem::can::RuntimeError::NonExhaustivePattern),
         ));
@@ -6943,6 +7749,44 @@ fn to_opt_branches<'a>(
     opt_branches
 }
 
+/// Alternatives like `1 | 2 | 3 -> ...` can share a single copy of the branch body if they
+/// bind nothing, since then it does not matter which of them matched.
+fn shared_alternatives<'a>(
+    env: &mut Env<'a, '_>,
+    procs: &mut Procs<'a>,
+    layout_cache: &mut LayoutCache<'a>,
+    when_branch: &roc_can::expr::WhenBranch,
+) -> Option<decision_tree::Alternatives<'a>> {
+    if when_branch.patterns.len() < 2 || when_branch.guard.is_some() {
+        return None;
+    }
+
+    // check the canonical patterns first, so that no alternative is lowered for nothing
+    let patterns = || when_branch.patterns.iter();
+    if patterns().any(|loc_pattern| loc_pattern.degenerate)
+        || BindingsFromPattern::new_many(patterns().map(|loc_pattern| &loc_pattern.pattern))
+            .next()
+            .is_some()
+    {
+        return None;
+    }
+
+    let mut alternatives = decision_tree::Alternatives::new();
+
+    for loc_pattern in patterns() {
+        match from_can_pattern(env, procs, layout_cache, &loc_pattern.pattern.value) {
+            Ok((mono_pattern, assignments)) => {
+                debug_assert!(assignments.is_empty() && !mono_pattern.binds_symbols());
+
+                alternatives.push(mono_pattern);
+            }
+            Err(_) => return None,
+        }
+    }
+
+    Some(alternatives)
+}
+
 #[allow(clippy::too_many_arguments)]
 fn from_can_when<'a>(
     env: &mut Env<'a, '_>,
@@ -6977,13 +7821,17 @@ fn from_can_when<'a>(
     let arena = env.arena;
     let it = opt_branches
         .into_iter()
-        .filter_map(|(pattern, opt_guard, can_expr)| {
+        .filter_map(|(mut patterns, opt_guard, can_expr)| {
             // If the pattern has a void layout we can drop it; however, we must still perform the
             // work of building the body, because that may contain specializations we must
             // discover for use elsewhere. See
             //   `unreachable_branch_is_eliminated_but_produces_lambda_specializations` in test_mono
             // for an example.
-            let should_eliminate_branch = pattern.is_voided();
+            let should_eliminate_branch = patterns.iter().all(|pattern| pattern.is_voided());
+
+            if !should_eliminate_branch {
+                patterns.retain(|pattern| !pattern.is_voided());
+            }
 
             // If we're going to eliminate the branch, we need to take a snapshot of the symbol
             // specializations before we enter the branch, because any new specializations that
@@ -7012,8 +7860,12 @@ fn from_can_when<'a>(
                     identity: env.next_call_specialization_id(),
                 };
 
+                // only alternatives without a guard are shared
+                debug_assert_eq!(patterns.len(), 1);
+                let pattern = patterns[0].clone();
+
                 (
-                    pattern.clone(),
+                    patterns,
                     Guard::Guard {
                         pattern,
                         stmt_spec: guard_spec,
@@ -7021,7 +7873,7 @@ fn from_can_when<'a>(
                     branch_stmt,
                 )
             } else {
-                (pattern, Guard::NoGuard, branch_stmt)
+                (patterns, Guard::NoGuard, branch_stmt)
             };
 
             if should_eliminate_branch {
@@ -7205,6 +8057,7 @@ fn substitute_in_stmt_help<'a>(
             cond_layout,
             branches,
             default_branch,
+            branch_hints,
             ret_layout,
         } => {
             let mut did_change = false;
@@ -7259,6 +8112,7 @@ fn substitute_in_stmt_help<'a>(
                     cond_layout: *cond_layout,
                     default_branch,
                     branches,
+                    branch_hints,
                     ret_layout: *ret_layout,
                 }))
             } else {
@@ -7432,7 +8286,7 @@ fn substitute_in_expr<'a>(
     use Expr::*;
 
     match expr {
-        Literal(_) | EmptyArray | RuntimeErrorFunction(_) => None,
+        Literal(_) | StaticArray { .. } | EmptyArray | RuntimeErrorFunction(_) => None,
 
         Call(call) => substitute_in_call(arena, call, subs).map(Expr::Call),
 
@@ -7900,7 +8754,7 @@ fn specialize_symbol<'a>(
                         // let layout = Layout::Closure(argument_layouts, lambda_set, ret_layout);
                         // panic!("suspicious");
                         let layout = lambda_set.full_layout;
-                        let top_level = ProcLayout::new(env.arena, &[], Niche::NONE, layout);
+                        let top_level = ProcLayout::new(&[], Niche::NONE, layout);
                         procs.insert_passed_by_name(
                             env,
                             arg_var,
@@ -7944,7 +8798,7 @@ fn specialize_symbol<'a>(
                 }
                 RawFunctionLayout::ZeroArgumentThunk(ret_layout) => {
                     // this is a 0-argument thunk
-                    let top_level = ProcLayout::new(env.arena, &[], Niche::NONE, ret_layout);
+                    let top_level = ProcLayout::new(&[], Niche::NONE, ret_layout);
                     procs.insert_passed_by_name(
                         env,
                         arg_var,
@@ -7990,15 +8844,17 @@ fn assign_to_symbol<'a>(
             )
         }
         Value(_symbol) => result,
-        NotASymbol => with_hole(
-            env,
-            loc_arg.value,
-            arg_var,
-            procs,
-            layout_cache,
-            symbol,
-            env.arena.alloc(result),
-        ),
+        NotASymbol => env.in_region(loc_arg.region, |env| {
+            with_hole(
+                env,
+                loc_arg.value,
+                arg_var,
+                procs,
+                layout_cache,
+                symbol,
+                env.arena.alloc(result),
+            )
+        }),
     }
 }
 
@@ -8059,13 +8915,14 @@ fn evaluate_arguments_then_runtime_error<'a>(
     env: &mut Env<'a, '_>,
     procs: &mut Procs<'a>,
     layout_cache: &mut LayoutCache<'a>,
+    problem: MonoProblemKind,
     msg: String,
     loc_args: std::vec::Vec<(Variable, Loc<roc_can::expr::Expr>)>,
 ) -> Stmt<'a> {
     let arena = env.arena;
 
     // eventually we will throw this runtime error
-    let result = runtime_error(env, env.arena.alloc(msg));
+    let result = lowering_error(env, problem, env.arena.alloc(msg));
 
     // but, we also still evaluate and specialize the arguments to give better error messages
     let arg_symbols = Vec::from_iter_in(
@@ -8099,7 +8956,14 @@ fn call_by_name<'a>(
                 var, proc_name, fn_var
             );
 
-            evaluate_arguments_then_runtime_error(env, procs, layout_cache, msg, loc_args)
+            evaluate_arguments_then_runtime_error(
+                env,
+                procs,
+                layout_cache,
+                MonoProblemKind::UnresolvedTypeVar,
+                msg,
+                loc_args,
+            )
         }
         Err(LayoutProblem::Erroneous) => {
             let msg = format!(
@@ -8107,7 +8971,14 @@ fn call_by_name<'a>(
                 proc_name
             );
 
-            evaluate_arguments_then_runtime_error(env, procs, layout_cache, msg, loc_args)
+            evaluate_arguments_then_runtime_error(
+                env,
+                procs,
+                layout_cache,
+                MonoProblemKind::Erroneous,
+                msg,
+                loc_args,
+            )
         }
         Ok(RawFunctionLayout::Function(arg_layouts, lambda_set, ret_layout)) => {
             if procs.is_module_thunk(proc_name) {
@@ -8271,8 +9142,8 @@ fn call_by_name_help<'a>(
     // number of arguments actually passed.
     let top_level_layout = {
         let argument_layouts =
-            lambda_set.extend_argument_list_for_named(env.arena, proc_name, argument_layouts);
-        ProcLayout::new(env.arena, argument_layouts, proc_name.niche(), ret_layout)
+            env.extended_argument_layouts(lambda_set, proc_name, argument_layouts);
+        ProcLayout::new(argument_layouts, proc_name.niche(), ret_layout)
     };
 
     // the variables of the given arguments
@@ -8285,7 +9156,11 @@ fn call_by_name_help<'a>(
             Err(_) => {
                 // One of this function's arguments code gens to a runtime error,
                 // so attempting to call it will immediately crash.
-                return runtime_error(env, "TODO runtime error for invalid layout");
+                return lowering_error(
+                    env,
+                    MonoProblemKind::ImproperLayout,
+                    "TODO runtime error for invalid layout",
+                );
             }
         }
     }
@@ -8526,7 +9401,7 @@ fn call_by_name_module_thunk<'a>(
     assigned: Symbol,
     hole: &'a Stmt<'a>,
 ) -> Stmt<'a> {
-    let top_level_layout = ProcLayout::new(env.arena, &[], Niche::NONE, ret_layout);
+    let top_level_layout = ProcLayout::new(&[], Niche::NONE, ret_layout);
 
     let inner_layout = ret_layout;
 
@@ -8971,6 +9846,7 @@ where
         cond_layout: closure_tag_id_layout,
         branches: branches.into_bump_slice(),
         default_branch,
+        branch_hints: &[],
         ret_layout: return_layout,
     };
 
@@ -8990,7 +9866,7 @@ where
 
 fn empty_lambda_set_error<'a>(env: &mut Env<'a, '_>) -> Stmt<'a> {
     let msg = "a Lambda Set is empty. Most likely there is a type error in your program.";
-    runtime_error(env, msg)
+    lowering_error(env, MonoProblemKind::EmptyLambdaSet, msg)
 }
 
 /// Use the lambda set to figure out how to make a call-by-name
@@ -9209,6 +10085,7 @@ fn union_lambda_set_to_switch<'a>(
         cond_layout: closure_tag_id_layout,
         branches: branches.into_bump_slice(),
         default_branch,
+        branch_hints: &[],
         ret_layout: return_layout,
     };
 
@@ -9280,11 +10157,8 @@ fn union_lambda_set_branch_help<'a>(
             lambda_set,
             closure_data_symbol,
         } => {
-            let argument_layouts = lambda_set.extend_argument_list_for_named(
-                env.arena,
-                lambda_name,
-                argument_layouts_slice,
-            );
+            let argument_layouts =
+                env.extended_argument_layouts(lambda_set, lambda_name, argument_layouts_slice);
 
             // Since this lambda captures, the arguments must have been extended.
             debug_assert!(argument_layouts.len() > argument_layouts_slice.len());
@@ -9376,6 +10250,7 @@ fn enum_lambda_set_to_switch<'a>(
         cond_layout: closure_tag_id_layout,
         branches: branches.into_bump_slice(),
         default_branch,
+        branch_hints: &[],
         ret_layout: return_layout,
     };
 
@@ -9479,6 +10354,7 @@ where
         cond_layout: closure_tag_id_layout,
         branches: branches.into_bump_slice(),
         default_branch,
+        branch_hints: &[],
         ret_layout: return_layout,
     };
 
@@ -9840,3 +10716,193 @@ where
 
     answer
 }
+
+#[cfg(test)]
+mod test {
+    use std::sync::{Arc, Mutex};
+
+    use bumpalo::Bump;
+    use roc_can::abilities::AbilitiesStore;
+    use roc_can::expr::{Expr as CanExpr, IntValue};
+    use roc_can::module::ExposedByModule;
+    use roc_collections::SliceInterner;
+    use roc_derive::DerivedModule;
+    use roc_late_solve::AbilitiesView;
+    use roc_module::symbol::{IdentIds, ModuleId};
+    use roc_region::all::{Loc, Position, Region};
+    use roc_target::TargetInfo;
+    use roc_types::num::IntBound;
+    use roc_types::subs::{Content, Descriptor, Subs, Variable};
+
+    use super::{Env, MonoProblem, MonoProblemKind, Procs, Stmt, ThreadIds, UpdateModeIds};
+    use crate::layout::{GlobalLayoutInterner, LayoutCache};
+
+    const TARGET_INFO: TargetInfo = TargetInfo::default_x86_64();
+
+    /// Runs `f` with the [Env] of an empty module
+    fn with_env<T>(subs: &mut Subs, f: impl FnOnce(&mut Env<'_, '_>) -> T) -> T {
+        let arena = &Bump::new();
+        let mut ident_ids = IdentIds::default();
+        let mut update_mode_ids = UpdateModeIds::new();
+        let abilities_store = AbilitiesStore::default();
+        let exposed_by_module = ExposedByModule::default();
+        let derived_module = Arc::new(Mutex::new(DerivedModule::default()));
+
+        let mut env = Env {
+            arena,
+            subs,
+            expectation_subs: None,
+            home: ModuleId::ATTR,
+            ident_ids: &mut ident_ids,
+            target_info: TARGET_INFO,
+            update_mode_ids: &mut update_mode_ids,
+            call_specialization_counter: 1,
+            abilities: AbilitiesView::Module(&abilities_store),
+            exposed_by_module: &exposed_by_module,
+            derived_module: &derived_module,
+            argument_layouts: SliceInterner::new_in(arena),
+            thread_ids: None,
+            problems: None,
+            region: Region::zero(),
+        };
+
+        f(&mut env)
+    }
+
+    /// The problems of lowering the expression with [Stmt::try_new]
+    fn lowering_problems(
+        subs: &mut Subs,
+        loc_expr: Loc<CanExpr>,
+        var: Variable,
+    ) -> std::vec::Vec<MonoProblem> {
+        with_env(subs, |env| {
+            let interner = GlobalLayoutInterner::with_capacity(4, TARGET_INFO);
+            let mut layout_cache = LayoutCache::new(interner.fork(), TARGET_INFO);
+            let mut procs = Procs::new_in(env.arena);
+
+            let result = Stmt::try_new(env, loc_expr, var, &mut procs, &mut layout_cache);
+
+            assert_eq!(env.problems, None);
+
+            result.err().unwrap_or_default()
+        })
+    }
+
+    fn region(start: u32, end: u32) -> Region {
+        Region::new(Position::new(start), Position::new(end))
+    }
+
+    fn int(value: i128) -> CanExpr {
+        CanExpr::Int(
+            Variable::I64,
+            Variable::SIGNED64,
+            value.to_string().into_boxed_str(),
+            IntValue::I128(value.to_ne_bytes()),
+            IntBound::None,
+        )
+    }
+
+    #[test]
+    fn typed_hole() {
+        let mut subs = Subs::new();
+        let var = subs.fresh_unnamed_flex_var();
+
+        let problems = lowering_problems(
+            &mut subs,
+            Loc::at(region(0, 1), CanExpr::TypedHole(var)),
+            var,
+        );
+
+        assert_eq!(
+            problems,
+            [MonoProblem {
+                kind: MonoProblemKind::TypedHole,
+                region: region(0, 1),
+            }]
+        );
+    }
+
+    #[test]
+    fn record_without_layout() {
+        let mut subs = Subs::new();
+        let record_var = subs.fresh(Descriptor::from(Content::Error));
+
+        let record = CanExpr::Record {
+            record_var,
+            fields: Default::default(),
+        };
+
+        let problems = lowering_problems(&mut subs, Loc::at(region(0, 2), record), record_var);
+
+        assert_eq!(
+            problems,
+            [MonoProblem {
+                kind: MonoProblemKind::ImproperLayout,
+                region: region(0, 2),
+            }]
+        );
+    }
+
+    #[test]
+    fn problem_points_at_the_innermost_expression() {
+        let mut subs = Subs::new();
+        let hole_var = subs.fresh_unnamed_flex_var();
+
+        // if _ then 1 else 2
+        let if_expr = CanExpr::If {
+            cond_var: Variable::BOOL,
+            branch_var: Variable::I64,
+            branches: vec![(
+                Loc::at(region(3, 4), CanExpr::TypedHole(hole_var)),
+                Loc::at(region(10, 11), int(1)),
+            )],
+            final_else: Box::new(Loc::at(region(17, 18), int(2))),
+        };
+
+        let problems = lowering_problems(&mut subs, Loc::at(region(0, 18), if_expr), Variable::I64);
+
+        assert_eq!(
+            problems,
+            [MonoProblem {
+                kind: MonoProblemKind::TypedHole,
+                region: region(3, 4),
+            }]
+        );
+    }
+
+    #[test]
+    fn thread_ids_do_not_collide() {
+        with_env(&mut Subs::new(), |env| {
+            env.unique_symbol();
+            env.next_call_specialization_id();
+
+            let made_on_threads: std::vec::Vec<_> = [3, 0, 1]
+                .into_iter()
+                .enumerate()
+                .map(|(index, symbols)| {
+                    let mut ids = ThreadIds::new(env, index, 3);
+                    let mut taken = std::vec::Vec::new();
+
+                    for _ in 0..symbols {
+                        taken.push(ThreadIds::take(&mut ids.ident_id, ids.stride));
+                    }
+
+                    (ids, taken)
+                })
+                .collect();
+
+            let taken: std::vec::Vec<_> = made_on_threads
+                .iter()
+                .map(|(_, taken)| taken.as_slice())
+                .collect();
+
+            assert_eq!(taken, [&[1, 4, 7][..], &[], &[3]]);
+
+            ThreadIds::write_back(made_on_threads.iter().map(|(ids, _)| *ids), env);
+
+            // the main thread continues after the last id of any thread
+            assert_eq!(env.unique_symbol().ident_id().index(), 8);
+            assert_eq!(env.next_call_specialization_id().id, 2);
+        })
+    }
+}
@@ -1,7 +1,7 @@
 use super::pattern::{build_list_index_probe, store_pattern, DestructType, ListIndex, Pattern};
 use crate::borrow::Ownership;
 use crate::ir::{
-    substitute_in_exprs_many, BranchInfo, Call, CallType, CompiledGuardStmt, Env, Expr,
+    substitute_in_exprs_many, BranchHint, BranchInfo, Call, CallType, CompiledGuardStmt, Env, Expr,
     GuardStmtSpec, JoinPointId, Literal, Param, Procs, Stmt,
 };
 use crate::layout::{
@@ -10,7 +10,7 @@ use crate::layout::{
 };
 use roc_builtins::bitcode::{FloatWidth, IntWidth};
 use roc_collections::all::{MutMap, MutSet};
-use roc_collections::BumpMap;
+use roc_collections::{BumpMap, SmallVec};
 use roc_error_macros::internal_error;
 use roc_exhaustive::{Ctor, CtorName, ListArity, RenderAs, TagId, Union};
 use roc_module::ident::TagName;
@@ -121,6 +121,8 @@ enum Test<'a> {
         bound: ListLenBound,
         len: u64,
     },
+    /// Length in bytes. Not generated from a pattern, but to switch on before comparing strings.
+    IsStrLen(u64),
 }
 
 impl<'a> Test<'a> {
@@ -140,6 +142,7 @@ impl<'a> Test<'a> {
                 ListLenBound::Exact => true,
                 ListLenBound::AtLeast => false,
             },
+            Test::IsStrLen(_) => true,
         }
     }
 }
@@ -186,6 +189,10 @@ impl<'a> Hash for Test<'a> {
                 state.write_u8(7);
                 (len, bound).hash(state);
             }
+            IsStrLen(len) => {
+                state.write_u8(8);
+                len.hash(state);
+            }
         }
     }
 }
@@ -373,6 +380,7 @@ fn tests_are_complete_help(last_test: &Test, number_of_tests: usize) -> bool {
             len: 0,
         } => true, // [..] test
         Test::IsListLen { .. } => false,
+        Test::IsStrLen(_) => false,
     }
 }
 
@@ -1404,6 +1412,9 @@ struct JumpSpec<'a> {
     join_body: Stmt<'a>,
 }
 
+/// The alternatives of a `when` branch, e.g. `A | B -> ...`. Almost every branch has just one.
+pub(crate) type Alternatives<'a> = SmallVec<Pattern<'a>, 1>;
+
 pub(crate) fn optimize_when<'a>(
     env: &mut Env<'a, '_>,
     procs: &mut Procs<'a>,
@@ -1411,19 +1422,23 @@ pub(crate) fn optimize_when<'a>(
     cond_symbol: Symbol,
     cond_layout: InLayout<'a>,
     ret_layout: InLayout<'a>,
-    opt_branches: bumpalo::collections::Vec<'a, (Pattern<'a>, Guard<'a>, Stmt<'a>)>,
+    opt_branches: bumpalo::collections::Vec<'a, (Alternatives<'a>, Guard<'a>, Stmt<'a>)>,
 ) -> Stmt<'a> {
-    let (patterns, indexed_branches): (_, Vec<_>) = opt_branches
-        .into_iter()
-        .enumerate()
-        .map(|(index, (pattern, guard, branch))| {
-            let has_guard = guard.is_some();
-            (
-                (guard, pattern.clone(), index as u64),
-                (index as u64, branch, pattern, has_guard),
-            )
-        })
-        .unzip();
+    let mut patterns = Vec::with_capacity(opt_branches.len());
+    let mut indexed_branches = Vec::with_capacity(opt_branches.len());
+
+    // Alternatives of one branch share its index, so the decision tree reaches the branch body
+    // from each of their tests, and the body is only generated once.
+    for (index, (alternatives, guard, branch)) in opt_branches.into_iter().enumerate() {
+        let has_guard = guard.is_some();
+        let pattern = alternatives[0].clone();
+
+        for alternative in alternatives {
+            patterns.push((guard.clone(), alternative, index as u64));
+        }
+
+        indexed_branches.push((index as u64, branch, pattern, has_guard));
+    }
 
     let decision_tree = compile(&layout_cache.interner, patterns);
     let decider = tree_to_decider(decision_tree);
@@ -1475,9 +1490,8 @@ pub(crate) fn optimize_when<'a>(
                 // updating.
                 let pattern_bindings = pattern.collect_symbols(cond_layout);
 
-                let mut parameters_buf = bumpalo::collections::Vec::with_capacity_in(1, env.arena);
-                let mut pattern_symbols_buf =
-                    bumpalo::collections::Vec::with_capacity_in(1, env.arena);
+                let mut parameters_buf: SmallVec<_, 4> = SmallVec::new();
+                let mut pattern_symbols_buf: SmallVec<_, 4> = SmallVec::new();
                 let mut substitutions = BumpMap::default();
 
                 for (pattern_symbol, layout) in pattern_bindings {
@@ -1491,8 +1505,8 @@ pub(crate) fn optimize_when<'a>(
                     substitutions.insert(pattern_symbol, param_symbol);
                 }
 
-                join_params = parameters_buf.into_bump_slice();
-                jump_pattern_param_symbols = pattern_symbols_buf.into_bump_slice();
+                join_params = parameters_buf.into_bump_slice(env.arena);
+                jump_pattern_param_symbols = pattern_symbols_buf.into_bump_slice(env.arena);
 
                 substitute_in_exprs_many(env.arena, &mut branch, substitutions);
             }
@@ -1765,6 +1779,27 @@ fn test_to_comparison<'a>(
             (stores, (lhs_symbol, Comparator::Eq, rhs_symbol), None)
         }
 
+        Test::IsStrLen(len) => {
+            let real_len_expr = Expr::Call(Call {
+                call_type: CallType::LowLevel {
+                    op: LowLevel::StrCountUtf8Bytes,
+                    update_mode: env.next_update_mode_id(),
+                },
+                arguments: env.arena.alloc([rhs_symbol]),
+            });
+            let test_len_expr = Expr::Literal(Literal::Int((len as i128).to_ne_bytes()));
+
+            let real_len = env.unique_symbol();
+            let test_len = env.unique_symbol();
+
+            let usize_layout = Layout::usize(env.target_info);
+
+            stores.push((real_len, usize_layout, real_len_expr));
+            stores.push((test_len, usize_layout, test_len_expr));
+
+            (stores, (real_len, Comparator::Eq, test_len), None)
+        }
+
         Test::IsListLen { bound, len } => {
             let list_layout = test_layout;
             let list_sym = rhs_symbol;
@@ -1923,6 +1958,10 @@ fn compile_test_help<'a>(
         }
     };
 
+    let branch_hints = BranchHint::for_branches(
+        arena,
+        [BranchHint::from_stmt(&cond), BranchHint::from_stmt(fail)],
+    );
     let branches = env.arena.alloc([(1u64, pass_info, cond)]);
     let default_branch = (fail_info, &*env.arena.alloc(fail.clone()));
 
@@ -1932,6 +1971,7 @@ fn compile_test_help<'a>(
         ret_layout,
         branches,
         default_branch,
+        branch_hints,
     };
 
     let op = match cmp {
@@ -2222,6 +2262,7 @@ fn decide_to_branching<'a>(
             );
 
             let mut branches = bumpalo::collections::Vec::with_capacity_in(tests.len(), env.arena);
+            let mut hints = bumpalo::collections::Vec::with_capacity_in(tests.len() + 1, env.arena);
 
             let mut tag_id_sum: i64 = (0..tests.len() as i64 + 1).sum();
             let mut union_size: i64 = -1;
@@ -2238,6 +2279,8 @@ fn decide_to_branching<'a>(
                     jumps,
                 );
 
+                hints.push(test_branch_hint(&test, &branch));
+
                 let tag = match test {
                     Test::IsInt(v, _) => i128::from_ne_bytes(v) as u64,
                     Test::IsFloat(v, _) => v as u64,
@@ -2250,6 +2293,7 @@ fn decide_to_branching<'a>(
                             unreachable!("at-least bounds cannot be switched on")
                         }
                     },
+                    Test::IsStrLen(len) => len,
                     Test::IsDecimal(_) => unreachable!("decimals cannot be switched on"),
                     Test::IsStr(_) => unreachable!("strings cannot be switched on"),
                 };
@@ -2272,6 +2316,9 @@ fn decide_to_branching<'a>(
                 branches.push((tag, branch_info, branch));
             }
 
+            hints.push(BranchHint::from_stmt(&default_branch));
+            let branch_hints = BranchHint::for_branches(env.arena, hints);
+
             // determine if the switch is exhaustive
             let default_branch_info = if tag_id_sum > 0 && union_size > 0 {
                 BranchInfo::Constructor {
@@ -2294,6 +2341,7 @@ fn decide_to_branching<'a>(
                     cond_symbol: tag_id_symbol,
                     branches: branches.into_bump_slice(),
                     default_branch: (default_branch_info, env.arena.alloc(default_branch)),
+                    branch_hints,
                     ret_layout,
                 };
 
@@ -2316,6 +2364,7 @@ fn decide_to_branching<'a>(
                     cond_symbol: len_symbol,
                     branches: branches.into_bump_slice(),
                     default_branch: (default_branch_info, env.arena.alloc(default_branch)),
+                    branch_hints,
                     ret_layout,
                 };
 
@@ -2327,6 +2376,32 @@ fn decide_to_branching<'a>(
                     arguments: env.arena.alloc([inner_cond_symbol]),
                 });
 
+                Stmt::Let(
+                    len_symbol,
+                    len_expr,
+                    Layout::usize(env.target_info),
+                    env.arena.alloc(switch),
+                )
+            } else if let Layout::Builtin(Builtin::Str) = inner_cond_layout_raw {
+                let len_symbol = env.unique_symbol();
+
+                let switch = Stmt::Switch {
+                    cond_layout: Layout::usize(env.target_info),
+                    cond_symbol: len_symbol,
+                    branches: branches.into_bump_slice(),
+                    default_branch: (default_branch_info, env.arena.alloc(default_branch)),
+                    branch_hints,
+                    ret_layout,
+                };
+
+                let len_expr = Expr::Call(Call {
+                    call_type: CallType::LowLevel {
+                        op: LowLevel::StrCountUtf8Bytes,
+                        update_mode: env.next_update_mode_id(),
+                    },
+                    arguments: env.arena.alloc([inner_cond_symbol]),
+                });
+
                 Stmt::Let(
                     len_symbol,
                     len_expr,
@@ -2339,6 +2414,7 @@ fn decide_to_branching<'a>(
                     cond_symbol: inner_cond_symbol,
                     branches: branches.into_bump_slice(),
                     default_branch: (default_branch_info, env.arena.alloc(default_branch)),
+                    branch_hints,
                     ret_layout,
                 }
             };
@@ -2353,6 +2429,17 @@ fn decide_to_branching<'a>(
     }
 }
 
+/// Error tags are expected to be rare, and so are branches that crash.
+fn test_branch_hint(test: &Test, branch: &Stmt) -> BranchHint {
+    match test {
+        Test::IsCtor {
+            ctor_name: CtorName::Tag(tag_name),
+            ..
+        } if tag_name.0.as_str() == "Err" => BranchHint::Unlikely,
+        _ => BranchHint::from_stmt(branch),
+    }
+}
+
 /*
 fn boolean_all<'a>(arena: &'a Bump, tests: Vec<(Expr<'a>, Expr<'a>, InLayout<'a>)>) -> Expr<'a> {
     let mut expr = Expr::Bool(true);
@@ -2548,19 +2635,88 @@ fn fanout_decider<'a>(
             fallback: Box::new(fallback_decider),
         }
     } else {
-        // in llvm, we cannot switch on strings so must chain
-        let mut decider = fallback_decider;
-
-        for (test, branch_decider) in necessary_tests.into_iter().rev() {
-            decider = Decider::Chain {
-                test_chain: vec![(path.clone(), test)],
-                success: Box::new(branch_decider),
-                failure: Box::new(decider),
-            };
+        match fallback_decider {
+            Decider::Leaf(fallback_target) if should_switch_on_str_len(&necessary_tests) => {
+                str_len_decider(path, fallback_target, necessary_tests)
+            }
+            // in llvm, we cannot switch on strings so must chain
+            _ => chain_tests(&path, necessary_tests, fallback_decider),
         }
+    }
+}
+
+/// Comparing with many string patterns one by one is slow, so we switch on the length first.
+const MIN_STR_TESTS_FOR_LEN_SWITCH: usize = 4;
+
+fn should_switch_on_str_len(tests: &[(Test, Decider<u64>)]) -> bool {
+    let mut lengths = Vec::with_capacity(tests.len());
 
-        decider
+    for (test, _) in tests {
+        match test {
+            Test::IsStr(string) => lengths.push(string.len()),
+            _ => return false,
+        }
     }
+
+    lengths.sort_unstable();
+    lengths.dedup();
+
+    tests.len() >= MIN_STR_TESTS_FOR_LEN_SWITCH && lengths.len() > 1
+}
+
+/// Switch on the length in bytes, then compare with the strings of that length.
+/// Every string test fails to the same target, so it can be duplicated.
+fn str_len_decider<'a>(
+    path: Vec<PathInstruction>,
+    fallback_target: u64,
+    tests: Vec<(Test<'a>, Decider<'a, u64>)>,
+) -> Decider<'a, u64> {
+    let mut tests_by_len: Vec<(u64, Vec<(Test<'a>, Decider<'a, u64>)>)> = Vec::new();
+
+    for (test, decider) in tests {
+        let len = match &test {
+            Test::IsStr(string) => string.len() as u64,
+            _ => internal_error!("only string tests can be grouped by length"),
+        };
+
+        match tests_by_len.iter_mut().find(|(l, _)| *l == len) {
+            Some((_, group)) => group.push((test, decider)),
+            None => tests_by_len.push((len, vec![(test, decider)])),
+        }
+    }
+
+    let len_tests = tests_by_len
+        .into_iter()
+        .map(|(len, group)| {
+            let decider = chain_tests(&path, group, Decider::Leaf(fallback_target));
+
+            (Test::IsStrLen(len), decider)
+        })
+        .collect();
+
+    Decider::FanOut {
+        path,
+        tests: len_tests,
+        fallback: Box::new(Decider::Leaf(fallback_target)),
+    }
+}
+
+fn chain_tests<'a>(
+    path: &[PathInstruction],
+    tests: Vec<(Test<'a>, Decider<'a, u64>)>,
+    fallback: Decider<'a, u64>,
+) -> Decider<'a, u64> {
+    let mut decider = fallback;
+
+    for (test, branch_decider) in tests.into_iter().rev() {
+        decider = Decider::Chain {
+            test_chain: vec![(path.to_vec(), test)],
+            success: Box::new(branch_decider),
+            failure: Box::new(decider),
+        };
+    }
+
+    decider
 }
 
 fn fanout_decider_help<'a>(
@@ -113,6 +113,11 @@ impl<'a> Pattern<'a> {
         false
     }
 
+    pub fn binds_symbols(&self) -> bool {
+        // the layout is only reported back, it does not matter for whether there are bindings
+        self.collect_symbols(Layout::UNIT).next().is_some()
+    }
+
     pub fn collect_symbols(
         &self,
         layout: InLayout<'a>,
@@ -647,7 +647,7 @@ impl<'a> RawFunctionLayout<'a> {
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
-pub struct FieldOrderHash(u64);
+pub struct FieldOrderHash(pub(crate) u64);
 
 impl FieldOrderHash {
     // NB: This should really be a proper "zero" hash via `DefaultHasher::new().finish()`, but Rust
@@ -739,6 +739,10 @@ pub enum UnionLayout<'a> {
     /// Cons is tag id 0 because Nil comes alphabetically after Cons. Here, Nil will be
     /// represented as NULL at runtime, so nullable_id is 1 - which is to say, `true`, because
     /// `(1 as bool)` is `true`.
+    ///
+    /// Non-recursive unions of the same shape, like `[None, Some a]`, are deliberately not
+    /// represented this way: their payload would have to be moved to the heap to get a pointer
+    /// that can be NULL, whereas a `NonRecursive` union stores it inline.
     NullableUnwrapped {
         nullable_id: bool,
         other_fields: &'a [InLayout<'a>],
@@ -941,8 +945,9 @@ impl<'a> UnionLayout<'a> {
         tags.len() < target_info.ptr_width() as usize
     }
 
-    pub const POINTER_MASK_32BIT: usize = 0b0000_0111;
-    pub const POINTER_MASK_64BIT: usize = 0b0000_0011;
+    // a pointer is aligned to its width, so 2 bits on 32-bit targets and 3 bits on 64-bit targets are free
+    pub const POINTER_MASK_32BIT: usize = 0b0000_0011;
+    pub const POINTER_MASK_64BIT: usize = 0b0000_0111;
 
     pub fn tag_id_pointer_bits_and_mask(target_info: TargetInfo) -> (usize, usize) {
         match target_info.ptr_width() {
@@ -1296,6 +1301,15 @@ pub struct Niche<'a>(NichePriv<'a>);
 impl<'a> Niche<'a> {
     pub const NONE: Niche<'a> = Niche(NichePriv::Captures(&[]));
 
+    pub(crate) fn from_captures(captures: &'a [InLayout<'a>]) -> Self {
+        Niche(NichePriv::Captures(captures))
+    }
+
+    pub(crate) fn captures(&self) -> &'a [InLayout<'a>] {
+        let NichePriv::Captures(captures) = self.0;
+        captures
+    }
+
     pub fn to_doc<'b, D, A, I>(
         self,
         alloc: &'b D,
@@ -1363,6 +1377,11 @@ impl<'a> LambdaName<'a> {
         }
     }
 
+    #[inline(always)]
+    pub(crate) fn with_niche(name: Symbol, niche: Niche<'a>) -> Self {
+        Self { name, niche }
+    }
+
     #[inline(always)]
     pub(crate) fn replace_name(&self, name: Symbol) -> Self {
         Self { name, ..*self }
@@ -2902,8 +2921,10 @@ impl<'a> Layout<'a> {
 }
 
 impl<'a> Builtin<'a> {
-    const I1_SIZE: u32 = std::mem::size_of::<bool>() as u32;
-    const DECIMAL_SIZE: u32 = std::mem::size_of::<i128>() as u32;
+    // NOTE: like the int and float widths, these must never use mem::size_of or mem::align_of,
+    // because that is the size of the compiler itself, not of the target
+    const I1_SIZE: u32 = 1;
+    const DECIMAL_SIZE: u32 = IntWidth::I128.stack_size();
 
     /// Number of machine words in an empty one of these
     pub const STR_WORDS: u32 = 3;
@@ -2930,7 +2951,6 @@ impl<'a> Builtin<'a> {
     }
 
     pub fn alignment_bytes(&self, target_info: TargetInfo) -> u32 {
-        use std::mem::align_of;
         use Builtin::*;
 
         let ptr_width = target_info.ptr_width() as u32;
@@ -2941,7 +2961,7 @@ impl<'a> Builtin<'a> {
         match self {
             Int(int_width) => int_width.alignment_bytes(target_info),
             Float(float_width) => float_width.alignment_bytes(target_info),
-            Bool => align_of::<bool>() as u32,
+            Bool => Builtin::I1_SIZE,
             Decimal => IntWidth::I128.alignment_bytes(target_info),
             // we often treat these as i128 (64-bit systems)
             // or i64 (32-bit systems).
@@ -3044,7 +3064,7 @@ impl<'a> Builtin<'a> {
             // The following are usually not heap-allocated, but they might be when inside a Box.
             Builtin::Int(int_width) => int_width.alignment_bytes(target_info).max(ptr_width),
             Builtin::Float(float_width) => float_width.alignment_bytes(target_info).max(ptr_width),
-            Builtin::Bool => (core::mem::align_of::<bool>() as u32).max(ptr_width),
+            Builtin::Bool => Builtin::I1_SIZE.max(ptr_width),
             Builtin::Decimal => IntWidth::I128.alignment_bytes(target_info).max(ptr_width),
         };
 
@@ -4593,4 +4613,36 @@ mod test {
         let interner = STLayoutInterner::with_capacity(4, TargetInfo::default_x86_64());
         assert_eq!(interner.alignment_bytes(Layout::U128), 16);
     }
+
+    #[test]
+    fn wasm32_pointer_sized_layouts() {
+        let interner = STLayoutInterner::with_capacity(4, TargetInfo::default_wasm32());
+
+        assert_eq!(interner.stack_size_and_alignment(Layout::STR), (12, 4));
+        assert_eq!(interner.stack_size(Layout::OPAQUE_PTR), 4);
+        assert_eq!(interner.allocation_alignment_bytes(Layout::BOOL), 4);
+
+        // 64-bit and 128-bit numbers keep their alignment on wasm32
+        assert_eq!(interner.alignment_bytes(Layout::U64), 8);
+        assert_eq!(interner.stack_size_and_alignment(Layout::DEC), (16, 16));
+    }
+
+    #[test]
+    fn tag_id_in_pointer_bits_depends_on_pointer_width() {
+        let tag = &[Layout::NAKED_RECURSIVE_PTR] as &[_];
+        let tags = [tag; 5];
+        let union_layout = UnionLayout::Recursive(&tags);
+
+        assert!(union_layout.stores_tag_id_in_pointer(TargetInfo::default_x86_64()));
+        assert!(union_layout.stores_tag_id_as_data(TargetInfo::default_wasm32()));
+
+        assert_eq!(
+            UnionLayout::tag_id_pointer_bits_and_mask(TargetInfo::default_x86_64()),
+            (3, 0b111)
+        );
+        assert_eq!(
+            UnionLayout::tag_id_pointer_bits_and_mask(TargetInfo::default_wasm32()),
+            (2, 0b11)
+        );
+    }
 }
@@ -709,6 +709,11 @@ struct WrittenGlobalRecursive<'a> {
 }
 
 impl<'a> TLLayoutInterner<'a> {
+    /// Creates another [TLLayoutInterner] with the same parent, for use on another thread.
+    pub fn fork(&self) -> TLLayoutInterner<'a> {
+        self.parent.fork()
+    }
+
     /// Records an interned value in thread-specific storage, for faster access on lookups.
     fn record(&self, key: Layout<'a>, interned: InLayout<'a>) {
         let mut vec = self.vec.borrow_mut();
@@ -872,6 +877,56 @@ impl<'a> STLayoutInterner<'a> {
     pub fn is_empty(&self) -> bool {
         self.vec.is_empty()
     }
+
+    /// All interned layouts, the index of a layout is its [InLayout].
+    pub(crate) fn layouts(&self) -> &[Layout<'a>] {
+        &self.vec
+    }
+
+    /// The keys that [LayoutInterner::insert_recursive] looks up, that are not themselves interned.
+    pub(crate) fn normalized_recursive_layouts(
+        &self,
+    ) -> impl Iterator<Item = (Layout<'a>, InLayout<'a>)> + '_ {
+        self.map
+            .iter()
+            .filter(|(layout, interned)| self.vec[interned.0] != **layout)
+            .map(|(layout, interned)| (*layout, *interned))
+    }
+
+    /// The keys that [LayoutInterner::insert_lambda_set] looks up.
+    pub(crate) fn normalized_lambda_sets(
+        &self,
+    ) -> impl Iterator<Item = (LambdaSet<'a>, LambdaSet<'a>)> + '_ {
+        self.normalized_lambda_set_map
+            .iter()
+            .map(|(normalized, lambda_set)| (*normalized, *lambda_set))
+    }
+
+    /// Recreates an interner from the parts above, so that every [InLayout] keeps its meaning.
+    pub(crate) fn from_parts(
+        layouts: Vec<Layout<'a>>,
+        normalized_recursive_layouts: impl IntoIterator<Item = (Layout<'a>, InLayout<'a>)>,
+        normalized_lambda_sets: impl IntoIterator<Item = (LambdaSet<'a>, LambdaSet<'a>)>,
+        target_info: TargetInfo,
+    ) -> Self {
+        let mut map = BumpMap::with_capacity_and_hasher(layouts.len(), default_hasher());
+
+        for (index, layout) in layouts.iter().enumerate() {
+            map.insert(*layout, InLayout(index, PhantomData));
+        }
+
+        map.extend(normalized_recursive_layouts);
+
+        let mut normalized_lambda_set_map = BumpMap::default();
+        normalized_lambda_set_map.extend(normalized_lambda_sets);
+
+        Self {
+            map,
+            normalized_lambda_set_map,
+            vec: layouts,
+            target_info,
+        }
+    }
 }
 
 macro_rules! st_impl {
@@ -17,6 +17,8 @@ pub mod layout;
 pub mod layout_soa;
 pub mod low_level;
 pub mod reset_reuse;
+pub mod serialize;
+pub mod stats;
 pub mod tail_recursion;
 
 pub mod debug;
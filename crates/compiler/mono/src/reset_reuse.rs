@@ -186,6 +186,7 @@ fn insert_reset_reuse_operations_stmt<'a, 'i>(
             cond_layout,
             branches,
             default_branch,
+            branch_hints,
             ret_layout,
         } => {
             let new_branches = branches
@@ -338,6 +339,7 @@ fn insert_reset_reuse_operations_stmt<'a, 'i>(
                 cond_layout: *cond_layout,
                 branches: newer_branches,
                 default_branch: newer_default_branch,
+                branch_hints,
                 ret_layout: *ret_layout,
             })
         }
@@ -0,0 +1,1513 @@
+//! A compact binary form of the specialized procedures, so that a build can write them to disk
+//! and load them in a later build instead of lowering the modules that did not change again.
+//!
+//! Layouts are written as their index in the layout interner, and the interner itself is written
+//! along with the procedures. It is loaded as a new interner in which every [InLayout] has the same
+//! index, so it must be the interner of the whole program. Symbols are written once, in a symbol
+//! table, and referred to by their index in that table everywhere else. The type variables of
+//! `expect` and `dbg` only mean something together with the type state of their module.
+//!
+//! The bytes are only meant to be read by the same version of the compiler, for the same target.
+//! Debug builds of the compiler round-trip the procedures of every program they build through this
+//! format when `ROC_CHECK_MONO_CACHE` is set.
+use std::io::{self, Write};
+
+use bumpalo::collections::Vec;
+use bumpalo::Bump;
+use roc_builtins::bitcode::{FloatWidth, IntWidth};
+use roc_collections::{BumpMap, MutMap};
+use roc_module::ident::{ForeignSymbol, Lowercase};
+use roc_module::low_level::LowLevel;
+use roc_module::symbol::Symbol;
+use roc_region::all::{Position, Region};
+use roc_serialize::bytes::serialize_slice;
+use roc_target::TargetInfo;
+use roc_types::subs::Variable;
+
+use crate::borrow::Ownership;
+use crate::ir::{
+    BranchHint, BranchInfo, Call, CallSpecId, CallType, CrashTag, Expr, HigherOrderLowLevel,
+    HostExposedLambdaSet, HostExposedLayouts, JoinPointId, LambdaSetId, ListLiteralElement,
+    Literal, ModifyRc, Param, PassedFunction, Proc, ProcLayout, SelfRecursive, Stmt, UpdateModeId,
+};
+use crate::layout::{
+    Builtin, FieldOrderHash, InLayout, LambdaName, LambdaSet, Layout, Niche, RawFunctionLayout,
+    STLayoutInterner, UnionLayout,
+};
+use crate::low_level::HigherOrder;
+
+/// Changes whenever the format changes
+const FORMAT_VERSION: u32 = 2;
+
+pub type SpecializedProcs<'a> = MutMap<(Symbol, ProcLayout<'a>), Proc<'a>>;
+
+pub fn serialize_procs<'a>(
+    layout_interner: &STLayoutInterner<'a>,
+    procs: &SpecializedProcs<'a>,
+    writer: &mut impl Write,
+) -> io::Result<usize> {
+    let mut body = Writer::default();
+
+    body.layout_interner(layout_interner);
+    body.procs(procs);
+
+    let mut header = Writer::default();
+
+    header.u32(FORMAT_VERSION);
+    header.usize(body.symbols.len());
+    writer.write_all(&header.bytes)?;
+
+    let written = serialize_slice(&body.symbols, writer, header.bytes.len())?;
+    writer.write_all(&body.bytes)?;
+
+    Ok(written + body.bytes.len())
+}
+
+/// `None` if the bytes were written by a version of the compiler with another format, or if they
+/// are not a complete set of procedures.
+pub fn deserialize_procs<'a>(
+    arena: &'a Bump,
+    target_info: TargetInfo,
+    bytes: &[u8],
+) -> Option<((STLayoutInterner<'a>, SpecializedProcs<'a>), usize)> {
+    let mut reader = Reader {
+        arena,
+        bytes,
+        offset: 0,
+        symbols: std::vec::Vec::new(),
+        layouts_end: 0,
+    };
+
+    if reader.u32()? != FORMAT_VERSION {
+        return None;
+    }
+
+    reader.symbol_table()?;
+
+    let layout_interner = reader.layout_interner(target_info)?;
+    let procs = reader.procs()?;
+
+    if reader.layouts_end > layout_interner.layouts().len() {
+        return None;
+    }
+
+    Some(((layout_interner, procs), reader.offset))
+}
+
+#[derive(Default)]
+struct Writer {
+    bytes: std::vec::Vec<u8>,
+    symbols: std::vec::Vec<Symbol>,
+    symbol_indices: MutMap<Symbol, usize>,
+}
+
+impl Writer {
+    fn u8(&mut self, value: u8) {
+        self.bytes.push(value);
+    }
+
+    fn bool(&mut self, value: bool) {
+        self.u8(value as u8);
+    }
+
+    /// LEB128, most numbers in the IR are small
+    fn u64(&mut self, mut value: u64) {
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+
+            if value == 0 {
+                self.u8(byte);
+                return;
+            }
+
+            self.u8(byte | 0x80);
+        }
+    }
+
+    fn u32(&mut self, value: u32) {
+        self.u64(value as u64);
+    }
+
+    fn usize(&mut self, value: usize) {
+        self.u64(value as u64);
+    }
+
+    fn str(&mut self, value: &str) {
+        self.usize(value.len());
+        self.bytes.extend_from_slice(value.as_bytes());
+    }
+
+    fn symbol(&mut self, symbol: Symbol) {
+        let next_index = self.symbols.len();
+        let index = *self.symbol_indices.entry(symbol).or_insert(next_index);
+
+        if index == next_index {
+            self.symbols.push(symbol);
+        }
+
+        self.usize(index);
+    }
+
+    fn symbols(&mut self, symbols: &[Symbol]) {
+        self.usize(symbols.len());
+
+        for symbol in symbols {
+            self.symbol(*symbol);
+        }
+    }
+
+    fn slice<T>(&mut self, slice: &[T], mut write: impl FnMut(&mut Self, &T)) {
+        self.usize(slice.len());
+
+        for elem in slice {
+            write(self, elem);
+        }
+    }
+
+    fn in_layout(&mut self, layout: InLayout) {
+        self.usize(layout.index());
+    }
+
+    fn in_layouts(&mut self, layouts: &[InLayout]) {
+        self.slice(layouts, |w, layout| w.in_layout(*layout));
+    }
+
+    fn option_in_layout(&mut self, layout: Option<InLayout>) {
+        match layout {
+            None => self.u8(0),
+            Some(layout) => {
+                self.u8(1);
+                self.in_layout(layout);
+            }
+        }
+    }
+
+    fn layout_interner(&mut self, interner: &STLayoutInterner) {
+        self.slice(interner.layouts(), |w, layout| w.layout(layout));
+
+        let recursive: std::vec::Vec<_> = interner.normalized_recursive_layouts().collect();
+        self.slice(&recursive, |w, (layout, interned)| {
+            w.layout(layout);
+            w.in_layout(*interned);
+        });
+
+        let lambda_sets: std::vec::Vec<_> = interner.normalized_lambda_sets().collect();
+        self.slice(&lambda_sets, |w, (normalized, lambda_set)| {
+            w.lambda_set(normalized);
+            w.lambda_set(lambda_set);
+        });
+    }
+
+    fn layout(&mut self, layout: &Layout) {
+        match layout {
+            Layout::Builtin(builtin) => {
+                self.u8(0);
+                self.builtin(builtin);
+            }
+            Layout::Struct {
+                field_order_hash,
+                field_layouts,
+            } => {
+                self.u8(1);
+                self.u64(field_order_hash.0);
+                self.in_layouts(field_layouts);
+            }
+            Layout::Boxed(inner) => {
+                self.u8(2);
+                self.in_layout(*inner);
+            }
+            Layout::Union(union_layout) => {
+                self.u8(3);
+                self.union_layout(union_layout);
+            }
+            Layout::LambdaSet(lambda_set) => {
+                self.u8(4);
+                self.lambda_set(lambda_set);
+            }
+            Layout::RecursivePointer(inner) => {
+                self.u8(5);
+                self.in_layout(*inner);
+            }
+        }
+    }
+
+    fn builtin(&mut self, builtin: &Builtin) {
+        match builtin {
+            Builtin::Int(width) => {
+                self.u8(0);
+                self.u8(*width as u8);
+            }
+            Builtin::Float(width) => {
+                self.u8(1);
+                self.u8(*width as u8);
+            }
+            Builtin::Bool => self.u8(2),
+            Builtin::Decimal => self.u8(3),
+            Builtin::Str => self.u8(4),
+            Builtin::List(elem) => {
+                self.u8(5);
+                self.in_layout(*elem);
+            }
+        }
+    }
+
+    fn union_layout(&mut self, union_layout: &UnionLayout) {
+        match union_layout {
+            UnionLayout::NonRecursive(tags) => {
+                self.u8(0);
+                self.slice(tags, |w, fields| w.in_layouts(fields));
+            }
+            UnionLayout::Recursive(tags) => {
+                self.u8(1);
+                self.slice(tags, |w, fields| w.in_layouts(fields));
+            }
+            UnionLayout::NonNullableUnwrapped(fields) => {
+                self.u8(2);
+                self.in_layouts(fields);
+            }
+            UnionLayout::NullableWrapped {
+                nullable_id,
+                other_tags,
+            } => {
+                self.u8(3);
+                self.u64(*nullable_id as u64);
+                self.slice(other_tags, |w, fields| w.in_layouts(fields));
+            }
+            UnionLayout::NullableUnwrapped {
+                nullable_id,
+                other_fields,
+            } => {
+                self.u8(4);
+                self.bool(*nullable_id);
+                self.in_layouts(other_fields);
+            }
+        }
+    }
+
+    fn lambda_set(&mut self, lambda_set: &LambdaSet) {
+        self.in_layouts(lambda_set.args);
+        self.in_layout(lambda_set.ret);
+        self.slice(lambda_set.set, |w, (name, captures)| {
+            w.symbol(*name);
+            w.in_layouts(captures);
+        });
+        self.in_layout(lambda_set.representation);
+        self.in_layout(lambda_set.full_layout);
+    }
+
+    fn lambda_name(&mut self, lambda_name: LambdaName) {
+        self.symbol(lambda_name.name());
+        self.in_layouts(lambda_name.niche().captures());
+    }
+
+    fn proc_layout(&mut self, proc_layout: &ProcLayout) {
+        self.in_layouts(proc_layout.arguments);
+        self.in_layout(proc_layout.result);
+        self.in_layouts(proc_layout.niche.captures());
+    }
+
+    fn procs(&mut self, procs: &SpecializedProcs) {
+        self.usize(procs.len());
+
+        for ((symbol, proc_layout), proc) in procs.iter() {
+            self.symbol(*symbol);
+            self.proc_layout(proc_layout);
+            self.proc(proc);
+        }
+    }
+
+    fn proc(&mut self, proc: &Proc) {
+        let Proc {
+            name,
+            args,
+            body,
+            closure_data_layout,
+            ret_layout,
+            is_self_recursive,
+            host_exposed_layouts,
+        } = proc;
+
+        self.lambda_name(*name);
+        self.slice(args, |w, (layout, symbol)| {
+            w.in_layout(*layout);
+            w.symbol(*symbol);
+        });
+        self.stmt(body);
+        self.option_in_layout(*closure_data_layout);
+        self.in_layout(*ret_layout);
+
+        match is_self_recursive {
+            SelfRecursive::NotSelfRecursive => self.u8(0),
+            SelfRecursive::SelfRecursive(id) => {
+                self.u8(1);
+                self.symbol(id.0);
+            }
+        }
+
+        match host_exposed_layouts {
+            HostExposedLayouts::NotHostExposed => self.u8(0),
+            HostExposedLayouts::HostExposed { rigids, aliases } => {
+                self.u8(1);
+
+                self.usize(rigids.len());
+                for (name, layout) in rigids.iter() {
+                    self.str(name.as_str());
+                    self.in_layout(*layout);
+                }
+
+                self.usize(aliases.len());
+                for (symbol, lambda_set) in aliases.iter() {
+                    self.symbol(*symbol);
+                    self.host_exposed_lambda_set(lambda_set);
+                }
+            }
+        }
+    }
+
+    fn host_exposed_lambda_set(&mut self, lambda_set: &HostExposedLambdaSet) {
+        self.u32(lambda_set.id.0);
+        self.symbol(lambda_set.symbol);
+        self.proc_layout(&lambda_set.proc_layout);
+
+        match &lambda_set.raw_function_layout {
+            RawFunctionLayout::Function(arguments, closure, result) => {
+                self.u8(0);
+                self.in_layouts(arguments);
+                self.lambda_set(closure);
+                self.in_layout(*result);
+            }
+            RawFunctionLayout::ZeroArgumentThunk(result) => {
+                self.u8(1);
+                self.in_layout(*result);
+            }
+        }
+    }
+
+    fn stmt(&mut self, stmt: &Stmt) {
+        match stmt {
+            Stmt::Let(symbol, expr, layout, cont) => {
+                self.u8(0);
+                self.symbol(*symbol);
+                self.expr(expr);
+                self.in_layout(*layout);
+                self.stmt(cont);
+            }
+            Stmt::Switch {
+                cond_symbol,
+                cond_layout,
+                branches,
+                default_branch,
+                branch_hints,
+                ret_layout,
+            } => {
+                self.u8(1);
+                self.symbol(*cond_symbol);
+                self.in_layout(*cond_layout);
+                self.slice(branches, |w, (tag, info, branch)| {
+                    w.u64(*tag);
+                    w.branch_info(info);
+                    w.stmt(branch);
+                });
+                self.branch_info(&default_branch.0);
+                self.stmt(default_branch.1);
+                self.slice(branch_hints, |w, hint| w.u8(*hint as u8));
+                self.in_layout(*ret_layout);
+            }
+            Stmt::Ret(symbol) => {
+                self.u8(2);
+                self.symbol(*symbol);
+            }
+            Stmt::Refcounting(modify, cont) => {
+                self.u8(3);
+
+                match modify {
+                    ModifyRc::Inc(symbol, amount) => {
+                        self.u8(0);
+                        self.symbol(*symbol);
+                        self.u64(*amount);
+                    }
+                    ModifyRc::Dec(symbol) => {
+                        self.u8(1);
+                        self.symbol(*symbol);
+                    }
+                    ModifyRc::DecRef(symbol) => {
+                        self.u8(2);
+                        self.symbol(*symbol);
+                    }
+                }
+
+                self.stmt(cont);
+            }
+            Stmt::Expect {
+                condition,
+                region,
+                lookups,
+                variables,
+                remainder,
+            }
+            | Stmt::ExpectFx {
+                condition,
+                region,
+                lookups,
+                variables,
+                remainder,
+            } => {
+                let tag = match stmt {
+                    Stmt::Expect { .. } => 4,
+                    _ => 5,
+                };
+
+                self.u8(tag);
+                self.symbol(*condition);
+                self.u32(region.start().offset);
+                self.u32(region.end().offset);
+                self.symbols(lookups);
+                self.slice(variables, |w, variable| w.u32(variable.index()));
+                self.stmt(remainder);
+            }
+            Stmt::Dbg {
+                symbol,
+                variable,
+                remainder,
+            } => {
+                self.u8(6);
+                self.symbol(*symbol);
+                self.u32(variable.index());
+                self.stmt(remainder);
+            }
+            Stmt::Join {
+                id,
+                parameters,
+                body,
+                remainder,
+            } => {
+                self.u8(7);
+                self.symbol(id.0);
+                self.slice(parameters, |w, param| {
+                    w.symbol(param.symbol);
+                    w.bool(matches!(param.ownership, Ownership::Borrowed));
+                    w.in_layout(param.layout);
+                });
+                self.stmt(body);
+                self.stmt(remainder);
+            }
+            Stmt::Jump(id, arguments) => {
+                self.u8(8);
+                self.symbol(id.0);
+                self.symbols(arguments);
+            }
+            Stmt::Crash(symbol, tag) => {
+                self.u8(9);
+                self.symbol(*symbol);
+                self.u32(*tag as u32);
+            }
+        }
+    }
+
+    fn branch_info(&mut self, info: &BranchInfo) {
+        match info {
+            BranchInfo::None => self.u8(0),
+            BranchInfo::Constructor {
+                scrutinee,
+                layout,
+                tag_id,
+            } => {
+                self.u8(1);
+                self.symbol(*scrutinee);
+                self.in_layout(*layout);
+                self.u64(*tag_id as u64);
+            }
+        }
+    }
+
+    fn literal(&mut self, literal: &Literal) {
+        match literal {
+            Literal::Int(bytes) => {
+                self.u8(0);
+                self.bytes.extend_from_slice(bytes);
+            }
+            Literal::U128(bytes) => {
+                self.u8(1);
+                self.bytes.extend_from_slice(bytes);
+            }
+            Literal::Float(float) => {
+                self.u8(2);
+                self.bytes.extend_from_slice(&float.to_le_bytes());
+            }
+            Literal::Decimal(bytes) => {
+                self.u8(3);
+                self.bytes.extend_from_slice(bytes);
+            }
+            Literal::Str(string) => {
+                self.u8(4);
+                self.str(string);
+            }
+            Literal::Bool(value) => {
+                self.u8(5);
+                self.bool(*value);
+            }
+            Literal::Byte(value) => {
+                self.u8(6);
+                self.u8(*value);
+            }
+        }
+    }
+
+    fn list_elements(&mut self, elems: &[ListLiteralElement]) {
+        self.slice(elems, |w, elem| match elem {
+            ListLiteralElement::Literal(literal) => {
+                w.u8(0);
+                w.literal(literal);
+            }
+            ListLiteralElement::Symbol(symbol) => {
+                w.u8(1);
+                w.symbol(*symbol);
+            }
+        });
+    }
+
+    fn expr(&mut self, expr: &Expr) {
+        match expr {
+            Expr::Literal(literal) => {
+                self.u8(0);
+                self.literal(literal);
+            }
+            Expr::Call(call) => {
+                self.u8(1);
+                self.call(call);
+            }
+            Expr::Tag {
+                tag_layout,
+                tag_id,
+                arguments,
+            } => {
+                self.u8(2);
+                self.union_layout(tag_layout);
+                self.u64(*tag_id as u64);
+                self.symbols(arguments);
+            }
+            Expr::Struct(fields) => {
+                self.u8(3);
+                self.symbols(fields);
+            }
+            Expr::NullPointer => self.u8(4),
+            Expr::StructAtIndex {
+                index,
+                field_layouts,
+                structure,
+            } => {
+                self.u8(5);
+                self.u64(*index);
+                self.in_layouts(field_layouts);
+                self.symbol(*structure);
+            }
+            Expr::GetTagId {
+                structure,
+                union_layout,
+            } => {
+                self.u8(6);
+                self.symbol(*structure);
+                self.union_layout(union_layout);
+            }
+            Expr::UnionAtIndex {
+                structure,
+                tag_id,
+                union_layout,
+                index,
+            } => {
+                self.u8(7);
+                self.symbol(*structure);
+                self.u64(*tag_id as u64);
+                self.union_layout(union_layout);
+                self.u64(*index);
+            }
+            Expr::Array { elem_layout, elems } => {
+                self.u8(8);
+                self.in_layout(*elem_layout);
+                self.list_elements(elems);
+            }
+            Expr::StaticArray { elem_layout, elems } => {
+                self.u8(9);
+                self.in_layout(*elem_layout);
+                self.slice(elems, |w, literal| w.literal(literal));
+            }
+            Expr::EmptyArray => self.u8(10),
+            Expr::ExprBox { symbol } => {
+                self.u8(11);
+                self.symbol(*symbol);
+            }
+            Expr::ExprUnbox { symbol } => {
+                self.u8(12);
+                self.symbol(*symbol);
+            }
+            Expr::Reuse {
+                symbol,
+                update_tag_id,
+                update_mode,
+                tag_layout,
+                tag_id,
+                arguments,
+            } => {
+                self.u8(13);
+                self.symbol(*symbol);
+                self.bool(*update_tag_id);
+                self.u32(update_mode.id);
+                self.union_layout(tag_layout);
+                self.u64(*tag_id as u64);
+                self.symbols(arguments);
+            }
+            Expr::Reset {
+                symbol,
+                update_mode,
+            } => {
+                self.u8(14);
+                self.symbol(*symbol);
+                self.u32(update_mode.id);
+            }
+            Expr::ResetRef {
+                symbol,
+                update_mode,
+            } => {
+                self.u8(15);
+                self.symbol(*symbol);
+                self.u32(update_mode.id);
+            }
+            Expr::RuntimeErrorFunction(message) => {
+                self.u8(16);
+                self.str(message);
+            }
+        }
+    }
+
+    fn call(&mut self, call: &Call) {
+        match &call.call_type {
+            CallType::ByName {
+                name,
+                ret_layout,
+                arg_layouts,
+                specialization_id,
+            } => {
+                self.u8(0);
+                self.lambda_name(*name);
+                self.in_layout(*ret_layout);
+                self.in_layouts(arg_layouts);
+                self.u32(specialization_id.id);
+            }
+            CallType::Foreign {
+                foreign_symbol,
+                ret_layout,
+            } => {
+                self.u8(1);
+                self.str(foreign_symbol.as_str());
+                self.in_layout(*ret_layout);
+            }
+            CallType::LowLevel { op, update_mode } => {
+                self.u8(2);
+                self.u8(*op as u8);
+                self.u32(update_mode.id);
+            }
+            CallType::HigherOrder(higher_order) => {
+                self.u8(3);
+                self.higher_order(higher_order);
+            }
+        }
+
+        self.symbols(call.arguments);
+    }
+
+    fn higher_order(&mut self, higher_order: &HigherOrderLowLevel) {
+        let HigherOrderLowLevel {
+            op,
+            closure_env_layout,
+            update_mode,
+            passed_function,
+        } = higher_order;
+
+        match op {
+            HigherOrder::ListMap { xs } => {
+                self.u8(0);
+                self.symbols(&[*xs]);
+            }
+            HigherOrder::ListMap2 { xs, ys } => {
+                self.u8(1);
+                self.symbols(&[*xs, *ys]);
+            }
+            HigherOrder::ListMap3 { xs, ys, zs } => {
+                self.u8(2);
+                self.symbols(&[*xs, *ys, *zs]);
+            }
+            HigherOrder::ListMap4 { xs, ys, zs, ws } => {
+                self.u8(3);
+                self.symbols(&[*xs, *ys, *zs, *ws]);
+            }
+            HigherOrder::ListSortWith { xs } => {
+                self.u8(4);
+                self.symbols(&[*xs]);
+            }
+        }
+
+        self.option_in_layout(*closure_env_layout);
+        self.u32(update_mode.id);
+
+        self.lambda_name(passed_function.name);
+        self.in_layouts(passed_function.argument_layouts);
+        self.in_layout(passed_function.return_layout);
+        self.u32(passed_function.specialization_id.id);
+        self.symbol(passed_function.captured_environment);
+        self.bool(passed_function.owns_captured_environment);
+    }
+}
+
+/// Every read returns `None` when the bytes run out or hold something the [Writer] never writes,
+/// so that a corrupted or truncated file is rejected instead of crashing the build.
+struct Reader<'a, 'b> {
+    arena: &'a Bump,
+    bytes: &'b [u8],
+    offset: usize,
+    symbols: std::vec::Vec<Symbol>,
+    /// One past the highest layout index read so far, to check against the interner at the end
+    layouts_end: usize,
+}
+
+impl<'a, 'b> Reader<'a, 'b> {
+    fn u8(&mut self) -> Option<u8> {
+        let byte = *self.bytes.get(self.offset)?;
+        self.offset += 1;
+        Some(byte)
+    }
+
+    fn bool(&mut self) -> Option<bool> {
+        match self.u8()? {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        }
+    }
+
+    fn bytes(&mut self, len: usize) -> Option<&'b [u8]> {
+        let bytes = self.bytes.get(self.offset..)?.get(..len)?;
+        self.offset += len;
+        Some(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Option<[u8; N]> {
+        self.bytes(N)?.try_into().ok()
+    }
+
+    fn u64(&mut self) -> Option<u64> {
+        let mut value = 0;
+        let mut shift = 0;
+
+        loop {
+            let byte = self.u8()?;
+
+            if shift >= u64::BITS {
+                return None;
+            }
+
+            value |= ((byte & 0x7f) as u64) << shift;
+
+            if byte & 0x80 == 0 {
+                return Some(value);
+            }
+
+            shift += 7;
+        }
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        self.u64()?.try_into().ok()
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        self.u64()?.try_into().ok()
+    }
+
+    fn usize(&mut self) -> Option<usize> {
+        self.u64()?.try_into().ok()
+    }
+
+    /// The length of a sequence in which every element takes at least one byte, so that a
+    /// corrupted length can not make us allocate more than the input could possibly hold.
+    fn len(&mut self) -> Option<usize> {
+        let len = self.usize()?;
+
+        (len <= self.bytes.len().saturating_sub(self.offset)).then_some(len)
+    }
+
+    fn str(&mut self) -> Option<&'a str> {
+        let len = self.usize()?;
+        let string = std::str::from_utf8(self.bytes(len)?).ok()?;
+
+        Some(self.arena.alloc_str(string))
+    }
+
+    fn symbol_table(&mut self) -> Option<()> {
+        let len = self.usize()?;
+
+        // the table is written with `serialize_slice`, which aligns it to a Symbol
+        let size = std::mem::size_of::<Symbol>();
+        let alignment = std::mem::align_of::<Symbol>();
+        self.offset = self.offset.checked_add(alignment - 1)? / alignment * alignment;
+
+        let table = self.bytes(len.checked_mul(size)?)?;
+
+        // a Symbol is its IdentId followed by its ModuleId, which is never zero
+        if table.chunks_exact(size).any(|symbol| symbol[4..] == [0; 4]) {
+            return None;
+        }
+
+        let mut symbols = std::vec::Vec::with_capacity(len);
+
+        // the bytes themselves need not be aligned, so we copy them rather than cast them
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                table.as_ptr(),
+                symbols.as_mut_ptr() as *mut u8,
+                table.len(),
+            );
+            symbols.set_len(len);
+        }
+
+        self.symbols = symbols;
+
+        Some(())
+    }
+
+    fn symbol(&mut self) -> Option<Symbol> {
+        let index = self.usize()?;
+        self.symbols.get(index).copied()
+    }
+
+    fn symbols(&mut self) -> Option<&'a [Symbol]> {
+        self.slice(|r| r.symbol())
+    }
+
+    fn slice<T>(&mut self, mut read: impl FnMut(&mut Self) -> Option<T>) -> Option<&'a [T]> {
+        let len = self.len()?;
+        let mut vec = Vec::with_capacity_in(len, self.arena);
+
+        for _ in 0..len {
+            vec.push(read(self)?);
+        }
+
+        Some(vec.into_bump_slice())
+    }
+
+    fn vec<T>(&mut self, mut read: impl FnMut(&mut Self) -> Option<T>) -> Option<std::vec::Vec<T>> {
+        let len = self.len()?;
+
+        (0..len).map(|_| read(self)).collect()
+    }
+
+    fn in_layout(&mut self) -> Option<InLayout<'a>> {
+        let index = self.usize()?;
+        self.layouts_end = self.layouts_end.max(index.saturating_add(1));
+
+        // the interner is recreated with the same indices, and `deserialize_procs` checks that
+        // every index we read is in it
+        Some(unsafe { InLayout::from_index(index) })
+    }
+
+    fn in_layouts(&mut self) -> Option<&'a [InLayout<'a>]> {
+        self.slice(|r| r.in_layout())
+    }
+
+    fn option_in_layout(&mut self) -> Option<Option<InLayout<'a>>> {
+        match self.u8()? {
+            0 => Some(None),
+            1 => Some(Some(self.in_layout()?)),
+            _ => None,
+        }
+    }
+
+    fn layout_interner(&mut self, target_info: TargetInfo) -> Option<STLayoutInterner<'a>> {
+        let layouts = self.vec(|r| r.layout())?;
+        let recursive = self.vec(|r| Some((r.layout()?, r.in_layout()?)))?;
+        let lambda_sets = self.vec(|r| Some((r.lambda_set()?, r.lambda_set()?)))?;
+
+        Some(STLayoutInterner::from_parts(
+            layouts,
+            recursive,
+            lambda_sets,
+            target_info,
+        ))
+    }
+
+    fn layout(&mut self) -> Option<Layout<'a>> {
+        let layout = match self.u8()? {
+            0 => Layout::Builtin(self.builtin()?),
+            1 => Layout::Struct {
+                field_order_hash: FieldOrderHash(self.u64()?),
+                field_layouts: self.in_layouts()?,
+            },
+            2 => Layout::Boxed(self.in_layout()?),
+            3 => Layout::Union(self.union_layout()?),
+            4 => Layout::LambdaSet(self.lambda_set()?),
+            5 => Layout::RecursivePointer(self.in_layout()?),
+            _ => return None,
+        };
+
+        Some(layout)
+    }
+
+    fn builtin(&mut self) -> Option<Builtin<'a>> {
+        let builtin = match self.u8()? {
+            0 => Builtin::Int(int_width(self.u8()?)?),
+            1 => Builtin::Float(match self.u8()? {
+                0 => FloatWidth::F32,
+                1 => FloatWidth::F64,
+                _ => return None,
+            }),
+            2 => Builtin::Bool,
+            3 => Builtin::Decimal,
+            4 => Builtin::Str,
+            5 => Builtin::List(self.in_layout()?),
+            _ => return None,
+        };
+
+        Some(builtin)
+    }
+
+    fn union_layout(&mut self) -> Option<UnionLayout<'a>> {
+        let union_layout = match self.u8()? {
+            0 => UnionLayout::NonRecursive(self.slice(|r| r.in_layouts())?),
+            1 => UnionLayout::Recursive(self.slice(|r| r.in_layouts())?),
+            2 => UnionLayout::NonNullableUnwrapped(self.in_layouts()?),
+            3 => UnionLayout::NullableWrapped {
+                nullable_id: self.u16()?,
+                other_tags: self.slice(|r| r.in_layouts())?,
+            },
+            4 => UnionLayout::NullableUnwrapped {
+                nullable_id: self.bool()?,
+                other_fields: self.in_layouts()?,
+            },
+            _ => return None,
+        };
+
+        Some(union_layout)
+    }
+
+    fn lambda_set(&mut self) -> Option<LambdaSet<'a>> {
+        let args = self.in_layouts()?;
+        let ret = self.in_layout()?;
+        let set = self.slice(|r| Some((r.symbol()?, r.in_layouts()?)))?;
+
+        Some(LambdaSet {
+            args: self.arena.alloc(args),
+            ret,
+            set: self.arena.alloc(set),
+            representation: self.in_layout()?,
+            full_layout: self.in_layout()?,
+        })
+    }
+
+    fn lambda_name(&mut self) -> Option<LambdaName<'a>> {
+        let name = self.symbol()?;
+        let captures = self.in_layouts()?;
+
+        Some(LambdaName::with_niche(name, Niche::from_captures(captures)))
+    }
+
+    fn proc_layout(&mut self) -> Option<ProcLayout<'a>> {
+        Some(ProcLayout {
+            arguments: self.in_layouts()?,
+            result: self.in_layout()?,
+            niche: Niche::from_captures(self.in_layouts()?),
+        })
+    }
+
+    fn procs(&mut self) -> Option<SpecializedProcs<'a>> {
+        let len = self.len()?;
+        let mut procs = SpecializedProcs::default();
+
+        for _ in 0..len {
+            let symbol = self.symbol()?;
+            let proc_layout = self.proc_layout()?;
+            let proc = self.proc()?;
+
+            procs.insert((symbol, proc_layout), proc);
+        }
+
+        Some(procs)
+    }
+
+    fn proc(&mut self) -> Option<Proc<'a>> {
+        let name = self.lambda_name()?;
+        let args = self.slice(|r| Some((r.in_layout()?, r.symbol()?)))?;
+        let body = self.stmt()?;
+        let closure_data_layout = self.option_in_layout()?;
+        let ret_layout = self.in_layout()?;
+
+        let is_self_recursive = match self.u8()? {
+            0 => SelfRecursive::NotSelfRecursive,
+            1 => SelfRecursive::SelfRecursive(JoinPointId(self.symbol()?)),
+            _ => return None,
+        };
+
+        let host_exposed_layouts = match self.u8()? {
+            0 => HostExposedLayouts::NotHostExposed,
+            1 => {
+                let mut rigids = BumpMap::default();
+                for _ in 0..self.len()? {
+                    let name = Lowercase::from(self.str()?);
+                    rigids.insert(name, self.in_layout()?);
+                }
+
+                let mut aliases = BumpMap::default();
+                for _ in 0..self.len()? {
+                    let symbol = self.symbol()?;
+                    aliases.insert(symbol, self.host_exposed_lambda_set()?);
+                }
+
+                HostExposedLayouts::HostExposed { rigids, aliases }
+            }
+            _ => return None,
+        };
+
+        Some(Proc {
+            name,
+            args,
+            body,
+            closure_data_layout,
+            ret_layout,
+            is_self_recursive,
+            host_exposed_layouts,
+        })
+    }
+
+    fn host_exposed_lambda_set(&mut self) -> Option<HostExposedLambdaSet<'a>> {
+        let id = LambdaSetId(self.u32()?);
+        let symbol = self.symbol()?;
+        let proc_layout = self.proc_layout()?;
+
+        let raw_function_layout = match self.u8()? {
+            0 => RawFunctionLayout::Function(
+                self.in_layouts()?,
+                self.lambda_set()?,
+                self.in_layout()?,
+            ),
+            1 => RawFunctionLayout::ZeroArgumentThunk(self.in_layout()?),
+            _ => return None,
+        };
+
+        Some(HostExposedLambdaSet {
+            id,
+            symbol,
+            proc_layout,
+            raw_function_layout,
+        })
+    }
+
+    fn stmt_ref(&mut self) -> Option<&'a Stmt<'a>> {
+        let stmt = self.stmt()?;
+        Some(self.arena.alloc(stmt))
+    }
+
+    fn stmt(&mut self) -> Option<Stmt<'a>> {
+        let stmt = match self.u8()? {
+            0 => Stmt::Let(
+                self.symbol()?,
+                self.expr()?,
+                self.in_layout()?,
+                self.stmt_ref()?,
+            ),
+            1 => Stmt::Switch {
+                cond_symbol: self.symbol()?,
+                cond_layout: self.in_layout()?,
+                branches: self.slice(|r| Some((r.u64()?, r.branch_info()?, r.stmt()?)))?,
+                default_branch: (self.branch_info()?, self.stmt_ref()?),
+                branch_hints: self.slice(|r| match r.u8()? {
+                    0 => Some(BranchHint::Likely),
+                    1 => Some(BranchHint::None),
+                    2 => Some(BranchHint::Unlikely),
+                    3 => Some(BranchHint::Cold),
+                    _ => None,
+                })?,
+                ret_layout: self.in_layout()?,
+            },
+            2 => Stmt::Ret(self.symbol()?),
+            3 => {
+                let modify = match self.u8()? {
+                    0 => ModifyRc::Inc(self.symbol()?, self.u64()?),
+                    1 => ModifyRc::Dec(self.symbol()?),
+                    2 => ModifyRc::DecRef(self.symbol()?),
+                    _ => return None,
+                };
+
+                Stmt::Refcounting(modify, self.stmt_ref()?)
+            }
+            tag @ (4 | 5) => {
+                let condition = self.symbol()?;
+                let region = Region::new(Position::new(self.u32()?), Position::new(self.u32()?));
+                let lookups = self.symbols()?;
+                let variables = self.slice(|r| Some(unsafe { Variable::from_index(r.u32()?) }))?;
+                let remainder = self.stmt_ref()?;
+
+                if tag == 4 {
+                    Stmt::Expect {
+                        condition,
+                        region,
+                        lookups,
+                        variables,
+                        remainder,
+                    }
+                } else {
+                    Stmt::ExpectFx {
+                        condition,
+                        region,
+                        lookups,
+                        variables,
+                        remainder,
+                    }
+                }
+            }
+            6 => Stmt::Dbg {
+                symbol: self.symbol()?,
+                variable: unsafe { Variable::from_index(self.u32()?) },
+                remainder: self.stmt_ref()?,
+            },
+            7 => Stmt::Join {
+                id: JoinPointId(self.symbol()?),
+                parameters: self.slice(|r| {
+                    Some(Param {
+                        symbol: r.symbol()?,
+                        ownership: if r.bool()? {
+                            Ownership::Borrowed
+                        } else {
+                            Ownership::Owned
+                        },
+                        layout: r.in_layout()?,
+                    })
+                })?,
+                body: self.stmt_ref()?,
+                remainder: self.stmt_ref()?,
+            },
+            8 => Stmt::Jump(JoinPointId(self.symbol()?), self.symbols()?),
+            9 => Stmt::Crash(self.symbol()?, CrashTag::try_from(self.u32()?).ok()?),
+            _ => return None,
+        };
+
+        Some(stmt)
+    }
+
+    fn branch_info(&mut self) -> Option<BranchInfo<'a>> {
+        match self.u8()? {
+            0 => Some(BranchInfo::None),
+            1 => Some(BranchInfo::Constructor {
+                scrutinee: self.symbol()?,
+                layout: self.in_layout()?,
+                tag_id: self.u16()?,
+            }),
+            _ => None,
+        }
+    }
+
+    fn literal(&mut self) -> Option<Literal<'a>> {
+        let literal = match self.u8()? {
+            0 => Literal::Int(self.array()?),
+            1 => Literal::U128(self.array()?),
+            2 => Literal::Float(f64::from_le_bytes(self.array()?)),
+            3 => Literal::Decimal(self.array()?),
+            4 => Literal::Str(self.str()?),
+            5 => Literal::Bool(self.bool()?),
+            6 => Literal::Byte(self.u8()?),
+            _ => return None,
+        };
+
+        Some(literal)
+    }
+
+    fn list_elements(&mut self) -> Option<&'a [ListLiteralElement<'a>]> {
+        self.slice(|r| match r.u8()? {
+            0 => Some(ListLiteralElement::Literal(r.literal()?)),
+            1 => Some(ListLiteralElement::Symbol(r.symbol()?)),
+            _ => None,
+        })
+    }
+
+    fn update_mode(&mut self) -> Option<UpdateModeId> {
+        Some(UpdateModeId { id: self.u32()? })
+    }
+
+    fn expr(&mut self) -> Option<Expr<'a>> {
+        let expr = match self.u8()? {
+            0 => Expr::Literal(self.literal()?),
+            1 => Expr::Call(self.call()?),
+            2 => Expr::Tag {
+                tag_layout: self.union_layout()?,
+                tag_id: self.u16()?,
+                arguments: self.symbols()?,
+            },
+            3 => Expr::Struct(self.symbols()?),
+            4 => Expr::NullPointer,
+            5 => Expr::StructAtIndex {
+                index: self.u64()?,
+                field_layouts: self.in_layouts()?,
+                structure: self.symbol()?,
+            },
+            6 => Expr::GetTagId {
+                structure: self.symbol()?,
+                union_layout: self.union_layout()?,
+            },
+            7 => Expr::UnionAtIndex {
+                structure: self.symbol()?,
+                tag_id: self.u16()?,
+                union_layout: self.union_layout()?,
+                index: self.u64()?,
+            },
+            8 => Expr::Array {
+                elem_layout: self.in_layout()?,
+                elems: self.list_elements()?,
+            },
+            9 => Expr::StaticArray {
+                elem_layout: self.in_layout()?,
+                elems: self.slice(|r| r.literal())?,
+            },
+            10 => Expr::EmptyArray,
+            11 => Expr::ExprBox {
+                symbol: self.symbol()?,
+            },
+            12 => Expr::ExprUnbox {
+                symbol: self.symbol()?,
+            },
+            13 => Expr::Reuse {
+                symbol: self.symbol()?,
+                update_tag_id: self.bool()?,
+                update_mode: self.update_mode()?,
+                tag_layout: self.union_layout()?,
+                tag_id: self.u16()?,
+                arguments: self.symbols()?,
+            },
+            14 => Expr::Reset {
+                symbol: self.symbol()?,
+                update_mode: self.update_mode()?,
+            },
+            15 => Expr::ResetRef {
+                symbol: self.symbol()?,
+                update_mode: self.update_mode()?,
+            },
+            16 => Expr::RuntimeErrorFunction(self.str()?),
+            _ => return None,
+        };
+
+        Some(expr)
+    }
+
+    fn call(&mut self) -> Option<Call<'a>> {
+        let call_type = match self.u8()? {
+            0 => CallType::ByName {
+                name: self.lambda_name()?,
+                ret_layout: self.in_layout()?,
+                arg_layouts: self.in_layouts()?,
+                specialization_id: CallSpecId { id: self.u32()? },
+            },
+            1 => CallType::Foreign {
+                foreign_symbol: ForeignSymbol::from(self.str()?),
+                ret_layout: self.in_layout()?,
+            },
+            2 => CallType::LowLevel {
+                op: low_level(self.u8()?)?,
+                update_mode: self.update_mode()?,
+            },
+            3 => {
+                let higher_order = self.higher_order()?;
+                CallType::HigherOrder(self.arena.alloc(higher_order))
+            }
+            _ => return None,
+        };
+
+        Some(Call {
+            call_type,
+            arguments: self.symbols()?,
+        })
+    }
+
+    fn higher_order(&mut self) -> Option<HigherOrderLowLevel<'a>> {
+        let tag = self.u8()?;
+        let op = match (tag, self.symbols()?) {
+            (0, [xs]) => HigherOrder::ListMap { xs: *xs },
+            (1, [xs, ys]) => HigherOrder::ListMap2 { xs: *xs, ys: *ys },
+            (2, [xs, ys, zs]) => HigherOrder::ListMap3 {
+                xs: *xs,
+                ys: *ys,
+                zs: *zs,
+            },
+            (3, [xs, ys, zs, ws]) => HigherOrder::ListMap4 {
+                xs: *xs,
+                ys: *ys,
+                zs: *zs,
+                ws: *ws,
+            },
+            (4, [xs]) => HigherOrder::ListSortWith { xs: *xs },
+            _ => return None,
+        };
+
+        Some(HigherOrderLowLevel {
+            op,
+            closure_env_layout: self.option_in_layout()?,
+            update_mode: self.update_mode()?,
+            passed_function: PassedFunction {
+                name: self.lambda_name()?,
+                argument_layouts: self.in_layouts()?,
+                return_layout: self.in_layout()?,
+                specialization_id: CallSpecId { id: self.u32()? },
+                captured_environment: self.symbol()?,
+                owns_captured_environment: self.bool()?,
+            },
+        })
+    }
+}
+
+fn int_width(value: u8) -> Option<IntWidth> {
+    use IntWidth::*;
+
+    [U8, U16, U32, U64, U128, I8, I16, I32, I64, I128]
+        .into_iter()
+        .find(|width| *width as u8 == value)
+}
+
+fn low_level(value: u8) -> Option<LowLevel> {
+    // LowLevel is repr(u8), without explicit discriminants, and Unreachable is its last variant
+    (value <= LowLevel::Unreachable as u8)
+        .then(|| unsafe { std::mem::transmute::<u8, LowLevel>(value) })
+}
+
+#[cfg(test)]
+mod test {
+    use bumpalo::Bump;
+    use roc_module::low_level::LowLevel;
+    use roc_module::symbol::Symbol;
+    use roc_target::TargetInfo;
+
+    use super::{deserialize_procs, serialize_procs, SpecializedProcs};
+    use crate::ir::{
+        Call, CallType, Expr, HostExposedLayouts, Literal, Proc, ProcLayout, SelfRecursive, Stmt,
+        UpdateModeId,
+    };
+    use crate::layout::{
+        Builtin, InLayout, LambdaName, Layout, LayoutInterner, Niche, STLayoutInterner, UnionLayout,
+    };
+
+    const TARGET_INFO: TargetInfo = TargetInfo::default_x86_64();
+
+    /// `len : ConsList -> U64`, where `ConsList : [Nil, Cons I64 ConsList]`
+    fn cons_list_procs<'a>(
+        arena: &'a Bump,
+    ) -> (STLayoutInterner<'a>, SpecializedProcs<'a>, InLayout<'a>) {
+        let mut interner = STLayoutInterner::with_capacity(4, TARGET_INFO);
+
+        let cons_list = interner.insert_recursive(
+            arena,
+            Layout::Union(UnionLayout::NullableUnwrapped {
+                nullable_id: true,
+                other_fields: arena.alloc([Layout::I64, Layout::NAKED_RECURSIVE_PTR]),
+            }),
+        );
+        let list = interner.insert(Layout::Builtin(Builtin::List(cons_list)));
+
+        let name = Symbol::NUM_ADD;
+        let x = Symbol::LIST_LEN;
+        let len = Symbol::LIST_GET;
+
+        let body = Stmt::Let(
+            len,
+            Expr::Call(Call {
+                call_type: CallType::LowLevel {
+                    op: LowLevel::ListLen,
+                    update_mode: UpdateModeId::BACKEND_DUMMY,
+                },
+                arguments: arena.alloc([x]),
+            }),
+            Layout::U64,
+            arena.alloc(Stmt::Let(
+                name,
+                Expr::Literal(Literal::Str("a string")),
+                Layout::STR,
+                arena.alloc(Stmt::Ret(len)),
+            )),
+        );
+
+        let arguments: &[InLayout] = arena.alloc([list]);
+        let proc_layout = ProcLayout {
+            arguments,
+            result: Layout::U64,
+            niche: Niche::NONE,
+        };
+
+        let proc = Proc {
+            name: LambdaName::no_niche(name),
+            args: arena.alloc([(list, x)]),
+            body,
+            closure_data_layout: None,
+            ret_layout: Layout::U64,
+            is_self_recursive: SelfRecursive::NotSelfRecursive,
+            host_exposed_layouts: HostExposedLayouts::NotHostExposed,
+        };
+
+        let mut procs = SpecializedProcs::default();
+        procs.insert((name, proc_layout), proc);
+
+        (interner, procs, cons_list)
+    }
+
+    #[test]
+    fn procs_round_trip() {
+        let arena = &Bump::new();
+        let (interner, procs, cons_list) = cons_list_procs(arena);
+
+        let mut bytes = Vec::new();
+        let written = serialize_procs(&interner, &procs, &mut bytes).unwrap();
+        assert_eq!(written, bytes.len());
+
+        let ((new_interner, new_procs), read) =
+            deserialize_procs(arena, TARGET_INFO, &bytes).unwrap();
+
+        assert_eq!(read, bytes.len());
+        assert_eq!(new_procs, procs);
+        assert_eq!(new_interner.layouts(), interner.layouts());
+
+        // the recursive union is still found under its normalized form
+        let mut new_interner = new_interner;
+        let again = new_interner.insert_recursive(
+            arena,
+            Layout::Union(UnionLayout::NullableUnwrapped {
+                nullable_id: true,
+                other_fields: arena.alloc([Layout::I64, Layout::NAKED_RECURSIVE_PTR]),
+            }),
+        );
+        assert_eq!(again, cons_list);
+    }
+
+    #[test]
+    fn bad_bytes_are_rejected() {
+        let arena = &Bump::new();
+        let (interner, procs, _) = cons_list_procs(arena);
+
+        let mut bytes = Vec::new();
+        serialize_procs(&interner, &procs, &mut bytes).unwrap();
+
+        for len in 0..bytes.len() {
+            assert!(
+                deserialize_procs(arena, TARGET_INFO, &bytes[..len]).is_none(),
+                "{len} of {} bytes were accepted",
+                bytes.len()
+            );
+        }
+
+        let mut other_version = bytes.clone();
+        other_version[0] += 1;
+        assert!(deserialize_procs(arena, TARGET_INFO, &other_version).is_none());
+
+        // a corrupted byte may still decode to some procedures, but must never crash the reader
+        for index in 0..bytes.len() {
+            for byte in [0x00, 0x7f, 0x80, 0xff] {
+                let mut corrupted = bytes.clone();
+                corrupted[index] = byte;
+
+                let _ = deserialize_procs(arena, TARGET_INFO, &corrupted);
+            }
+        }
+    }
+}
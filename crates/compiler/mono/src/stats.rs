@@ -0,0 +1,176 @@
+//! Statistics about making specializations: how much memory it took, how many expressions of each
+//! kind it made and how well the layout cache worked. `roc build --time` prints them, and
+//! benchmarks can track them to catch regressions in how much memory the compiler uses.
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use bumpalo::Bump;
+
+use crate::ir::{Expr, Proc, Stmt};
+use crate::layout::LayoutCache;
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct MonoStats {
+    /// Bytes allocated in the arenas while making specializations
+    pub arena_bytes: usize,
+    /// Number of specialized procedures
+    pub specializations: usize,
+    /// Number of expressions in the bodies of the specialized procedures, by kind of expression
+    pub exprs: BTreeMap<&'static str, usize>,
+    /// Like [crate::layout::CacheStatistics], these are only counted in debug builds
+    pub layout_cache_hits: u64,
+    pub layout_cache_misses: u64,
+    /// Copies of the [roc_types::subs::Subs] of a module, made for the parts of its
+    /// specializations that are made in parallel, and the time spent making them
+    pub subs_copies: usize,
+    pub subs_copy_time: Duration,
+}
+
+impl MonoStats {
+    /// Bytes allocated in `arena` since `arena_bytes_before`
+    pub fn add_arena(&mut self, arena: &Bump, arena_bytes_before: usize) {
+        self.arena_bytes += arena.allocated_bytes() - arena_bytes_before;
+    }
+
+    pub fn add_subs_copy(&mut self, copy_time: Duration) {
+        self.subs_copies += 1;
+        self.subs_copy_time += copy_time;
+    }
+
+    pub fn add_procs<'a, 'p>(&mut self, procs: impl IntoIterator<Item = &'p Proc<'a>>)
+    where
+        'a: 'p,
+    {
+        for proc in procs {
+            self.specializations += 1;
+
+            let mut stack = vec![&proc.body];
+
+            while let Some(stmt) = stack.pop() {
+                match stmt {
+                    Stmt::Let(_, expr, _, cont) => {
+                        *self.exprs.entry(expr_kind(expr)).or_default() += 1;
+                        stack.push(*cont);
+                    }
+                    Stmt::Switch {
+                        branches,
+                        default_branch,
+                        ..
+                    } => {
+                        stack.extend(branches.iter().map(|(_, _, branch)| branch));
+                        stack.push(default_branch.1);
+                    }
+                    Stmt::Refcounting(_, cont)
+                    | Stmt::Expect {
+                        remainder: cont, ..
+                    }
+                    | Stmt::ExpectFx {
+                        remainder: cont, ..
+                    }
+                    | Stmt::Dbg {
+                        remainder: cont, ..
+                    } => stack.push(*cont),
+                    Stmt::Join {
+                        body, remainder, ..
+                    } => {
+                        stack.push(*body);
+                        stack.push(*remainder);
+                    }
+                    Stmt::Ret(_) | Stmt::Jump(_, _) | Stmt::Crash(_, _) => {}
+                }
+            }
+        }
+    }
+
+    #[allow(unused_variables)]
+    pub fn add_layout_cache(&mut self, layout_cache: &LayoutCache) {
+        #[cfg(debug_assertions)]
+        {
+            let (stats, raw_function_stats) = layout_cache.statistics();
+
+            self.layout_cache_hits += stats.hits + raw_function_stats.hits;
+            self.layout_cache_misses += stats.misses + raw_function_stats.misses;
+        }
+    }
+
+    pub fn merge(&mut self, other: MonoStats) {
+        self.arena_bytes += other.arena_bytes;
+        self.specializations += other.specializations;
+        self.layout_cache_hits += other.layout_cache_hits;
+        self.layout_cache_misses += other.layout_cache_misses;
+        self.subs_copies += other.subs_copies;
+        self.subs_copy_time += other.subs_copy_time;
+
+        for (kind, count) in other.exprs {
+            *self.exprs.entry(kind).or_default() += count;
+        }
+    }
+
+    pub fn total_exprs(&self) -> usize {
+        self.exprs.values().sum()
+    }
+
+    /// The fraction of layout lookups that were cached, if there were any
+    pub fn layout_cache_hit_rate(&self) -> Option<f64> {
+        let lookups = self.layout_cache_hits + self.layout_cache_misses;
+
+        if lookups == 0 {
+            None
+        } else {
+            Some(self.layout_cache_hits as f64 / lookups as f64)
+        }
+    }
+}
+
+impl std::fmt::Display for MonoStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "        {:9.3} MB   Arenas",
+            self.arena_bytes as f64 / 1e6
+        )?;
+        writeln!(f, "        {:9}      Specializations", self.specializations)?;
+        writeln!(f, "        {:9}      Expressions", self.total_exprs())?;
+
+        for (kind, count) in &self.exprs {
+            writeln!(f, "        {:9}        {}", count, kind)?;
+        }
+
+        if let Some(hit_rate) = self.layout_cache_hit_rate() {
+            writeln!(f, "        {:9.1} %    Layout cache hits", hit_rate * 100.0)?;
+        }
+
+        if self.subs_copies > 0 {
+            writeln!(
+                f,
+                "        {:9.3} ms   Copying Subs ({} copies)",
+                self.subs_copy_time.as_secs_f64() * 1000.0,
+                self.subs_copies
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+fn expr_kind(expr: &Expr<'_>) -> &'static str {
+    match expr {
+        Expr::Literal(_) => "Literal",
+        Expr::Call(_) => "Call",
+        Expr::Tag { .. } => "Tag",
+        Expr::Struct(_) => "Struct",
+        Expr::NullPointer => "NullPointer",
+        Expr::StructAtIndex { .. } => "StructAtIndex",
+        Expr::GetTagId { .. } => "GetTagId",
+        Expr::UnionAtIndex { .. } => "UnionAtIndex",
+        Expr::Array { .. } => "Array",
+        Expr::StaticArray { .. } => "StaticArray",
+        Expr::EmptyArray => "EmptyArray",
+        Expr::ExprBox { .. } => "ExprBox",
+        Expr::ExprUnbox { .. } => "ExprUnbox",
+        Expr::Reuse { .. } => "Reuse",
+        Expr::Reset { .. } => "Reset",
+        Expr::ResetRef { .. } => "ResetRef",
+        Expr::RuntimeErrorFunction(_) => "RuntimeErrorFunction",
+    }
+}
@@ -68,6 +68,59 @@ pub fn make_tail_recursive<'a>(
     Some(join)
 }
 
+/// Whether `stmt` has a call to `needle` that `make_tail_recursive` would turn into a jump.
+///
+/// Canonicalization only marks defs that refer to themselves by name as self-recursive, but a
+/// specialization can also end up calling itself in other ways, e.g. an ability implementation
+/// that calls the ability member it implements.
+pub fn has_self_tail_call<'a>(
+    needle: LambdaName,
+    stmt: &Stmt<'a>,
+    needle_arguments: &[InLayout<'a>],
+    needle_result: InLayout<'a>,
+) -> bool {
+    use Stmt::*;
+
+    let recurse = |stmt| has_self_tail_call(needle, stmt, needle_arguments, needle_result);
+
+    match stmt {
+        Let(
+            symbol,
+            Expr::Call(crate::ir::Call {
+                call_type:
+                    CallType::ByName {
+                        name,
+                        ret_layout,
+                        arg_layouts,
+                        ..
+                    },
+                ..
+            }),
+            _,
+            Stmt::Ret(rsym),
+        ) if symbol == rsym
+            && *name == needle
+            && *arg_layouts == needle_arguments
+            && *ret_layout == needle_result =>
+        {
+            true
+        }
+        Let(_, _, _, cont) | Refcounting(_, cont) => recurse(cont),
+        Dbg { remainder, .. } | Expect { remainder, .. } | ExpectFx { remainder, .. } => {
+            recurse(remainder)
+        }
+        Join {
+            remainder, body, ..
+        } => recurse(remainder) || recurse(body),
+        Switch {
+            branches,
+            default_branch,
+            ..
+        } => branches.iter().any(|(_, _, branch)| recurse(branch)) || recurse(default_branch.1),
+        Ret(_) | Jump(_, _) | Crash(..) => false,
+    }
+}
+
 fn insert_jumps<'a>(
     arena: &'a Bump,
     stmt: &'a Stmt<'a>,
@@ -170,6 +223,7 @@ fn insert_jumps<'a>(
             cond_layout,
             branches,
             default_branch,
+            branch_hints,
             ret_layout,
         } => {
             let opt_default = insert_jumps(
@@ -230,6 +284,7 @@ fn insert_jumps<'a>(
                     cond_layout: *cond_layout,
                     default_branch,
                     branches,
+                    branch_hints,
                     ret_layout: *ret_layout,
                 }))
             } else {
@@ -0,0 +1,447 @@
+//! An index from byte offsets to the nodes of a parse tree, for hit-testing in the editor,
+//! hover and anchoring quick fixes. It is built from the regions the parser recorded, so it works
+//! on code that was just parsed, before canonicalization.
+use roc_region::all::{Loc, Region};
+
+use crate::ast::{
+    AssignedField, Defs, Expr, Pattern, StrLiteral, StrSegment, TypeAnnotation, TypeDef, ValueDef,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Node<'a> {
+    TypeDef(&'a TypeDef<'a>),
+    ValueDef(&'a ValueDef<'a>),
+    Expr(&'a Expr<'a>),
+    /// A field of a record literal or record update, e.g. `x: 1` in `{ x: 1 }`
+    Field(&'a AssignedField<'a, Expr<'a>>),
+    Pattern(&'a Pattern<'a>),
+    /// Type annotations are not split up any further
+    TypeAnnotation(&'a TypeAnnotation<'a>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IndexedNode<'a> {
+    pub region: Region,
+    pub node: Node<'a>,
+    parent: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LocationIndex<'a> {
+    /// Parents come before their children
+    nodes: Vec<IndexedNode<'a>>,
+    /// Indices into `nodes`, sorted by where the node starts
+    by_start: Vec<usize>,
+}
+
+impl<'a> LocationIndex<'a> {
+    pub fn from_defs(defs: &'a Defs<'a>) -> Self {
+        let mut index = Self::default();
+        index.add_defs(defs, None);
+        index.sort();
+
+        index
+    }
+
+    pub fn from_expr(loc_expr: &'a Loc<Expr<'a>>) -> Self {
+        let mut index = Self::default();
+        index.add_expr(loc_expr.region, &loc_expr.value, None);
+        index.sort();
+
+        index
+    }
+
+    /// The innermost node whose region contains `offset`. Regions include their first byte, but
+    /// not the byte after their end.
+    pub fn node_at(&self, offset: u32) -> Option<&IndexedNode<'a>> {
+        self.path_at(offset).into_iter().next()
+    }
+
+    /// The innermost node at `offset`, followed by its ancestors up to the def it is in
+    pub fn path_at(&self, offset: u32) -> Vec<&IndexedNode<'a>> {
+        // nodes never partially overlap, so the innermost node at `offset` is either the last node
+        // that starts at or before it, or one of that node's ancestors
+        let candidate_count = self
+            .by_start
+            .partition_point(|index| self.nodes[*index].region.start().offset <= offset);
+
+        let mut index_opt = match candidate_count {
+            0 => None,
+            _ => Some(self.by_start[candidate_count - 1]),
+        };
+
+        while let Some(index) = index_opt {
+            let node = &self.nodes[index];
+
+            if contains(node.region, offset) {
+                break;
+            }
+
+            index_opt = node.parent;
+        }
+
+        let mut path = Vec::new();
+
+        while let Some(index) = index_opt {
+            let node = &self.nodes[index];
+            path.push(node);
+            index_opt = node.parent;
+        }
+
+        path
+    }
+
+    fn sort(&mut self) {
+        self.by_start = (0..self.nodes.len()).collect();
+
+        // stable, so a parent stays before a child that starts at the same offset
+        self.by_start
+            .sort_by_key(|index| self.nodes[*index].region.start().offset);
+    }
+
+    fn push(&mut self, region: Region, node: Node<'a>, parent: Option<usize>) -> Option<usize> {
+        self.nodes.push(IndexedNode {
+            region,
+            node,
+            parent,
+        });
+
+        Some(self.nodes.len() - 1)
+    }
+
+    fn add_defs(&mut self, defs: &'a Defs<'a>, parent: Option<usize>) {
+        for (def, region) in defs.defs().zip(defs.regions.iter()) {
+            match def {
+                Ok(type_def) => {
+                    let id = self.push(*region, Node::TypeDef(type_def), parent);
+
+                    match type_def {
+                        TypeDef::Alias { ann, .. } => self.add_type_annotation(ann, id),
+                        TypeDef::Opaque { typ, .. } => self.add_type_annotation(typ, id),
+                        TypeDef::Ability { members, .. } => {
+                            for member in members.iter() {
+                                self.add_type_annotation(&member.typ, id);
+                            }
+                        }
+                    }
+                }
+                Err(value_def) => {
+                    let id = self.push(*region, Node::ValueDef(value_def), parent);
+
+                    match value_def {
+                        ValueDef::Annotation(loc_pattern, loc_ann) => {
+                            self.add_pattern(loc_pattern, id);
+                            self.add_type_annotation(loc_ann, id);
+                        }
+                        ValueDef::Body(loc_pattern, loc_expr) => {
+                            self.add_pattern(loc_pattern, id);
+                            self.add_expr(loc_expr.region, &loc_expr.value, id);
+                        }
+                        ValueDef::AnnotatedBody {
+                            ann_pattern,
+                            ann_type,
+                            body_pattern,
+                            body_expr,
+                            ..
+                        } => {
+                            self.add_pattern(ann_pattern, id);
+                            self.add_type_annotation(ann_type, id);
+                            self.add_pattern(body_pattern, id);
+                            self.add_expr(body_expr.region, &body_expr.value, id);
+                        }
+                        ValueDef::Dbg { condition, .. }
+                        | ValueDef::Expect { condition, .. }
+                        | ValueDef::ExpectFx { condition, .. } => {
+                            self.add_expr(condition.region, &condition.value, id);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn add_expr(&mut self, region: Region, expr: &'a Expr<'a>, parent: Option<usize>) {
+        match expr {
+            // the spaces are part of the region of the expression they are around
+            Expr::SpaceBefore(inner, _) | Expr::SpaceAfter(inner, _) => {
+                self.add_expr(region, inner, parent)
+            }
+            _ => {
+                let id = self.push(region, Node::Expr(expr), parent);
+                self.add_sub_exprs(expr, id);
+            }
+        }
+    }
+
+    fn add_loc_expr(&mut self, loc_expr: &'a Loc<Expr<'a>>, parent: Option<usize>) {
+        self.add_expr(loc_expr.region, &loc_expr.value, parent);
+    }
+
+    fn add_sub_exprs(&mut self, expr: &'a Expr<'a>, id: Option<usize>) {
+        match expr {
+            Expr::Str(str_literal) => self.add_str_literal(str_literal, id),
+            Expr::RecordAccess(inner, _)
+            | Expr::TupleAccess(inner, _)
+            | Expr::SpaceBefore(inner, _)
+            | Expr::SpaceAfter(inner, _)
+            | Expr::ParensAround(inner) => {
+                // these have no region of their own, they start where the outer expression starts
+                self.add_sub_exprs(inner, id);
+            }
+            Expr::List(items) | Expr::Tuple(items) => {
+                for item in items.iter() {
+                    self.add_loc_expr(item, id);
+                }
+            }
+            Expr::RecordUpdate { update, fields } => {
+                self.add_loc_expr(update, id);
+                self.add_fields(fields.items, id);
+            }
+            Expr::Record(fields) => self.add_fields(fields.items, id),
+            Expr::IngestedFile(_, loc_ann) => self.add_type_annotation(loc_ann, id),
+            Expr::Closure(loc_patterns, loc_body) => {
+                for loc_pattern in loc_patterns.iter() {
+                    self.add_pattern(loc_pattern, id);
+                }
+
+                self.add_loc_expr(loc_body, id);
+            }
+            Expr::Defs(defs, loc_ret) => {
+                self.add_defs(defs, id);
+                self.add_loc_expr(loc_ret, id);
+            }
+            Expr::Backpassing(loc_patterns, loc_call, loc_ret) => {
+                for loc_pattern in loc_patterns.iter() {
+                    self.add_pattern(loc_pattern, id);
+                }
+
+                self.add_loc_expr(loc_call, id);
+                self.add_loc_expr(loc_ret, id);
+            }
+            Expr::Expect(condition, continuation) | Expr::Dbg(condition, continuation) => {
+                self.add_loc_expr(condition, id);
+                self.add_loc_expr(continuation, id);
+            }
+            Expr::Apply(loc_fn, loc_args, _) => {
+                self.add_loc_expr(loc_fn, id);
+
+                for loc_arg in loc_args.iter() {
+                    self.add_loc_expr(loc_arg, id);
+                }
+            }
+            Expr::BinOps(lefts, loc_right) => {
+                for (loc_left, _) in lefts.iter() {
+                    self.add_loc_expr(loc_left, id);
+                }
+
+                self.add_loc_expr(loc_right, id);
+            }
+            Expr::UnaryOp(loc_operand, _) => self.add_loc_expr(loc_operand, id),
+            Expr::If(branches, loc_final_else) => {
+                for (loc_condition, loc_then) in branches.iter() {
+                    self.add_loc_expr(loc_condition, id);
+                    self.add_loc_expr(loc_then, id);
+                }
+
+                self.add_loc_expr(loc_final_else, id);
+            }
+            Expr::When(loc_condition, branches) => {
+                self.add_loc_expr(loc_condition, id);
+
+                for branch in branches.iter() {
+                    for loc_pattern in branch.patterns.iter() {
+                        self.add_pattern(loc_pattern, id);
+                    }
+
+                    if let Some(loc_guard) = &branch.guard {
+                        self.add_loc_expr(loc_guard, id);
+                    }
+
+                    self.add_loc_expr(&branch.value, id);
+                }
+            }
+            Expr::PrecedenceConflict(conflict) => self.add_loc_expr(conflict.expr, id),
+            Expr::Float(_)
+            | Expr::Num(_)
+            | Expr::NonBase10Int { .. }
+            | Expr::SingleQuote(_)
+            | Expr::AccessorFunction(_)
+            | Expr::Var { .. }
+            | Expr::Underscore(_)
+            | Expr::Crash
+            | Expr::Tag(_)
+            | Expr::OpaqueRef(_)
+            | Expr::MalformedIdent(_, _)
+            | Expr::MalformedClosure => {}
+        }
+    }
+
+    fn add_str_literal(&mut self, str_literal: &'a StrLiteral<'a>, id: Option<usize>) {
+        let add_segments = |index: &mut Self, segments: &'a [StrSegment<'a>]| {
+            for segment in segments.iter() {
+                if let StrSegment::Interpolated(loc_expr) = segment {
+                    index.add_expr(loc_expr.region, loc_expr.value, id);
+                }
+            }
+        };
+
+        match str_literal {
+            StrLiteral::PlainLine(_) => {}
+            StrLiteral::Line(segments) => add_segments(self, segments),
+            StrLiteral::Block(lines) => {
+                for segments in lines.iter() {
+                    add_segments(self, segments);
+                }
+            }
+        }
+    }
+
+    fn add_fields(&mut self, fields: &'a [Loc<AssignedField<'a, Expr<'a>>>], id: Option<usize>) {
+        for loc_field in fields.iter() {
+            let mut field = &loc_field.value;
+
+            while let AssignedField::SpaceBefore(inner, _) | AssignedField::SpaceAfter(inner, _) =
+                field
+            {
+                field = inner;
+            }
+
+            let field_id = self.push(loc_field.region, Node::Field(field), id);
+
+            match field {
+                AssignedField::RequiredValue(_, _, loc_value)
+                | AssignedField::OptionalValue(_, _, loc_value) => {
+                    self.add_loc_expr(loc_value, field_id)
+                }
+                AssignedField::LabelOnly(_)
+                | AssignedField::SpaceBefore(_, _)
+                | AssignedField::SpaceAfter(_, _)
+                | AssignedField::Malformed(_) => {}
+            }
+        }
+    }
+
+    fn add_pattern(&mut self, loc_pattern: &'a Loc<Pattern<'a>>, parent: Option<usize>) {
+        let mut pattern = &loc_pattern.value;
+
+        while let Pattern::SpaceBefore(inner, _) | Pattern::SpaceAfter(inner, _) = pattern {
+            pattern = inner;
+        }
+
+        let id = self.push(loc_pattern.region, Node::Pattern(pattern), parent);
+
+        match pattern {
+            Pattern::Apply(loc_tag, loc_args) => {
+                self.add_pattern(loc_tag, id);
+
+                for loc_arg in loc_args.iter() {
+                    self.add_pattern(loc_arg, id);
+                }
+            }
+            Pattern::RecordDestructure(loc_patterns)
+            | Pattern::Tuple(loc_patterns)
+            | Pattern::List(loc_patterns) => {
+                for loc_pattern in loc_patterns.iter() {
+                    self.add_pattern(loc_pattern, id);
+                }
+            }
+            Pattern::RequiredField(_, loc_pattern) | Pattern::As(loc_pattern, _) => {
+                self.add_pattern(loc_pattern, id)
+            }
+            Pattern::OptionalField(_, loc_default) => self.add_loc_expr(loc_default, id),
+            Pattern::Identifier(_)
+            | Pattern::Tag(_)
+            | Pattern::OpaqueRef(_)
+            | Pattern::NumLiteral(_)
+            | Pattern::NonBase10Literal { .. }
+            | Pattern::FloatLiteral(_)
+            | Pattern::StrLiteral(_)
+            | Pattern::Underscore(_)
+            | Pattern::SingleQuote(_)
+            | Pattern::ListRest(_)
+            | Pattern::SpaceBefore(_, _)
+            | Pattern::SpaceAfter(_, _)
+            | Pattern::Malformed(_)
+            | Pattern::MalformedIdent(_, _)
+            | Pattern::QualifiedIdentifier { .. } => {}
+        }
+    }
+
+    fn add_type_annotation(&mut self, loc_ann: &'a Loc<TypeAnnotation<'a>>, parent: Option<usize>) {
+        self.push(loc_ann.region, Node::TypeAnnotation(&loc_ann.value), parent);
+    }
+}
+
+fn contains(region: Region, offset: u32) -> bool {
+    region.start().offset <= offset && offset < region.end().offset
+}
+
+#[cfg(test)]
+mod tests {
+    use bumpalo::Bump;
+
+    use super::*;
+    use crate::test_helpers::parse_defs_with;
+
+    fn path_names<'a>(src: &'a str, index: &LocationIndex<'a>, offset: u32) -> Vec<&'a str> {
+        index
+            .path_at(offset)
+            .iter()
+            .map(|indexed| {
+                let start = indexed.region.start().offset as usize;
+                let end = indexed.region.end().offset as usize;
+
+                &src[start..end]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn innermost_node_and_ancestors() {
+        let arena = Bump::new();
+        let src = "main = \\x -> List.map [x, 2] inc\n";
+        let defs = arena.alloc(parse_defs_with(&arena, src).unwrap());
+        let index = LocationIndex::from_defs(defs);
+
+        let offset = src.find("2]").unwrap() as u32;
+
+        assert_eq!(
+            path_names(src, &index, offset),
+            vec![
+                "2",
+                "[x, 2]",
+                "List.map [x, 2] inc",
+                "\\x -> List.map [x, 2] inc",
+                "main = \\x -> List.map [x, 2] inc",
+            ]
+        );
+        assert!(matches!(
+            index.node_at(offset).map(|indexed| indexed.node),
+            Some(Node::Expr(Expr::Num("2")))
+        ));
+    }
+
+    #[test]
+    fn patterns_and_gaps() {
+        let arena = Bump::new();
+        let src = "f = \\{ a, b } -> a\n\ng = 1\n";
+        let defs = arena.alloc(parse_defs_with(&arena, src).unwrap());
+        let index = LocationIndex::from_defs(defs);
+
+        let offset = src.find("b }").unwrap() as u32;
+
+        assert!(matches!(
+            index.node_at(offset).map(|indexed| indexed.node),
+            Some(Node::Pattern(Pattern::Identifier("b")))
+        ));
+
+        // the blank line between the defs is not part of either one
+        let gap = src.find("\n\n").unwrap() as u32 + 1;
+        assert_eq!(index.path_at(gap), Vec::<&IndexedNode>::new());
+
+        assert_eq!(
+            path_names(src, &index, src.find('1').unwrap() as u32),
+            vec!["1", "g = 1"]
+        );
+    }
+}
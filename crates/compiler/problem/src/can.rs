@@ -38,6 +38,8 @@ pub enum Problem {
     UnusedDef(Symbol, Region),
     UnusedImport(Symbol, Region),
     UnusedModuleImport(ModuleId, Region),
+    /// Exposed by an interface module, but no other module uses it
+    UnusedExposedValue(Symbol, Region),
     ExposedButNotDefined(Symbol),
     UnknownGeneratesWith(Loc<Ident>),
     /// First symbol is the name of the closure with that argument
@@ -80,6 +82,12 @@ pub enum Problem {
         field_region: Region,
         replaced_region: Region,
     },
+    DuplicateRecordFieldPattern {
+        field_name: Lowercase,
+        record_region: Region,
+        field_region: Region,
+        first_region: Region,
+    },
     InvalidOptionalValue {
         field_name: Lowercase,
         record_region: Region,
@@ -104,6 +112,13 @@ pub enum Problem {
     InvalidInterpolation(Region),
     InvalidHexadecimal(Region),
     InvalidUnicodeCodePt(Region),
+    /// A string literal of more than `MAX_STR_LITERAL_BYTES` bytes, or a list literal of more
+    /// than `MAX_LIST_LITERAL_ELEMENTS` elements
+    LiteralTooLarge {
+        region: Region,
+        kind: LargeLiteralKind,
+        size: usize,
+    },
     NestedDatatype {
         alias: Symbol,
         def_region: Region,
@@ -221,6 +236,7 @@ impl Problem {
             Problem::UnusedDef(_, _) => Warning,
             Problem::UnusedImport(_, _) => Warning,
             Problem::UnusedModuleImport(_, _) => Warning,
+            Problem::UnusedExposedValue(_, _) => Warning,
             Problem::ExposedButNotDefined(_) => RuntimeError,
             Problem::UnknownGeneratesWith(_) => RuntimeError,
             Problem::UnusedArgument(_, _, _, _) => Warning,
@@ -234,6 +250,7 @@ impl Problem {
             Problem::UnboundTypeVariable { .. } => RuntimeError,
             Problem::DuplicateRecordFieldValue { .. } => Warning,
             Problem::DuplicateRecordFieldType { .. } => RuntimeError,
+            Problem::DuplicateRecordFieldPattern { .. } => Warning,
             Problem::InvalidOptionalValue { .. } => RuntimeError,
             Problem::DuplicateTag { .. } => RuntimeError,
             Problem::RuntimeError(_) => RuntimeError,
@@ -242,6 +259,7 @@ impl Problem {
             Problem::InvalidInterpolation(_) => RuntimeError,
             Problem::InvalidHexadecimal(_) => RuntimeError,
             Problem::InvalidUnicodeCodePt(_) => RuntimeError,
+            Problem::LiteralTooLarge { .. } => RuntimeError,
             Problem::NestedDatatype { .. } => RuntimeError,
             Problem::InvalidExtensionType { .. } => RuntimeError,
             Problem::AbilityHasTypeVariables { .. } => RuntimeError,
@@ -295,6 +313,7 @@ impl Problem {
             }
             | Problem::UnusedImport(_, region)
             | Problem::UnusedModuleImport(_, region)
+            | Problem::UnusedExposedValue(_, region)
             | Problem::UnknownGeneratesWith(Loc { region, .. })
             | Problem::UnusedArgument(_, _, _, region)
             | Problem::UnusedBranchDef(_, region)
@@ -317,6 +336,10 @@ impl Problem {
                 record_region: region,
                 ..
             }
+            | Problem::DuplicateRecordFieldPattern {
+                record_region: region,
+                ..
+            }
             | Problem::InvalidOptionalValue {
                 record_region: region,
                 ..
@@ -358,6 +381,7 @@ impl Problem {
             | Problem::RuntimeError(RuntimeError::InvalidInterpolation(region))
             | Problem::RuntimeError(RuntimeError::InvalidHexadecimal(region))
             | Problem::RuntimeError(RuntimeError::InvalidUnicodeCodePt(region))
+            | Problem::RuntimeError(RuntimeError::LiteralTooLarge(_, region))
             | Problem::RuntimeError(RuntimeError::EmptySingleQuote(region))
             | Problem::RuntimeError(RuntimeError::MultipleCharsInSingleQuote(region))
             | Problem::RuntimeError(RuntimeError::DegenerateBranch(region))
@@ -365,6 +389,7 @@ impl Problem {
             | Problem::InvalidInterpolation(region)
             | Problem::InvalidHexadecimal(region)
             | Problem::InvalidUnicodeCodePt(region)
+            | Problem::LiteralTooLarge { region, .. }
             | Problem::NestedDatatype {
                 def_region: region, ..
             }
@@ -428,6 +453,19 @@ impl Problem {
     }
 }
 
+/// The largest string literal, in bytes, that can be compiled. Bigger data is better read from
+/// a file when the program runs than stored in the binary.
+pub const MAX_STR_LITERAL_BYTES: usize = 16 * 1024 * 1024;
+
+/// The largest list literal, in elements, that can be compiled.
+pub const MAX_LIST_LITERAL_ELEMENTS: usize = 1024 * 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LargeLiteralKind {
+    Str,
+    List,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ExtensionTypeKind {
     Record,
@@ -570,6 +608,7 @@ pub enum RuntimeError {
     InvalidInterpolation(Region),
     InvalidHexadecimal(Region),
     InvalidUnicodeCodePt(Region),
+    LiteralTooLarge(LargeLiteralKind, Region),
 
     /// When the author specifies a type annotation but no implementation
     NoImplementationNamed {
@@ -3,6 +3,7 @@
 // See github.com/roc-lang/roc/issues/800 for discussion of the large_enum_variant check.
 #![allow(clippy::large_enum_variant)]
 pub mod can;
+pub mod cancel;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Severity {
@@ -1,4 +1,4 @@
-use crate::solve::{self, Aliases};
+use crate::solve::{self, Aliases, SolveFuel};
 use roc_can::abilities::{AbilitiesStore, ResolvedImpl};
 use roc_can::constraint::{Constraint as ConstraintSoa, Constraints};
 use roc_can::expr::PendingDerives;
@@ -8,6 +8,7 @@ use roc_collections::VecMap;
 use roc_derive::SharedDerivedModule;
 use roc_error_macros::internal_error;
 use roc_module::symbol::{ModuleId, Symbol};
+use roc_problem::cancel::{CancelToken, Cancelled};
 use roc_solve_problem::TypeError;
 use roc_types::subs::{Content, ExposedTypesStorageSubs, FlatType, StorageSubs, Subs, Variable};
 use roc_types::types::{Alias, MemberImpl, Types};
@@ -66,7 +67,9 @@ pub fn run_solve(
     pending_derives: PendingDerives,
     exposed_by_module: &ExposedByModule,
     derived_module: SharedDerivedModule,
-) -> (Solved<Subs>, solve::Env, Vec<TypeError>, AbilitiesStore) {
+    cancel: &CancelToken,
+    fuel: SolveFuel,
+) -> Result<(Solved<Subs>, solve::Env, Vec<TypeError>, AbilitiesStore), Cancelled> {
     for (var, name) in rigid_variables.named {
         subs.rigid_var(var, name);
     }
@@ -96,9 +99,11 @@ pub fn run_solve(
         &mut abilities_store,
         exposed_by_module,
         derived_module,
-    );
+        cancel,
+        fuel,
+    )?;
 
-    (solved_subs, solved_env, problems, abilities_store)
+    Ok((solved_subs, solved_env, problems, abilities_store))
 }
 
 /// Copies exposed types and all ability specializations, which may be implicitly exposed.
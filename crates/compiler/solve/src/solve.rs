@@ -25,6 +25,7 @@ use roc_error_macros::internal_error;
 use roc_module::ident::TagName;
 use roc_module::symbol::{ModuleId, Symbol};
 use roc_problem::can::CycleEntry;
+use roc_problem::cancel::{CancelToken, Cancelled};
 use roc_region::all::Loc;
 use roc_solve_problem::TypeError;
 use roc_types::subs::{
@@ -524,6 +525,19 @@ struct State {
     mark: Mark,
 }
 
+/// A budget for the work of solving one module. Some programs, e.g. ones with deeply recursive
+/// aliases, make the solver do so much work that it may as well never finish. Once the budget is
+/// used up, solving stops with a [TypeError::DidNotConverge] instead.
+#[derive(Debug, Clone, Copy)]
+pub struct SolveFuel(pub u64);
+
+impl Default for SolveFuel {
+    fn default() -> Self {
+        // far more than real modules need, but only seconds of work
+        Self(100_000_000)
+    }
+}
+
 #[allow(clippy::too_many_arguments)] // TODO: put params in a context/env var
 pub fn run(
     home: ModuleId,
@@ -537,7 +551,9 @@ pub fn run(
     abilities_store: &mut AbilitiesStore,
     exposed_by_module: &ExposedByModule,
     derived_module: SharedDerivedModule,
-) -> (Solved<Subs>, Env) {
+    cancel: &CancelToken,
+    fuel: SolveFuel,
+) -> Result<(Solved<Subs>, Env), Cancelled> {
     let env = run_in_place(
         home,
         types,
@@ -550,9 +566,11 @@ pub fn run(
         abilities_store,
         exposed_by_module,
         derived_module,
-    );
+        cancel,
+        fuel,
+    )?;
 
-    (Solved(subs), env)
+    Ok((Solved(subs), env))
 }
 
 /// Modify an existing subs in-place instead
@@ -569,7 +587,9 @@ fn run_in_place(
     abilities_store: &mut AbilitiesStore,
     exposed_by_module: &ExposedByModule,
     derived_module: SharedDerivedModule,
-) -> Env {
+    cancel: &CancelToken,
+    fuel: SolveFuel,
+) -> Result<Env, Cancelled> {
     let mut pools = Pools::default();
 
     let state = State {
@@ -617,9 +637,11 @@ fn run_in_place(
         &mut obligation_cache,
         &mut awaiting_specializations,
         &derived_env,
-    );
+        cancel,
+        fuel,
+    )?;
 
-    state.env
+    Ok(state.env)
 }
 
 #[derive(Debug)]
@@ -675,7 +697,11 @@ fn solve(
     obligation_cache: &mut ObligationCache,
     awaiting_specializations: &mut AwaitingSpecializations,
     derived_env: &DerivedEnv,
-) -> State {
+    cancel: &CancelToken,
+    fuel: SolveFuel,
+) -> Result<State, Cancelled> {
+    let mut fuel = fuel.0;
+
     let initial = Work::Constraint {
         env: &Env::default(),
         rank,
@@ -683,8 +709,19 @@ fn solve(
     };
 
     let mut stack = vec![initial];
+    let mut problems_before_work_item = problems.len();
 
     while let Some(work_item) = stack.pop() {
+        cancel.check()?;
+
+        // unification takes fuel too, and fails when there is none left
+        if fuel == 0 {
+            break;
+        }
+
+        fuel -= 1;
+        problems_before_work_item = problems.len();
+
         let (env, rank, constraint) = match work_item {
             Work::Constraint {
                 env,
@@ -941,7 +978,7 @@ fn solve(
                 );
 
                 match unify(
-                    &mut UEnv::new(subs),
+                    &mut UEnv::new(subs).with_fuel(&mut fuel),
                     actual,
                     expected,
                     Mode::EQ,
@@ -1053,7 +1090,7 @@ fn solve(
                         );
 
                         match unify(
-                            &mut UEnv::new(subs),
+                            &mut UEnv::new(subs).with_fuel(&mut fuel),
                             actual,
                             expected,
                             Mode::EQ,
@@ -1165,7 +1202,7 @@ fn solve(
                 };
 
                 match unify(
-                    &mut UEnv::new(subs),
+                    &mut UEnv::new(subs).with_fuel(&mut fuel),
                     actual,
                     expected,
                     mode,
@@ -1382,7 +1419,7 @@ fn solve(
                 );
 
                 match unify(
-                    &mut UEnv::new(subs),
+                    &mut UEnv::new(subs).with_fuel(&mut fuel),
                     actual,
                     includes,
                     Mode::PRESENT,
@@ -1521,7 +1558,7 @@ fn solve(
 
                 let snapshot = subs.snapshot();
                 let unify_cond_and_patterns_outcome = unify(
-                    &mut UEnv::new(subs),
+                    &mut UEnv::new(subs).with_fuel(&mut fuel),
                     branches_var,
                     real_var,
                     Mode::EQ,
@@ -1574,7 +1611,7 @@ fn solve(
                         open_tag_union(subs, branches_var);
                         let almost_eq = matches!(
                             unify(
-                                &mut UEnv::new(subs),
+                                &mut UEnv::new(subs).with_fuel(&mut fuel),
                                 real_var,
                                 branches_var,
                                 Mode::EQ,
@@ -1592,7 +1629,7 @@ fn solve(
                             // Case 4: incompatible types, report type error.
                             // Re-run first failed unification to get the type diff.
                             match unify(
-                                &mut UEnv::new(subs),
+                                &mut UEnv::new(subs).with_fuel(&mut fuel),
                                 real_var,
                                 branches_var,
                                 Mode::EQ,
@@ -1791,12 +1828,14 @@ fn solve(
                     lambda_sets_to_specialize,
                     extra_metadata: _,
                 } = unify(
-                    &mut UEnv::new(subs),
+                    &mut UEnv::new(subs).with_fuel(&mut fuel),
                     actual,
                     Variable::LIST_U8,
                     Mode::EQ,
                     Polarity::OF_VALUE,
                 ) {
+                    subs.commit_snapshot(snapshot);
+
                     // List U8 always valid.
                     introduce(subs, rank, pools, &vars);
 
@@ -1811,7 +1850,7 @@ fn solve(
 
                     // We explicitly match on the last unify to get the type in the case it errors.
                     match unify(
-                        &mut UEnv::new(subs),
+                        &mut UEnv::new(subs).with_fuel(&mut fuel),
                         actual,
                         Variable::STR,
                         Mode::EQ,
@@ -1855,7 +1894,36 @@ fn solve(
         };
     }
 
-    state
+    if fuel == 0 {
+        // the last work item may have failed only because unification ran out of fuel
+        problems.truncate(problems_before_work_item);
+
+        let defs = defs_in_progress(constraints, &stack);
+        problems.push(TypeError::DidNotConverge(defs));
+    }
+
+    Ok(state)
+}
+
+/// The defs of the let-constraints that are still being solved, innermost last
+fn defs_in_progress(constraints: &Constraints, stack: &[Work]) -> Vec<Loc<Symbol>> {
+    let mut defs = Vec::new();
+
+    for work in stack {
+        if let Work::LetConNoVariables { let_con, .. }
+        | Work::LetConIntroducesVariables { let_con, .. } = work
+        {
+            let loc_symbols = &constraints.loc_symbols[let_con.def_types.loc_symbols.indices()];
+
+            defs.extend(
+                loc_symbols
+                    .iter()
+                    .map(|(symbol, region)| Loc::at(*region, *symbol)),
+            );
+        }
+    }
+
+    defs
 }
 
 fn chase_alias_content(subs: &Subs, mut var: Variable) -> (Variable, &Content) {
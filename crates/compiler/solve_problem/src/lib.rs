@@ -4,7 +4,7 @@ use std::{path::PathBuf, str::Utf8Error};
 use roc_can::expected::{Expected, PExpected};
 use roc_module::{ident::Lowercase, symbol::Symbol};
 use roc_problem::{can::CycleEntry, Severity};
-use roc_region::all::Region;
+use roc_region::all::{Loc, Region};
 
 use roc_types::types::{Category, ErrorType, PatternCategory};
 
@@ -33,6 +33,8 @@ pub enum TypeError {
     },
     IngestedFileBadUtf8(Box<PathBuf>, Utf8Error),
     IngestedFileUnsupportedType(Box<PathBuf>, ErrorType),
+    /// The solver ran out of fuel while it was still solving these definitions, innermost last
+    DidNotConverge(Vec<Loc<Symbol>>),
 }
 
 impl TypeError {
@@ -54,6 +56,30 @@ impl TypeError {
             TypeError::WrongSpecialization { .. } => RuntimeError,
             TypeError::IngestedFileBadUtf8(..) => Fatal,
             TypeError::IngestedFileUnsupportedType(..) => Fatal,
+            TypeError::DidNotConverge(_) => Fatal,
+        }
+    }
+
+    /// Returns the region the problem is reported at, if it has one.
+    pub fn region(&self) -> Option<Region> {
+        match self {
+            TypeError::BadExpr(region, ..)
+            | TypeError::BadPattern(region, ..)
+            | TypeError::CircularType(region, ..)
+            | TypeError::BadExprMissingAbility(region, ..)
+            | TypeError::BadPatternMissingAbility(region, ..)
+            | TypeError::StructuralSpecialization { region, .. }
+            | TypeError::WrongSpecialization { region, .. } => Some(*region),
+            TypeError::UnfulfilledAbility(Unfulfilled::OpaqueUnderivable {
+                derive_region, ..
+            }) => Some(*derive_region),
+            TypeError::Exhaustive(exhtv) => Some(exhtv.region()),
+            TypeError::CircularDef(entries) => entries.first().map(|entry| entry.symbol_region),
+            TypeError::DidNotConverge(symbols) => symbols.first().map(|symbol| symbol.region),
+            TypeError::UnexposedLookup(_)
+            | TypeError::UnfulfilledAbility(_)
+            | TypeError::IngestedFileBadUtf8(..)
+            | TypeError::IngestedFileUnsupportedType(..) => None,
         }
     }
 }
@@ -4,11 +4,40 @@
 // For the `v!` macro we use uppercase variables when constructing tag unions.
 #![allow(non_snake_case)]
 
-use crate::{util::check_single_lset_immediate, v};
+use crate::{
+    test_key_eq, test_key_neq,
+    util::{check_derivable, check_single_lset_immediate, derive_test},
+    v,
+};
+use insta::assert_snapshot;
 use roc_module::symbol::Symbol;
 use roc_types::subs::Variable;
 
-use roc_derive_key::DeriveBuiltin::IsEq;
+use roc_derive_key::{eq::FlatEqKey, DeriveBuiltin::IsEq, DeriveKey};
+
+test_key_eq! {
+    IsEq,
+
+    same_record_with_opaque:
+        v!({ a: v!(@Symbol::SET_SET v!(U8) => v!(EMPTY_RECORD)), }),
+        v!({ a: v!(@Symbol::SET_SET v!(U8) => v!(EMPTY_RECORD)), })
+    same_record_fields_opaque_and_flex:
+        v!({ a: v!(@Symbol::SET_SET v!(U8) => v!(EMPTY_RECORD)), }),
+        v!({ a: v!(*), })
+    same_tag_union_tags_any_order:
+        v!([ A v!(*), B ]), v!([ B, A v!(*) ])
+}
+
+test_key_neq! {
+    IsEq,
+
+    different_record_fields:
+        v!({ a: v!(*), }), v!({ b: v!(*), })
+    different_tuple_arities:
+        v!((v!(*), v!(U8),)), v!((v!(*), v!(U8), v!(U16),))
+    different_tag_union_tags:
+        v!([ A v!(*) ]), v!([ B v!(*) ])
+}
 
 #[test]
 fn immediates() {
@@ -56,3 +85,128 @@ fn immediates() {
     // compared, because obligation checking will rule them out from `isEq`!
     check_single_lset_immediate(IsEq, v!(F64), Symbol::BOOL_STRUCTURAL_EQ);
 }
+
+#[test]
+fn list_of_opaque_compares_elements_with_is_eq() {
+    check_single_lset_immediate(
+        IsEq,
+        v!(Symbol::LIST_LIST v!(@Symbol::SET_SET v!(U8) => v!(EMPTY_RECORD))),
+        Symbol::LIST_IS_EQ,
+    );
+}
+
+#[test]
+fn box_of_opaque_compares_contents_with_is_eq() {
+    check_single_lset_immediate(
+        IsEq,
+        v!(Symbol::BOX_BOX_TYPE v!(@Symbol::SET_SET v!(U8) => v!(EMPTY_RECORD))),
+        Symbol::BOX_IS_EQ,
+    );
+}
+
+#[test]
+fn record_with_opaque_field_is_derived() {
+    check_derivable(
+        IsEq,
+        v!({ a: v!(U8), b: v!(@Symbol::SET_SET v!(U8) => v!(EMPTY_RECORD)), }),
+        DeriveKey::IsEq(FlatEqKey::Record(vec!["a".into(), "b".into()])),
+    );
+}
+
+#[test]
+fn tag_with_nested_opaque_payload_is_derived() {
+    check_derivable(
+        IsEq,
+        v!([ A v!({ a: v!(@Symbol::SET_SET v!(U8) => v!(EMPTY_RECORD)), }), B ]),
+        DeriveKey::IsEq(FlatEqKey::TagUnion(vec![("A".into(), 1), ("B".into(), 0)])),
+    );
+}
+
+#[test]
+fn two_field_record() {
+    derive_test(IsEq, v!({ a: v!(U8), b: v!(*), }), |golden| {
+        assert_snapshot!(golden, @r###"
+        # derived for { a : U8, b : * }
+        # { a : a, b : a1 }, { a : a, b : a1 } -[[isEq_{a,b}(0)]]-> Bool | a has Eq, a1 has Eq
+        # { a : a, b : a1 }, { a : a, b : a1 } -[[isEq_{a,b}(0)]]-> [False, True] | a has Eq, a1 has Eq
+        # Specialization lambda sets:
+        #   @<1>: [[isEq_{a,b}(0)]]
+        #Derived.isEq_{a,b} =
+          \#Derived.rcd1, #Derived.rcd2 ->
+            and
+              (isEq #Derived.rcd1.a #Derived.rcd2.a)
+              (isEq #Derived.rcd1.b #Derived.rcd2.b)
+        "###
+        )
+    })
+}
+
+#[test]
+fn two_element_tuple() {
+    derive_test(IsEq, v!((v!(U8), v!(*),)), |golden| {
+        assert_snapshot!(golden, @r###"
+        # derived for ( U8, * )*
+        # ( a, a1 )b, ( a, a1 )b -[[isEq_(arity:2)(0)]]-> Bool | a has Eq, a1 has Eq
+        # ( a, a1 )b, ( a, a1 )b -[[isEq_(arity:2)(0)]]-> [False, True] | a has Eq, a1 has Eq
+        # Specialization lambda sets:
+        #   @<1>: [[isEq_(arity:2)(0)]]
+        #Derived.isEq_(arity:2) =
+          \#Derived.tup1, #Derived.tup2 ->
+            and
+              (isEq #Derived.tup1.0 #Derived.tup2.0)
+              (isEq #Derived.tup1.1 #Derived.tup2.1)
+        "###
+        )
+    })
+}
+
+#[test]
+fn tag_one_label_newtype() {
+    derive_test(IsEq, v!([A v!(U8) v!(*)]), |golden| {
+        assert_snapshot!(golden, @r###"
+        # derived for [A U8 *]
+        # [A a a1], [A a a1] -[[isEq_[A 2](0)]]-> Bool | a has Eq, a1 has Eq
+        # [A a a1], [A a a1] -[[isEq_[A 2](0)]]-> [False, True] | a has Eq, a1 has Eq
+        # Specialization lambda sets:
+        #   @<1>: [[isEq_[A 2](0)]]
+        #Derived.isEq_[A 2] =
+          \#Derived.union1, #Derived.union2 ->
+            when #Derived.union1 is
+              A #Derived.3 #Derived.4 ->
+                when #Derived.union2 is
+                  A #Derived.5 #Derived.6 ->
+                    and (isEq #Derived.3 #Derived.5) (isEq #Derived.4 #Derived.6)
+        "###
+        )
+    })
+}
+
+#[test]
+fn tag_two_labels() {
+    derive_test(IsEq, v!([A v!(U8) v!(*), B, C v!(*)]), |golden| {
+        assert_snapshot!(golden, @r###"
+        # derived for [A U8 *, B, C *]
+        # [A a a1, B, C a2]b, [A a a1, B, C a2]b -[[isEq_[A 2,B 0,C 1](0)]]-> Bool | a has Eq, a1 has Eq, a2 has Eq
+        # [A a a1, B, C a2]b, [A a a1, B, C a2]b -[[isEq_[A 2,B 0,C 1](0)]]-> [False, True] | a has Eq, a1 has Eq, a2 has Eq
+        # Specialization lambda sets:
+        #   @<1>: [[isEq_[A 2,B 0,C 1](0)]]
+        #Derived.isEq_[A 2,B 0,C 1] =
+          \#Derived.union1, #Derived.union2 ->
+            when #Derived.union1 is
+              A #Derived.3 #Derived.4 ->
+                when #Derived.union2 is
+                  A #Derived.5 #Derived.6 ->
+                    and (isEq #Derived.3 #Derived.5) (isEq #Derived.4 #Derived.6)
+                  _ -> false
+              B ->
+                when #Derived.union2 is
+                  B -> true
+                  _ -> false
+              C #Derived.7 ->
+                when #Derived.union2 is
+                  C #Derived.8 -> isEq #Derived.7 #Derived.8
+                  _ -> false
+        "###
+        )
+    })
+}
@@ -0,0 +1,215 @@
+#![cfg(test)]
+// Even with #[allow(non_snake_case)] on individual idents, rust-analyzer issues diagnostics.
+// See https://github.com/rust-lang/rust-analyzer/issues/6541.
+// For the `v!` macro we use uppercase variables when constructing tag unions.
+#![allow(non_snake_case)]
+
+use crate::{
+    test_key_eq, test_key_neq,
+    util::{check_derivable, check_single_lset_immediate, check_underivable, derive_test},
+    v,
+};
+use insta::assert_snapshot;
+use roc_module::symbol::Symbol;
+use roc_types::subs::Variable;
+
+use roc_derive_key::{inspect::FlatInspectKey, DeriveBuiltin::Inspect, DeriveError, DeriveKey};
+
+test_key_eq! {
+    Inspect,
+
+    same_record:
+        v!({ a: v!(U8), }), v!({ a: v!(U8), })
+    same_record_fields_diff_types:
+        v!({ a: v!(U8), }), v!({ a: v!(STR), })
+    same_record_fields_any_order:
+        v!({ a: v!(U8), b: v!(U8), c: v!(U8), }),
+        v!({ c: v!(U8), a: v!(U8), b: v!(U8), })
+    explicit_empty_record_and_implicit_empty_record:
+        v!(EMPTY_RECORD), v!({})
+
+    same_tuple:
+        v!((v!(U8), v!(U16),)), v!((v!(U8), v!(U16),))
+    same_tuple_fields_diff_types:
+        v!((v!(U8), v!(U16),)), v!((v!(U32), v!(U64),))
+
+    same_tag_union:
+        v!([ A v!(U8) v!(STR), B v!(STR) ]), v!([ A v!(U8) v!(STR), B v!(STR) ])
+    same_tag_union_tags_diff_types:
+        v!([ A v!(U8) v!(U8), B v!(U8) ]), v!([ A v!(STR) v!(STR), B v!(STR) ])
+    same_tag_union_tags_any_order:
+        v!([ A v!(U8) v!(U8), B v!(U8), C ]), v!([ C, B v!(STR), A v!(STR) v!(STR) ])
+    explicit_empty_tag_union_and_implicit_empty_tag_union:
+        v!(EMPTY_TAG_UNION), v!([])
+
+    same_recursive_tag_union:
+        v!([ Nil, Cons v!(^lst)] as lst), v!([ Nil, Cons v!(^lst)] as lst)
+    same_tag_union_and_recursive_tag_union_fields:
+        v!([ Nil, Cons v!(STR)]), v!([ Nil, Cons v!(^lst)] as lst)
+}
+
+test_key_neq! {
+    Inspect,
+
+    different_record_fields:
+        v!({ a: v!(U8), }), v!({ b: v!(U8), })
+    record_empty_vs_nonempty:
+        v!(EMPTY_RECORD), v!({ a: v!(U8), })
+
+    different_tuple_arities:
+        v!((v!(U8), v!(U16),)), v!((v!(U8), v!(U16), v!(U32),))
+
+    different_tag_union_tags:
+        v!([ A v!(U8) ]), v!([ B v!(U8) ])
+    tag_union_empty_vs_nonempty:
+        v!(EMPTY_TAG_UNION), v!([ B v!(U8) ])
+    different_recursive_tag_union_tags:
+        v!([ Nil, Cons v!(^lst) ] as lst), v!([ Nil, Next v!(^lst) ] as lst)
+}
+
+#[test]
+fn immediates() {
+    check_single_lset_immediate(Inspect, v!(U8), Symbol::INSPECT_INSPECT_NUM);
+    check_single_lset_immediate(Inspect, v!(I128), Symbol::INSPECT_INSPECT_NUM);
+    check_single_lset_immediate(Inspect, v!(F64), Symbol::INSPECT_INSPECT_NUM);
+    check_single_lset_immediate(Inspect, v!(DEC), Symbol::INSPECT_INSPECT_NUM);
+    check_single_lset_immediate(Inspect, v!(STR), Symbol::INSPECT_INSPECT_STR);
+    check_single_lset_immediate(
+        Inspect,
+        v!(Symbol::LIST_LIST v!(U8)),
+        Symbol::INSPECT_INSPECT_LIST,
+    );
+    check_single_lset_immediate(
+        Inspect,
+        v!(Symbol::LIST_LIST v!(STR)),
+        Symbol::INSPECT_INSPECT_LIST,
+    );
+}
+
+#[test]
+fn optional_record_field_derive_error() {
+    check_underivable(Inspect, v!({ ?a: v!(U8), }), DeriveError::Underivable);
+}
+
+#[test]
+fn derivable_record_ext_flex_var() {
+    check_derivable(
+        Inspect,
+        v!({ a: v!(STR), }* ),
+        DeriveKey::Inspect(FlatInspectKey::Record(vec!["a".into()])),
+    );
+}
+
+#[test]
+fn derivable_tag_ext_flex_var() {
+    check_derivable(
+        Inspect,
+        v!([ A v!(STR) ]* ),
+        DeriveKey::Inspect(FlatInspectKey::TagUnion(vec![("A".into(), 1)])),
+    );
+}
+
+#[test]
+fn empty_record() {
+    derive_test(Inspect, v!(EMPTY_RECORD), |golden| {
+        assert_snapshot!(golden, @r###"
+        # derived for {}
+        # {} -[[inspect_{}(0)]]-> Str
+        # {} -[[inspect_{}(0)]]-> Str
+        # Specialization lambda sets:
+        #   @<1>: [[inspect_{}(0)]]
+        #Derived.inspect_{} = \#Derived.rcd -> "{}"
+        "###
+        )
+    })
+}
+
+#[test]
+fn two_field_record() {
+    derive_test(Inspect, v!({ a: v!(U8), b: v!(STR), }), |golden| {
+        assert_snapshot!(golden, @r###"
+        # derived for { a : U8, b : Str }
+        # { a : val, b : val1 } -[[inspect_{a,b}(0)]]-> Str | val has Inspect, val1 has Inspect
+        # { a : val, b : val1 } -[[inspect_{a,b}(0)]]-> Str | val has Inspect, val1 has Inspect
+        # Specialization lambda sets:
+        #   @<1>: [[inspect_{a,b}(0)]]
+        #Derived.inspect_{a,b} =
+          \#Derived.rcd ->
+            append
+              (append
+                (append (append "{ a: " (inspect #Derived.rcd.a)) ", b: ")
+                (inspect #Derived.rcd.b))
+              " }"
+        "###
+        )
+    })
+}
+
+#[test]
+fn two_element_tuple() {
+    derive_test(Inspect, v!((v!(U8), v!(STR),)), |golden| {
+        assert_snapshot!(golden, @r###"
+        # derived for ( U8, Str )*
+        # ( val, val1 )* -[[inspect_(arity:2)(0)]]-> Str | val has Inspect, val1 has Inspect
+        # ( val, val1 )* -[[inspect_(arity:2)(0)]]-> Str | val has Inspect, val1 has Inspect
+        # Specialization lambda sets:
+        #   @<1>: [[inspect_(arity:2)(0)]]
+        #Derived.inspect_(arity:2) =
+          \#Derived.tup ->
+            append
+              (append
+                (append (append "(" (inspect #Derived.tup.0)) ", ")
+                (inspect #Derived.tup.1))
+              ")"
+        "###
+        )
+    })
+}
+
+#[test]
+fn tag_two_labels() {
+    derive_test(Inspect, v!([A v!(U8) v!(STR), B]), |golden| {
+        assert_snapshot!(golden, @r###"
+        # derived for [A U8 Str, B]
+        # [A val val1, B] -[[inspect_[A 2,B 0](0)]]-> Str | val has Inspect, val1 has Inspect
+        # [A val val1, B] -[[inspect_[A 2,B 0](0)]]-> Str | val has Inspect, val1 has Inspect
+        # Specialization lambda sets:
+        #   @<1>: [[inspect_[A 2,B 0](0)]]
+        #Derived.inspect_[A 2,B 0] =
+          \#Derived.union ->
+            when #Derived.union is
+              A #Derived.2 #Derived.3 ->
+                append
+                  (append
+                    (append (append "A" " ") (parenthesize (inspect #Derived.2)))
+                    " ")
+                  (parenthesize (inspect #Derived.3))
+              B -> "B"
+        "###
+        )
+    })
+}
+
+#[test]
+fn recursive_tag_union() {
+    derive_test(Inspect, v!([Nil, Cons v!(U8) v!(^lst) ] as lst), |golden| {
+        assert_snapshot!(golden, @r###"
+        # derived for [Cons U8 $rec, Nil] as $rec
+        # [Cons val val1, Nil] -[[inspect_[Cons 2,Nil 0](0)]]-> Str | val has Inspect, val1 has Inspect
+        # [Cons val val1, Nil] -[[inspect_[Cons 2,Nil 0](0)]]-> Str | val has Inspect, val1 has Inspect
+        # Specialization lambda sets:
+        #   @<1>: [[inspect_[Cons 2,Nil 0](0)]]
+        #Derived.inspect_[Cons 2,Nil 0] =
+          \#Derived.union ->
+            when #Derived.union is
+              Cons #Derived.2 #Derived.3 ->
+                append
+                  (append
+                    (append (append "Cons" " ") (parenthesize (inspect #Derived.2)))
+                    " ")
+                  (parenthesize (inspect #Derived.3))
+              Nil -> "Nil"
+        "###
+        )
+    })
+}
@@ -63,6 +63,11 @@ fn module_source_and_path(builtin: DeriveBuiltin) -> (ModuleId, &'static str, Pa
             module_source(ModuleId::BOOL),
             builtins_path.join("Bool.roc"),
         ),
+        DeriveBuiltin::Inspect => (
+            ModuleId::INSPECT,
+            module_source(ModuleId::INSPECT),
+            builtins_path.join("Inspect.roc"),
+        ),
     }
 }
 
@@ -430,7 +435,10 @@ fn check_derived_typechecks_and_golden(
         Default::default(),
         &exposed_for_module.exposed_by_module,
         Default::default(),
-    );
+        &Default::default(),
+        Default::default(),
+    )
+    .unwrap();
     dbg_do!(
         roc_debug_flags::ROC_PRINT_UNIFICATIONS_DERIVED,
         std::env::set_var(roc_debug_flags::ROC_PRINT_UNIFICATIONS, "0")
@@ -548,6 +556,7 @@ where
     let pp_ctx = PPCtx {
         interns: &interns,
         print_lambda_names: false,
+        print_regions: false,
         home: builtin_module,
     };
     let derived_program = pretty_print_def(&pp_ctx, &derived_def);
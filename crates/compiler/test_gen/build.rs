@@ -14,6 +14,11 @@ fn main() {
         build_wasm_test_host();
         build_wasm_linking_test_host();
     }
+
+    if feature_is_enabled("check-leaks") {
+        // compiled tests allocate through the roc_alloc and friends of the test binary
+        println!("cargo:rustc-link-arg-tests=-rdynamic");
+    }
 }
 
 const fn object_file_extension() -> &'static str {
@@ -371,7 +371,7 @@ fn encode_use_stdlib() {
                     _ -> "<bad>"
             "#
         ),
-        RocStr::from("\"Hello, World!\n\""),
+        RocStr::from("\"Hello, World!\\n\""),
         RocStr
     )
 }
@@ -396,7 +396,7 @@ fn encode_use_stdlib_without_wrapping_custom() {
                     _ -> "<bad>"
             "#
         ),
-        RocStr::from("\"Hello, World!\n\""),
+        RocStr::from("\"Hello, World!\\n\""),
         RocStr
     )
 }
@@ -448,7 +448,7 @@ fn to_encoder_encode_custom_has_capture() {
                     _ -> "<bad>"
             "#
         ),
-        RocStr::from("\"Hello, World!\n\""),
+        RocStr::from("\"Hello, World!\\n\""),
         RocStr
     )
 }
@@ -1012,6 +1012,93 @@ fn decode_use_stdlib_json_list() {
     )
 }
 
+#[test]
+#[cfg(any(feature = "gen-llvm"))]
+fn json_to_str_escapes_strings() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test"
+                imports [Json]
+                provides [main] to "./platform"
+
+            main = Json.toStr { a: "say \"hi\"\n", b: "\u(1F600)\u(07)" }
+            "#
+        ),
+        RocStr::from(r#"{"a":"say \"hi\"\n","b":"😀\u0007"}"#),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm"))]
+fn json_from_str_unescapes_strings() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test"
+                imports [Json]
+                provides [main] to "./platform"
+
+            main =
+                when Json.fromStr "\"caf\\u00e9 \\ud83d\\ude00\\t\\/\"" is
+                    Ok s -> s
+                    _ -> "<bad>"
+            "#
+        ),
+        RocStr::from("café 😀\t/"),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm"))]
+fn json_from_str_reports_error_offset() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test"
+                imports [Json]
+                provides [main] to "./platform"
+
+            main =
+                decoded : Result (List U8) _
+                decoded = Json.fromStr "[1, 2, x]"
+
+                when decoded is
+                    Err (TooShort { offset }) -> offset
+                    _ -> 0
+            "#
+        ),
+        7,
+        usize
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm"))]
+fn json_from_str_reports_leftover_offset() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test"
+                imports [Json]
+                provides [main] to "./platform"
+
+            main =
+                decoded : Result (List U8) _
+                decoded = Json.fromStr " [1, 2] \n ]"
+
+                when decoded is
+                    Err (Leftover { offset }) -> offset
+                    _ -> 0
+            "#
+        ),
+        10,
+        usize
+    )
+}
+
 mod decode_immediate {
     #[cfg(feature = "gen-llvm")]
     use crate::helpers::llvm::assert_evals_to;
@@ -2073,6 +2160,51 @@ mod eq {
         )
     }
 
+    #[test]
+    fn derived_eq_defers_to_custom_eq_in_containers() {
+        assert_evals_to!(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                Mod10 := U64 has [Eq {isEq}]
+
+                isEq = \@Mod10 m, @Mod10 n -> m % 10 == n % 10
+
+                main =
+                    { a: [@Mod10 12], b: Box.box (@Mod10 3) } == { a: [@Mod10 2], b: Box.box (@Mod10 13) }
+                "#
+            ),
+            true,
+            bool
+        )
+    }
+
+    #[test]
+    fn dict_key_with_custom_eq_and_hash() {
+        assert_evals_to!(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                Mod10 := U64 has [Eq {isEq}, Hash {hash}]
+
+                isEq = \@Mod10 m, @Mod10 n -> m % 10 == n % 10
+
+                hash = \hasher, @Mod10 n -> Hash.hash hasher (n % 10)
+
+                main =
+                    Dict.empty {}
+                    |> Dict.insert { key: @Mod10 12 } "found"
+                    |> Dict.get { key: @Mod10 2 }
+                    |> Result.withDefault "missing"
+                "#
+            ),
+            RocStr::from("found"),
+            RocStr
+        )
+    }
+
     #[test]
     fn derive_structural_eq_for_opaque() {
         assert_evals_to!(
@@ -2091,6 +2223,114 @@ mod eq {
     }
 }
 
+#[cfg(all(test, any(feature = "gen-llvm", feature = "gen-wasm")))]
+mod inspect {
+    #[cfg(feature = "gen-llvm")]
+    use crate::helpers::llvm::assert_evals_to;
+
+    #[cfg(feature = "gen-wasm")]
+    use crate::helpers::wasm::assert_evals_to;
+
+    use indoc::indoc;
+    use roc_std::RocStr;
+
+    #[test]
+    fn inspect_record() {
+        assert_evals_to!(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                main = Inspect.inspect { name: "Roc", age: 3u8 }
+                "#
+            ),
+            RocStr::from(r#"{ age: 3, name: "Roc" }"#),
+            RocStr
+        )
+    }
+
+    #[test]
+    fn inspect_nested_tags() {
+        assert_evals_to!(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                main = Inspect.inspect [Ok (Some 1u8), Err Oops]
+                "#
+            ),
+            RocStr::from("[Ok (Some 1), Err Oops]"),
+            RocStr
+        )
+    }
+
+    #[test]
+    fn inspect_tuple_of_bools() {
+        assert_evals_to!(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                main = Inspect.inspect (Bool.true, Bool.false)
+                "#
+            ),
+            RocStr::from("(Bool.true, Bool.false)"),
+            RocStr
+        )
+    }
+
+    #[test]
+    fn inspect_str_is_escaped() {
+        assert_evals_to!(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                main = Inspect.inspect "say \"hi\""
+                "#
+            ),
+            RocStr::from(r#""say \"hi\"""#),
+            RocStr
+        )
+    }
+
+    #[test]
+    fn derived_inspect_for_opaque() {
+        assert_evals_to!(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                Age := U8 has [Inspect]
+
+                main = Inspect.inspect { age: @Age 21 }
+                "#
+            ),
+            RocStr::from("{ age: @Age 21 }"),
+            RocStr
+        )
+    }
+
+    #[test]
+    fn custom_inspect_for_opaque() {
+        assert_evals_to!(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                Secret := Str has [Inspect { inspect: inspectSecret }]
+
+                inspectSecret = \@Secret _ -> "<redacted>"
+
+                main = Inspect.inspect { password: @Secret "hunter2" }
+                "#
+            ),
+            RocStr::from("{ password: <redacted> }"),
+            RocStr
+        )
+    }
+}
+
 #[test]
 #[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn issue_4772_weakened_monomorphic_destructure() {
@@ -119,6 +119,54 @@ fn dict_nonempty_remove() {
     );
 }
 
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn remove_ordered_keeps_insertion_order() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            myDict : Dict.Dict I64 I64
+            myDict =
+                Dict.empty {}
+                    |> Dict.insert 0 100
+                    |> Dict.insert 1 200
+                    |> Dict.insert 2 300
+                    |> Dict.insert 3 400
+                    |> Dict.removeOrdered 1
+                    |> Dict.insert 4 500
+
+            Dict.keys myDict
+            "#
+        ),
+        RocList::from_slice(&[0, 2, 3, 4]),
+        RocList<i64>
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn remove_ordered_then_get() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            myDict : Dict.Dict I64 I64
+            myDict =
+                Dict.empty {}
+                    |> Dict.insert 0 100
+                    |> Dict.insert 1 200
+                    |> Dict.insert 2 300
+                    |> Dict.removeOrdered 0
+
+            when Dict.get myDict 2 is
+                Ok v -> v
+                Err KeyNotFound -> -1
+            "#
+        ),
+        300,
+        i64
+    );
+}
+
 #[test]
 #[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn dict_nonempty_get() {
@@ -2163,6 +2163,25 @@ fn set_unique_int_list() {
     );
 }
 
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn set_static_int_list() {
+    // the literal is read-only data, so List.set must copy it instead of writing into it
+    assert_evals_to!(
+        indoc!(
+            r#"
+            digits = \{} -> [1, 2, 3]
+
+            changed = List.set (digits {}) 0 10
+
+            List.concat changed (digits {})
+            "#
+        ),
+        RocList::from_slice(&[10, 2, 3, 1, 2, 3]),
+        RocList<i64>
+    );
+}
+
 #[test]
 #[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
 fn set_unique_list_oob() {
@@ -2201,6 +2220,39 @@ fn set_shared_int_list() {
     );
 }
 
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn append_unique_int_list() {
+    // List.append reserves capacity with List.reserve, which reuses a unique list
+    assert_evals_to!(
+        "List.append (List.withCapacity 4 |> List.concat [12, 9, 7]) 33",
+        RocList::from_slice(&[12, 9, 7, 33]),
+        RocList<i64>
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn append_shared_int_list() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            wrapper = \shared ->
+                # This should not mutate the original
+                x = List.len (List.append shared 7.7)
+
+                y = List.len shared
+
+                { x, y }
+
+            wrapper (List.withCapacity 4 |> List.concat [2.1, 4.3])
+            "#
+        ),
+        (3, 2),
+        (usize, usize)
+    );
+}
+
 #[test]
 #[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn set_shared_list_oob() {
@@ -270,6 +270,27 @@ fn or_pattern() {
     );
 }
 
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn or_pattern_shared_by_many_alternatives() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            classify = \n ->
+                when n is
+                    1 | 3 | 5 | 7 | 9 -> 1
+                    2 | 4 | 6 | 8 -> 2
+                    0 -> 0
+                    _ -> 3
+
+            List.map [0, 1, 2, 7, 8, 10] classify
+            "#
+        ),
+        RocList::from_slice(&[0, 1, 2, 1, 2, 3]),
+        RocList<i64>
+    );
+}
+
 #[test]
 #[cfg(any(feature = "gen-llvm", feature = "gen-dev", feature = "gen-wasm"))]
 fn apply_identity() {
@@ -123,6 +123,28 @@ fn list_str_dealloc() {
     );
 }
 
+#[test]
+#[cfg(any(feature = "gen-wasm"))]
+fn list_str_append_shared() {
+    assert_refcounts!(
+        indoc!(
+            r#"
+                s = Str.concat "A long enough string " "to be heap-allocated"
+                list = [s]
+                copy = List.append list s
+                [list, copy]
+            "#
+        ),
+        RocList<RocList<RocStr>>,
+        &[
+            Live(3), // s
+            Live(1), // list
+            Live(1), // copy
+            Live(1)  // result
+        ]
+    );
+}
+
 #[test]
 #[cfg(any(feature = "gen-wasm"))]
 fn struct_inc() {
@@ -104,6 +104,23 @@ fn remove() {
     );
 }
 
+#[test]
+#[cfg(any(feature = "gen-llvm"))]
+fn remove_ordered() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            Set.fromList [0, 1, 2, 3]
+                |> Set.removeOrdered 0
+                |> Set.removeOrdered 4
+                |> Set.toList
+            "#
+        ),
+        RocList::from_slice(&[1, 2, 3]),
+        RocList<i64>
+    );
+}
+
 #[test]
 #[cfg(any(feature = "gen-llvm"))]
 fn union() {
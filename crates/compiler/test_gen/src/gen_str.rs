@@ -2002,6 +2002,30 @@ fn when_on_strings() {
     );
 }
 
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn when_on_strings_of_the_same_length() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            toNum = \str ->
+                when str is
+                    "one" -> 1
+                    "two" -> 2
+                    "six" -> 6
+                    "four" -> 4
+                    "five" -> 5
+                    "eleven" -> 11
+                    _ -> 0
+
+            [toNum "six", toNum "four", toNum "ten", toNum "eleven", toNum "this is not a small string"]
+            "#
+        ),
+        RocList::from_slice(&[6, 4, 0, 11, 0]),
+        RocList<i64>
+    );
+}
+
 #[test]
 #[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
 fn with_capacity() {
@@ -1,7 +1,10 @@
 use libloading::Library;
 use roc_build::link::{link, LinkType};
 use roc_builtins::bitcode;
-use roc_load::{EntryPoint, ExecutionMode, LoadConfig, Threading};
+use roc_load::{
+    CancelToken, EntryPoint, ExecutionMode, HotModule, LoadConfig, ProgressReporter, SolveFuel,
+    Threading,
+};
 use roc_mono::ir::SingleEntryPoint;
 use roc_packaging::cache::RocCacheDir;
 use roc_region::all::LineInfo;
@@ -58,6 +61,10 @@ pub fn helper(
         palette: roc_reporting::report::DEFAULT_PALETTE,
         threading: Threading::Single,
         exec_mode: ExecutionMode::Executable,
+        cancel: CancelToken::default(),
+        progress: ProgressReporter::default(),
+        hot_module: HotModule::default(),
+        solve_fuel: SolveFuel::default(),
     };
     let loaded = roc_load::load_and_monomorphize_from_str(
         arena,
@@ -190,7 +197,9 @@ pub fn helper(
         module_id,
         exposed_to_host: exposed_to_host.top_level_values.keys().copied().collect(),
         lazy_literals,
-        generate_allocators: true, // Needed for testing, since we don't have a platform
+        // Needed for testing, since we don't have a platform. To check for leaks,
+        // the roc_alloc and friends of the test binary count the allocations instead.
+        generate_allocators: !cfg!(feature = "check-leaks"),
     };
 
     let target = target_lexicon::Triple::host();
@@ -271,13 +280,21 @@ macro_rules! assert_evals_to {
         let (main_fn_name, errors, lib) =
             $crate::helpers::dev::helper(&arena, $src, $leak, $lazy_literals);
 
+        let live_before = $crate::helpers::platform_functions::live_allocations();
+
         let transform = |success| {
             let expected = $expected;
             #[allow(clippy::redundant_closure_call)]
             let given = $transform(success);
             assert_eq!(&given, &expected);
         };
-        run_jit_function_raw!(lib, main_fn_name, $ty, transform, errors)
+        run_jit_function_raw!(lib, main_fn_name, $ty, transform, errors);
+
+        if cfg!(feature = "check-leaks") {
+            let leaked = $crate::helpers::platform_functions::live_allocations() - live_before;
+
+            assert_eq!(leaked, 0, "dev backend test leaked {} allocations", leaked);
+        }
     };
 }
 
@@ -8,7 +8,10 @@ use roc_collections::all::MutSet;
 use roc_command_utils::zig;
 use roc_gen_llvm::llvm::externs::add_default_roc_externs;
 use roc_gen_llvm::{llvm::build::LlvmBackendMode, run_roc::RocCallResult};
-use roc_load::{EntryPoint, ExecutionMode, LoadConfig, LoadMonomorphizedError, Threading};
+use roc_load::{
+    CancelToken, EntryPoint, ExecutionMode, HotModule, LoadConfig, LoadMonomorphizedError,
+    ProgressReporter, SolveFuel, Threading,
+};
 use roc_mono::ir::{CrashTag, OptLevel, SingleEntryPoint};
 use roc_packaging::cache::RocCacheDir;
 use roc_region::all::LineInfo;
@@ -74,6 +77,10 @@ fn create_llvm_module<'a>(
         palette: DEFAULT_PALETTE,
         threading: Threading::Single,
         exec_mode: ExecutionMode::Executable,
+        cancel: CancelToken::default(),
+        progress: ProgressReporter::default(),
+        hot_module: HotModule::default(),
+        solve_fuel: SolveFuel::default(),
     };
     let loaded = roc_load::load_and_monomorphize_from_str(
         arena,
@@ -226,6 +233,8 @@ fn create_llvm_module<'a>(
         mode: config.mode,
         // important! we don't want any procedures to get the C calling convention
         exposed_to_host: MutSet::default(),
+        stack_bytes: None,
+        sources: None,
     };
 
     // strip Zig debug stuff
@@ -235,6 +244,19 @@ fn create_llvm_module<'a>(
     // platform to provide them.
     add_default_roc_externs(&env);
 
+    // Allocate through the test binary instead, which counts the live allocations
+    if cfg!(feature = "check-leaks") && matches!(config.mode, LlvmBackendMode::GenTest) {
+        for name in ["roc_alloc", "roc_realloc", "roc_dealloc"] {
+            let fn_val = module.get_function(name).unwrap();
+
+            for block in fn_val.get_basic_blocks() {
+                unsafe { block.delete().unwrap() };
+            }
+
+            fn_val.set_linkage(Linkage::External);
+        }
+    }
+
     let entry_point = match loaded.entry_point {
         EntryPoint::Executable {
             exposed_to_host,
@@ -254,6 +276,7 @@ fn create_llvm_module<'a>(
         LlvmBackendMode::Binary => unreachable!(),
         LlvmBackendMode::BinaryDev => unreachable!(),
         LlvmBackendMode::BinaryGlue => unreachable!(),
+        LlvmBackendMode::BinaryVerify => unreachable!(),
         LlvmBackendMode::CliTest => unreachable!(),
         LlvmBackendMode::WasmGenTest => roc_gen_llvm::llvm::build::build_wasm_test_wrapper(
             &env,
@@ -595,6 +618,9 @@ where
 
     let (main_fn_name, errors, lib) = crate::helpers::llvm::helper(&arena, config, src, &context);
 
+    // `expected` may hold allocations of its own
+    let live_before = crate::helpers::platform_functions::live_allocations();
+
     let result = crate::helpers::llvm::try_run_lib_function::<T>(main_fn_name, &lib);
 
     match result {
@@ -609,6 +635,15 @@ where
             // on Windows, there are issues with the drop instances of some roc_std
             #[cfg(windows)]
             std::mem::forget(given);
+
+            #[cfg(not(windows))]
+            drop(given);
+
+            if cfg!(feature = "check-leaks") {
+                let leaked = crate::helpers::platform_functions::live_allocations() - live_before;
+
+                assert_eq!(leaked, 0, "LLVM test leaked {} allocations", leaked);
+            }
         }
         Err((msg, tag)) => match tag {
             CrashTag::Roc => panic!(r#"Roc failed with message: "{}""#, msg),
@@ -1,9 +1,26 @@
 use core::ffi::c_void;
+use std::cell::Cell;
+
+thread_local! {
+    // Every test runs on its own thread, so this only counts the allocations of that test.
+    // Signed, because without `check-leaks` compiled code uses its own allocator and only
+    // the deallocations of roc_std values come through here.
+    static LIVE_ALLOCATIONS: Cell<isize> = Cell::new(0);
+}
+
+/// The allocations of the current thread that were not deallocated yet.
+/// With the `check-leaks` feature, this includes the ones made by compiled Roc code.
+#[allow(dead_code)]
+pub fn live_allocations() -> isize {
+    LIVE_ALLOCATIONS.with(|live| live.get())
+}
 
 /// # Safety
 /// The Roc application needs this.
 #[no_mangle]
 pub unsafe fn roc_alloc(size: usize, _alignment: u32) -> *mut c_void {
+    LIVE_ALLOCATIONS.with(|live| live.set(live.get() + 1));
+
     libc::malloc(size)
 }
 
@@ -30,5 +47,7 @@ pub unsafe fn roc_realloc(
 /// The Roc application needs this.
 #[no_mangle]
 pub unsafe fn roc_dealloc(c_ptr: *mut c_void, _alignment: u32) {
+    LIVE_ALLOCATIONS.with(|live| live.set(live.get() - 1));
+
     libc::free(c_ptr)
 }
@@ -16,9 +16,9 @@ const EXPANDED_STACK_SIZE: usize = 8 * 1024 * 1024;
 use bumpalo::Bump;
 use roc_collections::all::MutMap;
 use roc_load::ExecutionMode;
-use roc_load::LoadConfig;
 use roc_load::LoadMonomorphizedError;
 use roc_load::Threading;
+use roc_load::{CancelToken, HotModule, LoadConfig, ProgressReporter, SolveFuel};
 use roc_module::symbol::Interns;
 use roc_module::symbol::Symbol;
 use roc_mono::ir::Proc;
@@ -26,8 +26,6 @@ use roc_mono::ir::ProcLayout;
 use roc_mono::layout::STLayoutInterner;
 use test_mono_macros::*;
 
-const TARGET_INFO: roc_target::TargetInfo = roc_target::TargetInfo::default_x86_64();
-
 /// Without this, some tests pass in `cargo test --release` but fail without
 /// the --release flag because they run out of stack space. This increases
 /// stack size for debug builds only, while leaving the stack space at the default
@@ -76,10 +74,30 @@ fn promote_expr_to_module(src: &str) -> String {
     buffer
 }
 
-fn compiles_to_ir(test_name: &str, src: &str, mode: &str, no_check: bool) {
+/// What `#[mono_test(..)]` passes on to `compiles_to_ir`
+struct MonoTestOptions {
+    mode: &'static str,
+    no_check: bool,
+    target_info: roc_target::TargetInfo,
+}
+
+fn compiles_to_ir(test_name: &str, src: &str, options: MonoTestOptions) {
+    let result = mono_ir(src, options);
+
+    verify_ir(test_name, &result);
+}
+
+/// The procs that `src` compiles to, sorted by name, with the main function last
+fn mono_ir(src: &str, options: MonoTestOptions) -> String {
     use roc_packaging::cache::RocCacheDir;
     use std::path::PathBuf;
 
+    let MonoTestOptions {
+        mode,
+        no_check,
+        target_info,
+    } = options;
+
     let exec_mode = match mode {
         "exec" => ExecutionMode::Executable,
         "test" => ExecutionMode::Test,
@@ -103,11 +121,15 @@ fn compiles_to_ir(test_name: &str, src: &str, mode: &str, no_check: bool) {
     }
 
     let load_config = LoadConfig {
-        target_info: TARGET_INFO,
+        target_info,
         threading: Threading::Single,
         render: roc_reporting::report::RenderTarget::Generic,
         palette: roc_reporting::report::DEFAULT_PALETTE,
         exec_mode,
+        cancel: CancelToken::default(),
+        progress: ProgressReporter::default(),
+        hot_module: HotModule::default(),
+        solve_fuel: SolveFuel::default(),
     };
     let loaded = roc_load::load_and_monomorphize_from_str(
         arena,
@@ -154,7 +176,7 @@ fn compiles_to_ir(test_name: &str, src: &str, mode: &str, no_check: bool) {
         check_procedures(arena, &interns, &mut layout_interner, &procedures);
     }
 
-    verify_procedures(test_name, layout_interner, procedures, main_fn_symbol);
+    procedures_to_string(layout_interner, procedures, main_fn_symbol)
 }
 
 fn check_procedures<'a>(
@@ -172,12 +194,11 @@ fn check_procedures<'a>(
     panic!("IR problems found:\n{formatted}");
 }
 
-fn verify_procedures<'a>(
-    test_name: &str,
+fn procedures_to_string<'a>(
     interner: STLayoutInterner<'a>,
     procedures: MutMap<(Symbol, ProcLayout<'a>), Proc<'a>>,
     opt_main_fn_symbol: Option<Symbol>,
-) {
+) -> String {
     let mut procs_string = procedures
         .values()
         .map(|proc| proc.to_pretty(&interner, 200, false))
@@ -197,8 +218,10 @@ fn verify_procedures<'a>(
         procs_string.push(main_fn);
     }
 
-    let result = procs_string.join("\n");
+    procs_string.join("\n")
+}
 
+fn verify_ir(test_name: &str, result: &str) {
     let path = format!("generated/{}.txt", test_name);
     std::fs::create_dir_all("generated").unwrap();
     std::fs::write(&path, result).unwrap();
@@ -741,7 +764,7 @@ fn factorial() {
     "#
 }
 
-#[mono_test]
+#[mono_test(wasm32 = "true")]
 fn is_nil() {
     r#"
     ConsList a : [Cons a (ConsList a), Nil]
@@ -830,6 +853,48 @@ fn list_get() {
     "#
 }
 
+#[mono_test]
+fn list_first_last() {
+    r#"
+    wrapper = \{} ->
+        list = ["a", "b", "c"]
+
+        when T (List.first list) (List.last list) is
+            T (Ok a) (Ok b) -> Str.concat a b
+            _ -> ""
+
+    wrapper {}
+    "#
+}
+
+#[test]
+fn list_first_last_skip_list_get() {
+    // they check the length once, and then read the element without the bounds check of `List.get`
+    let ir = mono_ir(
+        r#"
+        wrapper = \{} ->
+            list = ["a", "b", "c"]
+
+            when T (List.first list) (List.last list) is
+                T (Ok a) (Ok b) -> Str.concat a b
+                _ -> ""
+
+        wrapper {}
+        "#,
+        MonoTestOptions {
+            mode: "exec",
+            no_check: false,
+            target_info: roc_target::TargetInfo::default_x86_64(),
+        },
+    );
+
+    assert!(ir.contains("lowlevel ListGetUnsafe"));
+    assert!(
+        !ir.contains("procedure List.2 "),
+        "List.get was specialized:\n{ir}"
+    );
+}
+
 #[mono_test]
 fn peano() {
     r#"
@@ -842,7 +907,7 @@ fn peano() {
     "#
 }
 
-#[mono_test]
+#[mono_test(wasm32 = "true")]
 fn peano1() {
     r#"
     Peano : [S Peano, Z]
@@ -888,7 +953,7 @@ fn optional_when() {
     "#
 }
 
-#[mono_test]
+#[mono_test(wasm32 = "true")]
 fn nested_pattern_match() {
     r#"
     Maybe a : [Nothing, Just a]
@@ -1357,6 +1422,26 @@ fn specialize_ability_call() {
     )
 }
 
+#[mono_test]
+fn ability_impl_tail_calls_its_member() {
+    indoc!(
+        r#"
+        app "test" provides [main] to "./platform"
+
+        Countdown has
+            countdown : a, U64 -> U64 | a has Countdown
+
+        Id := {} has [Countdown {countdown}]
+
+        countdown : Id, U64 -> U64
+        countdown = \@Id {}, n ->
+            if n == 0 then 0 else countdown (@Id {}) (n - 1)
+
+        main = countdown (@Id {}) 10
+        "#
+    )
+}
+
 #[mono_test]
 fn opaque_assign_to_symbol() {
     indoc!(
@@ -2889,3 +2974,128 @@ fn layout_cache_structure_with_multiple_recursive_structures() {
         "#
     )
 }
+
+#[mono_test]
+fn when_on_many_strings_switches_on_length() {
+    indoc!(
+        r#"
+        app "test" provides [main] to "./platform"
+
+        main =
+            when "four" is
+                "one" -> 1u8
+                "two" -> 2u8
+                "four" -> 4u8
+                "eleven" -> 11u8
+                _ -> 0u8
+        "#
+    )
+}
+
+#[mono_test]
+fn when_on_alternatives_shares_one_branch() {
+    indoc!(
+        r#"
+        app "test" provides [main] to "./platform"
+
+        main =
+            x : U8
+            x = 4
+
+            when x is
+                1 | 2 | 3 -> 10u8
+                4 -> 20u8
+                _ -> 30u8
+        "#
+    )
+}
+
+#[mono_test]
+fn inc_dec_str_used_after_call() {
+    indoc!(
+        r#"
+        app "test" provides [main] to "./platform"
+
+        main =
+            s = Str.concat "a long string that is heap allocated" "!"
+            t = Str.concat s "?"
+
+            Str.concat s t
+        "#
+    )
+}
+
+#[mono_test]
+fn inc_dec_list_consumed_in_one_branch() {
+    indoc!(
+        r#"
+        app "test" provides [main] to "./platform"
+
+        f : List U8, Bool -> List U8
+        f = \list, b ->
+            if b then
+                List.append list 1
+            else
+                []
+
+        main = f [1, 2, 3] Bool.true
+        "#
+    )
+}
+
+#[mono_test]
+fn inc_dec_recursive_tag_union_dropped() {
+    indoc!(
+        r#"
+        app "test" provides [main] to "./platform"
+
+        LinkedList : [Nil, Cons U8 LinkedList]
+
+        head : LinkedList -> U8
+        head = \list ->
+            when list is
+                Cons x _ -> x
+                Nil -> 0
+
+        main = head (Cons 1 (Cons 2 Nil))
+        "#
+    )
+}
+
+#[mono_test]
+fn inspect_derived_record_and_opaque() {
+    indoc!(
+        r#"
+        app "test" provides [main] to "./platform"
+
+        Age := U8 has [Inspect]
+
+        main =
+            Inspect.inspect { name: "Roc", age: @Age 3, tags: [Fast, Friendly (Ok 1u8)] }
+        "#
+    )
+}
+
+#[mono_test(wasm32 = "true")]
+fn option_like_unions_recursive_and_not() {
+    indoc!(
+        r#"
+        app "test" provides [main] to "./platform"
+
+        Option a : [None, Some a]
+
+        Chain : [End, Link U8 Chain]
+
+        firstLink : Chain -> Option U8
+        firstLink = \chain ->
+            when chain is
+                Link x _ -> Some x
+                End -> None
+
+        main =
+            when firstLink (Link 1 End) is
+                Some x -> x
+                None -> 0
+        "#
+    )
+}
@@ -7,6 +7,7 @@ use quote::quote;
 #[proc_macro_attribute]
 pub fn mono_test(args: TokenStream, item: TokenStream) -> TokenStream {
     let mut no_check = false;
+    let mut wasm32 = false;
     let mut mode = "exec".to_owned();
     for arg in syn::parse_macro_input!(args as syn::AttributeArgs) {
         use syn::{Lit, Meta, MetaNameValue, NestedMeta};
@@ -20,7 +21,10 @@ pub fn mono_test(args: TokenStream, item: TokenStream) -> TokenStream {
                 mode = s.value();
             }
             if path.is_ident("no_check") {
-                no_check = true;
+                no_check = parse_bool("no_check", &s);
+            }
+            if path.is_ident("wasm32") {
+                wasm32 = parse_bool("wasm32", &s);
             }
         }
     }
@@ -36,13 +40,50 @@ pub fn mono_test(args: TokenStream, item: TokenStream) -> TokenStream {
     let visibility = &task_fn.vis;
     let attributes = task_fn.attrs;
 
-    let result = quote! {
+    let options = |target_info: proc_macro2::TokenStream| {
+        quote! {
+            MonoTestOptions {
+                mode: #mode,
+                no_check: #no_check,
+                target_info: #target_info,
+            }
+        }
+    };
+
+    let x86_64 = options(quote! { roc_target::TargetInfo::default_x86_64() });
+
+    let mut result = quote! {
         #[test]
         #(#attributes)*
         #visibility fn #name(#args) {
-            compiles_to_ir(#name_str, #body, &#mode, #no_check);
+            compiles_to_ir(#name_str, #body, #x86_64);
 
         }
     };
+
+    // the same test again with 4-byte pointers, its IR goes to its own file
+    if wasm32 {
+        let wasm32_name = syn::Ident::new(&format!("{}_wasm32", name_str), name.span());
+        let wasm32_name_str = wasm32_name.to_string();
+        let wasm32_target = options(quote! { roc_target::TargetInfo::default_wasm32() });
+
+        result.extend(quote! {
+            #[test]
+            #(#attributes)*
+            #visibility fn #wasm32_name(#args) {
+                compiles_to_ir(#wasm32_name_str, #body, #wasm32_target);
+
+            }
+        });
+    }
+
     result.into()
 }
+
+fn parse_bool(name: &str, value: &syn::LitStr) -> bool {
+    match value.value().as_str() {
+        "true" => true,
+        "false" => false,
+        other => panic!("{name} takes \"true\" or \"false\", not {other:?}"),
+    }
+}
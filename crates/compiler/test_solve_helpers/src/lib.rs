@@ -302,6 +302,7 @@ impl Program {
             home: self.home,
             interns: &self.interns,
             print_lambda_names: true,
+            print_regions: false,
         };
         pretty_write_declarations(writer, &ctx, &self.declarations)
     }
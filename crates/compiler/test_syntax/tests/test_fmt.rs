@@ -11,6 +11,7 @@ mod test_fmt {
     use roc_parse::module::{self, module_defs};
     use roc_parse::parser::Parser;
     use roc_parse::state::State;
+    use roc_parse::test_helpers::{parse_defs_with, parse_expr_with};
     use roc_test_utils::{assert_multiline_str_eq, workspace_root};
     use test_syntax::test_helpers::Input;
 
@@ -5843,6 +5844,38 @@ mod test_fmt {
         );
     }
 
+    // FRAGMENTS
+
+    #[test]
+    fn format_expr_fragment_at_indent() {
+        let arena = Bump::new();
+        let expr = parse_expr_with(&arena, "when  x  is\n  A ->   1\n  B->2").unwrap();
+
+        assert_multiline_str_eq!(
+            roc_fmt::format_expr(&arena, &expr, 4),
+            "    when x is\n        A -> 1\n        B -> 2"
+        );
+    }
+
+    #[test]
+    fn format_def_fragments() {
+        let arena = Bump::new();
+        let defs = parse_defs_with(
+            &arena,
+            "Pair a :  [ Pair a a ]\n\nswap = \\Pair x  y  -> Pair y x\n",
+        )
+        .unwrap();
+        let formatted: Vec<&str> = defs
+            .defs()
+            .map(|def| roc_fmt::format_def(&arena, def, 0))
+            .collect();
+
+        assert_eq!(
+            formatted,
+            vec!["Pair a : [Pair a a]", "swap = \\Pair x y -> Pair y x"]
+        );
+    }
+
     // this is a parse error atm
     //    #[test]
     //    fn multiline_apply() {
@@ -1717,6 +1717,8 @@ impl Subs {
     pub const AB_HASH: SubsSlice<Symbol>     = SubsSlice::new(3, 1);
     #[rustfmt::skip]
     pub const AB_EQ: SubsSlice<Symbol>       = SubsSlice::new(4, 1);
+    #[rustfmt::skip]
+    pub const AB_INSPECT: SubsSlice<Symbol>  = SubsSlice::new(5, 1);
 
     pub fn new() -> Self {
         Self::with_capacity(0)
@@ -1741,6 +1743,7 @@ impl Subs {
         symbol_names.push(Symbol::HASH_HASHER);
         symbol_names.push(Symbol::HASH_HASH_ABILITY);
         symbol_names.push(Symbol::BOOL_EQ);
+        symbol_names.push(Symbol::INSPECT_INSPECT_ABILITY);
 
         let mut subs = Subs {
             utable: UnificationTable::default(),
@@ -2139,9 +2142,8 @@ impl Subs {
         self.uls_of_var.rollback_to(snapshot.uls_of_var_snapshot);
     }
 
-    pub fn commit_snapshot(&mut self, _snapshot: SubsSnapshot) {
-        // self.utable.commit(snapshot.utable_snapshot)
-        // self.uls_of_var.commit(snapshot.uls_of_var_snapshot)
+    pub fn commit_snapshot(&mut self, snapshot: SubsSnapshot) {
+        self.utable.commit(snapshot.utable_snapshot);
     }
 
     pub fn vars_since_snapshot(&mut self, snapshot: &SubsSnapshot) -> core::ops::Range<Variable> {
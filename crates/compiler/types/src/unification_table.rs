@@ -7,9 +7,26 @@ use roc_serialize::bytes;
 pub struct UnificationTable {
     contents: Vec<Content>,
     metadata: Vec<Combine>,
+    /// While a snapshot is open, the old value of every entry that is changed, so that a rollback
+    /// can restore it without a copy of the whole table.
+    undo_log: Vec<Undo>,
+    /// The length of the table when the innermost open snapshot was taken. Entries that were
+    /// pushed after that are not logged, a rollback removes them anyway.
+    snapshot_len: Option<usize>,
 }
 
-pub(crate) struct Snapshot(UnificationTable);
+/// Snapshots must be committed or rolled back in the reverse order they were taken in.
+pub(crate) struct Snapshot {
+    len: usize,
+    undo_len: usize,
+    outer_len: Option<usize>,
+}
+
+#[derive(Clone, Copy)]
+enum Undo {
+    Contents(usize, Content),
+    Metadata(usize, Combine),
+}
 
 #[derive(Debug, Clone, Copy)]
 enum Combine {
@@ -42,6 +59,8 @@ impl UnificationTable {
         Self {
             contents: Vec::with_capacity(cap),
             metadata: Vec::with_capacity(cap),
+            undo_log: Vec::new(),
+            snapshot_len: None,
         }
     }
 
@@ -107,8 +126,10 @@ impl UnificationTable {
     ) {
         let index = key.index() as usize;
 
+        self.log_contents(index);
         self.contents[index] = content;
 
+        self.log_metadata(index);
         self.metadata[index] = Combine::Root(Root { rank, mark, copy });
     }
 
@@ -216,6 +237,8 @@ impl UnificationTable {
     where
         F: Fn(&mut Root) -> T,
     {
+        self.log_metadata(key.index() as usize);
+
         match &mut self.metadata[key.index() as usize] {
             Combine::Root(root) => f(root),
             Combine::Redirect(_) => {
@@ -247,6 +270,7 @@ impl UnificationTable {
     #[allow(unused)]
     #[inline(always)]
     pub fn set_content_unchecked(&mut self, key: Variable, value: Content) {
+        self.log_contents(key.index() as usize);
         self.contents[key.index() as usize] = value;
     }
 
@@ -273,6 +297,7 @@ impl UnificationTable {
     #[inline(always)]
     pub fn set_content(&mut self, key: Variable, value: Content) {
         let index = self.root_key(key).index() as usize;
+        self.log_contents(index);
         self.contents[index] = value;
     }
 
@@ -282,9 +307,14 @@ impl UnificationTable {
     pub fn root_key(&mut self, mut key: Variable) -> Variable {
         let root = self.root_key_without_compacting(key);
 
-        while let Combine::Redirect(redirect) = &mut self.metadata[key.index() as usize] {
-            key = *redirect;
-            *redirect = root;
+        // path compression: point every variable on the way directly to the root
+        while let Combine::Redirect(redirect) = self.metadata[key.index() as usize] {
+            if redirect != root {
+                self.log_metadata(key.index() as usize);
+                self.metadata[key.index() as usize] = Combine::Redirect(root);
+            }
+
+            key = redirect;
         }
 
         root
@@ -299,17 +329,63 @@ impl UnificationTable {
         key
     }
 
-    pub(crate) fn snapshot(&self) -> Snapshot {
-        Snapshot(self.clone())
+    // SNAPSHOTS
+
+    pub(crate) fn snapshot(&mut self) -> Snapshot {
+        let snapshot = Snapshot {
+            len: self.len(),
+            undo_len: self.undo_log.len(),
+            outer_len: self.snapshot_len,
+        };
+
+        self.snapshot_len = Some(self.len());
+
+        snapshot
     }
 
     pub(crate) fn rollback_to(&mut self, snapshot: Snapshot) {
-        *self = snapshot.0;
+        while self.undo_log.len() > snapshot.undo_len {
+            match self.undo_log.pop() {
+                Some(Undo::Contents(index, content)) => self.contents[index] = content,
+                Some(Undo::Metadata(index, combine)) => self.metadata[index] = combine,
+                None => unreachable!(),
+            }
+        }
+
+        self.contents.truncate(snapshot.len);
+        self.metadata.truncate(snapshot.len);
+
+        self.snapshot_len = snapshot.outer_len;
+    }
+
+    pub(crate) fn commit(&mut self, snapshot: Snapshot) {
+        // an outer snapshot may still roll back the changes made since this one
+        if snapshot.outer_len.is_none() {
+            self.undo_log.clear();
+        }
+
+        self.snapshot_len = snapshot.outer_len;
+    }
+
+    #[inline(always)]
+    fn log_contents(&mut self, index: usize) {
+        if matches!(self.snapshot_len, Some(len) if index < len) {
+            self.undo_log
+                .push(Undo::Contents(index, self.contents[index]));
+        }
+    }
+
+    #[inline(always)]
+    fn log_metadata(&mut self, index: usize) {
+        if matches!(self.snapshot_len, Some(len) if index < len) {
+            self.undo_log
+                .push(Undo::Metadata(index, self.metadata[index]));
+        }
     }
 
     pub(crate) fn vars_since_snapshot(&self, snapshot: &Snapshot) -> std::ops::Range<Variable> {
         unsafe {
-            let start = Variable::from_index(snapshot.0.len() as u32);
+            let start = Variable::from_index(snapshot.len as u32);
             let end = Variable::from_index(self.len() as u32);
 
             start..end
@@ -347,6 +423,7 @@ impl UnificationTable {
 
         // redirect from -> to
         if from_index != to_index {
+            self.log_metadata(from_index);
             self.metadata[from_index] = Combine::Redirect(to)
         }
 
@@ -443,8 +520,59 @@ impl UnificationTable {
         let this = Self {
             contents: contents.to_vec(),
             metadata,
+            undo_log: Vec::new(),
+            snapshot_len: None,
         };
 
         (this, offset)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::UnificationTable;
+    use crate::subs::{Content, Mark, OptVariable, Rank, Variable};
+
+    fn fresh(table: &mut UnificationTable) -> Variable {
+        table.push(
+            Content::FlexVar(None),
+            Rank::toplevel(),
+            Mark::NONE,
+            OptVariable::NONE,
+        )
+    }
+
+    fn union(table: &mut UnificationTable, from: Variable, to: Variable) {
+        let desc = table.get_descriptor(to);
+        table.unify_roots(to, from, desc);
+    }
+
+    #[test]
+    fn nested_snapshots() {
+        let mut table = UnificationTable::default();
+        let a = fresh(&mut table);
+        let b = fresh(&mut table);
+        let c = fresh(&mut table);
+
+        let outer = table.snapshot();
+        union(&mut table, a, b);
+
+        let inner = table.snapshot();
+        union(&mut table, b, c);
+        fresh(&mut table);
+
+        // compresses the path a -> b -> c
+        assert_eq!(table.root_key(a), c);
+
+        table.rollback_to(inner);
+
+        assert_eq!(table.len(), 3);
+        assert_eq!(table.root_key_without_compacting(a), b);
+        assert!(!table.is_redirect(c));
+
+        table.commit(outer);
+
+        assert_eq!(table.root_key(a), b);
+        assert!(table.undo_log.is_empty());
+    }
+}
@@ -332,6 +332,8 @@ pub struct Env<'a> {
     pub subs: &'a mut Subs,
     seen_recursion: VecSet<(Variable, Variable)>,
     fixed_variables: VecSet<Variable>,
+    /// The number of unification steps left, when unification has a budget
+    fuel: Option<&'a mut u64>,
 }
 
 impl<'a> Env<'a> {
@@ -340,6 +342,17 @@ impl<'a> Env<'a> {
             subs,
             seen_recursion: Default::default(),
             fixed_variables: Default::default(),
+            fuel: None,
+        }
+    }
+
+    /// Takes one unit of `fuel` for every step of unification. Once it runs out, unification
+    /// stops descending into types and fails; the caller has to check whether that happened
+    /// and give up on the result.
+    pub fn with_fuel(self, fuel: &'a mut u64) -> Self {
+        Self {
+            fuel: Some(fuel),
+            ..self
         }
     }
 
@@ -457,6 +470,11 @@ fn unify_help<M: MetaCollector>(
             lambda_sets_to_specialize,
             extra_metadata,
         }
+    } else if env.fuel.as_deref() == Some(&0) {
+        // the types may be too big to even turn into error types; nobody reports them anyway
+        env.subs.union(var1, var2, Content::Error.into());
+
+        Unified::Failure(vars, ErrorType::Error, ErrorType::Error, Vec::new())
     } else {
         let error_context = if mismatches.contains(&Mismatch::TypeNotInRange) {
             ErrorTypeContext::ExpandRanges
@@ -499,6 +517,13 @@ pub fn unify_pool<M: MetaCollector>(
     var2: Variable,
     mode: Mode,
 ) -> Outcome<M> {
+    if let Some(fuel) = env.fuel.as_deref_mut() {
+        match fuel.checked_sub(1) {
+            Some(rest) => *fuel = rest,
+            None => return mismatch!("Out of fuel"),
+        }
+    }
+
     if env.subs.equivalent(var1, var2) {
         Outcome::default()
     } else {
@@ -1507,6 +1532,8 @@ fn separate_union_lambdas<M: MetaCollector>(
                                 pool.truncate(pool_snapshot);
                                 continue 'try_next_right;
                             } else {
+                                env.subs.commit_snapshot(subs_snapshot);
+
                                 let outcome = unify_pool(env, pool, var1, var2, mode);
                                 whole_outcome.union(outcome);
                             }
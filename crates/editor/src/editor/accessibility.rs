@@ -0,0 +1,90 @@
+use crate::editor::ed_error::EdResult;
+use crate::editor::mvc::ed_model::EdModel;
+use crate::ui::text::lines::{Lines, SelectableLines};
+use std::fs;
+use std::path::Path;
+
+// The editor draws all text itself with wgpu, so screen readers can't read it from the window.
+// Instead, the code and the position of the caret are written to a plain text file on every change,
+// a screen reader or a script that feeds one can follow that file, see Config::text_mirror_path.
+
+// e.g. "line 9 of 12, column 5: sum = 1 + 2", followed by the type or value of the selected expression
+pub fn caret_context(ed_model: &EdModel) -> EdResult<String> {
+    let caret_pos = ed_model.get_caret();
+    let line = ed_model.code_lines.get_line_ref(caret_pos.line)?;
+
+    let mut context = format!(
+        "line {} of {}, column {}: {}",
+        caret_pos.line + 1,
+        ed_model.code_lines.nr_of_lines(),
+        caret_pos.column + 1,
+        line.trim_end()
+    );
+
+    if let Some(selected_block) = ed_model.selected_block_opt {
+        let selected_pool_str = selected_block
+            .value_str_opt
+            .unwrap_or(selected_block.type_str);
+
+        context.push_str("\nselected: ");
+        context.push_str(selected_pool_str.as_str(ed_model.module.env.pool));
+    }
+
+    Ok(context)
+}
+
+// the caret context, an empty line and then all code
+pub fn text_mirror(ed_model: &EdModel) -> EdResult<String> {
+    Ok(format!(
+        "{}\n\n{}",
+        caret_context(ed_model)?,
+        ed_model.code_lines.all_lines_as_string()
+    ))
+}
+
+pub fn write_text_mirror(ed_model: &EdModel, mirror_path: &Path) -> EdResult<()> {
+    fs::write(mirror_path, text_mirror(ed_model)?)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+pub mod test_accessibility {
+    use crate::editor::accessibility::caret_context;
+    use crate::editor::mvc::ed_model::test_ed_model::{ed_model_from_dsl, init_model_refs};
+    use crate::editor::resources::strings::nr_hello_world_lines;
+    use crate::ui::text::lines::Lines;
+    use bumpalo::Bump;
+    use roc_module::symbol::ModuleIds;
+
+    #[test]
+    fn caret_context_of_line() -> Result<(), String> {
+        let code_lines = vec!["val = 1┃23".to_owned()];
+        let mut code_str = code_lines.join("\n").replace('┃', "");
+
+        let mut model_refs = init_model_refs();
+        let code_arena = Bump::new();
+        let module_ids = ModuleIds::default();
+
+        let ed_model = ed_model_from_dsl(
+            &mut code_str,
+            code_lines,
+            &mut model_refs,
+            &module_ids,
+            &code_arena,
+        )?;
+
+        let context = caret_context(&ed_model).map_err(|e| format!("{:?}", e))?;
+
+        assert_eq!(
+            context,
+            format!(
+                "line {} of {}, column 8: val = 123",
+                nr_hello_world_lines() + 1,
+                ed_model.code_lines.nr_of_lines()
+            )
+        );
+
+        Ok(())
+    }
+}
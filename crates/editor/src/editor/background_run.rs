@@ -0,0 +1,363 @@
+use bumpalo::Bump;
+use roc_build::link::{LinkType, LinkingStrategy};
+use roc_build::program::{
+    build_file, problem_reports_typechecked, standard_load_config, BuildFileError, BuildOrdering,
+    CodeGenBackend, CodeGenOptions,
+};
+use roc_collections::MutSet;
+use roc_gen_llvm::llvm::build::LlvmBackendMode;
+use roc_load::{LoadEvent, LoadingProblem, ProgressReporter, Threading};
+use roc_module::symbol::ModuleId;
+use roc_mono::ir::OptLevel;
+use roc_packaging::cache::{self, RocCacheDir};
+use roc_problem::cancel::CancelToken;
+use roc_reporting::report::RenderTarget;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+use target_lexicon::Triple;
+
+// Builds the app that is open in the editor on a separate thread and runs the binary it produced.
+// The build uses the same compiler pipeline as `roc build`, the progress, the problems and the
+// output of the app are collected with `poll` and shown in the run panel, see render_run.rs.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunPhase {
+    Building,
+    BuildFailed,
+    Running,
+    Exited(Option<i32>), // the exit code, None if the app was stopped by a signal
+}
+
+#[derive(Debug)]
+enum RunMsg {
+    Progress(String),
+    Problems(Vec<String>),
+    BuildFailed(String),
+    Built(PathBuf),
+    Output(String),
+    Exited(Option<i32>),
+}
+
+#[derive(Debug)]
+pub struct BackgroundRun {
+    pub file_path: PathBuf,
+    pub phase: RunPhase,
+    pub progress: String, // how far along the build is, replaced by every new step
+    pub problems: Vec<String>, // errors of the build, or warnings if there are no errors
+    pub output_lines: Vec<String>, // stdout and stderr of the app, in the order they were printed
+    cancel: CancelToken,
+    receiver: Receiver<RunMsg>,
+}
+
+impl BackgroundRun {
+    pub fn start(file_path: &Path) -> BackgroundRun {
+        let (sender, receiver) = mpsc::channel();
+        let cancel = CancelToken::new();
+
+        let thread_file_path = file_path.to_path_buf();
+        let thread_cancel = cancel.clone();
+
+        thread::spawn(move || build_and_run(thread_file_path, thread_cancel, sender));
+
+        BackgroundRun {
+            file_path: file_path.to_path_buf(),
+            phase: RunPhase::Building,
+            progress: "Loading".to_owned(),
+            problems: Vec::new(),
+            output_lines: Vec::new(),
+            cancel,
+            receiver,
+        }
+    }
+
+    // collect what happened since the last poll, returns true if something changed
+    pub fn poll(&mut self) -> bool {
+        let mut changed = false;
+
+        loop {
+            match self.receiver.try_recv() {
+                Ok(msg) => {
+                    self.update(msg);
+                    changed = true;
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    // the thread stopped without saying how, it panicked
+                    if self.is_running() {
+                        self.update(RunMsg::BuildFailed(
+                            "The build stopped unexpectedly, see the terminal.".to_owned(),
+                        ));
+                        changed = true;
+                    }
+
+                    break;
+                }
+            }
+        }
+
+        changed
+    }
+
+    fn update(&mut self, msg: RunMsg) {
+        match msg {
+            RunMsg::Progress(progress) => self.progress = progress,
+            RunMsg::Problems(problems) => self.problems = problems,
+            RunMsg::BuildFailed(reason) => {
+                self.progress = reason;
+                self.phase = RunPhase::BuildFailed;
+            }
+            RunMsg::Built(binary_path) => {
+                self.progress = format!("Built {}", binary_path.display());
+                self.phase = RunPhase::Running;
+            }
+            RunMsg::Output(line) => self.output_lines.push(line),
+            RunMsg::Exited(exit_code) => self.phase = RunPhase::Exited(exit_code),
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        matches!(self.phase, RunPhase::Building | RunPhase::Running)
+    }
+}
+
+impl Drop for BackgroundRun {
+    // stops the build, or the app if it is already running
+    fn drop(&mut self) {
+        self.cancel.cancel();
+    }
+}
+
+fn build_and_run(file_path: PathBuf, cancel: CancelToken, sender: Sender<RunMsg>) {
+    let binary_path = match build(file_path, cancel.clone(), &sender) {
+        Some(binary_path) => binary_path,
+        None => return,
+    };
+
+    if cancel.is_cancelled() || sender.send(RunMsg::Built(binary_path.clone())).is_err() {
+        return;
+    }
+
+    let exit_code = match run_binary(&binary_path, &cancel, &sender) {
+        Ok(exit_code) => exit_code,
+        Err(err) => {
+            let _ = sender.send(RunMsg::Output(format!(
+                "Failed to run {}: {}",
+                binary_path.display(),
+                err
+            )));
+
+            None
+        }
+    };
+
+    let _ = sender.send(RunMsg::Exited(exit_code));
+}
+
+// the path of the binary, None if the build failed or was cancelled
+fn build(file_path: PathBuf, cancel: CancelToken, sender: &Sender<RunMsg>) -> Option<PathBuf> {
+    let arena = Bump::new();
+    let triple = Triple::host();
+
+    let linking_strategy = if roc_linker::supported(LinkType::Executable, &triple) {
+        LinkingStrategy::Surgical
+    } else {
+        LinkingStrategy::Legacy
+    };
+
+    let code_gen_options = CodeGenOptions {
+        backend: CodeGenBackend::Llvm(LlvmBackendMode::Binary),
+        opt_level: OptLevel::Normal,
+        emit_debug_info: false,
+        stack_bytes: None,
+    };
+
+    let mut load_config = standard_load_config(
+        &triple,
+        BuildOrdering::BuildIfChecks,
+        Threading::AllAvailable,
+    );
+
+    load_config.render = RenderTarget::Generic;
+    load_config.cancel = cancel;
+    load_config.progress = progress_reporter(sender.clone());
+
+    let res_built_file = build_file(
+        &arena,
+        &triple,
+        file_path,
+        code_gen_options,
+        false,
+        LinkType::Executable,
+        linking_strategy,
+        false,
+        None,
+        RocCacheDir::Persistent(cache::roc_cache_dir().as_path()),
+        load_config,
+    );
+
+    let failure = match res_built_file {
+        Ok(built_file) => {
+            // the build printed the warnings to the terminal, like `roc build` does
+            if built_file.problems.warnings > 0 {
+                let _ = sender.send(RunMsg::Problems(vec![format!(
+                    "The build has {} warnings, see the terminal.",
+                    built_file.problems.warnings
+                )]));
+            }
+
+            return Some(built_file.binary_path);
+        }
+        Err(BuildFileError::ErrorModule { mut module, .. }) => {
+            let (problems, reports) = problem_reports_typechecked(&mut module);
+            let _ = sender.send(RunMsg::Problems(reports));
+
+            format!(
+                "The build failed with {} errors and {} warnings.",
+                problems.errors, problems.warnings
+            )
+        }
+        Err(BuildFileError::LoadingProblem(LoadingProblem::FormattedReport(report))) => {
+            let _ = sender.send(RunMsg::Problems(vec![report]));
+
+            "The build failed.".to_owned()
+        }
+        Err(BuildFileError::LoadingProblem(LoadingProblem::Cancelled)) => return None,
+        Err(BuildFileError::LoadingProblem(problem)) => {
+            format!("The build failed with error: {:?}", problem)
+        }
+    };
+
+    let _ = sender.send(RunMsg::BuildFailed(failure));
+
+    None
+}
+
+// sends a line that says how far along the load is after every step
+fn progress_reporter(sender: Sender<RunMsg>) -> ProgressReporter {
+    // the reporter has to be Sync, a Sender is not
+    let state = Mutex::new((sender, BuildProgress::default()));
+
+    ProgressReporter::new(move |event| {
+        let mut state = state.lock().unwrap();
+        let (sender, progress) = &mut *state;
+
+        progress.update(event);
+
+        let _ = sender.send(RunMsg::Progress(progress.line()));
+    })
+}
+
+#[derive(Debug, Default)]
+struct BuildProgress {
+    loaded: MutSet<ModuleId>,
+    parsed: MutSet<ModuleId>,
+    checked: MutSet<ModuleId>,
+    specialized: MutSet<ModuleId>,
+    finished: bool,
+}
+
+impl BuildProgress {
+    fn update(&mut self, event: LoadEvent) {
+        match event {
+            LoadEvent::HeaderLoaded(module_id) => {
+                self.loaded.insert(module_id);
+            }
+            LoadEvent::Parsed(module_id) => {
+                self.parsed.insert(module_id);
+            }
+            LoadEvent::Checked(module_id) => {
+                self.checked.insert(module_id);
+            }
+            LoadEvent::Specialized(module_id) => {
+                self.specialized.insert(module_id);
+            }
+            LoadEvent::Finished => self.finished = true,
+        }
+    }
+
+    // the first phase that some modules have not completed yet, like the progress line of `roc build`
+    fn line(&self) -> String {
+        let total = self.loaded.len();
+
+        let phase = if self.finished {
+            "Generating code and linking"
+        } else if self.parsed.len() < total {
+            "Parsing"
+        } else if self.checked.len() < total {
+            "Checking types"
+        } else {
+            "Specializing"
+        };
+
+        format!(
+            "{}: {}/{total} parsed, {}/{total} checked, {}/{total} specialized",
+            phase,
+            self.parsed.len(),
+            self.checked.len(),
+            self.specialized.len(),
+        )
+    }
+}
+
+// runs the binary until it exits, sends every line it prints, returns its exit code
+fn run_binary(
+    binary_path: &Path,
+    cancel: &CancelToken,
+    sender: &Sender<RunMsg>,
+) -> std::io::Result<Option<i32>> {
+    let mut child = Command::new(binary_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let mut reader_threads = Vec::new();
+
+    if let Some(stdout) = child.stdout.take() {
+        let stdout_sender = sender.clone();
+        reader_threads.push(thread::spawn(move || send_lines(stdout, &stdout_sender)));
+    }
+
+    if let Some(stderr) = child.stderr.take() {
+        let stderr_sender = sender.clone();
+        reader_threads.push(thread::spawn(move || send_lines(stderr, &stderr_sender)));
+    }
+
+    // the app is stopped when the run panel is closed
+    let exit_status = loop {
+        if let Some(exit_status) = child.try_wait()? {
+            break exit_status;
+        }
+
+        if cancel.is_cancelled() {
+            child.kill()?;
+            break child.wait()?;
+        }
+
+        thread::sleep(Duration::from_millis(20));
+    };
+
+    for reader_thread in reader_threads {
+        let _ = reader_thread.join();
+    }
+
+    Ok(exit_status.code())
+}
+
+fn send_lines(output: impl Read, sender: &Sender<RunMsg>) {
+    for line in BufReader::new(output).lines() {
+        match line {
+            Ok(line) => {
+                if sender.send(RunMsg::Output(line)).is_err() {
+                    return;
+                }
+            }
+            Err(_) => return,
+        }
+    }
+}
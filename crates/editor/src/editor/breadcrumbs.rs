@@ -0,0 +1,325 @@
+use crate::editor::config::Config;
+use crate::editor::ed_error::EdResult;
+use crate::editor::mvc::ed_model::EdModel;
+use crate::graphics::colors;
+use crate::graphics::primitives::rect::Rect;
+use crate::graphics::primitives::text as gr_text;
+use crate::ui::text::lines::SelectableLines;
+use cgmath::Vector2;
+use roc_ast::lang::core::ast::ASTNodeId;
+use roc_ast::lang::core::def::def2::Def2;
+use roc_ast::lang::core::expr::expr2::Expr2;
+use roc_code_markup::slow_pool::MarkNodeId;
+use winit::dpi::PhysicalSize;
+
+pub const CRUMB_SEPARATOR: &str = " > ";
+
+// An ancestor of the node at the caret, e.g. `main > List > [1] Str`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Crumb {
+    pub label: String,
+    pub mark_node_id_opt: Option<MarkNodeId>, // None for the module crumb, clicking it selects everything
+}
+
+// Path from the module to the selected block, or to the node at the caret if nothing is selected.
+pub fn get_breadcrumbs(ed_model: &EdModel) -> EdResult<Vec<Crumb>> {
+    let mut crumbs = Vec::new();
+
+    if let Some(file_stem) = ed_model.file_path.file_stem() {
+        crumbs.push(Crumb {
+            label: file_stem.to_string_lossy().to_string(),
+            mark_node_id_opt: None,
+        });
+    }
+
+    let start_id_opt = if let Some(selected_block) = &ed_model.selected_block_opt {
+        Some(selected_block.mark_node_id)
+    } else {
+        let caret_pos = ed_model.get_caret();
+
+        if ed_model.grid_node_map.node_exists_at_pos(caret_pos) {
+            Some(ed_model.grid_node_map.get_id_at_row_col(caret_pos)?)
+        } else if ed_model
+            .grid_node_map
+            .node_exists_at_pos(caret_pos.decrement_col())
+        {
+            Some(
+                ed_model
+                    .grid_node_map
+                    .get_id_at_row_col(caret_pos.decrement_col())?,
+            )
+        } else {
+            None
+        }
+    };
+
+    // innermost ancestor first
+    let mut ancestors: Vec<(ASTNodeId, MarkNodeId)> = Vec::new();
+    let mut curr_id_opt = start_id_opt;
+
+    while let Some(curr_id) = curr_id_opt {
+        // brackets, commas... refer to the same ast node as their Nested parent, keep only the outermost MarkupNode
+        if let Ok(ast_node_id) = ed_model.mark_id_ast_id_map.get(curr_id) {
+            match ancestors.last_mut() {
+                Some((prev_ast_node_id, prev_mark_id)) if *prev_ast_node_id == ast_node_id => {
+                    *prev_mark_id = curr_id
+                }
+                _ => ancestors.push((ast_node_id, curr_id)),
+            }
+        }
+
+        curr_id_opt = ed_model.mark_node_pool.get(curr_id).get_parent_id_opt();
+    }
+
+    ancestors.reverse();
+
+    let mut parent_ast_id_opt = None;
+
+    for (ast_node_id, mark_node_id) in ancestors {
+        crumbs.push(Crumb {
+            label: ast_node_label(ast_node_id, parent_ast_id_opt, ed_model)?,
+            mark_node_id_opt: Some(mark_node_id),
+        });
+
+        parent_ast_id_opt = Some(ast_node_id);
+    }
+
+    Ok(crumbs)
+}
+
+fn ast_node_label(
+    ast_node_id: ASTNodeId,
+    parent_ast_id_opt: Option<ASTNodeId>,
+    ed_model: &EdModel,
+) -> EdResult<String> {
+    let pool = &ed_model.module.env.pool;
+
+    let label = match ast_node_id {
+        ASTNodeId::ADefId(def_id) => match pool.get(def_id) {
+            Def2::ValueDef { identifier_id, .. } => ed_model
+                .module
+                .env
+                .ident_ids
+                .get_name_str_res(*identifier_id)?
+                .to_owned(),
+            Def2::Expect { .. } => "expect".to_owned(),
+            Def2::Blank => "def".to_owned(),
+            Def2::CommentsBefore { def_id, .. } | Def2::CommentsAfter { def_id, .. } => {
+                return ast_node_label(ASTNodeId::ADefId(*def_id), parent_ast_id_opt, ed_model)
+            }
+        },
+        ASTNodeId::AExprId(expr_id) => {
+            let expr_label = match pool.get(expr_id) {
+                Expr2::SmallInt { .. } | Expr2::I128 { .. } | Expr2::U128 { .. } => {
+                    "Num".to_owned()
+                }
+                Expr2::Float { .. } => "Frac".to_owned(),
+                Expr2::SmallStr(_) | Expr2::Str(_) => "Str".to_owned(),
+                Expr2::Var(symbol) => symbol.as_str(&ed_model.loaded_module.interns).to_owned(),
+                Expr2::List { .. } => "List".to_owned(),
+                Expr2::If { .. } => "if".to_owned(),
+                Expr2::When { .. } => "when".to_owned(),
+                Expr2::Call { .. } | Expr2::RunLowLevel { .. } => "call".to_owned(),
+                Expr2::Closure { .. } => "\\".to_owned(),
+                Expr2::Record { .. } | Expr2::EmptyRecord | Expr2::Update { .. } => {
+                    "Record".to_owned()
+                }
+                Expr2::Access { field, .. } | Expr2::Accessor { field, .. } => {
+                    format!(".{}", field.as_str(pool))
+                }
+                Expr2::Tag { name, .. } => name.as_str(pool).to_owned(),
+                Expr2::LetRec { .. } | Expr2::LetFunction { .. } | Expr2::LetValue { .. } => {
+                    "let".to_owned()
+                }
+                Expr2::InvalidLookup(_) | Expr2::Blank | Expr2::RuntimeError() => "expr".to_owned(),
+            };
+
+            // show which element of a list we're in
+            if let Some(ASTNodeId::AExprId(parent_expr_id)) = parent_ast_id_opt {
+                if let Expr2::List { elems, .. } = pool.get(parent_expr_id) {
+                    if let Some(index) = elems.iter(pool).position(|elem_id| *elem_id == expr_id) {
+                        return Ok(format!("[{}] {}", index, expr_label));
+                    }
+                }
+            }
+
+            expr_label
+        }
+    };
+
+    Ok(label)
+}
+
+pub fn crumbs_to_string(crumbs: &[Crumb]) -> String {
+    crumbs
+        .iter()
+        .map(|crumb| crumb.label.as_str())
+        .collect::<Vec<&str>>()
+        .join(CRUMB_SEPARATOR)
+}
+
+// The breadcrumb bar is drawn on the line right above the code.
+pub fn breadcrumbs_txt_coords(txt_coords: Vector2<f32>, glyph_dim_rect: &Rect) -> Vector2<f32> {
+    (txt_coords.x, txt_coords.y - glyph_dim_rect.height).into()
+}
+
+// the innermost crumb is highlighted
+pub fn build_breadcrumb_graphics(
+    crumbs: &[Crumb],
+    size: &PhysicalSize<u32>,
+    txt_coords: Vector2<f32>,
+    config: &Config,
+    glyph_dim_rect: &Rect,
+) -> glyph_brush::OwnedSection {
+    let area_bounds = (size.width as f32, size.height as f32);
+    let layout = wgpu_glyph::Layout::default().h_align(wgpu_glyph::HorizontalAlign::Left);
+
+    let mut glyph_texts = Vec::new();
+
+    for (index, crumb) in crumbs.iter().enumerate() {
+        let is_last = index + 1 == crumbs.len();
+
+        let color = if is_last {
+            config.ed_theme.ui_theme.light_brand
+        } else {
+            config.ed_theme.subtle_text
+        };
+
+        glyph_texts.push(
+            glyph_brush::OwnedText::new(crumb.label.clone())
+                .with_color(colors::to_slice(color))
+                .with_scale(config.code_font_size),
+        );
+
+        if !is_last {
+            glyph_texts.push(
+                glyph_brush::OwnedText::new(CRUMB_SEPARATOR)
+                    .with_color(colors::to_slice(config.ed_theme.subtle_text))
+                    .with_scale(config.code_font_size),
+            );
+        }
+    }
+
+    gr_text::owned_section_from_glyph_texts(
+        glyph_texts,
+        breadcrumbs_txt_coords(txt_coords, glyph_dim_rect).into(),
+        area_bounds,
+        layout,
+    )
+}
+
+// The font is monospace so we can find the clicked crumb by counting chars.
+pub fn crumb_at_pos<'a>(
+    crumbs: &'a [Crumb],
+    click_x: f32,
+    click_y: f32,
+    txt_coords: Vector2<f32>,
+    glyph_dim_rect: &Rect,
+) -> Option<&'a Crumb> {
+    let crumb_coords = breadcrumbs_txt_coords(txt_coords, glyph_dim_rect);
+
+    if click_y < crumb_coords.y
+        || click_y >= crumb_coords.y + glyph_dim_rect.height
+        || click_x < crumb_coords.x
+    {
+        return None;
+    }
+
+    let click_col = ((click_x - crumb_coords.x) / glyph_dim_rect.width) as usize;
+    let mut start_col = 0;
+
+    for crumb in crumbs {
+        let end_col = start_col + crumb.label.chars().count();
+
+        if click_col >= start_col && click_col < end_col {
+            return Some(crumb);
+        }
+
+        start_col = end_col + CRUMB_SEPARATOR.chars().count();
+    }
+
+    None
+}
+
+#[cfg(test)]
+pub mod test_breadcrumbs {
+    use crate::editor::breadcrumbs::{crumb_at_pos, crumbs_to_string, get_breadcrumbs, Crumb};
+    use crate::editor::mvc::ed_model::test_ed_model::{ed_model_from_dsl, init_model_refs};
+    use crate::graphics::primitives::rect::Rect;
+    use bumpalo::Bump;
+    use roc_module::symbol::ModuleIds;
+
+    fn assert_breadcrumbs(code_line: &str, expected_crumbs_str: &str) -> Result<(), String> {
+        let mut code_str = code_line.replace('┃', "");
+
+        let mut model_refs = init_model_refs();
+        let code_arena = Bump::new();
+        let module_ids = ModuleIds::default();
+
+        let ed_model = ed_model_from_dsl(
+            &mut code_str,
+            vec![code_line.to_owned()],
+            &mut model_refs,
+            &module_ids,
+            &code_arena,
+        )?;
+
+        let crumbs = get_breadcrumbs(&ed_model)?;
+
+        assert_eq!(crumbs_to_string(&crumbs), expected_crumbs_str);
+
+        Ok(())
+    }
+
+    #[test]
+    fn breadcrumbs_of_caret() -> Result<(), String> {
+        assert_breadcrumbs("val = 5┃", "val > Num")?;
+        assert_breadcrumbs("val = \"ab┃c\"", "val > Str")?;
+        assert_breadcrumbs("val = [ 1, \"a┃\" ]", "val > List > [1] Str")?;
+        assert_breadcrumbs("val = [ [ ┃1 ] ]", "val > List > [0] List > [0] Num")?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn click_on_crumb() {
+        let crumbs = vec![
+            Crumb {
+                label: "main".to_owned(),
+                mark_node_id_opt: None,
+            },
+            Crumb {
+                label: "val".to_owned(),
+                mark_node_id_opt: Some(3),
+            },
+        ];
+
+        let glyph_dim_rect = Rect {
+            top_left_coords: (0.0, 0.0).into(),
+            width: 10.0,
+            height: 20.0,
+            color: (0.0, 0.0, 0.0, 1.0),
+        };
+        let txt_coords = (10.0, 100.0).into();
+
+        // "main > val", the first char of `val` is at column 7
+        assert_eq!(
+            crumb_at_pos(&crumbs, 85.0, 85.0, txt_coords, &glyph_dim_rect),
+            Some(&crumbs[1])
+        );
+        assert_eq!(
+            crumb_at_pos(&crumbs, 15.0, 85.0, txt_coords, &glyph_dim_rect),
+            Some(&crumbs[0])
+        );
+        // click on the separator
+        assert_eq!(
+            crumb_at_pos(&crumbs, 65.0, 85.0, txt_coords, &glyph_dim_rect),
+            None
+        );
+        // click in the code
+        assert_eq!(
+            crumb_at_pos(&crumbs, 85.0, 105.0, txt_coords, &glyph_dim_rect),
+            None
+        );
+    }
+}
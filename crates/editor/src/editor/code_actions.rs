@@ -0,0 +1,192 @@
+use crate::editor::config::Config;
+use crate::editor::ed_error::EdResult;
+use crate::editor::mvc::ed_model::EdModel;
+use crate::graphics::colors;
+use crate::graphics::primitives::rect::Rect;
+use crate::graphics::primitives::text as gr_text;
+use crate::ui::text::lines::{Lines, SelectableLines};
+use cgmath::Vector2;
+use roc_ast::lang::core::ast::ASTNodeId;
+use roc_ast::lang::core::def::def2::Def2;
+use roc_reporting::code_action::{
+    can_problem_code_actions, type_problem_code_actions, CodeAction, Fix,
+};
+use winit::dpi::PhysicalSize;
+
+// Quick fixes for the problems that were found when the file was loaded, that apply to the caret position.
+// The editor applies them as AST edits, so only fixes for code it can display are offered.
+pub fn get_code_actions_at_caret(ed_model: &EdModel) -> EdResult<Vec<CodeAction>> {
+    let loaded_module = &ed_model.loaded_module;
+    let module_id = loaded_module.module_id;
+
+    let src = match loaded_module.sources.get(&module_id) {
+        Some((_, src)) => src,
+        None => return Ok(Vec::new()),
+    };
+
+    let mut actions = Vec::new();
+
+    if let Some(can_problems) = loaded_module.can_problems.get(&module_id) {
+        for problem in can_problems {
+            actions.extend(can_problem_code_actions(src, problem));
+        }
+    }
+
+    if let Some(type_problems) = loaded_module.type_problems.get(&module_id) {
+        for problem in type_problems {
+            actions.extend(type_problem_code_actions(
+                src,
+                problem,
+                &loaded_module.interns,
+            ));
+        }
+    }
+
+    let def_name_opt = def_name_at_caret(ed_model)?;
+
+    actions.retain(|action| match &action.fix {
+        Fix::AddImport { entry, .. } => !ed_model.module.ast.header.imports.contains(entry),
+        Fix::PrefixWithUnderscore { symbol, .. } => {
+            def_name_opt.as_deref() == Some(symbol.as_str(&loaded_module.interns))
+        }
+        // the editor can not add branches to an existing `when` yet
+        Fix::AddWhenBranches { .. } => false,
+    });
+
+    Ok(actions)
+}
+
+// name of the top level definition that contains the caret
+pub fn def_name_at_caret(ed_model: &EdModel) -> EdResult<Option<String>> {
+    let caret_pos = ed_model.get_caret();
+
+    let mut curr_id = if ed_model.grid_node_map.node_exists_at_pos(caret_pos) {
+        ed_model.grid_node_map.get_id_at_row_col(caret_pos)?
+    } else if ed_model
+        .grid_node_map
+        .node_exists_at_pos(caret_pos.decrement_col())
+    {
+        ed_model
+            .grid_node_map
+            .get_id_at_row_col(caret_pos.decrement_col())?
+    } else {
+        return Ok(None);
+    };
+
+    while let Some(parent_id) = ed_model.mark_node_pool.get(curr_id).get_parent_id_opt() {
+        curr_id = parent_id;
+    }
+
+    let mut def_id = match ed_model.mark_id_ast_id_map.get(curr_id)? {
+        ASTNodeId::ADefId(def_id) => def_id,
+        ASTNodeId::AExprId(_) => return Ok(None),
+    };
+
+    loop {
+        match ed_model.module.env.pool.get(def_id) {
+            Def2::ValueDef { identifier_id, .. } => {
+                let def_name = ed_model
+                    .module
+                    .env
+                    .ident_ids
+                    .get_name_str_res(*identifier_id)?;
+
+                return Ok(Some(def_name.to_owned()));
+            }
+            Def2::CommentsBefore {
+                def_id: inner_def_id,
+                ..
+            }
+            | Def2::CommentsAfter {
+                def_id: inner_def_id,
+                ..
+            } => def_id = *inner_def_id,
+            Def2::Expect { .. } | Def2::Blank => return Ok(None),
+        }
+    }
+}
+
+// The first action is shown at the end of the caret line, like a lightbulb.
+pub fn build_code_action_graphics(
+    code_actions: &[CodeAction],
+    ed_model: &EdModel,
+    size: &PhysicalSize<u32>,
+    txt_coords: Vector2<f32>,
+    config: &Config,
+    glyph_dim_rect: &Rect,
+) -> EdResult<Option<glyph_brush::OwnedSection>> {
+    let first_action = match code_actions.first() {
+        Some(action) => action,
+        None => return Ok(None),
+    };
+
+    let caret_line = ed_model.get_caret().line;
+    let line_len = ed_model.code_lines.line_len(caret_line)?;
+
+    let action_txt_coords = (
+        txt_coords.x + (line_len + 2) as f32 * glyph_dim_rect.width,
+        txt_coords.y + caret_line as f32 * glyph_dim_rect.height,
+    );
+
+    let more_str = if code_actions.len() > 1 {
+        format!(" (+{} more)", code_actions.len() - 1)
+    } else {
+        String::new()
+    };
+
+    let action_text = glyph_brush::OwnedText::new(format!(
+        "Quick fix: {}{} (Ctrl+.)",
+        first_action.title, more_str
+    ))
+    .with_color(colors::to_slice(config.ed_theme.ui_theme.light_brand))
+    .with_scale(config.code_font_size);
+
+    Ok(Some(gr_text::owned_section_from_glyph_texts(
+        vec![action_text],
+        action_txt_coords,
+        (size.width as f32, size.height as f32),
+        wgpu_glyph::Layout::default().h_align(wgpu_glyph::HorizontalAlign::Left),
+    )))
+}
+
+#[cfg(test)]
+pub mod test_code_actions {
+    use crate::editor::code_actions::get_code_actions_at_caret;
+    use crate::editor::mvc::ed_model::test_ed_model::{ed_model_from_dsl, init_model_refs};
+    use crate::editor::resources::strings::nr_hello_world_lines;
+    use crate::ui::text::lines::Lines;
+    use bumpalo::Bump;
+    use roc_module::symbol::ModuleIds;
+
+    #[test]
+    fn prefix_unused_def_with_underscore() -> Result<(), String> {
+        let code_line = "val = 5┃";
+        let mut code_str = code_line.replace('┃', "");
+
+        let mut model_refs = init_model_refs();
+        let code_arena = Bump::new();
+        let module_ids = ModuleIds::default();
+
+        let mut ed_model = ed_model_from_dsl(
+            &mut code_str,
+            vec![code_line.to_owned()],
+            &mut model_refs,
+            &module_ids,
+            &code_arena,
+        )?;
+
+        let code_actions = get_code_actions_at_caret(&ed_model)?;
+
+        assert_eq!(code_actions.len(), 1);
+        assert_eq!(code_actions[0].title, "Prefix `val` with an underscore");
+
+        ed_model.apply_code_action_at_caret()?;
+
+        let val_line = ed_model.code_lines.get_line_ref(nr_hello_world_lines())?;
+
+        assert_eq!(val_line, "_val = 5");
+        assert!(get_code_actions_at_caret(&ed_model)?.is_empty());
+
+        Ok(())
+    }
+}
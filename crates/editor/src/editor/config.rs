@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 
+use crate::editor::mvc::literal_fmt::LiteralFmtConfig;
 use crate::editor::theme::EdTheme;
 
 use super::resources::strings::START_TIP;
@@ -9,6 +11,11 @@ pub struct Config {
     pub code_font_size: f32,
     pub debug_font_size: f32,
     pub ed_theme: EdTheme,
+    pub literal_fmt: LiteralFmtConfig,
+    pub soft_wrap: bool, // wrap lines that are too long for the window instead of scrolling horizontally
+    pub caret_blink_ms: Option<u64>, // time the caret is shown and then hidden, None for a caret that does not blink
+    pub text_mirror_path: Option<PathBuf>, // file that is kept up to date with the code and the caret for screen readers, see accessibility.rs
+    pub show_minimap: bool, // overview of the whole file on the right edge of the window, see minimap.rs
 }
 
 impl Default for Config {
@@ -17,11 +24,23 @@ impl Default for Config {
             code_font_size: 30.0,
             debug_font_size: 20.0,
             ed_theme: EdTheme::default(),
+            literal_fmt: LiteralFmtConfig::default(),
+            soft_wrap: false,
+            caret_blink_ms: None,
+            text_mirror_path: None,
+            show_minimap: true,
         }
     }
 }
 
 impl Config {
+    pub fn high_contrast() -> Self {
+        Self {
+            ed_theme: EdTheme::high_contrast(),
+            ..Self::default()
+        }
+    }
+
     pub fn make_code_txt_xy(&self) -> (f32, f32) {
         (
             self.code_font_size,
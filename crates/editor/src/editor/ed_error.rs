@@ -191,11 +191,6 @@ pub enum EdError {
         backtrace: Backtrace,
     },
 
-    #[snafu(display(
-        "RocCheckFailed: `cargo run check`/`roc check` detected errors(see terminal)."
-    ))]
-    RocCheckFailed,
-
     #[snafu(display("ParseError: Failed to parse AST: SyntaxError: {}.", syntax_err))]
     SrcParseError {
         syntax_err: String,
@@ -0,0 +1,665 @@
+use crate::editor::ed_error::EdResult;
+use crate::editor::mvc::ed_model::{EdModel, SelectedBlock};
+use roc_ast::lang::core::ast::ASTNodeId;
+use roc_ast::lang::core::def::def2::{Def2, DefId};
+use roc_ast::lang::core::expr::expr2::{Expr2, ExprId, FloatVal, IntVal};
+use roc_ast::lang::core::expr::record_field::RecordField;
+use roc_ast::lang::core::pattern::Pattern2;
+use roc_ast::lang::core::val_def::ValueDef;
+use roc_ast::mem_pool::pool::Pool;
+use roc_ast::mem_pool::pool_str::PoolStr;
+use roc_module::symbol::{IdentId, Symbol};
+use std::cmp::Ordering;
+use std::fmt;
+
+// "Evaluate selection": runs the selected expression and shows its value in the tooltip of the selection.
+// Only pure code is evaluated: literals, lists, records, tags, functions and defs of this module,
+// and the builtins in `is_builtin`. Anything that refers to another module may perform effects,
+// so evaluation stops there. Numbers are not checked against the width of their type.
+
+// evaluation gives up after this many steps, the selection may not terminate
+const MAX_STEPS: usize = 100_000;
+
+#[derive(Debug, Clone)]
+pub enum Value {
+    Int(i128),
+    Float(f64),
+    Bool(bool),
+    Str(String),
+    List(Vec<Value>),
+    Record(Vec<(String, Value)>),
+    Tag(String, Vec<Value>),
+    Closure {
+        params: Vec<Option<Symbol>>, // None for `_`
+        body: ExprId,
+        captured: Vec<(Symbol, Value)>,
+    },
+    Builtin(Symbol),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Int(int) => write!(f, "{}", int),
+            Value::Float(float) if float.fract() == 0.0 && float.is_finite() => {
+                write!(f, "{:.1}", float)
+            }
+            Value::Float(float) => write!(f, "{}", float),
+            Value::Bool(true) => write!(f, "Bool.true"),
+            Value::Bool(false) => write!(f, "Bool.false"),
+            Value::Str(string) => write!(f, "{:?}", string),
+            Value::List(elems) => {
+                write!(f, "[")?;
+
+                for (index, elem) in elems.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+
+                    write!(f, "{}", elem)?;
+                }
+
+                write!(f, "]")
+            }
+            Value::Record(fields) if fields.is_empty() => write!(f, "{{}}"),
+            Value::Record(fields) => {
+                write!(f, "{{ ")?;
+
+                for (index, (name, value)) in fields.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+
+                    write!(f, "{}: {}", name, value)?;
+                }
+
+                write!(f, " }}")
+            }
+            Value::Tag(name, arguments) => {
+                write!(f, "{}", name)?;
+
+                for argument in arguments {
+                    match argument {
+                        Value::Tag(_, nested) if !nested.is_empty() => {
+                            write!(f, " ({})", argument)?
+                        }
+                        _ => write!(f, " {}", argument)?,
+                    }
+                }
+
+                Ok(())
+            }
+            Value::Closure { .. } | Value::Builtin(_) => write!(f, "<function>"),
+        }
+    }
+}
+
+// Runs the command of the "Evaluate" plugin: the tooltip of the selection gets the value of the selected expression.
+pub fn evaluate_selection(ed_model: &mut EdModel) -> EdResult<()> {
+    let selected_block = match ed_model.selected_block_opt {
+        Some(selected_block) => selected_block,
+        None => {
+            println!("\nSelect an expression with ctrl+shift+up to evaluate it.");
+
+            return Ok(());
+        }
+    };
+
+    let pool: &Pool = ed_model.module.env.pool;
+
+    let expr_id_opt = match selected_block.ast_node_id {
+        ASTNodeId::AExprId(expr_id) => Some(expr_id),
+        ASTNodeId::ADefId(def_id) => def_expr(pool, def_id).map(|(_, expr_id)| expr_id),
+    };
+
+    let tooltip = match expr_id_opt.map(|expr_id| evaluate(ed_model, expr_id)) {
+        Some(Ok(value)) => format!(
+            "{} : {}",
+            value,
+            selected_block.type_str.as_str(ed_model.module.env.pool)
+        ),
+        Some(Err(reason)) => format!("can't evaluate: {}", reason),
+        None => "can't evaluate: this def has no value yet".to_owned(),
+    };
+
+    ed_model.selected_block_opt = Some(SelectedBlock {
+        value_str_opt: Some(PoolStr::new(&tooltip, ed_model.module.env.pool)),
+        ..selected_block
+    });
+    ed_model.dirty = true;
+
+    Ok(())
+}
+
+pub fn evaluate(ed_model: &EdModel, expr_id: ExprId) -> Result<Value, String> {
+    let mut evaluator = Evaluator {
+        ed_model,
+        pool: ed_model.module.env.pool,
+        steps: 0,
+    };
+
+    evaluator.eval(expr_id, &[])
+}
+
+// the identifier and the expression of a top-level def, skipping its comments
+fn def_expr(pool: &Pool, def_id: DefId) -> Option<(IdentId, ExprId)> {
+    match pool.get(def_id) {
+        Def2::ValueDef {
+            identifier_id,
+            expr_id,
+        } => Some((*identifier_id, *expr_id)),
+        Def2::Expect { .. } | Def2::Blank => None,
+        Def2::CommentsBefore { def_id, .. } | Def2::CommentsAfter { def_id, .. } => {
+            def_expr(pool, *def_id)
+        }
+    }
+}
+
+fn is_builtin(symbol: Symbol) -> bool {
+    matches!(
+        symbol,
+        Symbol::NUM_ADD
+            | Symbol::NUM_SUB
+            | Symbol::NUM_MUL
+            | Symbol::NUM_DIV_FRAC
+            | Symbol::NUM_DIV_TRUNC
+            | Symbol::NUM_REM
+            | Symbol::NUM_POW
+            | Symbol::NUM_NEG
+            | Symbol::NUM_ABS
+            | Symbol::NUM_LT
+            | Symbol::NUM_GT
+            | Symbol::NUM_LTE
+            | Symbol::NUM_GTE
+            | Symbol::NUM_TO_STR
+            | Symbol::BOOL_IS_EQ
+            | Symbol::BOOL_NEQ
+            | Symbol::BOOL_AND
+            | Symbol::BOOL_OR
+            | Symbol::BOOL_NOT
+            | Symbol::STR_CONCAT
+            | Symbol::STR_IS_EMPTY
+            | Symbol::LIST_LEN
+            | Symbol::LIST_IS_EMPTY
+            | Symbol::LIST_APPEND
+            | Symbol::LIST_PREPEND
+            | Symbol::LIST_CONCAT
+            | Symbol::LIST_REVERSE
+            | Symbol::LIST_SUM
+            | Symbol::LIST_MAP
+            | Symbol::LIST_KEEP_IF
+            | Symbol::LIST_WALK
+    )
+}
+
+struct Evaluator<'m, 'a> {
+    ed_model: &'m EdModel<'a>,
+    pool: &'m Pool,
+    steps: usize,
+}
+
+impl<'m, 'a> Evaluator<'m, 'a> {
+    fn eval(&mut self, expr_id: ExprId, locals: &[(Symbol, Value)]) -> Result<Value, String> {
+        self.steps += 1;
+
+        if self.steps > MAX_STEPS {
+            return Err("it takes too long, it may never finish".to_owned());
+        }
+
+        let pool = self.pool;
+
+        match pool.get(expr_id) {
+            Expr2::SmallInt { number, .. } => Ok(Value::Int(match number {
+                IntVal::I64(int) => *int as i128,
+                IntVal::U64(int) => *int as i128,
+                IntVal::I32(int) => *int as i128,
+                IntVal::U32(int) => *int as i128,
+                IntVal::I16(int) => *int as i128,
+                IntVal::U16(int) => *int as i128,
+                IntVal::I8(int) => *int as i128,
+                IntVal::U8(int) => *int as i128,
+            })),
+            Expr2::I128 { number, .. } => Ok(Value::Int(*number)),
+            Expr2::U128 { number, .. } => i128::try_from(*number)
+                .map(Value::Int)
+                .map_err(|_| "the number is too big".to_owned()),
+            Expr2::Float { number, .. } => Ok(Value::Float(match number {
+                FloatVal::F64(float) => *float,
+                FloatVal::F32(float) => *float as f64,
+            })),
+            Expr2::SmallStr(string) => Ok(Value::Str(string.as_str().to_owned())),
+            Expr2::Str(string) => Ok(Value::Str(string.as_str(pool).to_owned())),
+            Expr2::Var(symbol) => self.lookup(*symbol, locals),
+            Expr2::List { elems, .. } => {
+                let mut values = Vec::with_capacity(elems.len());
+
+                for elem_id in elems.iter(pool) {
+                    values.push(self.eval(*elem_id, locals)?);
+                }
+
+                Ok(Value::List(values))
+            }
+            Expr2::If {
+                branches,
+                final_else,
+                ..
+            } => {
+                for (cond_id, then_id) in branches.iter(pool) {
+                    if self.eval_bool(*cond_id, locals)? {
+                        return self.eval(*then_id, locals);
+                    }
+                }
+
+                self.eval(*final_else, locals)
+            }
+            Expr2::LetValue {
+                def_id, body_id, ..
+            } => {
+                let (pattern_id, value_id) = match pool.get(*def_id) {
+                    ValueDef::WithAnnotation {
+                        pattern_id,
+                        expr_id,
+                        ..
+                    }
+                    | ValueDef::NoAnnotation {
+                        pattern_id,
+                        expr_id,
+                        ..
+                    } => (*pattern_id, *expr_id),
+                };
+
+                let value = self.eval(value_id, locals)?;
+                let mut body_locals = locals.to_vec();
+
+                match pool.get(pattern_id) {
+                    Pattern2::Identifier(symbol) => body_locals.push((*symbol, value)),
+                    Pattern2::Underscore => {}
+                    _ => return Err("destructuring is not supported yet".to_owned()),
+                }
+
+                self.eval(*body_id, &body_locals)
+            }
+            Expr2::Call { args, expr_id, .. } => {
+                let function = self.eval(*expr_id, locals)?;
+                let mut arguments = Vec::with_capacity(args.len());
+
+                for (_, arg_id) in args.iter(pool) {
+                    arguments.push(self.eval(*arg_id, locals)?);
+                }
+
+                self.apply(function, arguments)
+            }
+            Expr2::Closure { args, body_id, .. } => {
+                let mut params = Vec::with_capacity(args.len());
+
+                for (_, pattern_id) in args.iter(pool) {
+                    match pool.get(*pattern_id) {
+                        Pattern2::Identifier(symbol) => params.push(Some(*symbol)),
+                        Pattern2::Underscore => params.push(None),
+                        _ => return Err("destructuring is not supported yet".to_owned()),
+                    }
+                }
+
+                Ok(Value::Closure {
+                    params,
+                    body: *body_id,
+                    captured: locals.to_vec(),
+                })
+            }
+            Expr2::Record { fields, .. } => {
+                let mut values = Vec::with_capacity(fields.len());
+
+                for field in fields.iter(pool) {
+                    match field {
+                        RecordField::LabeledValue(name, _, field_id) => {
+                            values.push((
+                                name.as_str(pool).to_owned(),
+                                self.eval(*field_id, locals)?,
+                            ));
+                        }
+                        RecordField::LabelOnly(name, _, symbol) => {
+                            values.push((
+                                name.as_str(pool).to_owned(),
+                                self.lookup(*symbol, locals)?,
+                            ));
+                        }
+                        RecordField::InvalidLabelOnly(..) => {
+                            return Err("the record has an invalid field".to_owned())
+                        }
+                    }
+                }
+
+                Ok(Value::Record(values))
+            }
+            Expr2::EmptyRecord => Ok(Value::Record(Vec::new())),
+            Expr2::Access { field, expr, .. } => {
+                let field = field.as_str(pool);
+
+                match self.eval(*expr, locals)? {
+                    Value::Record(fields) => fields
+                        .into_iter()
+                        .find(|(name, _)| name == field)
+                        .map(|(_, value)| value)
+                        .ok_or_else(|| format!("the record has no field {}", field)),
+                    _ => Err(format!(
+                        "can't access .{} on something that is not a record",
+                        field
+                    )),
+                }
+            }
+            Expr2::Tag {
+                name, arguments, ..
+            } => {
+                let mut values = Vec::with_capacity(arguments.len());
+
+                for (_, arg_id) in arguments.iter(pool) {
+                    values.push(self.eval(*arg_id, locals)?);
+                }
+
+                Ok(Value::Tag(name.as_str(pool).to_owned(), values))
+            }
+            Expr2::Blank => Err("the expression is not finished".to_owned()),
+            Expr2::InvalidLookup(_) | Expr2::RuntimeError() => {
+                Err("the expression has a problem".to_owned())
+            }
+            _ => Err("this kind of expression is not supported yet".to_owned()),
+        }
+    }
+
+    fn eval_bool(&mut self, expr_id: ExprId, locals: &[(Symbol, Value)]) -> Result<bool, String> {
+        match self.eval(expr_id, locals)? {
+            Value::Bool(boolean) => Ok(boolean),
+            other => Err(format!("expected a Bool, but got {}", other)),
+        }
+    }
+
+    fn lookup(&mut self, symbol: Symbol, locals: &[(Symbol, Value)]) -> Result<Value, String> {
+        if let Some((_, value)) = locals.iter().rev().find(|(local, _)| *local == symbol) {
+            return Ok(value.clone());
+        }
+
+        match symbol {
+            Symbol::BOOL_TRUE => return Ok(Value::Bool(true)),
+            Symbol::BOOL_FALSE => return Ok(Value::Bool(false)),
+            _ if is_builtin(symbol) => return Ok(Value::Builtin(symbol)),
+            _ => {}
+        }
+
+        if symbol.module_id() != self.ed_model.module.env.home {
+            return Err(format!(
+                "{} is defined in another module, it may perform effects",
+                self.name(symbol)
+            ));
+        }
+
+        let pool = self.pool;
+
+        let expr_id = self
+            .ed_model
+            .module
+            .ast
+            .def_ids
+            .iter()
+            .filter_map(|def_id| def_expr(pool, *def_id))
+            .find(|(ident_id, _)| *ident_id == symbol.ident_id())
+            .map(|(_, expr_id)| expr_id)
+            .ok_or_else(|| format!("{} is not defined", self.name(symbol)))?;
+
+        // top-level defs only see other top-level defs
+        self.eval(expr_id, &[])
+    }
+
+    fn apply(&mut self, function: Value, arguments: Vec<Value>) -> Result<Value, String> {
+        match function {
+            Value::Closure {
+                params,
+                body,
+                captured,
+            } => {
+                if params.len() != arguments.len() {
+                    return Err(format!(
+                        "the function takes {} arguments, but got {}",
+                        params.len(),
+                        arguments.len()
+                    ));
+                }
+
+                let mut locals = captured;
+
+                for (param, argument) in params.into_iter().zip(arguments) {
+                    if let Some(symbol) = param {
+                        locals.push((symbol, argument));
+                    }
+                }
+
+                self.eval(body, &locals)
+            }
+            Value::Builtin(symbol) => self.builtin(symbol, arguments),
+            other => Err(format!("{} is not a function", other)),
+        }
+    }
+
+    fn builtin(&mut self, symbol: Symbol, arguments: Vec<Value>) -> Result<Value, String> {
+        use Value::*;
+
+        match (symbol, arguments.as_slice()) {
+            (Symbol::NUM_ADD, [a, b]) => arithmetic(a, b, i128::checked_add, |x, y| x + y),
+            (Symbol::NUM_SUB, [a, b]) => arithmetic(a, b, i128::checked_sub, |x, y| x - y),
+            (Symbol::NUM_MUL, [a, b]) => arithmetic(a, b, i128::checked_mul, |x, y| x * y),
+            (Symbol::NUM_DIV_FRAC, [a, b]) => Ok(Float(as_float(a)? / as_float(b)?)),
+            (Symbol::NUM_DIV_TRUNC, [Int(a), Int(b)]) => a
+                .checked_div(*b)
+                .map(Int)
+                .ok_or_else(|| "division by zero".to_owned()),
+            (Symbol::NUM_REM, [Int(a), Int(b)]) => a
+                .checked_rem(*b)
+                .map(Int)
+                .ok_or_else(|| "division by zero".to_owned()),
+            (Symbol::NUM_POW, [Int(a), Int(b)]) => u32::try_from(*b)
+                .ok()
+                .and_then(|exponent| a.checked_pow(exponent))
+                .map(Int)
+                .ok_or_else(|| "the result overflows".to_owned()),
+            (Symbol::NUM_POW, [a, b]) => Ok(Float(as_float(a)?.powf(as_float(b)?))),
+            (Symbol::NUM_NEG, [Int(a)]) => a
+                .checked_neg()
+                .map(Int)
+                .ok_or_else(|| "the result overflows".to_owned()),
+            (Symbol::NUM_NEG, [Float(a)]) => Ok(Float(-a)),
+            (Symbol::NUM_ABS, [Int(a)]) => a
+                .checked_abs()
+                .map(Int)
+                .ok_or_else(|| "the result overflows".to_owned()),
+            (Symbol::NUM_ABS, [Float(a)]) => Ok(Float(a.abs())),
+            (Symbol::NUM_LT, [a, b]) => Ok(Bool(compare(a, b)? == Ordering::Less)),
+            (Symbol::NUM_GT, [a, b]) => Ok(Bool(compare(a, b)? == Ordering::Greater)),
+            (Symbol::NUM_LTE, [a, b]) => Ok(Bool(compare(a, b)? != Ordering::Greater)),
+            (Symbol::NUM_GTE, [a, b]) => Ok(Bool(compare(a, b)? != Ordering::Less)),
+            (Symbol::NUM_TO_STR, [number @ (Int(_) | Float(_))]) => Ok(Str(number.to_string())),
+            (Symbol::BOOL_IS_EQ, [a, b]) => Ok(Bool(equal(a, b)?)),
+            (Symbol::BOOL_NEQ, [a, b]) => Ok(Bool(!equal(a, b)?)),
+            (Symbol::BOOL_AND, [Bool(a), Bool(b)]) => Ok(Bool(*a && *b)),
+            (Symbol::BOOL_OR, [Bool(a), Bool(b)]) => Ok(Bool(*a || *b)),
+            (Symbol::BOOL_NOT, [Bool(a)]) => Ok(Bool(!a)),
+            (Symbol::STR_CONCAT, [Str(a), Str(b)]) => Ok(Str(format!("{}{}", a, b))),
+            (Symbol::STR_IS_EMPTY, [Str(a)]) => Ok(Bool(a.is_empty())),
+            (Symbol::LIST_LEN, [List(list)]) => Ok(Int(list.len() as i128)),
+            (Symbol::LIST_IS_EMPTY, [List(list)]) => Ok(Bool(list.is_empty())),
+            (Symbol::LIST_APPEND, [List(list), elem]) => {
+                let mut list = list.clone();
+                list.push(elem.clone());
+
+                Ok(List(list))
+            }
+            (Symbol::LIST_PREPEND, [List(list), elem]) => {
+                let mut list = list.clone();
+                list.insert(0, elem.clone());
+
+                Ok(List(list))
+            }
+            (Symbol::LIST_CONCAT, [List(a), List(b)]) => {
+                Ok(List(a.iter().chain(b.iter()).cloned().collect()))
+            }
+            (Symbol::LIST_REVERSE, [List(list)]) => Ok(List(list.iter().rev().cloned().collect())),
+            (Symbol::LIST_SUM, [List(list)]) => {
+                let mut sum = Int(0);
+
+                for elem in list {
+                    sum = arithmetic(&sum, elem, i128::checked_add, |x, y| x + y)?;
+                }
+
+                Ok(sum)
+            }
+            (Symbol::LIST_MAP, [List(list), function]) => {
+                let mut mapped = Vec::with_capacity(list.len());
+
+                for elem in list {
+                    mapped.push(self.apply(function.clone(), vec![elem.clone()])?);
+                }
+
+                Ok(List(mapped))
+            }
+            (Symbol::LIST_KEEP_IF, [List(list), predicate]) => {
+                let mut kept = Vec::new();
+
+                for elem in list {
+                    match self.apply(predicate.clone(), vec![elem.clone()])? {
+                        Bool(true) => kept.push(elem.clone()),
+                        Bool(false) => {}
+                        other => return Err(format!("expected a Bool, but got {}", other)),
+                    }
+                }
+
+                Ok(List(kept))
+            }
+            (Symbol::LIST_WALK, [List(list), state, function]) => {
+                let mut state = state.clone();
+
+                for elem in list {
+                    state = self.apply(function.clone(), vec![state, elem.clone()])?;
+                }
+
+                Ok(state)
+            }
+            _ => Err(format!("unexpected arguments for {}", self.name(symbol))),
+        }
+    }
+
+    fn name(&self, symbol: Symbol) -> String {
+        let ed_model = self.ed_model;
+
+        let name_opt = if symbol.module_id() == ed_model.module.env.home {
+            ed_model.module.env.ident_ids.get_name(symbol.ident_id())
+        } else {
+            ed_model
+                .loaded_module
+                .interns
+                .all_ident_ids
+                .get(&symbol.module_id())
+                .and_then(|ident_ids| ident_ids.get_name(symbol.ident_id()))
+        };
+
+        name_opt.unwrap_or("this value").to_owned()
+    }
+}
+
+fn as_float(value: &Value) -> Result<f64, String> {
+    match value {
+        Value::Int(int) => Ok(*int as f64),
+        Value::Float(float) => Ok(*float),
+        other => Err(format!("expected a number, but got {}", other)),
+    }
+}
+
+// integer literals are also used for fractions, so a mix of both is computed with floats
+fn arithmetic(
+    a: &Value,
+    b: &Value,
+    int_op: fn(i128, i128) -> Option<i128>,
+    float_op: fn(f64, f64) -> f64,
+) -> Result<Value, String> {
+    match (a, b) {
+        (Value::Int(x), Value::Int(y)) => int_op(*x, *y)
+            .map(Value::Int)
+            .ok_or_else(|| "the result overflows".to_owned()),
+        _ => Ok(Value::Float(float_op(as_float(a)?, as_float(b)?))),
+    }
+}
+
+fn compare(a: &Value, b: &Value) -> Result<Ordering, String> {
+    match (a, b) {
+        (Value::Int(x), Value::Int(y)) => Ok(x.cmp(y)),
+        _ => as_float(a)?
+            .partial_cmp(&as_float(b)?)
+            .ok_or_else(|| "can't compare NaN".to_owned()),
+    }
+}
+
+fn equal(a: &Value, b: &Value) -> Result<bool, String> {
+    use Value::*;
+
+    let all_equal = |xs: &[Value], ys: &[Value]| -> Result<bool, String> {
+        if xs.len() != ys.len() {
+            return Ok(false);
+        }
+
+        for (x, y) in xs.iter().zip(ys) {
+            if !equal(x, y)? {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    };
+
+    match (a, b) {
+        (Int(_) | Float(_), Int(_) | Float(_)) => Ok(compare(a, b)? == Ordering::Equal),
+        (Bool(x), Bool(y)) => Ok(x == y),
+        (Str(x), Str(y)) => Ok(x == y),
+        (List(xs), List(ys)) => all_equal(xs, ys),
+        (Record(xs), Record(ys)) => {
+            for (name, x) in xs {
+                match ys.iter().find(|(other_name, _)| other_name == name) {
+                    Some((_, y)) if equal(x, y)? => {}
+                    _ => return Ok(false),
+                }
+            }
+
+            Ok(xs.len() == ys.len())
+        }
+        (Tag(x, xs), Tag(y, ys)) => Ok(x == y && all_equal(xs, ys)?),
+        (Closure { .. } | Builtin(_), _) | (_, Closure { .. } | Builtin(_)) => {
+            Err("functions can't be compared".to_owned())
+        }
+        _ => Ok(false),
+    }
+}
+
+#[cfg(test)]
+pub mod test_evaluate {
+    use crate::editor::evaluate::Value;
+
+    #[test]
+    fn display_values() {
+        let value = Value::Record(vec![
+            ("name".to_owned(), Value::Str("Ada".to_owned())),
+            (
+                "scores".to_owned(),
+                Value::List(vec![Value::Int(1), Value::Float(2.0), Value::Float(2.5)]),
+            ),
+            (
+                "result".to_owned(),
+                Value::Tag(
+                    "Ok".to_owned(),
+                    vec![Value::Tag("Just".to_owned(), vec![Value::Bool(true)])],
+                ),
+            ),
+        ]);
+
+        assert_eq!(
+            value.to_string(),
+            r#"{ name: "Ada", scores: [1, 2.0, 2.5], result: Ok (Just Bool.true) }"#
+        );
+        assert_eq!(Value::Record(Vec::new()).to_string(), "{}");
+    }
+}
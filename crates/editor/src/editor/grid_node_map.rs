@@ -14,12 +14,14 @@ use roc_code_markup::markup::nodes::get_root_mark_node_id;
 use roc_code_markup::slow_pool::MarkNodeId;
 use roc_code_markup::slow_pool::SlowPool;
 use snafu::OptionExt;
-use std::cmp::Ordering;
+use std::cmp::{max, min, Ordering};
 use std::fmt;
+use std::ops::Range;
 
 #[derive(Debug)]
 pub struct GridNodeMap {
     pub lines: Vec<Vec<MarkNodeId>>,
+    pub wrap_width_opt: Option<usize>, // lines longer than this are soft-wrapped into several rows on screen, see viewport.rs
 }
 
 impl GridNodeMap {
@@ -414,12 +416,115 @@ impl GridNodeMap {
 
         NoDefMarkNodeBeforeLineNrSnafu { line_nr }.fail()
     }
+
+    pub fn set_wrap_width(&mut self, wrap_width_opt: Option<usize>) {
+        self.wrap_width_opt = wrap_width_opt.filter(|wrap_width| *wrap_width > 0);
+    }
+
+    // number of rows on screen that line_nr takes up, always 1 without soft wrap
+    pub fn nr_of_rows(&self, line_nr: usize) -> usize {
+        match (self.wrap_width_opt, self.lines.get(line_nr)) {
+            (Some(wrap_width), Some(line)) => max(1, (line.len() + wrap_width - 1) / wrap_width),
+            _ => 1,
+        }
+    }
+
+    // row and column on screen of a position in the text, the screen row is stored in TextPos.line
+    // A caret at the end of a row that was wrapped is shown at the start of the next row, except at the end of the line.
+    pub fn screen_pos(&self, txt_pos: TextPos) -> TextPos {
+        let first_row: usize = (0..txt_pos.line)
+            .map(|line_nr| self.nr_of_rows(line_nr))
+            .sum();
+
+        match self.wrap_width_opt {
+            Some(wrap_width) => {
+                let row_in_line = min(
+                    txt_pos.column / wrap_width,
+                    self.nr_of_rows(txt_pos.line) - 1,
+                );
+
+                TextPos {
+                    line: first_row + row_in_line,
+                    column: txt_pos.column - row_in_line * wrap_width,
+                }
+            }
+            None => TextPos {
+                line: first_row,
+                column: txt_pos.column,
+            },
+        }
+    }
+
+    // position in the text that is shown at a row and column on screen, used for mouse clicks and moving the caret between rows
+    // Columns past the end of a row are moved to the end of that row, rows past the last line to the end of the last line.
+    pub fn txt_pos_at_screen_pos(&self, screen_pos: TextPos) -> TextPos {
+        let mut first_row = 0;
+
+        for (line_nr, line) in self.lines.iter().enumerate() {
+            let nr_of_rows = self.nr_of_rows(line_nr);
+
+            if screen_pos.line < first_row + nr_of_rows {
+                let row_in_line = screen_pos.line - first_row;
+
+                let column = match self.wrap_width_opt {
+                    Some(wrap_width) if row_in_line + 1 < nr_of_rows => {
+                        // the end of a wrapped row is the start of the next one, stay on this row
+                        row_in_line * wrap_width + min(screen_pos.column, wrap_width - 1)
+                    }
+                    Some(wrap_width) => {
+                        min(row_in_line * wrap_width + screen_pos.column, line.len())
+                    }
+                    None => min(screen_pos.column, line.len()),
+                };
+
+                return TextPos {
+                    line: line_nr,
+                    column,
+                };
+            }
+
+            first_row += nr_of_rows;
+        }
+
+        let last_line_nr = self.lines.len() - 1;
+
+        TextPos {
+            line: last_line_nr,
+            column: self.lines[last_line_nr].len(),
+        }
+    }
+
+    // splits the columns of line_nr into the parts that are shown on the same row, as (screen position of first column, nr of columns)
+    pub fn screen_parts(&self, line_nr: usize, cols: Range<usize>) -> Vec<(TextPos, usize)> {
+        let mut parts = Vec::new();
+        let mut start_col = cols.start;
+
+        while start_col < cols.end {
+            let part_end_col = match self.wrap_width_opt {
+                Some(wrap_width) => min((start_col / wrap_width + 1) * wrap_width, cols.end),
+                None => cols.end,
+            };
+
+            parts.push((
+                self.screen_pos(TextPos {
+                    line: line_nr,
+                    column: start_col,
+                }),
+                part_end_col - start_col,
+            ));
+
+            start_col = part_end_col;
+        }
+
+        parts
+    }
 }
 
 impl Default for GridNodeMap {
     fn default() -> Self {
         GridNodeMap {
             lines: vec![Vec::new()],
+            wrap_width_opt: None,
         }
     }
 }
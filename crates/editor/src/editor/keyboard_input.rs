@@ -1,7 +1,8 @@
 use crate::editor::ed_error::EdResult;
+use crate::editor::keyboard_macro::{repeat_digit, MacroStep};
 use crate::editor::mvc::app_model::AppModel;
 use crate::editor::mvc::app_update::{
-    handle_copy, handle_cut, handle_paste, pass_keydown_to_focused,
+    handle_copy, handle_cut, handle_paste, pass_keydown_to_focused, pass_new_char_to_focused,
 };
 use crate::window::keyboard_input::from_winit;
 use winit::event::VirtualKeyCode::*;
@@ -19,6 +20,38 @@ pub fn handle_keydown(
 
     let modifiers = from_winit(&modifiers_winit);
 
+    // Ctrl+Alt is used to type chars on some keyboard layouts
+    if modifiers.alt && !modifiers.cmd_or_ctrl() {
+        match virtual_keycode {
+            M => {
+                app_model.keyboard_macro.toggle_recording();
+                return Ok(());
+            }
+            P => return replay_macro(app_model),
+            _ => {
+                if let Some(digit) = repeat_digit(virtual_keycode) {
+                    app_model.keyboard_macro.push_repeat_digit(digit);
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    app_model.keyboard_macro.record(MacroStep::KeyDown {
+        virtual_keycode,
+        modifiers: modifiers_winit,
+    });
+
+    dispatch_keydown(virtual_keycode, modifiers_winit, app_model)
+}
+
+fn dispatch_keydown(
+    virtual_keycode: VirtualKeyCode,
+    modifiers_winit: ModifiersState,
+    app_model: &mut AppModel,
+) -> EdResult<()> {
+    let modifiers = from_winit(&modifiers_winit);
+
     match virtual_keycode {
         Left | Up | Right | Down => {
             pass_keydown_to_focused(&modifiers, virtual_keycode, app_model)?
@@ -49,6 +82,29 @@ pub fn handle_keydown(
     Ok(())
 }
 
+fn replay_macro(app_model: &mut AppModel) -> EdResult<()> {
+    if let Some((steps, repeat)) = app_model.keyboard_macro.take_replay() {
+        for _ in 0..repeat {
+            for step in steps.iter() {
+                match *step {
+                    MacroStep::KeyDown {
+                        virtual_keycode,
+                        modifiers,
+                    } => dispatch_keydown(virtual_keycode, modifiers, app_model)?,
+                    MacroStep::NewChar {
+                        received_char,
+                        modifiers,
+                    } => {
+                        pass_new_char_to_focused(&received_char, app_model, modifiers)?;
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
 // pub fn handle_text_input(
 //     text_state: &mut String,
 //     elem_state: ElementState,
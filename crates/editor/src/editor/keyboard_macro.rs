@@ -0,0 +1,167 @@
+use winit::event::{ModifiersState, VirtualKeyCode};
+
+// `Alt+M` starts recording everything that is typed, `Alt+M` again stops the recording.
+// `Alt+P` replays it, type a number with `Alt` first to replay it that many times, e.g. `Alt+1 Alt+2 Alt+P`.
+// Together with the structural commands this repeats an edit on many expressions,
+// e.g. select the surrounding expression with `Ctrl+Shift+Up`, replace it and move to the next one.
+// Mouse clicks are not recorded, a macro should only move the caret with the keyboard.
+
+// a single input event, as it was passed to the editor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MacroStep {
+    KeyDown {
+        virtual_keycode: VirtualKeyCode,
+        modifiers: ModifiersState,
+    },
+    NewChar {
+        received_char: char,
+        modifiers: ModifiersState,
+    },
+}
+
+#[derive(Debug, Default)]
+pub struct KeyboardMacro {
+    recording_opt: Option<Vec<MacroStep>>, // Some while recording
+    recorded: Vec<MacroStep>,              // the last finished recording
+    repeat_opt: Option<usize>,             // nr of replays typed with Alt+digits
+}
+
+impl KeyboardMacro {
+    pub fn is_recording(&self) -> bool {
+        self.recording_opt.is_some()
+    }
+
+    pub fn toggle_recording(&mut self) {
+        match self.recording_opt.take() {
+            Some(steps) => {
+                println!(
+                    "\nRecorded a macro of {} steps, use `Alt+P` to replay it.",
+                    steps.len()
+                );
+
+                self.recorded = steps;
+            }
+            None => {
+                println!("\nRecording a macro, use `Alt+M` to stop.");
+
+                self.recording_opt = Some(Vec::new());
+            }
+        }
+    }
+
+    pub fn record(&mut self, step: MacroStep) {
+        if let Some(steps) = &mut self.recording_opt {
+            steps.push(step);
+        }
+    }
+
+    // Alt+1 Alt+2 sets the nr of replays to 12
+    pub fn push_repeat_digit(&mut self, digit: usize) {
+        let repeat = self.repeat_opt.unwrap_or(0);
+
+        self.repeat_opt = Some(repeat.saturating_mul(10).saturating_add(digit));
+    }
+
+    // the steps to replay and how many times
+    // Nothing is replayed while recording, a macro that replays itself would never end.
+    pub fn take_replay(&mut self) -> Option<(Vec<MacroStep>, usize)> {
+        let repeat = self.repeat_opt.take().unwrap_or(1);
+
+        if self.is_recording() {
+            println!("\nStop recording with `Alt+M` before replaying the macro.");
+
+            None
+        } else if self.recorded.is_empty() {
+            println!("\nThere is no macro to replay, use `Alt+M` to start recording one.");
+
+            None
+        } else {
+            Some((self.recorded.clone(), repeat))
+        }
+    }
+}
+
+pub fn repeat_digit(virtual_keycode: VirtualKeyCode) -> Option<usize> {
+    use VirtualKeyCode::*;
+
+    let digit = match virtual_keycode {
+        Key0 | Numpad0 => 0,
+        Key1 | Numpad1 => 1,
+        Key2 | Numpad2 => 2,
+        Key3 | Numpad3 => 3,
+        Key4 | Numpad4 => 4,
+        Key5 | Numpad5 => 5,
+        Key6 | Numpad6 => 6,
+        Key7 | Numpad7 => 7,
+        Key8 | Numpad8 => 8,
+        Key9 | Numpad9 => 9,
+        _ => return None,
+    };
+
+    Some(digit)
+}
+
+#[cfg(test)]
+pub mod test_keyboard_macro {
+    use crate::editor::keyboard_macro::{KeyboardMacro, MacroStep};
+    use winit::event::{ModifiersState, VirtualKeyCode};
+
+    fn new_char(received_char: char) -> MacroStep {
+        MacroStep::NewChar {
+            received_char,
+            modifiers: ModifiersState::empty(),
+        }
+    }
+
+    #[test]
+    fn record_and_replay() {
+        let mut keyboard_macro = KeyboardMacro::default();
+
+        // nothing is recorded before Alt+M
+        keyboard_macro.record(new_char('a'));
+        assert_eq!(keyboard_macro.take_replay(), None);
+
+        keyboard_macro.toggle_recording();
+        keyboard_macro.record(new_char('b'));
+        keyboard_macro.record(MacroStep::KeyDown {
+            virtual_keycode: VirtualKeyCode::Down,
+            modifiers: ModifiersState::empty(),
+        });
+
+        // a macro can't replay itself
+        assert_eq!(keyboard_macro.take_replay(), None);
+
+        keyboard_macro.toggle_recording();
+        keyboard_macro.record(new_char('c'));
+
+        let (steps, repeat) = keyboard_macro.take_replay().unwrap();
+
+        assert_eq!(repeat, 1);
+        assert_eq!(
+            steps,
+            vec![
+                new_char('b'),
+                MacroStep::KeyDown {
+                    virtual_keycode: VirtualKeyCode::Down,
+                    modifiers: ModifiersState::empty(),
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn repeat_count() {
+        let mut keyboard_macro = KeyboardMacro::default();
+
+        keyboard_macro.toggle_recording();
+        keyboard_macro.record(new_char('x'));
+        keyboard_macro.toggle_recording();
+
+        keyboard_macro.push_repeat_digit(1);
+        keyboard_macro.push_repeat_digit(2);
+        assert_eq!(keyboard_macro.take_replay().unwrap().1, 12);
+
+        // the count is only used for one replay
+        assert_eq!(keyboard_macro.take_replay().unwrap().1, 1);
+    }
+}
@@ -1,13 +1,16 @@
 use super::keyboard_input;
 use super::resources::strings::PLATFORM_DIR_NAME;
+use crate::editor::accessibility::write_text_mirror;
 use crate::editor::mvc::ed_view;
 use crate::editor::mvc::ed_view::RenderedWgpu;
+use crate::editor::mvc::literal_fmt::{fmt_literal_if_left, get_literal_at_caret};
 use crate::editor::resources::strings::{HELLO_WORLD, NOTHING_OPENED};
+use crate::editor::viewport::SCROLL_COLS_PER_LINE;
 use crate::editor::{
     config::Config,
     ed_error::print_err,
-    mvc::{app_model::AppModel, app_update, app_update::InputOutcome, ed_model},
-    theme::EdTheme,
+    mvc::{app_model::AppModel, app_update, app_update::InputOutcome, ed_model, ed_model::EdModel},
+    project_search::ProjectSearch,
 };
 use crate::graphics::{
     colors::to_wgpu_color,
@@ -27,21 +30,22 @@ use roc_ast::lang::env::Env;
 use roc_ast::mem_pool::pool::Pool;
 use roc_ast::module::load_module;
 use roc_load::Threading;
-use roc_module::symbol::IdentIds;
+use roc_module::symbol::{IdentIds, ModuleIds};
 use roc_packaging::cache::{self, RocCacheDir};
 use roc_types::subs::VarStore;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::env;
 use std::fs::{self, metadata, File};
 use std::io::Write;
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
 use std::{error::Error, io, path::Path};
 use wgpu::{CommandEncoder, LoadOp, RenderPass, TextureView};
 use wgpu_glyph::GlyphBrush;
 use winit::{
-    dpi::PhysicalSize,
+    dpi::{PhysicalPosition, PhysicalSize},
     event,
-    event::{Event, ModifiersState},
+    event::{ElementState, Event, ModifiersState, MouseButton, MouseScrollDelta},
     event_loop::ControlFlow,
     platform::run_return::EventLoopExtRunReturn,
 };
@@ -120,285 +124,484 @@ fn run_event_loop(project_path_opt: Option<&Path>) -> Result<(), Box<dyn Error>>
 
     let is_animating = true;
 
-    let mut env_pool = Pool::with_capacity(1024);
-    let env_arena = Bump::new();
-    let code_arena = Bump::new();
-
     let (file_path_buf, code_str) = read_main_roc_file(project_path_opt);
-    println!("Loading file {:?}...", file_path_buf);
 
-    let file_path = Path::new(&file_path_buf);
-    let loaded_module = load_module(
-        file_path,
-        RocCacheDir::Persistent(cache::roc_cache_dir().as_path()),
-        Threading::AllAvailable,
-    );
+    // white and bright colors on black, for people who need more contrast
+    let config: Config = if env::var("ROC_EDITOR_HIGH_CONTRAST").is_ok() {
+        Config::high_contrast()
+    } else {
+        Config::default()
+    }; //confy::load("roc_editor", None)?;
 
-    let mut var_store = VarStore::default();
-    let dep_idents = IdentIds::exposed_builtins(8);
-    let exposed_ident_ids = IdentIds::default();
-    let module_ids = loaded_module.interns.module_ids.clone();
+    let mut keyboard_modifiers = ModifiersState::empty();
+    let mut cursor_pos: PhysicalPosition<f64> = PhysicalPosition::new(0.0, 0.0);
+    // the caret is always shown right after typing
+    let mut caret_blink_start = Instant::now();
 
-    let env = Env::new(
-        loaded_module.module_id,
-        &env_arena,
-        &mut env_pool,
-        &mut var_store,
-        dep_idents,
-        &module_ids,
-        exposed_ident_ids,
-    );
+    let mut app_model_between_modules = AppModel::init(None);
 
-    let config: Config = Config::default(); //confy::load("roc_editor", None)?;
-    let ed_model_opt = {
-        let ed_model_res = ed_model::init_model(
-            &code_str,
-            file_path,
-            env,
-            loaded_module,
-            &code_arena,
-            CaretPos::End,
+    // the code of the modules that were switched away from, it is only written to disk on save
+    let mut unsaved_code: HashMap<PathBuf, String> = HashMap::new();
+
+    let mut module_to_open_opt = Some(ModuleToOpen {
+        file_path: file_path_buf,
+        code_str,
+        caret_pos: CaretPos::End,
+        project_search_opt: None,
+        switched_from_opt: None,
+    });
+
+    // The editor shows a single module. Opening another one stops the event loop, and runs it
+    // again with the other module, so everything the module that was shown borrowed is dropped.
+    while let Some(module_to_open) = module_to_open_opt.take() {
+        let switched_from_opt = module_to_open.switched_from_opt;
+
+        let mut storage = ModuleStorage::new(module_to_open.file_path, module_to_open.code_str);
+
+        let ed_model_opt = load_ed_model(
+            &mut storage,
+            module_to_open.caret_pos,
+            module_to_open.project_search_opt,
+            &config,
+            &mut glyph_brush,
         );
 
-        match ed_model_res {
-            Ok(mut ed_model) => {
-                ed_model.glyph_dim_rect_opt = Some(example_code_glyph_rect(
-                    &mut glyph_brush,
-                    config.code_font_size,
-                ));
+        // go back to the module that was shown if the other one can not be loaded
+        if ed_model_opt.is_none() {
+            if let Some(switched_from) = switched_from_opt {
+                module_to_open_opt = reopen_module(switched_from, &mut unsaved_code);
 
-                Some(ed_model)
-            }
-            Err(e) => {
-                print_err(&e);
-                None
+                if module_to_open_opt.is_some() {
+                    continue;
+                }
             }
         }
-    };
 
-    let mut rendered_wgpu_opt: Option<RenderedWgpu> = None;
+        let mut rendered_wgpu_opt: Option<RenderedWgpu> = None;
 
-    let mut app_model = AppModel::init(ed_model_opt);
+        let mut app_model = app_model_between_modules.with_ed_model(ed_model_opt);
 
-    let mut keyboard_modifiers = ModifiersState::empty();
-    let ed_theme = EdTheme::default();
+        // Render loop
+        window.request_redraw();
 
-    // Render loop
-    window.request_redraw();
+        event_loop.run_return(|event, _, control_flow| {
+            // TODO dynamically switch this on/off depending on whether any
+            // animations are running. Should conserve CPU usage and battery life!
+            if is_animating {
+                *control_flow = ControlFlow::Poll;
+            } else {
+                *control_flow = ControlFlow::Wait;
+            }
 
-    event_loop.run_return(|event, _, control_flow| {
-        // TODO dynamically switch this on/off depending on whether any
-        // animations are running. Should conserve CPU usage and battery life!
-        if is_animating {
-            *control_flow = ControlFlow::Poll;
-        } else {
-            *control_flow = ControlFlow::Wait;
-        }
+            match event {
+                //Close
+                Event::WindowEvent {
+                    event: event::WindowEvent::CloseRequested,
+                    ..
+                } => *control_flow = ControlFlow::Exit,
+                //Resize
+                Event::WindowEvent {
+                    event: event::WindowEvent::Resized(new_size),
+                    ..
+                } => {
+                    size = new_size;
+
+                    surface.configure(
+                        &gpu_device,
+                        &wgpu::SurfaceConfiguration {
+                            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                            format: color_format,
+                            width: size.width,
+                            height: size.height,
+                            present_mode: wgpu::PresentMode::Mailbox,
+                        },
+                    );
 
-        match event {
-            //Close
-            Event::WindowEvent {
-                event: event::WindowEvent::CloseRequested,
-                ..
-            } => *control_flow = ControlFlow::Exit,
-            //Resize
-            Event::WindowEvent {
-                event: event::WindowEvent::Resized(new_size),
-                ..
-            } => {
-                size = new_size;
-
-                surface.configure(
-                    &gpu_device,
-                    &wgpu::SurfaceConfiguration {
-                        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
-                        format: color_format,
-                        width: size.width,
-                        height: size.height,
-                        present_mode: wgpu::PresentMode::Mailbox,
-                    },
-                );
-
-                update_ortho_buffer(
-                    size.width,
-                    size.height,
-                    &gpu_device,
-                    &rect_resources.ortho.buffer,
-                    &cmd_queue,
-                );
-            }
-            //Received Character
-            Event::WindowEvent {
-                event: event::WindowEvent::ReceivedCharacter(ch),
-                ..
-            } => {
-                let input_outcome_res =
-                    app_update::handle_new_char(&ch, &mut app_model, keyboard_modifiers);
-                if let Err(e) = input_outcome_res {
-                    print_err(&e)
-                } else if let Ok(InputOutcome::Ignored) = input_outcome_res {
-                    println!("\nInput '{}' ignored!", ch);
-                } else {
-                    window.request_redraw()
+                    update_ortho_buffer(
+                        size.width,
+                        size.height,
+                        &gpu_device,
+                        &rect_resources.ortho.buffer,
+                        &cmd_queue,
+                    );
                 }
-            }
-            //Keyboard Input
-            Event::WindowEvent {
-                event: event::WindowEvent::KeyboardInput { input, .. },
-                ..
-            } => {
-                if let Some(virtual_keycode) = input.virtual_keycode {
-                    if let Some(ref mut ed_model) = app_model.ed_model_opt {
-                        if ed_model.has_focus {
-                            let keydown_res = keyboard_input::handle_keydown(
-                                input.state,
-                                virtual_keycode,
-                                keyboard_modifiers,
-                                &mut app_model,
-                            );
+                //Received Character
+                Event::WindowEvent {
+                    event: event::WindowEvent::ReceivedCharacter(ch),
+                    ..
+                } => {
+                    caret_blink_start = Instant::now();
+
+                    let input_outcome_res =
+                        app_update::handle_new_char(&ch, &mut app_model, keyboard_modifiers);
+                    if let Err(e) = input_outcome_res {
+                        print_err(&e)
+                    } else if let Ok(InputOutcome::Ignored) = input_outcome_res {
+                        println!("\nInput '{}' ignored!", ch);
+                    } else {
+                        window.request_redraw()
+                    }
+                }
+                //Keyboard Input
+                Event::WindowEvent {
+                    event: event::WindowEvent::KeyboardInput { input, .. },
+                    ..
+                } => {
+                    caret_blink_start = Instant::now();
+
+                    if let Some(virtual_keycode) = input.virtual_keycode {
+                        if let Some(ref mut ed_model) = app_model.ed_model_opt {
+                            if ed_model.has_focus {
+                                let keydown_res = keyboard_input::handle_keydown(
+                                    input.state,
+                                    virtual_keycode,
+                                    keyboard_modifiers,
+                                    &mut app_model,
+                                );
+
+                                if let Err(e) = keydown_res {
+                                    print_err(&e)
+                                }
+
+                                window.request_redraw()
+                            }
+                        }
+                    }
+                }
+                //Modifiers Changed
+                Event::WindowEvent {
+                    event: event::WindowEvent::ModifiersChanged(modifiers),
+                    ..
+                } => {
+                    keyboard_modifiers = modifiers;
+                }
+                //Mouse
+                Event::WindowEvent {
+                    event: event::WindowEvent::CursorMoved { position, .. },
+                    ..
+                } => {
+                    cursor_pos = position;
 
-                            if let Err(e) = keydown_res {
+                    if let Some(ref mut ed_model) = app_model.ed_model_opt {
+                        if ed_model.minimap.dragging {
+                            if let Some(line_nr) =
+                                ed_model.minimap.nearest_line_at(cursor_pos.y as f32)
+                            {
+                                ed_model.set_caret_at_minimap_line(line_nr);
+                                window.request_redraw()
+                            }
+                        }
+                    }
+                }
+                Event::WindowEvent {
+                    event:
+                        event::WindowEvent::MouseInput {
+                            state: ElementState::Pressed,
+                            button: MouseButton::Left,
+                            ..
+                        },
+                    ..
+                } => {
+                    if let Some(ref mut ed_model) = app_model.ed_model_opt {
+                        let minimap_line_opt = if config.show_minimap {
+                            ed_model
+                                .minimap
+                                .line_at(cursor_pos.x as f32, cursor_pos.y as f32)
+                        } else {
+                            None
+                        };
+
+                        if let Some(line_nr) = minimap_line_opt {
+                            ed_model.minimap.dragging = true;
+                            ed_model.set_caret_at_minimap_line(line_nr);
+                        } else {
+                            let click_res =
+                                get_literal_at_caret(ed_model).and_then(|prev_literal_opt| {
+                                    // the Run button is at the end of the breadcrumb bar
+                                    if ed_model.click_run_button(
+                                        cursor_pos.x as f32,
+                                        cursor_pos.y as f32,
+                                        &size,
+                                        config.make_code_txt_xy().into(),
+                                    )? {
+                                        return Ok(());
+                                    }
+
+                                    ed_model.select_breadcrumb_at(
+                                        cursor_pos.x as f32,
+                                        cursor_pos.y as f32,
+                                        config.make_code_txt_xy().into(),
+                                    )?;
+                                    ed_model.set_caret_at_click(
+                                        cursor_pos.x as f32,
+                                        cursor_pos.y as f32,
+                                        config.make_code_txt_xy().into(),
+                                    )?;
+
+                                    fmt_literal_if_left(ed_model, prev_literal_opt)
+                                });
+
+                            if let Err(e) = click_res {
                                 print_err(&e)
                             }
+                        }
+
+                        window.request_redraw()
+                    }
+                }
+                Event::WindowEvent {
+                    event:
+                        event::WindowEvent::MouseInput {
+                            state: ElementState::Released,
+                            button: MouseButton::Left,
+                            ..
+                        },
+                    ..
+                } => {
+                    if let Some(ref mut ed_model) = app_model.ed_model_opt {
+                        ed_model.minimap.dragging = false;
+                    }
+                }
+                Event::WindowEvent {
+                    event: event::WindowEvent::MouseWheel { delta, .. },
+                    ..
+                } => {
+                    if let Some(ref mut ed_model) = app_model.ed_model_opt {
+                        // shift turns the vertical scroll wheel into a horizontal one
+                        let (delta_x, delta_y) = match delta {
+                            MouseScrollDelta::LineDelta(x, y) => {
+                                (x * SCROLL_COLS_PER_LINE, y * SCROLL_COLS_PER_LINE)
+                            }
+                            MouseScrollDelta::PixelDelta(pos) => {
+                                let char_width = ed_model
+                                    .glyph_dim_rect_opt
+                                    .map_or(config.code_font_size, |glyph_dim_rect| {
+                                        glyph_dim_rect.width
+                                    });
+
+                                (pos.x as f32 / char_width, pos.y as f32 / char_width)
+                            }
+                        };
+
+                        let nr_of_cols = if keyboard_modifiers.shift() {
+                            -delta_y
+                        } else {
+                            -delta_x
+                        };
 
+                        if nr_of_cols.round() != 0.0 {
+                            ed_model.scroll_horizontally(nr_of_cols.round() as isize);
                             window.request_redraw()
                         }
                     }
                 }
-            }
-            //Modifiers Changed
-            Event::WindowEvent {
-                event: event::WindowEvent::ModifiersChanged(modifiers),
-                ..
-            } => {
-                keyboard_modifiers = modifiers;
-            }
-            Event::RedrawRequested { .. } => {
-                // Get a command encoder for the current frame
-                let mut encoder =
-                    gpu_device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                        label: Some("Redraw"),
-                    });
-
-                let surface_texture = surface
-                    .get_current_texture()
-                    .expect("Failed to acquire next SwapChainTexture");
-
-                let view = surface_texture
-                    .texture
-                    .create_view(&wgpu::TextureViewDescriptor::default());
-
-                if let Some(ref mut ed_model) = app_model.ed_model_opt {
-                    if rendered_wgpu_opt.is_none() || ed_model.dirty {
-                        let rendered_wgpu_res = ed_view::model_to_wgpu(
-                            ed_model,
-                            &size,
-                            config.make_code_txt_xy().into(),
-                            &config,
-                        );
+                Event::RedrawRequested { .. } => {
+                    // Get a command encoder for the current frame
+                    let mut encoder =
+                        gpu_device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                            label: Some("Redraw"),
+                        });
 
-                        match rendered_wgpu_res {
-                            Ok(rendered_wgpu) => rendered_wgpu_opt = Some(rendered_wgpu),
-                            Err(e) => print_err(&e),
-                        }
+                    let surface_texture = surface
+                        .get_current_texture()
+                        .expect("Failed to acquire next SwapChainTexture");
 
-                        ed_model.dirty = false;
-                    }
+                    let view = surface_texture
+                        .texture
+                        .create_view(&wgpu::TextureViewDescriptor::default());
 
-                    if let Some(ref rendered_wgpu) = rendered_wgpu_opt {
-                        draw_rects(
-                            &rendered_wgpu.rects_behind,
-                            &mut encoder,
-                            &view,
-                            &gpu_device,
-                            &rect_resources,
-                            wgpu::LoadOp::Clear(to_wgpu_color(ed_theme.background)),
-                        );
+                    if let Some(ref mut ed_model) = app_model.ed_model_opt {
+                        if rendered_wgpu_opt.is_none() || ed_model.dirty {
+                            let rendered_wgpu_res = ed_view::model_to_wgpu(
+                                ed_model,
+                                &size,
+                                config.make_code_txt_xy().into(),
+                                &config,
+                            );
+
+                            match rendered_wgpu_res {
+                                Ok(rendered_wgpu) => rendered_wgpu_opt = Some(rendered_wgpu),
+                                Err(e) => print_err(&e),
+                            }
 
-                        for text_section in &rendered_wgpu.text_sections_behind {
-                            let borrowed_text = text_section.to_borrowed();
+                            if let Some(mirror_path) = &config.text_mirror_path {
+                                if let Err(e) = write_text_mirror(ed_model, mirror_path) {
+                                    print_err(&e)
+                                }
+                            }
 
-                            glyph_brush.queue(borrowed_text);
+                            ed_model.dirty = false;
                         }
 
-                        // draw first layer of text
-                        glyph_brush
-                            .draw_queued(
+                        if let Some(ref rendered_wgpu) = rendered_wgpu_opt {
+                            draw_rects(
+                                &rendered_wgpu.rects_behind,
+                                &mut encoder,
+                                &view,
                                 &gpu_device,
-                                &mut staging_belt,
+                                &rect_resources,
+                                wgpu::LoadOp::Clear(to_wgpu_color(config.ed_theme.background)),
+                            );
+
+                            for text_section in &rendered_wgpu.text_sections_behind {
+                                let borrowed_text = text_section.to_borrowed();
+
+                                glyph_brush.queue(borrowed_text);
+                            }
+
+                            // draw first layer of text
+                            glyph_brush
+                                .draw_queued(
+                                    &gpu_device,
+                                    &mut staging_belt,
+                                    &mut encoder,
+                                    &view,
+                                    size.width,
+                                    size.height,
+                                )
+                                .expect("Failed to draw first layer of text.");
+
+                            // draw rects on top of first text layer
+                            draw_rects(
+                                &rendered_wgpu.rects_front,
                                 &mut encoder,
                                 &view,
-                                size.width,
-                                size.height,
-                            )
-                            .expect("Failed to draw first layer of text.");
-
-                        // draw rects on top of first text layer
-                        draw_rects(
-                            &rendered_wgpu.rects_front,
+                                &gpu_device,
+                                &rect_resources,
+                                wgpu::LoadOp::Load,
+                            );
+
+                            for text_section in &rendered_wgpu.text_sections_front {
+                                let borrowed_text = text_section.to_borrowed();
+
+                                glyph_brush.queue(borrowed_text);
+                            }
+                        }
+                    } else {
+                        begin_render_pass(
                             &mut encoder,
                             &view,
-                            &gpu_device,
-                            &rect_resources,
-                            wgpu::LoadOp::Load,
+                            wgpu::LoadOp::Clear(to_wgpu_color(config.ed_theme.background)),
                         );
 
-                        for text_section in &rendered_wgpu.text_sections_front {
-                            let borrowed_text = text_section.to_borrowed();
+                        queue_no_file_text(
+                            &size,
+                            NOTHING_OPENED,
+                            config.make_code_txt_xy().into(),
+                            &config,
+                            &mut glyph_brush,
+                        );
+                    }
+
+                    // draw text
+                    glyph_brush
+                        .draw_queued(
+                            &gpu_device,
+                            &mut staging_belt,
+                            &mut encoder,
+                            &view,
+                            size.width,
+                            size.height,
+                        )
+                        .expect("Failed to draw queued text.");
+
+                    staging_belt.finish();
+                    cmd_queue.submit(Some(encoder.finish()));
+                    surface_texture.present();
+
+                    // Recall unused staging buffers
+                    use futures::task::SpawnExt;
 
-                            glyph_brush.queue(borrowed_text);
+                    local_spawner
+                        .spawn(staging_belt.recall())
+                        .expect("Recall staging belt");
+
+                    local_pool.run_until_stalled();
+                }
+                Event::MainEventsCleared => {
+                    let file_to_open_opt = app_model
+                        .ed_model_opt
+                        .as_mut()
+                        .and_then(|ed_model| ed_model.file_to_open_opt.take());
+
+                    if let Some(file_path) = file_to_open_opt {
+                        if let Some(ref mut ed_model) = app_model.ed_model_opt {
+                            module_to_open_opt =
+                                switch_to_module(ed_model, file_path, &mut unsaved_code);
+                        }
+
+                        if module_to_open_opt.is_some() {
+                            *control_flow = ControlFlow::Exit;
+
+                            return;
                         }
                     }
-                } else {
-                    begin_render_pass(
-                        &mut encoder,
-                        &view,
-                        wgpu::LoadOp::Clear(to_wgpu_color(ed_theme.background)),
-                    );
 
-                    queue_no_file_text(
-                        &size,
-                        NOTHING_OPENED,
-                        config.make_code_txt_xy().into(),
-                        &config,
-                        &mut glyph_brush,
-                    );
-                }
+                    if let Some(ref mut ed_model) = app_model.ed_model_opt {
+                        if ed_model.poll_project_search() {
+                            window.request_redraw()
+                        }
 
-                // draw text
-                glyph_brush
-                    .draw_queued(
-                        &gpu_device,
-                        &mut staging_belt,
-                        &mut encoder,
-                        &view,
-                        size.width,
-                        size.height,
-                    )
-                    .expect("Failed to draw queued text.");
+                        // keep polling until every file of the project has been searched
+                        if ed_model.project_search_running() {
+                            *control_flow = ControlFlow::Poll;
+                        }
 
-                staging_belt.finish();
-                cmd_queue.submit(Some(encoder.finish()));
-                surface_texture.present();
+                        if ed_model.poll_background_run() {
+                            window.request_redraw()
+                        }
 
-                // Recall unused staging buffers
-                use futures::task::SpawnExt;
+                        // keep polling until the app is built and has exited
+                        if ed_model.background_run_running() {
+                            *control_flow = ControlFlow::Poll;
+                        }
 
-                local_spawner
-                    .spawn(staging_belt.recall())
-                    .expect("Recall staging belt");
+                        if let Some(blink_ms) = config.caret_blink_ms {
+                            let (show_caret, _) = caret_blink_phase(caret_blink_start, blink_ms);
 
-                local_pool.run_until_stalled();
-            }
-            _ => {
-                *control_flow = winit::event_loop::ControlFlow::Wait;
+                            if show_caret != ed_model.show_caret {
+                                ed_model.show_caret = show_caret;
+                                ed_model.dirty = true;
+                                window.request_redraw()
+                            }
+                        }
+                    }
+                }
+                Event::RedrawEventsCleared => {
+                    *control_flow = match (config.caret_blink_ms, &app_model.ed_model_opt) {
+                        // wake up when the caret has to be shown or hidden
+                        (Some(blink_ms), Some(_)) => {
+                            let (_, next_blink) = caret_blink_phase(caret_blink_start, blink_ms);
+
+                            ControlFlow::WaitUntil(next_blink)
+                        }
+                        _ => ControlFlow::Wait,
+                    };
+                }
+                _ => {
+                    *control_flow = winit::event_loop::ControlFlow::Wait;
+                }
             }
-        }
-    });
+        });
+
+        app_model_between_modules = app_model.with_ed_model(None);
+    }
 
     Ok(())
 }
 
+// The caret is shown for blink_ms, then hidden for blink_ms and so on.
+// Returns whether the caret is shown now, and when that changes.
+fn caret_blink_phase(blink_start: Instant, blink_ms: u64) -> (bool, Instant) {
+    let blink_ms = blink_ms.max(1);
+    let phase = blink_start.elapsed().as_millis() as u64 / blink_ms;
+
+    (
+        phase % 2 == 0,
+        blink_start + Duration::from_millis((phase + 1) * blink_ms),
+    )
+}
+
 async fn create_device(
     instance: &wgpu::Instance,
     surface: &wgpu::Surface,
@@ -487,6 +690,171 @@ fn begin_render_pass<'a>(
 const ROC_PROJECTS_FOLDER: &str = "roc-projects";
 const ROC_NEW_PROJECT_FOLDER: &str = "new-roc-project-1";
 
+/// A module for the event loop to show next
+struct ModuleToOpen {
+    file_path: PathBuf,
+    code_str: String,
+    caret_pos: CaretPos,
+    project_search_opt: Option<ProjectSearch>,
+    // the module that was shown before, it is shown again if this one can not be loaded
+    switched_from_opt: Option<PathBuf>,
+}
+
+/// Everything the [EdModel] of a module borrows, it is dropped when another module is opened.
+struct ModuleStorage {
+    file_path: PathBuf,
+    code_str: String,
+    env_arena: Bump,
+    code_arena: Bump,
+    env_pool: Pool,
+    var_store: VarStore,
+    module_ids: ModuleIds,
+}
+
+impl ModuleStorage {
+    fn new(file_path: PathBuf, code_str: String) -> Self {
+        Self {
+            file_path,
+            code_str,
+            env_arena: Bump::new(),
+            code_arena: Bump::new(),
+            env_pool: Pool::with_capacity(1024),
+            var_store: VarStore::default(),
+            module_ids: ModuleIds::default(),
+        }
+    }
+}
+
+/// Loads the roc file of `storage` into an [EdModel], which borrows the code and the arenas
+/// from `storage`.
+fn load_ed_model<'a>(
+    storage: &'a mut ModuleStorage,
+    caret_pos: CaretPos,
+    project_search_opt: Option<ProjectSearch>,
+    config: &Config,
+    glyph_brush: &mut GlyphBrush<()>,
+) -> Option<EdModel<'a>> {
+    println!("Loading file {:?}...", storage.file_path);
+
+    let loaded_module = load_module(
+        &storage.file_path,
+        RocCacheDir::Persistent(cache::roc_cache_dir().as_path()),
+        Threading::AllAvailable,
+    );
+
+    storage.module_ids = loaded_module.interns.module_ids.clone();
+
+    let ModuleStorage {
+        file_path,
+        code_str,
+        env_arena,
+        code_arena,
+        env_pool,
+        var_store,
+        module_ids,
+    } = storage;
+
+    let dep_idents = IdentIds::exposed_builtins(8);
+    let exposed_ident_ids = IdentIds::default();
+
+    let env = Env::new(
+        loaded_module.module_id,
+        env_arena,
+        env_pool,
+        var_store,
+        dep_idents,
+        module_ids,
+        exposed_ident_ids,
+    );
+
+    let ed_model_res = ed_model::init_model(
+        code_str,
+        file_path,
+        env,
+        loaded_module,
+        code_arena,
+        caret_pos,
+    );
+
+    match ed_model_res {
+        Ok(mut ed_model) => {
+            ed_model.literal_fmt_config = config.literal_fmt;
+            ed_model.viewport.soft_wrap = config.soft_wrap;
+            ed_model.glyph_dim_rect_opt =
+                Some(example_code_glyph_rect(glyph_brush, config.code_font_size));
+
+            if project_search_opt.is_some() {
+                ed_model.project_search_opt = project_search_opt;
+
+                if let Err(e) = ed_model.goto_selected_search_match() {
+                    print_err(&e.into());
+                }
+            }
+
+            Some(ed_model)
+        }
+        Err(e) => {
+            print_err(&e);
+            None
+        }
+    }
+}
+
+/// The editor shows a single module, this prepares to replace it with the module at `file_path`
+/// and to select the search match in it. The module that was shown is not saved, its code is
+/// kept in `unsaved_code` and shown again when it is reopened.
+fn switch_to_module(
+    ed_model: &mut EdModel,
+    file_path: PathBuf,
+    unsaved_code: &mut HashMap<PathBuf, String>,
+) -> Option<ModuleToOpen> {
+    let mut module_to_open = reopen_module(file_path, unsaved_code)?;
+
+    let switched_from = ed_model.file_path.to_path_buf();
+
+    unsaved_code.insert(
+        unsaved_code_key(&switched_from),
+        ed_model.code_lines.all_lines_as_string(),
+    );
+
+    module_to_open.caret_pos = CaretPos::Start;
+    module_to_open.project_search_opt = ed_model.project_search_opt.take();
+    module_to_open.switched_from_opt = Some(switched_from);
+
+    Some(module_to_open)
+}
+
+/// Opens the module at `file_path` with the code it had when it was switched away from, or with
+/// the code on disk if it was not shown before.
+fn reopen_module(
+    file_path: PathBuf,
+    unsaved_code: &mut HashMap<PathBuf, String>,
+) -> Option<ModuleToOpen> {
+    let code_str = match unsaved_code.remove(&unsaved_code_key(&file_path)) {
+        Some(code_str) => code_str,
+        None => match fs::read_to_string(&file_path) {
+            Ok(code_str) => code_str,
+            Err(err) => {
+                println!("\nFailed to open {:?}: {}", file_path, err);
+                return None;
+            }
+        },
+    };
+
+    Some(ModuleToOpen {
+        file_path,
+        code_str,
+        caret_pos: CaretPos::End,
+        project_search_opt: None,
+        switched_from_opt: None,
+    })
+}
+
+// search matches and the module given on the command line may name the same file differently
+fn unsaved_code_key(file_path: &Path) -> PathBuf {
+    fs::canonicalize(file_path).unwrap_or_else(|_| file_path.to_path_buf())
+}
+
 fn read_main_roc_file(project_path_opt: Option<&Path>) -> (PathBuf, String) {
     if let Some(project_path) = project_path_opt {
         let path_metadata = metadata(project_path).unwrap_or_else(|err| panic!("You provided the path {:?}, but I could not read the metadata for the provided path; error: {:?}", &project_path, err));
@@ -0,0 +1,319 @@
+use crate::editor::code_lines::CodeLines;
+use crate::editor::ed_error::EdResult;
+use crate::editor::grid_node_map::GridNodeMap;
+use crate::editor::semantic_highlight::SemanticStyle;
+use crate::editor::theme::EdTheme;
+use crate::graphics::colors::RgbaTup;
+use crate::graphics::primitives::rect::Rect;
+use crate::ui::text::lines::Lines;
+use cgmath::Vector2;
+use roc_code_markup::markup::nodes::MarkupNode;
+use roc_code_markup::slow_pool::{MarkNodeId, SlowPool};
+use roc_code_markup::syntax_highlight::HighlightStyle;
+use roc_collections::MutMap;
+use winit::dpi::PhysicalSize;
+
+// A miniature overview of the whole file on the right edge of the window.
+// Chars are drawn as small blocks in the color of their highlight style instead of as glyphs,
+// so the shape of the code stays recognizable while a thousand lines fit on screen.
+// The lines that fit in the window are marked, clicking or dragging in the minimap moves the caret to a line.
+
+// size of one char in the minimap, lines get less high when the file does not fit in the window
+const CHAR_WIDTH: f32 = 2.0;
+const MAX_LINE_HEIGHT: f32 = 4.0;
+// columns past this are not shown
+const MAX_COLS: usize = 100;
+const MARGIN: f32 = 10.0;
+
+pub const MINIMAP_WIDTH: f32 = CHAR_WIDTH * (MAX_COLS as f32) + 2.0 * MARGIN;
+
+// neighbouring chars with the same color, drawn as one rect
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MinimapBlock {
+    pub start_col: usize,
+    pub len: usize,
+    pub color: RgbaTup,
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct MinimapLine {
+    text: String,
+    node_ids: Vec<MarkNodeId>,
+    blocks: Vec<MinimapBlock>,
+}
+
+// where the minimap was drawn, to find the line of a click
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct MinimapArea {
+    left: f32,
+    top: f32,
+    line_height: f32,
+}
+
+#[derive(Debug, Default)]
+pub struct Minimap {
+    lines: Vec<MinimapLine>,
+    area_opt: Option<MinimapArea>,
+    pub dragging: bool, // the left mouse button was pressed in the minimap and is still down
+}
+
+impl Minimap {
+    // only the lines whose text or nodes changed since the previous update are built again, returns how many that were
+    pub fn update(
+        &mut self,
+        code_lines: &CodeLines,
+        grid_node_map: &GridNodeMap,
+        mark_node_pool: &SlowPool,
+        semantic_styles: &MutMap<MarkNodeId, SemanticStyle>,
+        ed_theme: &EdTheme,
+    ) -> EdResult<usize> {
+        let nr_of_lines = code_lines.nr_of_lines();
+        let mut nr_of_rebuilt_lines = 0;
+
+        self.lines.truncate(nr_of_lines);
+
+        for line_nr in 0..nr_of_lines {
+            let text = code_lines.get_line_ref(line_nr)?;
+            let node_ids = grid_node_map
+                .lines
+                .get(line_nr)
+                .map_or(&[][..], |line| line.as_slice());
+
+            let unchanged = self
+                .lines
+                .get(line_nr)
+                .map_or(false, |line| line.text == text && line.node_ids == node_ids);
+
+            if unchanged {
+                continue;
+            }
+
+            let new_line = MinimapLine {
+                text: text.to_owned(),
+                node_ids: node_ids.to_vec(),
+                blocks: line_blocks(text, node_ids, mark_node_pool, semantic_styles, ed_theme),
+            };
+
+            if line_nr < self.lines.len() {
+                self.lines[line_nr] = new_line;
+            } else {
+                self.lines.push(new_line);
+            }
+
+            nr_of_rebuilt_lines += 1;
+        }
+
+        Ok(nr_of_rebuilt_lines)
+    }
+
+    pub fn blocks(&self, line_nr: usize) -> &[MinimapBlock] {
+        self.lines
+            .get(line_nr)
+            .map_or(&[], |line| line.blocks.as_slice())
+    }
+
+    pub fn build_graphics(
+        &mut self,
+        size: &PhysicalSize<u32>,
+        txt_coords: Vector2<f32>,
+        glyph_dim_rect: &Rect,
+        grid_node_map: &GridNodeMap,
+        ed_theme: &EdTheme,
+    ) -> Vec<Rect> {
+        let left = size.width as f32 - MINIMAP_WIDTH + MARGIN;
+        let top = txt_coords.y;
+        let available_height = (size.height as f32 - top - MARGIN).max(0.0);
+        let nr_of_lines = self.lines.len().max(1);
+        let line_height = MAX_LINE_HEIGHT.min(available_height / nr_of_lines as f32);
+
+        self.area_opt = Some(MinimapArea {
+            left,
+            top,
+            line_height,
+        });
+
+        let mut rects = vec![Rect {
+            top_left_coords: (left - MARGIN, top).into(),
+            width: MINIMAP_WIDTH,
+            height: line_height * nr_of_lines as f32,
+            color: ed_theme.background,
+        }];
+
+        // the lines that fit in the window, long lines can take several rows on screen when they are soft-wrapped
+        let nr_of_visible_rows = ((size.height as f32 - top) / glyph_dim_rect.height) as usize;
+        let mut nr_of_rows = 0;
+        let nr_of_visible_lines = (0..self.lines.len())
+            .take_while(|line_nr| {
+                nr_of_rows += grid_node_map.nr_of_rows(*line_nr);
+                nr_of_rows <= nr_of_visible_rows
+            })
+            .count();
+
+        rects.push(Rect {
+            top_left_coords: (left - MARGIN, top).into(),
+            width: MINIMAP_WIDTH,
+            height: line_height * nr_of_visible_lines as f32,
+            color: ed_theme.ui_theme.select_highlight,
+        });
+
+        for line_nr in 0..self.lines.len() {
+            for block in self.blocks(line_nr) {
+                rects.push(Rect {
+                    top_left_coords: (
+                        left + block.start_col as f32 * CHAR_WIDTH,
+                        top + line_nr as f32 * line_height,
+                    )
+                        .into(),
+                    width: block.len as f32 * CHAR_WIDTH,
+                    // a small gap between lines
+                    height: line_height * 0.75,
+                    color: block.color,
+                });
+            }
+        }
+
+        rects
+    }
+
+    // the line at a position in the window, None if the position is not in the minimap
+    pub fn line_at(&self, x: f32, y: f32) -> Option<usize> {
+        let area = self.area_opt?;
+
+        if x < area.left - MARGIN || y < area.top || area.line_height <= 0.0 {
+            return None;
+        }
+
+        let line_nr = ((y - area.top) / area.line_height) as usize;
+
+        if line_nr < self.lines.len() {
+            Some(line_nr)
+        } else {
+            None
+        }
+    }
+
+    // like line_at, but positions above or below the minimap go to the first or last line, for dragging
+    pub fn nearest_line_at(&self, y: f32) -> Option<usize> {
+        let area = self.area_opt?;
+        let last_line_nr = self.lines.len().checked_sub(1)?;
+
+        if y < area.top || area.line_height <= 0.0 {
+            Some(0)
+        } else {
+            Some((((y - area.top) / area.line_height) as usize).min(last_line_nr))
+        }
+    }
+}
+
+fn line_blocks(
+    text: &str,
+    node_ids: &[MarkNodeId],
+    mark_node_pool: &SlowPool,
+    semantic_styles: &MutMap<MarkNodeId, SemanticStyle>,
+    ed_theme: &EdTheme,
+) -> Vec<MinimapBlock> {
+    let mut blocks: Vec<MinimapBlock> = Vec::new();
+
+    for (col, (ch, node_id)) in text.chars().zip(node_ids).take(MAX_COLS).enumerate() {
+        // whitespace is left empty
+        if ch.is_whitespace() {
+            continue;
+        }
+
+        let color = match node_color(*node_id, mark_node_pool, semantic_styles, ed_theme) {
+            Some(color) => color,
+            None => continue,
+        };
+
+        match blocks.last_mut() {
+            Some(block) if block.start_col + block.len == col && block.color == color => {
+                block.len += 1;
+            }
+            _ => blocks.push(MinimapBlock {
+                start_col: col,
+                len: 1,
+                color,
+            }),
+        }
+    }
+
+    blocks
+}
+
+// the color the node is highlighted with in the code, see render_ast.rs
+fn node_color(
+    node_id: MarkNodeId,
+    mark_node_pool: &SlowPool,
+    semantic_styles: &MutMap<MarkNodeId, SemanticStyle>,
+    ed_theme: &EdTheme,
+) -> Option<RgbaTup> {
+    let style = match semantic_styles.get(&node_id) {
+        Some(semantic_style) => &semantic_style.style,
+        None => match mark_node_pool.get(node_id) {
+            MarkupNode::Text { syn_high_style, .. } => syn_high_style,
+            MarkupNode::Blank { .. } => &HighlightStyle::Blank,
+            MarkupNode::Nested { .. } => return None,
+        },
+    };
+
+    ed_theme.syntax_high_map.get(style).copied()
+}
+
+#[cfg(test)]
+pub mod test_minimap {
+    use crate::editor::minimap::Minimap;
+    use crate::editor::mvc::ed_model::test_ed_model::{ed_model_from_dsl, init_model_refs};
+    use crate::editor::resources::strings::nr_hello_world_lines;
+    use crate::editor::theme::EdTheme;
+    use crate::ui::text::lines::Lines;
+    use bumpalo::Bump;
+    use roc_module::symbol::ModuleIds;
+
+    #[test]
+    fn blocks_skip_whitespace_and_unchanged_lines() -> Result<(), String> {
+        let code_lines = vec!["val = 123┃".to_owned()];
+        let mut code_str = code_lines.join("\n").replace('┃', "");
+
+        let mut model_refs = init_model_refs();
+        let code_arena = Bump::new();
+        let module_ids = ModuleIds::default();
+
+        let ed_model = ed_model_from_dsl(
+            &mut code_str,
+            code_lines,
+            &mut model_refs,
+            &module_ids,
+            &code_arena,
+        )?;
+
+        let ed_theme = EdTheme::default();
+        let mut minimap = Minimap::default();
+
+        let update = |minimap: &mut Minimap| {
+            minimap
+                .update(
+                    &ed_model.code_lines,
+                    &ed_model.grid_node_map,
+                    &ed_model.mark_node_pool,
+                    &ed_model.semantic_styles,
+                    &ed_theme,
+                )
+                .map_err(|e| format!("{:?}", e))
+        };
+
+        assert_eq!(update(&mut minimap)?, ed_model.code_lines.nr_of_lines());
+
+        let block_cols: Vec<(usize, usize)> = minimap
+            .blocks(nr_hello_world_lines())
+            .iter()
+            .map(|block| (block.start_col, block.len))
+            .collect();
+
+        assert_eq!(block_cols, vec![(0, 3), (4, 1), (6, 3)]);
+
+        // nothing changed, so nothing is built again
+        assert_eq!(update(&mut minimap)?, 0);
+
+        Ok(())
+    }
+}
@@ -1,12 +1,28 @@
+mod accessibility;
+mod background_run;
+mod breadcrumbs;
+mod code_actions;
 mod code_lines;
 mod config;
 pub mod ed_error;
+mod evaluate;
 mod grid_node_map;
 mod keyboard_input;
+mod keyboard_macro;
 pub mod main;
+mod minimap;
 mod mvc;
+mod plugin;
+mod project_search;
+mod rename;
 mod render_ast;
+mod render_command_palette;
 mod render_debug;
+mod render_run;
+mod render_search;
 mod resources;
+mod semantic_highlight;
+mod test_lens;
 mod theme;
 mod util;
+mod viewport;
@@ -6,6 +6,7 @@ use crate::editor::ed_error::{
     EdError::{ClipboardInitFailed, ClipboardReadFailed, ClipboardWriteFailed},
     EdResult,
 };
+use crate::editor::keyboard_macro::KeyboardMacro;
 use copypasta::{ClipboardContext, ClipboardProvider};
 use std::fmt;
 use threadpool::ThreadPool;
@@ -14,6 +15,7 @@ pub struct AppModel<'a> {
     pub ed_model_opt: Option<EdModel<'a>>,
     pub clipboard_opt: Option<Clipboard>,
     pub sound_thread_pool: ThreadPool, // thread is blocked while sound is played, hence the threadpool
+    pub keyboard_macro: KeyboardMacro, // recorded input that can be replayed, see keyboard_macro.rs
 }
 
 impl<'a> AppModel<'a> {
@@ -22,6 +24,17 @@ impl<'a> AppModel<'a> {
             ed_model_opt,
             clipboard_opt: AppModel::init_clipboard_opt(),
             sound_thread_pool: ThreadPool::new(7), // can play up to 7 sounds simultaneously
+            keyboard_macro: KeyboardMacro::default(),
+        }
+    }
+
+    /// The same app, showing `ed_model_opt` instead of the module it showed
+    pub fn with_ed_model<'b>(self, ed_model_opt: Option<EdModel<'b>>) -> AppModel<'b> {
+        AppModel {
+            ed_model_opt,
+            clipboard_opt: self.clipboard_opt,
+            sound_thread_pool: self.sound_thread_pool,
+            keyboard_macro: self.keyboard_macro,
         }
     }
 
@@ -1,5 +1,7 @@
-use super::app_model::AppModel;
+use super::app_model::{self, AppModel};
 use super::ed_update;
+use super::literal_fmt::{fmt_literal_if_left, get_literal_at_caret};
+use crate::editor::keyboard_macro::MacroStep;
 use crate::window::keyboard_input::Modifiers;
 use crate::{editor::ed_error::EdResult, window::keyboard_input::from_winit};
 use winit::event::{ModifiersState, VirtualKeyCode};
@@ -41,11 +43,28 @@ pub fn pass_keydown_to_focused(
 ) -> EdResult<()> {
     if let Some(ref mut ed_model) = app_model.ed_model_opt {
         if ed_model.has_focus {
+            if virtual_keycode == VirtualKeyCode::F2 {
+                // there is no text input for the new name yet, so it is taken from the clipboard
+                if ed_model.pending_rename_opt.is_some() {
+                    ed_model.apply_pending_rename()?;
+                } else {
+                    let new_name = app_model::get_clipboard_txt(&mut app_model.clipboard_opt)?;
+
+                    ed_model.start_rename_at_caret(&new_name)?;
+                }
+
+                return Ok(());
+            }
+
+            let prev_literal_opt = get_literal_at_caret(ed_model)?;
+
             ed_model.ed_handle_key_down(
                 modifiers,
                 virtual_keycode,
                 &mut app_model.sound_thread_pool,
             )?;
+
+            fmt_literal_if_left(ed_model, prev_literal_opt)?;
         }
     }
 
@@ -63,6 +82,23 @@ pub fn handle_new_char(
     received_char: &char,
     app_model: &mut AppModel,
     modifiers_winit: ModifiersState,
+) -> EdResult<InputOutcome> {
+    let input_outcome = pass_new_char_to_focused(received_char, app_model, modifiers_winit)?;
+
+    if !matches!(input_outcome, InputOutcome::SilentIgnored) {
+        app_model.keyboard_macro.record(MacroStep::NewChar {
+            received_char: *received_char,
+            modifiers: modifiers_winit,
+        });
+    }
+
+    Ok(input_outcome)
+}
+
+pub fn pass_new_char_to_focused(
+    received_char: &char,
+    app_model: &mut AppModel,
+    modifiers_winit: ModifiersState,
 ) -> EdResult<InputOutcome> {
     if let Some(ref mut ed_model) = app_model.ed_model_opt {
         if ed_model.has_focus {
@@ -70,7 +106,13 @@ pub fn handle_new_char(
 
             if modifiers.new_char_modifiers() {
                 // shortcuts with modifiers are handled by ed_handle_key_down
-                return ed_update::handle_new_char(received_char, ed_model);
+                let prev_literal_opt = get_literal_at_caret(ed_model)?;
+
+                let input_outcome = ed_update::handle_new_char(received_char, ed_model)?;
+
+                fmt_literal_if_left(ed_model, prev_literal_opt)?;
+
+                return Ok(input_outcome);
             }
         }
     }
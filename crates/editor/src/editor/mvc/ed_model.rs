@@ -1,5 +1,14 @@
+use crate::editor::background_run::BackgroundRun;
 use crate::editor::code_lines::CodeLines;
 use crate::editor::grid_node_map::GridNodeMap;
+use crate::editor::minimap::Minimap;
+use crate::editor::mvc::literal_fmt::LiteralFmtConfig;
+use crate::editor::plugin::{plugin_decorations, CommandPalette, Decoration};
+use crate::editor::project_search::ProjectSearch;
+use crate::editor::rename::RenamePlan;
+use crate::editor::semantic_highlight::{semantic_styles, SemanticStyle};
+use crate::editor::test_lens::ExpectResult;
+use crate::editor::viewport::Viewport;
 use crate::editor::{
     ed_error::SrcParseSnafu,
     ed_error::{EdResult, EmptyCodeStringSnafu, MissingParentSnafu, NoNodeAtCaretPositionSnafu},
@@ -19,9 +28,10 @@ use roc_code_markup::markup::convert::from_ast::ast_to_mark_nodes;
 use roc_code_markup::markup::mark_id_ast_id_map::MarkIdAstIdMap;
 use roc_code_markup::markup::nodes;
 use roc_code_markup::slow_pool::{MarkNodeId, SlowPool};
+use roc_collections::MutMap;
 use roc_load::LoadedModule;
 use roc_module::symbol::Interns;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Contains nearly all state related to a single roc file in the editor.
 #[derive(Debug)]
@@ -33,13 +43,25 @@ pub struct EdModel<'a> {
     pub markup_ids: Vec<MarkNodeId>, // one root node for every top level definition
     pub mark_node_pool: SlowPool, // all MarkupNodes for this file are saved into this pool and can be retrieved using their MarkNodeId
     pub mark_id_ast_id_map: MarkIdAstIdMap, // To find the ASTNode that is represented by a MarkNode
+    pub semantic_styles: MutMap<MarkNodeId, SemanticStyle>, // highlighting based on canonicalization, see semantic_highlight.rs
+    pub plugin_decorations: MutMap<MarkNodeId, Decoration>, // see plugin.rs
     pub glyph_dim_rect_opt: Option<Rect>, // represents the width and height of single monospace glyph(char)
     pub has_focus: bool,
     pub caret_w_select_vec: NonEmpty<(CaretWSelect, Option<MarkNodeId>)>, // the editor supports multiple carets/cursors and multiple selections
     pub selected_block_opt: Option<SelectedBlock>, // a selected AST node, the roc type of this node is shown in the editor on ctrl+shift+"up arrow"
     pub loaded_module: LoadedModule, // contains all roc symbols, exposed values, exposed aliases, solved types... in the file(=module)
     pub show_debug_view: bool,       // see render_debug.rs for the debug view
-    pub dirty: bool, // EdModel is dirty if it has changed since the previous render.
+    pub project_search_opt: Option<ProjectSearch>, // find in files, see project_search.rs
+    pub file_to_open_opt: Option<PathBuf>, // module of a search match, opened by the event loop in main.rs
+    pub background_run_opt: Option<BackgroundRun>, // build and run of the app, see background_run.rs
+    pub pending_rename_opt: Option<RenamePlan>, // rename that was previewed but not yet applied, see rename.rs
+    pub command_palette_opt: Option<CommandPalette>, // commands of plugins, see plugin.rs
+    pub expect_results: Vec<ExpectResult>, // of the expects that were run since the code last changed, see test_lens.rs
+    pub literal_fmt_config: LiteralFmtConfig,
+    pub viewport: Viewport, // horizontal scroll and soft wrap of long lines, see viewport.rs
+    pub show_caret: bool,   // false while a blinking caret is hidden, see Config::caret_blink_ms
+    pub minimap: Minimap,   // overview of the whole file, see minimap.rs
+    pub dirty: bool,        // EdModel is dirty if it has changed since the previous render.
 }
 
 // a selected AST node, the roc type of this node is shown in the editor on ctrl+shift+"up arrow"
@@ -48,6 +70,7 @@ pub struct SelectedBlock {
     pub ast_node_id: ASTNodeId,
     pub mark_node_id: MarkNodeId,
     pub type_str: PoolStr,
+    pub value_str_opt: Option<PoolStr>, // set by the "Evaluate" plugin, shown instead of the type, see evaluate.rs
 }
 
 pub fn init_model<'a>(
@@ -102,7 +125,7 @@ pub fn init_model<'a>(
         CaretPos::End => CaretWSelect::new(code_lines.end_txt_pos(), None),
     };
 
-    Ok(EdModel {
+    let mut ed_model = EdModel {
         module,
         file_path,
         code_lines,
@@ -110,14 +133,31 @@ pub fn init_model<'a>(
         markup_ids,
         mark_node_pool,
         mark_id_ast_id_map,
+        semantic_styles: MutMap::default(),
+        plugin_decorations: MutMap::default(),
         glyph_dim_rect_opt: None,
         has_focus: true,
         caret_w_select_vec: NonEmpty::new((caret, None)),
         selected_block_opt: None,
         loaded_module: owned_loaded_module,
         show_debug_view: false,
+        project_search_opt: None,
+        file_to_open_opt: None,
+        background_run_opt: None,
+        pending_rename_opt: None,
+        command_palette_opt: None,
+        expect_results: Vec::new(),
+        literal_fmt_config: LiteralFmtConfig::default(),
+        viewport: Viewport::default(),
+        show_caret: true,
+        minimap: Minimap::default(),
         dirty: true,
-    })
+    };
+
+    ed_model.semantic_styles = semantic_styles(&ed_model)?;
+    ed_model.plugin_decorations = plugin_decorations(&ed_model);
+
+    Ok(ed_model)
 }
 
 impl<'a> EdModel<'a> {
@@ -1,10 +1,14 @@
 #![allow(dead_code)]
 
-use std::process::Stdio;
+use std::fs;
+use std::path::Path;
 
+use crate::editor::background_run::BackgroundRun;
+use crate::editor::breadcrumbs::{crumb_at_pos, get_breadcrumbs};
+use crate::editor::code_actions::{def_name_at_caret, get_code_actions_at_caret};
 use crate::editor::code_lines::CodeLines;
 use crate::editor::ed_error::EdResult;
-use crate::editor::ed_error::{MissingSelectionSnafu, RocCheckFailedSnafu};
+use crate::editor::ed_error::{FailedToUpdateIdentIdNameSnafu, MissingSelectionSnafu};
 use crate::editor::grid_node_map::GridNodeMap;
 use crate::editor::mvc::app_update::InputOutcome;
 use crate::editor::mvc::ed_model::EdModel;
@@ -21,6 +25,12 @@ use crate::editor::mvc::string_update::start_new_string;
 use crate::editor::mvc::string_update::update_small_string;
 use crate::editor::mvc::string_update::update_string;
 use crate::editor::mvc::tld_value_update::{start_new_tld_value, update_tld_val_name};
+use crate::editor::mvc::when_update::insert_when_at_caret;
+use crate::editor::plugin::{plugin_decorations, CommandPalette};
+use crate::editor::project_search::ProjectSearch;
+use crate::editor::rename::plan_rename;
+use crate::editor::render_run::run_button_at_pos;
+use crate::editor::semantic_highlight::semantic_styles;
 use crate::ui::text::caret_w_select::CaretWSelect;
 use crate::ui::text::lines::MoveCaretFun;
 use crate::ui::text::selection::validate_raw_sel;
@@ -28,11 +38,12 @@ use crate::ui::text::selection::RawSelection;
 use crate::ui::text::selection::Selection;
 use crate::ui::text::text_pos::TextPos;
 use crate::ui::text::{lines, lines::Lines, lines::SelectableLines};
-use crate::ui::ui_error::UIResult;
-use crate::ui::util::path_to_string;
+use crate::ui::ui_error::{MissingGlyphDimsSnafu, UIResult};
 use crate::ui::util::write_to_file;
 use crate::window::keyboard_input::Modifiers;
 use bumpalo::Bump;
+use cgmath::Vector2;
+use nonempty::NonEmpty;
 use roc_ast::constrain::constrain_expr;
 use roc_ast::constrain::Constraint;
 use roc_ast::lang::core::ast::ASTNodeId;
@@ -53,16 +64,17 @@ use roc_code_markup::markup::nodes::EQUALS;
 use roc_code_markup::slow_pool::MarkNodeId;
 use roc_code_markup::slow_pool::SlowPool;
 use roc_collections::all::MutMap;
-use roc_command_utils::cargo;
 use roc_module::ident::Lowercase;
 use roc_module::symbol::Symbol;
 use roc_region::all::Region;
+use roc_reporting::code_action::Fix;
 use roc_solve::module::Solved;
 use roc_types::pretty_print::name_and_print_var;
 use roc_types::pretty_print::DebugPrint;
 use roc_types::subs::{Subs, VarStore, Variable};
 use snafu::OptionExt;
 use threadpool::ThreadPool;
+use winit::dpi::PhysicalSize;
 use winit::event::VirtualKeyCode;
 use VirtualKeyCode::*;
 
@@ -362,6 +374,7 @@ impl<'a> EdModel<'a> {
             ast_node_id,
             mark_node_id,
             type_str,
+            value_str_opt: None,
         });
 
         self.dirty = true;
@@ -408,6 +421,264 @@ impl<'a> EdModel<'a> {
         Ok(())
     }
 
+    // select the MarkupNode and everything it contains
+    pub fn select_mark_node(&mut self, mark_node_id: MarkNodeId) -> EdResult<()> {
+        let ast_node_id = self.mark_id_ast_id_map.get(mark_node_id)?;
+
+        let (expr_start_pos, expr_end_pos) = if self.mark_node_pool.get(mark_node_id).is_nested() {
+            self.grid_node_map
+                .get_nested_start_end_pos(mark_node_id, self)?
+        } else {
+            (
+                self.grid_node_map.get_node_position(mark_node_id, true)?,
+                self.grid_node_map
+                    .get_node_position(mark_node_id, false)?
+                    .increment_col(),
+            )
+        };
+
+        self.set_selected_expr(expr_start_pos, expr_end_pos, ast_node_id, mark_node_id)
+    }
+
+    // a click on a crumb of the breadcrumb bar selects that ancestor
+    pub fn select_breadcrumb_at(
+        &mut self,
+        click_x: f32,
+        click_y: f32,
+        txt_coords: Vector2<f32>,
+    ) -> EdResult<()> {
+        let glyph_dim_rect = self.glyph_dim_rect_opt.context(MissingGlyphDimsSnafu {})?;
+
+        let crumbs = get_breadcrumbs(self)?;
+
+        if let Some(crumb) = crumb_at_pos(&crumbs, click_x, click_y, txt_coords, &glyph_dim_rect) {
+            match crumb.mark_node_id_opt {
+                Some(mark_node_id) => self.select_mark_node(mark_node_id)?,
+                None => self.select_all()?,
+            }
+
+            self.dirty = true;
+        }
+
+        Ok(())
+    }
+
+    // a click in the code moves the caret to the clicked char, also when lines are soft-wrapped or scrolled
+    pub fn set_caret_at_click(
+        &mut self,
+        click_x: f32,
+        click_y: f32,
+        txt_coords: Vector2<f32>,
+    ) -> EdResult<()> {
+        let glyph_dim_rect = self.glyph_dim_rect_opt.context(MissingGlyphDimsSnafu {})?;
+
+        // clicks above the code are handled by select_breadcrumb_at
+        if click_x >= txt_coords.x && click_y >= txt_coords.y {
+            let screen_pos = TextPos {
+                line: ((click_y - txt_coords.y) / glyph_dim_rect.height) as usize,
+                // the caret goes in front of the char if the click was on its left half
+                column: self
+                    .viewport
+                    .col_at(((click_x - txt_coords.x) / glyph_dim_rect.width).round() as usize),
+            };
+
+            let txt_pos = self.grid_node_map.txt_pos_at_screen_pos(screen_pos);
+
+            self.caret_w_select_vec = NonEmpty::new((CaretWSelect::new(txt_pos, None), None));
+            self.selected_block_opt = None;
+            self.dirty = true;
+        }
+
+        Ok(())
+    }
+
+    // a click or drag in the minimap moves the caret to the start of the line under the mouse
+    pub fn set_caret_at_minimap_line(&mut self, line_nr: usize) {
+        let txt_pos = TextPos {
+            line: line_nr,
+            column: 0,
+        };
+
+        self.caret_w_select_vec = NonEmpty::new((CaretWSelect::new(txt_pos, None), None));
+        self.selected_block_opt = None;
+        self.dirty = true;
+    }
+
+    // moves the carets to the previous or next row on screen, which may be on the same line when lines are soft-wrapped
+    fn move_caret_screen_row(&mut self, up: bool, modifiers: &Modifiers) -> UIResult<()> {
+        self.dirty = true;
+
+        for caret_tup in self.caret_w_select_vec.iter_mut() {
+            let caret_w_sel = caret_tup.0;
+            let screen_pos = self.grid_node_map.screen_pos(caret_w_sel.caret_pos);
+
+            let new_pos = match caret_w_sel.selection_opt {
+                // like lines::move_caret_up and lines::move_caret_down
+                Some(selection) if !modifiers.shift => {
+                    if up {
+                        selection.start_pos
+                    } else {
+                        selection.end_pos
+                    }
+                }
+                _ if up && screen_pos.line == 0 => TextPos { line: 0, column: 0 },
+                _ => self.grid_node_map.txt_pos_at_screen_pos(TextPos {
+                    line: if up {
+                        screen_pos.line - 1
+                    } else {
+                        screen_pos.line + 1
+                    },
+                    column: screen_pos.column,
+                }),
+            };
+
+            caret_tup.0 = caret_w_sel.move_caret_w_mods(new_pos, modifiers)?;
+            caret_tup.1 = None;
+        }
+        self.selected_block_opt = None;
+
+        Ok(())
+    }
+
+    // a negative nr_of_cols scrolls to the left
+    pub fn scroll_horizontally(&mut self, nr_of_cols: isize) {
+        self.viewport.scroll_by(nr_of_cols);
+        self.dirty = true;
+    }
+
+    // applies the first quick fix for the caret position, see code_actions.rs
+    pub fn apply_code_action_at_caret(&mut self) -> EdResult<()> {
+        let code_actions = get_code_actions_at_caret(self)?;
+
+        if let Some(code_action) = code_actions.first() {
+            let mut new_def_name_opt = None;
+
+            match &code_action.fix {
+                Fix::AddImport { entry, .. } => {
+                    self.module.ast.header.imports.push(entry.clone());
+                }
+                Fix::PrefixWithUnderscore { .. } => {
+                    if let Some(def_name) = def_name_at_caret(self)? {
+                        let new_def_name = format!("_{}", def_name);
+
+                        if let Err(err_str) = self
+                            .module
+                            .env
+                            .ident_ids
+                            .update_key(&def_name, &new_def_name)
+                        {
+                            FailedToUpdateIdentIdNameSnafu { err_str }.fail()?;
+                        }
+
+                        new_def_name_opt = Some(new_def_name);
+                    }
+                }
+                Fix::AddWhenBranches { .. } => (),
+            }
+
+            let caret_pos = self.get_caret();
+
+            self.post_process_ast_update()?;
+
+            // keep the caret on the same char if the underscore was inserted before it
+            if let Some(new_def_name) = new_def_name_opt {
+                if self
+                    .code_lines
+                    .get_line_ref(caret_pos.line)?
+                    .starts_with(&new_def_name)
+                {
+                    self.simple_move_caret_right(caret_pos, 1);
+                }
+            }
+
+            self.dirty = true;
+        }
+
+        Ok(())
+    }
+
+    // Prints which lines of which files renaming the def at the caret would change.
+    // Nothing is renamed until apply_pending_rename is called.
+    pub fn start_rename_at_caret(&mut self, new_name: &str) -> EdResult<()> {
+        self.pending_rename_opt = None;
+
+        let home = self.loaded_module.module_id;
+
+        let ident_id_opt = match def_name_at_caret(self)? {
+            Some(def_name) => self
+                .loaded_module
+                .interns
+                .all_ident_ids
+                .get(&home)
+                .and_then(|ident_ids| ident_ids.get_id(&def_name)),
+            None => None,
+        };
+
+        match ident_id_opt {
+            Some(ident_id) => {
+                match plan_rename(
+                    &self.loaded_module,
+                    Symbol::new(home, ident_id),
+                    new_name.trim(),
+                ) {
+                    Ok(rename_plan) => {
+                        println!(
+                            "\n{}\nPress F2 again to rename, Escape to cancel.",
+                            rename_plan.preview()
+                        );
+
+                        self.pending_rename_opt = Some(rename_plan);
+                    }
+                    Err(err_str) => println!("\n{}", err_str),
+                }
+            }
+            None => println!("\nPut the caret on a definition to rename it."),
+        }
+
+        Ok(())
+    }
+
+    // Writes the other modules to disk, this module is updated through its AST and saved like any other edit.
+    pub fn apply_pending_rename(&mut self) -> EdResult<()> {
+        if let Some(rename_plan) = self.pending_rename_opt.take() {
+            let home = self.loaded_module.module_id;
+            let def_module_id = rename_plan.symbol.module_id();
+
+            rename_plan.write_files(home)?;
+
+            if def_module_id == home {
+                if let Err(err_str) = self
+                    .module
+                    .env
+                    .ident_ids
+                    .update_key(&rename_plan.old_name, &rename_plan.new_name)
+                {
+                    FailedToUpdateIdentIdNameSnafu { err_str }.fail()?;
+                }
+            }
+
+            // lookups are displayed using the names in interns
+            if let Some(ident_ids) = self
+                .loaded_module
+                .interns
+                .all_ident_ids
+                .get_mut(&def_module_id)
+            {
+                if let Err(err_str) =
+                    ident_ids.update_key(&rename_plan.old_name, &rename_plan.new_name)
+                {
+                    FailedToUpdateIdentIdNameSnafu { err_str }.fail()?;
+                }
+            }
+
+            self.post_process_ast_update()?;
+
+            self.dirty = true;
+        }
+
+        Ok(())
+    }
+
     fn extract_expr_from_def(&self, def_id: DefId) -> Option<ExprId> {
         let def = self.module.env.pool.get(def_id);
 
@@ -416,6 +687,7 @@ impl<'a> EdModel<'a> {
                 identifier_id: _,
                 expr_id,
             } => Some(*expr_id),
+            Def2::Expect { expr_id } => Some(*expr_id),
             Def2::Blank => None,
             Def2::CommentsBefore {
                 comments: _,
@@ -508,6 +780,23 @@ impl<'a> EdModel<'a> {
         virtual_keycode: VirtualKeyCode,
         _sound_thread_pool: &mut ThreadPool,
     ) -> EdResult<()> {
+        // the arrow keys choose a command while the command palette is open
+        if let Some(command_palette) = &mut self.command_palette_opt {
+            match virtual_keycode {
+                Up => {
+                    command_palette.select_prev();
+                    self.dirty = true;
+                    return Ok(());
+                }
+                Down => {
+                    command_palette.select_next();
+                    self.dirty = true;
+                    return Ok(());
+                }
+                _ => (),
+            }
+        }
+
         match virtual_keycode {
             Left => self.move_caret_left(modifiers)?,
             Up => {
@@ -525,6 +814,50 @@ impl<'a> EdModel<'a> {
                     self.select_all()?
                 }
             }
+            F => {
+                if modifiers.cmd_or_ctrl() && modifiers.shift {
+                    self.start_project_search()?
+                }
+            }
+            P => {
+                if modifiers.cmd_or_ctrl() && modifiers.shift {
+                    self.command_palette_opt = match self.command_palette_opt.take() {
+                        Some(_) => None,
+                        None => CommandPalette::open(),
+                    };
+                    self.dirty = true;
+                }
+            }
+            F3 => {
+                if modifiers.shift {
+                    self.goto_search_match(ProjectSearch::select_prev)?
+                } else {
+                    self.goto_search_match(ProjectSearch::select_next)?
+                }
+            }
+            Period => {
+                if modifiers.cmd_or_ctrl() {
+                    self.apply_code_action_at_caret()?
+                }
+            }
+            W => {
+                if modifiers.cmd_or_ctrl() && modifiers.shift {
+                    insert_when_at_caret(self)?
+                }
+            }
+            Escape => {
+                let closed_search = self.project_search_opt.take().is_some();
+                let closed_palette = self.command_palette_opt.take().is_some();
+                let closed_run = self.background_run_opt.take().is_some();
+
+                if closed_search || closed_palette || closed_run {
+                    self.dirty = true;
+                }
+
+                if self.pending_rename_opt.take().is_some() {
+                    println!("\nRename cancelled.");
+                }
+            }
             S => {
                 if modifiers.cmd_or_ctrl() {
                     self.save_file()?
@@ -532,8 +865,7 @@ impl<'a> EdModel<'a> {
             }
             R => {
                 if modifiers.cmd_or_ctrl() {
-                    self.check_file()?;
-                    self.run_file()?
+                    self.start_background_run()?
                 }
             }
 
@@ -544,6 +876,12 @@ impl<'a> EdModel<'a> {
                 self.show_debug_view = !self.show_debug_view;
                 self.dirty = true;
             }
+            Z => {
+                if modifiers.alt {
+                    self.viewport.toggle_soft_wrap();
+                    self.dirty = true;
+                }
+            }
             _ => (),
         }
 
@@ -599,55 +937,166 @@ impl<'a> EdModel<'a> {
         Ok(())
     }
 
-    fn save_file(&mut self) -> UIResult<()> {
-        let all_lines_str = self.code_lines.all_lines_as_string();
+    // search all .roc files of the project for the selected text
+    fn start_project_search(&mut self) -> UIResult<()> {
+        match self.get_selected_str()? {
+            Some(query) if !query.is_empty() => {
+                let project_dir = match self.file_path.parent() {
+                    Some(parent) if parent.as_os_str().is_empty() => Path::new("."),
+                    Some(parent) => parent,
+                    None => Path::new("."),
+                };
 
-        write_to_file(self.file_path, &all_lines_str)?;
+                self.project_search_opt = Some(ProjectSearch::start(project_dir, query));
+                self.dirty = true;
+            }
+            _ => println!("\nSelect some text to search for it in all files of the project."),
+        }
 
-        println!("\nsave successful!");
+        Ok(())
+    }
+
+    // collect new results of a running project search, returns true if a redraw is needed
+    pub fn poll_project_search(&mut self) -> bool {
+        if let Some(project_search) = &mut self.project_search_opt {
+            if project_search.poll() {
+                self.dirty = true;
+
+                return true;
+            }
+        }
+
+        false
+    }
+
+    pub fn project_search_running(&self) -> bool {
+        self.project_search_opt
+            .as_ref()
+            .map(|project_search| !project_search.done)
+            .unwrap_or(false)
+    }
+
+    // the build reads the file from disk, so it is saved first
+    fn start_background_run(&mut self) -> EdResult<()> {
+        if self.background_run_running() {
+            println!("\nThe app is already being built or run, press Esc to stop it.");
+
+            return Ok(());
+        }
+
+        self.save_file()?;
+
+        self.background_run_opt = Some(BackgroundRun::start(self.file_path));
+        self.dirty = true;
 
         Ok(())
     }
 
-    fn check_file(&mut self) -> EdResult<()> {
-        println!("\nChecking file (cargo run check <file>)...");
+    // collect the progress and output of a running build or app, returns true if a redraw is needed
+    pub fn poll_background_run(&mut self) -> bool {
+        if let Some(background_run) = &mut self.background_run_opt {
+            if background_run.poll() {
+                self.dirty = true;
 
-        let roc_file_str = path_to_string(self.file_path);
+                return true;
+            }
+        }
 
-        let cmd_out = cargo()
-            .arg("run")
-            .arg("--release")
-            .arg("check")
-            .arg(roc_file_str)
-            .stdout(Stdio::inherit())
-            .stderr(Stdio::inherit())
-            .output()?;
+        false
+    }
+
+    pub fn background_run_running(&self) -> bool {
+        self.background_run_opt
+            .as_ref()
+            .map(|background_run| background_run.is_running())
+            .unwrap_or(false)
+    }
 
-        if !cmd_out.status.success() {
-            RocCheckFailedSnafu.fail()?
+    // returns true if the click was on the Run button, see render_run.rs
+    pub fn click_run_button(
+        &mut self,
+        click_x: f32,
+        click_y: f32,
+        size: &PhysicalSize<u32>,
+        txt_coords: Vector2<f32>,
+    ) -> EdResult<bool> {
+        let glyph_dim_rect = self.glyph_dim_rect_opt.context(MissingGlyphDimsSnafu {})?;
+
+        if run_button_at_pos(click_x, click_y, size, txt_coords, &glyph_dim_rect) {
+            self.start_background_run()?;
+
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn goto_search_match(&mut self, select_fun: fn(&mut ProjectSearch)) -> UIResult<()> {
+        if let Some(project_search) = &mut self.project_search_opt {
+            select_fun(project_search);
+        }
+
+        self.goto_selected_search_match()
+    }
+
+    // select the text of the selected search match, if the match is in another module, that module
+    // is opened by the event loop, see `file_to_open_opt`
+    pub fn goto_selected_search_match(&mut self) -> UIResult<()> {
+        let (search_match, query_len) = match &self.project_search_opt {
+            Some(project_search) => match project_search.selected_match() {
+                Some(search_match) => (search_match.clone(), project_search.query.chars().count()),
+                None => return Ok(()),
+            },
+            None => return Ok(()),
+        };
+
+        self.dirty = true;
+
+        if !is_same_file(&search_match.file_path, self.file_path) {
+            self.file_to_open_opt = Some(search_match.file_path);
+
+            return Ok(());
+        }
+
+        // the file on disk may not be formatted the same way as the code in the editor
+        let TextPos { line, column } = search_match.txt_pos;
+
+        if line < self.code_lines.nr_of_lines() {
+            let line_len = self.code_lines.get_line_ref(line)?.chars().count();
+            let start_pos = TextPos {
+                line,
+                column: column.min(line_len),
+            };
+
+            let end_pos = TextPos {
+                line,
+                column: (column + query_len).min(line_len),
+            };
+
+            self.set_sel_none();
+
+            if start_pos < end_pos {
+                self.set_raw_sel(RawSelection { start_pos, end_pos })?;
+            }
+
+            self.set_caret(start_pos);
         }
 
         Ok(())
     }
 
-    fn run_file(&mut self) -> EdResult<()> {
-        println!("\nExecuting file (cargo run --release <file>)...");
+    pub fn save_file(&mut self) -> UIResult<()> {
+        let all_lines_str = self.code_lines.all_lines_as_string();
 
-        let roc_file_str = path_to_string(self.file_path);
+        write_to_file(self.file_path, &all_lines_str)?;
 
-        cargo()
-            .arg("run")
-            .arg("--release")
-            .arg(roc_file_str)
-            .stdout(Stdio::inherit())
-            .stderr(Stdio::inherit())
-            .output()?;
+        println!("\nsave successful!");
 
         Ok(())
     }
 
     /// update MarkupNode's, grid_node_map, code_lines after the AST has been updated
-    fn post_process_ast_update(&mut self) -> EdResult<()> {
+    pub fn post_process_ast_update(&mut self) -> EdResult<()> {
         //dbg!("{}",self.module.ast.ast_to_string(self.module.env.pool));
 
         let markup_ids_tup = ast_to_mark_nodes(
@@ -664,7 +1113,10 @@ impl<'a> EdModel<'a> {
             &self.markup_ids,
             &self.mark_node_pool,
         ));
+        // the wrap width only changes when the window is resized
+        let wrap_width_opt = self.grid_node_map.wrap_width_opt;
         self.grid_node_map = GridNodeMap::default();
+        self.grid_node_map.set_wrap_width(wrap_width_opt);
 
         let mut line_nr = 0;
         let mut col_nr = 0;
@@ -681,6 +1133,11 @@ impl<'a> EdModel<'a> {
             )?
         }
 
+        self.semantic_styles = semantic_styles(self)?;
+        // the results point to the old markup and may no longer hold for the new code
+        self.expect_results.clear();
+        self.plugin_decorations = plugin_decorations(self);
+
         Ok(())
     }
 }
@@ -712,6 +1169,10 @@ impl<'a> SelectableLines for EdModel<'a> {
     }
 
     fn move_caret_up(&mut self, modifiers: &Modifiers) -> UIResult<()> {
+        if self.viewport.soft_wrap {
+            return self.move_caret_screen_row(true, modifiers);
+        }
+
         let move_fun: MoveCaretFun<CodeLines> = lines::move_caret_up;
         EdModel::move_caret(self, move_fun, modifiers)?;
 
@@ -719,6 +1180,10 @@ impl<'a> SelectableLines for EdModel<'a> {
     }
 
     fn move_caret_down(&mut self, modifiers: &Modifiers) -> UIResult<()> {
+        if self.viewport.soft_wrap {
+            return self.move_caret_screen_row(false, modifiers);
+        }
+
         let move_fun: MoveCaretFun<CodeLines> = lines::move_caret_down;
         EdModel::move_caret(self, move_fun, modifiers)?;
 
@@ -819,6 +1284,13 @@ impl<'a> SelectableLines for EdModel<'a> {
     }
 }
 
+fn is_same_file(path_a: &Path, path_b: &Path) -> bool {
+    match (fs::canonicalize(path_a), fs::canonicalize(path_b)) {
+        (Ok(canonical_a), Ok(canonical_b)) => canonical_a == canonical_b,
+        _ => path_a == path_b,
+    }
+}
+
 pub struct NodeContext<'a> {
     pub old_caret_pos: TextPos,
     pub curr_mark_node_id: MarkNodeId,
@@ -906,6 +1378,7 @@ pub fn handle_new_char_def(
                 )?
             }
         }
+        Def2::Expect { .. } => InputOutcome::Ignored,
         Def2::CommentsBefore { .. } => {
             todo!()
         }
@@ -1182,6 +1655,22 @@ pub fn handle_new_char_diff_mark_nodes_prev_is_expr(
 pub fn handle_new_char(received_char: &char, ed_model: &mut EdModel) -> EdResult<InputOutcome> {
     //dbg!("{}", ed_model.module.ast.ast_to_string(ed_model.module.env.pool));
 
+    // Enter runs the selected command of the command palette, other chars don't change the code while it is open
+    if let Some(command_palette) = &ed_model.command_palette_opt {
+        if *received_char == '\r' {
+            let command = command_palette.selected_command();
+
+            ed_model.command_palette_opt = None;
+            ed_model.dirty = true;
+
+            (command.run)(ed_model)?;
+
+            return Ok(InputOutcome::Accepted);
+        }
+
+        return Ok(InputOutcome::Ignored);
+    }
+
     let input_outcome = match received_char {
             '\u{e000}'..='\u{f8ff}' // http://www.unicode.org/faq/private_use.html
             | '\u{f0000}'..='\u{ffffd}' // ^
@@ -1272,6 +1761,7 @@ pub mod test_ed_update {
     use std::iter;
 
     use crate::editor::ed_error::print_err;
+    use crate::editor::evaluate::evaluate_selection;
     use crate::editor::mvc::ed_model::test_ed_model::ed_model_from_dsl;
     use crate::editor::mvc::ed_model::test_ed_model::ed_model_to_dsl;
     use crate::editor::mvc::ed_model::test_ed_model::init_model_refs;
@@ -3145,6 +3635,54 @@ pub mod test_ed_update {
         Ok(())
     }
 
+    // Create ed_model from lines DSL, select the current Expr2 `selects` times, evaluate the selection,
+    // check if the tooltip shows expected_tooltip.
+    pub fn assert_evaluation(
+        lines: Vec<String>,
+        selects: usize,
+        expected_tooltip: &str,
+    ) -> Result<(), String> {
+        let mut code_str = lines.join("").replace('┃', "");
+
+        let mut model_refs = init_model_refs();
+        let code_arena = Bump::new();
+        let module_ids = ModuleIds::default();
+
+        let mut ed_model = ed_model_from_dsl(
+            &mut code_str,
+            lines,
+            &mut model_refs,
+            &module_ids,
+            &code_arena,
+        )?;
+
+        for _ in 0..selects {
+            ed_model.select_expr()?;
+        }
+
+        ed_res_to_res(evaluate_selection(&mut ed_model))?;
+
+        let created_tooltip = ed_model.selected_block_opt.unwrap().value_str_opt.unwrap();
+
+        assert_eq!(
+            created_tooltip.as_str(ed_model.module.env.pool),
+            expected_tooltip
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_evaluate_selection() -> Result<(), String> {
+        assert_evaluation(ovec!["val = ┃5"], 1, "5 : Num *")?;
+        assert_evaluation(ovec!["val = \"abc┃\""], 1, "\"abc\" : Str")?;
+        assert_evaluation(ovec!["val = { a: ┃1 }"], 2, "{ a: 1 } : { a : Num * }")?;
+        assert_evaluation(ovec!["val = [ ┃1, 2, 3 ]"], 2, "[1, 2, 3] : List (Num *)")?;
+        assert_evaluation(ovec!["val = [ ┃ ]"], 1, "[] : List *")?;
+
+        Ok(())
+    }
+
     #[test]
     fn test_type_tooltip_mismatch() -> Result<(), String> {
         assert_type_tooltips_clean(
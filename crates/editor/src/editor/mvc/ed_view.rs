@@ -1,15 +1,25 @@
 use super::ed_model::EdModel;
+use crate::editor::breadcrumbs::{build_breadcrumb_graphics, get_breadcrumbs};
+use crate::editor::code_actions::{build_code_action_graphics, get_code_actions_at_caret};
 use crate::editor::config::Config;
 use crate::editor::ed_error::EdResult;
+use crate::editor::grid_node_map::GridNodeMap;
+use crate::editor::minimap::MINIMAP_WIDTH;
 use crate::editor::mvc::ed_model::SelectedBlock;
 use crate::editor::render_ast::build_code_graphics;
+use crate::editor::render_command_palette::build_command_palette_graphics;
 use crate::editor::render_debug::build_debug_graphics;
+use crate::editor::render_run::{build_run_button_graphics, build_run_graphics};
+use crate::editor::render_search::build_search_graphics;
 use crate::editor::resources::strings::START_TIP;
+use crate::editor::test_lens::build_expect_failure_graphics;
+use crate::editor::viewport::Viewport;
 use crate::graphics::primitives::rect::Rect;
 use crate::graphics::primitives::text::{owned_section_from_text, Text};
 use crate::ui::text::caret_w_select::make_caret_rect;
 use crate::ui::text::caret_w_select::make_selection_rect;
 use crate::ui::text::caret_w_select::CaretWSelect;
+use crate::ui::text::lines::SelectableLines;
 use crate::ui::text::selection::Selection;
 use crate::ui::tooltip::ToolTip;
 use crate::ui::ui_error::MissingGlyphDimsSnafu;
@@ -77,6 +87,19 @@ pub fn model_to_wgpu<'a>(
         .glyph_dim_rect_opt
         .context(MissingGlyphDimsSnafu {})?;
 
+    // the code may not fit in the window anymore, or the caret may have left the visible columns
+    let minimap_width = if config.show_minimap {
+        MINIMAP_WIDTH
+    } else {
+        0.0
+    };
+    let nr_of_cols = ((size.width as f32 - txt_coords.x - minimap_width) / glyph_dim_rect.width)
+        .max(1.0) as usize;
+    let caret_pos = ed_model.get_caret();
+    ed_model
+        .viewport
+        .update(nr_of_cols, caret_pos, &mut ed_model.grid_node_map);
+
     let mut all_rendered = RenderedWgpu::new();
 
     let tip_txt_coords = (
@@ -95,6 +118,22 @@ pub fn model_to_wgpu<'a>(
 
     all_rendered.add_text_behind(start_tip_text);
 
+    all_rendered.add_text_behind(build_breadcrumb_graphics(
+        &get_breadcrumbs(ed_model)?,
+        size,
+        txt_coords,
+        config,
+        &glyph_dim_rect,
+    ));
+
+    all_rendered.add_text_behind(build_run_button_graphics(
+        size,
+        txt_coords,
+        config,
+        &glyph_dim_rect,
+        ed_model.background_run_running(),
+    ));
+
     let rendered_code_graphics = build_code_graphics(
         &ed_model.markup_ids,
         size,
@@ -102,10 +141,32 @@ pub fn model_to_wgpu<'a>(
         config,
         glyph_dim_rect,
         &ed_model.mark_node_pool,
+        &ed_model.semantic_styles,
+        &ed_model.plugin_decorations,
+        &ed_model.grid_node_map,
+        &ed_model.viewport,
     )?;
 
     all_rendered.extend(rendered_code_graphics);
 
+    if config.show_minimap {
+        ed_model.minimap.update(
+            &ed_model.code_lines,
+            &ed_model.grid_node_map,
+            &ed_model.mark_node_pool,
+            &ed_model.semantic_styles,
+            &config.ed_theme,
+        )?;
+
+        all_rendered.add_rects_behind(ed_model.minimap.build_graphics(
+            size,
+            txt_coords,
+            &glyph_dim_rect,
+            &ed_model.grid_node_map,
+            &config.ed_theme,
+        ));
+    }
+
     let caret_w_sel_vec = ed_model
         .caret_w_select_vec
         .iter()
@@ -119,17 +180,55 @@ pub fn model_to_wgpu<'a>(
         config,
         glyph_dim_rect,
         ed_model.module.env.pool,
+        &ed_model.grid_node_map,
+        &ed_model.viewport,
+        ed_model.show_caret,
     )?;
 
     all_rendered.extend(rendered_selection);
 
+    if let Some(code_action_section) = build_code_action_graphics(
+        &get_code_actions_at_caret(ed_model)?,
+        ed_model,
+        size,
+        txt_coords,
+        config,
+        &glyph_dim_rect,
+    )? {
+        all_rendered.add_text_behind(code_action_section);
+    }
+
+    if let Some((tip_rect, tip_text)) =
+        build_expect_failure_graphics(ed_model, txt_coords, config, &glyph_dim_rect)?
+    {
+        all_rendered.add_rect_front(tip_rect);
+        all_rendered.add_text_front(tip_text);
+    }
+
     if ed_model.show_debug_view {
         all_rendered.add_text_behind(build_debug_graphics(size, txt_coords, config, ed_model)?);
+    } else if let Some(command_palette) = &ed_model.command_palette_opt {
+        all_rendered.add_text_behind(build_command_palette_graphics(
+            size,
+            txt_coords,
+            config,
+            command_palette,
+        ));
+    } else if let Some(project_search) = &ed_model.project_search_opt {
+        all_rendered.add_text_behind(build_search_graphics(
+            size,
+            txt_coords,
+            config,
+            project_search,
+        ));
+    } else if let Some(background_run) = &ed_model.background_run_opt {
+        all_rendered.add_text_behind(build_run_graphics(size, txt_coords, config, background_run));
     }
 
     Ok(all_rendered)
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn build_selection_graphics(
     caret_w_select_vec: Vec<CaretWSelect>,
     selected_expr_opt: &Option<SelectedBlock>,
@@ -137,6 +236,9 @@ pub fn build_selection_graphics(
     config: &Config,
     glyph_dim_rect: Rect,
     pool: &Pool,
+    grid_node_map: &GridNodeMap,
+    viewport: &Viewport,
+    show_caret: bool,
 ) -> EdResult<RenderedWgpu> {
     let mut all_rendered = RenderedWgpu::new();
     let char_width = glyph_dim_rect.width;
@@ -145,35 +247,60 @@ pub fn build_selection_graphics(
     let y_offset = 0.1 * char_height;
 
     for caret_w_sel in caret_w_select_vec {
-        let caret_row = caret_w_sel.caret_pos.line as f32;
-        let caret_col = caret_w_sel.caret_pos.column as f32;
-
-        let top_left_x = txt_coords.x + caret_col * char_width;
-        let top_left_y = txt_coords.y + caret_row * char_height + y_offset;
-
         if let Some(selection) = caret_w_sel.selection_opt {
             let Selection { start_pos, end_pos } = selection;
 
-            let sel_rect_x = txt_coords.x + ((start_pos.column as f32) * char_width);
-            let sel_rect_y = txt_coords.y + char_height * (start_pos.line as f32) + y_offset;
+            let mut sel_rect_coords_opt = None;
 
-            let width =
-                ((end_pos.column as f32) * char_width) - ((start_pos.column as f32) * char_width);
+            // a selection can cover several rows on screen when lines are soft-wrapped
+            for line_nr in start_pos.line..=end_pos.line {
+                let start_col = if line_nr == start_pos.line {
+                    start_pos.column
+                } else {
+                    0
+                };
+                let end_col = if line_nr == end_pos.line {
+                    end_pos.column
+                } else {
+                    grid_node_map
+                        .lines
+                        .get(line_nr)
+                        .map_or(0, |line| line.len())
+                };
 
-            all_rendered.add_rect_behind(make_selection_rect(
-                sel_rect_x,
-                sel_rect_y,
-                width,
-                &glyph_dim_rect,
-                &config.ed_theme.ui_theme,
-            ));
+                for (part_pos, part_len) in grid_node_map.screen_parts(line_nr, start_col..end_col)
+                {
+                    if let Some(visible_cols) =
+                        viewport.visible_cols(part_pos.column..(part_pos.column + part_len))
+                    {
+                        let sel_rect_x = txt_coords.x + (visible_cols.start as f32) * char_width;
+                        let sel_rect_y =
+                            txt_coords.y + char_height * (part_pos.line as f32) + y_offset;
+
+                        all_rendered.add_rect_behind(make_selection_rect(
+                            sel_rect_x,
+                            sel_rect_y,
+                            (visible_cols.len() as f32) * char_width,
+                            &glyph_dim_rect,
+                            &config.ed_theme.ui_theme,
+                        ));
+
+                        sel_rect_coords_opt.get_or_insert((sel_rect_x, sel_rect_y));
+                    }
+                }
+            }
 
             // render tooltip showing type
-            if let Some(selected_expr) = selected_expr_opt {
+            if let (Some(selected_expr), Some((sel_rect_x, sel_rect_y))) =
+                (selected_expr_opt, sel_rect_coords_opt)
+            {
                 let tooltip = ToolTip {
                     position_x: sel_rect_x,
                     position_y: sel_rect_y - glyph_dim_rect.height,
-                    text: selected_expr.type_str.as_str(pool),
+                    text: selected_expr
+                        .value_str_opt
+                        .unwrap_or(selected_expr.type_str)
+                        .as_str(pool),
                 };
 
                 let (tip_rect, tip_text) = tooltip.render_tooltip(
@@ -187,12 +314,25 @@ pub fn build_selection_graphics(
             }
         }
 
-        all_rendered.add_rect_front(make_caret_rect(
-            top_left_x,
-            top_left_y,
-            &glyph_dim_rect,
-            &config.ed_theme.ui_theme,
-        ));
+        // a blinking caret is hidden half of the time
+        if !show_caret {
+            continue;
+        }
+
+        let caret_screen_pos = grid_node_map.screen_pos(caret_w_sel.caret_pos);
+
+        // the caret may have been scrolled out of view with the mouse wheel
+        if let Some(caret_col) = viewport.visible_col(caret_screen_pos.column) {
+            let top_left_x = txt_coords.x + (caret_col as f32) * char_width;
+            let top_left_y = txt_coords.y + (caret_screen_pos.line as f32) * char_height + y_offset;
+
+            all_rendered.add_rect_front(make_caret_rect(
+                top_left_x,
+                top_left_y,
+                &glyph_dim_rect,
+                &config.ed_theme.ui_theme,
+            ));
+        }
     }
 
     Ok(all_rendered)
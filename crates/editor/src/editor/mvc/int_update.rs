@@ -108,6 +108,9 @@ pub fn update_int(
 fn update_small_int_num(number: &mut IntVal, updated_str: &str) -> EdResult<()> {
     use IntVal::*;
 
+    // digit separators may have been added when the int was formatted
+    let updated_str = &updated_str.replace('_', "");
+
     *number = match number {
         I64(_) => I64(check_parse_res(updated_str.parse::<i64>())?),
         U64(_) => U64(check_parse_res(updated_str.parse::<u64>())?),
@@ -0,0 +1,349 @@
+use roc_ast::lang::core::ast::ASTNodeId;
+use roc_ast::lang::core::expr::expr2::{ArrString, Expr2, ExprId, ARR_STRING_CAPACITY};
+use roc_ast::mem_pool::pool_str::PoolStr;
+use roc_fmt::expr::{needs_unicode_escape, short_escape};
+use serde::{Deserialize, Serialize};
+
+use crate::editor::ed_error::EdResult;
+use crate::editor::mvc::ed_model::EdModel;
+use crate::ui::text::lines::SelectableLines;
+use crate::ui::text::text_pos::TextPos;
+
+// Literals are normalized once the caret leaves them, never while the user is typing.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct LiteralFmtConfig {
+    pub digit_separators: bool, // 1000000 -> 1_000_000
+    pub min_digits_for_separators: usize,
+    pub normalize_str_escapes: bool, // \u(000A) -> \n
+}
+
+impl Default for LiteralFmtConfig {
+    fn default() -> Self {
+        Self {
+            digit_separators: true,
+            min_digits_for_separators: 5,
+            normalize_str_escapes: true,
+        }
+    }
+}
+
+// number or string literal that contains or touches the caret
+pub fn get_literal_at_caret(ed_model: &EdModel) -> EdResult<Option<ExprId>> {
+    let caret_pos = ed_model.get_caret();
+
+    // the caret is right after the literal while typing it, so check that position first
+    let mut positions = Vec::new();
+
+    if caret_pos.column > 0 {
+        positions.push(caret_pos.decrement_col());
+    }
+    positions.push(caret_pos);
+
+    for pos in positions {
+        if ed_model.grid_node_map.node_exists_at_pos(pos) {
+            let mark_node_id = ed_model.grid_node_map.get_id_at_row_col(pos)?;
+
+            if let ASTNodeId::AExprId(expr_id) = ed_model.mark_id_ast_id_map.get(mark_node_id)? {
+                if is_literal(ed_model.module.env.pool.get(expr_id)) {
+                    return Ok(Some(expr_id));
+                }
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+fn is_literal(expr: &Expr2) -> bool {
+    matches!(
+        expr,
+        Expr2::SmallInt { .. }
+            | Expr2::I128 { .. }
+            | Expr2::U128 { .. }
+            | Expr2::Float { .. }
+            | Expr2::SmallStr(_)
+            | Expr2::Str(_)
+    )
+}
+
+// Call after handling input, with the literal that was at the caret before the input.
+pub fn fmt_literal_if_left(
+    ed_model: &mut EdModel,
+    prev_literal_opt: Option<ExprId>,
+) -> EdResult<()> {
+    if let Some(prev_literal_id) = prev_literal_opt {
+        if get_literal_at_caret(ed_model)? != Some(prev_literal_id) {
+            fmt_literal(ed_model, prev_literal_id)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn fmt_literal(ed_model: &mut EdModel, expr_id: ExprId) -> EdResult<()> {
+    let config = ed_model.literal_fmt_config;
+    let pool = &ed_model.module.env.pool;
+
+    let (is_num, old_text) = match pool.get(expr_id) {
+        Expr2::SmallInt { text, .. }
+        | Expr2::I128 { text, .. }
+        | Expr2::U128 { text, .. }
+        | Expr2::Float { text, .. } => (true, text.as_str(pool).to_owned()),
+        Expr2::SmallStr(array_str) => (false, array_str.as_str().to_owned()),
+        Expr2::Str(pool_str) => (false, pool_str.as_str(pool).to_owned()),
+        _ => return Ok(()),
+    };
+
+    let new_text = if is_num && config.digit_separators {
+        fmt_num_text(&old_text, config.min_digits_for_separators)
+    } else if !is_num && config.normalize_str_escapes {
+        fmt_str_escapes(&old_text)
+    } else {
+        return Ok(());
+    };
+
+    if new_text == old_text {
+        return Ok(());
+    }
+
+    let literal_start_opt = literal_start_pos(ed_model, expr_id)?;
+
+    let pool = &mut ed_model.module.env.pool;
+
+    if is_num {
+        let new_pool_str = PoolStr::new(&new_text, pool);
+
+        match pool.get_mut(expr_id) {
+            Expr2::SmallInt { text, .. }
+            | Expr2::I128 { text, .. }
+            | Expr2::U128 { text, .. }
+            | Expr2::Float { text, .. } => *text = new_pool_str,
+            _ => unreachable!(),
+        }
+    } else {
+        let new_expr = if new_text.len() <= ARR_STRING_CAPACITY {
+            // safe unwrap because we checked the length
+            Expr2::SmallStr(ArrString::from(&new_text).unwrap())
+        } else {
+            Expr2::Str(PoolStr::new(&new_text, pool))
+        };
+
+        pool.set(expr_id, new_expr);
+    }
+
+    ed_model.post_process_ast_update()?;
+
+    // the literal may be located before the caret on the same line
+    if let Some(literal_start) = literal_start_opt {
+        let caret_pos = ed_model.get_caret();
+
+        if caret_pos.line == literal_start.line && caret_pos.column > literal_start.column {
+            ed_model.set_caret(TextPos {
+                line: caret_pos.line,
+                column: (caret_pos.column + new_text.len()).saturating_sub(old_text.len()),
+            });
+        }
+    }
+
+    ed_model.dirty = true;
+
+    Ok(())
+}
+
+fn literal_start_pos(ed_model: &EdModel, expr_id: ExprId) -> EdResult<Option<TextPos>> {
+    for (line, line_node_ids) in ed_model.grid_node_map.lines.iter().enumerate() {
+        for (column, mark_node_id) in line_node_ids.iter().enumerate() {
+            if ed_model.mark_id_ast_id_map.get(*mark_node_id)? == ASTNodeId::AExprId(expr_id) {
+                return Ok(Some(TextPos { line, column }));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+// Groups the digits before the dot (if any) in threes: `-1234567.891` -> `-1_234_567.891`.
+// Numbers with a base prefix (0x...) are left alone.
+pub fn fmt_num_text(num_text: &str, min_digits_for_separators: usize) -> String {
+    let (sign, unsigned) = match num_text.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", num_text),
+    };
+
+    let int_part_len = unsigned
+        .find(|ch: char| !(ch.is_ascii_digit() || ch == '_'))
+        .unwrap_or(unsigned.len());
+    let (int_part, rest) = unsigned.split_at(int_part_len);
+
+    let is_base_prefix = rest.starts_with(|ch: char| ch.is_ascii_alphabetic()) && int_part == "0";
+
+    if int_part.is_empty() || is_base_prefix {
+        return num_text.to_owned();
+    }
+
+    let digits: Vec<char> = int_part.chars().filter(|ch| *ch != '_').collect();
+
+    let mut grouped = String::with_capacity(num_text.len() + digits.len() / 3);
+    grouped.push_str(sign);
+
+    for (index, digit) in digits.iter().enumerate() {
+        let digits_left = digits.len() - index;
+
+        if index > 0 && digits_left % 3 == 0 && digits.len() >= min_digits_for_separators {
+            grouped.push('_');
+        }
+
+        grouped.push(*digit);
+    }
+
+    grouped.push_str(rest);
+
+    grouped
+}
+
+// The content of a string literal (without quotes) with the same escape sequences the formatter uses:
+// `\u(000A)` becomes `\n`, `\u(001B)` becomes `\u(1b)` and a raw tab becomes `\t`.
+pub fn fmt_str_escapes(str_content: &str) -> String {
+    let mut formatted = String::with_capacity(str_content.len());
+    let mut chars = str_content.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => match chars.peek() {
+                Some('u') => {
+                    let rest: String = chars.clone().collect();
+
+                    match parse_unicode_escape(&rest) {
+                        Some((escaped_char, escape_len)) => {
+                            push_escaped_char(&mut formatted, escaped_char);
+
+                            for _ in 0..escape_len {
+                                chars.next();
+                            }
+                        }
+                        None => formatted.push('\\'),
+                    }
+                }
+                Some(next_ch) => {
+                    // \\, \", \t, \( interpolation... are kept as is
+                    formatted.push('\\');
+                    formatted.push(*next_ch);
+                    chars.next();
+                }
+                None => formatted.push('\\'),
+            },
+            _ => push_escaped_char(&mut formatted, ch),
+        }
+    }
+
+    formatted
+}
+
+fn push_escaped_char(formatted: &mut String, ch: char) {
+    if let Some(escaped) = short_escape(ch) {
+        formatted.push_str(escaped)
+    } else if needs_unicode_escape(ch) {
+        formatted.push_str(&format!("\\u({:x})", ch as u32))
+    } else if ch == '"' {
+        formatted.push_str("\\\"")
+    } else {
+        formatted.push(ch)
+    }
+}
+
+// `u(00A0)...` -> ('\u{a0}', 7)
+fn parse_unicode_escape(after_backslash: &str) -> Option<(char, usize)> {
+    let hex_start = after_backslash.strip_prefix("u(")?;
+    let hex_len = hex_start.find(')')?;
+
+    let code_point = u32::from_str_radix(&hex_start[..hex_len], 16).ok()?;
+
+    char::from_u32(code_point).map(|ch| (ch, "u(".len() + hex_len + ")".len()))
+}
+
+#[cfg(test)]
+pub mod test_literal_fmt {
+    use crate::editor::mvc::ed_model::test_ed_model::{
+        ed_model_from_dsl, ed_model_to_dsl, init_model_refs,
+    };
+    use crate::editor::mvc::literal_fmt::{
+        fmt_literal_if_left, fmt_num_text, fmt_str_escapes, get_literal_at_caret,
+    };
+    use crate::editor::resources::strings::nr_hello_world_lines;
+    use crate::ui::text::lines::SelectableLines;
+    use bumpalo::Bump;
+    use roc_module::symbol::ModuleIds;
+
+    #[test]
+    fn num_separators() {
+        assert_eq!(fmt_num_text("1234", 5), "1234");
+        assert_eq!(fmt_num_text("12345", 5), "12_345");
+        assert_eq!(fmt_num_text("1_2345", 5), "12_345");
+        assert_eq!(fmt_num_text("1000000", 5), "1_000_000");
+        assert_eq!(fmt_num_text("-1234567.891", 5), "-1_234_567.891");
+        assert_eq!(fmt_num_text("100000u64", 5), "100_000u64");
+        assert_eq!(fmt_num_text("0x123456", 5), "0x123456");
+    }
+
+    #[test]
+    fn str_escapes() {
+        assert_eq!(fmt_str_escapes("hello"), "hello");
+        assert_eq!(fmt_str_escapes("a\\u(000A)b"), "a\\nb");
+        assert_eq!(fmt_str_escapes("\\u(001B)"), "\\u(1b)");
+        assert_eq!(fmt_str_escapes("\\u(0041)"), "A");
+        assert_eq!(fmt_str_escapes("tab\there"), "tab\\there");
+        assert_eq!(fmt_str_escapes("\\\\ \\\" \\(name)"), "\\\\ \\\" \\(name)");
+        assert_eq!(fmt_str_escapes("\\u(zz)"), "\\u(zz)");
+    }
+
+    // Put the caret at `┃`, move it with `move_caret` and compare with the expected code.
+    fn assert_fmt_on_leave(
+        pre_line: &str,
+        expected_post_line: &str,
+        move_caret: fn(&mut crate::editor::mvc::ed_model::EdModel),
+    ) -> Result<(), String> {
+        let mut code_str = pre_line.replace('┃', "");
+
+        let mut model_refs = init_model_refs();
+        let code_arena = Bump::new();
+        let module_ids = ModuleIds::default();
+
+        let mut ed_model = ed_model_from_dsl(
+            &mut code_str,
+            vec![pre_line.to_owned()],
+            &mut model_refs,
+            &module_ids,
+            &code_arena,
+        )?;
+
+        let prev_literal_opt = get_literal_at_caret(&ed_model)?;
+        move_caret(&mut ed_model);
+        fmt_literal_if_left(&mut ed_model, prev_literal_opt)?;
+
+        let mut post_lines = ed_model_to_dsl(&ed_model)?;
+        post_lines.drain(0..nr_hello_world_lines());
+
+        assert_eq!(post_lines[0], expected_post_line);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fmt_num_on_leave() -> Result<(), String> {
+        // caret stays in the literal, nothing changes
+        assert_fmt_on_leave("val = 12345┃", "val = 1234┃5", |ed_model| {
+            ed_model.simple_move_carets_left(1)
+        })?;
+        // caret leaves the literal
+        assert_fmt_on_leave("val = ┃12345", "val =┃ 12_345", |ed_model| {
+            ed_model.simple_move_carets_left(1)
+        })?;
+        assert_fmt_on_leave(
+            "val = [ 12345┃, 1 ]",
+            "val = [ 12_345, ┃1 ]",
+            |ed_model| ed_model.simple_move_carets_right(2),
+        )?;
+
+        Ok(())
+    }
+}
@@ -7,7 +7,9 @@ pub mod ed_view;
 mod int_update;
 mod let_update;
 mod list_update;
+pub mod literal_fmt;
 mod lookup_update;
 mod record_update;
 mod string_update;
 pub mod tld_value_update;
+mod when_update;
@@ -0,0 +1,178 @@
+use roc_ast::lang::core::ast::ASTNodeId;
+use roc_ast::lang::core::expr::expr2::{Expr2, WhenBranch};
+use roc_ast::lang::core::pattern::Pattern2;
+use roc_ast::mem_pool::pool_str::PoolStr;
+use roc_ast::mem_pool::pool_vec::PoolVec;
+use roc_module::symbol::Symbol;
+use roc_types::subs::{Content, FlatType, Subs, Variable};
+use roc_types::types::AliasKind;
+
+use crate::editor::ed_error::EdResult;
+use crate::editor::mvc::ed_model::EdModel;
+use crate::editor::mvc::ed_update::get_node_context;
+use crate::editor::semantic_highlight::loaded_symbol;
+
+// Replaces the value at the caret with a `when` that has a branch for every tag of its type, e.g. if
+// `color` is a `[Red, Green]`, then `color` becomes
+//
+// when color is
+//     Green -> ␣
+//     Red -> ␣
+//
+// The patterns have an underscore for every argument of the tag and the bodies are blanks to fill in.
+// The type comes from the last time the file was loaded, like the types of the quick fixes.
+pub fn insert_when_at_caret(ed_model: &mut EdModel) -> EdResult<()> {
+    let var_opt = match get_node_context(ed_model)?.ast_node_id {
+        ASTNodeId::AExprId(expr_id) => match ed_model.module.env.pool.get(expr_id) {
+            Expr2::Var(symbol) => Some((expr_id, *symbol)),
+            _ => None,
+        },
+        ASTNodeId::ADefId(_) => None,
+    };
+
+    let (expr_id, symbol) = match var_opt {
+        Some(var) => var,
+        None => {
+            println!("\nPut the caret on a value to match on its tags with a `when`.");
+            return Ok(());
+        }
+    };
+
+    let tags = match value_tags(ed_model, symbol) {
+        Some(tags) => tags,
+        None => {
+            println!("\nOnly values with a closed tag union type, like [Red, Green], get a `when` with a branch for every tag.");
+            return Ok(());
+        }
+    };
+
+    let env = &mut ed_model.module.env;
+
+    let mut branches = Vec::with_capacity(tags.len());
+
+    for (tag_name, nr_of_args) in tags {
+        let arguments: Vec<_> = (0..nr_of_args)
+            .map(|_| (env.var_store.fresh(), env.pool.add(Pattern2::Underscore)))
+            .collect();
+
+        let pattern = Pattern2::Tag {
+            whole_var: env.var_store.fresh(),
+            ext_var: env.var_store.fresh(),
+            tag_name: PoolStr::new(&tag_name, env.pool),
+            arguments: PoolVec::new(arguments.into_iter(), env.pool),
+        };
+
+        branches.push(WhenBranch {
+            patterns: PoolVec::new(std::iter::once(pattern), env.pool),
+            body: env.pool.add(Expr2::Blank),
+            guard: None,
+        });
+    }
+
+    let when_expr = Expr2::When {
+        cond_var: env.var_store.fresh(),
+        expr_var: env.var_store.fresh(),
+        branches: PoolVec::new(branches.into_iter(), env.pool),
+        cond: env.pool.add(Expr2::Var(symbol)),
+    };
+
+    env.pool.set(expr_id, when_expr);
+
+    let caret_pos = ed_model.get_caret();
+
+    ed_model.post_process_ast_update()?;
+
+    // the `when ` keyword is now in front of the value
+    ed_model.simple_move_caret_right(caret_pos, "when ".len());
+
+    ed_model.dirty = true;
+
+    Ok(())
+}
+
+// the names of the tags of the value and how many arguments they have, sorted by name
+fn value_tags(ed_model: &EdModel, symbol: Symbol) -> Option<Vec<(String, usize)>> {
+    let loaded_module = &ed_model.loaded_module;
+    let symbol = loaded_symbol(ed_model, symbol)?;
+    let decls = loaded_module.declarations_by_id.get(&symbol.module_id())?;
+
+    let index = decls
+        .symbols
+        .iter()
+        .position(|loc_symbol| loc_symbol.value == symbol)?;
+
+    tag_union_tags(loaded_module.solved.inner(), decls.variables[index])
+}
+
+fn tag_union_tags(subs: &Subs, var: Variable) -> Option<Vec<(String, usize)>> {
+    match subs.get_content_without_compacting(var) {
+        Content::Alias(_, _, real_var, AliasKind::Structural) => tag_union_tags(subs, *real_var),
+        Content::Structure(FlatType::TagUnion(union_tags, ext))
+        | Content::Structure(FlatType::RecursiveTagUnion(_, union_tags, ext)) => {
+            let (labels, ext) = union_tags.unsorted_tags_and_ext(subs, *ext);
+
+            // a flex extension only means the value can be used where more tags are possible,
+            // a rigid one stands for tags that are not known here
+            match subs.get_content_without_compacting(ext.var()) {
+                Content::Structure(FlatType::EmptyTagUnion) | Content::FlexVar(_) => {}
+                _ => return None,
+            }
+
+            let mut tags: Vec<(String, usize)> = labels
+                .tags
+                .iter()
+                .map(|(tag_name, args)| (tag_name.0.as_str().to_owned(), args.len()))
+                .collect();
+
+            tags.sort();
+
+            Some(tags)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+pub mod test_when_update {
+    use crate::editor::mvc::ed_model::test_ed_model::{ed_model_from_dsl, init_model_refs};
+    use crate::editor::mvc::when_update::insert_when_at_caret;
+    use crate::editor::resources::strings::nr_hello_world_lines;
+    use crate::ui::text::lines::Lines;
+    use bumpalo::Bump;
+    use roc_module::symbol::ModuleIds;
+
+    #[test]
+    fn when_with_branch_per_tag() -> Result<(), String> {
+        let code_lines = vec![
+            "color = Green".to_owned(),
+            "".to_owned(),
+            "val = co┃lor".to_owned(),
+        ];
+        let mut code_str = code_lines.join("\n").replace('┃', "");
+
+        let mut model_refs = init_model_refs();
+        let code_arena = Bump::new();
+        let module_ids = ModuleIds::default();
+
+        let mut ed_model = ed_model_from_dsl(
+            &mut code_str,
+            code_lines,
+            &mut model_refs,
+            &module_ids,
+            &code_arena,
+        )?;
+
+        let val_line_nr = nr_hello_world_lines() + 2;
+
+        insert_when_at_caret(&mut ed_model).map_err(|e| format!("{:?}", e))?;
+
+        let when_lines: Vec<&str> = (val_line_nr..val_line_nr + 2)
+            .map(|line_nr| ed_model.code_lines.get_line_ref(line_nr))
+            .collect::<Result<_, _>>()
+            .map_err(|e| format!("{:?}", e))?;
+
+        assert_eq!(when_lines, vec!["val = when color is", "    Green ->  "]);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,239 @@
+use crate::editor::ed_error::EdResult;
+use crate::editor::evaluate::evaluate_selection;
+use crate::editor::mvc::ed_model::EdModel;
+use crate::editor::test_lens::{
+    expect_status, run_expect_at_caret, run_module_expects, ExpectStatus,
+};
+use crate::graphics::colors::RgbaTup;
+use crate::ui::text::lines::Lines;
+use roc_code_markup::markup::nodes::MarkupNode;
+use roc_code_markup::slow_pool::MarkNodeId;
+use roc_collections::MutMap;
+use std::fmt;
+
+// Extension points for editor experiments that should not live in ed_model or ed_update.
+// A plugin can decorate markup nodes and add commands to the command palette (`Ctrl+Shift+P`).
+// Plugins are compiled in for now, every plugin in PLUGINS is active.
+pub const PLUGINS: &[&dyn EdPlugin] = &[&HexColorSwatch, &EvaluateSelection, &TestLens];
+
+pub trait EdPlugin {
+    fn name(&self) -> &'static str;
+
+    // Called for every markup node after the AST changed, the first decoration of any plugin is shown.
+    fn decoration(&self, _ed_model: &EdModel, _mark_node_id: MarkNodeId) -> Option<Decoration> {
+        None
+    }
+
+    fn commands(&self) -> Vec<PluginCommand> {
+        Vec::new()
+    }
+}
+
+// drawn together with the text of a markup node, decorations never move the code around
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Decoration {
+    Underline(RgbaTup),
+    Gutter(RgbaTup), // a square in the margin left of the line the node starts on
+}
+
+#[derive(Clone, Copy)]
+pub struct PluginCommand {
+    pub name: &'static str,
+    pub run: fn(&mut EdModel) -> EdResult<()>,
+}
+
+impl fmt::Debug for PluginCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PluginCommand")
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+pub fn plugin_decorations(ed_model: &EdModel) -> MutMap<MarkNodeId, Decoration> {
+    let mut decorations = MutMap::default();
+    let mut stack: Vec<MarkNodeId> = ed_model.markup_ids.clone();
+
+    while let Some(mark_node_id) = stack.pop() {
+        if let MarkupNode::Nested { children_ids, .. } = ed_model.mark_node_pool.get(mark_node_id) {
+            stack.extend(children_ids);
+        }
+
+        if let Some(decoration) = PLUGINS
+            .iter()
+            .find_map(|plugin| plugin.decoration(ed_model, mark_node_id))
+        {
+            decorations.insert(mark_node_id, decoration);
+        }
+    }
+
+    decorations
+}
+
+// the commands of all plugins, as shown in the command palette
+#[derive(Debug)]
+pub struct CommandPalette {
+    pub entries: Vec<(&'static str, PluginCommand)>, // (plugin name, command)
+    pub selected_index: usize,
+}
+
+impl CommandPalette {
+    pub fn open() -> Option<CommandPalette> {
+        let entries: Vec<(&'static str, PluginCommand)> = PLUGINS
+            .iter()
+            .flat_map(|plugin| {
+                plugin
+                    .commands()
+                    .into_iter()
+                    .map(move |command| (plugin.name(), command))
+            })
+            .collect();
+
+        if entries.is_empty() {
+            println!("\nThe plugins in the editor have no commands.");
+
+            None
+        } else {
+            Some(CommandPalette {
+                entries,
+                selected_index: 0,
+            })
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        self.selected_index = (self.selected_index + 1) % self.entries.len();
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected_index = (self.selected_index + self.entries.len() - 1) % self.entries.len();
+    }
+
+    pub fn selected_command(&self) -> PluginCommand {
+        self.entries[self.selected_index].1
+    }
+}
+
+// Underlines hex literals like 0xFF8800 with the color they describe.
+pub struct HexColorSwatch;
+
+impl EdPlugin for HexColorSwatch {
+    fn name(&self) -> &'static str {
+        "Hex colors"
+    }
+
+    fn decoration(&self, ed_model: &EdModel, mark_node_id: MarkNodeId) -> Option<Decoration> {
+        match ed_model.mark_node_pool.get(mark_node_id) {
+            MarkupNode::Text { content, .. } => {
+                hex_color(content.trim()).map(Decoration::Underline)
+            }
+            _ => None,
+        }
+    }
+
+    fn commands(&self) -> Vec<PluginCommand> {
+        vec![PluginCommand {
+            name: "List the colors in this file",
+            run: list_hex_colors,
+        }]
+    }
+}
+
+// Evaluates the selected expression, see evaluate.rs.
+pub struct EvaluateSelection;
+
+impl EdPlugin for EvaluateSelection {
+    fn name(&self) -> &'static str {
+        "Evaluate"
+    }
+
+    fn commands(&self) -> Vec<PluginCommand> {
+        vec![PluginCommand {
+            name: "Evaluate the selection",
+            run: evaluate_selection,
+        }]
+    }
+}
+
+// Runs the expects of the module, each expect gets a green or red square in the margin, see test_lens.rs.
+pub struct TestLens;
+
+const PASSED_COLOR: RgbaTup = (0.1, 0.7, 0.2, 1.0);
+const FAILED_COLOR: RgbaTup = (0.9, 0.15, 0.1, 1.0);
+
+impl EdPlugin for TestLens {
+    fn name(&self) -> &'static str {
+        "Test lens"
+    }
+
+    fn decoration(&self, ed_model: &EdModel, mark_node_id: MarkNodeId) -> Option<Decoration> {
+        match expect_status(ed_model, mark_node_id)? {
+            ExpectStatus::Passed => Some(Decoration::Gutter(PASSED_COLOR)),
+            ExpectStatus::Failed(_) => Some(Decoration::Gutter(FAILED_COLOR)),
+        }
+    }
+
+    fn commands(&self) -> Vec<PluginCommand> {
+        vec![
+            PluginCommand {
+                name: "Run the expects in this module",
+                run: run_module_expects,
+            },
+            PluginCommand {
+                name: "Run the expect at the caret",
+                run: run_expect_at_caret,
+            },
+        ]
+    }
+}
+
+// 0xRRGGBB or 0xRRGGBBAA
+pub fn hex_color(content: &str) -> Option<RgbaTup> {
+    let digits = content.strip_prefix("0x")?;
+
+    if !(digits.len() == 6 || digits.len() == 8) || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let channel = |index: usize| -> f32 {
+        match digits.get((2 * index)..(2 * index + 2)) {
+            Some(hex) => u8::from_str_radix(hex, 16).unwrap_or(0) as f32 / 255.0,
+            None => 1.0,
+        }
+    };
+
+    Some((channel(0), channel(1), channel(2), channel(3)))
+}
+
+fn list_hex_colors(ed_model: &mut EdModel) -> EdResult<()> {
+    println!("\nColors in {}:", ed_model.file_path.display());
+
+    for (line_nr, line) in ed_model
+        .code_lines
+        .all_lines_as_string()
+        .lines()
+        .enumerate()
+    {
+        for word in line.split(|c: char| !c.is_ascii_alphanumeric()) {
+            if hex_color(word).is_some() {
+                println!("{:>5} | {}", line_nr + 1, word);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+pub mod test_plugin {
+    use crate::editor::plugin::hex_color;
+
+    #[test]
+    fn parse_hex_color() {
+        assert_eq!(hex_color("0xFF0000"), Some((1.0, 0.0, 0.0, 1.0)));
+        assert_eq!(hex_color("0x00ff0000"), Some((0.0, 1.0, 0.0, 0.0)));
+        assert_eq!(hex_color("0xFF00"), None);
+        assert_eq!(hex_color("0xGG0000"), None);
+        assert_eq!(hex_color("255"), None);
+    }
+}
@@ -0,0 +1,268 @@
+use crate::ui::text::text_pos::TextPos;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+
+// number of lines shown above and below every match
+pub const NR_CONTEXT_LINES: usize = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchMatch {
+    pub file_path: PathBuf,
+    pub txt_pos: TextPos, // position of the first char of the match
+    pub first_context_line_nr: usize,
+    pub context_lines: Vec<String>, // lines around the match, the matching line included
+}
+
+/// A search for a piece of text in all .roc files of the project.
+/// The files are scanned on a separate thread, matches are collected with `poll`.
+#[derive(Debug)]
+pub struct ProjectSearch {
+    pub query: String,
+    pub matches: Vec<SearchMatch>,
+    pub selected_match_index: usize,
+    pub done: bool, // true once every file has been scanned
+    receiver: Receiver<SearchMatch>,
+}
+
+impl ProjectSearch {
+    pub fn start(project_dir: &Path, query: String) -> ProjectSearch {
+        let (sender, receiver) = mpsc::channel();
+
+        let project_dir = project_dir.to_path_buf();
+        let thread_query = query.clone();
+
+        thread::spawn(move || {
+            for file_path in roc_files_in_dir(&project_dir).unwrap_or_default() {
+                // a file that can't be read should not stop the search of the other files
+                if let Ok(content) = fs::read_to_string(&file_path) {
+                    for search_match in
+                        search_in_str(&file_path, &content, &thread_query, NR_CONTEXT_LINES)
+                    {
+                        if sender.send(search_match).is_err() {
+                            // the search was closed, nobody is listening anymore
+                            return;
+                        }
+                    }
+                }
+            }
+        });
+
+        ProjectSearch {
+            query,
+            matches: Vec::new(),
+            selected_match_index: 0,
+            done: false,
+            receiver,
+        }
+    }
+
+    // collect the matches found since the last poll, returns true if something changed
+    pub fn poll(&mut self) -> bool {
+        let mut changed = false;
+
+        loop {
+            match self.receiver.try_recv() {
+                Ok(search_match) => {
+                    self.matches.push(search_match);
+                    changed = true;
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    changed |= !self.done;
+                    self.done = true;
+                    break;
+                }
+            }
+        }
+
+        changed
+    }
+
+    pub fn selected_match(&self) -> Option<&SearchMatch> {
+        self.matches.get(self.selected_match_index)
+    }
+
+    pub fn select_next(&mut self) {
+        if !self.matches.is_empty() {
+            self.selected_match_index = (self.selected_match_index + 1) % self.matches.len();
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        if !self.matches.is_empty() {
+            self.selected_match_index =
+                (self.selected_match_index + self.matches.len() - 1) % self.matches.len();
+        }
+    }
+}
+
+// all .roc files in dir and its subdirectories, sorted to get a stable order of matches
+pub fn roc_files_in_dir(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut roc_files = Vec::new();
+    let mut dirs_to_visit = vec![dir.to_path_buf()];
+
+    while let Some(curr_dir) = dirs_to_visit.pop() {
+        for entry in fs::read_dir(&curr_dir)? {
+            let path = entry?.path();
+
+            let is_hidden = path
+                .file_name()
+                .map(|name| name.to_string_lossy().starts_with('.'))
+                .unwrap_or(false);
+
+            if is_hidden {
+                continue;
+            }
+
+            if path.is_dir() {
+                dirs_to_visit.push(path);
+            } else if path.extension().map(|ext| ext == "roc").unwrap_or(false) {
+                roc_files.push(path);
+            }
+        }
+    }
+
+    roc_files.sort();
+
+    Ok(roc_files)
+}
+
+pub fn search_in_str(
+    file_path: &Path,
+    content: &str,
+    query: &str,
+    nr_context_lines: usize,
+) -> Vec<SearchMatch> {
+    let mut matches = Vec::new();
+
+    if query.is_empty() {
+        return matches;
+    }
+
+    let lines: Vec<&str> = content.lines().collect();
+
+    for (line_nr, line) in lines.iter().enumerate() {
+        for (byte_index, _) in line.match_indices(query) {
+            // the editor counts columns in chars, not in bytes
+            let column = line[..byte_index].chars().count();
+
+            let first_context_line_nr = line_nr.saturating_sub(nr_context_lines);
+            let last_context_line_nr = (line_nr + nr_context_lines).min(lines.len() - 1);
+
+            matches.push(SearchMatch {
+                file_path: file_path.to_path_buf(),
+                txt_pos: TextPos {
+                    line: line_nr,
+                    column,
+                },
+                first_context_line_nr,
+                context_lines: lines[first_context_line_nr..=last_context_line_nr]
+                    .iter()
+                    .map(|context_line| context_line.to_string())
+                    .collect(),
+            });
+        }
+    }
+
+    matches
+}
+
+#[cfg(test)]
+pub mod test_project_search {
+    use crate::editor::project_search::{roc_files_in_dir, search_in_str, ProjectSearch};
+    use crate::ui::text::text_pos::TextPos;
+    use std::fs;
+    use std::path::Path;
+    use tempfile::tempdir;
+
+    #[test]
+    fn search_in_str_with_context() {
+        let content = "app \"test\"\n\nmain = greeting\n\ngreeting = \"hello\"\n";
+
+        let matches = search_in_str(Path::new("main.roc"), content, "greeting", 1);
+
+        assert_eq!(matches.len(), 2);
+
+        assert_eq!(matches[0].txt_pos, TextPos { line: 2, column: 7 });
+        assert_eq!(matches[0].first_context_line_nr, 1);
+        assert_eq!(matches[0].context_lines, vec!["", "main = greeting", ""]);
+
+        assert_eq!(matches[1].txt_pos, TextPos { line: 4, column: 0 });
+        assert_eq!(matches[1].context_lines, vec!["", "greeting = \"hello\""]);
+    }
+
+    #[test]
+    fn search_in_str_multiple_matches_on_line() {
+        let matches = search_in_str(Path::new("main.roc"), "x = x + x", "x", 0);
+
+        let columns: Vec<usize> = matches.iter().map(|m| m.txt_pos.column).collect();
+
+        assert_eq!(columns, vec![0, 4, 8]);
+    }
+
+    #[test]
+    fn search_in_str_columns_in_chars() {
+        let matches = search_in_str(
+            Path::new("main.roc"),
+            "greeting = \"héllo wörld\"",
+            "wörld",
+            0,
+        );
+
+        assert_eq!(
+            matches[0].txt_pos,
+            TextPos {
+                line: 0,
+                column: 18
+            }
+        );
+    }
+
+    #[test]
+    fn search_in_str_empty_query() {
+        assert!(search_in_str(Path::new("main.roc"), "main = 1", "", 1).is_empty());
+    }
+
+    #[test]
+    fn project_search_all_roc_files() {
+        let temp_dir = tempdir().expect("Failed to create temporary directory for test.");
+        let sub_dir = temp_dir.path().join("Parser");
+
+        fs::create_dir(&sub_dir).unwrap();
+        fs::write(temp_dir.path().join("main.roc"), "main = Parser.run 5").unwrap();
+        fs::write(sub_dir.join("Parser.roc"), "run = \\x -> x").unwrap();
+        fs::write(temp_dir.path().join("notes.txt"), "run run run").unwrap();
+
+        let roc_files = roc_files_in_dir(temp_dir.path()).unwrap();
+        assert_eq!(roc_files.len(), 2);
+
+        let mut project_search = ProjectSearch::start(temp_dir.path(), "run".to_owned());
+
+        while !project_search.done {
+            project_search.poll();
+        }
+
+        let mut found_in: Vec<String> = project_search
+            .matches
+            .iter()
+            .map(|m| {
+                m.file_path
+                    .file_name()
+                    .unwrap()
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .collect();
+        found_in.sort();
+
+        assert_eq!(found_in, vec!["Parser.roc", "main.roc"]);
+
+        project_search.select_prev();
+        assert_eq!(project_search.selected_match_index, 1);
+        project_search.select_next();
+        assert_eq!(project_search.selected_match_index, 0);
+    }
+}
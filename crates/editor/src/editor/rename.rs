@@ -0,0 +1,485 @@
+use roc_can::expr::{DeclarationTag, Declarations, Expr};
+use roc_can::traverse::{walk_decls, walk_expr, Visitor};
+use roc_load::LoadedModule;
+use roc_module::symbol::{ModuleId, Symbol};
+use roc_region::all::Region;
+use roc_types::subs::Variable;
+use std::fs;
+use std::io;
+use std::ops::Range;
+use std::path::PathBuf;
+
+// Renaming a top level value in every module of the project that uses it.
+// The edits are computed from the regions of the canonicalized modules, so nothing is guessed
+// from the text except the header lists and type annotations, which have no canonical region.
+#[derive(Debug)]
+pub struct RenamePlan {
+    pub symbol: Symbol,
+    pub old_name: String,
+    pub new_name: String,
+    pub file_edits: Vec<FileEdit>,
+}
+
+#[derive(Debug)]
+pub struct FileEdit {
+    pub module_id: ModuleId,
+    pub file_path: PathBuf,
+    pub old_src: String,
+    pub new_src: String,
+}
+
+pub fn plan_rename(
+    loaded_module: &LoadedModule,
+    symbol: Symbol,
+    new_name: &str,
+) -> Result<RenamePlan, String> {
+    let interns = &loaded_module.interns;
+    let old_name = symbol.as_str(interns).to_owned();
+    let def_module_id = symbol.module_id();
+
+    if symbol.is_builtin() {
+        return Err(format!(
+            "`{}` is a builtin, it can not be renamed.",
+            old_name
+        ));
+    }
+
+    if loaded_module.exposed_to_host.contains_key(&symbol) {
+        return Err(format!(
+            "`{}` is provided to the platform, rename it in the platform too.",
+            old_name
+        ));
+    }
+
+    if !is_lowercase_ident(new_name) {
+        return Err(format!("`{}` is not a valid name for a value.", new_name));
+    }
+
+    let def_module_name = interns.module_name(def_module_id).as_str().to_owned();
+
+    let mut module_ids: Vec<ModuleId> = loaded_module
+        .sources
+        .keys()
+        .copied()
+        .filter(|module_id| !module_id.is_builtin())
+        .collect();
+    module_ids.sort_by_key(|module_id| interns.module_name(*module_id).as_str().to_owned());
+
+    let mut file_edits = Vec::new();
+
+    for module_id in module_ids {
+        let (file_path, src) = &loaded_module.sources[&module_id];
+        let mut new_src: String = src.to_string();
+
+        // all regions point into the original source, so edit from the back
+        let mut regions = match loaded_module.declarations_by_id.get(&module_id) {
+            Some(decls) => references(decls, symbol),
+            None => Vec::new(),
+        };
+
+        if module_id == def_module_id {
+            if let Some(decls) = loaded_module.declarations_by_id.get(&module_id) {
+                regions.extend(def_regions(decls, symbol));
+            }
+        }
+
+        regions.sort_by_key(|region| std::cmp::Reverse(region.start().offset));
+        regions.dedup();
+
+        for region in regions {
+            let start = region.start().offset as usize;
+            let end = region.end().offset as usize;
+
+            // qualified references (`Dep.foo`) end in the name
+            match src.get(start..end) {
+                Some(ref_src) if ref_src.ends_with(old_name.as_str()) => {
+                    new_src.replace_range(end - old_name.len()..end, new_name);
+                }
+                _ => {
+                    return Err(format!(
+                        "The source of {:?} changed since it was loaded, reload before renaming.",
+                        file_path
+                    ))
+                }
+            }
+        }
+
+        let header_changed = if module_id == def_module_id {
+            new_src = rename_annotations(&new_src, &old_name, new_name);
+
+            let exposes_opt = header_list_range(&new_src, "exposes");
+
+            rename_in_range(&mut new_src, exposes_opt, &old_name, new_name)
+        } else {
+            let exposing_opt = import_exposing_range(&new_src, &def_module_name);
+
+            rename_in_range(&mut new_src, exposing_opt, &old_name, new_name)
+        };
+
+        if new_src == src.as_ref() {
+            continue;
+        }
+
+        // an unqualified import or the def itself would clash with an existing name
+        let renames_unqualified = module_id == def_module_id || header_changed;
+        let ident_ids_opt = interns.all_ident_ids.get(&module_id);
+
+        if renames_unqualified
+            && ident_ids_opt.map_or(false, |ident_ids| ident_ids.get_id(new_name).is_some())
+        {
+            return Err(format!(
+                "`{}` is already used in {}.",
+                new_name,
+                interns.module_name(module_id).as_str()
+            ));
+        }
+
+        file_edits.push(FileEdit {
+            module_id,
+            file_path: file_path.clone(),
+            old_src: src.to_string(),
+            new_src,
+        });
+    }
+
+    Ok(RenamePlan {
+        symbol,
+        old_name,
+        new_name: new_name.to_owned(),
+        file_edits,
+    })
+}
+
+impl RenamePlan {
+    // Changed lines of every file, a rename never adds or removes lines.
+    pub fn preview(&self) -> String {
+        let mut preview = format!("Rename `{}` to `{}`\n", self.old_name, self.new_name);
+
+        for file_edit in &self.file_edits {
+            preview.push_str(&format!("\n{}\n", file_edit.file_path.display()));
+
+            let line_pairs = file_edit.old_src.lines().zip(file_edit.new_src.lines());
+
+            for (line_nr, (old_line, new_line)) in line_pairs.enumerate() {
+                if old_line != new_line {
+                    preview.push_str(&format!("{:>4} - {}\n", line_nr + 1, old_line));
+                    preview.push_str(&format!("{:>4} + {}\n", line_nr + 1, new_line));
+                }
+            }
+        }
+
+        preview
+    }
+
+    // Writes the files of all modules except `open_module_id`, which the editor updates
+    // through its AST. Files that changed on disk since they were loaded are not touched.
+    pub fn write_files(&self, open_module_id: ModuleId) -> io::Result<()> {
+        for file_edit in &self.file_edits {
+            if file_edit.module_id != open_module_id
+                && fs::read_to_string(&file_edit.file_path)? != file_edit.old_src
+            {
+                return Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    format!(
+                        "{:?} changed since it was loaded, nothing was renamed.",
+                        file_edit.file_path
+                    ),
+                ));
+            }
+        }
+
+        for file_edit in &self.file_edits {
+            if file_edit.module_id != open_module_id {
+                fs::write(&file_edit.file_path, &file_edit.new_src)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn is_lowercase_ident(name: &str) -> bool {
+    name.starts_with(|ch: char| ch.is_ascii_lowercase())
+        && name.chars().all(|ch| ch.is_ascii_alphanumeric())
+}
+
+fn is_ident_char(ch: char) -> bool {
+    ch.is_ascii_alphanumeric() || ch == '_'
+}
+
+// replaces whole identifiers only, so renaming `foo` leaves `fooBar` alone
+fn rename_word(text: &str, old_name: &str, new_name: &str) -> String {
+    let mut renamed = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(index) = rest.find(old_name) {
+        renamed.push_str(&rest[..index]);
+        rest = &rest[index + old_name.len()..];
+
+        if renamed.ends_with(is_ident_char) || rest.starts_with(is_ident_char) {
+            renamed.push_str(old_name);
+        } else {
+            renamed.push_str(new_name);
+        }
+    }
+
+    renamed.push_str(rest);
+
+    renamed
+}
+
+// The header ends where the next unindented line (the first def) starts.
+fn header_end(src: &str) -> usize {
+    let mut offset = 0;
+    let mut in_header = false;
+
+    for line in src.split_inclusive('\n') {
+        let is_unindented = !line.trim().is_empty()
+            && !line.starts_with(char::is_whitespace)
+            && !line.starts_with('#');
+
+        if is_unindented {
+            if in_header {
+                return offset;
+            }
+
+            in_header = true;
+        }
+
+        offset += line.len();
+    }
+
+    src.len()
+}
+
+// the part of the header between the brackets after `keyword`, e.g. `exposes [foo, bar]`
+fn header_list_range(src: &str, keyword: &str) -> Option<Range<usize>> {
+    let header = &src[..header_end(src)];
+    let keyword_start = header.find(keyword)?;
+    let open = keyword_start + header[keyword_start..].find('[')?;
+    let close = open + header[open..].find(']')?;
+
+    Some(open + 1..close)
+}
+
+// e.g. `Dep.{ foo }` or `pf.Dep.{ foo }` in `imports [...]`
+fn import_exposing_range(src: &str, module_name: &str) -> Option<Range<usize>> {
+    let imports = header_list_range(src, "imports")?;
+    let qualified = format!("{}.{{", module_name);
+
+    let mut search_start = imports.start;
+
+    while let Some(index) = src[search_start..imports.end].find(&qualified) {
+        let entry_start = search_start + index;
+        let open = entry_start + qualified.len();
+
+        if !src[..entry_start].ends_with(char::is_alphanumeric) {
+            let close = open + src[open..imports.end].find('}')?;
+
+            return Some(open..close);
+        }
+
+        search_start = open;
+    }
+
+    None
+}
+
+// returns true if something in `range` was renamed
+fn rename_in_range(
+    src: &mut String,
+    range_opt: Option<Range<usize>>,
+    old_name: &str,
+    new_name: &str,
+) -> bool {
+    match range_opt {
+        Some(range) => {
+            let renamed = rename_word(&src[range.clone()], old_name, new_name);
+            let changed = renamed != src[range.clone()];
+
+            src.replace_range(range, &renamed);
+
+            changed
+        }
+        None => false,
+    }
+}
+
+// top level annotations like `foo : Str`, their name has no region after canonicalization
+fn rename_annotations(src: &str, old_name: &str, new_name: &str) -> String {
+    src.split_inclusive('\n')
+        .map(|line| match line.strip_prefix(old_name) {
+            Some(rest) => {
+                let after_name = rest.trim_start();
+
+                if after_name.starts_with(':') && !after_name.starts_with(":=") {
+                    format!("{}{}", new_name, rest)
+                } else {
+                    line.to_owned()
+                }
+            }
+            None => line.to_owned(),
+        })
+        .collect()
+}
+
+fn def_regions(decls: &Declarations, symbol: Symbol) -> Vec<Region> {
+    decls
+        .declarations
+        .iter()
+        .enumerate()
+        .filter(|(index, tag)| {
+            matches!(
+                tag,
+                DeclarationTag::Value
+                    | DeclarationTag::Function(_)
+                    | DeclarationTag::Recursive(_)
+                    | DeclarationTag::TailRecursive(_)
+            ) && decls.symbols[*index].value == symbol
+        })
+        .map(|(index, _)| decls.symbols[index].region)
+        .collect()
+}
+
+fn references(decls: &Declarations, symbol: Symbol) -> Vec<Region> {
+    let mut collector = ReferenceCollector {
+        symbol,
+        regions: Vec::new(),
+    };
+
+    walk_decls(&mut collector, decls);
+
+    collector.regions
+}
+
+struct ReferenceCollector {
+    symbol: Symbol,
+    regions: Vec<Region>,
+}
+
+impl Visitor for ReferenceCollector {
+    fn visit_expr(&mut self, expr: &Expr, region: Region, var: Variable) {
+        match expr {
+            Expr::Var(symbol, _) | Expr::AbilityMember(symbol, _, _) if *symbol == self.symbol => {
+                self.regions.push(region);
+            }
+            _ => walk_expr(self, expr, var),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test_rename {
+    use crate::editor::mvc::ed_model::test_ed_model::{ed_model_from_dsl, init_model_refs};
+    use crate::editor::rename::plan_rename;
+    use crate::editor::resources::strings::nr_hello_world_lines;
+    use crate::ui::text::lines::Lines;
+    use bumpalo::Bump;
+    use roc_ast::module::load_module;
+    use roc_load::Threading;
+    use roc_module::symbol::{ModuleIds, Symbol};
+    use roc_packaging::cache::RocCacheDir;
+    use std::fs;
+    use tempfile::tempdir;
+
+    const DEP_STR: &str = r#"interface Dep
+    exposes [foo, fooBar]
+    imports []
+
+foo : Str
+foo = "foo"
+
+fooBar = Str.concat foo "bar"
+"#;
+
+    const MAIN_STR: &str = r#"interface Main
+    exposes [main]
+    imports [Dep.{ foo }]
+
+main = Str.concat foo Dep.foo
+"#;
+
+    #[test]
+    fn rename_exposed_value_in_all_modules() -> Result<(), String> {
+        let temp_dir = tempdir().expect("Failed to create temporary directory for test.");
+
+        fs::write(temp_dir.path().join("Dep.roc"), DEP_STR).unwrap();
+        fs::write(temp_dir.path().join("Main.roc"), MAIN_STR).unwrap();
+
+        let main_path = temp_dir.path().join("Main.roc");
+        let loaded_module = load_module(&main_path, RocCacheDir::Disallowed, Threading::Single);
+
+        let interns = &loaded_module.interns;
+        let dep_id = interns.module_ids.get_id(&"Dep".into()).unwrap();
+        let foo_id = interns
+            .all_ident_ids
+            .get(&dep_id)
+            .unwrap()
+            .get_id("foo")
+            .unwrap();
+        let foo = Symbol::new(dep_id, foo_id);
+
+        let plan = plan_rename(&loaded_module, foo, "greeting")?;
+
+        let new_srcs: Vec<&str> = plan
+            .file_edits
+            .iter()
+            .map(|file_edit| file_edit.new_src.as_str())
+            .collect();
+
+        assert_eq!(
+            new_srcs,
+            vec![
+                DEP_STR
+                    .replace("[foo,", "[greeting,")
+                    .replace("foo :", "greeting :")
+                    .replace("foo =", "greeting =")
+                    .replace("concat foo", "concat greeting"),
+                MAIN_STR
+                    .replace("Dep.{ foo }", "Dep.{ greeting }")
+                    .replace("foo Dep.foo", "greeting Dep.greeting"),
+            ]
+        );
+
+        assert!(plan
+            .preview()
+            .contains("   5 - main = Str.concat foo Dep.foo\n   5 + main = Str.concat greeting Dep.greeting\n"));
+
+        assert!(plan_rename(&loaded_module, foo, "fooBar").is_err());
+        assert!(plan_rename(&loaded_module, foo, "Greeting").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn rename_def_at_caret() -> Result<(), String> {
+        let code_line = "val = 5┃";
+        let mut code_str = code_line.replace('┃', "");
+
+        let mut model_refs = init_model_refs();
+        let code_arena = Bump::new();
+        let module_ids = ModuleIds::default();
+
+        let mut ed_model = ed_model_from_dsl(
+            &mut code_str,
+            vec![code_line.to_owned()],
+            &mut model_refs,
+            &module_ids,
+            &code_arena,
+        )?;
+
+        ed_model.start_rename_at_caret("five")?;
+
+        // nothing changes before the rename is applied
+        let val_line = ed_model.code_lines.get_line_ref(nr_hello_world_lines())?;
+        assert_eq!(val_line, "val = 5");
+
+        ed_model.apply_pending_rename()?;
+
+        let val_line = ed_model.code_lines.get_line_ref(nr_hello_world_lines())?;
+        assert_eq!(val_line, "five = 5");
+        assert!(ed_model.pending_rename_opt.is_none());
+
+        Ok(())
+    }
+}
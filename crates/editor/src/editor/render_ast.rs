@@ -1,7 +1,12 @@
+use crate::editor::grid_node_map::GridNodeMap;
 use crate::editor::mvc::ed_view::RenderedWgpu;
+use crate::editor::plugin::Decoration;
+use crate::editor::semantic_highlight::SemanticStyle;
+use crate::editor::viewport::Viewport;
 use crate::editor::{ed_error::EdResult, theme::EdTheme, util::map_get};
 use crate::graphics::primitives::rect::Rect;
 use crate::graphics::primitives::text as gr_text;
+use crate::ui::text::text_pos::TextPos;
 use cgmath::Vector2;
 use roc_code_markup::{
     markup::{
@@ -12,10 +17,12 @@ use roc_code_markup::{
     syntax_highlight::HighlightStyle,
     underline_style::UnderlineStyle,
 };
+use roc_collections::MutMap;
 use winit::dpi::PhysicalSize;
 
 use crate::{editor::config::Config, graphics::colors};
 
+#[allow(clippy::too_many_arguments)]
 pub fn build_code_graphics<'a>(
     markup_ids: &[MarkNodeId],
     size: &PhysicalSize<u32>,
@@ -23,36 +30,37 @@ pub fn build_code_graphics<'a>(
     config: &Config,
     glyph_dim_rect: Rect,
     mark_node_pool: &'a SlowPool,
+    semantic_styles: &MutMap<MarkNodeId, SemanticStyle>,
+    plugin_decorations: &MutMap<MarkNodeId, Decoration>,
+    grid_node_map: &GridNodeMap,
+    viewport: &Viewport,
 ) -> EdResult<RenderedWgpu> {
     let area_bounds = (size.width as f32, size.height as f32);
     let layout = wgpu_glyph::Layout::default().h_align(wgpu_glyph::HorizontalAlign::Left);
     let mut rendered_wgpu = RenderedWgpu::new();
 
-    let mut all_glyph_text_vec = vec![];
+    let mut code_writer = CodeWriter::new(grid_node_map, viewport, config.code_font_size);
     let mut all_rects = vec![];
-    let mut txt_row_col = (0, 0);
 
     for markup_id in markup_ids.iter() {
-        let mark_node = mark_node_pool.get(*markup_id);
-
-        let (mut glyph_text_vec, mut rects) = markup_to_wgpu(
-            mark_node,
+        let mut rects = markup_to_wgpu(
+            *markup_id,
             &CodeStyle {
                 ed_theme: &config.ed_theme,
-                font_size: config.code_font_size,
                 txt_coords,
                 glyph_dim_rect,
+                semantic_styles,
+                plugin_decorations,
             },
-            &mut txt_row_col,
+            &mut code_writer,
             mark_node_pool,
         )?;
 
-        all_glyph_text_vec.append(&mut glyph_text_vec);
         all_rects.append(&mut rects)
     }
 
     let section = gr_text::owned_section_from_glyph_texts(
-        all_glyph_text_vec,
+        code_writer.wgpu_texts,
         txt_coords.into(),
         area_bounds,
         layout,
@@ -66,42 +74,160 @@ pub fn build_code_graphics<'a>(
 
 struct CodeStyle<'a> {
     ed_theme: &'a EdTheme,
-    font_size: f32,
     txt_coords: Vector2<f32>,
     glyph_dim_rect: Rect,
+    semantic_styles: &'a MutMap<MarkNodeId, SemanticStyle>,
+    plugin_decorations: &'a MutMap<MarkNodeId, Decoration>,
+}
+
+// Puts the text of the MarkupNodes on screen one after the other.
+// Lines that are too long are soft-wrapped or cut off at the edge of the window, see viewport.rs.
+struct CodeWriter<'a> {
+    grid_node_map: &'a GridNodeMap,
+    viewport: &'a Viewport,
+    font_size: f32,
+    wgpu_texts: Vec<glyph_brush::OwnedText>,
+    txt_pos: TextPos,      // position in the code of the next char
+    line_first_row: usize, // row on screen of the start of txt_pos.line
+    written_pos: TextPos, // row and visible column on screen right after the last char that was written
+}
+
+impl<'a> CodeWriter<'a> {
+    fn new(grid_node_map: &'a GridNodeMap, viewport: &'a Viewport, font_size: f32) -> Self {
+        Self {
+            grid_node_map,
+            viewport,
+            font_size,
+            wgpu_texts: Vec::new(),
+            txt_pos: TextPos { line: 0, column: 0 },
+            line_first_row: 0,
+            written_pos: TextPos { line: 0, column: 0 },
+        }
+    }
+
+    // row and visible column on screen of a column of the current line, None if it is scrolled out of view
+    fn screen_pos(&self, column: usize) -> Option<TextPos> {
+        let (row_in_line, row_col) = match self.grid_node_map.wrap_width_opt {
+            Some(wrap_width) => (column / wrap_width, column % wrap_width),
+            None => (0, column),
+        };
+
+        self.viewport
+            .visible_cols(row_col..(row_col + 1))
+            .map(|visible_cols| TextPos {
+                line: self.line_first_row + row_in_line,
+                column: visible_cols.start,
+            })
+    }
+
+    // the visible parts of the next nr_of_cols columns, as (row and visible column on screen, nr of columns)
+    fn visible_parts(&self, nr_of_cols: usize) -> Vec<(TextPos, usize)> {
+        let cols = self.txt_pos.column..(self.txt_pos.column + nr_of_cols);
+
+        self.grid_node_map
+            .screen_parts(self.txt_pos.line, cols)
+            .into_iter()
+            .filter_map(|(part_pos, part_len)| {
+                self.viewport
+                    .visible_cols(part_pos.column..(part_pos.column + part_len))
+                    .map(|visible_cols| {
+                        (
+                            TextPos {
+                                line: part_pos.line,
+                                column: visible_cols.start,
+                            },
+                            visible_cols.len(),
+                        )
+                    })
+            })
+            .collect()
+    }
+
+    fn write(&mut self, text: &str, color: [f32; 4]) {
+        let mut visible_text = String::new();
+
+        for (index, chr) in text.chars().enumerate() {
+            if let Some(screen_pos) = self.screen_pos(self.txt_pos.column + index) {
+                if screen_pos != self.written_pos {
+                    self.push_text(&mut visible_text, color);
+                    self.move_to(screen_pos);
+                }
+
+                visible_text.push(chr);
+                self.written_pos.column += 1;
+            }
+        }
+
+        self.push_text(&mut visible_text, color);
+        self.txt_pos.column += text.chars().count();
+    }
+
+    fn newline(&mut self) {
+        self.line_first_row += self.grid_node_map.nr_of_rows(self.txt_pos.line);
+        self.txt_pos = TextPos {
+            line: self.txt_pos.line + 1,
+            column: 0,
+        };
+    }
+
+    // moves to a later position on screen with newlines and spaces
+    fn move_to(&mut self, screen_pos: TextPos) {
+        let mut filler = String::new();
+
+        if screen_pos.line > self.written_pos.line {
+            filler.push_str(&"\n".repeat(screen_pos.line - self.written_pos.line));
+            self.written_pos.column = 0;
+        }
+
+        filler.push_str(&" ".repeat(screen_pos.column.saturating_sub(self.written_pos.column)));
+        self.written_pos = screen_pos;
+
+        if !filler.is_empty() {
+            self.wgpu_texts
+                .push(glyph_brush::OwnedText::new(filler).with_scale(self.font_size));
+        }
+    }
+
+    fn push_text(&mut self, text: &mut String, color: [f32; 4]) {
+        if !text.is_empty() {
+            self.wgpu_texts.push(
+                glyph_brush::OwnedText::new(std::mem::take(text))
+                    .with_color(color)
+                    .with_scale(self.font_size),
+            );
+        }
+    }
 }
 
 fn markup_to_wgpu<'a>(
-    markup_node: &'a MarkupNode,
+    markup_id: MarkNodeId,
     code_style: &CodeStyle,
-    txt_row_col: &mut (usize, usize),
+    code_writer: &mut CodeWriter,
     mark_node_pool: &'a SlowPool,
-) -> EdResult<(Vec<glyph_brush::OwnedText>, Vec<Rect>)> {
-    let mut wgpu_texts: Vec<glyph_brush::OwnedText> = Vec::new();
+) -> EdResult<Vec<Rect>> {
     let mut rects: Vec<Rect> = Vec::new();
 
     markup_to_wgpu_helper(
-        markup_node,
-        &mut wgpu_texts,
+        markup_id,
         &mut rects,
         code_style,
-        txt_row_col,
+        code_writer,
         mark_node_pool,
     )?;
 
-    Ok((wgpu_texts, rects))
+    Ok(rects)
 }
 
 fn markup_to_wgpu_helper<'a>(
-    markup_node: &'a MarkupNode,
-    wgpu_texts: &mut Vec<glyph_brush::OwnedText>,
+    markup_id: MarkNodeId,
     rects: &mut Vec<Rect>,
     code_style: &CodeStyle,
-    txt_row_col: &mut (usize, usize),
+    code_writer: &mut CodeWriter,
     mark_node_pool: &'a SlowPool,
 ) -> EdResult<()> {
     let char_width = code_style.glyph_dim_rect.width;
     let char_height = code_style.glyph_dim_rect.height;
+    let markup_node = mark_node_pool.get(markup_id);
 
     match markup_node {
         MarkupNode::Nested {
@@ -110,22 +236,11 @@ fn markup_to_wgpu_helper<'a>(
             newlines_at_end,
         } => {
             for child_id in children_ids.iter() {
-                let child = mark_node_pool.get(*child_id);
-                markup_to_wgpu_helper(
-                    child,
-                    wgpu_texts,
-                    rects,
-                    code_style,
-                    txt_row_col,
-                    mark_node_pool,
-                )?;
+                markup_to_wgpu_helper(*child_id, rects, code_style, code_writer, mark_node_pool)?;
             }
 
             for _ in 0..*newlines_at_end {
-                wgpu_texts.push(newline(code_style.font_size));
-
-                txt_row_col.0 += 1;
-                txt_row_col.1 = 0;
+                code_writer.newline();
             }
         }
         MarkupNode::Text {
@@ -135,103 +250,147 @@ fn markup_to_wgpu_helper<'a>(
             parent_id_opt: _,
             newlines_at_end,
         } => {
-            let highlight_color = map_get(&code_style.ed_theme.syntax_high_map, syn_high_style)?;
+            let content_str = content.replace('\n', "\\n"); // any \n left here should be escaped so that it can be shown as \n
 
-            let full_content = markup_node.get_full_content().replace('\n', "\\n"); // any \n left here should be escaped so that it can be shown as \n
-
-            let glyph_text = glyph_brush::OwnedText::new(&full_content)
-                .with_color(colors::to_slice(*highlight_color))
-                .with_scale(code_style.font_size);
+            // what the name resolved to takes precedence over the kind of node
+            let (module_len, style) = match code_style.semantic_styles.get(&markup_id) {
+                Some(semantic_style) if content_str.is_char_boundary(semantic_style.module_len) => {
+                    (semantic_style.module_len, &semantic_style.style)
+                }
+                _ => (0, syn_high_style),
+            };
 
             for attribute in &attributes.all {
                 match attribute {
                     Attribute::Underline { underline_spec: _ } => {
                         // TODO use underline_spec
-                        let top_left_coords = (
-                            code_style.txt_coords.x + (txt_row_col.1 as f32) * char_width,
-                            code_style.txt_coords.y
-                                + (txt_row_col.0 as f32) * char_height
-                                + 1.0 * char_height,
-                        );
-
-                        let underline_rect = Rect {
-                            top_left_coords: top_left_coords.into(),
-                            width: char_width * (full_content.len() as f32),
-                            height: 5.0,
-                            color: *code_style
-                                .ed_theme
-                                .underline_color_map
-                                .get(&UnderlineStyle::Error)
-                                .unwrap(),
-                        };
-
-                        rects.push(underline_rect);
+                        // one underline for every row on screen that the text is shown on
+                        for (part_pos, part_len) in code_writer.visible_parts(content_str.len()) {
+                            let top_left_coords = (
+                                code_style.txt_coords.x + (part_pos.column as f32) * char_width,
+                                code_style.txt_coords.y
+                                    + (part_pos.line as f32) * char_height
+                                    + 1.0 * char_height,
+                            );
+
+                            let underline_rect = Rect {
+                                top_left_coords: top_left_coords.into(),
+                                width: char_width * (part_len as f32),
+                                height: 5.0,
+                                color: *code_style
+                                    .ed_theme
+                                    .underline_color_map
+                                    .get(&UnderlineStyle::Error)
+                                    .unwrap(),
+                            };
+
+                            rects.push(underline_rect);
+                        }
                     }
                     rest => todo!("handle Attribute: {:?}", rest),
                 }
             }
 
-            txt_row_col.1 += content.len();
+            if let Some(Decoration::Gutter(color)) = code_style.plugin_decorations.get(&markup_id) {
+                // shown even when the line is scrolled horizontally
+                rects.push(Rect {
+                    top_left_coords: (
+                        code_style.txt_coords.x - 1.2 * char_width,
+                        code_style.txt_coords.y
+                            + (code_writer.line_first_row as f32) * char_height
+                            + 0.3 * char_height,
+                    )
+                        .into(),
+                    width: 0.8 * char_width,
+                    height: 0.8 * char_width,
+                    color: *color,
+                });
+            }
 
-            for _ in 0..*newlines_at_end {
-                txt_row_col.0 += 1;
-                txt_row_col.1 = 0;
+            if let Some(Decoration::Underline(color)) =
+                code_style.plugin_decorations.get(&markup_id)
+            {
+                // the indentation that is part of the content is not underlined
+                let mut indent_len = content_str.len() - content_str.trim_start().len();
+
+                for (part_pos, part_len) in code_writer.visible_parts(content_str.len()) {
+                    let skipped_len = indent_len.min(part_len);
+                    indent_len -= skipped_len;
+
+                    if part_len > skipped_len {
+                        rects.push(Rect {
+                            top_left_coords: (
+                                code_style.txt_coords.x
+                                    + ((part_pos.column + skipped_len) as f32) * char_width,
+                                code_style.txt_coords.y
+                                    + (part_pos.line as f32) * char_height
+                                    + 1.0 * char_height,
+                            )
+                                .into(),
+                            width: char_width * ((part_len - skipped_len) as f32),
+                            height: 3.0,
+                            color: *color,
+                        });
+                    }
+                }
             }
 
-            wgpu_texts.push(glyph_text);
+            if module_len > 0 {
+                let module_color = map_get(
+                    &code_style.ed_theme.syntax_high_map,
+                    &HighlightStyle::Module,
+                )?;
+
+                code_writer.write(&content_str[..module_len], colors::to_slice(*module_color));
+            }
+
+            let highlight_color = map_get(&code_style.ed_theme.syntax_high_map, style)?;
+
+            code_writer.write(
+                &content_str[module_len..],
+                colors::to_slice(*highlight_color),
+            );
+
+            for _ in 0..*newlines_at_end {
+                code_writer.newline();
+            }
         }
         MarkupNode::Blank {
             attributes: _,
             parent_id_opt: _,
             newlines_at_end,
         } => {
-            let full_content = markup_node.get_full_content();
-
-            let glyph_text = glyph_brush::OwnedText::new(full_content)
-                .with_color(colors::to_slice(colors::WHITE))
-                .with_scale(code_style.font_size);
-
             let highlight_color =
                 map_get(&code_style.ed_theme.syntax_high_map, &HighlightStyle::Blank)?;
 
-            let blank_rect = Rect {
-                top_left_coords: (
-                    code_style.txt_coords.x + (txt_row_col.1 as f32) * char_width,
-                    code_style.txt_coords.y
-                        + (txt_row_col.0 as f32) * char_height
-                        + 0.1 * char_height,
-                )
-                    .into(),
-                width: char_width,
-                height: char_height,
-                color: *highlight_color,
-            };
-            rects.push(blank_rect);
+            for (part_pos, part_len) in code_writer.visible_parts(BLANK_PLACEHOLDER.len()) {
+                let blank_rect = Rect {
+                    top_left_coords: (
+                        code_style.txt_coords.x + (part_pos.column as f32) * char_width,
+                        code_style.txt_coords.y
+                            + (part_pos.line as f32) * char_height
+                            + 0.1 * char_height,
+                    )
+                        .into(),
+                    width: char_width * (part_len as f32),
+                    height: char_height,
+                    color: *highlight_color,
+                };
+                rects.push(blank_rect);
+            }
 
-            txt_row_col.1 += BLANK_PLACEHOLDER.len();
-            wgpu_texts.push(glyph_text);
+            code_writer.write(BLANK_PLACEHOLDER, colors::to_slice(colors::WHITE));
 
             for _ in 0..*newlines_at_end {
-                txt_row_col.0 += 1;
-                txt_row_col.1 = 0;
+                code_writer.newline();
             }
         }
         MarkupNode::Indent { .. } => {
             let full_content: String = markup_node.get_content();
 
-            txt_row_col.1 += full_content.len();
-
-            let glyph_text = glyph_brush::OwnedText::new(full_content)
-                .with_color(colors::to_slice(colors::WHITE))
-                .with_scale(code_style.font_size);
-
-            wgpu_texts.push(glyph_text);
+            code_writer.write(&full_content, colors::to_slice(colors::WHITE));
         }
     };
 
     Ok(())
 }
-
-fn newline(font_size: f32) -> glyph_brush::OwnedText {
-    glyph_brush::OwnedText::new("\n").with_scale(font_size)
-}
@@ -0,0 +1,49 @@
+use crate::editor::config::Config;
+use crate::editor::plugin::CommandPalette;
+use crate::graphics::colors;
+use crate::graphics::primitives::text as gr_text;
+use cgmath::Vector2;
+use winit::dpi::PhysicalSize;
+
+// list of plugin commands, drawn on the right half of the window
+pub fn build_command_palette_graphics(
+    size: &PhysicalSize<u32>,
+    txt_coords: Vector2<f32>,
+    config: &Config,
+    command_palette: &CommandPalette,
+) -> glyph_brush::OwnedSection {
+    let area_bounds = (size.width as f32, size.height as f32);
+    let layout = wgpu_glyph::Layout::default().h_align(wgpu_glyph::HorizontalAlign::Left);
+
+    let palette_txt_coords: Vector2<f32> = (size.width as f32 / 2.0, txt_coords.y).into();
+
+    let ui_theme = &config.ed_theme.ui_theme;
+
+    let mut glyph_texts =
+        vec![
+            glyph_brush::OwnedText::new("Commands (Up/Down: select, Enter: run, Esc: close)\n\n")
+                .with_color(colors::to_slice(ui_theme.text))
+                .with_scale(config.debug_font_size),
+        ];
+
+    for (index, (plugin_name, command)) in command_palette.entries.iter().enumerate() {
+        let color = if index == command_palette.selected_index {
+            ui_theme.light_brand
+        } else {
+            config.ed_theme.subtle_text
+        };
+
+        glyph_texts.push(
+            glyph_brush::OwnedText::new(format!("{}: {}\n", plugin_name, command.name))
+                .with_color(colors::to_slice(color))
+                .with_scale(config.debug_font_size),
+        );
+    }
+
+    gr_text::owned_section_from_glyph_texts(
+        glyph_texts,
+        palette_txt_coords.into(),
+        area_bounds,
+        layout,
+    )
+}
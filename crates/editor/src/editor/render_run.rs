@@ -0,0 +1,179 @@
+use crate::editor::background_run::{BackgroundRun, RunPhase};
+use crate::editor::breadcrumbs::breadcrumbs_txt_coords;
+use crate::editor::config::Config;
+use crate::graphics::colors;
+use crate::graphics::primitives::rect::Rect;
+use crate::graphics::primitives::text as gr_text;
+use cgmath::Vector2;
+use winit::dpi::PhysicalSize;
+
+pub const RUN_BUTTON_LABEL: &str = "[ Run ]";
+
+// The Run button is drawn at the right end of the breadcrumb bar, the code is not drawn there.
+fn run_button_txt_coords(
+    size: &PhysicalSize<u32>,
+    txt_coords: Vector2<f32>,
+    glyph_dim_rect: &Rect,
+) -> Vector2<f32> {
+    let crumb_coords = breadcrumbs_txt_coords(txt_coords, glyph_dim_rect);
+    let button_width = RUN_BUTTON_LABEL.len() as f32 * glyph_dim_rect.width;
+
+    (
+        size.width as f32 - txt_coords.x - button_width,
+        crumb_coords.y,
+    )
+        .into()
+}
+
+pub fn run_button_at_pos(
+    click_x: f32,
+    click_y: f32,
+    size: &PhysicalSize<u32>,
+    txt_coords: Vector2<f32>,
+    glyph_dim_rect: &Rect,
+) -> bool {
+    let button_coords = run_button_txt_coords(size, txt_coords, glyph_dim_rect);
+    let button_width = RUN_BUTTON_LABEL.len() as f32 * glyph_dim_rect.width;
+
+    click_x >= button_coords.x
+        && click_x < button_coords.x + button_width
+        && click_y >= button_coords.y
+        && click_y < button_coords.y + glyph_dim_rect.height
+}
+
+// the button is dimmed while a run is busy, clicking it then does nothing
+pub fn build_run_button_graphics(
+    size: &PhysicalSize<u32>,
+    txt_coords: Vector2<f32>,
+    config: &Config,
+    glyph_dim_rect: &Rect,
+    running: bool,
+) -> glyph_brush::OwnedSection {
+    let area_bounds = (size.width as f32, size.height as f32);
+    let layout = wgpu_glyph::Layout::default().h_align(wgpu_glyph::HorizontalAlign::Left);
+
+    let color = if running {
+        config.ed_theme.subtle_text
+    } else {
+        config.ed_theme.ui_theme.light_brand
+    };
+
+    let glyph_texts = vec![glyph_brush::OwnedText::new(RUN_BUTTON_LABEL)
+        .with_color(colors::to_slice(color))
+        .with_scale(config.code_font_size)];
+
+    gr_text::owned_section_from_glyph_texts(
+        glyph_texts,
+        run_button_txt_coords(size, txt_coords, glyph_dim_rect).into(),
+        area_bounds,
+        layout,
+    )
+}
+
+// problems of the build and output of the app, drawn on the right half of the window
+pub fn build_run_graphics(
+    size: &PhysicalSize<u32>,
+    txt_coords: Vector2<f32>,
+    config: &Config,
+    background_run: &BackgroundRun,
+) -> glyph_brush::OwnedSection {
+    let area_bounds = (size.width as f32, size.height as f32);
+    let layout = wgpu_glyph::Layout::default().h_align(wgpu_glyph::HorizontalAlign::Left);
+
+    let run_txt_coords: Vector2<f32> = (size.width as f32 / 2.0, txt_coords.y).into();
+
+    let ui_theme = &config.ed_theme.ui_theme;
+
+    let status = match background_run.phase {
+        RunPhase::Building => "building...".to_owned(),
+        RunPhase::BuildFailed => "build failed".to_owned(),
+        RunPhase::Running => "running...".to_owned(),
+        RunPhase::Exited(Some(exit_code)) => format!("exited with code {}", exit_code),
+        RunPhase::Exited(None) => "stopped".to_owned(),
+    };
+
+    let mut glyph_texts = vec![glyph_brush::OwnedText::new(format!(
+        "Run {}: {} (Esc: close)\n\n",
+        background_run.file_path.display(),
+        status
+    ))
+    .with_color(colors::to_slice(ui_theme.text))
+    .with_scale(config.debug_font_size)];
+
+    let mut build_str = format!("Build\n{}\n\n", background_run.progress);
+
+    for problem in &background_run.problems {
+        build_str.push_str(problem);
+        build_str.push_str("\n\n");
+    }
+
+    glyph_texts.push(
+        glyph_brush::OwnedText::new(build_str)
+            .with_color(colors::to_slice(config.ed_theme.subtle_text))
+            .with_scale(config.debug_font_size),
+    );
+
+    if !matches!(
+        background_run.phase,
+        RunPhase::Building | RunPhase::BuildFailed
+    ) {
+        let mut output_str = "Output\n".to_owned();
+
+        for line in &background_run.output_lines {
+            output_str.push_str(line);
+            output_str.push('\n');
+        }
+
+        glyph_texts.push(
+            glyph_brush::OwnedText::new(output_str)
+                .with_color(colors::to_slice(ui_theme.text))
+                .with_scale(config.debug_font_size),
+        );
+    }
+
+    gr_text::owned_section_from_glyph_texts(glyph_texts, run_txt_coords.into(), area_bounds, layout)
+}
+
+#[cfg(test)]
+pub mod test_render_run {
+    use crate::editor::render_run::{run_button_at_pos, RUN_BUTTON_LABEL};
+    use crate::graphics::primitives::rect::Rect;
+    use winit::dpi::PhysicalSize;
+
+    #[test]
+    fn click_on_run_button() {
+        let size = PhysicalSize::new(1000, 800);
+        let glyph_dim_rect = Rect {
+            top_left_coords: (0.0, 0.0).into(),
+            width: 10.0,
+            height: 20.0,
+            color: (0.0, 0.0, 0.0, 1.0),
+        };
+        let txt_coords = (50.0, 100.0).into();
+
+        // the button ends where the margin on the right of the window starts, on the line above the code
+        let button_left = 1000.0 - 50.0 - RUN_BUTTON_LABEL.len() as f32 * 10.0;
+
+        assert!(run_button_at_pos(
+            button_left + 1.0,
+            85.0,
+            &size,
+            txt_coords,
+            &glyph_dim_rect
+        ));
+        assert!(!run_button_at_pos(
+            button_left - 1.0,
+            85.0,
+            &size,
+            txt_coords,
+            &glyph_dim_rect
+        ));
+        assert!(!run_button_at_pos(
+            button_left + 1.0,
+            105.0,
+            &size,
+            txt_coords,
+            &glyph_dim_rect
+        ));
+    }
+}
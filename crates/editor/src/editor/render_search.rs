@@ -0,0 +1,72 @@
+use crate::editor::config::Config;
+use crate::editor::project_search::ProjectSearch;
+use crate::graphics::colors;
+use crate::graphics::primitives::text as gr_text;
+use cgmath::Vector2;
+use winit::dpi::PhysicalSize;
+
+// list of matches of a project search, drawn on the right half of the window
+pub fn build_search_graphics(
+    size: &PhysicalSize<u32>,
+    txt_coords: Vector2<f32>,
+    config: &Config,
+    project_search: &ProjectSearch,
+) -> glyph_brush::OwnedSection {
+    let area_bounds = (size.width as f32, size.height as f32);
+    let layout = wgpu_glyph::Layout::default().h_align(wgpu_glyph::HorizontalAlign::Left);
+
+    let search_txt_coords: Vector2<f32> = (size.width as f32 / 2.0, txt_coords.y).into();
+
+    let ui_theme = &config.ed_theme.ui_theme;
+
+    let status = if project_search.done {
+        format!("{} matches", project_search.matches.len())
+    } else {
+        format!("{} matches, searching...", project_search.matches.len())
+    };
+
+    let mut glyph_texts = vec![glyph_brush::OwnedText::new(format!(
+        "Search \"{}\": {} (F3: next, Shift+F3: previous, Esc: close)\n\n",
+        project_search.query, status
+    ))
+    .with_color(colors::to_slice(ui_theme.text))
+    .with_scale(config.debug_font_size)];
+
+    for (index, search_match) in project_search.matches.iter().enumerate() {
+        let color = if index == project_search.selected_match_index {
+            ui_theme.light_brand
+        } else {
+            config.ed_theme.subtle_text
+        };
+
+        let mut match_str = format!(
+            "{}:{}:{}\n",
+            search_match.file_path.display(),
+            search_match.txt_pos.line + 1,
+            search_match.txt_pos.column + 1
+        );
+
+        for (offset, context_line) in search_match.context_lines.iter().enumerate() {
+            match_str.push_str(&format!(
+                "{:>5} | {}\n",
+                search_match.first_context_line_nr + offset + 1,
+                context_line
+            ));
+        }
+
+        match_str.push('\n');
+
+        glyph_texts.push(
+            glyph_brush::OwnedText::new(match_str)
+                .with_color(colors::to_slice(color))
+                .with_scale(config.debug_font_size),
+        );
+    }
+
+    gr_text::owned_section_from_glyph_texts(
+        glyph_texts,
+        search_txt_coords.into(),
+        area_bounds,
+        layout,
+    )
+}
@@ -7,9 +7,16 @@ pub const START_TIP: &str = r#"Currently supported: lists, records, string, numb
 
 Use `Ctrl+Shift+Up` or `Cmd+Shift+Up` to select surrounding expression.
 Use backspace after `Ctrl+Shift+Up` to delete the selected expression.
+Click on a part of the path above the code to select that expression.
+
+Use `Ctrl+Shift+F` or `Cmd+Shift+F` to search for the selected text in all files of the project.
+Use `Ctrl+.` or `Cmd+.` to apply the quick fix shown next to the caret.
+Use `Alt+Z` to wrap lines that are too long for the window, or scroll them sideways with `Shift` and the mouse wheel.
+Use `Alt+M` to start and stop recording a macro, and `Alt+P` to replay it.
+Use `Ctrl+Shift+P` or `Cmd+Shift+P` to open the commands of the editor plugins.
 
 `Ctrl+S` or `Cmd+S` to save.
-`Ctrl+R` to run.
+`Ctrl+R` or the Run button to build and run the app, `Esc` to stop it.
 
 Input chars that would create parse errors or change formatting will be ignored.
 For convenience and consistency, there is only one way to format roc.
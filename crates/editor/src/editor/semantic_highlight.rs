@@ -0,0 +1,197 @@
+use crate::editor::ed_error::EdResult;
+use crate::editor::mvc::ed_model::EdModel;
+use roc_ast::lang::core::ast::ASTNodeId;
+use roc_ast::lang::core::def::def2::Def2;
+use roc_ast::lang::core::expr::expr2::Expr2;
+use roc_can::expr::DeclarationTag;
+use roc_code_markup::markup::nodes::MarkupNode;
+use roc_code_markup::slow_pool::MarkNodeId;
+use roc_code_markup::syntax_highlight::HighlightStyle;
+use roc_collections::MutMap;
+use roc_load::LoadedModule;
+use roc_module::symbol::Symbol;
+
+// Highlighting based on what a name resolved to during canonicalization, instead of on the kind of
+// markup node. Names that were added after the module was loaded have not been analyzed yet,
+// they keep their syntax highlighting until the module is loaded again.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SemanticStyle {
+    pub style: HighlightStyle,
+    // the first `module_len` bytes of the node's content are a module name, e.g. `Str` in `Str.concat`
+    pub module_len: usize,
+}
+
+pub fn semantic_styles(ed_model: &EdModel) -> EdResult<MutMap<MarkNodeId, SemanticStyle>> {
+    let mut styles = MutMap::default();
+    let mut stack: Vec<MarkNodeId> = ed_model.markup_ids.clone();
+
+    while let Some(mark_node_id) = stack.pop() {
+        match ed_model.mark_node_pool.get(mark_node_id) {
+            MarkupNode::Nested { children_ids, .. } => stack.extend(children_ids),
+            MarkupNode::Text {
+                content,
+                syn_high_style,
+                ..
+            } => {
+                let ast_node_id = ed_model.mark_id_ast_id_map.get(mark_node_id)?;
+
+                if let Some(semantic_style) =
+                    text_semantic_style(ed_model, ast_node_id, content, *syn_high_style)
+                {
+                    styles.insert(mark_node_id, semantic_style);
+                }
+            }
+            MarkupNode::Blank { .. } | MarkupNode::Indent { .. } => (),
+        }
+    }
+
+    Ok(styles)
+}
+
+fn text_semantic_style(
+    ed_model: &EdModel,
+    ast_node_id: ASTNodeId,
+    content: &str,
+    syn_high_style: HighlightStyle,
+) -> Option<SemanticStyle> {
+    let pool = &ed_model.module.env.pool;
+
+    match ast_node_id {
+        ASTNodeId::AExprId(expr_id) => match pool.get(expr_id) {
+            Expr2::Var(symbol) => {
+                let style = loaded_symbol(ed_model, *symbol)
+                    .and_then(|symbol| def_style(&ed_model.loaded_module, symbol))?;
+
+                let module_len = if symbol.module_id() == ed_model.module.env.home {
+                    0
+                } else {
+                    content.rfind('.').unwrap_or(0)
+                };
+
+                Some(SemanticStyle { style, module_len })
+            }
+            Expr2::RuntimeError() => Some(SemanticStyle {
+                style: HighlightStyle::Unresolved,
+                module_len: 0,
+            }),
+            _ => None,
+        },
+        // the name of a top level def, the other text nodes of a def are `=` and comments
+        ASTNodeId::ADefId(def_id) if syn_high_style == HighlightStyle::Value => {
+            let identifier_id = match pool.get(def_id) {
+                Def2::ValueDef { identifier_id, .. } => *identifier_id,
+                _ => return None,
+            };
+
+            let symbol = Symbol::new(ed_model.module.env.home, identifier_id);
+            let style = loaded_symbol(ed_model, symbol)
+                .and_then(|symbol| def_style(&ed_model.loaded_module, symbol))?;
+
+            Some(SemanticStyle {
+                style,
+                module_len: 0,
+            })
+        }
+        ASTNodeId::ADefId(_) => None,
+    }
+}
+
+// The editor gives the idents of the open module its own ids, so those are matched by name.
+pub fn loaded_symbol(ed_model: &EdModel, symbol: Symbol) -> Option<Symbol> {
+    let home = ed_model.module.env.home;
+
+    if symbol.module_id() != home {
+        return Some(symbol);
+    }
+
+    let name = ed_model.module.env.ident_ids.get_name(symbol.ident_id())?;
+    let loaded_ident_ids = ed_model.loaded_module.interns.all_ident_ids.get(&home)?;
+
+    loaded_ident_ids
+        .get_id(name)
+        .map(|ident_id| Symbol::new(home, ident_id))
+}
+
+fn def_style(loaded_module: &LoadedModule, symbol: Symbol) -> Option<HighlightStyle> {
+    let decls = loaded_module.declarations_by_id.get(&symbol.module_id())?;
+
+    let index = decls
+        .symbols
+        .iter()
+        .position(|loc_symbol| loc_symbol.value == symbol)?;
+
+    match decls.declarations[index] {
+        DeclarationTag::Function(_)
+        | DeclarationTag::Recursive(_)
+        | DeclarationTag::TailRecursive(_) => Some(HighlightStyle::FunctionName),
+        DeclarationTag::Value | DeclarationTag::Destructure(_) => Some(HighlightStyle::Value),
+        DeclarationTag::Expectation
+        | DeclarationTag::ExpectationFx
+        | DeclarationTag::MutualRecursion { .. } => None,
+    }
+}
+
+#[cfg(test)]
+pub mod test_semantic_highlight {
+    use crate::editor::mvc::ed_model::test_ed_model::{ed_model_from_dsl, init_model_refs};
+    use crate::editor::semantic_highlight::SemanticStyle;
+    use bumpalo::Bump;
+    use roc_code_markup::markup::nodes::MarkupNode;
+    use roc_code_markup::syntax_highlight::HighlightStyle;
+    use roc_module::symbol::ModuleIds;
+
+    #[test]
+    fn function_def_and_qualified_lookup() -> Result<(), String> {
+        let code_line = "val = Num.toStr┃";
+        let mut code_str = code_line.replace('┃', "");
+
+        let mut model_refs = init_model_refs();
+        let code_arena = Bump::new();
+        let module_ids = ModuleIds::default();
+
+        let ed_model = ed_model_from_dsl(
+            &mut code_str,
+            vec![code_line.to_owned()],
+            &mut model_refs,
+            &module_ids,
+            &code_arena,
+        )?;
+
+        let style_of = |content: &str| {
+            ed_model
+                .semantic_styles
+                .iter()
+                .find(|(mark_node_id, _)| {
+                    matches!(
+                        ed_model.mark_node_pool.get(**mark_node_id),
+                        MarkupNode::Text { content: text, .. } if text == content
+                    )
+                })
+                .map(|(_, semantic_style)| *semantic_style)
+        };
+
+        assert_eq!(
+            style_of("main"),
+            Some(SemanticStyle {
+                style: HighlightStyle::Value,
+                module_len: 0
+            })
+        );
+        assert_eq!(
+            style_of("Num.toStr"),
+            Some(SemanticStyle {
+                style: HighlightStyle::FunctionName,
+                module_len: 3
+            })
+        );
+        assert_eq!(
+            style_of("val"),
+            Some(SemanticStyle {
+                style: HighlightStyle::Value,
+                module_len: 0
+            })
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,234 @@
+use crate::editor::config::Config;
+use crate::editor::ed_error::EdResult;
+use crate::editor::evaluate::{evaluate, Value};
+use crate::editor::mvc::ed_model::EdModel;
+use crate::editor::plugin::plugin_decorations;
+use crate::graphics::primitives::rect::Rect;
+use crate::ui::text::lines::SelectableLines;
+use crate::ui::tooltip::ToolTip;
+use cgmath::Vector2;
+use roc_ast::lang::core::ast::ASTNodeId;
+use roc_ast::lang::core::def::def2::{Def2, DefId};
+use roc_ast::lang::core::expr::expr2::ExprId;
+use roc_code_markup::markup::nodes::{get_root_mark_node_id, MarkupNode};
+use roc_code_markup::slow_pool::MarkNodeId;
+
+// Runs the top-level `expect`s of a module and shows whether they passed next to them, see the TestLens plugin.
+// The expects are run with the interpreter of evaluate.rs, so an expect that uses something it
+// does not support fails with the reason instead of a wrong result.
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExpectStatus {
+    Passed,
+    Failed(String), // shown in a popup when the caret is on the line of the expect
+}
+
+#[derive(Debug, Clone)]
+pub struct ExpectResult {
+    pub def_id: DefId,
+    pub keyword_mn_id: MarkNodeId, // the `expect ` keyword, the status is shown in the gutter next to it
+    pub status: ExpectStatus,
+}
+
+struct ModuleExpect {
+    def_id: DefId,
+    expr_id: ExprId,
+    keyword_mn_id: MarkNodeId,
+}
+
+// the top-level expects in the order they appear in the module
+fn module_expects(ed_model: &EdModel) -> Vec<ModuleExpect> {
+    let mut expects = Vec::new();
+    let mut stack: Vec<MarkNodeId> = ed_model.markup_ids.iter().rev().copied().collect();
+
+    while let Some(mark_node_id) = stack.pop() {
+        match ed_model.mark_node_pool.get(mark_node_id) {
+            MarkupNode::Nested { children_ids, .. } => {
+                stack.extend(children_ids.iter().rev());
+            }
+            MarkupNode::Text { content, .. } if content == "expect " => {
+                if let Ok(ASTNodeId::ADefId(def_id)) = ed_model.mark_id_ast_id_map.get(mark_node_id)
+                {
+                    if let Def2::Expect { expr_id } = ed_model.module.env.pool.get(def_id) {
+                        expects.push(ModuleExpect {
+                            def_id,
+                            expr_id: *expr_id,
+                            keyword_mn_id: mark_node_id,
+                        });
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    expects
+}
+
+fn run_expect(ed_model: &EdModel, expect: &ModuleExpect) -> ExpectResult {
+    let status = match evaluate(ed_model, expect.expr_id) {
+        Ok(Value::Bool(true)) => ExpectStatus::Passed,
+        Ok(Value::Bool(false)) => ExpectStatus::Failed("This expectation failed.".to_owned()),
+        Ok(value) => ExpectStatus::Failed(format!("An expect needs a Bool, but this is {}", value)),
+        Err(reason) => ExpectStatus::Failed(format!("Can't run this expect: {}", reason)),
+    };
+
+    ExpectResult {
+        def_id: expect.def_id,
+        keyword_mn_id: expect.keyword_mn_id,
+        status,
+    }
+}
+
+fn show_results(ed_model: &mut EdModel, results: Vec<ExpectResult>) {
+    // an expect that was run again replaces its old result
+    ed_model
+        .expect_results
+        .retain(|old| results.iter().all(|new| new.def_id != old.def_id));
+    ed_model.expect_results.extend(results);
+
+    ed_model.plugin_decorations = plugin_decorations(ed_model);
+    ed_model.dirty = true;
+}
+
+pub fn run_module_expects(ed_model: &mut EdModel) -> EdResult<()> {
+    let results: Vec<ExpectResult> = module_expects(ed_model)
+        .iter()
+        .map(|expect| run_expect(ed_model, expect))
+        .collect();
+
+    let failed = results
+        .iter()
+        .filter(|result| result.status != ExpectStatus::Passed)
+        .count();
+
+    println!(
+        "\n{} passed and {} failed in {}.",
+        results.len() - failed,
+        failed,
+        ed_model.file_path.display()
+    );
+
+    show_results(ed_model, results);
+
+    Ok(())
+}
+
+pub fn run_expect_at_caret(ed_model: &mut EdModel) -> EdResult<()> {
+    let caret_def_mn_id = ed_model.grid_node_map.get_def_mark_node_id_before_line(
+        ed_model.get_caret().line + 1,
+        &ed_model.mark_node_pool,
+        &ed_model.mark_id_ast_id_map,
+    );
+
+    let expect_opt = caret_def_mn_id.ok().and_then(|def_mn_id| {
+        module_expects(ed_model).into_iter().find(|expect| {
+            get_root_mark_node_id(expect.keyword_mn_id, &ed_model.mark_node_pool) == def_mn_id
+        })
+    });
+
+    match expect_opt {
+        Some(expect) => {
+            let result = run_expect(ed_model, &expect);
+
+            show_results(ed_model, vec![result]);
+        }
+        None => println!("\nPut the caret on an expect to run it."),
+    }
+
+    Ok(())
+}
+
+pub fn expect_status(ed_model: &EdModel, mark_node_id: MarkNodeId) -> Option<&ExpectStatus> {
+    ed_model
+        .expect_results
+        .iter()
+        .find(|result| result.keyword_mn_id == mark_node_id)
+        .map(|result| &result.status)
+}
+
+// the reason an expect on the caret line failed, shown above that line
+pub fn build_expect_failure_graphics(
+    ed_model: &EdModel,
+    txt_coords: Vector2<f32>,
+    config: &Config,
+    glyph_dim_rect: &Rect,
+) -> EdResult<Option<(Rect, glyph_brush::OwnedSection)>> {
+    let caret_line = ed_model.get_caret().line;
+
+    for result in &ed_model.expect_results {
+        if let ExpectStatus::Failed(reason) = &result.status {
+            let keyword_pos = ed_model
+                .grid_node_map
+                .get_node_position(result.keyword_mn_id, true)?;
+
+            if keyword_pos.line == caret_line {
+                let screen_pos = ed_model.grid_node_map.screen_pos(keyword_pos);
+
+                let tooltip = ToolTip {
+                    position_x: txt_coords.x,
+                    position_y: txt_coords.y
+                        + (screen_pos.line as f32 - 0.9) * glyph_dim_rect.height,
+                    text: reason,
+                };
+
+                return Ok(Some(tooltip.render_tooltip(
+                    glyph_dim_rect,
+                    &config.ed_theme.ui_theme,
+                    config.code_font_size,
+                )));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+pub mod test_test_lens {
+    use crate::editor::mvc::ed_model::test_ed_model::{ed_model_from_dsl, init_model_refs};
+    use crate::editor::test_lens::{run_module_expects, ExpectStatus};
+    use bumpalo::Bump;
+    use roc_module::symbol::ModuleIds;
+
+    #[test]
+    fn run_expects() -> Result<(), String> {
+        let code_lines = vec![
+            "expect 1 + 1 == 2".to_owned(),
+            "".to_owned(),
+            "".to_owned(),
+            "expect 1 + 1 == 3┃".to_owned(),
+        ];
+        let mut code_str = code_lines.join("\n").replace('┃', "");
+
+        let mut model_refs = init_model_refs();
+        let code_arena = Bump::new();
+        let module_ids = ModuleIds::default();
+
+        let mut ed_model = ed_model_from_dsl(
+            &mut code_str,
+            code_lines,
+            &mut model_refs,
+            &module_ids,
+            &code_arena,
+        )?;
+
+        run_module_expects(&mut ed_model)?;
+
+        let statuses: Vec<&ExpectStatus> = ed_model
+            .expect_results
+            .iter()
+            .map(|result| &result.status)
+            .collect();
+
+        assert_eq!(
+            statuses,
+            vec![
+                &ExpectStatus::Passed,
+                &ExpectStatus::Failed("This expectation failed.".to_owned())
+            ]
+        );
+
+        Ok(())
+    }
+}
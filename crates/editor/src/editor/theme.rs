@@ -1,7 +1,9 @@
 use gr_colors::{from_hsb, RgbaTup};
 use roc_code_markup::{
-    syntax_highlight::{default_highlight_map, HighlightStyle},
-    underline_style::{default_underline_color_map, UnderlineStyle},
+    syntax_highlight::{default_highlight_map, high_contrast_highlight_map, HighlightStyle},
+    underline_style::{
+        default_underline_color_map, high_contrast_underline_color_map, UnderlineStyle,
+    },
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -29,3 +31,16 @@ impl Default for EdTheme {
         }
     }
 }
+
+impl EdTheme {
+    // white and bright colors on black, with a wide caret that stands out from the text
+    pub fn high_contrast() -> Self {
+        Self {
+            background: gr_colors::BLACK,
+            subtle_text: from_hsb(0, 0, 80),
+            syntax_high_map: high_contrast_highlight_map(),
+            ui_theme: UITheme::high_contrast(),
+            underline_color_map: high_contrast_underline_color_map(),
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use crate::editor::grid_node_map::GridNodeMap;
+use crate::ui::text::text_pos::TextPos;
+use std::cmp::{max, min};
+use std::ops::Range;
+
+// nr of columns to scroll for one step of the mouse wheel
+pub const SCROLL_COLS_PER_LINE: f32 = 3.0;
+
+// The columns of the code that fit in the window.
+// Lines that are too long are cut off at the edge of the window and the viewport scrolls horizontally to keep the caret in view.
+// With soft wrap, long lines are split over several rows on screen instead, see GridNodeMap::screen_pos.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Viewport {
+    pub soft_wrap: bool,
+    pub first_col: usize,  // first visible column, always 0 with soft wrap
+    pub nr_of_cols: usize, // nr of columns that fit in the window, updated on every render
+    followed_caret_opt: Option<TextPos>, // caret position the viewport last scrolled to
+}
+
+impl Default for Viewport {
+    fn default() -> Self {
+        Self {
+            soft_wrap: false,
+            first_col: 0,
+            nr_of_cols: usize::MAX, // the window size is not known before the first render
+            followed_caret_opt: None,
+        }
+    }
+}
+
+impl Viewport {
+    // The window may have been resized or the caret may have moved since the previous render.
+    pub fn update(
+        &mut self,
+        nr_of_cols: usize,
+        caret_pos: TextPos,
+        grid_node_map: &mut GridNodeMap,
+    ) {
+        self.nr_of_cols = max(nr_of_cols, 1);
+
+        if self.soft_wrap {
+            self.first_col = 0;
+            grid_node_map.set_wrap_width(Some(self.nr_of_cols));
+        } else {
+            grid_node_map.set_wrap_width(None);
+
+            // only follow the caret when it moved, otherwise scrolling with the mouse wheel would be undone
+            if self.followed_caret_opt != Some(caret_pos) {
+                self.scroll_to_col(caret_pos.column);
+                self.followed_caret_opt = Some(caret_pos);
+            }
+        }
+    }
+
+    fn scroll_to_col(&mut self, col: usize) {
+        if col < self.first_col {
+            self.first_col = col;
+        } else if col >= self.first_col.saturating_add(self.nr_of_cols) {
+            self.first_col = col + 1 - self.nr_of_cols;
+        }
+    }
+
+    // a negative nr_of_cols scrolls to the left
+    pub fn scroll_by(&mut self, nr_of_cols: isize) {
+        if !self.soft_wrap {
+            self.first_col = if nr_of_cols < 0 {
+                self.first_col.saturating_sub(nr_of_cols.unsigned_abs())
+            } else {
+                self.first_col.saturating_add(nr_of_cols as usize)
+            };
+        }
+    }
+
+    pub fn toggle_soft_wrap(&mut self) {
+        self.soft_wrap = !self.soft_wrap;
+        self.first_col = 0;
+        self.followed_caret_opt = None;
+    }
+
+    // the part of cols (on a row on screen) that is visible, counted from the left edge of the code
+    pub fn visible_cols(&self, cols: Range<usize>) -> Option<Range<usize>> {
+        let start = max(cols.start, self.first_col);
+        let end = min(cols.end, self.first_col.saturating_add(self.nr_of_cols));
+
+        if start < end {
+            Some((start - self.first_col)..(end - self.first_col))
+        } else {
+            None
+        }
+    }
+
+    // column on screen of a caret (on a row on screen), None if it was scrolled out of view
+    // The caret fits right after the last visible column, at the end of a row that fills the window.
+    pub fn visible_col(&self, col: usize) -> Option<usize> {
+        if col >= self.first_col && col - self.first_col <= self.nr_of_cols {
+            Some(col - self.first_col)
+        } else {
+            None
+        }
+    }
+
+    // column on a row on screen of a click at visible_col, counted from the left edge of the code
+    pub fn col_at(&self, visible_col: usize) -> usize {
+        self.first_col + visible_col
+    }
+}
+
+#[cfg(test)]
+pub mod test_viewport {
+    use crate::editor::grid_node_map::GridNodeMap;
+    use crate::editor::viewport::Viewport;
+    use crate::ui::text::text_pos::TextPos;
+
+    fn pos(line: usize, column: usize) -> TextPos {
+        TextPos { line, column }
+    }
+
+    // a map with lines of the given lengths, the node ids don't matter here
+    fn grid_node_map_w_lines(line_lens: &[usize]) -> GridNodeMap {
+        let mut grid_node_map = GridNodeMap::default();
+
+        grid_node_map.lines = line_lens.iter().map(|len| vec![0; *len]).collect();
+
+        grid_node_map
+    }
+
+    #[test]
+    fn follow_caret() {
+        let mut grid_node_map = grid_node_map_w_lines(&[30, 5]);
+        let mut viewport = Viewport::default();
+
+        viewport.update(10, pos(0, 4), &mut grid_node_map);
+        assert_eq!(viewport.first_col, 0);
+
+        viewport.update(10, pos(0, 25), &mut grid_node_map);
+        assert_eq!(viewport.first_col, 16);
+        assert_eq!(viewport.visible_col(25), Some(9));
+        assert_eq!(viewport.visible_cols(10..20), Some(0..4));
+
+        // scrolling with the mouse wheel is kept until the caret moves
+        viewport.scroll_by(-20);
+        viewport.update(10, pos(0, 25), &mut grid_node_map);
+        assert_eq!(viewport.first_col, 0);
+        assert_eq!(viewport.visible_col(25), None);
+
+        viewport.update(10, pos(1, 3), &mut grid_node_map);
+        assert_eq!(viewport.first_col, 0);
+        assert_eq!(grid_node_map.wrap_width_opt, None);
+    }
+
+    #[test]
+    fn soft_wrap_screen_pos() {
+        let mut grid_node_map = grid_node_map_w_lines(&[25, 0, 20, 3]);
+        let mut viewport = Viewport::default();
+
+        viewport.toggle_soft_wrap();
+        viewport.update(10, pos(0, 25), &mut grid_node_map);
+
+        assert_eq!(viewport.first_col, 0);
+        assert_eq!(grid_node_map.nr_of_rows(0), 3);
+        assert_eq!(grid_node_map.nr_of_rows(1), 1);
+        assert_eq!(grid_node_map.nr_of_rows(2), 2);
+
+        assert_eq!(grid_node_map.screen_pos(pos(0, 9)), pos(0, 9));
+        assert_eq!(grid_node_map.screen_pos(pos(0, 10)), pos(1, 0));
+        assert_eq!(grid_node_map.screen_pos(pos(0, 25)), pos(2, 5));
+        assert_eq!(grid_node_map.screen_pos(pos(1, 0)), pos(3, 0));
+        // the end of a line that fills its last row stays on that row
+        assert_eq!(grid_node_map.screen_pos(pos(2, 20)), pos(5, 10));
+        assert_eq!(grid_node_map.screen_pos(pos(3, 1)), pos(6, 1));
+
+        assert_eq!(
+            grid_node_map.screen_parts(0, 5..22),
+            vec![(pos(0, 5), 5), (pos(1, 0), 10), (pos(2, 0), 2)]
+        );
+    }
+
+    #[test]
+    fn soft_wrap_hit_test() {
+        let mut grid_node_map = grid_node_map_w_lines(&[25, 0, 20, 3]);
+        grid_node_map.set_wrap_width(Some(10));
+
+        assert_eq!(grid_node_map.txt_pos_at_screen_pos(pos(1, 3)), pos(0, 13));
+        // past the end of a wrapped row
+        assert_eq!(grid_node_map.txt_pos_at_screen_pos(pos(1, 40)), pos(0, 19));
+        // past the end of the last row of a line
+        assert_eq!(grid_node_map.txt_pos_at_screen_pos(pos(2, 40)), pos(0, 25));
+        assert_eq!(grid_node_map.txt_pos_at_screen_pos(pos(3, 4)), pos(1, 0));
+        assert_eq!(grid_node_map.txt_pos_at_screen_pos(pos(5, 10)), pos(2, 20));
+        // below the last line
+        assert_eq!(grid_node_map.txt_pos_at_screen_pos(pos(40, 0)), pos(3, 3));
+
+        for txt_pos in [pos(0, 0), pos(0, 13), pos(0, 25), pos(2, 10), pos(3, 2)] {
+            assert_eq!(
+                grid_node_map.txt_pos_at_screen_pos(grid_node_map.screen_pos(txt_pos)),
+                txt_pos
+            );
+        }
+    }
+}
@@ -2,6 +2,7 @@ use palette::{FromColor, Hsv, LinSrgb, Srgb};
 
 pub type RgbaTup = (f32, f32, f32, f32);
 pub const WHITE: RgbaTup = (1.0, 1.0, 1.0, 1.0);
+pub const BLACK: RgbaTup = (0.0, 0.0, 0.0, 1.0);
 
 pub fn to_wgpu_color((r, g, b, a): RgbaTup) -> wgpu::Color {
     wgpu::Color {
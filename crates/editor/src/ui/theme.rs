@@ -12,6 +12,7 @@ pub struct UITheme {
     pub dark_brand: RgbaTup,
     pub text: RgbaTup,
     pub caret: RgbaTup,
+    pub caret_width: f32, // as a fraction of the width of a glyph
     pub select_highlight: RgbaTup,
     pub tooltip_bg: RgbaTup,
     pub tooltip_text: RgbaTup,
@@ -25,6 +26,7 @@ impl Default for UITheme {
             dark_brand: DARK_BRAND_COL,
             text: gr_colors::WHITE,
             caret: gr_colors::WHITE,
+            caret_width: 1.0 / 6.0,
             select_highlight: from_hsba(240, 55, 100, 0.3),
             tooltip_bg: from_hsb(240, 60, 50),
             tooltip_text: gr_colors::WHITE,
@@ -32,3 +34,22 @@ impl Default for UITheme {
         }
     }
 }
+
+impl UITheme {
+    // for people who need a lot of contrast to read the screen, see EdTheme::high_contrast
+    pub fn high_contrast() -> Self {
+        let yellow = from_hsb(60, 100, 100);
+
+        Self {
+            light_brand: yellow,
+            dark_brand: from_hsb(240, 100, 60),
+            text: gr_colors::WHITE,
+            caret: yellow,
+            caret_width: 1.0 / 3.0,
+            select_highlight: from_hsba(60, 100, 100, 0.4),
+            tooltip_bg: gr_colors::BLACK,
+            tooltip_text: yellow,
+            default_font_size: 30.0,
+        }
+    }
+}
@@ -0,0 +1,568 @@
+app "c-glue"
+    packages { pf: "../platform/main.roc" }
+    imports [pf.Types.{ Types }, pf.Shape.{ RocFn }, pf.File.{ File }, pf.TypeId.{ TypeId }, pf.Target.{ Architecture }]
+    provides [makeGlue] to pf
+
+## Generates a single C header, roc_app.h, for hosts written in C, C++, Zig or any other
+## language that can include C declarations. The declarations of every target architecture
+## are in the same file, behind an `#if` on the predefined macro of that architecture.
+makeGlue : List Types -> Result (List File) Str
+makeGlue = \typesByArch ->
+    archSections =
+        typesByArch
+        |> List.mapWithIndex \types, index ->
+            directive = if index == 0 then "#if" else "#elif"
+            macro = archMacro (Types.target types).architecture
+            declarations = generateTypes types
+
+            """
+            \(directive) defined(\(macro))
+
+            \(declarations)
+            """
+        |> Str.joinWith ""
+
+    content =
+        """
+        \(fileHeader)
+        \(archSections)
+        #else
+        #error "roc glue did not generate declarations for this target architecture"
+        #endif
+
+        #endif // ROC_APP_H
+
+        """
+
+    Ok [{ name: "roc_app.h", content }]
+
+generateTypes : Types -> Str
+generateTypes = \types ->
+    # Recursive tag unions are pointers, so they are declared first.
+    # That way the payloads of their tags can refer to them.
+    pointers =
+        Types.walkShapes types "" \buf, type, _ ->
+            when type is
+                TagUnion (Recursive { name }) -> generatePointerTagUnion buf name
+                TagUnion (NullableWrapped { name }) -> generatePointerTagUnion buf name
+                TagUnion (NullableUnwrapped { name }) -> generatePointerTagUnion buf name
+                TagUnion (NonNullableUnwrapped { name }) -> generatePointerTagUnion buf name
+                _ -> buf
+
+    # The glue types are added after the types they depend on,
+    # so walking them in order also declares them in an order C accepts.
+    declarations =
+        Types.walkShapes types pointers \buf, type, id ->
+            when type is
+                Struct { name, fields } ->
+                    generateStruct buf types name (Types.size types id) (structFieldList fields)
+
+                TagUnionPayload { name, fields } ->
+                    generateStruct buf types name (Types.size types id) (payloadFieldList fields)
+
+                TagUnion (Enumeration { name, tags, size }) ->
+                    generateEnumeration buf name tags size
+
+                TagUnion (NonRecursive { name, tags, discriminantSize, discriminantOffset }) ->
+                    generateNonRecursiveTagUnion buf types id name tags discriminantSize discriminantOffset
+
+                TagUnion (SingleTagStruct { name, payload }) ->
+                    generateStruct buf types name (Types.size types id) (singleTagPayloadFieldList payload)
+
+                RocResult ok err ->
+                    generateResult buf types id ok err
+
+                RocDict _ _ ->
+                    generateOpaque buf types id
+
+                RocSet _ ->
+                    generateOpaque buf types id
+
+                Function rocFn ->
+                    if rocFn.isToplevel then
+                        buf
+                    else
+                        generateFunction buf types rocFn
+
+                TagUnion _
+                | RecursivePointer _
+                | Unit
+                | Unsized
+                | EmptyTagUnion
+                | Num _
+                | Bool
+                | RocStr
+                | RocList _
+                | RocBox _ ->
+                    # These are either declared above, or once for all architectures in the file header.
+                    buf
+
+    generateEntryPoints declarations types
+
+generateEntryPoints : Str, Types -> Str
+generateEntryPoints = \buf, types ->
+    List.walk (Types.entryPoints types) buf \accum, T name id -> generateEntryPoint accum types name id
+
+generateEntryPoint : Str, Types, Str, TypeId -> Str
+generateEntryPoint = \buf, types, name, id ->
+    { args, ret } =
+        when Types.shape types id is
+            Function rocFn -> { args: List.keepIf rocFn.args \argId -> Types.size types argId > 0, ret: rocFn.ret }
+            _ -> { args: [], ret: id }
+
+    retType = typeName types ret
+    externName = "roc__\(name)_1_exposed_generic"
+
+    declaredArgs =
+        args
+        |> List.mapWithIndex \argId, i ->
+            type = typeName types argId
+            c = Num.toStr i
+            "\(type) arg\(c)"
+
+    parameters =
+        declaredArgs
+        |> List.map \arg -> ", \(arg)"
+        |> Str.joinWith ""
+
+    publicParameters = declaredArgs |> Str.joinWith ", " |> withVoid
+
+    arguments =
+        args
+        |> List.mapWithIndex \_, i ->
+            c = Num.toStr i
+            ", arg\(c)"
+        |> Str.joinWith ""
+
+    if Types.size types ret == 0 then
+        """
+        \(buf)
+        void \(externName)(void* ret\(parameters));
+
+        static inline void roc_\(name)(\(publicParameters)) {
+            \(externName)(NULL\(arguments));
+        }
+
+        """
+    else
+        """
+        \(buf)
+        void \(externName)(\(retType)* ret\(parameters));
+
+        static inline \(retType) roc_\(name)(\(publicParameters)) {
+            \(retType) ret;
+            \(externName)(&ret\(arguments));
+            return ret;
+        }
+
+        """
+
+## The caller of a closure that Roc passes to the host. Like the Rust glue, the host calls
+## `externName` with pointers to the arguments, the closure data, and the output.
+generateFunction : Str, Types, RocFn -> Str
+generateFunction = \buf, types, rocFn ->
+    name = escapeKW rocFn.functionName
+    retType = typeName types rocFn.ret
+
+    closureStruct =
+        generateStruct "" types name (Types.size types rocFn.lambdaSet) [{ name: "closure_data", id: rocFn.lambdaSet }]
+
+    parameters =
+        rocFn.args
+        |> List.keepIf \argId -> Types.size types argId > 0
+        |> List.mapWithIndex \argId, i ->
+            type = typeName types argId
+            c = Num.toStr i
+            "const \(type)* arg\(c), "
+        |> Str.joinWith ""
+
+    output = if Types.size types rocFn.ret == 0 then "void* output" else "\(retType)* output"
+
+    """
+    \(buf)\(closureStruct)void \(rocFn.externName)(\(parameters)uint8_t* closure_data, \(output));
+
+    """
+
+## C decides the offset of every field on its own. The fields are still padded explicitly,
+## so the header shows the exact layout that Roc uses and C can't add padding anywhere else.
+generateStruct : Str, Types, Str, U32, List { name : Str, id : TypeId } -> Str
+generateStruct = \buf, types, name, size, fields ->
+    body =
+        fields
+        |> List.walk { content: "", offset: 0, paddingIndex: 0 } \state, field ->
+            fieldSize = Types.size types field.id
+
+            if fieldSize == 0 then
+                # C has no zero-sized types, and these take no space in Roc either
+                state
+            else
+                fieldOffset = roundUpToAlignment state.offset (Types.alignment types field.id)
+                type = typeName types field.id
+                fieldName = escapeKW field.name
+                padded = addPadding state fieldOffset
+
+                { padded & content: Str.concat padded.content "\(indent)\(type) \(fieldName);\n", offset: fieldOffset + fieldSize }
+        |> addPadding size
+        |> .content
+
+    generateTypedefStruct buf name body
+
+generateNonRecursiveTagUnion = \buf, types, id, name, tags, discriminantSize, discriminantOffset ->
+    escapedName = escapeKW name
+    payloads =
+        List.walk tags [] \accum, { name: tagName, payload } ->
+            when payload is
+                Some payloadId if Types.size types payloadId > 0 ->
+                    List.append accum { name: tagName, id: payloadId }
+
+                _ ->
+                    accum
+
+    discriminantType = unsignedIntType discriminantSize
+
+    body =
+        { content: generateUnion types payloads, offset: unionSize types payloads, paddingIndex: 0 }
+        |> addPadding discriminantOffset
+        |> \state -> { state & content: Str.concat state.content "\(indent)\(discriminantType) discriminant;\n", offset: discriminantOffset + discriminantSize }
+        |> addPadding (Types.size types id)
+        |> .content
+
+    buf
+    |> generateDiscriminant escapedName (List.map tags .name)
+    |> generateTypedefStruct escapedName body
+
+generateResult = \buf, types, id, ok, err ->
+    payloads =
+        [{ name: "ok", id: ok }, { name: "err", id: err }]
+        |> List.keepIf \{ id: payloadId } -> Types.size types payloadId > 0
+
+    # Like any other tag union, the tags are sorted by name, so Err is 0 and Ok is 1
+    discriminantOffset = unionSize types payloads
+
+    body =
+        { content: generateUnion types payloads, offset: discriminantOffset, paddingIndex: 0 }
+        |> \state -> { state & content: Str.concat state.content "\(indent)uint8_t is_ok;\n", offset: discriminantOffset + 1 }
+        |> addPadding (Types.size types id)
+        |> .content
+
+    generateTypedefStruct buf (typeName types id) body
+
+## Roc implements dictionaries and sets itself, the host can only pass them around.
+generateOpaque = \buf, types, id ->
+    name = typeName types id
+    alignment = Num.toStr (Types.alignment types id)
+    size = Num.toStr (Types.size types id)
+
+    generateTypedefStruct buf name "\(indent)_Alignas(\(alignment)) uint8_t bytes[\(size)];\n"
+
+generatePointerTagUnion = \buf, name ->
+    escapedName = escapeKW name
+
+    """
+    \(buf)// A pointer to a heap-allocated \(escapedName). Some tags may be stored in the low bits of the pointer,
+    // and one tag may be represented by a null pointer.
+    typedef struct \(escapedName) {
+        void* pointer;
+    } \(escapedName);
+
+
+    """
+
+generateEnumeration = \buf, name, tags, size ->
+    escapedName = escapeKW name
+    type = unsignedIntType size
+
+    buf
+    |> generateDiscriminant escapedName tags
+    |> Str.concat "typedef \(type) \(escapedName);\n\n"
+
+generateDiscriminant = \buf, name, tags ->
+    constants =
+        tags
+        |> List.mapWithIndex \tag, index ->
+            indexStr = Num.toStr index
+
+            "\(indent)\(name)_\(tag) = \(indexStr),\n"
+        |> Str.joinWith ""
+
+    Str.concat buf "enum \(name)_Discriminant {\n\(constants)};\n\n"
+
+generateUnion = \types, payloads ->
+    if List.isEmpty payloads then
+        ""
+    else
+        members =
+            payloads
+            |> List.map \{ name, id } ->
+                type = typeName types id
+                escapedName = escapeKW name
+
+                "\(indent)\(indent)\(type) \(escapedName);\n"
+            |> Str.joinWith ""
+
+        "\(indent)union {\n\(members)\(indent)} payload;\n"
+
+## The size C gives a union of these payloads
+unionSize = \types, payloads ->
+    { size, alignment } =
+        List.walk payloads { size: 0, alignment: 1 } \state, { id } ->
+            { size: maxU32 state.size (Types.size types id), alignment: maxU32 state.alignment (Types.alignment types id) }
+
+    roundUpToAlignment size alignment
+
+generateTypedefStruct = \buf, name, body ->
+    # C structs need at least one field
+    fields = if Str.isEmpty body then "\(indent)uint8_t _unused;\n" else body
+
+    Str.concat buf "typedef struct \(name) {\n\(fields)} \(name);\n\n"
+
+addPadding = \state, targetOffset ->
+    if targetOffset > state.offset then
+        bytes = Num.toStr (targetOffset - state.offset)
+        index = Num.toStr state.paddingIndex
+
+        {
+            content: Str.concat state.content "\(indent)uint8_t _padding\(index)[\(bytes)];\n",
+            offset: targetOffset,
+            paddingIndex: state.paddingIndex + 1,
+        }
+    else
+        state
+
+structFieldList = \structFields ->
+    when structFields is
+        HasNoClosure fields -> fields
+        HasClosure fields -> List.map fields \{ name, id } -> { name, id }
+
+structFieldListWithPrefix = \structFields ->
+    # Tag union payloads have numbered fields, so we prefix them
+    # with an "f" because C doesn't allow struct fields to be numbers.
+    structFieldList structFields
+    |> List.map \{ name, id } -> { name: "f\(name)", id }
+
+payloadFieldList = structFieldListWithPrefix
+
+singleTagPayloadFieldList = \payload ->
+    ids =
+        when payload is
+            HasNoClosure fields -> List.map fields .id
+            HasClosure fields -> List.map fields .id
+
+    List.mapWithIndex ids \id, index ->
+        indexStr = Num.toStr index
+
+        { name: "f\(indexStr)", id }
+
+typeName = \types, id ->
+    when Types.shape types id is
+        Unit -> "void"
+        Unsized -> "RocList"
+        EmptyTagUnion -> "void"
+        RocStr -> "RocStr"
+        Bool -> "bool"
+        Num U8 -> "uint8_t"
+        Num U16 -> "uint16_t"
+        Num U32 -> "uint32_t"
+        Num U64 -> "uint64_t"
+        Num U128 -> "__uint128_t"
+        Num I8 -> "int8_t"
+        Num I16 -> "int16_t"
+        Num I32 -> "int32_t"
+        Num I64 -> "int64_t"
+        Num I128 -> "__int128_t"
+        Num F32 -> "float"
+        Num F64 -> "double"
+        Num Dec -> "RocDec"
+        RocDict key value ->
+            keyName = typeName types key
+            valueName = typeName types value
+
+            "RocDict_\(keyName)_\(valueName)"
+
+        RocSet elem ->
+            elemName = typeName types elem
+
+            "RocSet_\(elemName)"
+
+        RocList _ -> "RocList"
+        RocBox _ -> "RocBox"
+        RocResult ok err ->
+            okName = typeName types ok
+            errName = typeName types err
+
+            "RocResult_\(okName)_\(errName)"
+
+        RecursivePointer content ->
+            typeName types content
+
+        Struct { name } -> escapeKW name
+        TagUnionPayload { name } -> escapeKW name
+        TagUnion (NonRecursive { name }) -> escapeKW name
+        TagUnion (Recursive { name }) -> escapeKW name
+        TagUnion (Enumeration { name }) -> escapeKW name
+        TagUnion (NullableWrapped { name }) -> escapeKW name
+        TagUnion (NullableUnwrapped { name }) -> escapeKW name
+        TagUnion (NonNullableUnwrapped { name }) -> escapeKW name
+        TagUnion (SingleTagStruct { name }) -> escapeKW name
+        Function { functionName } -> escapeKW functionName
+
+unsignedIntType = \size ->
+    when size is
+        1 -> "uint8_t"
+        2 -> "uint16_t"
+        4 -> "uint32_t"
+        _ -> "uint64_t"
+
+withVoid = \parameters ->
+    if Str.isEmpty parameters then "void" else parameters
+
+maxU32 : U32, U32 -> U32
+maxU32 = \a, b -> if a > b then a else b
+
+roundUpToAlignment = \width, alignment ->
+    when alignment is
+        0 -> width
+        1 -> width
+        _ ->
+            if width % alignment > 0 then
+                width + alignment - (width % alignment)
+            else
+                width
+
+archMacro : Architecture -> Str
+archMacro = \arch ->
+    when arch is
+        Aarch32 ->
+            "__arm__"
+
+        Aarch64 ->
+            "__aarch64__"
+
+        Wasm32 ->
+            "__wasm32__"
+
+        X86x32 ->
+            "__i386__"
+
+        X86x64 ->
+            "__x86_64__"
+
+fileHeader =
+    """
+    // ⚠️ GENERATED CODE ⚠️ - this entire file was generated by the `roc glue` CLI command
+
+    #ifndef ROC_APP_H
+    #define ROC_APP_H
+
+    #include <stdbool.h>
+    #include <stddef.h>
+    #include <stdint.h>
+
+    // The host implements these. Roc uses them to manage memory and to report a crash.
+    void* roc_alloc(size_t size, uint32_t alignment);
+    void* roc_realloc(void* ptr, size_t new_size, size_t old_size, uint32_t alignment);
+    void roc_dealloc(void* ptr, uint32_t alignment);
+    void roc_panic(void* msg, uint32_t tag_id);
+    void* roc_memcpy(void* dest, const void* src, size_t n);
+    void* roc_memset(void* str, int c, size_t n);
+
+    typedef struct RocStr {
+        uint8_t* bytes;
+        size_t len;
+        size_t capacity;
+    } RocStr;
+
+    // A string of at most 3 words is stored inline. Then the highest bit of the capacity is set.
+    static inline bool roc_str_is_small(RocStr str) {
+        return (intptr_t)str.capacity < 0;
+    }
+
+    typedef struct RocList {
+        void* elements;
+        size_t len;
+        size_t capacity;
+    } RocList;
+
+    typedef void* RocBox;
+
+    typedef struct RocDec {
+        __int128_t value;
+    } RocDec;
+
+    // The refcount of a heap allocation is stored in the word right before its data.
+    // A refcount of zero means the data is static, and is never freed.
+    #define ROC_REFCOUNT_ONE INTPTR_MIN
+
+    static inline intptr_t* roc_refcount_ptr(void* data) {
+        return ((intptr_t*)data) - 1;
+    }
+
+    static inline void roc_incref(void* data) {
+        intptr_t* refcount = roc_refcount_ptr(data);
+
+        if (*refcount != 0) {
+            *refcount += 1;
+        }
+    }
+
+    // `alignment` is the alignment of the elements, it decides where the allocation starts.
+    static inline void roc_decref(void* data, uint32_t alignment) {
+        intptr_t* refcount = roc_refcount_ptr(data);
+
+        if (*refcount == ROC_REFCOUNT_ONE) {
+            size_t extra_bytes = alignment > sizeof(size_t) ? alignment : sizeof(size_t);
+            roc_dealloc((uint8_t*)data - extra_bytes, alignment);
+        } else if (*refcount != 0) {
+            *refcount -= 1;
+        }
+    }
+
+    """
+
+indent = "    "
+
+reservedKeywords = Set.fromList [
+    "auto",
+    "bool",
+    "break",
+    "case",
+    "char",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extern",
+    "false",
+    "float",
+    "for",
+    "goto",
+    "if",
+    "inline",
+    "int",
+    "long",
+    "register",
+    "restrict",
+    "return",
+    "short",
+    "signed",
+    "sizeof",
+    "static",
+    "struct",
+    "switch",
+    "true",
+    "typedef",
+    "union",
+    "unsigned",
+    "void",
+    "volatile",
+    "while",
+]
+
+escapeKW = \input ->
+    if Set.contains reservedKeywords input then
+        "\(input)_"
+    else
+        input
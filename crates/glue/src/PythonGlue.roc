@@ -0,0 +1,725 @@
+app "python-glue"
+    packages { pf: "../platform/main.roc" }
+    imports [pf.Types.{ Types }, pf.Shape.{ RocFn }, pf.File.{ File }, pf.TypeId.{ TypeId }, pf.Target.{ Architecture }]
+    provides [makeGlue] to pf
+
+## Generates roc_app.py, a ctypes module that loads the shared library of `roc build --lib`
+## and wraps its exposed functions. Strings, lists and records are converted from and to
+## Python values, every other type is passed around as its ctypes structure.
+makeGlue : List Types -> Result (List File) Str
+makeGlue = \typesByArch ->
+    archSections =
+        typesByArch
+        |> List.keepIf \types -> (Types.target types).architecture != Wasm32
+        |> List.mapWithIndex \types, index ->
+            keyword = if index == 0 then "if" else "elif"
+            arch = archName (Types.target types).architecture
+            declarations = generateTypes types
+
+            """
+            \(keyword) _ARCH == "\(arch)":
+            \(indentLines declarations)
+            """
+        |> Str.joinWith ""
+
+    content =
+        """
+        \(fileHeader)
+        \(archSections)else:
+            raise ImportError("roc glue did not generate bindings for the architecture " + platform.machine())
+
+        """
+
+    Ok [{ name: "roc_app.py", content }]
+
+generateTypes : Types -> Str
+generateTypes = \types ->
+    # Recursive tag unions are pointers, so they are declared first.
+    # That way the payloads of their tags can refer to them.
+    pointers =
+        Types.walkShapes types "" \buf, type, _ ->
+            when type is
+                TagUnion (Recursive { name }) -> generatePointerTagUnion buf name
+                TagUnion (NullableWrapped { name }) -> generatePointerTagUnion buf name
+                TagUnion (NullableUnwrapped { name }) -> generatePointerTagUnion buf name
+                TagUnion (NonNullableUnwrapped { name }) -> generatePointerTagUnion buf name
+                _ -> buf
+
+    # The glue types are added after the types they depend on, so walking them
+    # in order defines every class before another class refers to it.
+    declarations =
+        Types.walkShapes types pointers \buf, type, id ->
+            when type is
+                Struct { name, fields } ->
+                    generateRecord buf types name (Types.size types id) (structFieldList fields)
+
+                TagUnionPayload { name, fields } ->
+                    fieldsWithPrefix = structFieldListWithPrefix fields
+
+                    generateStruct buf types name (Types.size types id) fieldsWithPrefix ""
+
+                TagUnion (Enumeration { name, tags }) ->
+                    generateEnumeration buf name tags
+
+                TagUnion (NonRecursive { name, tags, discriminantSize, discriminantOffset }) ->
+                    generateNonRecursiveTagUnion buf types id name tags discriminantSize discriminantOffset
+
+                TagUnion (SingleTagStruct { name, payload }) ->
+                    generateStruct buf types name (Types.size types id) (singleTagPayloadFieldList payload) ""
+
+                RocResult ok err ->
+                    generateResult buf types id ok err
+
+                RocDict _ _ ->
+                    generateOpaque buf types id
+
+                RocSet _ ->
+                    generateOpaque buf types id
+
+                Function rocFn ->
+                    if rocFn.isToplevel then
+                        buf
+                    else
+                        generateFunction buf types rocFn
+
+                TagUnion _
+                | RecursivePointer _
+                | Unit
+                | Unsized
+                | EmptyTagUnion
+                | Num _
+                | Bool
+                | RocStr
+                | RocList _
+                | RocBox _ ->
+                    # These are either defined above, or once for all architectures in the file header.
+                    buf
+
+    List.walk (Types.entryPoints types) declarations \accum, T name id -> generateEntryPoint accum types name id
+
+generateEntryPoint : Str, Types, Str, TypeId -> Str
+generateEntryPoint = \buf, types, name, id ->
+    { args, ret } =
+        when Types.shape types id is
+            Function rocFn -> { args: List.keepIf rocFn.args \argId -> Types.size types argId > 0, ret: rocFn.ret }
+            _ -> { args: [], ret: id }
+
+    parameters = argNames args |> Str.joinWith ", "
+
+    argTypes =
+        args
+        |> List.map \argId -> ", \(typeName types argId)"
+        |> Str.joinWith ""
+
+    arguments =
+        args
+        |> List.mapWithIndex \argId, i ->
+            c = Num.toStr i
+            ", \(toRoc types argId "arg\(c)" 0)"
+        |> Str.joinWith ""
+
+    externName = "roc__\(name)_1_exposed_generic"
+
+    { retPointerType, retArgument, retValue } =
+        if Types.size types ret == 0 then
+            { retPointerType: "ctypes.c_void_p", retArgument: "None", retValue: "None" }
+        else
+            retType = typeName types ret
+
+            { retPointerType: "ctypes.POINTER(\(retType))", retArgument: "ctypes.byref(ret)", retValue: fromRoc types ret "ret" 0 }
+
+    retDeclaration =
+        if Types.size types ret == 0 then
+            ""
+        else
+            retType = typeName types ret
+
+            "\(indent)ret = \(retType)()\n"
+
+    """
+    \(buf)def \(escapeKW name)(\(parameters)):
+        keep_alive = []
+        function = _library().\(externName)
+        function.argtypes = [\(retPointerType)\(argTypes)]
+        function.restype = None
+    \(retDeclaration)    function(\(retArgument)\(arguments))
+        return \(retValue)
+
+
+    """
+
+## The closure data of a function that Roc passes to the host. `call` runs the closure through
+## its caller, which takes pointers to the arguments, the closure data, and the output.
+generateFunction : Str, Types, RocFn -> Str
+generateFunction = \buf, types, rocFn ->
+    args = List.keepIf rocFn.args \argId -> Types.size types argId > 0
+    parameters = argNames args |> List.map \arg -> ", \(arg)" |> Str.joinWith ""
+
+    arguments =
+        args
+        |> List.mapWithIndex \argId, i ->
+            c = Num.toStr i
+            type = typeName types argId
+
+            "_by_ref(\(type), \(toRoc types argId "arg\(c)" 0)), "
+        |> Str.joinWith ""
+
+    { outputDeclaration, outputArgument, outputValue } =
+        if Types.size types rocFn.ret == 0 then
+            { outputDeclaration: "", outputArgument: "None", outputValue: "None" }
+        else
+            retType = typeName types rocFn.ret
+
+            {
+                outputDeclaration: "\(indent)\(indent)output = \(retType)()\n",
+                outputArgument: "ctypes.byref(output)",
+                outputValue: fromRoc types rocFn.ret "output" 0,
+            }
+
+    methods =
+        """
+            def call(self\(parameters)):
+                keep_alive = []
+        \(outputDeclaration)        _library().\(rocFn.externName)(\(arguments)ctypes.byref(self), \(outputArgument))
+                return \(outputValue)
+
+        """
+
+    fields = [{ name: "closure_data", id: rocFn.lambdaSet }]
+
+    generateStruct buf types rocFn.functionName (Types.size types rocFn.lambdaSet) fields methods
+
+## A record is converted from a dict with the same field names, and back to one.
+generateRecord = \buf, types, name, size, fields ->
+    nonEmptyFields = List.keepIf fields \{ id } -> Types.size types id > 0
+
+    stores =
+        nonEmptyFields
+        |> List.map \{ name: fieldName, id } ->
+            value = toRoc types id "value[\"\(fieldName)\"]" 0
+
+            "\(indent)\(indent)result.\(escapeKW fieldName) = \(value)\n"
+        |> Str.joinWith ""
+
+    entries =
+        nonEmptyFields
+        |> List.map \{ name: fieldName, id } ->
+            value = fromRoc types id "self.\(escapeKW fieldName)" 0
+
+            "\(indent)\(indent)\(indent)\"\(fieldName)\": \(value),\n"
+        |> Str.joinWith ""
+
+    methods =
+        """
+            @classmethod
+            def from_py(cls, value, keep_alive):
+                result = cls()
+        \(stores)        return result
+
+            def to_py(self):
+                return {
+        \(entries)        }
+
+        """
+
+    generateStruct buf types name size fields methods
+
+## ctypes lays out the fields like C does. The fields are still padded explicitly,
+## so the class shows the exact layout that Roc uses.
+generateStruct : Str, Types, Str, U32, List { name : Str, id : TypeId }, Str -> Str
+generateStruct = \buf, types, name, size, fields, methods ->
+    body =
+        fields
+        |> List.walk { content: "", offset: 0, paddingIndex: 0 } \state, field ->
+            fieldSize = Types.size types field.id
+
+            if fieldSize == 0 then
+                # ctypes has no zero-sized types, and these take no space in Roc either
+                state
+            else
+                fieldOffset = roundUpToAlignment state.offset (Types.alignment types field.id)
+                type = typeName types field.id
+                fieldName = escapeKW field.name
+                padded = addPadding state fieldOffset
+
+                { padded & content: Str.concat padded.content "\(indent)\(indent)(\"\(fieldName)\", \(type)),\n", offset: fieldOffset + fieldSize }
+        |> addPadding size
+        |> .content
+
+    generateClass buf (escapeKW name) "" body methods
+
+generateNonRecursiveTagUnion = \buf, types, id, name, tags, discriminantSize, discriminantOffset ->
+    payloads =
+        List.walk tags [] \accum, { name: tagName, payload } ->
+            when payload is
+                Some payloadId if Types.size types payloadId > 0 ->
+                    List.append accum { name: tagName, id: payloadId }
+
+                _ ->
+                    accum
+
+    discriminantType = unsignedIntType discriminantSize
+
+    constants =
+        tags
+        |> List.mapWithIndex \{ name: tagName }, index ->
+            "\(indent)\(escapeKW tagName) = \(Num.toStr index)\n"
+        |> Str.joinWith ""
+
+    fields =
+        { content: payloadField payloads, offset: unionSize types payloads, paddingIndex: 0 }
+        |> addPadding discriminantOffset
+        |> \state -> { state & content: Str.concat state.content "\(indent)\(indent)(\"discriminant\", \(discriminantType)),\n", offset: discriminantOffset + discriminantSize }
+        |> addPadding (Types.size types id)
+        |> .content
+
+    generateClass buf (escapeKW name) (Str.concat (generateUnion types payloads) constants) fields ""
+
+generateResult = \buf, types, id, ok, err ->
+    payloads =
+        [{ name: "ok", id: ok }, { name: "err", id: err }]
+        |> List.keepIf \{ id: payloadId } -> Types.size types payloadId > 0
+
+    # Like any other tag union, the tags are sorted by name, so Err is 0 and Ok is 1
+    discriminantOffset = unionSize types payloads
+
+    fields =
+        { content: payloadField payloads, offset: discriminantOffset, paddingIndex: 0 }
+        |> \state -> { state & content: Str.concat state.content "\(indent)\(indent)(\"is_ok\", ctypes.c_bool),\n", offset: discriminantOffset + 1 }
+        |> addPadding (Types.size types id)
+        |> .content
+
+    generateClass buf (typeName types id) (generateUnion types payloads) fields ""
+
+## Roc implements dictionaries and sets itself, Python can only pass them around.
+generateOpaque = \buf, types, id ->
+    size = Num.toStr (Types.size types id)
+
+    generateClass buf (typeName types id) "" "\(indent)\(indent)(\"bytes\", ctypes.c_uint8 * \(size)),\n" ""
+
+generatePointerTagUnion = \buf, name ->
+    escapedName = escapeKW name
+
+    """
+    \(buf)# A pointer to a heap-allocated \(escapedName). Some tags may be stored in the low bits of the pointer,
+    # and one tag may be represented by a null pointer.
+    class \(escapedName)(ctypes.Structure):
+        _fields_ = [
+            ("pointer", ctypes.c_void_p),
+        ]
+
+
+    """
+
+generateEnumeration = \buf, name, tags ->
+    constants =
+        tags
+        |> List.mapWithIndex \tag, index ->
+            "\(indent)\(escapeKW tag) = \(Num.toStr index)\n"
+        |> Str.joinWith ""
+
+    Str.concat buf "class \(escapeKW name)(enum.IntEnum):\n\(constants)\n\n"
+
+generateUnion = \types, payloads ->
+    if List.isEmpty payloads then
+        ""
+    else
+        members =
+            payloads
+            |> List.map \{ name, id } ->
+                "\(indent)\(indent)\(indent)(\"\(escapeKW name)\", \(typeName types id)),\n"
+            |> Str.joinWith ""
+
+        "\(indent)class _Payload(ctypes.Union):\n\(indent)\(indent)_fields_ = [\n\(members)\(indent)\(indent)]\n\n"
+
+payloadField = \payloads ->
+    if List.isEmpty payloads then
+        ""
+    else
+        "\(indent)\(indent)(\"payload\", _Payload),\n"
+
+## The size ctypes gives a union of these payloads
+unionSize = \types, payloads ->
+    { size, alignment } =
+        List.walk payloads { size: 0, alignment: 1 } \state, { id } ->
+            { size: maxU32 state.size (Types.size types id), alignment: maxU32 state.alignment (Types.alignment types id) }
+
+    roundUpToAlignment size alignment
+
+generateClass = \buf, name, declarations, fields, methods ->
+    """
+    \(buf)class \(name)(ctypes.Structure):
+    \(declarations)    _fields_ = [
+    \(fields)    ]
+    \(methods)
+
+    """
+
+addPadding = \state, targetOffset ->
+    if targetOffset > state.offset then
+        bytes = Num.toStr (targetOffset - state.offset)
+        index = Num.toStr state.paddingIndex
+
+        {
+            content: Str.concat state.content "\(indent)\(indent)(\"_padding\(index)\", ctypes.c_uint8 * \(bytes)),\n",
+            offset: targetOffset,
+            paddingIndex: state.paddingIndex + 1,
+        }
+    else
+        state
+
+argNames = \args ->
+    List.mapWithIndex args \_, i -> "arg\(Num.toStr i)"
+
+structFieldList = \structFields ->
+    when structFields is
+        HasNoClosure fields -> fields
+        HasClosure fields -> List.map fields \{ name, id } -> { name, id }
+
+structFieldListWithPrefix = \structFields ->
+    # Tag union payloads have numbered fields, so we prefix them
+    # with an "f" because Python attributes can't be numbers.
+    structFieldList structFields
+    |> List.map \{ name, id } -> { name: "f\(name)", id }
+
+singleTagPayloadFieldList = \payload ->
+    ids =
+        when payload is
+            HasNoClosure fields -> List.map fields .id
+            HasClosure fields -> List.map fields .id
+
+    List.mapWithIndex ids \id, index -> { name: "f\(Num.toStr index)", id }
+
+## The Python expression that converts `value` to the ctypes value Roc expects.
+## `depth` keeps the variables of nested list comprehensions apart.
+toRoc : Types, TypeId, Str, U32 -> Str
+toRoc = \types, id, value, depth ->
+    when Types.shape types id is
+        RocStr -> "RocStr.from_py(\(value), keep_alive)"
+        Num U128 | Num I128 | Num Dec -> "\(typeName types id).from_py(\(value))"
+        TagUnion (Enumeration _) -> "int(\(value))"
+        Struct { name } -> "\(escapeKW name).from_py(\(value), keep_alive)"
+        RocList elem ->
+            element = "x\(Num.toStr depth)"
+            elements = toRoc types elem element (depth + 1)
+
+            "RocList.from_py(\(typeName types elem), [\(elements) for \(element) in \(value)], keep_alive)"
+
+        _ -> value
+
+## The Python expression that converts the ctypes `value` Roc returned to a Python value.
+fromRoc : Types, TypeId, Str, U32 -> Str
+fromRoc = \types, id, value, depth ->
+    when Types.shape types id is
+        RocStr | Num U128 | Num I128 | Num Dec | Struct _ -> "\(value).to_py()"
+        TagUnion (Enumeration { name }) -> "\(escapeKW name)(\(value))"
+        RocList elem ->
+            element = "x\(Num.toStr depth)"
+            elements = fromRoc types elem element (depth + 1)
+
+            "[\(elements) for \(element) in \(value).to_py(\(typeName types elem))]"
+
+        _ -> value
+
+typeName = \types, id ->
+    when Types.shape types id is
+        Unit -> "None"
+        Unsized -> "RocList"
+        EmptyTagUnion -> "None"
+        RocStr -> "RocStr"
+        Bool -> "ctypes.c_bool"
+        Num U8 -> "ctypes.c_uint8"
+        Num U16 -> "ctypes.c_uint16"
+        Num U32 -> "ctypes.c_uint32"
+        Num U64 -> "ctypes.c_uint64"
+        Num U128 -> "RocU128"
+        Num I8 -> "ctypes.c_int8"
+        Num I16 -> "ctypes.c_int16"
+        Num I32 -> "ctypes.c_int32"
+        Num I64 -> "ctypes.c_int64"
+        Num I128 -> "RocI128"
+        Num F32 -> "ctypes.c_float"
+        Num F64 -> "ctypes.c_double"
+        Num Dec -> "RocDec"
+        RocDict key value -> "RocDict_\(shapeName types key)_\(shapeName types value)"
+        RocSet elem -> "RocSet_\(shapeName types elem)"
+        RocList _ -> "RocList"
+        RocBox _ -> "ctypes.c_void_p"
+        RocResult ok err -> "RocResult_\(shapeName types ok)_\(shapeName types err)"
+        RecursivePointer content -> typeName types content
+        Struct { name } -> escapeKW name
+        TagUnionPayload { name } -> escapeKW name
+        TagUnion (NonRecursive { name }) -> escapeKW name
+        TagUnion (Recursive { name }) -> escapeKW name
+        TagUnion (Enumeration { size }) -> unsignedIntType size
+        TagUnion (NullableWrapped { name }) -> escapeKW name
+        TagUnion (NullableUnwrapped { name }) -> escapeKW name
+        TagUnion (NonNullableUnwrapped { name }) -> escapeKW name
+        TagUnion (SingleTagStruct { name }) -> escapeKW name
+        Function { functionName } -> escapeKW functionName
+
+## The part of a class name that stands for a type argument, e.g. the I64 in RocResult_I64_Str
+shapeName = \types, id ->
+    when Types.shape types id is
+        Unit -> "Unit"
+        EmptyTagUnion -> "Never"
+        RocStr -> "Str"
+        Bool -> "Bool"
+        Num U8 -> "U8"
+        Num U16 -> "U16"
+        Num U32 -> "U32"
+        Num U64 -> "U64"
+        Num U128 -> "U128"
+        Num I8 -> "I8"
+        Num I16 -> "I16"
+        Num I32 -> "I32"
+        Num I64 -> "I64"
+        Num I128 -> "I128"
+        Num F32 -> "F32"
+        Num F64 -> "F64"
+        Num Dec -> "Dec"
+        RocList _ | Unsized -> "List"
+        RocBox _ -> "Box"
+        TagUnion (Enumeration { name }) -> escapeKW name
+        _ -> typeName types id
+
+unsignedIntType = \size ->
+    when size is
+        1 -> "ctypes.c_uint8"
+        2 -> "ctypes.c_uint16"
+        4 -> "ctypes.c_uint32"
+        _ -> "ctypes.c_uint64"
+
+indentLines = \code ->
+    code
+    |> Str.split "\n"
+    |> List.map \line -> if Str.isEmpty line then line else Str.concat indent line
+    |> Str.joinWith "\n"
+
+maxU32 : U32, U32 -> U32
+maxU32 = \a, b -> if a > b then a else b
+
+roundUpToAlignment = \width, alignment ->
+    when alignment is
+        0 -> width
+        1 -> width
+        _ ->
+            if width % alignment > 0 then
+                width + alignment - (width % alignment)
+            else
+                width
+
+## The value of `_ARCH` in the generated module, see `fileHeader`
+archName : Architecture -> Str
+archName = \arch ->
+    when arch is
+        Aarch32 ->
+            "arm"
+
+        Aarch64 ->
+            "aarch64"
+
+        Wasm32 ->
+            "wasm32"
+
+        X86x32 ->
+            "x86"
+
+        X86x64 ->
+            "x86_64"
+
+fileHeader =
+    """
+    # ⚠️ GENERATED CODE ⚠️ - this entire file was generated by the `roc glue` CLI command
+
+    # Bindings for a Roc library built with `roc build --lib`. Call `load` with the path
+    # of the shared library before calling any of the exposed functions.
+    #
+    # Values passed to Roc live in Python memory with a refcount of zero, so Roc treats
+    # them as static: it never frees them, and copies them before changing them.
+    # TODO: values returned by Roc are copied into Python objects, but the Roc
+    # allocations are not freed yet.
+
+    import ctypes
+    import decimal
+    import enum
+    import platform
+
+    _ARCH = {
+        "x86_64": "x86_64",
+        "AMD64": "x86_64",
+        "aarch64": "aarch64",
+        "arm64": "aarch64",
+        "armv7l": "arm",
+        "i386": "x86",
+        "i686": "x86",
+        "x86": "x86",
+    }.get(platform.machine())
+
+    _lib = None
+
+
+    def load(path):
+        global _lib
+        _lib = ctypes.CDLL(path)
+
+
+    def _library():
+        if _lib is None:
+            raise RuntimeError("call load with the path of the Roc library first")
+        return _lib
+
+
+    def _by_ref(ctype, value):
+        if not isinstance(value, ctype):
+            value = ctype(value)
+        return ctypes.byref(value)
+
+
+    _WORD = ctypes.sizeof(ctypes.c_size_t)
+    _SEAMLESS_SLICE_BIT = 1 << (8 * _WORD - 1)
+
+
+    # Copies `data` after a refcount of zero. The buffer stays in `keep_alive` until the call into Roc returns.
+    def _static_allocation(data, alignment, keep_alive):
+        offset = max(alignment, _WORD)
+        buffer = ctypes.create_string_buffer(offset + len(data))
+        ctypes.memmove(ctypes.addressof(buffer) + offset, data, len(data))
+        keep_alive.append(buffer)
+        return ctypes.addressof(buffer) + offset
+
+
+    class RocStr(ctypes.Structure):
+        _fields_ = [
+            ("bytes", ctypes.c_void_p),
+            ("len", ctypes.c_size_t),
+            ("capacity", ctypes.c_size_t),
+        ]
+
+        @classmethod
+        def from_py(cls, value, keep_alive):
+            data = value.encode("utf-8")
+            size = ctypes.sizeof(cls)
+            result = cls()
+            if len(data) < size:
+                # A small string is stored inline, its last byte is the length with the highest bit set.
+                raw = (ctypes.c_uint8 * size).from_buffer(result)
+                raw[: len(data)] = data
+                raw[size - 1] = len(data) | 0x80
+            else:
+                result.bytes = _static_allocation(data, 1, keep_alive)
+                result.len = len(data)
+                result.capacity = len(data)
+            return result
+
+        def is_small(self):
+            return ctypes.c_ssize_t(self.capacity).value < 0
+
+        def to_py(self):
+            if self.is_small():
+                raw = bytes(self)
+                return raw[: raw[-1] ^ 0x80].decode("utf-8")
+            return ctypes.string_at(self.bytes, self.len & ~_SEAMLESS_SLICE_BIT).decode("utf-8")
+
+
+    class RocList(ctypes.Structure):
+        _fields_ = [
+            ("elements", ctypes.c_void_p),
+            ("len", ctypes.c_size_t),
+            ("capacity", ctypes.c_size_t),
+        ]
+
+        @classmethod
+        def from_py(cls, element_type, values, keep_alive):
+            result = cls()
+            if values:
+                array = (element_type * len(values))(*values)
+                result.elements = _static_allocation(bytes(array), ctypes.alignment(element_type), keep_alive)
+                result.len = len(values)
+                result.capacity = len(values)
+            return result
+
+        def to_py(self, element_type):
+            if self.len == 0:
+                return []
+            return list((element_type * self.len).from_address(self.elements))
+
+
+    class RocU128(ctypes.Structure):
+        _fields_ = [
+            ("lo", ctypes.c_uint64),
+            ("hi", ctypes.c_uint64),
+        ]
+
+        @classmethod
+        def from_py(cls, value):
+            value &= (1 << 128) - 1
+            return cls(value & ((1 << 64) - 1), value >> 64)
+
+        def to_py(self):
+            return self.lo | (self.hi << 64)
+
+
+    class RocI128(RocU128):
+        def to_py(self):
+            value = super().to_py()
+            return value - (1 << 128) if value >= (1 << 127) else value
+
+
+    # A fixed-point decimal with 18 digits after the point
+    class RocDec(RocI128):
+        @classmethod
+        def from_py(cls, value):
+            return super().from_py(int(decimal.Decimal(value).scaleb(18)))
+
+        def to_py(self):
+            return decimal.Decimal(super().to_py()).scaleb(-18)
+
+
+    """
+
+indent = "    "
+
+reservedKeywords = Set.fromList [
+    "False",
+    "None",
+    "True",
+    "and",
+    "as",
+    "assert",
+    "async",
+    "await",
+    "break",
+    "class",
+    "continue",
+    "def",
+    "del",
+    "elif",
+    "else",
+    "except",
+    "finally",
+    "for",
+    "from",
+    "global",
+    "if",
+    "import",
+    "in",
+    "is",
+    "lambda",
+    "nonlocal",
+    "not",
+    "or",
+    "pass",
+    "raise",
+    "return",
+    "try",
+    "while",
+    "with",
+    "yield",
+]
+
+escapeKW = \input ->
+    if Set.contains reservedKeywords input then
+        "\(input)_"
+    else
+        input
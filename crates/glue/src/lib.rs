@@ -1,7 +1,8 @@
 //! Generates code needed for platform hosts to communicate with Roc apps.
 //! This tool is not necessary for writing a platform in another language,
-//! however, it's a great convenience! Currently supports Rust platforms, and
-//! the plan is to support any language via a plugin model.
+//! however, it's a great convenience! Currently supports Rust platforms,
+//! C headers (see `src/CGlue.roc`) and Python's ctypes (see `src/PythonGlue.roc`),
+//! and the plan is to support any language via a plugin model.
 pub mod enums;
 pub mod load;
 pub mod roc_type;
@@ -11,7 +11,10 @@ use roc_build::{
 };
 use roc_collections::MutMap;
 use roc_gen_llvm::llvm::build::LlvmBackendMode;
-use roc_load::{ExecutionMode, LoadConfig, LoadedModule, LoadingProblem, Threading};
+use roc_load::{
+    CancelToken, ExecutionMode, HotModule, LoadConfig, LoadedModule, LoadingProblem,
+    ProgressReporter, SolveFuel, Threading,
+};
 use roc_mono::ir::{generate_glue_procs, GlueProc, OptLevel};
 use roc_mono::layout::{GlobalLayoutInterner, LayoutCache, LayoutInterner};
 use roc_packaging::cache::{self, RocCacheDir};
@@ -51,6 +54,7 @@ pub fn generate(input_path: &Path, output_path: &Path, spec_path: &Path) -> io::
                 backend: CodeGenBackend::Llvm(LlvmBackendMode::BinaryGlue),
                 opt_level: OptLevel::Development,
                 emit_debug_info: false,
+                stack_bytes: None,
             };
 
             let load_config = standard_load_config(
@@ -351,6 +355,10 @@ pub fn load_types(
             palette: DEFAULT_PALETTE,
             threading,
             exec_mode: ExecutionMode::Check,
+            cancel: CancelToken::default(),
+            progress: ProgressReporter::default(),
+            hot_module: HotModule::default(),
+            solve_fuel: SolveFuel::default(),
         },
     )
     .unwrap_or_else(|problem| match problem {
@@ -6,7 +6,10 @@
 use memmap2::{Mmap, MmapMut};
 use object::Object;
 use roc_error_macros::internal_error;
-use roc_load::{EntryPoint, ExecutionMode, ExposedToHost, LoadConfig, Threading};
+use roc_load::{
+    CancelToken, EntryPoint, ExecutionMode, ExposedToHost, HotModule, LoadConfig, ProgressReporter,
+    SolveFuel, Threading,
+};
 use roc_module::symbol::Interns;
 use roc_packaging::cache::RocCacheDir;
 use roc_reporting::report::{RenderTarget, DEFAULT_PALETTE};
@@ -106,6 +109,10 @@ pub fn generate_stub_lib(
             palette: DEFAULT_PALETTE,
             threading: Threading::AllAvailable,
             exec_mode: ExecutionMode::Executable,
+            cancel: CancelToken::default(),
+            progress: ProgressReporter::default(),
+            hot_module: HotModule::default(),
+            solve_fuel: SolveFuel::default(),
         },
     )
     .unwrap_or_else(|problem| todo!("{:?}", problem));
@@ -1,5 +1,8 @@
 use bumpalo::Bump;
-use roc_load::{ExecutionMode, LoadConfig, LoadMonomorphizedError, Threading};
+use roc_load::{
+    CancelToken, ExecutionMode, HotModule, LoadConfig, LoadMonomorphizedError, ProgressReporter,
+    SolveFuel, Threading,
+};
 use roc_packaging::cache::{self, RocCacheDir};
 use roc_problem::Severity;
 use roc_reporting::report::Palette;
@@ -66,6 +69,10 @@ pub fn compile_to_mono<'a, 'i, I: Iterator<Item = &'i str>>(
             palette,
             threading: Threading::Single,
             exec_mode: ExecutionMode::Executable,
+            cancel: CancelToken::default(),
+            progress: ProgressReporter::default(),
+            hot_module: HotModule::default(),
+            solve_fuel: SolveFuel::default(),
         },
     );
 
@@ -94,7 +94,10 @@ mod test {
     use indoc::indoc;
     use pretty_assertions::assert_eq;
     use roc_gen_llvm::{llvm::build::LlvmBackendMode, run_roc::RocCallResult, run_roc_dylib};
-    use roc_load::{ExecutionMode, LoadConfig, LoadMonomorphizedError, Threading};
+    use roc_load::{
+        CancelToken, ExecutionMode, HotModule, LoadConfig, LoadMonomorphizedError,
+        ProgressReporter, SolveFuel, Threading,
+    };
     use roc_packaging::cache::RocCacheDir;
     use roc_reporting::report::{RenderTarget, DEFAULT_PALETTE};
     use target_lexicon::Triple;
@@ -125,6 +128,10 @@ mod test {
             palette: DEFAULT_PALETTE,
             threading: Threading::Single,
             exec_mode: ExecutionMode::Test,
+            cancel: CancelToken::default(),
+            progress: ProgressReporter::default(),
+            hot_module: HotModule::default(),
+            solve_fuel: SolveFuel::default(),
         };
         let loaded = match roc_load::load_and_monomorphize_from_str(
             arena,
@@ -747,6 +747,8 @@ pub fn expect_mono_module_to_dylib<'a>(
         mode,
         // important! we don't want any procedures to get the C calling convention
         exposed_to_host: MutSet::default(),
+        stack_bytes: None,
+        sources: None,
     };
 
     // Add roc_alloc, roc_realloc, and roc_dealloc, since the repl has no
@@ -2,9 +2,12 @@ use std::path::PathBuf;
 
 use roc_collections::MutMap;
 use roc_module::symbol::{Interns, ModuleId};
-use roc_region::all::LineInfo;
+use roc_region::all::{LineInfo, Region};
 use roc_solve_problem::TypeError;
 
+use crate::error_code;
+use crate::report::{pretty_header, Palette, RenderTarget, Report};
+
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
 pub struct Problems {
     pub fatally_errored: bool,
@@ -51,14 +54,122 @@ impl Problems {
     }
 }
 
+/// The most reports that are printed for a single def, the rest are only counted. One mistake
+/// often causes a cascade of problems in the same def, and only the first few are useful.
+const MAX_REPORTS_PER_DEF: usize = 3;
+
+/// A rendered report, and where it was found
+struct ProblemReport {
+    home: ModuleId,
+    /// The top-level def that the problem is in, if we could tell
+    def: Option<String>,
+    offset: u32,
+    text: String,
+}
+
 pub fn report_problems(
     total_problems: usize,
     sources: &MutMap<ModuleId, (PathBuf, Box<str>)>,
     interns: &Interns,
+    parse_problem_reports: &mut MutMap<ModuleId, String>,
     can_problems: &mut MutMap<ModuleId, Vec<roc_problem::can::Problem>>,
     type_problems: &mut MutMap<ModuleId, Vec<TypeError>>,
 ) -> Problems {
-    use crate::report::{can_problem, type_problem, Report, RocDocAllocator, DEFAULT_PALETTE};
+    use crate::report::DEFAULT_PALETTE;
+    let palette = DEFAULT_PALETTE;
+
+    let reports = collect_reports(
+        total_problems,
+        sources,
+        interns,
+        parse_problem_reports,
+        can_problems,
+        type_problems,
+        RenderTarget::ColorTerminal,
+    );
+
+    // Only print warnings if there are no errors
+    let shown = reports.shown();
+
+    print_reports(shown, &palette);
+
+    // If we printed any problems, print a horizontal rule at the end,
+    // and then clear any ANSI escape codes (e.g. colors) we've used.
+    //
+    // The horizontal rule is nice when running the program right after
+    // compiling it, as it lets you clearly see where the compiler
+    // errors/warnings end and the program output begins.
+    if !shown.is_empty() {
+        println!("{}\u{001B}[0m\n", Report::horizontal_rule(&palette));
+    }
+
+    reports.problems()
+}
+
+/// Like [report_problems], but the reports are returned as text without colors instead of
+/// printed, for tools that show them somewhere else than in a terminal. Like with
+/// [report_problems], the warnings are only returned when there are no errors.
+pub fn problem_reports(
+    total_problems: usize,
+    sources: &MutMap<ModuleId, (PathBuf, Box<str>)>,
+    interns: &Interns,
+    parse_problem_reports: &mut MutMap<ModuleId, String>,
+    can_problems: &mut MutMap<ModuleId, Vec<roc_problem::can::Problem>>,
+    type_problems: &mut MutMap<ModuleId, Vec<TypeError>>,
+) -> (Problems, Vec<String>) {
+    let reports = collect_reports(
+        total_problems,
+        sources,
+        interns,
+        parse_problem_reports,
+        can_problems,
+        type_problems,
+        RenderTarget::Generic,
+    );
+
+    let texts = reports
+        .shown()
+        .iter()
+        .map(|report| report.text.clone())
+        .collect();
+
+    (reports.problems(), texts)
+}
+
+struct CollectedReports {
+    warnings: Vec<ProblemReport>,
+    errors: Vec<ProblemReport>,
+    fatally_errored: bool,
+}
+
+impl CollectedReports {
+    fn shown(&self) -> &[ProblemReport] {
+        if self.errors.is_empty() {
+            &self.warnings
+        } else {
+            &self.errors
+        }
+    }
+
+    fn problems(&self) -> Problems {
+        Problems {
+            fatally_errored: self.fatally_errored,
+            errors: self.errors.len(),
+            warnings: self.warnings.len(),
+        }
+    }
+}
+
+fn collect_reports(
+    total_problems: usize,
+    sources: &MutMap<ModuleId, (PathBuf, Box<str>)>,
+    interns: &Interns,
+    parse_problem_reports: &mut MutMap<ModuleId, String>,
+    can_problems: &mut MutMap<ModuleId, Vec<roc_problem::can::Problem>>,
+    type_problems: &mut MutMap<ModuleId, Vec<TypeError>>,
+    render_target: RenderTarget,
+) -> CollectedReports {
+    use crate::report::{can_problem, type_problem, RocDocAllocator, DEFAULT_PALETTE};
     use roc_problem::Severity::*;
     let palette = DEFAULT_PALETTE;
 
@@ -69,6 +180,19 @@ pub fn report_problems(
     let mut fatally_errored = false;
 
     for (home, (module_path, src)) in sources.iter() {
+        let module_warnings_start = warnings.len();
+        let module_errors_start = errors.len();
+
+        // the module was checked as if it was empty, so this is its only problem
+        if let Some(text) = parse_problem_reports.remove(home) {
+            errors.push(ProblemReport {
+                home: *home,
+                def: None,
+                offset: 0,
+                text,
+            });
+        }
+
         let mut src_lines: Vec<&str> = Vec::new();
 
         src_lines.extend(src.split('\n'));
@@ -81,22 +205,25 @@ pub fn report_problems(
         let problems = can_problems.remove(home).unwrap_or_default();
 
         for problem in problems.into_iter() {
-            let report = can_problem(&alloc, &lines, module_path.clone(), problem);
+            let region = problem.region();
+            let report = with_error_code(can_problem(&alloc, &lines, module_path.clone(), problem));
             let severity = report.severity;
             let mut buf = String::new();
 
-            report.render_color_terminal(&mut buf, &alloc, &palette);
+            report.render(render_target, &mut buf, &alloc, &palette);
+
+            let report = problem_report(*home, src, region, buf);
 
             match severity {
                 Warning => {
-                    warnings.push(buf);
+                    warnings.push(report);
                 }
                 RuntimeError => {
-                    errors.push(buf);
+                    errors.push(report);
                 }
                 Fatal => {
                     fatally_errored = true;
-                    errors.push(buf);
+                    errors.push(report);
                 }
             }
         }
@@ -104,58 +231,159 @@ pub fn report_problems(
         let problems = type_problems.remove(home).unwrap_or_default();
 
         for problem in problems {
+            let region = problem.region();
+
             if let Some(report) = type_problem(&alloc, &lines, module_path.clone(), problem) {
+                let report = with_error_code(report);
                 let severity = report.severity;
                 let mut buf = String::new();
 
-                report.render_color_terminal(&mut buf, &alloc, &palette);
+                report.render(render_target, &mut buf, &alloc, &palette);
+
+                let report = problem_report(*home, src, region, buf);
 
                 match severity {
                     Warning => {
-                        warnings.push(buf);
+                        warnings.push(report);
                     }
                     RuntimeError => {
-                        errors.push(buf);
+                        errors.push(report);
                     }
                     Fatal => {
                         fatally_errored = true;
-                        errors.push(buf);
+                        errors.push(report);
                     }
                 }
             }
         }
+
+        // Within a module, report problems in the order they appear in the source, which also
+        // puts the problems of a def next to each other. The sort is stable, so problems without
+        // a region stay in the order they were found in.
+        warnings[module_warnings_start..].sort_by_key(|report| report.offset);
+        errors[module_errors_start..].sort_by_key(|report| report.offset);
     }
 
-    let problems_reported;
+    CollectedReports {
+        warnings,
+        errors,
+        fatally_errored,
+    }
+}
 
-    // Only print warnings if there are no errors
-    if errors.is_empty() {
-        problems_reported = warnings.len();
+/// Shows the code of the report in its header, so it can be looked up with `roc check --explain`
+fn with_error_code(mut report: Report<'_>) -> Report<'_> {
+    if let Some(error_code) = error_code::by_title(&report.title) {
+        report.title = format!("{} [{}]", report.title, error_code.code);
+    }
 
-        for warning in warnings.iter() {
-            println!("\n{}\n", warning);
-        }
-    } else {
-        problems_reported = errors.len();
+    report
+}
 
-        for error in errors.iter() {
-            println!("\n{}\n", error);
+fn problem_report(
+    home: ModuleId,
+    src: &str,
+    region: Option<Region>,
+    text: String,
+) -> ProblemReport {
+    match region {
+        Some(region) => {
+            let offset = region.start().offset;
+
+            ProblemReport {
+                home,
+                def: enclosing_def(src, offset).map(|name| name.to_string()),
+                offset,
+                text,
+            }
         }
+        None => ProblemReport {
+            home,
+            def: None,
+            // put problems without a region after the others of their module
+            offset: u32::MAX,
+            text,
+        },
     }
+}
 
-    // If we printed any problems, print a horizontal rule at the end,
-    // and then clear any ANSI escape codes (e.g. colors) we've used.
-    //
-    // The horizontal rule is nice when running the program right after
-    // compiling it, as it lets you clearly see where the compiler
-    // errors/warnings end and the program output begins.
-    if problems_reported > 0 {
-        println!("{}\u{001B}[0m\n", Report::horizontal_rule(&palette));
+/// Prints the reports, grouping consecutive reports that are in the same def under one header
+fn print_reports(reports: &[ProblemReport], palette: &Palette) {
+    let mut remaining = reports;
+
+    while let Some(first) = remaining.first() {
+        let group_len = match &first.def {
+            Some(def) => remaining
+                .iter()
+                .take_while(|report| report.home == first.home && report.def.as_ref() == Some(def))
+                .count(),
+            None => 1,
+        };
+
+        let (group, rest) = remaining.split_at(group_len);
+        remaining = rest;
+
+        if group.len() == 1 {
+            println!("\n{}\n", first.text);
+            continue;
+        }
+
+        let def = first.def.as_deref().unwrap_or_default();
+        let title = format!("{} PROBLEMS IN {}", group.len(), def);
+
+        println!(
+            "\n{}{}{}",
+            palette.header,
+            pretty_header(&title),
+            palette.reset
+        );
+
+        for report in group.iter().take(MAX_REPORTS_PER_DEF) {
+            println!("\n{}\n", report.text);
+        }
+
+        if group.len() > MAX_REPORTS_PER_DEF {
+            let more = group.len() - MAX_REPORTS_PER_DEF;
+
+            println!(
+                "\n... and {} more {} in {}{}{}\n",
+                more,
+                if more == 1 { "problem" } else { "problems" },
+                palette.variable,
+                def,
+                palette.reset,
+            );
+        }
     }
+}
 
-    Problems {
-        fatally_errored,
-        errors: errors.len(),
-        warnings: warnings.len(),
+/// The name of the top-level def that the given offset is in.
+///
+/// Top-level defs start at the beginning of a line, and everything that belongs to them is
+/// indented, so this looks for the closest line before the offset that is not indented.
+fn enclosing_def(src: &str, offset: u32) -> Option<&str> {
+    let offset = (offset as usize).min(src.len());
+
+    // include the line that the offset is on, the name of the def may be right at the offset
+    let end = match src[offset..].find('\n') {
+        Some(index) => offset + index,
+        None => src.len(),
+    };
+
+    let line = src[..end]
+        .lines()
+        .rev()
+        .find(|line| !line.is_empty() && !line.starts_with([' ', '\t', '#']))?;
+
+    let name_len = line.find(|c: char| !(c.is_alphanumeric() || c == '_'))?;
+    let (name, rest) = line.split_at(name_len);
+    let rest = rest.trim_start();
+
+    let is_def = (rest.starts_with('=') && !rest.starts_with("==")) || rest.starts_with(':');
+
+    if name.starts_with(|c: char| c.is_ascii_lowercase()) && is_def {
+        Some(name)
+    } else {
+        None
     }
 }
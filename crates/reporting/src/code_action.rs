@@ -0,0 +1,378 @@
+//! Machine-applicable fixes for problems, e.g. the quick fixes behind a lightbulb in an editor.
+//!
+//! Every [CodeAction] carries its [Fix], so a tool that edits an AST (like the Roc editor) can
+//! apply it structurally, and a list of [TextEdit]s on the original source for tools that only
+//! deal in text, like a language server.
+use roc_can::exhaustive::NONEXHAUSIVE_CTOR;
+use roc_exhaustive::{
+    Context as ExhaustiveContext, CtorName, Error as ExhaustiveError, ListArity,
+    Pattern as ExhaustivePattern, RenderAs,
+};
+use roc_module::ident::TagName;
+use roc_module::symbol::{Interns, Symbol};
+use roc_problem::can::{Problem, RuntimeError};
+use roc_region::all::{Position, Region};
+use roc_solve_problem::TypeError;
+use roc_std::RocDec;
+
+/// Body of generated `when` branches, so the fix typechecks before the user fills it in.
+pub const BRANCH_SKELETON_BODY: &str = "crash \"TODO\"";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeAction {
+    pub title: String,
+    pub fix: Fix,
+    pub edits: Vec<TextEdit>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fix {
+    /// `entry` is how the module is written in the `imports` list, e.g. `pf.Stdout`
+    AddImport {
+        module_name: String,
+        entry: String,
+    },
+    /// `patterns` are rendered as source, one branch is added for each of them
+    AddWhenBranches {
+        when_region: Region,
+        patterns: Vec<String>,
+    },
+    PrefixWithUnderscore {
+        symbol: Symbol,
+        region: Region,
+    },
+}
+
+/// Replace the source in `region` with `new_text`. An empty region inserts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    pub region: Region,
+    pub new_text: String,
+}
+
+impl TextEdit {
+    fn insert(offset: usize, new_text: String) -> Self {
+        let position = Position::new(offset as u32);
+
+        TextEdit {
+            region: Region::new(position, position),
+            new_text,
+        }
+    }
+}
+
+/// Apply non-overlapping edits to the source they were created for.
+pub fn apply_edits(src: &str, edits: &[TextEdit]) -> String {
+    let mut sorted_edits: Vec<&TextEdit> = edits.iter().collect();
+    sorted_edits.sort_by_key(|edit| edit.region.start().offset);
+
+    let mut new_src = String::with_capacity(src.len());
+    let mut copied_up_to = 0;
+
+    for edit in sorted_edits {
+        let start = edit.region.start().offset as usize;
+        let end = edit.region.end().offset as usize;
+
+        new_src.push_str(&src[copied_up_to..start]);
+        new_src.push_str(&edit.new_text);
+
+        copied_up_to = end;
+    }
+
+    new_src.push_str(&src[copied_up_to..]);
+
+    new_src
+}
+
+pub fn can_problem_code_actions(src: &str, problem: &Problem) -> Vec<CodeAction> {
+    match problem {
+        Problem::UnusedDef(symbol, region) | Problem::UnusedArgument(_, _, symbol, region) => {
+            prefix_with_underscore(src, *symbol, *region)
+                .into_iter()
+                .collect()
+        }
+        Problem::RuntimeError(RuntimeError::ModuleNotImported {
+            module_name,
+            module_exists: true,
+            ..
+        }) => add_import(src, module_name.as_str()).into_iter().collect(),
+        _ => Vec::new(),
+    }
+}
+
+pub fn type_problem_code_actions(
+    src: &str,
+    problem: &TypeError,
+    interns: &Interns,
+) -> Vec<CodeAction> {
+    match problem {
+        TypeError::Exhaustive(ExhaustiveError::Incomplete(
+            when_region,
+            ExhaustiveContext::BadCase,
+            missing,
+        )) => {
+            let patterns = missing
+                .iter()
+                .map(|pattern| pattern_to_source(pattern, interns))
+                .collect();
+
+            add_when_branches(src, *when_region, patterns)
+                .into_iter()
+                .collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Renders a pattern that the exhaustiveness checker found to be missing as Roc source,
+/// e.g. `Ok (Err _)` or `[_, .., _]`.
+pub fn pattern_to_source(pattern: &ExhaustivePattern, interns: &Interns) -> String {
+    pattern_to_source_help(pattern, interns, false)
+}
+
+fn pattern_to_source_help(
+    pattern: &ExhaustivePattern,
+    interns: &Interns,
+    in_type_param: bool,
+) -> String {
+    use roc_exhaustive::Literal::*;
+
+    match pattern {
+        ExhaustivePattern::Anything => "_".to_owned(),
+        ExhaustivePattern::Literal(literal) => match literal {
+            Int(bytes) => i128::from_ne_bytes(*bytes).to_string(),
+            U128(bytes) => u128::from_ne_bytes(*bytes).to_string(),
+            Bit(true) => "Bool.true".to_owned(),
+            Bit(false) => "Bool.false".to_owned(),
+            Byte(byte) => byte.to_string(),
+            Float(bits) => f64::from_bits(*bits).to_string(),
+            Decimal(bytes) => RocDec::from_ne_bytes(*bytes).to_string(),
+            Str(string) => str_to_source(string),
+        },
+        ExhaustivePattern::List(arity, patterns) => {
+            let mut elems: Vec<String> = patterns
+                .iter()
+                .map(|pattern| pattern_to_source_help(pattern, interns, false))
+                .collect();
+
+            if let ListArity::Slice(num_before, _) = arity {
+                elems.insert(*num_before, "..".to_owned());
+            }
+
+            format!("[{}]", elems.join(", "))
+        }
+        ExhaustivePattern::Ctor(union, tag_id, args) => match &union.render_as {
+            // #Guard <fake-condition-tag> <unexhausted-pattern>
+            RenderAs::Guard => pattern_to_source_help(&args[1], interns, in_type_param),
+            RenderAs::Record(field_names) => {
+                let fields: Vec<String> = field_names
+                    .iter()
+                    .zip(args.iter())
+                    .map(|(label, arg)| match arg {
+                        ExhaustivePattern::Anything => label.to_string(),
+                        _ => format!("{}: {}", label, pattern_to_source_help(arg, interns, false)),
+                    })
+                    .collect();
+
+                format!("{{ {} }}", fields.join(", "))
+            }
+            RenderAs::Tuple => {
+                let elems: Vec<String> = args
+                    .iter()
+                    .map(|arg| pattern_to_source_help(arg, interns, false))
+                    .collect();
+
+                format!("({})", elems.join(", "))
+            }
+            RenderAs::Tag | RenderAs::Opaque => {
+                let ctor = &union.alternatives[tag_id.0 as usize];
+
+                let ctor_name = match &ctor.name {
+                    CtorName::Tag(TagName(name)) if name.as_str() == NONEXHAUSIVE_CTOR => {
+                        return "_".to_owned();
+                    }
+                    CtorName::Tag(TagName(name)) => name.to_string(),
+                    CtorName::Opaque(opaque) => format!("@{}", opaque.as_str(interns)),
+                };
+
+                let mut parts = vec![ctor_name];
+
+                parts.extend(
+                    args.iter()
+                        .map(|arg| pattern_to_source_help(arg, interns, true)),
+                );
+
+                if in_type_param && !args.is_empty() {
+                    format!("({})", parts.join(" "))
+                } else {
+                    parts.join(" ")
+                }
+            }
+        },
+    }
+}
+
+fn str_to_source(string: &str) -> String {
+    let mut source = String::with_capacity(string.len() + 2);
+    source.push('"');
+
+    for ch in string.chars() {
+        match ch {
+            '"' => source.push_str("\\\""),
+            '\\' => source.push_str("\\\\"),
+            '\n' => source.push_str("\\n"),
+            '\r' => source.push_str("\\r"),
+            '\t' => source.push_str("\\t"),
+            _ => source.push(ch),
+        }
+    }
+
+    source.push('"');
+
+    source
+}
+
+fn prefix_with_underscore(src: &str, symbol: Symbol, region: Region) -> Option<CodeAction> {
+    let start = region.start().offset as usize;
+    let ident = src.get(start..region.end().offset as usize)?;
+
+    // only plain identifiers, not destructures like `{ x }`
+    let is_ident = ident.starts_with(|ch: char| ch.is_ascii_lowercase())
+        && ident.chars().all(|ch| ch.is_ascii_alphanumeric());
+
+    if !is_ident {
+        return None;
+    }
+
+    Some(CodeAction {
+        title: format!("Prefix `{}` with an underscore", ident),
+        fix: Fix::PrefixWithUnderscore { symbol, region },
+        edits: vec![TextEdit::insert(start, "_".to_owned())],
+    })
+}
+
+fn add_import(src: &str, module_name: &str) -> Option<CodeAction> {
+    let header_end = header_end(src);
+    let header = &src[..header_end];
+
+    // modules that are not local come from the (only) package, e.g. `pf.Stdout`
+    let entry = match package_shorthand(header) {
+        Some(shorthand) => format!("{}.{}", shorthand, module_name),
+        None => module_name.to_owned(),
+    };
+
+    let edit = match header.find("imports") {
+        Some(imports_start) => {
+            let open = imports_start + header[imports_start..].find('[')?;
+            let close = open + header[open..].find(']')?;
+
+            // keep the closing bracket on its own line if it was
+            let before_close = header[open + 1..close].trim_end();
+            let insert_at = open + 1 + before_close.len();
+
+            let new_text = if before_close.trim().is_empty() {
+                entry.clone()
+            } else if before_close.ends_with(',') {
+                format!(" {},", entry)
+            } else {
+                format!(", {}", entry)
+            };
+
+            TextEdit::insert(insert_at, new_text)
+        }
+        None => {
+            let provides_start = header.find("provides")?;
+            let line_start = header[..provides_start].rfind('\n').map_or(0, |i| i + 1);
+            let indent = &header[line_start..provides_start];
+
+            if indent.trim().is_empty() {
+                TextEdit::insert(line_start, format!("{}imports [{}]\n", indent, entry))
+            } else {
+                TextEdit::insert(provides_start, format!("imports [{}] ", entry))
+            }
+        }
+    };
+
+    Some(CodeAction {
+        title: format!("Import `{}`", entry),
+        fix: Fix::AddImport {
+            module_name: module_name.to_owned(),
+            entry,
+        },
+        edits: vec![edit],
+    })
+}
+
+// The header ends where the next unindented line (the first def) starts.
+fn header_end(src: &str) -> usize {
+    let mut offset = 0;
+    let mut in_header = false;
+
+    for line in src.split_inclusive('\n') {
+        let is_unindented = !line.trim().is_empty()
+            && !line.starts_with(char::is_whitespace)
+            && !line.starts_with('#');
+
+        if is_unindented {
+            if in_header {
+                return offset;
+            }
+
+            in_header = true;
+        }
+
+        offset += line.len();
+    }
+
+    src.len()
+}
+
+fn package_shorthand(header: &str) -> Option<&str> {
+    let packages_start = header.find("packages")?;
+    let after_brace = &header[packages_start..][header[packages_start..].find('{')? + 1..];
+    let shorthand = after_brace[..after_brace.find(':')?].trim();
+
+    if shorthand.is_empty() || after_brace[..after_brace.find('}')?].contains(',') {
+        None
+    } else {
+        Some(shorthand)
+    }
+}
+
+/// Adds a branch with a [BRANCH_SKELETON_BODY] for each pattern after the last branch of the `when`.
+pub fn add_when_branches(
+    src: &str,
+    when_region: Region,
+    patterns: Vec<String>,
+) -> Option<CodeAction> {
+    let when_start = when_region.start().offset as usize;
+    let when_end = when_region.end().offset as usize;
+    let when_src = src.get(when_start..when_end)?;
+
+    // branches are indented the same, so copy the indentation of the first one
+    let first_branch_line = when_src.lines().nth(1)?;
+    let indent: String = first_branch_line
+        .chars()
+        .take_while(|ch| ch.is_whitespace())
+        .collect();
+
+    let new_text: String = patterns
+        .iter()
+        .map(|pattern| format!("\n{}{} -> {}", indent, pattern, BRANCH_SKELETON_BODY))
+        .collect();
+
+    let title = if patterns.len() == 1 {
+        "Add the missing branch".to_owned()
+    } else {
+        format!("Add the {} missing branches", patterns.len())
+    };
+
+    Some(CodeAction {
+        title,
+        fix: Fix::AddWhenBranches {
+            when_region,
+            patterns,
+        },
+        edits: vec![TextEdit::insert(when_end, new_text)],
+    })
+}
@@ -3,8 +3,8 @@ use roc_module::ident::{Ident, Lowercase, ModuleName};
 use roc_module::symbol::DERIVABLE_ABILITIES;
 use roc_problem::can::PrecedenceProblem::BothNonAssociative;
 use roc_problem::can::{
-    BadPattern, CycleEntry, ExtensionTypeKind, FloatErrorKind, IntErrorKind, Problem, RuntimeError,
-    ShadowKind,
+    BadPattern, CycleEntry, ExtensionTypeKind, FloatErrorKind, IntErrorKind, LargeLiteralKind,
+    Problem, RuntimeError, ShadowKind, MAX_LIST_LITERAL_ELEMENTS, MAX_STR_LITERAL_BYTES,
 };
 use roc_problem::Severity;
 use roc_region::all::{LineColumn, LineColumnRegion, LineInfo, Loc, Region};
@@ -20,6 +20,7 @@ const NAMING_PROBLEM: &str = "NAMING PROBLEM";
 const UNRECOGNIZED_NAME: &str = "UNRECOGNIZED NAME";
 const UNUSED_DEF: &str = "UNUSED DEFINITION";
 const UNUSED_IMPORT: &str = "UNUSED IMPORT";
+const UNUSED_EXPOSED_VALUE: &str = "UNUSED EXPOSED VALUE";
 const UNUSED_ALIAS_PARAM: &str = "UNUSED TYPE ALIAS PARAMETER";
 const UNBOUND_TYPE_VARIABLE: &str = "UNBOUND TYPE VARIABLE";
 const UNUSED_ARG: &str = "UNUSED ARGUMENT";
@@ -28,6 +29,7 @@ const UNKNOWN_GENERATES_WITH: &str = "UNKNOWN GENERATES FUNCTION";
 const DUPLICATE_FIELD_NAME: &str = "DUPLICATE FIELD NAME";
 const DUPLICATE_TAG_NAME: &str = "DUPLICATE TAG NAME";
 const INVALID_UNICODE: &str = "INVALID UNICODE";
+const LITERAL_TOO_LARGE: &str = "LITERAL TOO LARGE";
 pub const CIRCULAR_DEF: &str = "CIRCULAR DEFINITION";
 const DUPLICATE_NAME: &str = "DUPLICATE NAME";
 const VALUE_NOT_EXPOSED: &str = "NOT EXPOSED";
@@ -104,6 +106,24 @@ pub fn can_problem<'b>(
 
             title = UNUSED_IMPORT.to_string();
         }
+        Problem::UnusedExposedValue(symbol, region) => {
+            doc = alloc.stack([
+                alloc.concat([
+                    alloc.symbol_unqualified(symbol),
+                    alloc.reflow(" is exposed, but no other module uses it."),
+                ]),
+                alloc.region(lines.convert_region(region)),
+                alloc.concat([
+                    alloc.reflow("If nothing outside of this module needs "),
+                    alloc.symbol_unqualified(symbol),
+                    alloc.reflow(", you can remove it from the "),
+                    alloc.keyword("exposes"),
+                    alloc.reflow(" list."),
+                ]),
+            ]);
+
+            title = UNUSED_EXPOSED_VALUE.to_string();
+        }
         Problem::UnusedModuleImport(module_id, region) => {
             doc = alloc.stack([
                 alloc.concat([
@@ -408,6 +428,29 @@ pub fn can_problem<'b>(
 
             title = DUPLICATE_FIELD_NAME.to_string();
         }
+        Problem::DuplicateRecordFieldPattern {
+            field_name,
+            record_region,
+            field_region,
+            first_region,
+        } => {
+            doc = alloc.stack([
+                alloc.concat([
+                    alloc.reflow("This record pattern destructures the "),
+                    alloc.record_field(field_name.clone()),
+                    alloc.reflow(" field twice!"),
+                ]),
+                alloc.region_all_the_things(
+                    lines.convert_region(record_region),
+                    lines.convert_region(first_region),
+                    lines.convert_region(field_region),
+                    Annotation::Error,
+                ),
+                alloc.reflow("In the rest of the program, I will only use the first one."),
+            ]);
+
+            title = DUPLICATE_FIELD_NAME.to_string();
+        }
         Problem::InvalidOptionalValue {
             field_name,
             field_region,
@@ -569,6 +612,11 @@ pub fn can_problem<'b>(
 
             title = SYNTAX_PROBLEM.to_string();
         }
+        Problem::LiteralTooLarge { region, kind, size } => {
+            doc = to_literal_too_large_doc(alloc, lines, kind, region, Some(size));
+
+            title = LITERAL_TOO_LARGE.to_string();
+        }
         Problem::RuntimeError(runtime_error) => {
             let answer = pretty_runtime_error(alloc, lines, runtime_error);
 
@@ -1108,6 +1156,40 @@ pub fn can_problem<'b>(
     }
 }
 
+fn to_literal_too_large_doc<'b>(
+    alloc: &'b RocDocAllocator<'b>,
+    lines: &LineInfo,
+    kind: LargeLiteralKind,
+    region: Region,
+    size: Option<usize>,
+) -> RocDocBuilder<'b> {
+    let (what, max, unit) = match kind {
+        LargeLiteralKind::Str => ("string", MAX_STR_LITERAL_BYTES, "bytes"),
+        LargeLiteralKind::List => ("list", MAX_LIST_LITERAL_ELEMENTS, "elements"),
+    };
+
+    let limit = match size {
+        Some(size) => format!(
+            "It has {} {}, but a {} literal can have at most {} {}.",
+            size, unit, what, max, unit
+        ),
+        None => format!("A {} literal can have at most {} {}.", what, max, unit),
+    };
+
+    alloc.stack([
+        alloc.concat([
+            alloc.reflow("This "),
+            alloc.text(what),
+            alloc.reflow(" literal is too large:"),
+        ]),
+        alloc.region(lines.convert_region(region)),
+        alloc.text(limit),
+        alloc.tip().append(alloc.reflow(
+            "Put this data in a file and read it when the program runs, instead of writing it in the source code.",
+        )),
+    ])
+}
+
 fn list_builtin_abilities<'a>(alloc: &'a RocDocAllocator<'a>) -> RocDocBuilder<'a> {
     alloc.intersperse(
         DERIVABLE_ABILITIES
@@ -1986,6 +2068,11 @@ fn pretty_runtime_error<'b>(
                 region
             );
         }
+        RuntimeError::LiteralTooLarge(kind, region) => {
+            doc = to_literal_too_large_doc(alloc, lines, kind, region, None);
+
+            title = LITERAL_TOO_LARGE;
+        }
         RuntimeError::InvalidInterpolation(region) => {
             todo!(
                 "TODO runtime error for an invalid string interpolation at region {:?}",
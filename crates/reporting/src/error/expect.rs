@@ -55,11 +55,6 @@ impl<'a> Renderer<'a> {
         expr: &Expr<'_>,
         error_type: ErrorType,
     ) -> RocDocBuilder<'a> {
-        use roc_fmt::annotation::Formattable;
-
-        let mut buf = roc_fmt::Buf::new_in(self.arena);
-        expr.format(&mut buf, 0);
-
         self.alloc.vcat([
             self.alloc
                 .symbol_unqualified(symbol)
@@ -68,7 +63,7 @@ impl<'a> Renderer<'a> {
             self.alloc
                 .symbol_unqualified(symbol)
                 .append(" = ")
-                .append(buf.into_bump_str()),
+                .append(roc_fmt::format_expr(self.arena, expr, 0)),
         ])
     }
 
@@ -192,13 +187,7 @@ impl<'a> Renderer<'a> {
 
         let expr = expressions[0];
 
-        let mut buf = roc_fmt::Buf::new_in(self.arena);
-        {
-            use roc_fmt::annotation::Formattable;
-            expr.format(&mut buf, 0);
-        }
-
-        writeln!(writer, "{}", buf.as_str())
+        writeln!(writer, "{}", roc_fmt::format_expr(self.arena, &expr, 0))
     }
 
     pub fn render_panic<W>(
@@ -248,6 +248,45 @@ pub fn type_problem<'b>(
                 severity,
             })
         }
+        DidNotConverge(defs) => {
+            let mut stack = vec![alloc.reflow(
+                "I gave up on type checking this module, because it was taking far too long.",
+            )];
+
+            if let Some(innermost) = defs.last() {
+                let mut names: Vec<Symbol> = Vec::with_capacity(defs.len());
+
+                for def in defs.iter() {
+                    if !names.contains(&def.value) {
+                        names.push(def.value);
+                    }
+                }
+
+                stack.push(alloc.reflow("I was still working on these definitions:"));
+                stack.push(
+                    alloc
+                        .stack(names.into_iter().map(|name| alloc.symbol_unqualified(name)))
+                        .indent(4),
+                );
+                stack.push(alloc.concat([
+                    alloc.reflow("The innermost one is "),
+                    alloc.symbol_unqualified(innermost.value),
+                    alloc.reflow(":"),
+                ]));
+                stack.push(alloc.region(lines.convert_region(innermost.region)));
+            }
+
+            stack.push(alloc.reflow(
+                "This usually comes from type aliases that refer to each other in deeply nested ways. Simplifying those aliases, or adding type annotations, may help.",
+            ));
+
+            Some(Report {
+                title: "TYPE CHECKING DID NOT CONVERGE".to_string(),
+                filename,
+                doc: alloc.stack(stack),
+                severity,
+            })
+        }
     }
 }
 
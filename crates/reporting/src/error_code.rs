@@ -0,0 +1,248 @@
+//! Stable codes for the kinds of problems we report, and a longer explanation of each one.
+//!
+//! The explanations are plain text, so that `roc check --explain`, the docs site and editor
+//! tooltips can all show the same thing. A code never changes meaning once it has been
+//! published: add new kinds at the end, and don't reuse the code of a kind that was removed.
+
+pub struct ErrorCode {
+    /// Like "E0001"
+    pub code: &'static str,
+    /// The title of the reports of this kind, like "TYPE MISMATCH"
+    pub title: &'static str,
+    pub explanation: &'static str,
+}
+
+pub const ERROR_CODES: &[ErrorCode] = &[
+    ErrorCode {
+        code: "E0001",
+        title: "TYPE MISMATCH",
+        explanation: r#"Two parts of the program disagree about the type of a value.
+
+For example, the body of this definition is a string, but its annotation
+says it should be a number:
+
+    answer : I64
+    answer = "forty-two"
+
+The report shows the two types that were compared. Either the annotation
+or the code that produces the value is wrong, fix whichever one does not
+match what you meant."#,
+    },
+    ErrorCode {
+        code: "E0002",
+        title: "UNRECOGNIZED NAME",
+        explanation: r#"A name is used, but nothing with that name is in scope.
+
+    main = greet "Ada"
+
+If `greet` is defined in another module, import it, or refer to it with
+its module name, like `Greeting.greet`. Names are also case sensitive, and
+a definition inside a function body can only be used in that body."#,
+    },
+    ErrorCode {
+        code: "E0003",
+        title: "MISSING DEFINITION",
+        explanation: r#"The module header exposes a value that the module does not define.
+
+    interface Greeting
+        exposes [greet]
+        imports []
+
+Either add a top-level definition of `greet` to the module, or remove it
+from `exposes`."#,
+    },
+    ErrorCode {
+        code: "E0004",
+        title: "UNUSED DEFINITION",
+        explanation: r#"A definition is never used.
+
+    area = \width, height ->
+        perimeter = 2 * (width + height)
+        width * height
+
+Here `perimeter` is computed but never used. Remove it, or use it. This is
+a warning: the program still runs."#,
+    },
+    ErrorCode {
+        code: "E0005",
+        title: "UNUSED IMPORT",
+        explanation: r#"A module is imported, but nothing from it is used.
+
+    imports [Json]
+
+Remove the import, or use a value or type from the module. This is a
+warning: the program still runs."#,
+    },
+    ErrorCode {
+        code: "E0006",
+        title: "UNUSED ARGUMENT",
+        explanation: r#"A function does not use one of its arguments.
+
+    first = \x, y -> x
+
+If the argument is unused on purpose, prefix its name with an underscore,
+like `_y`, or replace it with just `_`."#,
+    },
+    ErrorCode {
+        code: "E0007",
+        title: "DUPLICATE NAME",
+        explanation: r#"A name is defined twice in the same scope.
+
+    total = 1
+    total = 2
+
+Roc does not allow shadowing, even in nested scopes, because it makes it
+unclear which definition a use refers to. Give one of them another name."#,
+    },
+    ErrorCode {
+        code: "E0008",
+        title: "CIRCULAR DEFINITION",
+        explanation: r#"A value is defined in terms of itself, without a function in between.
+
+    x = y + 1
+    y = x + 1
+
+Only functions can refer to themselves, because their body is evaluated
+when they are called. Values are evaluated once, when they are defined, so
+the cycle would never end."#,
+    },
+    ErrorCode {
+        code: "E0009",
+        title: "CIRCULAR TYPE",
+        explanation: r#"A value would need a type that contains itself.
+
+    f = \x -> f
+
+The type of `f` would have to be `a -> (a -> (a -> ...))`, which never
+ends. This usually means a function is returned or passed where the result
+of calling it was meant."#,
+    },
+    ErrorCode {
+        code: "E0010",
+        title: "UNSAFE PATTERN",
+        explanation: r#"A `when` or a destructuring does not handle every possible value.
+
+    describe = \color ->
+        when color is
+            Red -> "warm"
+            Blue -> "cold"
+
+If `color` can also be `Green`, this `when` has no branch for it. Add the
+missing branches, or add a `_ ->` branch that handles everything else."#,
+    },
+    ErrorCode {
+        code: "E0011",
+        title: "REDUNDANT PATTERN",
+        explanation: r#"A branch of a `when` can never be reached.
+
+    when count is
+        _ -> "many"
+        0 -> "none"
+
+The first branch already matches every value, so the second one never
+runs. Remove it, or move it before the branch that covers it."#,
+    },
+    ErrorCode {
+        code: "E0012",
+        title: "TOO MANY ARGS",
+        explanation: r#"A function is called with more arguments than it takes.
+
+    add = \a, b -> a + b
+
+    main = add 1 2 3
+
+Remove the extra arguments. If a value that is not a function is called,
+there may be a missing operator or comma between it and what follows."#,
+    },
+    ErrorCode {
+        code: "E0013",
+        title: "TOO FEW ARGS",
+        explanation: r#"A function is called with fewer arguments than it takes.
+
+    add = \a, b -> a + b
+
+    main = add 1
+
+Roc functions are not curried, so every argument has to be passed at once.
+To create a function that adds one, write `\x -> add 1 x`."#,
+    },
+    ErrorCode {
+        code: "E0014",
+        title: "DUPLICATE FIELD NAME",
+        explanation: r#"A record has two fields with the same name.
+
+    { name: "Ada", name: "Grace" }
+
+Remove one of the fields, or give it another name."#,
+    },
+    ErrorCode {
+        code: "E0015",
+        title: "NOT EXPOSED",
+        explanation: r#"A value is used from another module, but that module does not expose it.
+
+    main = Greeting.helper "Ada"
+
+Add `helper` to the `exposes` list in the header of the Greeting module, or
+use one of the values it does expose."#,
+    },
+    ErrorCode {
+        code: "E0016",
+        title: "MODULE NOT IMPORTED",
+        explanation: r#"A value is used from a module that is not imported.
+
+    main = Greeting.greet "Ada"
+
+Add the module to the `imports` list of the module header:
+
+    imports [Greeting]"#,
+    },
+    ErrorCode {
+        code: "E0017",
+        title: "UNKNOWN GENERATES FUNCTION",
+        explanation: r#"A hosted module asks to generate a function that Roc does not know.
+
+    generates Effect with [after, map, always, foobar]
+
+Only specific functions, like `after`, `map` and `always`, can be
+generated. Remove the others from the list."#,
+    },
+    ErrorCode {
+        code: "E0018",
+        title: "NUMBER OVERFLOWS SUFFIX",
+        explanation: r#"A number literal is too big for the type its suffix asks for.
+
+    limit = 300u8
+
+A `U8` can hold at most 255. Use a smaller number, or a suffix for a wider
+type, like `300u16`."#,
+    },
+    ErrorCode {
+        code: "E0019",
+        title: "UNMATCHABLE PATTERN",
+        explanation: r#"A branch of a `when` matches a value that can never be created.
+
+    toI64 : Result I64 [] -> I64
+    toI64 = \result ->
+        when result is
+            Ok n -> n
+            Err _ -> 0
+
+The error type `[]` has no tags, so there is no value for `Err` to hold,
+and the second branch can't run. Remove it. This is a warning: the program
+still runs."#,
+    },
+];
+
+/// The code of the reports with this title
+pub fn by_title(title: &str) -> Option<&'static ErrorCode> {
+    ERROR_CODES
+        .iter()
+        .find(|error_code| error_code.title == title)
+}
+
+/// Looks up a code, like "E0001" or "e0001"
+pub fn by_code(code: &str) -> Option<&'static ErrorCode> {
+    ERROR_CODES
+        .iter()
+        .find(|error_code| error_code.code.eq_ignore_ascii_case(code))
+}
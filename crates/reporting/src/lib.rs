@@ -4,5 +4,7 @@
 #![allow(clippy::large_enum_variant)]
 
 pub mod cli;
+pub mod code_action;
 pub mod error;
+pub mod error_code;
 pub mod report;
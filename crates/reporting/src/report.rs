@@ -64,7 +64,11 @@ const HEADER_WIDTH: usize = 80;
 
 pub fn pretty_header(title: &str) -> String {
     let title_width = title.len() + 4;
-    let header = format!("── {} {}", title, "─".repeat(HEADER_WIDTH - title_width));
+    let header = format!(
+        "── {} {}",
+        title,
+        "─".repeat(HEADER_WIDTH.saturating_sub(title_width))
+    );
     header
 }
 
@@ -1079,6 +1083,21 @@ where
 }
 
 pub fn to_file_problem_report_string(filename: &Path, error: io::ErrorKind) -> String {
+    render_file_report(|alloc| to_file_problem_report(alloc, filename, error))
+}
+
+pub fn to_module_not_found_report_string(module_name: &str, filename: &Path) -> String {
+    render_file_report(|alloc| to_module_not_found_report(alloc, module_name, filename))
+}
+
+pub fn to_invalid_utf8_report_string(filename: &Path, byte_index: usize) -> String {
+    render_file_report(|alloc| to_invalid_utf8_report(alloc, filename, byte_index))
+}
+
+/// Reports about files have no source to show, so they don't need the modules or idents
+fn render_file_report(
+    make_report: impl for<'b> FnOnce(&'b RocDocAllocator<'b>) -> Report<'b>,
+) -> String {
     let src_lines: Vec<&str> = Vec::new();
 
     let mut module_ids = ModuleIds::default();
@@ -1092,18 +1111,73 @@ pub fn to_file_problem_report_string(filename: &Path, error: io::ErrorKind) -> S
 
     let mut buf = String::new();
     let palette = DEFAULT_PALETTE;
-    let report = to_file_problem_report(&alloc, filename, error);
+    let report = make_report(&alloc);
     report.render_color_terminal(&mut buf, &alloc, &palette);
 
     buf
 }
 
+pub fn to_module_not_found_report<'b>(
+    alloc: &'b RocDocAllocator<'b>,
+    module_name: &str,
+    filename: &Path,
+) -> Report<'b> {
+    let doc = alloc.stack([
+        alloc.concat([
+            alloc.reflow("I could not find the "),
+            alloc.module_name(module_name.into()),
+            alloc.reflow(" module. I looked for it here:"),
+        ]),
+        alloc
+            .string(filename.display().to_string())
+            .annotate(Annotation::ParserSuggestion)
+            .indent(4),
+        alloc.concat([
+            alloc.reflow("Is the file supposed to be there? "),
+            alloc.reflow("Maybe there is a typo in the module name, or in the imports?"),
+        ]),
+    ]);
+
+    Report {
+        filename: filename.to_path_buf(),
+        doc,
+        title: "MODULE NOT FOUND".to_string(),
+        severity: Severity::Fatal,
+    }
+}
+
+pub fn to_invalid_utf8_report<'b>(
+    alloc: &'b RocDocAllocator<'b>,
+    filename: &Path,
+    byte_index: usize,
+) -> Report<'b> {
+    let doc = alloc.stack([
+        alloc.reflow("Roc files must be valid UTF-8, but this one is not:"),
+        alloc
+            .string(filename.display().to_string())
+            .annotate(Annotation::Error)
+            .indent(4),
+        alloc.concat([
+            alloc.reflow("The first invalid byte is at offset "),
+            alloc.string(byte_index.to_string()),
+            alloc.reflow(". Was the file saved with a different encoding?"),
+        ]),
+    ]);
+
+    Report {
+        filename: filename.to_path_buf(),
+        doc,
+        title: "INVALID UTF-8".to_string(),
+        severity: Severity::Fatal,
+    }
+}
+
 pub fn to_file_problem_report<'b>(
     alloc: &'b RocDocAllocator<'b>,
     filename: &Path,
     error: io::ErrorKind,
 ) -> Report<'b> {
-    let filename: String = filename.to_str().unwrap().to_string();
+    let filename: String = filename.display().to_string();
     match error {
         io::ErrorKind::NotFound => {
             let doc = alloc.stack([
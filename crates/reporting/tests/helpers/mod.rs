@@ -15,7 +15,7 @@ use roc_module::symbol::{IdentIds, Interns, ModuleId, ModuleIds};
 use roc_parse::parser::{SourceError, SyntaxError};
 use roc_problem::can::Problem;
 use roc_region::all::Loc;
-use roc_solve::solve::{self, Aliases};
+use roc_solve::solve::{self, Aliases, SolveFuel};
 use roc_solve_problem::TypeError;
 use roc_types::subs::{Content, Subs, VarStore, Variable};
 use roc_types::types::Types;
@@ -39,6 +39,7 @@ pub fn infer_expr(
     abilities_store: &mut AbilitiesStore,
     derived_module: SharedDerivedModule,
     expr_var: Variable,
+    fuel: SolveFuel,
 ) -> (Content, Subs) {
     let (solved, _) = solve::run(
         ModuleId::ATTR,
@@ -52,7 +53,10 @@ pub fn infer_expr(
         abilities_store,
         &Default::default(),
         derived_module,
-    );
+        &Default::default(),
+        fuel,
+    )
+    .unwrap();
 
     let content = *solved.inner().get_content_without_compacting(expr_var);
 
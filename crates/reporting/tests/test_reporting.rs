@@ -13,7 +13,10 @@ mod test_reporting {
     use indoc::indoc;
     use roc_can::abilities::AbilitiesStore;
     use roc_can::expr::PendingDerives;
-    use roc_load::{self, ExecutionMode, LoadConfig, LoadedModule, LoadingProblem, Threading};
+    use roc_load::{
+        self, CancelToken, ExecutionMode, HotModule, LoadConfig, LoadedModule, LoadingProblem,
+        ProgressReporter, Threading,
+    };
     use roc_module::symbol::{Interns, ModuleId};
     use roc_packaging::cache::RocCacheDir;
     use roc_parse::module::parse_header;
@@ -21,11 +24,15 @@ mod test_reporting {
     use roc_parse::test_helpers::parse_expr_with;
     use roc_problem::Severity;
     use roc_region::all::LineInfo;
+    use roc_reporting::code_action::{
+        apply_edits, can_problem_code_actions, type_problem_code_actions,
+    };
     use roc_reporting::report::{
         can_problem, parse_problem, type_problem, RenderTarget, Report, ANSI_STYLE_CODES,
         DEFAULT_PALETTE,
     };
     use roc_reporting::report::{RocDocAllocator, RocDocBuilder};
+    use roc_solve::solve::SolveFuel;
     use roc_solve_problem::TypeError;
     use roc_types::subs::Subs;
     use std::path::PathBuf;
@@ -127,6 +134,10 @@ mod test_reporting {
                 palette: DEFAULT_PALETTE,
                 threading: Threading::Single,
                 exec_mode: ExecutionMode::Check,
+                cancel: CancelToken::default(),
+                progress: ProgressReporter::default(),
+                hot_module: HotModule::default(),
+                solve_fuel: SolveFuel::default(),
             };
             let result = roc_load::load_and_typecheck(
                 arena,
@@ -234,6 +245,22 @@ mod test_reporting {
             Interns,
         ),
         ParseErrOut<'a>,
+    > {
+        infer_expr_help_with_fuel(arena, expr_src, SolveFuel::default())
+    }
+
+    fn infer_expr_help_with_fuel<'a>(
+        arena: &'a Bump,
+        expr_src: &'a str,
+        fuel: SolveFuel,
+    ) -> Result<
+        (
+            Vec<TypeError>,
+            Vec<roc_problem::can::Problem>,
+            ModuleId,
+            Interns,
+        ),
+        ParseErrOut<'a>,
     > {
         let CanExprOut {
             loc_expr: _,
@@ -279,6 +306,7 @@ mod test_reporting {
             &mut abilities_store,
             Default::default(),
             var,
+            fuel,
         );
 
         Ok((unify_problems, can_problems, home, interns))
@@ -789,6 +817,64 @@ mod test_reporting {
         assert_eq!(human_readable(&buf), "<green>Util.Int<reset>");
     }
 
+    #[test]
+    fn type_checking_did_not_converge() {
+        let src: &str = indoc!(
+            r#"
+                identity = \x -> x
+
+                identity (identity 1)
+            "#
+        );
+
+        let arena = Bump::new();
+        let (type_problems, _can_problems, home, interns) =
+            infer_expr_help_with_fuel(&arena, src, SolveFuel(8)).expect("parse error");
+
+        let mut buf = String::new();
+        let src_lines: Vec<&str> = src.split('\n').collect();
+        let lines = LineInfo::new(src);
+        let filename = filename_from_string(r"/code/proj/Main.roc");
+
+        let alloc = RocDocAllocator::new(&src_lines, home, &interns);
+
+        let problem = type_problems
+            .into_iter()
+            .find(|problem| matches!(problem, TypeError::DidNotConverge(_)))
+            .expect("the solver should have run out of fuel");
+
+        type_problem(&alloc, &lines, filename, problem)
+            .unwrap()
+            .pretty(&alloc)
+            .1
+            .render_raw(70, &mut roc_reporting::report::CiWrite::new(&mut buf))
+            .unwrap();
+
+        assert_eq!(
+            buf,
+            indoc!(
+                r#"
+                ── TYPE CHECKING DID NOT CONVERGE ──────────────────────── /code/proj/Main.roc ─
+
+                I gave up on type checking this module, because it was taking far too
+                long.
+
+                I was still working on these definitions:
+
+                    `identity`
+
+                The innermost one is `identity`:
+
+                1│  identity = \x -> x
+                    ^^^^^^^^
+
+                This usually comes from type aliases that refer to each other in
+                deeply nested ways. Simplifying those aliases, or adding type
+                annotations, may help."#
+            )
+        );
+    }
+
     #[test]
     fn report_region_in_color() {
         color_report_problem_as(
@@ -2678,6 +2764,32 @@ mod test_reporting {
     "###
     );
 
+    test_report!(
+        patterns_str_not_exhaustive,
+        indoc!(
+            r#"
+            when "yes" is
+                "yes" -> 1
+                "no" -> 0
+            "#
+        ),
+        @r###"
+    ── UNSAFE PATTERN ──────────────────────────────────────── /code/proj/Main.roc ─
+
+    This `when` does not cover all the possibilities:
+
+    4│>      when "yes" is
+    5│>          "yes" -> 1
+    6│>          "no" -> 0
+
+    Other possibilities include:
+
+        _
+
+    I would have to crash if I saw one of those! Add branches for them!
+    "###
+    );
+
     test_report!(
         patterns_bool_not_exhaustive,
         indoc!(
@@ -3135,6 +3247,27 @@ mod test_reporting {
     "###
     );
 
+    test_report!(
+        record_pattern_duplicate_field,
+        indoc!(
+            r#"
+            f = \{ x, y, x } -> x + y
+
+            f { x: 1, y: 2 }
+            "#
+        ),
+        @r###"
+    ── DUPLICATE FIELD NAME ────────────────────────────────── /code/proj/Main.roc ─
+
+    This record pattern destructures the `.x` field twice!
+
+    4│      f = \{ x, y, x } -> x + y
+                   ^     ^
+
+    In the rest of the program, I will only use the first one.
+    "###
+    );
+
     test_report!(
         annotation_definition_mismatch,
         indoc!(
@@ -5375,6 +5508,49 @@ Tab characters are not allowed."###,
     "###
     );
 
+    #[test]
+    fn list_literal_too_large() {
+        use roc_problem::can::{LargeLiteralKind, Problem, MAX_LIST_LITERAL_ELEMENTS};
+        use roc_region::all::{Position, Region};
+
+        // a literal over the cap does not fit in a snapshot, so the problem is made by hand
+        let src = "main = [1, 2, 3]";
+        let src_lines: Vec<&str> = src.split('\n').collect();
+        let lines = LineInfo::new(src);
+        let interns = Interns::default();
+        let alloc = RocDocAllocator::new(&src_lines, test_home(), &interns);
+
+        let problem = Problem::LiteralTooLarge {
+            region: Region::new(Position::new(7), Position::new(16)),
+            kind: LargeLiteralKind::List,
+            size: MAX_LIST_LITERAL_ELEMENTS + 1,
+        };
+
+        let filename = filename_from_string(r"/code/proj/Main.roc");
+        let report = can_problem(&alloc, &lines, filename, problem);
+
+        let mut buf = String::new();
+        report
+            .pretty(&alloc)
+            .1
+            .render_raw(70, &mut roc_reporting::report::CiWrite::new(&mut buf))
+            .expect("list_literal_too_large");
+
+        insta::assert_snapshot!(buf, @r###"
+        ── LITERAL TOO LARGE ───────────────────────────────────── /code/proj/Main.roc ─
+
+        This list literal is too large:
+
+        1│  main = [1, 2, 3]
+                   ^^^^^^^^^
+
+        It has 1048577 elements, but a list literal can have at most 1048576 elements.
+
+        Tip: Put this data in a file and read it when the program runs,
+        instead of writing it in the source code.
+        "###);
+    }
+
     test_report!(
         weird_escape,
         r#""abc\qdef""#,
@@ -9678,7 +9854,7 @@ In roc, functions are always written as a lambda, like{}
 
     Only builtin abilities can be derived.
 
-    Note: The builtin abilities are `Encoding`, `Decoding`, `Hash`, `Eq`
+    Note: The builtin abilities are `Encoding`, `Decoding`, `Hash`, `Eq`, `Inspect`
     "###
     );
 
@@ -13410,4 +13586,113 @@ I recommend using camelCase. It's the standard style in Roc code!
     meant to unwrap it first?
     "###
     );
+
+    fn apply_code_actions(subdir: &str, src: &str) -> String {
+        let arena = Bump::new();
+        let (module_src, type_problems, can_problems, _home, interns) =
+            infer_expr_help_new(subdir, &arena, src).expect("failed to load");
+
+        let mut edits = Vec::new();
+
+        for problem in can_problems.iter() {
+            for action in can_problem_code_actions(&module_src, problem) {
+                edits.extend(action.edits);
+            }
+        }
+
+        for problem in type_problems.iter() {
+            for action in type_problem_code_actions(&module_src, problem, &interns) {
+                edits.extend(action.edits);
+            }
+        }
+
+        apply_edits(&module_src, &edits)
+    }
+
+    #[test]
+    fn code_action_prefix_unused_def() {
+        assert_eq!(
+            apply_code_actions(
+                "code_action_prefix_unused_def",
+                indoc!(
+                    r#"
+                    x = 1
+                    y = 2
+
+                    x
+                    "#
+                )
+            ),
+            promote_expr_to_module(indoc!(
+                r#"
+                x = 1
+                _y = 2
+
+                x
+                "#
+            ))
+        );
+    }
+
+    #[test]
+    fn code_action_add_missing_when_branch() {
+        assert_eq!(
+            apply_code_actions(
+                "code_action_add_missing_when_branch",
+                indoc!(
+                    r#"
+                    x : [Red, Green, Blue]
+                    x = Red
+
+                    when x is
+                        Red -> 1
+                        Green -> 2
+                    "#
+                )
+            ),
+            promote_expr_to_module(indoc!(
+                r#"
+                x : [Red, Green, Blue]
+                x = Red
+
+                when x is
+                    Red -> 1
+                    Green -> 2
+                    Blue -> crash "TODO"
+                "#
+            ))
+        );
+    }
+
+    #[test]
+    fn code_action_add_missing_nested_when_branches() {
+        assert_eq!(
+            apply_code_actions(
+                "code_action_add_missing_nested_when_branches",
+                indoc!(
+                    r#"
+                    x : Result [A, B, C] Str
+                    x = Ok A
+
+                    when x is
+                        Ok A -> 1
+                        Err "" -> 2
+                    "#
+                )
+            ),
+            promote_expr_to_module(indoc!(
+                r#"
+                x : Result [A, B, C] Str
+                x = Ok A
+
+                when x is
+                    Ok A -> 1
+                    Err "" -> 2
+                    Err _ -> crash "TODO"
+                    Ok B -> crash "TODO"
+                    Ok C -> crash "TODO"
+                "#
+            ))
+        );
+    }
 }